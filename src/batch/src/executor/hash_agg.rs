@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::mem::size_of;
 use std::sync::Arc;
 use std::vec;
 
@@ -21,6 +22,7 @@ use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::DataChunk;
+use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::hash::{
@@ -34,11 +36,15 @@ use risingwave_pb::batch_plan::HashAggNode;
 
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    SpillFileExecutor, SpillOptions, SpillPartitions, MAX_SPILL_DEPTH,
 };
 use crate::task::{BatchTaskContext, TaskId};
 
 type AggHashMap<K> = HashMap<K, Vec<BoxedAggState>, PrecomputedBuildHasher>;
 
+/// Rough estimation of the size of an aggregation state in bytes.
+const ESTIMATED_AGG_STATE_SIZE: usize = 64;
+
 struct HashAggExecutorBuilderDispatcher;
 
 /// A dispatcher to help create specialized hash agg executor.
@@ -59,6 +65,7 @@ pub struct HashAggExecutorBuilder {
     schema: Schema,
     task_id: TaskId,
     identity: String,
+    spill_options: SpillOptions,
 }
 
 impl HashAggExecutorBuilder {
@@ -67,6 +74,7 @@ impl HashAggExecutorBuilder {
         child: BoxedExecutor,
         task_id: TaskId,
         identity: String,
+        spill_options: SpillOptions,
     ) -> Result<BoxedExecutor> {
         let group_key_columns = hash_agg_node
             .get_group_keys()
@@ -104,6 +112,7 @@ impl HashAggExecutorBuilder {
             schema: Schema { fields },
             task_id,
             identity,
+            spill_options,
         };
 
         Ok(HashAggExecutorBuilderDispatcher::dispatch_by_kind(
@@ -129,11 +138,22 @@ impl BoxedExecutorBuilder for HashAggExecutorBuilder {
         )?;
 
         let identity = source.plan_node().get_identity().clone();
-        Self::deserialize(hash_agg_node, child, source.task_id.clone(), identity)
+        let spill_options = SpillOptions::from_config(&source.batch_task_context().config());
+        Self::deserialize(
+            hash_agg_node,
+            child,
+            source.task_id.clone(),
+            identity,
+            spill_options,
+        )
     }
 }
 
 /// `HashAggExecutor` implements the hash aggregate algorithm.
+///
+/// Once the groups exceed the memory budget, the rows of new groups are spilled to the disk,
+/// partitioned by the hash of group keys. Each partition is aggregated by another
+/// `HashAggExecutor` after the groups in memory are emitted.
 pub(crate) struct HashAggExecutor<K> {
     /// factories to construct aggregator for each groups
     agg_factories: Vec<AggStateFactory>,
@@ -145,6 +165,9 @@ pub(crate) struct HashAggExecutor<K> {
     group_key_types: Vec<DataType>,
     schema: Schema,
     identity: String,
+    spill_options: SpillOptions,
+    /// Depth of recursive partitioning, 0 if the input is not spilled.
+    spill_depth: usize,
     _phantom: PhantomData<K>,
 }
 
@@ -157,6 +180,8 @@ impl<K> HashAggExecutor<K> {
             group_key_types: builder.group_key_types,
             schema: builder.schema,
             identity: builder.identity,
+            spill_options: builder.spill_options,
+            spill_depth: 0,
             _phantom: PhantomData,
         }
     }
@@ -181,13 +206,41 @@ impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
     async fn do_execute(self: Box<Self>) {
         // hash map for each agg groups
         let mut groups = AggHashMap::<K>::default();
+        let group_size = size_of::<K>()
+            + size_of::<Vec<BoxedAggState>>()
+            + self.agg_factories.len() * ESTIMATED_AGG_STATE_SIZE;
+        // partitions of the spilled rows, created once the groups exceed the memory budget
+        let mut partitions: Option<SpillPartitions> = None;
+        let child_schema = self.child.schema().clone();
 
         // consume all chunks to compute the agg result
         #[for_await]
         for chunk in self.child.execute() {
             let chunk = chunk?.compact()?;
             let keys = K::build(self.group_key_columns.as_slice(), &chunk)?;
+            let mut spilled = vec![false; chunk.cardinality()];
             for (row_id, key) in keys.into_iter().enumerate() {
+                if !groups.contains_key(&key) {
+                    if partitions.is_none()
+                        && self.spill_depth < MAX_SPILL_DEPTH
+                        && groups.len() * group_size > self.spill_options.memory_budget
+                    {
+                        partitions = Some(
+                            SpillPartitions::create(
+                                &self.spill_options,
+                                self.group_key_columns.clone(),
+                                self.spill_depth,
+                            )
+                            .await?,
+                        );
+                    }
+                    // rows of the new groups are spilled
+                    if partitions.is_some() {
+                        spilled[row_id] = true;
+                        continue;
+                    }
+                }
+
                 let mut err_flag = Ok(());
                 let states: &mut Vec<BoxedAggState> = groups.entry(key).or_insert_with(|| {
                     self.agg_factories
@@ -206,6 +259,11 @@ impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
                     .iter_mut()
                     .for_each(|state| state.update_with_row(&chunk, row_id).unwrap());
             }
+            if let Some(partitions) = &mut partitions && spilled.iter().any(|s| *s) {
+                partitions
+                    .write_chunk(chunk.with_visibility(Bitmap::try_from(spilled)?))
+                    .await?;
+            }
         }
 
         // generate output data chunks
@@ -250,13 +308,42 @@ impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
             let output = DataChunk::builder().columns(columns).build();
             yield output;
         }
+
+        // aggregate each partition of the spilled rows
+        for file in partitions.into_iter().flat_map(SpillPartitions::into_files) {
+            if file.is_empty() {
+                continue;
+            }
+            let executor = HashAggExecutor::<K> {
+                agg_factories: self.agg_factories.clone(),
+                group_key_columns: self.group_key_columns.clone(),
+                child: Box::new(SpillFileExecutor::new(
+                    file,
+                    child_schema.clone(),
+                    self.identity.clone(),
+                )),
+                group_key_types: self.group_key_types.clone(),
+                schema: self.schema.clone(),
+                identity: self.identity.clone(),
+                spill_options: self.spill_options.clone(),
+                spill_depth: self.spill_depth + 1,
+                _phantom: PhantomData,
+            };
+            #[for_await]
+            for chunk in Box::new(executor).execute() {
+                yield chunk?;
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures_async_stream::for_await;
+    use risingwave_common::array::Row;
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::ScalarImpl;
     use risingwave_pb::data::data_type::TypeName;
     use risingwave_pb::data::DataType as ProstDataType;
     use risingwave_pb::expr::agg_call::{Arg, Type};
@@ -317,6 +404,7 @@ mod tests {
             Box::new(src_exec),
             TaskId::default(),
             "HashAggExecutor".to_string(),
+            SpillOptions::no_spill(),
         )
         .unwrap();
 
@@ -342,6 +430,80 @@ mod tests {
         diff_executor_output(actual_exec, Box::new(expect_exec)).await;
     }
 
+    #[tokio::test]
+    async fn execute_spilled() {
+        let t32 = DataType::Int32;
+        let mut src_exec = MockExecutor::new(Schema {
+            fields: vec![Field::unnamed(t32.clone()), Field::unnamed(t32.clone())],
+        });
+        src_exec.add(DataChunk::from_pretty(
+            "i i
+             0 1
+             1 1
+             2 1
+             3 1",
+        ));
+        src_exec.add(DataChunk::from_pretty(
+            "i i
+             4 1
+             3 2
+             2 3
+             0 4",
+        ));
+
+        let agg_call = AggCall {
+            r#type: Type::Sum as i32,
+            args: vec![Arg {
+                input: Some(InputRefExpr { column_idx: 1 }),
+                r#type: Some(ProstDataType {
+                    type_name: TypeName::Int32 as i32,
+                    ..Default::default()
+                }),
+            }],
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Int64 as i32,
+                ..Default::default()
+            }),
+            distinct: false,
+        };
+        let agg_prost = HashAggNode {
+            group_keys: vec![0],
+            agg_calls: vec![agg_call],
+        };
+        // Only one group is kept in memory at each depth, and the others are spilled.
+        let spill_options = SpillOptions {
+            memory_budget: 0,
+            directory: tempfile::tempdir().unwrap().into_path(),
+        };
+
+        let actual_exec = HashAggExecutorBuilder::deserialize(
+            &agg_prost,
+            Box::new(src_exec),
+            TaskId::default(),
+            "HashAggExecutor".to_string(),
+            spill_options,
+        )
+        .unwrap();
+
+        let mut rows = vec![];
+        #[for_await]
+        for chunk in actual_exec.execute() {
+            let chunk = chunk.unwrap();
+            rows.extend(chunk.rows().map(|row| row.to_owned_row()));
+        }
+        rows.sort();
+        let expected = [(0, 5), (1, 1), (2, 4), (3, 3), (4, 1)]
+            .into_iter()
+            .map(|(key, sum)| {
+                Row(vec![
+                    Some(ScalarImpl::Int32(key)),
+                    Some(ScalarImpl::Int64(sum)),
+                ])
+            })
+            .collect_vec();
+        assert_eq!(rows, expected);
+    }
+
     #[tokio::test]
     async fn execute_count_star() {
         let t32 = DataType::Int32;
@@ -382,6 +544,7 @@ mod tests {
             Box::new(src_exec),
             TaskId::default(),
             "HashAggExecutor".to_string(),
+            SpillOptions::no_spill(),
         )
         .unwrap();
         let schema = Schema {
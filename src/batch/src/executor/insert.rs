@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::iter::once;
+use std::sync::Arc;
 
 use futures::future::try_join_all;
 use futures_async_stream::try_stream;
@@ -20,7 +20,7 @@ use risingwave_common::array::column::Column;
 use risingwave_common::array::{
     ArrayBuilder, DataChunk, I64ArrayBuilder, Op, PrimitiveArrayBuilder, StreamChunk,
 };
-use risingwave_common::catalog::{Field, Schema, TableId};
+use risingwave_common::catalog::{ColumnId, Field, Schema, TableId};
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::types::DataType;
@@ -37,6 +37,8 @@ pub struct InsertExecutor {
     /// Target table id.
     table_id: TableId,
     source_manager: SourceManagerRef,
+    /// Ids of the table columns that the child outputs, in order.
+    column_ids: Vec<ColumnId>,
 
     child: BoxedExecutor,
     schema: Schema,
@@ -44,10 +46,16 @@ pub struct InsertExecutor {
 }
 
 impl InsertExecutor {
-    pub fn new(table_id: TableId, source_manager: SourceManagerRef, child: BoxedExecutor) -> Self {
+    pub fn new(
+        table_id: TableId,
+        source_manager: SourceManagerRef,
+        column_ids: Vec<ColumnId>,
+        child: BoxedExecutor,
+    ) -> Self {
         Self {
             table_id,
             source_manager,
+            column_ids,
             child,
             schema: Schema {
                 fields: vec![Field::unnamed(DataType::Int64)],
@@ -91,12 +99,35 @@ impl InsertExecutor {
                 builder.append(Some(source_desc.next_row_id())).unwrap();
             }
 
-            let rowid_column = once(Column::from(builder.finish().unwrap()));
-            let child_columns = data_chunk.into_parts().0.into_iter();
+            let mut rowid_column = Some(Column::from(builder.finish().unwrap()));
+            let child_columns = data_chunk.into_parts().0;
 
-            // Materialize plan is assembled manually with Rust frontend, so we put the row
-            // id column to the first.
-            let columns = rowid_column.chain(child_columns).collect();
+            // Put the columns of the child to the positions of their ids in the table. The
+            // columns not inserted into, e.g. the dropped ones, are filled with `NULL`s.
+            let columns = source_desc
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(idx, column_desc)| {
+                    if idx == source_desc.row_id_index {
+                        return Ok(rowid_column.take().unwrap());
+                    }
+                    match self
+                        .column_ids
+                        .iter()
+                        .position(|column_id| *column_id == column_desc.column_id)
+                    {
+                        Some(pos) => Ok(child_columns[pos].clone()),
+                        None => {
+                            let mut builder = column_desc.data_type.create_array_builder(len)?;
+                            for _ in 0..len {
+                                builder.append_null()?;
+                            }
+                            Ok(Column::new(Arc::new(builder.finish()?)))
+                        }
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
             let chunk = StreamChunk::new(vec![Op::Insert; len], columns, None);
 
             let notifier = source.write_chunk(chunk)?;
@@ -138,6 +169,11 @@ impl BoxedExecutorBuilder for InsertExecutor {
         )?;
 
         let table_id = TableId::from(&insert_node.table_source_ref_id);
+        let column_ids = insert_node
+            .column_ids
+            .iter()
+            .map(|&column_id| ColumnId::new(column_id))
+            .collect();
 
         let proto_child = source.plan_node.get_children().get(0).ok_or_else(|| {
             RwError::from(ErrorCode::InternalError(String::from(
@@ -152,6 +188,7 @@ impl BoxedExecutorBuilder for InsertExecutor {
                 .batch_task_context()
                 .source_manager_ref()
                 .ok_or_else(|| InternalError("Source manager not found".to_string()))?,
+            column_ids,
             child,
         )))
     }
@@ -203,6 +240,7 @@ mod tests {
                 name: f.name.clone(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             })
             .collect();
 
@@ -238,6 +276,7 @@ mod tests {
         let insert_executor = Box::new(InsertExecutor::new(
             table_id,
             source_manager.clone(),
+            vec![1.into(), 2.into(), 3.into()],
             Box::new(mock_executor),
         ));
         let handle = tokio::spawn(async move {
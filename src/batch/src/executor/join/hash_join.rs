@@ -17,6 +17,7 @@ use std::marker::PhantomData;
 
 use futures::StreamExt;
 use futures_async_stream::try_stream;
+use itertools::Itertools;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{Result, RwError};
@@ -30,6 +31,7 @@ use crate::executor::join::hash_join_state::{BuildTable, ProbeTable};
 use crate::executor::join::JoinType;
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    SpillOptions, SpillPartitions, MAX_SPILL_DEPTH,
 };
 use crate::task::{BatchTaskContext, TaskId};
 
@@ -64,6 +66,10 @@ pub(super) struct EquiJoinParams {
     pub cond: Option<BoxedExpression>,
 }
 
+/// `HashJoinExecutor` builds a hash table with the right side and probes it with the left side.
+///
+/// Once the build side exceeds the memory budget, both sides are spilled to the disk, partitioned
+/// by the hash of join keys, and each pair of partitions is joined separately.
 pub(super) struct HashJoinExecutor<K> {
     /// Probe side
     left_child: Option<BoxedExecutor>,
@@ -72,6 +78,7 @@ pub(super) struct HashJoinExecutor<K> {
     params: EquiJoinParams,
     schema: Schema,
     identity: String,
+    spill_options: SpillOptions,
     _phantom: PhantomData<K>,
 }
 
@@ -134,17 +141,81 @@ impl<K: HashKey + Send + Sync> Executor for HashJoinExecutor<K> {
 impl<K: HashKey + Send + Sync> HashJoinExecutor<K> {
     #[try_stream(boxed, ok = DataChunk, error = RwError)]
     async fn do_execute(mut self: Box<Self>) {
-        let mut right_child_stream = self.right_child.take().unwrap().execute();
-        let mut build_table = BuildTable::with_params(self.params);
+        let mut params = self.params;
+        // Pairs of the probe and build inputs to join, with their depth of partitioning.
+        let mut inputs = vec![(
+            self.left_child.take().unwrap().execute(),
+            self.right_child.take().unwrap().execute(),
+            0,
+        )];
+
+        while let Some((mut left_child_stream, mut right_child_stream, depth)) = inputs.pop() {
+            let mut build_chunks = vec![];
+            let mut build_size = 0;
+            let mut partitions: Option<(SpillPartitions, SpillPartitions)> = None;
+            while let Some(chunk) = right_child_stream.next().await {
+                let chunk = chunk?;
+                if let Some((_, right_partitions)) = &mut partitions {
+                    right_partitions.write_chunk(chunk).await?;
+                    continue;
+                }
+                build_size += chunk.estimated_size();
+                build_chunks.push(chunk);
+                if depth < MAX_SPILL_DEPTH && build_size > self.spill_options.memory_budget {
+                    let left_partitions = SpillPartitions::create(
+                        &self.spill_options,
+                        params.left_key_columns.clone(),
+                        depth,
+                    )
+                    .await?;
+                    let mut right_partitions = SpillPartitions::create(
+                        &self.spill_options,
+                        params.right_key_columns.clone(),
+                        depth,
+                    )
+                    .await?;
+                    for chunk in build_chunks.drain(..) {
+                        right_partitions.write_chunk(chunk).await?;
+                    }
+                    partitions = Some((left_partitions, right_partitions));
+                }
+            }
+
+            if let Some((mut left_partitions, right_partitions)) = partitions {
+                while let Some(chunk) = left_child_stream.next().await {
+                    left_partitions.write_chunk(chunk?).await?;
+                }
+                for (left, right) in left_partitions
+                    .into_files()
+                    .into_iter()
+                    .zip_eq(right_partitions.into_files())
+                {
+                    if !left.is_empty() || !right.is_empty() {
+                        inputs.push((left.into_stream(), right.into_stream(), depth + 1));
+                    }
+                }
+                continue;
+            }
 
-        while let Some(chunk) = right_child_stream.next().await {
-            let chunk = chunk?;
-            build_table.append_build_chunk(chunk)?;
+            let mut build_table = BuildTable::with_params(params);
+            for chunk in build_chunks {
+                build_table.append_build_chunk(chunk)?;
+            }
+            let mut probe_table: ProbeTable<K> = build_table.try_into()?;
+            #[for_await]
+            for chunk in Self::probe(&mut probe_table, left_child_stream) {
+                yield chunk?;
+            }
+            params = probe_table.into_params();
         }
-        let mut probe_table: ProbeTable<K> = build_table.try_into()?;
+    }
 
+    #[try_stream(ok = DataChunk, error = RwError)]
+    async fn probe<'a>(
+        probe_table: &'a mut ProbeTable<K>,
+        mut left_child_stream: BoxedDataChunkStream,
+    ) {
         let mut state = HashJoinState::Probe;
-        let mut left_child_stream = self.left_child.take().unwrap().execute();
 
         // first probe
         match left_child_stream.next().await {
@@ -245,6 +316,7 @@ impl<K> HashJoinExecutor<K> {
         params: EquiJoinParams,
         schema: Schema,
         identity: String,
+        spill_options: SpillOptions,
     ) -> Self {
         HashJoinExecutor {
             left_child: Some(left_child),
@@ -252,6 +324,7 @@ impl<K> HashJoinExecutor<K> {
             params,
             schema,
             identity,
+            spill_options,
             _phantom: PhantomData,
         }
     }
@@ -263,6 +336,7 @@ pub struct HashJoinExecutorBuilder {
     right_child: BoxedExecutor,
     schema: Schema,
     task_id: TaskId,
+    spill_options: SpillOptions,
 }
 
 struct HashJoinExecutorBuilderDispatcher;
@@ -279,6 +353,7 @@ impl HashKeyDispatcher for HashJoinExecutorBuilderDispatcher {
             input.params,
             input.schema,
             format!("HashJoinExecutor{:?}", input.task_id),
+            input.spill_options,
         ))
     }
 }
@@ -371,6 +446,7 @@ impl BoxedExecutorBuilder for HashJoinExecutorBuilder {
                 fields: schema_fields,
             },
             task_id: context.task_id.clone(),
+            spill_options: SpillOptions::from_config(&context.batch_task_context().config()),
         };
 
         Ok(HashJoinExecutorBuilderDispatcher::dispatch_by_kind(
@@ -400,7 +476,7 @@ mod tests {
     use crate::executor::join::hash_join::{EquiJoinParams, HashJoinExecutor};
     use crate::executor::join::JoinType;
     use crate::executor::test_utils::MockExecutor;
-    use crate::executor::BoxedExecutor;
+    use crate::executor::{BoxedExecutor, SpillOptions};
     struct DataChunkMerger {
         data_types: Vec<DataType>,
         array_builders: Vec<ArrayBuilderImpl>,
@@ -583,7 +659,11 @@ mod tests {
             )
         }
 
-        fn create_join_executor(&self, has_non_equi_cond: bool) -> BoxedExecutor {
+        fn create_join_executor(
+            &self,
+            has_non_equi_cond: bool,
+            spill_options: SpillOptions,
+        ) -> BoxedExecutor {
             let join_type = self.join_type;
 
             let left_child = self.create_left_executor();
@@ -637,6 +717,7 @@ mod tests {
                 params,
                 schema,
                 "HashJoinExecutor2".to_string(),
+                spill_options,
             )) as BoxedExecutor
         }
 
@@ -656,7 +737,8 @@ mod tests {
         }
 
         async fn do_test(&self, expected: DataChunk, has_non_equi_cond: bool) {
-            let join_executor = self.create_join_executor(has_non_equi_cond);
+            let join_executor =
+                self.create_join_executor(has_non_equi_cond, SpillOptions::no_spill());
 
             let mut data_chunk_merger = DataChunkMerger::new(self.output_data_types()).unwrap();
 
@@ -691,6 +773,32 @@ mod tests {
             // assert_eq!(expected, result_chunk);
             assert!(is_data_chunk_eq(&expected, &output_chunk));
         }
+
+        /// Joins with both sides spilled to the disk, whose output order differs.
+        async fn do_spilled_test(&self, expected: DataChunk, has_non_equi_cond: bool) {
+            let spill_options = SpillOptions {
+                memory_budget: 0,
+                directory: tempfile::tempdir().unwrap().into_path(),
+            };
+            let join_executor = self.create_join_executor(has_non_equi_cond, spill_options);
+
+            let mut data_chunk_merger = DataChunkMerger::new(self.output_data_types()).unwrap();
+            let mut stream = join_executor.execute();
+            while let Some(data_chunk) = stream.next().await {
+                let data_chunk = data_chunk.unwrap().compact().unwrap();
+                data_chunk_merger.append(&data_chunk).unwrap();
+            }
+            let output_chunk = self.select_from_chunk(data_chunk_merger.finish().unwrap());
+
+            let sorted_rows = |chunk: &DataChunk| {
+                chunk
+                    .rows()
+                    .map(|row| row.to_owned_row())
+                    .sorted()
+                    .collect_vec()
+            };
+            assert_eq!(sorted_rows(&expected), sorted_rows(&output_chunk));
+        }
     }
 
     /// Sql:
@@ -1033,4 +1141,61 @@ mod tests {
 
         test_fixture.do_test(expected_chunk, true).await;
     }
+
+    #[tokio::test]
+    async fn test_full_outer_join_spilled() {
+        let test_fixture = TestFixture::with_join_type(JoinType::FullOuter);
+
+        let expected_chunk = DataChunk::from_pretty(
+            "f   F
+             6.1 .
+             .   .
+             8.4 .
+             3.9 3.7
+             3.9 .
+             .   .
+             6.6 7.5
+             .   3.7
+             .   .
+             0.7 .
+             .   .
+             5.5 .
+             .   6.1
+             .   8.9
+             .   3.5
+             .   .
+             .   .
+             .   8.0
+             .   .
+             .   9.1
+             .   .
+             .   .
+             .   9.6
+             .   .
+             .   8.18
+             .   .",
+        );
+
+        test_fixture.do_spilled_test(expected_chunk, false).await;
+    }
+
+    #[tokio::test]
+    async fn test_left_anti_join_with_non_equi_condition_spilled() {
+        let test_fixture = TestFixture::with_join_type(JoinType::LeftAnti);
+
+        let expected_chunk = DataChunk::from_pretty(
+            "f
+             6.1
+             .
+             8.4
+             3.9
+             .
+             .
+             0.7
+             .
+             5.5",
+        );
+
+        test_fixture.do_spilled_test(expected_chunk, true).await;
+    }
 }
@@ -221,6 +221,12 @@ impl<K: HashKey> TryFrom<BuildTable> for ProbeTable<K> {
 }
 
 impl<K: HashKey> ProbeTable<K> {
+    /// Consumes the table and returns the join parameters, so that they can be reused to join
+    /// another pair of inputs.
+    pub(super) fn into_params(self) -> EquiJoinParams {
+        self.params
+    }
+
     pub(super) fn build_data_empty(&self) -> bool {
         self.build_data.is_empty()
     }
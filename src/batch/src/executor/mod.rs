@@ -24,13 +24,18 @@ mod limit;
 mod merge_sort_exchange;
 pub mod monitor;
 mod order_by;
+mod over_agg;
 mod project;
 mod row_seq_scan;
 mod sort_agg;
+mod spill;
+mod sys_row_seq_scan;
+mod table_function;
 #[cfg(test)]
 pub mod test_utils;
 mod top_n;
 mod trace;
+mod union;
 mod update;
 mod values;
 
@@ -47,6 +52,7 @@ pub use limit::*;
 pub use merge_sort_exchange::*;
 pub use monitor::*;
 pub use order_by::*;
+pub use over_agg::*;
 pub use project::*;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Schema;
@@ -56,8 +62,12 @@ use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::PlanNode;
 pub use row_seq_scan::*;
 pub use sort_agg::*;
+pub use spill::*;
+pub use sys_row_seq_scan::*;
+pub use table_function::*;
 pub use top_n::*;
 pub use trace::*;
+pub use union::*;
 pub use update::*;
 pub use values::*;
 
@@ -101,6 +111,8 @@ pub struct ExecutorBuilder<'a, C> {
     pub task_id: &'a TaskId,
     context: C,
     epoch: u64,
+    /// If set, the runtime statistics of every executor are collected into it.
+    stats_collector: Option<ExecutorStatsCollectorRef>,
 }
 
 macro_rules! build_executor {
@@ -122,9 +134,16 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             task_id,
             context,
             epoch,
+            stats_collector: None,
         }
     }
 
+    #[must_use]
+    pub fn with_stats_collector(mut self, stats_collector: ExecutorStatsCollectorRef) -> Self {
+        self.stats_collector = Some(stats_collector);
+        self
+    }
+
     pub async fn build(&self) -> Result<BoxedExecutor> {
         self.try_build().await.map_err(|e| {
             InternalError(format!(
@@ -138,7 +157,13 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
 
     #[must_use]
     pub fn clone_for_plan(&self, plan_node: &'a PlanNode) -> Self {
-        ExecutorBuilder::new(plan_node, self.task_id, self.context.clone(), self.epoch)
+        Self {
+            plan_node,
+            task_id: self.task_id,
+            context: self.context.clone(),
+            epoch: self.epoch,
+            stats_collector: self.stats_collector.clone(),
+        }
     }
 
     async fn try_build(&self) -> Result<BoxedExecutor> {
@@ -162,10 +187,23 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             NodeBody::MergeSortExchange => MergeSortExchangeExecutorBuilder,
             NodeBody::GenerateSeries => GenerateSeriesExecutorBuilder,
             NodeBody::HopWindow => HopWindowExecutor,
+            NodeBody::OverAgg => OverAggExecutor,
+            NodeBody::Union => UnionExecutor,
+            NodeBody::SysRowSeqScan => SysRowSeqScanExecutor,
+            NodeBody::TableFunction => TableFunctionExecutor,
+            NodeBody::LateralTableFunction => LateralTableFunctionExecutor,
         }
         .await?;
         let input_desc = real_executor.identity().to_string();
-        Ok(Box::new(TraceExecutor::new(real_executor, input_desc)))
+        let executor: BoxedExecutor = Box::new(TraceExecutor::new(real_executor, input_desc));
+        match &self.stats_collector {
+            Some(stats_collector) => Ok(Box::new(StatsExecutor::new(
+                executor,
+                self.plan_node.get_identity().clone(),
+                stats_collector.clone(),
+            ))),
+            None => Ok(executor),
+        }
     }
 
     pub fn plan_node(&self) -> &PlanNode {
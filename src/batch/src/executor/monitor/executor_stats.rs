@@ -0,0 +1,202 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::stream::StreamExt;
+use futures_async_stream::try_stream;
+use parking_lot::Mutex;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::RwError;
+use risingwave_pb::task_service::ExecutorStats as ProstExecutorStats;
+
+use crate::executor::{BoxedDataChunkStream, BoxedExecutor, Executor};
+
+/// Runtime statistics of an executor, collected for `EXPLAIN ANALYZE`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExecutorStats {
+    pub rows: u64,
+    pub chunks: u64,
+    /// Time spent on producing the output, including the time spent in the children.
+    pub elapsed: Duration,
+    /// The estimated size of the largest output chunk. It is not the memory used by the
+    /// executor, which does not account the memory of its states, e.g. the hash table of a hash
+    /// join.
+    pub max_chunk_bytes: u64,
+}
+
+impl ExecutorStats {
+    /// Merges the stats of the same plan node in another task. As the tasks run in parallel, the
+    /// elapsed time is the largest of them.
+    pub fn merge(&mut self, other: &ExecutorStats) {
+        self.rows += other.rows;
+        self.chunks += other.chunks;
+        self.elapsed = self.elapsed.max(other.elapsed);
+        self.max_chunk_bytes = self.max_chunk_bytes.max(other.max_chunk_bytes);
+    }
+}
+
+impl From<&ProstExecutorStats> for ExecutorStats {
+    fn from(prost: &ProstExecutorStats) -> Self {
+        Self {
+            rows: prost.rows,
+            chunks: prost.chunks,
+            elapsed: Duration::from_nanos(prost.elapsed_nanos),
+            max_chunk_bytes: prost.max_chunk_bytes,
+        }
+    }
+}
+
+/// Collects the [`ExecutorStats`] of the executors in a task, keyed by the identity of their plan
+/// nodes.
+#[derive(Debug, Default)]
+pub struct ExecutorStatsCollector {
+    stats: Mutex<HashMap<String, ExecutorStats>>,
+}
+
+pub type ExecutorStatsCollectorRef = Arc<ExecutorStatsCollector>;
+
+impl ExecutorStatsCollector {
+    /// Records a call to the `next` of the executor with `identity`, which took `elapsed` and
+    /// produced `chunk`.
+    fn record(&self, identity: &str, elapsed: Duration, chunk: Option<&DataChunk>) {
+        let mut stats = self.stats.lock();
+        let stats = stats.entry(identity.to_string()).or_default();
+        stats.elapsed += elapsed;
+        if let Some(chunk) = chunk {
+            stats.rows += chunk.cardinality() as u64;
+            stats.chunks += 1;
+            stats.max_chunk_bytes = stats.max_chunk_bytes.max(chunk.estimated_size() as u64);
+        }
+    }
+
+    pub fn get(&self, identity: &str) -> Option<ExecutorStats> {
+        self.stats.lock().get(identity).cloned()
+    }
+
+    pub fn to_protobuf(&self) -> Vec<ProstExecutorStats> {
+        self.stats
+            .lock()
+            .iter()
+            .map(|(identity, stats)| ProstExecutorStats {
+                identity: identity.clone(),
+                rows: stats.rows,
+                chunks: stats.chunks,
+                elapsed_nanos: stats.elapsed.as_nanos() as u64,
+                max_chunk_bytes: stats.max_chunk_bytes,
+            })
+            .collect()
+    }
+}
+
+/// Records the [`ExecutorStats`] of its child into an [`ExecutorStatsCollector`].
+pub struct StatsExecutor {
+    child: BoxedExecutor,
+    /// Identity of the plan node that the child is built from.
+    identity: String,
+    collector: ExecutorStatsCollectorRef,
+}
+
+impl StatsExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        identity: String,
+        collector: ExecutorStatsCollectorRef,
+    ) -> Self {
+        Self {
+            child,
+            identity,
+            collector,
+        }
+    }
+}
+
+impl Executor for StatsExecutor {
+    fn schema(&self) -> &Schema {
+        self.child.schema()
+    }
+
+    fn identity(&self) -> &str {
+        "StatsExecutor"
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl StatsExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let mut child_stream = self.child.execute();
+        loop {
+            let start = Instant::now();
+            let chunk = child_stream.next().await.transpose()?;
+            self.collector
+                .record(&self.identity, start.elapsed(), chunk.as_ref());
+            match chunk {
+                Some(chunk) => yield chunk,
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::DataChunk;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_stats_executor() {
+        let schema = Schema::new(vec![Field::unnamed(DataType::Int32)]);
+        let mut child = MockExecutor::new(schema);
+        child.add(DataChunk::from_pretty(
+            "i
+             1
+             2
+             3",
+        ));
+        child.add(DataChunk::from_pretty(
+            "i
+             4
+             5",
+        ));
+        let collector = ExecutorStatsCollectorRef::default();
+        let executor = Box::new(StatsExecutor::new(
+            Box::new(child),
+            "child".to_string(),
+            collector.clone(),
+        ));
+
+        let mut stream = executor.execute();
+        while let Some(chunk) = stream.next().await {
+            chunk.unwrap();
+        }
+
+        let stats = collector.get("child").unwrap();
+        assert_eq!(stats.rows, 5);
+        assert_eq!(stats.chunks, 2);
+        assert!(stats.max_chunk_bytes > 0);
+        assert!(collector.get("other").is_none());
+    }
+}
@@ -25,6 +25,7 @@ use risingwave_common::array::{Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl,
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::ToOwnedDatum;
 use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
 use risingwave_common::util::encoding_for_comparison::{encode_chunk, is_type_encodable};
 use risingwave_common::util::sort_util::{compare_two_row, HeapElem, OrderPair};
@@ -32,9 +33,12 @@ use risingwave_pb::batch_plan::plan_node::NodeBody;
 
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    SpillFile, SpillOptions,
 };
 use crate::task::BatchTaskContext;
 
+/// `OrderByExecutor` sorts the chunks in memory. Once the buffered chunks exceed the memory budget,
+/// they are sorted and spilled to the disk as a sorted run, and the runs are merged at last.
 pub struct OrderByExecutor {
     child: Option<BoxedExecutor>,
     sorted_indices: Vec<Vec<usize>>,
@@ -48,6 +52,7 @@ pub struct OrderByExecutor {
     identity: String,
     chunk_size: usize,
     schema: Schema,
+    spill_options: SpillOptions,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -64,6 +69,7 @@ impl OrderByExecutor {
         disable_encoding: bool,
         identity: String,
         chunk_size: usize,
+        spill_options: SpillOptions,
     ) -> Self {
         let schema = child.schema().clone();
         Self {
@@ -79,6 +85,7 @@ impl OrderByExecutor {
             identity,
             chunk_size,
             schema,
+            spill_options,
         }
     }
 }
@@ -114,6 +121,7 @@ impl BoxedExecutorBuilder for OrderByExecutor {
                 false,
                 source.plan_node().get_identity().clone(),
                 DEFAULT_CHUNK_BUFFER_SIZE,
+                SpillOptions::from_config(&source.batch_task_context().config()),
             )));
         }
         Err(InternalError("OrderBy must have one child".to_string()).into())
@@ -172,23 +180,85 @@ impl OrderByExecutor {
         index
     }
 
-    async fn collect_child_data(&mut self) -> Result<()> {
-        let mut stream = self.child.take().unwrap().execute();
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            if !self.disable_encoding && self.encodable {
-                self.encoded_keys
-                    .push(encode_chunk(&chunk, self.order_pairs.clone()));
-            }
-            self.chunks.push(chunk);
-            self.sorted_indices
-                .push(self.get_order_index_from(self.chunks.len() - 1));
+    fn push_chunk(&mut self, chunk: DataChunk) {
+        if !self.disable_encoding && self.encodable {
+            self.encoded_keys
+                .push(encode_chunk(&chunk, self.order_pairs.clone()));
         }
+        self.chunks.push(chunk);
+        self.sorted_indices
+            .push(self.get_order_index_from(self.chunks.len() - 1));
+    }
+
+    fn init_heap(&mut self) {
         self.vis_indices = vec![0usize; self.chunks.len()];
         for idx in 0..self.chunks.len() {
             self.push_heap_for_chunk(idx);
         }
-        Ok(())
+    }
+
+    /// Pops the next sorted chunk of the buffered chunks from the heap.
+    fn next_sorted_chunk(&mut self) -> Result<Option<DataChunk>> {
+        let mut array_builders = self.schema().create_array_builders(self.chunk_size)?;
+
+        let mut chunk_size = 0usize;
+        while !self.min_heap.is_empty() && chunk_size < self.chunk_size {
+            let top = self.min_heap.pop().unwrap();
+            for (idx, builder) in array_builders.iter_mut().enumerate() {
+                let chunk_arr = self.chunks[top.chunk_idx].column_at(idx).array();
+                let chunk_arr = chunk_arr.as_ref();
+                macro_rules! gen_match {
+                    ($b: ident, $a: ident, [$( $tt: ident), *]) => {
+                        match ($b, $a) {
+                            $((ArrayBuilderImpl::$tt($b), ArrayImpl::$tt($a)) => Ok($b.append($a.value_at(top.elem_idx))),)*
+                                _ => Err(InternalError(String::from("Unmatched array and array builder types"))),
+                        }?
+                    }
+                }
+                let _ = gen_match!(
+                    builder,
+                    chunk_arr,
+                    [
+                        Int16,
+                        Int32,
+                        Int64,
+                        Float32,
+                        Float64,
+                        Utf8,
+                        Bool,
+                        Decimal,
+                        Interval,
+                        NaiveDate,
+                        NaiveTime,
+                        NaiveDateTime
+                    ]
+                );
+            }
+            chunk_size += 1;
+            self.push_heap_for_chunk(top.chunk_idx);
+        }
+        if chunk_size == 0 {
+            return Ok(None);
+        }
+        let columns = array_builders
+            .into_iter()
+            .map(|b| Ok(Column::new(Arc::new(b.finish()?))))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(DataChunk::builder().columns(columns).build()))
+    }
+
+    /// Sorts the buffered chunks and spills them as a sorted run.
+    async fn spill_sorted_run(&mut self) -> Result<SpillFile> {
+        self.init_heap();
+        let mut run = SpillFile::create(&self.spill_options).await?;
+        while let Some(chunk) = self.next_sorted_chunk()? {
+            run.write_chunk(chunk).await?;
+        }
+        self.chunks.clear();
+        self.sorted_indices.clear();
+        self.encoded_keys.clear();
+        self.vis_indices.clear();
+        Ok(run)
     }
 }
 
@@ -218,56 +288,100 @@ impl OrderByExecutor {
                 .all(is_type_encodable)
         }
 
-        self.collect_child_data().await?;
-
-        loop {
-            let mut array_builders = self.schema().create_array_builders(self.chunk_size)?;
-
-            let mut chunk_size = 0usize;
-            while !self.min_heap.is_empty() && chunk_size < self.chunk_size {
-                let top = self.min_heap.pop().unwrap();
-                for (idx, builder) in array_builders.iter_mut().enumerate() {
-                    let chunk_arr = self.chunks[top.chunk_idx].column_at(idx).array();
-                    let chunk_arr = chunk_arr.as_ref();
-                    macro_rules! gen_match {
-                        ($b: ident, $a: ident, [$( $tt: ident), *]) => {
-                            match ($b, $a) {
-                                $((ArrayBuilderImpl::$tt($b), ArrayImpl::$tt($a)) => Ok($b.append($a.value_at(top.elem_idx))),)*
-                                    _ => Err(InternalError(String::from("Unmatched array and array builder types"))),
-                            }?
-                        }
-                    }
-                    let _ = gen_match!(
-                        builder,
-                        chunk_arr,
-                        [
-                            Int16,
-                            Int32,
-                            Int64,
-                            Float32,
-                            Float64,
-                            Utf8,
-                            Bool,
-                            Decimal,
-                            Interval,
-                            NaiveDate,
-                            NaiveTime,
-                            NaiveDateTime
-                        ]
-                    );
-                }
-                chunk_size += 1;
-                self.push_heap_for_chunk(top.chunk_idx);
+        let mut runs = vec![];
+        let mut buffered_size = 0;
+        let mut stream = self.child.take().unwrap().execute();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            buffered_size += chunk.estimated_size();
+            self.push_chunk(chunk);
+            if buffered_size > self.spill_options.memory_budget {
+                runs.push(self.spill_sorted_run().await?);
+                buffered_size = 0;
             }
-            if chunk_size == 0 {
-                break;
+        }
+
+        if runs.is_empty() {
+            self.init_heap();
+            while let Some(chunk) = self.next_sorted_chunk()? {
+                yield chunk;
+            }
+        } else {
+            if !self.chunks.is_empty() {
+                runs.push(self.spill_sorted_run().await?);
+            }
+            #[for_await]
+            for chunk in Self::merge_sorted_runs(
+                runs,
+                self.order_pairs.clone(),
+                self.schema.clone(),
+                self.chunk_size,
+            ) {
+                yield chunk?;
+            }
+        }
+    }
+
+    /// Merges the sorted runs spilled to the disk, keeping one chunk of each run in memory.
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn merge_sorted_runs(
+        runs: Vec<SpillFile>,
+        order_pairs: Arc<Vec<OrderPair>>,
+        schema: Schema,
+        chunk_size: usize,
+    ) {
+        let mut streams = runs
+            .into_iter()
+            .map(SpillFile::into_stream)
+            .collect::<Vec<_>>();
+        let mut min_heap = BinaryHeap::new();
+        for (run_idx, stream) in streams.iter_mut().enumerate() {
+            if let Some(chunk) = stream.next().await {
+                min_heap.push(HeapElem {
+                    order_pairs: order_pairs.clone(),
+                    chunk: chunk?,
+                    chunk_idx: run_idx,
+                    elem_idx: 0,
+                    encoded_chunk: None,
+                });
+            }
+        }
+
+        while !min_heap.is_empty() {
+            let mut builders = schema.create_array_builders(chunk_size)?;
+            let mut size = 0;
+            while size < chunk_size && let Some(top) = min_heap.pop() {
+                for (idx, builder) in builders.iter_mut().enumerate() {
+                    let datum = top
+                        .chunk
+                        .column_at(idx)
+                        .array_ref()
+                        .value_at(top.elem_idx)
+                        .to_owned_datum();
+                    builder.append_datum(&datum)?;
+                }
+                size += 1;
+
+                // Spilled chunks are compact, so the next row is visible.
+                if top.elem_idx + 1 < top.chunk.cardinality() {
+                    min_heap.push(HeapElem {
+                        elem_idx: top.elem_idx + 1,
+                        ..top
+                    });
+                } else if let Some(chunk) = streams[top.chunk_idx].next().await {
+                    min_heap.push(HeapElem {
+                        chunk: chunk?,
+                        elem_idx: 0,
+                        ..top
+                    });
+                }
             }
-            let columns = array_builders
+
+            let columns = builders
                 .into_iter()
                 .map(|b| Ok(Column::new(Arc::new(b.finish()?))))
                 .collect::<Result<Vec<_>>>()?;
-            let chunk = DataChunk::builder().columns(columns).build();
-            yield chunk
+            yield DataChunk::builder().columns(columns).build();
         }
     }
 }
@@ -323,6 +437,7 @@ mod tests {
             false,
             "OrderByExecutor2".to_string(),
             DEFAULT_CHUNK_BUFFER_SIZE,
+            SpillOptions::no_spill(),
         ));
         let fields = &order_by_executor.schema().fields;
         assert_eq!(fields[0].data_type, DataType::Int32);
@@ -379,6 +494,7 @@ mod tests {
             false,
             "OrderByExecutor2".to_string(),
             DEFAULT_CHUNK_BUFFER_SIZE,
+            SpillOptions::no_spill(),
         ));
         let fields = &order_by_executor.schema().fields;
         assert_eq!(fields[0].data_type, DataType::Float32);
@@ -435,6 +551,7 @@ mod tests {
             false,
             "OrderByExecutor2".to_string(),
             DEFAULT_CHUNK_BUFFER_SIZE,
+            SpillOptions::no_spill(),
         ));
         let fields = &order_by_executor.schema().fields;
         assert_eq!(fields[0].data_type, DataType::Varchar);
@@ -452,6 +569,82 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_external_sort() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Varchar),
+            ],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i T
+             5 e
+             1 a
+             8 h",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i T
+             3 c
+             7 g",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i T
+             6 f
+             2 b
+             4 d",
+        ));
+        let order_pairs = vec![OrderPair {
+            column_idx: 0,
+            order_type: OrderType::Ascending,
+        }];
+        // Spill each chunk as a sorted run.
+        let spill_options = SpillOptions {
+            memory_budget: 0,
+            directory: tempfile::tempdir().unwrap().into_path(),
+        };
+
+        let order_by_executor = Box::new(OrderByExecutor::new(
+            Box::new(mock_executor),
+            vec![],
+            vec![],
+            vec![],
+            BinaryHeap::new(),
+            Arc::new(order_pairs),
+            vec![],
+            false,
+            false,
+            "OrderByExecutor2".to_string(),
+            3,
+            spill_options,
+        ));
+
+        let mut stream = order_by_executor.execute();
+        let mut col0 = vec![];
+        let mut col1 = vec![];
+        while let Some(res) = stream.next().await {
+            let res = res.unwrap();
+            assert!(res.cardinality() <= 3);
+            col0.extend(res.column_at(0).array().as_int32().iter());
+            col1.extend(
+                res.column_at(1)
+                    .array()
+                    .as_utf8()
+                    .iter()
+                    .map(|s| s.map(str::to_string)),
+            );
+        }
+        assert_eq!(col0, (1..=8).map(Some).collect::<Vec<_>>());
+        assert_eq!(
+            col1,
+            ["a", "b", "c", "d", "e", "f", "g", "h"]
+                .iter()
+                .map(|s| Some(s.to_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+
     // TODO: enable benches
 
     // fn benchmark_1e4(b: &mut Bencher, enable_encoding: bool) {
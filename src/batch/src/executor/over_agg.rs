@@ -0,0 +1,250 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
+use risingwave_common::util::ordered::OrderedRow;
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_expr::window_function::{evaluate_partition, WindowFuncCall};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// [`OverAggExecutor`] evaluates window functions over the partitions of its input, and outputs
+/// the input rows with the results appended. All input rows are buffered, as a partition may
+/// spread over the whole input.
+pub struct OverAggExecutor {
+    child: BoxedExecutor,
+    calls: Vec<WindowFuncCall>,
+    partition_by: Vec<usize>,
+    order_by: Vec<OrderPair>,
+    schema: Schema,
+    identity: String,
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for OverAggExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<C>,
+    ) -> Result<BoxedExecutor> {
+        ensure!(source.plan_node().get_children().len() == 1);
+        let over_agg_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::OverAgg
+        )?;
+        let calls: Vec<_> = over_agg_node
+            .get_window_functions()
+            .iter()
+            .map(WindowFuncCall::from_protobuf)
+            .try_collect()?;
+        let partition_by = over_agg_node
+            .get_partition_by()
+            .iter()
+            .map(|idx| *idx as usize)
+            .collect_vec();
+        let order_by = over_agg_node
+            .get_order_by()
+            .iter()
+            .map(OrderPair::from_prost)
+            .collect_vec();
+        if let Some(child_plan) = source.plan_node.get_children().get(0) {
+            let child = source.clone_for_plan(child_plan).build().await?;
+            return Ok(Box::new(OverAggExecutor::new(
+                child,
+                calls,
+                partition_by,
+                order_by,
+                source.plan_node().get_identity().clone(),
+            )));
+        }
+        Err(ErrorCode::InternalError("OverAgg must have one child".to_string()).into())
+    }
+}
+
+impl OverAggExecutor {
+    fn new(
+        child: BoxedExecutor,
+        calls: Vec<WindowFuncCall>,
+        partition_by: Vec<usize>,
+        order_by: Vec<OrderPair>,
+        identity: String,
+    ) -> Self {
+        let schema = child
+            .schema()
+            .clone()
+            .into_fields()
+            .into_iter()
+            .chain(
+                calls
+                    .iter()
+                    .map(|call| Field::unnamed(call.return_type.clone())),
+            )
+            .collect();
+        Self {
+            child,
+            calls,
+            partition_by,
+            order_by,
+            schema,
+            identity,
+        }
+    }
+}
+
+impl Executor for OverAggExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl OverAggExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let input_types = self.child.schema().data_types();
+        let output_types = self.schema.data_types();
+        let order_key_indices = self
+            .order_by
+            .iter()
+            .map(|order| order.column_idx)
+            .collect_vec();
+        let order_types = self
+            .order_by
+            .iter()
+            .map(|order| order.order_type)
+            .collect_vec();
+
+        // Group the rows by partition, in the order that the partitions first appear.
+        let mut partitions: Vec<Vec<Row>> = vec![];
+        let mut partition_positions = HashMap::new();
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?;
+            for row in chunk.rows() {
+                let partition_key = row.row_by_indices(&self.partition_by);
+                let position = *partition_positions.entry(partition_key).or_insert_with(|| {
+                    partitions.push(vec![]);
+                    partitions.len() - 1
+                });
+                partitions[position].push(row.to_owned_row());
+            }
+        }
+
+        let mut output_rows = Vec::with_capacity(DEFAULT_CHUNK_BUFFER_SIZE);
+        for mut rows in partitions {
+            rows.sort_by_cached_key(|row| {
+                OrderedRow::new(row.by_indices(&order_key_indices), &order_types)
+            });
+            let results = evaluate_partition(&self.calls, &rows, &input_types, &order_key_indices)?;
+            for (row, result) in rows.into_iter().zip_eq(results) {
+                output_rows.push(Row(row.0.into_iter().chain(result.0).collect()));
+                if output_rows.len() == DEFAULT_CHUNK_BUFFER_SIZE {
+                    yield DataChunk::from_rows(&output_rows, &output_types)?;
+                    output_rows.clear();
+                }
+            }
+        }
+        if !output_rows.is_empty() {
+            yield DataChunk::from_rows(&output_rows, &output_types)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream::StreamExt;
+    use risingwave_common::array::{DataChunk, DataChunkTestExt};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+    use risingwave_expr::expr::AggKind;
+    use risingwave_expr::window_function::{Frame, WindowFuncKind};
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_execute() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "I I I
+             1 2 20
+             2 1 10
+             1 1 10
+             2 3 30
+             1 3 30",
+        ));
+
+        // row_number() OVER (PARTITION BY #0 ORDER BY #1 DESC),
+        // sum(#2) OVER (PARTITION BY #0 ORDER BY #1 DESC)
+        let calls = vec![
+            WindowFuncCall {
+                kind: WindowFuncKind::RowNumber,
+                args: vec![],
+                return_type: DataType::Int64,
+                frame: Frame::default(),
+                offset: 1,
+            },
+            WindowFuncCall {
+                kind: WindowFuncKind::Aggregate(AggKind::Sum),
+                args: vec![(2, DataType::Int64)],
+                return_type: DataType::Int64,
+                frame: Frame::default(),
+                offset: 1,
+            },
+        ];
+        let executor = Box::new(OverAggExecutor::new(
+            Box::new(mock_executor),
+            calls,
+            vec![0],
+            vec![OrderPair::new(1, OrderType::Descending)],
+            "OverAggExecutor".to_string(),
+        ));
+
+        let mut stream = executor.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk,
+            DataChunk::from_pretty(
+                "I I I  I I
+                 1 3 30 1 30
+                 1 2 20 2 50
+                 1 1 10 3 60
+                 2 3 30 1 30
+                 2 1 10 2 40"
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Spilling the data buffered by executors to the local disk, so that large queries can run within
+//! a bounded memory budget.
+
+use std::hash::BuildHasher;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+
+use futures_async_stream::try_stream;
+use prost::Message;
+use risingwave_common::array::DataChunk;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::config::BatchConfig;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::data::DataChunk as ProstDataChunk;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
+use twox_hash::XxHash64;
+
+use crate::executor::{BoxedDataChunkStream, Executor};
+
+/// Number of partitions to split the data into when spilling by hash.
+pub const SPILL_PARTITION_COUNT: usize = 16;
+
+/// Maximum depth of recursive partitioning. Partitions at this depth are processed in memory
+/// regardless of the memory budget, since they are unlikely to be split further, e.g., when all
+/// rows have the same key.
+pub const MAX_SPILL_DEPTH: usize = 3;
+
+/// Options of spilling data to the local disk.
+#[derive(Clone, Debug)]
+pub struct SpillOptions {
+    /// Memory budget in bytes. Once the buffered data exceeds it, the executor spills.
+    pub memory_budget: usize,
+    /// Directory to create the spill files in.
+    pub directory: PathBuf,
+}
+
+impl SpillOptions {
+    pub fn from_config(config: &BatchConfig) -> Self {
+        let directory = if config.spill_directory.is_empty() {
+            std::env::temp_dir()
+        } else {
+            PathBuf::from(&config.spill_directory)
+        };
+        Self {
+            memory_budget: config.spill_memory_budget_mb << 20,
+            directory,
+        }
+    }
+
+    /// Options that never spill.
+    pub fn no_spill() -> Self {
+        Self {
+            memory_budget: usize::MAX,
+            directory: std::env::temp_dir(),
+        }
+    }
+}
+
+/// A temporary file on the local disk storing a sequence of [`DataChunk`]s. Each chunk is encoded
+/// with its protobuf serialization and prefixed by the length of the encoding.
+///
+/// The file is removed once dropped.
+pub struct SpillFile {
+    writer: BufWriter<File>,
+    chunk_count: usize,
+}
+
+impl SpillFile {
+    pub async fn create(options: &SpillOptions) -> Result<Self> {
+        tokio::fs::create_dir_all(&options.directory).await?;
+        let file = tempfile::tempfile_in(&options.directory)?;
+        Ok(Self {
+            writer: BufWriter::new(File::from_std(file)),
+            chunk_count: 0,
+        })
+    }
+
+    pub async fn write_chunk(&mut self, chunk: DataChunk) -> Result<()> {
+        let chunk = chunk.compact()?;
+        if chunk.cardinality() == 0 {
+            return Ok(());
+        }
+        let encoded = chunk.to_protobuf().encode_to_vec();
+        self.writer.write_u32_le(encoded.len() as u32).await?;
+        self.writer.write_all(&encoded).await?;
+        self.chunk_count += 1;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunk_count == 0
+    }
+
+    /// Finishes writing, and reads the chunks back in the order they are written.
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    pub async fn into_stream(self) {
+        let Self {
+            mut writer,
+            chunk_count,
+        } = self;
+        writer.flush().await?;
+        let mut file = writer.into_inner();
+        file.seek(SeekFrom::Start(0)).await?;
+
+        let mut reader = BufReader::new(file);
+        let mut buf = vec![];
+        for _ in 0..chunk_count {
+            let len = reader.read_u32_le().await? as usize;
+            buf.resize(len, 0);
+            reader.read_exact(&mut buf).await?;
+            let chunk = DataChunk::from_protobuf(&ProstDataChunk::decode(buf.as_slice())?)?;
+            yield chunk;
+        }
+    }
+}
+
+/// `SpillFileExecutor` reads the chunks from a [`SpillFile`], so that the spilled data can be
+/// processed by another executor.
+pub struct SpillFileExecutor {
+    file: SpillFile,
+    schema: Schema,
+    identity: String,
+}
+
+impl SpillFileExecutor {
+    pub fn new(file: SpillFile, schema: Schema, identity: String) -> Self {
+        Self {
+            file,
+            schema,
+            identity,
+        }
+    }
+}
+
+impl Executor for SpillFileExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.file.into_stream()
+    }
+}
+
+/// Hasher to partition the rows. It's seeded with the depth of partitioning, so that the rows of a
+/// partition can be split again at the next depth.
+#[derive(Clone, Copy)]
+struct PartitionHasherBuilder(u64);
+
+impl BuildHasher for PartitionHasherBuilder {
+    type Hasher = XxHash64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        XxHash64::with_seed(self.0)
+    }
+}
+
+/// Spill files partitioning the rows by the hash of their keys. Rows with the same key are always
+/// in the same partition.
+pub struct SpillPartitions {
+    files: Vec<SpillFile>,
+    key_indices: Vec<usize>,
+    hasher_builder: PartitionHasherBuilder,
+}
+
+impl SpillPartitions {
+    pub async fn create(
+        options: &SpillOptions,
+        key_indices: Vec<usize>,
+        depth: usize,
+    ) -> Result<Self> {
+        let mut files = Vec::with_capacity(SPILL_PARTITION_COUNT);
+        for _ in 0..SPILL_PARTITION_COUNT {
+            files.push(SpillFile::create(options).await?);
+        }
+        Ok(Self {
+            files,
+            key_indices,
+            hasher_builder: PartitionHasherBuilder(depth as u64),
+        })
+    }
+
+    /// Writes the visible rows of the chunk into their partitions.
+    pub async fn write_chunk(&mut self, chunk: DataChunk) -> Result<()> {
+        let chunk = chunk.compact()?;
+        let partitions = chunk
+            .get_hash_values(&self.key_indices, self.hasher_builder)?
+            .into_iter()
+            .map(|hash_code| hash_code.0 as usize % SPILL_PARTITION_COUNT)
+            .collect::<Vec<_>>();
+        for (partition, file) in self.files.iter_mut().enumerate() {
+            let visibility = partitions
+                .iter()
+                .map(|p| *p == partition)
+                .collect::<Vec<_>>();
+            if visibility.iter().any(|v| *v) {
+                file.write_chunk(chunk.with_visibility(Bitmap::try_from(visibility)?))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn into_files(self) -> Vec<SpillFile> {
+        self.files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use itertools::Itertools;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spill_file() {
+        let options = SpillOptions {
+            memory_budget: 0,
+            directory: tempfile::tempdir().unwrap().into_path(),
+        };
+        let chunks = vec![
+            DataChunk::from_pretty(
+                "i T
+                 1 a
+                 2 .",
+            ),
+            DataChunk::from_pretty(
+                "i T
+                 . c",
+            ),
+        ];
+
+        let mut file = SpillFile::create(&options).await.unwrap();
+        for chunk in chunks.clone() {
+            file.write_chunk(chunk).await.unwrap();
+        }
+        let read_chunks = file
+            .into_stream()
+            .map(|chunk| chunk.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(read_chunks.len(), chunks.len());
+        for (read, expected) in read_chunks.iter().zip_eq(&chunks) {
+            assert_eq!(read.to_pretty_string(), expected.to_pretty_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spill_partitions() {
+        let options = SpillOptions {
+            memory_budget: 0,
+            directory: tempfile::tempdir().unwrap().into_path(),
+        };
+        let chunk = DataChunk::from_pretty(
+            "i i
+             1 1
+             2 2
+             1 3
+             3 4
+             2 5",
+        );
+
+        let mut partitions = SpillPartitions::create(&options, vec![0], 0).await.unwrap();
+        partitions.write_chunk(chunk).await.unwrap();
+
+        let mut total = 0;
+        for file in partitions.into_files() {
+            let rows = file
+                .into_stream()
+                .map(|chunk| chunk.unwrap())
+                .collect::<Vec<_>>()
+                .await
+                .iter()
+                .flat_map(|chunk| chunk.rows().map(|row| row.to_owned_row()).collect_vec())
+                .collect_vec();
+            total += rows.len();
+            // All rows with the same key are in the same partition.
+            for row in &rows {
+                let count = if row[0] == Some(ScalarImpl::Int32(3)) {
+                    1
+                } else {
+                    2
+                };
+                assert_eq!(rows.iter().filter(|r| r[0] == row[0]).count(), count);
+            }
+        }
+        assert_eq!(total, 5);
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// [`SysRowSeqScanExecutor`] outputs the rows of a system table, which are generated by the
+/// frontend and sent along with the plan.
+pub struct SysRowSeqScanExecutor {
+    chunk: DataChunk,
+    schema: Schema,
+    identity: String,
+}
+
+impl SysRowSeqScanExecutor {
+    pub fn new(chunk: DataChunk, schema: Schema, identity: String) -> Self {
+        Self {
+            chunk,
+            schema,
+            identity,
+        }
+    }
+}
+
+impl Executor for SysRowSeqScanExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SysRowSeqScanExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        if self.chunk.cardinality() > 0 {
+            yield self.chunk;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for SysRowSeqScanExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<C>,
+    ) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::SysRowSeqScan
+        )?;
+
+        let fields = node.get_fields().iter().map(Field::from).collect();
+        let chunk = DataChunk::from_protobuf(node.get_chunk()?)?;
+
+        Ok(Box::new(Self::new(
+            chunk,
+            Schema { fields },
+            source.plan_node().get_identity().clone(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream::StreamExt;
+    use risingwave_common::array::{DataChunk, DataChunkTestExt};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use crate::executor::{Executor, SysRowSeqScanExecutor};
+
+    #[tokio::test]
+    async fn test_sys_row_seq_scan_executor() {
+        let chunk = DataChunk::from_pretty(
+            "i T
+             1 t
+             2 mv",
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::with_name(DataType::Int32, "oid"),
+                Field::with_name(DataType::Varchar, "relname"),
+            ],
+        };
+        let executor = Box::new(SysRowSeqScanExecutor::new(
+            DataChunk::from_protobuf(&chunk.to_protobuf()).unwrap(),
+            schema,
+            "SysRowSeqScanExecutor".to_string(),
+        ));
+
+        let mut stream = executor.execute();
+        let result = stream.next().await.unwrap().unwrap();
+        assert_eq!(result, chunk);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_empty_sys_row_seq_scan_executor() {
+        let executor = Box::new(SysRowSeqScanExecutor::new(
+            DataChunk::new_dummy(0),
+            Schema { fields: vec![] },
+            "SysRowSeqScanExecutor".to_string(),
+        ));
+        let mut stream = executor.execute();
+        assert!(stream.next().await.is_none());
+    }
+}
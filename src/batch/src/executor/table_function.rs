@@ -0,0 +1,291 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
+use risingwave_expr::table_function::{
+    build_table_function_from_prost, eval_lateral, lateral_return_types, BoxedTableFunction,
+};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use super::{BoxedExecutor, BoxedExecutorBuilder};
+use crate::executor::{BoxedDataChunkStream, Executor, ExecutorBuilder};
+use crate::task::BatchTaskContext;
+
+/// `TableFunctionExecutor` outputs the rows returned by a table function, whose arguments are all
+/// constant.
+pub struct TableFunctionExecutor {
+    schema: Schema,
+    identity: String,
+    table_function: BoxedTableFunction,
+    chunk_size: usize,
+}
+
+impl TableFunctionExecutor {
+    pub fn new(table_function: BoxedTableFunction, identity: String, chunk_size: usize) -> Self {
+        let schema = Schema::new(
+            table_function
+                .return_types()
+                .into_iter()
+                .map(Field::unnamed)
+                .collect(),
+        );
+        Self {
+            schema,
+            identity,
+            table_function,
+            chunk_size,
+        }
+    }
+}
+
+impl Executor for TableFunctionExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl TableFunctionExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let rows = self.table_function.eval(&Row::new(vec![]))?;
+        let data_types = self.schema.data_types();
+        for rows in rows.chunks(self.chunk_size) {
+            yield DataChunk::from_rows(rows, &data_types)?;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for TableFunctionExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<C>,
+    ) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::TableFunction
+        )?;
+        let table_function = build_table_function_from_prost(node.get_table_function()?)?;
+        Ok(Box::new(Self::new(
+            table_function,
+            source.plan_node().get_identity().clone(),
+            DEFAULT_CHUNK_BUFFER_SIZE,
+        )))
+    }
+}
+
+/// `LateralTableFunctionExecutor` outputs each input row followed by each row returned by a table
+/// function on it, and the 1-based ordinality of the returned row. The arguments of the table
+/// function may reference the input columns.
+pub struct LateralTableFunctionExecutor {
+    child: BoxedExecutor,
+    schema: Schema,
+    identity: String,
+    table_function: BoxedTableFunction,
+    chunk_size: usize,
+}
+
+impl LateralTableFunctionExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        table_function: BoxedTableFunction,
+        identity: String,
+        chunk_size: usize,
+    ) -> Self {
+        let schema = Schema::new(
+            lateral_return_types(table_function.as_ref(), &child.schema().data_types())
+                .into_iter()
+                .map(Field::unnamed)
+                .collect(),
+        );
+        Self {
+            child,
+            schema,
+            identity,
+            table_function,
+            chunk_size,
+        }
+    }
+}
+
+impl Executor for LateralTableFunctionExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl LateralTableFunctionExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let data_types = self.schema.data_types();
+        let mut rows = Vec::with_capacity(self.chunk_size);
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?;
+            for row in chunk.rows() {
+                rows.extend(eval_lateral(
+                    self.table_function.as_ref(),
+                    &row.to_owned_row(),
+                )?);
+                if rows.len() >= self.chunk_size {
+                    yield DataChunk::from_rows(&rows, &data_types)?;
+                    rows.clear();
+                }
+            }
+        }
+        if !rows.is_empty() {
+            yield DataChunk::from_rows(&rows, &data_types)?;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for LateralTableFunctionExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<C>,
+    ) -> Result<BoxedExecutor> {
+        ensure!(source.plan_node().get_children().len() == 1);
+        let node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::LateralTableFunction
+        )?;
+        let child_plan = &source.plan_node().get_children()[0];
+        let child = source.clone_for_plan(child_plan).build().await?;
+        let table_function = build_table_function_from_prost(node.get_table_function()?)?;
+        Ok(Box::new(Self::new(
+            child,
+            table_function,
+            source.plan_node().get_identity().clone(),
+            DEFAULT_CHUNK_BUFFER_SIZE,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::types::{DataType, JsonbVal, Scalar};
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
+    use risingwave_expr::table_function::JsonbArrayElements;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_table_function_executor() {
+        let json: JsonbVal = "[1, 2, 3]".parse().unwrap();
+        let arg = LiteralExpression::new(DataType::Jsonb, Some(json.to_scalar_value()));
+        let table_function =
+            JsonbArrayElements::new(vec![Box::new(arg)], vec![DataType::Jsonb]).unwrap();
+        let executor = Box::new(TableFunctionExecutor::new(
+            Box::new(table_function),
+            "TableFunctionExecutor".to_string(),
+            2,
+        ));
+        assert_eq!(executor.schema().data_types(), vec![DataType::Jsonb]);
+
+        let mut stream = executor.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk.cardinality(), 2);
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk.cardinality(), 1);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_lateral_table_function_executor() {
+        let json = |s: &str| Some(s.parse::<JsonbVal>().unwrap().to_scalar_value());
+        let input_types = [DataType::Int32, DataType::Jsonb];
+        let input = DataChunk::from_rows(
+            &[
+                Row::new(vec![Some(1.into()), json("[10, 20]")]),
+                Row::new(vec![Some(2.into()), json("[]")]),
+                Row::new(vec![Some(3.into()), None]),
+                Row::new(vec![Some(4.into()), json("[30]")]),
+            ],
+            &input_types,
+        )
+        .unwrap();
+        let child = MockExecutor::with_chunk(
+            input,
+            Schema::new(input_types.iter().cloned().map(Field::unnamed).collect()),
+        );
+        let arg = InputRefExpression::new(DataType::Jsonb, 1);
+        let table_function =
+            JsonbArrayElements::new(vec![Box::new(arg)], vec![DataType::Jsonb]).unwrap();
+        let executor = Box::new(LateralTableFunctionExecutor::new(
+            Box::new(child),
+            Box::new(table_function),
+            "LateralTableFunctionExecutor".to_string(),
+            2,
+        ));
+        assert_eq!(
+            executor.schema().data_types(),
+            vec![
+                DataType::Int32,
+                DataType::Jsonb,
+                DataType::Jsonb,
+                DataType::Int64
+            ]
+        );
+
+        let mut stream = executor.execute();
+        let mut rows = vec![];
+        while let Some(chunk) = stream.next().await {
+            rows.extend(chunk.unwrap().rows().map(|row| row.to_owned_row()));
+        }
+        assert_eq!(
+            rows,
+            vec![
+                Row::new(vec![
+                    Some(1.into()),
+                    json("[10, 20]"),
+                    json("10"),
+                    Some(1i64.into())
+                ]),
+                Row::new(vec![
+                    Some(1.into()),
+                    json("[10, 20]"),
+                    json("20"),
+                    Some(2i64.into())
+                ]),
+                Row::new(vec![
+                    Some(4.into()),
+                    json("[30]"),
+                    json("30"),
+                    Some(1i64.into())
+                ]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// Union executor, which outputs the chunks of its children one after another.
+pub struct UnionExecutor {
+    children: Vec<BoxedExecutor>,
+    identity: String,
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for UnionExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<C>,
+    ) -> Result<BoxedExecutor> {
+        ensure!(!source.plan_node().get_children().is_empty());
+        let _union_node =
+            try_match_expand!(source.plan_node().get_node_body().unwrap(), NodeBody::Union)?;
+
+        let mut children = Vec::with_capacity(source.plan_node().get_children().len());
+        for child_plan in source.plan_node().get_children() {
+            children.push(source.clone_for_plan(child_plan).build().await?);
+        }
+        Ok(Box::new(Self::new(
+            children,
+            source.plan_node().get_identity().clone(),
+        )))
+    }
+}
+
+impl UnionExecutor {
+    pub fn new(children: Vec<BoxedExecutor>, identity: String) -> Self {
+        Self { children, identity }
+    }
+
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        for child in self.children {
+            #[for_await]
+            for data_chunk in child.execute() {
+                yield data_chunk?;
+            }
+        }
+    }
+}
+
+impl Executor for UnionExecutor {
+    fn schema(&self) -> &Schema {
+        self.children[0].schema()
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream::StreamExt;
+    use risingwave_common::array::{DataChunk, DataChunkTestExt};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_union_executor() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let mut left = MockExecutor::new(schema.clone());
+        left.add(DataChunk::from_pretty(
+            "i I
+             1 10
+             2 20",
+        ));
+        left.add(DataChunk::from_pretty(
+            "i I
+             3 30",
+        ));
+        let mut right = MockExecutor::new(schema);
+        right.add(DataChunk::from_pretty(
+            "i I
+             1 10
+             . 40",
+        ));
+
+        let executor = Box::new(UnionExecutor::new(
+            vec![Box::new(left), Box::new(right)],
+            "UnionExecutor".to_string(),
+        ));
+        assert_eq!(executor.schema().len(), 2);
+
+        let mut stream = executor.execute();
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i I
+                 1 10
+                 2 20",
+            )
+        );
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i I
+                 3 30",
+            )
+        );
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i I
+                 1 10
+                 . 40",
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
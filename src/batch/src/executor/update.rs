@@ -232,6 +232,7 @@ mod tests {
                 name: f.name.clone(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             })
             .collect();
 
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use risingwave_common::config::BatchConfig;
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::Result;
 use risingwave_common::util::addr::{is_local_address, HostAddr};
@@ -52,6 +53,9 @@ pub trait BatchTaskContext: Clone + Send + Sync + 'static {
     }
 
     fn stats(&self) -> Arc<BatchMetrics>;
+
+    /// Get the batch related configurations, e.g. the memory budget before spilling.
+    fn config(&self) -> Arc<BatchConfig>;
 }
 
 /// Batch task context on compute node.
@@ -82,6 +86,10 @@ impl BatchTaskContext for ComputeNodeContext {
     fn stats(&self) -> Arc<BatchMetrics> {
         self.env.stats()
     }
+
+    fn config(&self) -> Arc<BatchConfig> {
+        self.env.config_ref()
+    }
 }
 
 impl ComputeNodeContext {
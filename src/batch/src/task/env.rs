@@ -110,6 +110,10 @@ impl BatchEnvironment {
         self.config.as_ref()
     }
 
+    pub fn config_ref(&self) -> Arc<BatchConfig> {
+        self.config.clone()
+    }
+
     pub fn worker_id(&self) -> WorkerNodeId {
         self.worker_id
     }
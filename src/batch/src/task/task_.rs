@@ -23,11 +23,11 @@ use risingwave_pb::batch_plan::{
     PlanFragment, TaskId as ProstTaskId, TaskOutputId as ProstOutputId,
 };
 use risingwave_pb::task_service::task_info::TaskStatus;
-use risingwave_pb::task_service::GetDataResponse;
+use risingwave_pb::task_service::{GetDataResponse, TaskInfo};
 use tokio::sync::oneshot::{Receiver, Sender};
 use tracing_futures::Instrument;
 
-use crate::executor::{BoxedExecutor, ExecutorBuilder};
+use crate::executor::{BoxedExecutor, ExecutorBuilder, ExecutorStatsCollectorRef};
 use crate::rpc::service::exchange::ExchangeWriter;
 use crate::task::channel::{create_output_channel, ChanReceiverImpl, ChanSenderImpl};
 use crate::task::BatchTaskContext;
@@ -183,6 +183,9 @@ pub struct BatchTaskExecution<C> {
     shutdown_tx: Mutex<Option<Sender<u64>>>,
 
     epoch: u64,
+
+    /// Collects the runtime statistics of the executors if required.
+    stats_collector: Option<ExecutorStatsCollectorRef>,
 }
 
 impl<C: BatchTaskContext> BatchTaskExecution<C> {
@@ -191,6 +194,7 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
         plan: PlanFragment,
         context: C,
         epoch: u64,
+        collect_stats: bool,
     ) -> Result<Self> {
         Ok(Self {
             task_id: TaskId::from(prost_tid),
@@ -201,6 +205,7 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
             failure: Arc::new(Mutex::new(None)),
             epoch,
             shutdown_tx: Mutex::new(None),
+            stats_collector: collect_stats.then(ExecutorStatsCollectorRef::default),
         })
     }
 
@@ -221,14 +226,16 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
             serde_json::to_string_pretty(self.plan.get_root()?).unwrap()
         );
         *self.state.lock() = TaskStatus::Running;
-        let exec = ExecutorBuilder::new(
+        let mut builder = ExecutorBuilder::new(
             self.plan.root.as_ref().unwrap(),
             &self.task_id.clone(),
             self.context.clone(),
             self.epoch,
-        )
-        .build()
-        .await?;
+        );
+        if let Some(stats_collector) = &self.stats_collector {
+            builder = builder.with_stats_collector(stats_collector.clone());
+        }
+        let exec = builder.build().await?;
 
         let (sender, receivers) = create_output_channel(self.plan.get_exchange_info()?)?;
         let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<u64>();
@@ -283,7 +290,12 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
                 // We prioritize abort signal over normal data chunks.
                 biased;
                 _ = &mut shutdown_rx => {
-                    sender.send(None).await?;
+                    // EOF is not sent, so that the consumers fail with this error when the channel
+                    // is closed, instead of taking the partial output as complete.
+                    *self.failure.lock() = Some(
+                        ErrorCode::InternalError(format!("task {:?} aborted", self.task_id))
+                            .into(),
+                    );
                     *self.state.lock() = TaskStatus::Aborted;
                     break;
                 }
@@ -346,6 +358,20 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
         self.failure.lock().clone()
     }
 
+    /// Returns the state of the task, along with the runtime statistics of its executors if they
+    /// are collected.
+    pub fn get_task_info(&self) -> TaskInfo {
+        TaskInfo {
+            task_id: Some(self.task_id.to_prost()),
+            task_status: *self.state.lock() as i32,
+            executor_stats: self
+                .stats_collector
+                .as_ref()
+                .map(|collector| collector.to_protobuf())
+                .unwrap_or_default(),
+        }
+    }
+
     pub fn check_if_running(&self) -> Result<()> {
         if *self.state.lock() != TaskStatus::Running {
             return Err(ErrorCode::InternalError(format!(
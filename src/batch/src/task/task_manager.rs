@@ -22,7 +22,7 @@ use risingwave_common::error::{Result, RwError};
 use risingwave_pb::batch_plan::{
     PlanFragment, TaskId as ProstTaskId, TaskOutputId as ProstTaskOutputId,
 };
-use risingwave_pb::task_service::GetDataResponse;
+use risingwave_pb::task_service::{GetDataResponse, TaskInfo};
 use tokio::sync::mpsc::Sender;
 use tonic::Status;
 
@@ -49,9 +49,10 @@ impl BatchManager {
         plan: PlanFragment,
         epoch: u64,
         context: ComputeNodeContext,
+        collect_stats: bool,
     ) -> Result<()> {
         trace!("Received task id: {:?}, plan: {:?}", tid, plan);
-        let task = BatchTaskExecution::new(tid, plan, context, epoch)?;
+        let task = BatchTaskExecution::new(tid, plan, context, epoch, collect_stats)?;
         let task_id = task.get_task_id().clone();
         let task = Arc::new(task);
 
@@ -104,6 +105,14 @@ impl BatchManager {
             .get_task_output(output_id)
     }
 
+    pub fn get_task_info(&self, sid: &ProstTaskId) -> Result<TaskInfo> {
+        let sid = TaskId::from(sid);
+        match self.tasks.lock().get(&sid) {
+            Some(task) => Ok(task.get_task_info()),
+            None => Err(TaskNotFound.into()),
+        }
+    }
+
     pub fn abort_task(&self, sid: &ProstTaskId) -> Result<()> {
         let sid = TaskId::from(sid);
         match self.tasks.lock().get(&sid) {
@@ -242,11 +251,11 @@ mod tests {
             task_id: 0,
         };
         manager
-            .fire_task(&task_id, plan.clone(), 0, context.clone())
+            .fire_task(&task_id, plan.clone(), 0, context.clone(), false)
             .await
             .unwrap();
         let err = manager
-            .fire_task(&task_id, plan, 0, context)
+            .fire_task(&task_id, plan, 0, context, false)
             .await
             .unwrap_err();
         assert!(err
@@ -281,12 +290,62 @@ mod tests {
             task_id: 0,
         };
         manager
-            .fire_task(&task_id, plan.clone(), 0, context.clone())
+            .fire_task(&task_id, plan.clone(), 0, context.clone(), false)
             .await
             .unwrap();
         manager.abort_task(&task_id).unwrap();
         let task_id = TaskId::from(&task_id);
         let res = manager.wait_until_task_aborted(&task_id).await;
         assert_eq!(res, Ok(()));
+        assert!(manager.get_error(&task_id).unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_task_executor_stats() {
+        let manager = BatchManager::new();
+        let plan = PlanFragment {
+            root: Some(PlanNode {
+                children: vec![],
+                identity: "GenerateSeries".to_string(),
+                node_body: Some(NodeBody::GenerateSeries(GenerateSeriesNode {
+                    start: Some(make_i32_literal(1)),
+                    stop: Some(make_i32_literal(10)),
+                    step: Some(make_i32_literal(1)),
+                })),
+            }),
+            exchange_info: Some(ExchangeInfo {
+                mode: DistributionMode::Single as i32,
+                distribution: None,
+            }),
+        };
+        let context = ComputeNodeContext::new_for_test();
+        let task_id = ProstTaskId {
+            query_id: "".to_string(),
+            stage_id: 0,
+            task_id: 0,
+        };
+        manager
+            .fire_task(&task_id, plan, 0, context, true)
+            .await
+            .unwrap();
+
+        let mut output = manager
+            .take_output(&ProstTaskOutputId {
+                task_id: Some(task_id.clone()),
+                output_id: 0,
+            })
+            .unwrap();
+        let mut rows = 0;
+        while let Some(chunk) = output.direct_take_data().await.unwrap() {
+            rows += chunk.cardinality();
+        }
+        assert_eq!(rows, 10);
+
+        let task_info = manager.get_task_info(&task_id).unwrap();
+        assert_eq!(task_info.executor_stats.len(), 1);
+        let stats = &task_info.executor_stats[0];
+        assert_eq!(stats.identity, "GenerateSeries");
+        assert_eq!(stats.rows, 10);
+        assert!(stats.chunks > 0);
     }
 }
@@ -155,6 +155,8 @@ async fn main() {
         enable_local_spill: false,
         local_object_store: "memory".to_string(),
         share_buffer_compaction_worker_threads_number: 1,
+        disk_cache_capacity_mb: 0,
+        disk_cache_dir: "".to_string(),
     });
 
     let (_env, hummock_manager_ref, _cluster_manager_ref, worker_node) =
@@ -90,6 +90,10 @@ impl Array for BoolArray {
         &self.bitmap
     }
 
+    fn estimated_size(&self) -> usize {
+        self.bitmap.estimated_size() + self.data.estimated_size()
+    }
+
     fn set_bitmap(&mut self, bitmap: Bitmap) {
         self.bitmap = bitmap;
     }
@@ -0,0 +1,206 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::mem::size_of;
+
+use itertools::Itertools;
+use risingwave_pb::data::buffer::CompressionType;
+use risingwave_pb::data::{Array as ProstArray, ArrayType, Buffer};
+
+use super::{Array, ArrayBuilder, ArrayIterator, ArrayMeta, NULL_VAL_FOR_HASH};
+use crate::array::ArrayBuilderImpl;
+use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::error::Result;
+use crate::types::{ByteaRef, ByteaVal};
+
+/// `BytesArray` is a collection of byte strings, which is the array of `BYTEA`. It has the same
+/// layout as a `Utf8Array`.
+#[derive(Debug)]
+pub struct BytesArray {
+    offset: Vec<usize>,
+    bitmap: Bitmap,
+    data: Vec<u8>,
+}
+
+impl Array for BytesArray {
+    type Builder = BytesArrayBuilder;
+    type Iter<'a> = ArrayIterator<'a, Self>;
+    type OwnedItem = ByteaVal;
+    type RefItem<'a> = ByteaRef<'a>;
+
+    fn value_at(&self, idx: usize) -> Option<ByteaRef<'_>> {
+        if !self.is_null(idx) {
+            Some(self.data[self.offset[idx]..self.offset[idx + 1]].into())
+        } else {
+            None
+        }
+    }
+
+    unsafe fn value_at_unchecked(&self, idx: usize) -> Option<ByteaRef<'_>> {
+        if !self.is_null_unchecked(idx) {
+            Some(
+                self.data
+                    .get_unchecked(
+                        *self.offset.get_unchecked(idx)..*self.offset.get_unchecked(idx + 1),
+                    )
+                    .into(),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.offset.len() - 1
+    }
+
+    fn iter(&self) -> ArrayIterator<'_, Self> {
+        ArrayIterator::new(self)
+    }
+
+    fn to_protobuf(&self) -> ProstArray {
+        let offset_buffer = self
+            .offset
+            .iter()
+            .zip_eq(self.null_bitmap().iter().chain(iter::once(true)))
+            .filter(|(_, not_null)| *not_null)
+            .flat_map(|(offset, _)| (*offset as u64).to_be_bytes())
+            .collect_vec();
+
+        let values = vec![
+            Buffer {
+                compression: CompressionType::None as i32,
+                body: offset_buffer,
+            },
+            Buffer {
+                compression: CompressionType::None as i32,
+                body: self.data.clone(),
+            },
+        ];
+        let null_bitmap = self.null_bitmap().to_protobuf();
+        ProstArray {
+            null_bitmap: Some(null_bitmap),
+            values,
+            array_type: ArrayType::Bytea as i32,
+            struct_array_data: None,
+            list_array_data: None,
+        }
+    }
+
+    fn null_bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    fn estimated_size(&self) -> usize {
+        self.bitmap.estimated_size() + self.offset.len() * size_of::<usize>() + self.data.len()
+    }
+
+    fn set_bitmap(&mut self, bitmap: Bitmap) {
+        self.bitmap = bitmap;
+    }
+
+    #[inline(always)]
+    fn hash_at<H: Hasher>(&self, idx: usize, state: &mut H) {
+        match self.value_at(idx) {
+            Some(v) => v.hash(state),
+            None => NULL_VAL_FOR_HASH.hash(state),
+        }
+    }
+
+    fn create_builder(&self, capacity: usize) -> Result<ArrayBuilderImpl> {
+        let array_builder = BytesArrayBuilder::new(capacity)?;
+        Ok(ArrayBuilderImpl::Bytea(array_builder))
+    }
+}
+
+/// `BytesArrayBuilder` uses `ByteaRef` to build a `BytesArray`.
+#[derive(Debug)]
+pub struct BytesArrayBuilder {
+    offset: Vec<usize>,
+    bitmap: BitmapBuilder,
+    data: Vec<u8>,
+}
+
+impl ArrayBuilder for BytesArrayBuilder {
+    type ArrayType = BytesArray;
+
+    fn with_meta(capacity: usize, _meta: ArrayMeta) -> Result<Self> {
+        let mut offset = Vec::with_capacity(capacity + 1);
+        offset.push(0);
+        Ok(Self {
+            offset,
+            data: Vec::with_capacity(capacity),
+            bitmap: BitmapBuilder::with_capacity(capacity),
+        })
+    }
+
+    fn append(&mut self, value: Option<ByteaRef<'_>>) -> Result<()> {
+        match value {
+            Some(x) => {
+                self.bitmap.append(true);
+                self.data.extend_from_slice(x.as_bytes());
+            }
+            None => {
+                self.bitmap.append(false);
+            }
+        }
+        self.offset.push(self.data.len());
+        Ok(())
+    }
+
+    fn append_array(&mut self, other: &BytesArray) -> Result<()> {
+        for bit in other.bitmap.iter() {
+            self.bitmap.append(bit);
+        }
+        self.data.extend_from_slice(&other.data);
+        let start = *self.offset.last().unwrap();
+        for other_offset in &other.offset[1..] {
+            self.offset.push(*other_offset + start);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<BytesArray> {
+        Ok(BytesArray {
+            bitmap: self.bitmap.finish(),
+            data: self.data,
+            offset: self.offset,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::ArrayImpl;
+
+    #[test]
+    fn test_bytes_array_protobuf() {
+        let values = [Some(&b"\x00\xff"[..]), None, Some(b""), Some(b"abc")];
+        let mut builder = BytesArrayBuilder::new(values.len()).unwrap();
+        for v in values {
+            builder.append(v.map(ByteaRef::from)).unwrap();
+        }
+        let array = builder.finish().unwrap();
+        assert_eq!(
+            array.iter().map(|v| v.map(|v| v.as_bytes())).collect_vec(),
+            values
+        );
+
+        let decoded = ArrayImpl::from_protobuf(&array.to_protobuf(), values.len()).unwrap();
+        assert_eq!(decoded, ArrayImpl::from(array));
+    }
+}
@@ -201,6 +201,19 @@ impl DataChunk {
         &self.columns
     }
 
+    /// Estimated size of the chunk in bytes, used to track the memory usage of executors
+    /// buffering chunks.
+    pub fn estimated_size(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|column| column.array_ref().estimated_size())
+            .sum::<usize>()
+            + self
+                .visibility
+                .as_ref()
+                .map_or(0, |visibility| visibility.estimated_size())
+    }
+
     pub fn to_protobuf(&self) -> ProstDataChunk {
         assert!(
             self.visibility.is_none(),
@@ -277,6 +277,11 @@ impl Row {
         self.0.iter()
     }
 
+    /// Returns a new row by the given `indices` of this row.
+    pub fn by_indices(&self, indices: &[usize]) -> Row {
+        Row(indices.iter().map(|&idx| self.0[idx].clone()).collect_vec())
+    }
+
     /// Hash row data all in one
     pub fn hash_row<H>(&self, hash_builder: &H) -> HashCode
     where
@@ -399,7 +404,7 @@ mod tests {
             Ty::Int64,
             Ty::Float32,
             Ty::Float64,
-            Ty::Decimal,
+            Ty::Decimal(None),
             Ty::Interval,
         ]);
         let row1 = de.deserialize_not_null(&bytes).unwrap();
@@ -430,7 +435,7 @@ mod tests {
             Ty::Int64,
             Ty::Float32,
             Ty::Float64,
-            Ty::Decimal,
+            Ty::Decimal(None),
             Ty::Interval,
         ]);
         let row1 = de.deserialize(&bytes).unwrap();
@@ -110,6 +110,10 @@ impl Array for DecimalArray {
         &self.bitmap
     }
 
+    fn estimated_size(&self) -> usize {
+        self.bitmap.estimated_size() + self.data.len() * size_of::<Decimal>()
+    }
+
     fn set_bitmap(&mut self, bitmap: Bitmap) {
         self.bitmap = bitmap;
     }
@@ -28,6 +28,7 @@ use crate::for_all_native_types;
 use crate::types::interval::IntervalUnit;
 use crate::types::{
     NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, NativeType, Scalar, ScalarRef,
+    UuidWrapper,
 };
 
 /// Physical type of array items which have fixed size.
@@ -130,7 +131,8 @@ impl_primitive_for_others! {
     { IntervalUnit, Interval, Interval },
     { NaiveDateWrapper, Date, NaiveDate },
     { NaiveTimeWrapper, Time, NaiveTime },
-    { NaiveDateTimeWrapper, Timestamp, NaiveDateTime }
+    { NaiveDateTimeWrapper, Timestamp, NaiveDateTime },
+    { UuidWrapper, Uuid, Uuid }
 }
 
 /// `PrimitiveArray` is a collection of primitive types, such as `i32`, `f32`.
@@ -209,6 +211,10 @@ impl<T: PrimitiveArrayItemType> Array for PrimitiveArray<T> {
         &self.bitmap
     }
 
+    fn estimated_size(&self) -> usize {
+        self.bitmap.estimated_size() + self.data.len() * size_of::<T>()
+    }
+
     fn set_bitmap(&mut self, bitmap: Bitmap) {
         self.bitmap = bitmap;
     }
@@ -179,6 +179,15 @@ impl Array for StructArray {
         &self.bitmap
     }
 
+    fn estimated_size(&self) -> usize {
+        self.bitmap.estimated_size()
+            + self
+                .children
+                .iter()
+                .map(|child| child.estimated_size())
+                .sum::<usize>()
+    }
+
     fn set_bitmap(&mut self, bitmap: Bitmap) {
         self.bitmap = bitmap;
     }
@@ -113,6 +113,10 @@ impl Array for Utf8Array {
         &self.bitmap
     }
 
+    fn estimated_size(&self) -> usize {
+        self.bitmap.estimated_size() + self.offset.len() * size_of::<usize>() + self.data.len()
+    }
+
     fn set_bitmap(&mut self, bitmap: Bitmap) {
         self.bitmap = bitmap;
     }
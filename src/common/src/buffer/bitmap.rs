@@ -195,6 +195,11 @@ impl Bitmap {
         self.bits.len()
     }
 
+    /// Estimated size of the bitmap in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.num_of_buffer_bytes()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.bits.is_empty()
     }
@@ -25,6 +25,16 @@ pub use schema::{test_utils as schema_test_utils, Field, Schema};
 pub const DEFAULT_DATABASE_NAME: &str = "dev";
 pub const DEFAULT_SCHEMA_NAME: &str = "dev";
 
+pub const DEFAULT_SUPER_USER: &str = "root";
+pub const DEFAULT_SUPER_USER_ID: u32 = 1;
+// This is for compatibility with customized utils for PostgreSQL.
+pub const DEFAULT_SUPER_USER_FOR_PG: &str = "postgres";
+pub const DEFAULT_SUPER_USER_FOR_PG_ID: u32 = 2;
+
+/// Ids of the users created by `CREATE USER` start from this value, the smaller ones are reserved
+/// for the default super users.
+pub const NON_RESERVED_USER_ID: i32 = 3;
+
 pub type CatalogVersion = u64;
 
 pub enum CatalogId {
@@ -247,7 +247,7 @@ pub mod test_utils {
     }
 
     fn decimal_n<const N: usize>() -> Schema {
-        field_n::<N>(DataType::Decimal)
+        field_n::<N>(DataType::Decimal(None))
     }
 
     /// Create a util schema **for test only** with three decimal fields.
@@ -54,6 +54,7 @@ impl ColumnDescTestExt for ColumnDesc {
             column_id,
             name: name.to_string(),
             type_name: type_name.to_string(),
+            default_value: vec![],
             field_descs: fields,
         }
     }
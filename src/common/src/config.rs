@@ -76,6 +76,14 @@ impl Default for ServerConfig {
 pub struct BatchConfig {
     // #[serde(default = "default::chunk_size")]
     // pub chunk_size: u32,
+    /// Memory budget of each executor able to spill, like sort, hash aggregation and hash join.
+    /// Once the buffered data exceeds the budget, the executor spills it to the local disk.
+    #[serde(default = "default::spill_memory_budget_mb")]
+    pub spill_memory_budget_mb: usize,
+
+    /// Directory of the spill files. The system temporary directory is used if empty.
+    #[serde(default)]
+    pub spill_directory: String,
 }
 
 impl Default for BatchConfig {
@@ -157,6 +165,14 @@ pub struct StorageConfig {
     /// Local object store root. We should call `get_local_object_store` to get the object store.
     #[serde(default = "default::local_object_store")]
     pub local_object_store: String,
+
+    /// Capacity of the local disk cache for remote sstable blocks and metas. 0 means disabled.
+    #[serde(default = "default::disk_cache_capacity_mb")]
+    pub disk_cache_capacity_mb: usize,
+
+    /// Local directory of the disk cache.
+    #[serde(default = "default::disk_cache_dir")]
+    pub disk_cache_dir: String,
 }
 
 impl Default for StorageConfig {
@@ -262,7 +278,19 @@ mod default {
         "tempdisk".to_string()
     }
 
+    pub fn disk_cache_capacity_mb() -> usize {
+        0
+    }
+
+    pub fn disk_cache_dir() -> String {
+        "disk_cache".to_string()
+    }
+
     pub fn checkpoint_interval_ms() -> u32 {
         100
     }
+
+    pub fn spill_memory_budget_mb() -> usize {
+        512
+    }
 }
@@ -131,6 +131,10 @@ pub enum ErrorCode {
     },
     #[error("Invalid Parameter Value: {0}")]
     InvalidParameterValue(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("canceling statement due to {0}")]
+    QueryCancelled(String),
 
     /// This error occurs when the meta node receives heartbeat from a previous removed worker
     /// node. Currently we don't support re-register, and the worker node need a full restart.
@@ -307,6 +311,8 @@ impl ErrorCode {
             ErrorCode::UnknownWorker => 24,
             ErrorCode::ConnectorError(_) => 25,
             ErrorCode::InvalidParameterValue(_) => 26,
+            ErrorCode::PermissionDenied(_) => 27,
+            ErrorCode::QueryCancelled(_) => 28,
             ErrorCode::UnknownError(_) => 101,
         }
     }
@@ -27,8 +27,9 @@ use crate::array::{
 };
 use crate::error::Result;
 use crate::types::{
-    DataType, Datum, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper,
-    NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarRef, ToOwnedDatum,
+    ByteaRef, DataType, Datum, Decimal, IntervalUnit, JsonbRef, NaiveDateTimeWrapper,
+    NaiveDateWrapper, NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarRef, ToOwnedDatum,
+    UuidWrapper,
 };
 use crate::util::hash_util::CRC32FastBuilder;
 
@@ -415,6 +416,47 @@ impl HashKeySerDe<'_> for NaiveTimeWrapper {
     }
 }
 
+impl<'a> HashKeySerDe<'a> for JsonbRef<'a> {
+    type S = Vec<u8>;
+
+    /// This should never be called
+    fn serialize(self) -> Self::S {
+        panic!("Should not serialize jsonb for hash!")
+    }
+
+    /// This should never be called
+    fn deserialize<R: Read>(_source: &mut R) -> Self {
+        panic!("Should not serialize jsonb for hash!")
+    }
+}
+
+impl<'a> HashKeySerDe<'a> for ByteaRef<'a> {
+    type S = Vec<u8>;
+
+    /// This should never be called
+    fn serialize(self) -> Self::S {
+        panic!("Should not serialize bytea for hash!")
+    }
+
+    /// This should never be called
+    fn deserialize<R: Read>(_source: &mut R) -> Self {
+        panic!("Should not serialize bytea for hash!")
+    }
+}
+
+impl HashKeySerDe<'_> for UuidWrapper {
+    type S = [u8; 16];
+
+    fn serialize(self) -> Self::S {
+        *self.0.as_bytes()
+    }
+
+    fn deserialize<R: Read>(source: &mut R) -> Self {
+        let value = Self::read_fixed_size_bytes::<R, 16>(source);
+        UuidWrapper::new(uuid::Uuid::from_bytes(value))
+    }
+}
+
 impl<'a> HashKeySerDe<'a> for StructRef<'a> {
     type S = Vec<u8>;
 
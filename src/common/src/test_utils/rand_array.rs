@@ -27,8 +27,8 @@ use rand::{Rng, SeedableRng};
 
 use crate::array::{Array, ArrayBuilder, ArrayRef, ListValue, StructValue};
 use crate::types::{
-    Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, NativeType,
-    Scalar,
+    ByteaVal, Decimal, IntervalUnit, JsonbVal, NaiveDateTimeWrapper, NaiveDateWrapper,
+    NaiveTimeWrapper, NativeType, Scalar, UuidWrapper,
 };
 
 pub trait RandValue {
@@ -103,6 +103,28 @@ impl RandValue for bool {
     }
 }
 
+impl RandValue for JsonbVal {
+    fn rand_value<R: rand::Rng>(rand: &mut R) -> Self {
+        serde_json::Value::from(rand.gen::<i32>()).into()
+    }
+}
+
+impl RandValue for ByteaVal {
+    fn rand_value<R: rand::Rng>(rand: &mut R) -> Self {
+        let len = rand.gen_range(0..=10);
+        (0..len)
+            .map(|_| rand.gen::<u8>())
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl RandValue for UuidWrapper {
+    fn rand_value<R: rand::Rng>(rand: &mut R) -> Self {
+        UuidWrapper::new(uuid::Uuid::from_u128(rand.gen()))
+    }
+}
+
 impl RandValue for StructValue {
     fn rand_value<R: rand::Rng>(_rand: &mut R) -> Self {
         StructValue::new(vec![])
@@ -0,0 +1,216 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bytes::{Buf, BufMut};
+use serde::de::Visitor;
+use serde::{Deserializer, Serializer};
+
+use crate::error::{ErrorCode, Result, RwError};
+
+/// An owned bytea value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteaVal(Box<[u8]>);
+
+/// A reference to a bytea value, which can be borrowed from a [`ByteaVal`] or a `BytesArray`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteaRef<'a>(&'a [u8]);
+
+impl ByteaVal {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.0
+    }
+
+    /// Deserializes a value encoded by [`ByteaRef::memcmp_serialize`].
+    pub fn memcmp_deserialize(
+        de: &mut memcomparable::Deserializer<impl Buf>,
+    ) -> memcomparable::Result<Self> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(
+                self,
+                v: Vec<u8>,
+            ) -> std::result::Result<Vec<u8>, E> {
+                Ok(v)
+            }
+        }
+
+        de.deserialize_byte_buf(BytesVisitor).map(Self::from)
+    }
+}
+
+impl From<Vec<u8>> for ByteaVal {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes.into_boxed_slice())
+    }
+}
+
+impl From<&[u8]> for ByteaVal {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.into())
+    }
+}
+
+impl<'a> From<&'a [u8]> for ByteaRef<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// Parses a bytea literal in either the hex format (`\x0a1b`) or the escape format (`a\\b\001`),
+/// as Postgres does.
+impl FromStr for ByteaVal {
+    type Err = RwError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "invalid input syntax for type bytea: \"{}\"",
+                s
+            )))
+        };
+
+        if let Some(hex) = s.strip_prefix("\\x") {
+            let digits = hex
+                .chars()
+                .filter(|c| !c.is_ascii_whitespace())
+                .map(|c| c.to_digit(16).map(|d| d as u8))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+            if digits.len() % 2 != 0 {
+                return Err(invalid());
+            }
+            return Ok(digits
+                .chunks(2)
+                .map(|pair| pair[0] << 4 | pair[1])
+                .collect::<Vec<_>>()
+                .into());
+        }
+
+        let mut bytes = Vec::with_capacity(s.len());
+        let mut rest = s.as_bytes();
+        while let Some((&b, tail)) = rest.split_first() {
+            if b != b'\\' {
+                bytes.push(b);
+                rest = tail;
+            } else if tail.first() == Some(&b'\\') {
+                bytes.push(b'\\');
+                rest = &tail[1..];
+            } else if tail.len() >= 3
+                && tail[0] <= b'3'
+                && tail[..3].iter().all(|c| (b'0'..=b'7').contains(c))
+            {
+                bytes.push((tail[0] - b'0') << 6 | (tail[1] - b'0') << 3 | (tail[2] - b'0'));
+                rest = &tail[3..];
+            } else {
+                return Err(invalid());
+            }
+        }
+        Ok(bytes.into())
+    }
+}
+
+impl<'a> ByteaRef<'a> {
+    pub fn as_bytes(self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Serializes the value into a memcomparable encoding, which keeps the lexicographic order of
+    /// the bytes.
+    pub fn memcmp_serialize(
+        self,
+        ser: &mut memcomparable::Serializer<impl BufMut>,
+    ) -> memcomparable::Result<()> {
+        (&mut *ser).serialize_bytes(self.0)
+    }
+}
+
+/// Displays the value in the hex format, which is the default output format of Postgres.
+impl fmt::Display for ByteaRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\\x")?;
+        for b in self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ByteaVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ByteaRef(&self.0).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytea_text() {
+        let v: ByteaVal = "\\x00ff1A".parse().unwrap();
+        assert_eq!(v.as_bytes(), &[0x00, 0xff, 0x1a]);
+        assert_eq!(v.to_string(), "\\x00ff1a");
+
+        let v: ByteaVal = "a\\\\b\\001".parse().unwrap();
+        assert_eq!(v.as_bytes(), b"a\\b\x01");
+
+        assert!("\\x0".parse::<ByteaVal>().is_err());
+        assert!("\\xzz".parse::<ByteaVal>().is_err());
+        assert!("a\\b".parse::<ByteaVal>().is_err());
+    }
+
+    #[test]
+    fn test_bytea_memcmp() {
+        let values = [
+            &b""[..],
+            b"\x00",
+            b"\x00\x00",
+            b"a",
+            b"abcdefgh",
+            b"abcdefghi",
+            b"b",
+        ];
+        let encoded = values
+            .iter()
+            .map(|v| {
+                let mut ser = memcomparable::Serializer::new(vec![]);
+                ByteaRef(v).memcmp_serialize(&mut ser).unwrap();
+                ser.into_inner()
+            })
+            .collect::<Vec<_>>();
+        assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+
+        for (v, bytes) in values.iter().zip(encoded) {
+            let mut de = memcomparable::Deserializer::new(bytes.as_slice());
+            assert_eq!(
+                ByteaVal::memcmp_deserialize(&mut de).unwrap().as_bytes(),
+                *v
+            );
+        }
+    }
+}
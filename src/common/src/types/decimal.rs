@@ -421,6 +421,25 @@ impl Decimal {
         }
     }
 
+    /// Rounds the decimal to `scale` fractional digits and checks that it has at most `precision`
+    /// digits in total, as a cast to `NUMERIC(precision, scale)` does. Returns `None` on overflow.
+    /// Infinities never fit in a constrained numeric, while `NaN` always does.
+    pub fn with_typmod(&self, precision: u32, scale: u32) -> Option<Self> {
+        match self {
+            Self::Normalized(d) => {
+                let mut d = d.round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+                d.rescale(scale);
+                if d.mantissa().unsigned_abs() < 10u128.pow(precision) {
+                    Some(Self::Normalized(d))
+                } else {
+                    None
+                }
+            }
+            Self::NaN => Some(Self::NaN),
+            Self::PositiveINF | Self::NegativeINF => None,
+        }
+    }
+
     pub fn from_i128_with_scale(num: i128, scale: u32) -> Self {
         Decimal::Normalized(RustDecimal::from_i128_with_scale(num, scale))
     }
@@ -644,4 +663,23 @@ mod tests {
         assert_eq!(Decimal::to_u64(&Decimal::from_u64(1).unwrap()).unwrap(), 1,);
         assert_eq!(Decimal::to_i64(&Decimal::from_i64(1).unwrap()).unwrap(), 1,);
     }
+
+    #[test]
+    fn test_with_typmod() {
+        let d = Decimal::from_str("123.456").unwrap();
+        assert_eq!(d.with_typmod(5, 2).unwrap().to_string(), "123.46");
+        assert_eq!(d.with_typmod(6, 0).unwrap().to_string(), "123");
+        assert_eq!(d.with_typmod(4, 2), None);
+        assert_eq!(Decimal::from_str("-9.995").unwrap().with_typmod(3, 2), None);
+        assert_eq!(
+            Decimal::from_str("1.5")
+                .unwrap()
+                .with_typmod(3, 2)
+                .unwrap()
+                .to_string(),
+            "1.50"
+        );
+        assert_eq!(Decimal::NaN.with_typmod(3, 2), Some(Decimal::NaN));
+        assert_eq!(Decimal::PositiveINF.with_typmod(3, 2), None);
+    }
 }
@@ -174,6 +174,27 @@ impl IntervalUnit {
         Some(IntervalUnit { months, days, ms })
     }
 
+    /// Multiple [`IntervalUnit`] by a float with overflow check. As in PG, the fractional part of
+    /// months cascades down to days (30 days per month), and that of days down to milliseconds.
+    pub fn checked_mul_float(&self, rhs: f64) -> Option<Self> {
+        let months = self.months as f64 * rhs;
+        let days = self.days as f64 * rhs + months.fract() * 30.0;
+        let ms = self.ms as f64 * rhs + days.fract() * 24.0 * 3600.0 * 1000.0;
+
+        let i32_range = i32::MIN as f64..=i32::MAX as f64;
+        if !i32_range.contains(&months.trunc())
+            || !i32_range.contains(&days.trunc())
+            || !(i64::MIN as f64..=i64::MAX as f64).contains(&ms.round())
+        {
+            return None;
+        }
+        Some(IntervalUnit {
+            months: months.trunc() as i32,
+            days: days.trunc() as i32,
+            ms: ms.round() as i64,
+        })
+    }
+
     /// Performs an exact division, returns [`None`] if for any unit, lhs % rhs != 0.
     pub fn exact_div(&self, rhs: &Self) -> Option<i64> {
         let mut res = None;
@@ -308,6 +329,21 @@ mod tests {
         assert_eq!(interval.to_string(), "-1 years -2 mons 3 days 11:45:14.233");
     }
 
+    #[test]
+    fn test_checked_mul_float() {
+        let interval = IntervalUnit::new(1, 1, 0);
+        assert_eq!(
+            interval.checked_mul_float(1.5),
+            Some(IntervalUnit::new(1, 16, 12 * 3600 * 1000))
+        );
+        assert_eq!(
+            IntervalUnit::from_days(3).checked_mul_float(-0.5),
+            Some(IntervalUnit::new(0, -1, -12 * 3600 * 1000))
+        );
+        assert_eq!(interval.checked_mul_float(f64::NAN), None);
+        assert_eq!(interval.checked_mul_float(1e10), None);
+    }
+
     #[test]
     fn test_exact_div() {
         let cases = [
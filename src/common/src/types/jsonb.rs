@@ -0,0 +1,676 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::{fmt, io};
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{ErrorCode, Result, RwError};
+
+/// An owned jsonb value.
+///
+/// Jsonb values are ordered and compared the way Postgres does: first by the kind of the value
+/// (`null` < string < number < boolean < array < object), then arrays and objects by the number of
+/// their elements and finally by their elements. Numbers are compared by their numeric value, so
+/// `1` equals `1.0`.
+#[derive(Debug, Clone)]
+pub struct JsonbVal(Box<Value>);
+
+/// A reference to a jsonb value, which can be borrowed from a [`JsonbVal`] or a `JsonbArray`.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonbRef<'a>(&'a Value);
+
+impl JsonbVal {
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
+
+    pub fn into_value(self) -> Value {
+        *self.0
+    }
+
+    /// Decodes a value encoded by [`JsonbRef::value_serialize`].
+    pub fn value_deserialize(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok().map(Self::from)
+    }
+
+    /// Deserializes a value encoded by [`JsonbRef::memcmp_serialize`].
+    pub fn memcmp_deserialize(
+        de: &mut memcomparable::Deserializer<impl Buf>,
+    ) -> memcomparable::Result<Self> {
+        memcmp_deserialize_value(de).map(Self::from)
+    }
+}
+
+impl From<Value> for JsonbVal {
+    fn from(value: Value) -> Self {
+        Self(Box::new(value))
+    }
+}
+
+impl<'a> From<&'a Value> for JsonbRef<'a> {
+    fn from(value: &'a Value) -> Self {
+        Self(value)
+    }
+}
+
+impl FromStr for JsonbVal {
+    type Err = RwError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(s).map_err(|e| {
+            ErrorCode::InvalidInputSyntax(format!("invalid input syntax for type jsonb: {}", e))
+        })?;
+        Ok(value.into())
+    }
+}
+
+impl<'a> JsonbRef<'a> {
+    pub fn value(self) -> &'a Value {
+        self.0
+    }
+
+    pub fn is_null(self) -> bool {
+        self.0.is_null()
+    }
+
+    /// Returns the name of the kind of the value, as `jsonb_typeof` does.
+    pub fn type_name(self) -> &'static str {
+        match self.0 {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Returns the field of an object with the given key, as `->` with a text operand.
+    pub fn access_object_field(self, key: &str) -> Option<JsonbRef<'a>> {
+        self.0.as_object()?.get(key).map(JsonbRef)
+    }
+
+    /// Returns the element of an array at the given index, as `->` with an integer operand.
+    /// Negative indexes count from the end of the array.
+    pub fn access_array_element(self, idx: i32) -> Option<JsonbRef<'a>> {
+        let array = self.0.as_array()?;
+        let idx = if idx < 0 {
+            array.len().checked_sub(idx.unsigned_abs() as usize)?
+        } else {
+            idx as usize
+        };
+        array.get(idx).map(JsonbRef)
+    }
+
+    /// Follows a path of object keys and array indexes, as `#>` does. Returns `None` if any of the
+    /// steps does not exist.
+    pub fn access_path<'b>(self, path: impl IntoIterator<Item = &'b str>) -> Option<JsonbRef<'a>> {
+        path.into_iter()
+            .try_fold(self, |value, step| match value.0 {
+                Value::Object(_) => value.access_object_field(step),
+                Value::Array(_) => value.access_array_element(step.trim().parse().ok()?),
+                _ => None,
+            })
+    }
+
+    /// Converts the value to text, as `->>` and `#>>` do. Strings are unquoted and a json `null`
+    /// becomes a SQL `NULL`.
+    pub fn to_text(self) -> Option<String> {
+        match self.0 {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            _ => Some(self.to_string()),
+        }
+    }
+
+    /// Returns whether `self` contains `other`, as `@>` does.
+    ///
+    /// An object contains another one if it has all of its keys and each value contains the
+    /// corresponding one. An array contains another one if every element of the latter is
+    /// contained by some element of the former. As a special case, a top-level array contains a
+    /// primitive value if it has the value as an element.
+    pub fn contains(self, other: JsonbRef<'_>) -> bool {
+        match (self.0, other.0) {
+            (Value::Array(_), Value::Array(_) | Value::Object(_)) => {
+                value_contains(self.0, other.0)
+            }
+            (Value::Array(array), _) => array.iter().any(|elem| value_eq(elem, other.0)),
+            _ => value_contains(self.0, other.0),
+        }
+    }
+
+    /// Returns whether the string exists as a top-level key of an object or a string element of an
+    /// array, as `?` does.
+    pub fn exists(self, key: &str) -> bool {
+        match self.0 {
+            Value::Object(object) => object.contains_key(key),
+            Value::Array(array) => array.iter().any(|elem| elem.as_str() == Some(key)),
+            Value::String(s) => s == key,
+            _ => false,
+        }
+    }
+
+    /// Returns the elements of an array, as `jsonb_array_elements` does.
+    pub fn array_elements(self) -> Result<impl Iterator<Item = JsonbRef<'a>>> {
+        let array = self.0.as_array().ok_or_else(|| {
+            ErrorCode::InvalidParameterValue(format!(
+                "cannot extract elements from a jsonb {}",
+                self.type_name()
+            ))
+        })?;
+        Ok(array.iter().map(JsonbRef))
+    }
+
+    /// Returns the key-value pairs of an object, as `jsonb_each` does.
+    pub fn object_key_values(self) -> Result<impl Iterator<Item = (&'a str, JsonbRef<'a>)>> {
+        let object = self.0.as_object().ok_or_else(|| {
+            ErrorCode::InvalidParameterValue(format!(
+                "cannot call jsonb_each on a jsonb {}",
+                self.type_name()
+            ))
+        })?;
+        Ok(object.iter().map(|(k, v)| (k.as_str(), JsonbRef(v))))
+    }
+
+    /// Encodes the value as compact json text, which is used by the value encoding.
+    pub fn value_serialize(self) -> Vec<u8> {
+        serde_json::to_vec(self.0).unwrap()
+    }
+
+    /// Serializes the value into a memcomparable encoding, which is consistent with the order of
+    /// jsonb values and can be decoded back.
+    pub fn memcmp_serialize(
+        self,
+        ser: &mut memcomparable::Serializer<impl BufMut>,
+    ) -> memcomparable::Result<()> {
+        memcmp_serialize_value(self.0, ser)
+    }
+}
+
+/// The rank of each kind of json values in the order of jsonb values.
+fn kind_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::String(_) => 1,
+        Value::Number(_) => 2,
+        Value::Bool(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+/// A json number, whose integers are kept exactly instead of being converted to `f64`.
+#[derive(Clone, Copy)]
+enum JsonNumber {
+    Int(i128),
+    Float(f64),
+}
+
+impl JsonNumber {
+    fn new(number: &serde_json::Number) -> Self {
+        if let Some(i) = number.as_i64() {
+            Self::Int(i as i128)
+        } else if let Some(u) = number.as_u64() {
+            Self::Int(u as i128)
+        } else {
+            // Normalize `-0` to `0`.
+            Self::Float(number.as_f64().unwrap_or_default() + 0.0)
+        }
+    }
+
+    /// The nearest `f64`, which orders the numbers except for the integers rounded to the same
+    /// `f64`.
+    fn approx(self) -> f64 {
+        match self {
+            Self::Int(i) => i as f64,
+            Self::Float(f) => f,
+        }
+    }
+
+    /// The exact integer value of an integer or an integral float, which breaks the ties of
+    /// [`Self::approx`]. Only an integral float can be equal to an integer.
+    fn exact_int(self) -> Option<i128> {
+        match self {
+            Self::Int(i) => Some(i),
+            Self::Float(f) if f.fract() == 0.0 => Some(f as i128),
+            Self::Float(_) => None,
+        }
+    }
+
+    fn to_value(self) -> Value {
+        match self {
+            Self::Int(i) => match i64::try_from(i) {
+                Ok(i) => Value::from(i),
+                Err(_) => Value::from(i as u64),
+            },
+            Self::Float(f) => Value::from(f),
+        }
+    }
+}
+
+impl Ord for JsonNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // An integer is rounded to the nearest `f64`, which keeps the order against any `f64`
+        // unless they are equal, in which case both are integral.
+        self.approx()
+            .total_cmp(&other.approx())
+            .then_with(|| self.exact_int().cmp(&other.exact_int()))
+    }
+}
+
+impl PartialOrd for JsonNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for JsonNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for JsonNumber {}
+
+impl Hash for JsonNumber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Integral floats are hashed as the integers they equal.
+        match self.exact_int() {
+            Some(i) => i.hash(state),
+            None => self.approx().to_bits().hash(state),
+        }
+    }
+}
+
+fn cmp_value(a: &Value, b: &Value) -> Ordering {
+    fn cmp_elements(mut orderings: impl Iterator<Item = Ordering>) -> Ordering {
+        orderings.find(|ord| ord.is_ne()).unwrap_or(Ordering::Equal)
+    }
+
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => JsonNumber::new(a).cmp(&JsonNumber::new(b)),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a
+            .len()
+            .cmp(&b.len())
+            .then_with(|| cmp_elements(a.iter().zip(b).map(|(a, b)| cmp_value(a, b)))),
+        (Value::Object(a), Value::Object(b)) => a.len().cmp(&b.len()).then_with(|| {
+            cmp_elements(
+                a.iter()
+                    .zip(b)
+                    .map(|((ka, va), (kb, vb))| ka.cmp(kb).then_with(|| cmp_value(va, vb))),
+            )
+        }),
+        _ => kind_rank(a).cmp(&kind_rank(b)),
+    }
+}
+
+fn value_eq(a: &Value, b: &Value) -> bool {
+    cmp_value(a, b).is_eq()
+}
+
+fn value_contains(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => b
+            .iter()
+            .all(|(k, vb)| a.get(k).map_or(false, |va| value_contains(va, vb))),
+        (Value::Array(a), Value::Array(b)) => {
+            b.iter().all(|vb| a.iter().any(|va| value_contains(va, vb)))
+        }
+        (Value::Array(_) | Value::Object(_), _) | (_, Value::Array(_) | Value::Object(_)) => false,
+        _ => value_eq(a, b),
+    }
+}
+
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    kind_rank(value).hash(state);
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => b.hash(state),
+        Value::Number(n) => JsonNumber::new(n).hash(state),
+        Value::String(s) => s.hash(state),
+        Value::Array(array) => {
+            array.len().hash(state);
+            array.iter().for_each(|v| hash_value(v, state));
+        }
+        Value::Object(object) => {
+            object.len().hash(state);
+            object.iter().for_each(|(k, v)| {
+                k.hash(state);
+                hash_value(v, state);
+            });
+        }
+    }
+}
+
+/// The memcomparable encoding of a value is its kind rank followed by its content. Numbers are
+/// encoded as their nearest `f64`, the exact integer value breaking its ties and whether they are
+/// integers or floats, so that they are decoded exactly. Arrays and objects are prefixed by their
+/// length.
+fn memcmp_serialize_value(
+    value: &Value,
+    ser: &mut memcomparable::Serializer<impl BufMut>,
+) -> memcomparable::Result<()> {
+    kind_rank(value).serialize(&mut *ser)?;
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => b.serialize(&mut *ser)?,
+        Value::Number(n) => {
+            let number = JsonNumber::new(n);
+            number.approx().serialize(&mut *ser)?;
+            // The `i128` is encoded as its high and low 64 bits, which keeps its order.
+            let exact = number.exact_int().unwrap_or_default();
+            ((exact >> 64) as i64).serialize(&mut *ser)?;
+            (exact as u64).serialize(&mut *ser)?;
+            matches!(number, JsonNumber::Float(_)).serialize(&mut *ser)?;
+        }
+        Value::String(s) => s.serialize(&mut *ser)?,
+        Value::Array(array) => {
+            (array.len() as u32).serialize(&mut *ser)?;
+            for v in array {
+                memcmp_serialize_value(v, ser)?;
+            }
+        }
+        Value::Object(object) => {
+            (object.len() as u32).serialize(&mut *ser)?;
+            for (k, v) in object {
+                k.serialize(&mut *ser)?;
+                memcmp_serialize_value(v, ser)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn memcmp_deserialize_value(
+    de: &mut memcomparable::Deserializer<impl Buf>,
+) -> memcomparable::Result<Value> {
+    let rank = u8::deserialize(&mut *de)?;
+    Ok(match rank {
+        0 => Value::Null,
+        1 => Value::String(String::deserialize(&mut *de)?),
+        2 => {
+            let approx = f64::deserialize(&mut *de)?;
+            let high = i64::deserialize(&mut *de)?;
+            let low = u64::deserialize(&mut *de)?;
+            let number = match bool::deserialize(&mut *de)? {
+                true => JsonNumber::Float(approx),
+                false => JsonNumber::Int(((high as i128) << 64) | low as i128),
+            };
+            number.to_value()
+        }
+        3 => Value::Bool(bool::deserialize(&mut *de)?),
+        4 => {
+            let len = u32::deserialize(&mut *de)?;
+            let array = (0..len)
+                .map(|_| memcmp_deserialize_value(de))
+                .collect::<memcomparable::Result<Vec<_>>>()?;
+            Value::Array(array)
+        }
+        5 => {
+            let len = u32::deserialize(&mut *de)?;
+            let mut object = Map::new();
+            for _ in 0..len {
+                let k = String::deserialize(&mut *de)?;
+                let v = memcmp_deserialize_value(de)?;
+                object.insert(k, v);
+            }
+            Value::Object(object)
+        }
+        _ => return Err(memcomparable::Error::InvalidTagEncoding(rank as _)),
+    })
+}
+
+impl PartialEq for JsonbRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        value_eq(self.0, other.0)
+    }
+}
+
+impl Eq for JsonbRef<'_> {}
+
+impl PartialOrd for JsonbRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_value(self.0, other.0)
+    }
+}
+
+impl Hash for JsonbRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(self.0, state)
+    }
+}
+
+impl PartialEq for JsonbVal {
+    fn eq(&self, other: &Self) -> bool {
+        value_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for JsonbVal {}
+
+impl PartialOrd for JsonbVal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbVal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_value(&self.0, &other.0)
+    }
+}
+
+impl Hash for JsonbVal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(&self.0, state)
+    }
+}
+
+/// Formats json the way Postgres displays jsonb values, with a space after each `,` and `:`.
+struct PgFormatter;
+
+impl serde_json::ser::Formatter for PgFormatter {
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+}
+
+impl fmt::Display for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = Vec::new();
+        let mut ser = serde_json::Serializer::with_formatter(&mut buf, PgFormatter);
+        self.0.serialize(&mut ser).map_err(|_| fmt::Error)?;
+        f.write_str(std::str::from_utf8(&buf).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Display for JsonbVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        JsonbRef(&self.0).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn jsonb(s: &str) -> JsonbVal {
+        s.parse().unwrap()
+    }
+
+    fn jsonb_ref(v: &JsonbVal) -> JsonbRef<'_> {
+        JsonbRef(v.value())
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            jsonb(r#"{"b":[1,2.5,null],"a":"x"}"#).to_string(),
+            r#"{"a": "x", "b": [1, 2.5, null]}"#
+        );
+        assert!("{a: 1}".parse::<JsonbVal>().is_err());
+    }
+
+    #[test]
+    fn test_access() {
+        let v = jsonb(r#"{"a": {"b": [10, 20, {"c": "x"}]}, "n": null}"#);
+        let v = jsonb_ref(&v);
+        assert_eq!(
+            v.access_object_field("a").unwrap().to_string(),
+            r#"{"b": [10, 20, {"c": "x"}]}"#
+        );
+        assert!(v.access_object_field("z").is_none());
+        assert!(v.access_array_element(0).is_none());
+        let array = v.access_path(["a", "b"]).unwrap();
+        assert_eq!(array.access_array_element(1).unwrap().to_string(), "20");
+        assert_eq!(
+            array.access_array_element(-1).unwrap().to_string(),
+            r#"{"c": "x"}"#
+        );
+        assert!(array.access_array_element(-4).is_none());
+        assert_eq!(
+            v.access_path(["a", "b", "2", "c"])
+                .unwrap()
+                .to_text()
+                .unwrap(),
+            "x"
+        );
+        assert!(v.access_path(["a", "b", "x"]).is_none());
+        assert!(v.access_object_field("n").unwrap().to_text().is_none());
+    }
+
+    #[test]
+    fn test_contains_and_exists() {
+        let contains = |a: &str, b: &str| jsonb_ref(&jsonb(a)).contains(jsonb_ref(&jsonb(b)));
+        assert!(contains(r#"{"a": 1, "b": [1, 2]}"#, r#"{"b": [2]}"#));
+        assert!(!contains(r#"{"a": 1, "b": [1, 2]}"#, r#"{"b": 2}"#));
+        assert!(contains("[1, 2, [3, 4]]", "[[4], 1]"));
+        assert!(contains("[1, 2]", "1.0"));
+        assert!(!contains("[[1, 2]]", "[1]"));
+        assert!(contains(r#""a""#, r#""a""#));
+
+        let v = jsonb(r#"{"a": 1, "b": ["c"]}"#);
+        assert!(jsonb_ref(&v).exists("a"));
+        assert!(!jsonb_ref(&v).exists("c"));
+        assert!(jsonb_ref(&jsonb(r#"["a", 1]"#)).exists("a"));
+    }
+
+    #[test]
+    fn test_order_and_memcomparable() {
+        let values = [
+            "null",
+            r#""""#,
+            r#""b""#,
+            "-9223372036854775808",
+            "-1.5",
+            "0",
+            "2",
+            "2.5",
+            "9007199254740992",
+            "9007199254740993",
+            "18446744073709551615",
+            "1e300",
+            "false",
+            "true",
+            "[]",
+            "[3]",
+            r#"["a", 1]"#,
+            "[1, 2]",
+            "{}",
+            r#"{"a": 2}"#,
+            r#"{"b": 1}"#,
+            r#"{"a": 1, "b": 1}"#,
+        ]
+        .into_iter()
+        .map(jsonb)
+        .collect_vec();
+        assert!(values.is_sorted());
+        assert_eq!(jsonb("1"), jsonb("1.0"));
+        assert_eq!(jsonb("-0"), jsonb("0"));
+
+        // Integers beyond the precision of `f64` are compared exactly.
+        let big = jsonb("9007199254740993");
+        assert!(jsonb("9007199254740992") < big);
+        assert!(big < jsonb("9007199254740994"));
+        assert_eq!(jsonb("9007199254740992"), jsonb("9007199254740992.0"));
+        assert!(jsonb("9007199254740992.0") < big);
+        assert!(jsonb("-9223372036854775808") < jsonb("18446744073709551615"));
+        let hash = |v: &JsonbVal| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&jsonb("2")), hash(&jsonb("2.0")));
+        assert_ne!(hash(&big), hash(&jsonb("9007199254740992")));
+
+        let encoded = values
+            .iter()
+            .map(|v| {
+                let mut ser = memcomparable::Serializer::new(vec![]);
+                jsonb_ref(v).memcmp_serialize(&mut ser).unwrap();
+                ser.into_inner()
+            })
+            .collect_vec();
+        assert!(encoded.is_sorted());
+        for (v, bytes) in values.iter().zip_eq(&encoded) {
+            let mut de = memcomparable::Deserializer::new(bytes.as_slice());
+            assert_eq!(&JsonbVal::memcmp_deserialize(&mut de).unwrap(), v);
+        }
+
+        let v = jsonb(r#"{"a": [1, "x", null]}"#);
+        let decoded = JsonbVal::value_deserialize(&jsonb_ref(&v).value_serialize()).unwrap();
+        assert_eq!(decoded, v);
+    }
+}
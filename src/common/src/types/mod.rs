@@ -32,12 +32,16 @@ pub use native_type::*;
 use risingwave_pb::data::data_type::IntervalType::*;
 use risingwave_pb::data::data_type::{IntervalType, TypeName};
 pub use scalar_impl::*;
+mod bytea;
 mod chrono_wrapper;
 mod decimal;
 pub mod interval;
+mod jsonb;
 
 mod ordered_float;
+mod uuid_wrapper;
 
+pub use bytea::{ByteaRef, ByteaVal};
 use chrono::{Datelike, Timelike};
 pub use chrono_wrapper::{
     NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, UNIX_EPOCH_DAYS,
@@ -45,11 +49,13 @@ pub use chrono_wrapper::{
 pub use decimal::Decimal;
 pub use interval::*;
 use itertools::Itertools;
+pub use jsonb::{JsonbRef, JsonbVal};
 pub use ops::CheckedAdd;
 pub use ordered_float::IntoOrdered;
 use paste::paste;
 use prost::Message;
 use risingwave_pb::expr::StructValue as ProstStructValue;
+pub use uuid_wrapper::UuidWrapper;
 
 use crate::array::{
     read_interval_unit, ArrayBuilderImpl, ListRef, ListValue, PrimitiveArrayItemType, StructRef,
@@ -60,7 +66,7 @@ use crate::error::ErrorCode::InternalError;
 pub type OrderedF32 = ordered_float::OrderedFloat<f32>;
 pub type OrderedF64 = ordered_float::OrderedFloat<f64>;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub enum DataType {
     Boolean,
     Int16,
@@ -68,15 +74,58 @@ pub enum DataType {
     Int64,
     Float32,
     Float64,
-    Decimal,
+    /// `NUMERIC` with an optional `(precision, scale)`. Values of a constrained numeric are
+    /// rounded to `scale` fractional digits and may have at most `precision` digits in total.
+    Decimal(Option<(u32, u32)>),
     Date,
     Varchar,
     Time,
     Timestamp,
     Timestampz,
     Interval,
-    Struct { fields: Arc<[DataType]> },
-    List { datatype: Box<DataType> },
+    Jsonb,
+    Bytea,
+    Uuid,
+    Struct {
+        fields: Arc<[DataType]>,
+    },
+    List {
+        datatype: Box<DataType>,
+    },
+}
+
+/// An unconstrained `NUMERIC` is displayed as `Decimal`, and a constrained one as
+/// `Decimal(precision, scale)`.
+impl Debug for DataType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataType::Boolean => write!(f, "Boolean"),
+            DataType::Int16 => write!(f, "Int16"),
+            DataType::Int32 => write!(f, "Int32"),
+            DataType::Int64 => write!(f, "Int64"),
+            DataType::Float32 => write!(f, "Float32"),
+            DataType::Float64 => write!(f, "Float64"),
+            DataType::Decimal(None) => write!(f, "Decimal"),
+            DataType::Decimal(Some((precision, scale))) => {
+                write!(f, "Decimal({}, {})", precision, scale)
+            }
+            DataType::Date => write!(f, "Date"),
+            DataType::Varchar => write!(f, "Varchar"),
+            DataType::Time => write!(f, "Time"),
+            DataType::Timestamp => write!(f, "Timestamp"),
+            DataType::Timestampz => write!(f, "Timestampz"),
+            DataType::Interval => write!(f, "Interval"),
+            DataType::Jsonb => write!(f, "Jsonb"),
+            DataType::Bytea => write!(f, "Bytea"),
+            DataType::Uuid => write!(f, "Uuid"),
+            DataType::Struct { fields } => {
+                f.debug_struct("Struct").field("fields", fields).finish()
+            }
+            DataType::List { datatype } => {
+                f.debug_struct("List").field("datatype", datatype).finish()
+            }
+        }
+    }
 }
 
 const DECIMAL_DEFAULT_PRECISION: u32 = 20;
@@ -104,8 +153,15 @@ impl From<&ProstDataType> for DataType {
             TypeName::Time => DataType::Time,
             TypeName::Timestamp => DataType::Timestamp,
             TypeName::Timestampz => DataType::Timestampz,
-            TypeName::Decimal => DataType::Decimal,
+            TypeName::Decimal => DataType::Decimal(if proto.precision == 0 {
+                None
+            } else {
+                Some((proto.precision, proto.scale))
+            }),
             TypeName::Interval => DataType::Interval,
+            TypeName::Jsonb => DataType::Jsonb,
+            TypeName::Bytea => DataType::Bytea,
+            TypeName::Uuid => DataType::Uuid,
             TypeName::Struct => {
                 let fields: Vec<DataType> = proto.field_type.iter().map(|f| f.into()).collect_vec();
                 DataType::Struct {
@@ -130,13 +186,16 @@ impl DataType {
             DataType::Int64 => PrimitiveArrayBuilder::<i64>::new(capacity)?.into(),
             DataType::Float32 => PrimitiveArrayBuilder::<OrderedF32>::new(capacity)?.into(),
             DataType::Float64 => PrimitiveArrayBuilder::<OrderedF64>::new(capacity)?.into(),
-            DataType::Decimal => DecimalArrayBuilder::new(capacity)?.into(),
+            DataType::Decimal(_) => DecimalArrayBuilder::new(capacity)?.into(),
             DataType::Date => NaiveDateArrayBuilder::new(capacity)?.into(),
             DataType::Varchar => Utf8ArrayBuilder::new(capacity)?.into(),
             DataType::Time => NaiveTimeArrayBuilder::new(capacity)?.into(),
             DataType::Timestamp => NaiveDateTimeArrayBuilder::new(capacity)?.into(),
             DataType::Timestampz => PrimitiveArrayBuilder::<i64>::new(capacity)?.into(),
             DataType::Interval => IntervalArrayBuilder::new(capacity)?.into(),
+            DataType::Jsonb => JsonbArrayBuilder::new(capacity)?.into(),
+            DataType::Bytea => BytesArrayBuilder::new(capacity)?.into(),
+            DataType::Uuid => UuidArrayBuilder::new(capacity)?.into(),
             DataType::Struct { fields } => StructArrayBuilder::with_meta(
                 capacity,
                 ArrayMeta::Struct {
@@ -167,8 +226,11 @@ impl DataType {
             DataType::Time => TypeName::Time,
             DataType::Timestamp => TypeName::Timestamp,
             DataType::Timestampz => TypeName::Timestampz,
-            DataType::Decimal => TypeName::Decimal,
+            DataType::Decimal(_) => TypeName::Decimal,
             DataType::Interval => TypeName::Interval,
+            DataType::Jsonb => TypeName::Jsonb,
+            DataType::Bytea => TypeName::Bytea,
+            DataType::Uuid => TypeName::Uuid,
             DataType::Struct { .. } => TypeName::Struct,
             DataType::List { .. } => TypeName::List,
         }
@@ -180,10 +242,17 @@ impl DataType {
             DataType::List { datatype } => vec![datatype.to_protobuf()],
             _ => vec![],
         };
+        // A precision of 0 denotes an unconstrained numeric.
+        let (precision, scale) = match self {
+            DataType::Decimal(Some((precision, scale))) => (*precision, *scale),
+            _ => (0, 0),
+        };
         ProstDataType {
             type_name: self.prost_type_name() as i32,
             is_nullable: true,
             field_type,
+            precision,
+            scale,
             ..Default::default()
         }
     }
@@ -197,13 +266,16 @@ impl DataType {
             DataType::Int64 => DataSize::Fixed(size_of::<i64>()),
             DataType::Float32 => DataSize::Fixed(size_of::<OrderedF32>()),
             DataType::Float64 => DataSize::Fixed(size_of::<OrderedF64>()),
-            DataType::Decimal => DataSize::Fixed(16),
+            DataType::Decimal(_) => DataSize::Fixed(16),
             DataType::Varchar => DataSize::Variable,
             DataType::Date => DataSize::Fixed(size_of::<NaiveDateWrapper>()),
             DataType::Time => DataSize::Fixed(size_of::<NaiveTimeWrapper>()),
             DataType::Timestamp => DataSize::Fixed(size_of::<NaiveDateTimeWrapper>()),
             DataType::Timestampz => DataSize::Fixed(size_of::<NaiveDateTimeWrapper>()),
             DataType::Interval => DataSize::Variable,
+            DataType::Jsonb => DataSize::Variable,
+            DataType::Bytea => DataSize::Variable,
+            DataType::Uuid => DataSize::Fixed(size_of::<UuidWrapper>()),
             DataType::Struct { .. } => DataSize::Variable,
             DataType::List { .. } => DataSize::Variable,
         }
@@ -217,7 +289,7 @@ impl DataType {
                 | DataType::Int64
                 | DataType::Float32
                 | DataType::Float64
-                | DataType::Decimal
+                | DataType::Decimal(_)
         )
     }
 }
@@ -295,6 +367,9 @@ macro_rules! for_all_scalar_variants {
             { NaiveDate, naivedate, NaiveDateWrapper, NaiveDateWrapper },
             { NaiveDateTime, naivedatetime, NaiveDateTimeWrapper, NaiveDateTimeWrapper },
             { NaiveTime, naivetime, NaiveTimeWrapper, NaiveTimeWrapper },
+            { Jsonb, jsonb, JsonbVal, JsonbRef<'scalar> },
+            { Bytea, bytea, ByteaVal, ByteaRef<'scalar> },
+            { Uuid, uuid, UuidWrapper, UuidWrapper },
             { Struct, struct, StructValue, StructRef<'scalar> },
             { List, list, ListValue, ListRef<'scalar> }
         }
@@ -567,6 +642,9 @@ impl std::hash::Hash for ScalarImpl {
                     Self::NaiveDate(naivedate) => naivedate.hash(state),
                     Self::NaiveDateTime(naivedatetime) => naivedatetime.hash(state),
                     Self::NaiveTime(naivetime) => naivetime.hash(state),
+                    Self::Jsonb(v) => v.hash(state),
+                    Self::Bytea(v) => v.hash(state),
+                    Self::Uuid(v) => v.hash(state),
                     Self::Struct(v) => v.hash(state),
                     Self::List(v) => v.hash(state),
                 }
@@ -640,6 +718,9 @@ impl ScalarRefImpl<'_> {
             &Self::NaiveTime(v) => {
                 ser.serialize_naivetime(v.0.num_seconds_from_midnight(), v.0.nanosecond())?
             }
+            &Self::Jsonb(v) => v.memcmp_serialize(ser)?,
+            &Self::Bytea(v) => v.memcmp_serialize(ser)?,
+            &Self::Uuid(v) => v.memcmp_serialize(ser)?,
             _ => {
                 panic!("Type is unable to be serialized.")
             }
@@ -671,7 +752,7 @@ impl ScalarImpl {
             Ty::Float64 => Self::Float64(f64::deserialize(de)?.into()),
             Ty::Varchar => Self::Utf8(String::deserialize(de)?),
             Ty::Boolean => Self::Bool(bool::deserialize(de)?),
-            Ty::Decimal => Self::Decimal({
+            Ty::Decimal(_) => Self::Decimal({
                 let (mantissa, scale) = de.deserialize_decimal()?;
                 match scale {
                     29 => Decimal::NegativeINF,
@@ -694,6 +775,9 @@ impl ScalarImpl {
                 let days = de.deserialize_naivedate()?;
                 NaiveDateWrapper::with_days(days)?
             }),
+            Ty::Jsonb => Self::Jsonb(JsonbVal::memcmp_deserialize(de)?),
+            Ty::Bytea => Self::Bytea(ByteaVal::memcmp_deserialize(de)?),
+            Ty::Uuid => Self::Uuid(UuidWrapper::memcmp_deserialize(de)?),
             _ => {
                 panic!("Type is unable to be deserialized.")
             }
@@ -714,6 +798,9 @@ impl ScalarImpl {
             ScalarImpl::NaiveDate(_) => todo!(),
             ScalarImpl::NaiveDateTime(_) => todo!(),
             ScalarImpl::NaiveTime(_) => todo!(),
+            ScalarImpl::Jsonb(v) => v.as_scalar_ref().value_serialize(),
+            ScalarImpl::Bytea(v) => v.as_bytes().to_vec(),
+            ScalarImpl::Uuid(v) => v.0.as_bytes().to_vec(),
             ScalarImpl::Struct(v) => v.to_protobuf_owned(),
             ScalarImpl::List(_) => todo!(),
         };
@@ -770,6 +857,12 @@ impl ScalarImpl {
                 b,
                 data_type.get_interval_type()?,
             )?),
+            TypeName::Jsonb => ScalarImpl::Jsonb(
+                JsonbVal::value_deserialize(b)
+                    .ok_or_else(|| InternalError("Failed to deserialize jsonb".to_string()))?,
+            ),
+            TypeName::Bytea => ScalarImpl::Bytea(b.as_slice().into()),
+            TypeName::Uuid => ScalarImpl::Uuid(UuidWrapper::from_protobuf(b)?),
             TypeName::Struct => {
                 let struct_value: ProstStructValue = Message::decode(b.as_slice())?;
                 let fields: Vec<Datum> = struct_value
@@ -261,6 +261,84 @@ impl<'a> ScalarRef<'a> for NaiveTimeWrapper {
     }
 }
 
+/// Implement `Scalar` for `JsonbVal`.
+impl Scalar for JsonbVal {
+    type ScalarRefType<'a> = JsonbRef<'a>;
+
+    fn as_scalar_ref(&self) -> JsonbRef<'_> {
+        self.value().into()
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Jsonb(self)
+    }
+}
+
+/// Implement `ScalarRef` for `JsonbRef`.
+impl<'a> ScalarRef<'a> for JsonbRef<'a> {
+    type ScalarType = JsonbVal;
+
+    fn to_owned_scalar(&self) -> JsonbVal {
+        self.value().clone().into()
+    }
+}
+
+impl ScalarPartialOrd for JsonbVal {
+    fn scalar_cmp(&self, other: JsonbRef<'_>) -> Option<std::cmp::Ordering> {
+        Some(self.as_scalar_ref().cmp(&other))
+    }
+}
+
+/// Implement `Scalar` for `ByteaVal`.
+impl Scalar for ByteaVal {
+    type ScalarRefType<'a> = ByteaRef<'a>;
+
+    fn as_scalar_ref(&self) -> ByteaRef<'_> {
+        self.as_bytes().into()
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Bytea(self)
+    }
+}
+
+/// Implement `ScalarRef` for `ByteaRef`.
+impl<'a> ScalarRef<'a> for ByteaRef<'a> {
+    type ScalarType = ByteaVal;
+
+    fn to_owned_scalar(&self) -> ByteaVal {
+        self.as_bytes().into()
+    }
+}
+
+impl ScalarPartialOrd for ByteaVal {
+    fn scalar_cmp(&self, other: ByteaRef<'_>) -> Option<std::cmp::Ordering> {
+        Some(self.as_scalar_ref().cmp(&other))
+    }
+}
+
+/// Implement `Scalar` for `UuidWrapper`.
+impl Scalar for UuidWrapper {
+    type ScalarRefType<'a> = UuidWrapper;
+
+    fn as_scalar_ref(&self) -> UuidWrapper {
+        *self
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Uuid(self)
+    }
+}
+
+/// Implement `ScalarRef` for `UuidWrapper`.
+impl<'a> ScalarRef<'a> for UuidWrapper {
+    type ScalarType = UuidWrapper;
+
+    fn to_owned_scalar(&self) -> UuidWrapper {
+        *self
+    }
+}
+
 /// Implement `Scalar` for `StructValue`.
 impl<'a> ScalarRef<'a> for StructRef<'a> {
     type ScalarType = StructValue;
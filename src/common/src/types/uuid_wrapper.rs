@@ -0,0 +1,120 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::str::FromStr;
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::ErrorCode::{InternalError, IoError};
+use crate::error::{ErrorCode, Result, RwError};
+
+/// A wrapper of [`Uuid`], which is stored in a `UuidArray` as a fixed-size primitive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct UuidWrapper(pub Uuid);
+
+impl Default for UuidWrapper {
+    fn default() -> Self {
+        UuidWrapper(Uuid::nil())
+    }
+}
+
+impl UuidWrapper {
+    pub fn new(data: Uuid) -> Self {
+        UuidWrapper(data)
+    }
+
+    /// Writes the 16 bytes of the uuid in big-endian order.
+    pub fn to_protobuf<T: Write>(self, output: &mut T) -> Result<usize> {
+        output
+            .write(self.0.as_bytes())
+            .map_err(|e| RwError::from(IoError(e)))
+    }
+
+    pub fn from_protobuf(bytes: &[u8]) -> Result<Self> {
+        Uuid::from_slice(bytes).map(Self).map_err(|e| {
+            RwError::from(InternalError(format!(
+                "failed to read uuid from bytes: {}",
+                e
+            )))
+        })
+    }
+
+    /// Serializes the uuid as two big-endian `u64`s, which keeps the order of uuids.
+    pub fn memcmp_serialize(
+        self,
+        ser: &mut memcomparable::Serializer<impl BufMut>,
+    ) -> memcomparable::Result<()> {
+        let v = self.0.as_u128();
+        ((v >> 64) as u64).serialize(&mut *ser)?;
+        (v as u64).serialize(ser)
+    }
+
+    pub fn memcmp_deserialize(
+        de: &mut memcomparable::Deserializer<impl Buf>,
+    ) -> memcomparable::Result<Self> {
+        let hi = u64::deserialize(&mut *de)?;
+        let lo = u64::deserialize(de)?;
+        Ok(Self(Uuid::from_u128((hi as u128) << 64 | lo as u128)))
+    }
+}
+
+impl FromStr for UuidWrapper {
+    type Err = RwError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Uuid::parse_str(s).map(Self).map_err(|_| {
+            ErrorCode::InvalidInputSyntax(format!("invalid input syntax for type uuid: \"{}\"", s))
+                .into()
+        })
+    }
+}
+
+impl Display for UuidWrapper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_text_and_memcmp() {
+        let a: UuidWrapper = "A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11".parse().unwrap();
+        assert_eq!(a.to_string(), "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+        let b: UuidWrapper = "a0eebc999c0b4ef8bb6d6bb9bd380a12".parse().unwrap();
+        assert!("a0eebc99-9c0b".parse::<UuidWrapper>().is_err());
+
+        let encode = |v: UuidWrapper| {
+            let mut ser = memcomparable::Serializer::new(vec![]);
+            v.memcmp_serialize(&mut ser).unwrap();
+            ser.into_inner()
+        };
+        assert!(a < b);
+        assert!(encode(a) < encode(b));
+        let bytes = encode(b);
+        let mut de = memcomparable::Deserializer::new(bytes.as_slice());
+        assert_eq!(UuidWrapper::memcmp_deserialize(&mut de).unwrap(), b);
+
+        let mut buf = vec![];
+        a.to_protobuf(&mut buf).unwrap();
+        assert_eq!(UuidWrapper::from_protobuf(&buf).unwrap(), a);
+    }
+}
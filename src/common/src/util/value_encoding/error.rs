@@ -26,6 +26,8 @@ pub enum ValueEncodingError {
     InvalidNaiveDateTimeEncoding(i64, u32),
     #[error("invalid NaiveTime value encoding: secs: {0} nano: {1}")]
     InvalidNaiveTimeEncoding(u32, u32),
+    #[error("Invalid jsonb value encoding")]
+    InvalidJsonbEncoding,
     #[error("Invalid null tag value encoding: {0}")]
     InvalidTagEncoding(u8),
 }
@@ -20,8 +20,9 @@ use chrono::{Datelike, Timelike};
 
 use crate::error::Result;
 use crate::types::{
-    DataType, Datum, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper,
-    NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarImpl, ScalarRefImpl,
+    ByteaVal, DataType, Datum, Decimal, IntervalUnit, JsonbVal, NaiveDateTimeWrapper,
+    NaiveDateWrapper, NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarImpl, ScalarRefImpl,
+    UuidWrapper,
 };
 
 pub mod error;
@@ -86,6 +87,9 @@ fn serialize_value(value: ScalarRefImpl, mut buf: impl BufMut) {
         ScalarRefImpl::NaiveTime(v) => {
             serialize_naivetime(v.0.num_seconds_from_midnight(), v.0.nanosecond(), buf)
         }
+        ScalarRefImpl::Jsonb(v) => serialize_str(&v.value_serialize(), buf),
+        ScalarRefImpl::Bytea(v) => serialize_str(v.as_bytes(), buf),
+        ScalarRefImpl::Uuid(v) => buf.put_slice(v.0.as_bytes()),
         ScalarRefImpl::Struct(StructRef::ValueRef { val }) => {
             serialize_struct(val.to_protobuf_owned(), buf);
         }
@@ -138,12 +142,15 @@ fn deserialize_value(ty: &DataType, mut data: impl Buf) -> Result<Datum> {
         DataType::Float64 => ScalarImpl::Float64(OrderedF64::from(data.get_f64_le())),
         DataType::Varchar => ScalarImpl::Utf8(deserialize_str(data)?),
         DataType::Boolean => ScalarImpl::Bool(deserialize_bool(data)?),
-        DataType::Decimal => ScalarImpl::Decimal(deserialize_decimal(data)?),
+        DataType::Decimal(_) => ScalarImpl::Decimal(deserialize_decimal(data)?),
         DataType::Interval => ScalarImpl::Interval(deserialize_interval(data)?),
         DataType::Time => ScalarImpl::NaiveTime(deserialize_naivetime(data)?),
         DataType::Timestamp => ScalarImpl::NaiveDateTime(deserialize_naivedatetime(data)?),
         DataType::Timestampz => ScalarImpl::Int64(data.get_i64_le()),
         DataType::Date => ScalarImpl::NaiveDate(deserialize_naivedate(data)?),
+        DataType::Jsonb => ScalarImpl::Jsonb(deserialize_jsonb(data)?),
+        DataType::Bytea => ScalarImpl::Bytea(deserialize_bytea(data)),
+        DataType::Uuid => ScalarImpl::Uuid(deserialize_uuid(data)),
         DataType::Struct { fields: _ } => deserialize_struct(ty, data)?,
         _ => {
             panic!("Type is unable to be deserialized.")
@@ -165,6 +172,26 @@ fn deserialize_str(mut data: impl Buf) -> Result<String> {
     Ok(String::from_utf8(bytes).map_err(ValueEncodingError::InvalidUtf8)?)
 }
 
+fn deserialize_jsonb(mut data: impl Buf) -> Result<JsonbVal> {
+    let len = data.get_u32_le();
+    let mut bytes = vec![0; len as usize];
+    data.copy_to_slice(&mut bytes);
+    Ok(JsonbVal::value_deserialize(&bytes).ok_or(ValueEncodingError::InvalidJsonbEncoding)?)
+}
+
+fn deserialize_bytea(mut data: impl Buf) -> ByteaVal {
+    let len = data.get_u32_le();
+    let mut bytes = vec![0; len as usize];
+    data.copy_to_slice(&mut bytes);
+    bytes.into()
+}
+
+fn deserialize_uuid(mut data: impl Buf) -> UuidWrapper {
+    let mut bytes = [0; 16];
+    data.copy_to_slice(&mut bytes);
+    UuidWrapper::new(uuid::Uuid::from_bytes(bytes))
+}
+
 fn deserialize_bool(mut data: impl Buf) -> Result<bool> {
     match data.get_u8() {
         1 => Ok(true),
@@ -204,6 +204,43 @@ impl StreamService for StreamServiceImpl {
 
         Ok(Response::new(DropSourceResponse { status: None }))
     }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn alter_source(
+        &self,
+        request: Request<AlterSourceRequest>,
+    ) -> Result<Response<AlterSourceResponse>, Status> {
+        use risingwave_pb::catalog::source::Info;
+
+        let source = request.into_inner().source.unwrap();
+        let id = TableId::new(source.id); // TODO: use SourceId instead
+
+        match source.get_info().map_err(tonic_err)? {
+            Info::StreamSource(info) => {
+                self.env
+                    .source_manager()
+                    .alter_source(&id, info.to_owned())
+                    .await
+                    .map_err(tonic_err)?;
+            }
+            Info::TableSource(info) => {
+                let columns = info
+                    .columns
+                    .iter()
+                    .cloned()
+                    .map(|c| c.column_desc.unwrap().into())
+                    .collect_vec();
+
+                self.env
+                    .source_manager()
+                    .alter_table_source(&id, columns)
+                    .map_err(tonic_err)?;
+            }
+        };
+        tracing::debug!(id = %id, "alter source");
+
+        Ok(Response::new(AlterSourceResponse { status: None }))
+    }
 }
 
 impl StreamServiceImpl {
@@ -147,12 +147,14 @@ async fn test_table_v2_materialize() -> Result<()> {
         Arc::new(StreamingMetrics::unused()),
         vec![],
         u64::MAX,
+        vec![],
     )?;
 
     // Create a `Materialize` to write the changes to storage
     let keyspace = Keyspace::table_root(memory_state_store.clone(), &source_table_id);
     let mut materialize = MaterializeExecutor::new(
         Box::new(stream_source),
+        source_table_id,
         keyspace.clone(),
         vec![OrderPair::new(0, OrderType::Ascending)],
         all_column_ids.clone(),
@@ -176,6 +178,7 @@ async fn test_table_v2_materialize() -> Result<()> {
     let insert = Box::new(InsertExecutor::new(
         source_table_id,
         source_manager.clone(),
+        vec![ColumnId::from(1)],
         insert_inner,
     ));
 
@@ -194,6 +197,7 @@ async fn test_table_v2_materialize() -> Result<()> {
             name: field.name,
             field_descs: vec![],
             type_name: "".to_string(),
+            default_value: None,
         })
         .collect_vec();
 
@@ -245,7 +249,7 @@ async fn test_table_v2_materialize() -> Result<()> {
             assert_eq!(col_data.value_at(0).unwrap(), 1.14.into_ordered());
             assert_eq!(col_data.value_at(1).unwrap(), 5.14.into_ordered());
         }
-        Message::Barrier(_) => panic!(),
+        _ => panic!(),
     }
 
     // Send a barrier and poll again, should write changes to storage
@@ -314,7 +318,7 @@ async fn test_table_v2_materialize() -> Result<()> {
             let col_data = c.columns()[1].array_ref().as_float64();
             assert_eq!(col_data.value_at(0).unwrap(), 1.14.into_ordered());
         }
-        Message::Barrier(_) => panic!(),
+        _ => panic!(),
     }
 
     // Send a barrier and poll again, should write changes to storage
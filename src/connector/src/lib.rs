@@ -40,6 +40,7 @@ mod kafka;
 pub mod kinesis;
 mod nexmark;
 mod pulsar;
+pub mod sink;
 
 pub use base::*;
 
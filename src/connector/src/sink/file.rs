@@ -0,0 +1,205 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::catalog::Schema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use super::{row_to_json, Sink, SinkFormat};
+
+pub const FILE_SINK: &str = "file";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FileSinkConfig {
+    #[serde(rename = "file.path")]
+    pub path: String,
+
+    #[serde(default)]
+    pub format: SinkFormat,
+}
+
+/// Appends rows to a local file as newline-delimited JSON. In append-only mode each line is a
+/// row. In upsert mode each line is `{"key": <pk>, "value": <row>}`, where a `null` value marks
+/// a deletion.
+///
+/// The frontend plans a file sink with a single actor, so that the lines are not interleaved with
+/// other actors appending to the same file.
+#[derive(Debug)]
+pub struct FileSink {
+    config: FileSinkConfig,
+    file: File,
+    schema: Schema,
+    pk_indices: Vec<usize>,
+    all_indices: Vec<usize>,
+    epoch: u64,
+    buffer: String,
+}
+
+impl FileSink {
+    pub async fn new(
+        config: FileSinkConfig,
+        schema: Schema,
+        pk_indices: Vec<usize>,
+    ) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)
+            .await
+            .map_err(|e| anyhow!("failed to open sink file {}: {}", config.path, e))?;
+        let all_indices = (0..schema.len()).collect();
+
+        Ok(Self {
+            config,
+            file,
+            schema,
+            pk_indices,
+            all_indices,
+            epoch: 0,
+            buffer: String::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for FileSink {
+    async fn write_batch(&mut self, chunk: StreamChunk) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            let value = row_to_json(&row, &self.schema, &self.all_indices);
+            let line = match self.config.format {
+                SinkFormat::AppendOnly => match op {
+                    Op::Insert => value,
+                    _ => {
+                        return Err(anyhow!(
+                            "append-only file sink received a retraction: {:?}",
+                            op
+                        ))
+                    }
+                },
+                SinkFormat::Upsert => {
+                    let key = row_to_json(&row, &self.schema, &self.pk_indices);
+                    match op {
+                        Op::Insert | Op::UpdateInsert => json!({ "key": key, "value": value }),
+                        Op::Delete | Op::UpdateDelete => json!({ "key": key, "value": null }),
+                    }
+                }
+            };
+            self.buffer.push_str(&line.to_string());
+            self.buffer.push('\n');
+        }
+        Ok(())
+    }
+
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        self.epoch = epoch;
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.file.write_all(self.buffer.as_bytes()).await?;
+        self.file.sync_data().await?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "v"),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_append_only_file_sink() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sink.json");
+        let config = FileSinkConfig {
+            path: path.to_str().unwrap().to_string(),
+            format: SinkFormat::AppendOnly,
+        };
+        let mut sink = FileSink::new(config, schema(), vec![0]).await.unwrap();
+
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            " I I
+            + 1 2
+            + 3 .",
+        ))
+        .await
+        .unwrap();
+        // Nothing is visible before commit.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+        sink.commit().await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"k\":1,\"v\":2}\n{\"k\":3,\"v\":null}\n"
+        );
+
+        sink.begin_epoch(2).await.unwrap();
+        assert!(sink
+            .write_batch(StreamChunk::from_pretty(
+                " I I
+                - 1 2",
+            ))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_file_sink() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sink.json");
+        let config = FileSinkConfig {
+            path: path.to_str().unwrap().to_string(),
+            format: SinkFormat::Upsert,
+        };
+        let mut sink = FileSink::new(config, schema(), vec![0]).await.unwrap();
+
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            "  I I
+            +  1 2
+            U- 1 2
+            U+ 1 3
+            -  1 3",
+        ))
+        .await
+        .unwrap();
+        sink.commit().await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"key\":{\"k\":1},\"value\":{\"k\":1,\"v\":2}}\n\
+             {\"key\":{\"k\":1},\"value\":null}\n\
+             {\"key\":{\"k\":1},\"value\":{\"k\":1,\"v\":3}}\n\
+             {\"key\":{\"k\":1},\"value\":null}\n"
+        );
+    }
+}
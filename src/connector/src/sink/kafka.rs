@@ -0,0 +1,158 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::ClientConfig;
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::catalog::Schema;
+use serde::Deserialize;
+
+use super::{row_to_json, Sink, SinkFormat};
+
+pub const KAFKA_SINK: &str = "kafka";
+
+const KAFKA_SINK_SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct KafkaSinkConfig {
+    #[serde(rename = "kafka.brokers")]
+    pub brokers: String,
+
+    #[serde(rename = "kafka.topic")]
+    pub topic: String,
+
+    #[serde(default)]
+    pub format: SinkFormat,
+}
+
+/// A record buffered until the end of the epoch. A record without payload is a tombstone.
+#[derive(Debug)]
+struct KafkaRecord {
+    key: Option<String>,
+    payload: Option<String>,
+}
+
+/// Writes rows as JSON messages into a Kafka topic. In upsert mode, messages are keyed by the
+/// primary key of the row and deletions are written as tombstones.
+pub struct KafkaSink {
+    config: KafkaSinkConfig,
+    producer: FutureProducer,
+    schema: Schema,
+    pk_indices: Vec<usize>,
+    all_indices: Vec<usize>,
+    epoch: u64,
+    buffer: Vec<KafkaRecord>,
+}
+
+impl KafkaSink {
+    pub fn new(config: KafkaSinkConfig, schema: Schema, pk_indices: Vec<usize>) -> Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", config.brokers.as_str())
+            .set("message.timeout.ms", "5000")
+            .create()
+            .map_err(|e| anyhow!("failed to create kafka producer: {}", e))?;
+        let all_indices = (0..schema.len()).collect();
+
+        Ok(Self {
+            config,
+            producer,
+            schema,
+            pk_indices,
+            all_indices,
+            epoch: 0,
+            buffer: vec![],
+        })
+    }
+}
+
+impl std::fmt::Debug for KafkaSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaSink")
+            .field("config", &self.config)
+            .field("pk_indices", &self.pk_indices)
+            .field("epoch", &self.epoch)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Sink for KafkaSink {
+    async fn write_batch(&mut self, chunk: StreamChunk) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            let payload = row_to_json(&row, &self.schema, &self.all_indices).to_string();
+            let record = match self.config.format {
+                SinkFormat::AppendOnly => match op {
+                    Op::Insert => KafkaRecord {
+                        key: None,
+                        payload: Some(payload),
+                    },
+                    _ => {
+                        return Err(anyhow!(
+                            "append-only kafka sink received a retraction: {:?}",
+                            op
+                        ))
+                    }
+                },
+                SinkFormat::Upsert => {
+                    let key = row_to_json(&row, &self.schema, &self.pk_indices).to_string();
+                    match op {
+                        Op::Insert | Op::UpdateInsert => KafkaRecord {
+                            key: Some(key),
+                            payload: Some(payload),
+                        },
+                        Op::Delete | Op::UpdateDelete => KafkaRecord {
+                            key: Some(key),
+                            payload: None,
+                        },
+                    }
+                }
+            };
+            self.buffer.push(record);
+        }
+        Ok(())
+    }
+
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        self.epoch = epoch;
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        let records = std::mem::take(&mut self.buffer);
+        let producer = &self.producer;
+        let topic = self.config.topic.as_str();
+
+        try_join_all(records.iter().map(|record| async move {
+            let mut future_record = FutureRecord::<str, str>::to(topic);
+            if let Some(key) = &record.key {
+                future_record = future_record.key(key.as_str());
+            }
+            if let Some(payload) = &record.payload {
+                future_record = future_record.payload(payload.as_str());
+            }
+            producer
+                .send(future_record, KAFKA_SINK_SEND_TIMEOUT)
+                .await
+                .map_err(|(e, _)| anyhow!("failed to deliver message to kafka: {}", e))
+        }))
+        .await?;
+
+        Ok(())
+    }
+}
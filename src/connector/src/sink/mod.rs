@@ -0,0 +1,174 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sinks write the changes of a materialized view into external systems. The `SinkExecutor` in
+//! the stream crate drives a [`SinkImpl`]: chunks are handed over with [`Sink::write_batch`], and
+//! buffered records are made visible in the external system with [`Sink::commit`] on each barrier.
+
+pub mod file;
+pub mod kafka;
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use risingwave_common::array::{RowRef, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DatumRef, ScalarRefImpl};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+pub use self::file::{FileSink, FileSinkConfig, FILE_SINK};
+pub use self::kafka::{KafkaSink, KafkaSinkConfig, KAFKA_SINK};
+
+const SINK_CONNECTOR_KEY: &str = "connector";
+
+#[async_trait]
+pub trait Sink {
+    /// Buffers the records in `chunk`. They become visible downstream on the next
+    /// [`Sink::commit`].
+    async fn write_batch(&mut self, chunk: StreamChunk) -> Result<()>;
+
+    /// Starts a new epoch. Records written afterwards belong to `epoch`.
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()>;
+
+    /// Flushes all records of the current epoch to the external system.
+    async fn commit(&mut self) -> Result<()>;
+}
+
+/// The way a sink represents the changes of its input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkFormat {
+    /// Only inserts are accepted. A retraction in the input results in an error.
+    #[default]
+    AppendOnly,
+    /// Records are keyed by the primary key of the input, and deletions are emitted as well.
+    Upsert,
+}
+
+#[derive(Clone, Debug)]
+pub enum SinkConfig {
+    Kafka(KafkaSinkConfig),
+    File(FileSinkConfig),
+}
+
+impl SinkConfig {
+    pub fn from_hashmap(mut properties: HashMap<String, String>) -> Result<Self> {
+        let connector = properties
+            .remove(SINK_CONNECTOR_KEY)
+            .ok_or_else(|| anyhow!("Must specify 'connector' in WITH clause"))?;
+        let json_value = serde_json::to_value(properties).map_err(|e| anyhow!(e))?;
+        match connector.to_lowercase().as_str() {
+            KAFKA_SINK => serde_json::from_value(json_value)
+                .map_err(|e| anyhow!(e.to_string()))
+                .map(SinkConfig::Kafka),
+            FILE_SINK => serde_json::from_value(json_value)
+                .map_err(|e| anyhow!(e.to_string()))
+                .map(SinkConfig::File),
+            _ => Err(anyhow!("sink connector '{}' is not supported", connector)),
+        }
+    }
+
+    pub fn format(&self) -> SinkFormat {
+        match self {
+            SinkConfig::Kafka(config) => config.format,
+            SinkConfig::File(config) => config.format,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SinkImpl {
+    Kafka(Box<KafkaSink>),
+    File(Box<FileSink>),
+}
+
+impl SinkImpl {
+    /// Creates a sink writing rows of `schema`. `pk_indices` identifies a row in upsert mode.
+    pub async fn new(config: SinkConfig, schema: Schema, pk_indices: Vec<usize>) -> Result<Self> {
+        if config.format() == SinkFormat::Upsert && pk_indices.is_empty() {
+            return Err(anyhow!("upsert sink requires a primary key"));
+        }
+        Ok(match config {
+            SinkConfig::Kafka(config) => {
+                SinkImpl::Kafka(Box::new(KafkaSink::new(config, schema, pk_indices)?))
+            }
+            SinkConfig::File(config) => {
+                SinkImpl::File(Box::new(FileSink::new(config, schema, pk_indices).await?))
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for SinkImpl {
+    async fn write_batch(&mut self, chunk: StreamChunk) -> Result<()> {
+        match self {
+            SinkImpl::Kafka(sink) => sink.write_batch(chunk).await,
+            SinkImpl::File(sink) => sink.write_batch(chunk).await,
+        }
+    }
+
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        match self {
+            SinkImpl::Kafka(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::File(sink) => sink.begin_epoch(epoch).await,
+        }
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        match self {
+            SinkImpl::Kafka(sink) => sink.commit().await,
+            SinkImpl::File(sink) => sink.commit().await,
+        }
+    }
+}
+
+/// Encodes the columns of `row` at `indices` as a JSON object keyed by the column names.
+pub(crate) fn row_to_json(row: &RowRef<'_>, schema: &Schema, indices: &[usize]) -> Value {
+    let mut map = Map::with_capacity(indices.len());
+    for &idx in indices {
+        map.insert(schema[idx].name.clone(), datum_to_json(row.value_at(idx)));
+    }
+    Value::Object(map)
+}
+
+/// Numbers and booleans are encoded natively. Structs and lists become JSON arrays, jsonb values
+/// are embedded as they are, and the remaining types use their text representation.
+pub(crate) fn datum_to_json(datum: DatumRef<'_>) -> Value {
+    let scalar = match datum {
+        Some(scalar) => scalar,
+        None => return Value::Null,
+    };
+    match scalar {
+        ScalarRefImpl::Int16(v) => Value::from(v),
+        ScalarRefImpl::Int32(v) => Value::from(v),
+        ScalarRefImpl::Int64(v) => Value::from(v),
+        ScalarRefImpl::Float32(v) => Value::from(v.0),
+        ScalarRefImpl::Float64(v) => Value::from(v.0),
+        ScalarRefImpl::Utf8(v) => Value::from(v),
+        ScalarRefImpl::Bool(v) => Value::from(v),
+        ScalarRefImpl::Struct(v) => v.fields_ref().into_iter().map(datum_to_json).collect(),
+        ScalarRefImpl::List(v) => v.values_ref().into_iter().map(datum_to_json).collect(),
+        ScalarRefImpl::Jsonb(v) => v.value().clone(),
+        ScalarRefImpl::Decimal(_)
+        | ScalarRefImpl::Interval(_)
+        | ScalarRefImpl::NaiveDate(_)
+        | ScalarRefImpl::NaiveDateTime(_)
+        | ScalarRefImpl::NaiveTime(_)
+        | ScalarRefImpl::Bytea(_)
+        | ScalarRefImpl::Uuid(_) => Value::from(scalar.to_string()),
+    }
+}
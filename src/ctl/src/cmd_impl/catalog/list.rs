@@ -0,0 +1,94 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+use risingwave_pb::catalog::Table;
+
+use crate::common::MetaServiceOpts;
+
+/// Kind of a relation in the `tables` of the catalog.
+fn table_kind(table: &Table) -> &'static str {
+    if table.is_index {
+        "index"
+    } else if let Some(OptionalAssociatedSourceId::AssociatedSourceId(_)) =
+        table.optional_associated_source_id
+    {
+        "table"
+    } else {
+        "materialized view"
+    }
+}
+
+pub async fn list_catalog(json: bool) -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let catalog = meta_client.get_catalog().await?;
+
+    if json {
+        let value = serde_json::json!({
+            "databases": catalog.databases,
+            "schemas": catalog.schemas,
+            "tables": catalog.tables,
+            "sources": catalog.sources,
+            "sinks": catalog.sinks,
+        });
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    let schema_names: HashMap<_, _> = catalog
+        .schemas
+        .iter()
+        .map(|schema| (schema.id, schema.name.as_str()))
+        .collect();
+    let qualified_name = |schema_id: u32, name: &str| match schema_names.get(&schema_id) {
+        Some(schema_name) => format!("{}.{}", schema_name, name),
+        None => name.to_string(),
+    };
+
+    for database in &catalog.databases {
+        println!("database {}: {}", database.id, database.name);
+    }
+    for schema in &catalog.schemas {
+        println!(
+            "schema {}: {} (database {})",
+            schema.id, schema.name, schema.database_id
+        );
+    }
+    for source in &catalog.sources {
+        println!(
+            "source {}: {}",
+            source.id,
+            qualified_name(source.schema_id, &source.name)
+        );
+    }
+    for table in &catalog.tables {
+        println!(
+            "{} {}: {}",
+            table_kind(table),
+            table.id,
+            qualified_name(table.schema_id, &table.name)
+        );
+    }
+    for sink in &catalog.sinks {
+        println!(
+            "sink {}: {}",
+            sink.id,
+            qualified_name(sink.schema_id, &sink.name)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::common::WorkerType;
+
+use crate::common::MetaServiceOpts;
+
+pub async fn cluster_info(json: bool) -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let mut workers = vec![];
+    for worker_type in [
+        WorkerType::Frontend,
+        WorkerType::ComputeNode,
+        WorkerType::Compactor,
+    ] {
+        workers.extend(meta_client.list_all_nodes(worker_type, true).await?);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&workers)?);
+        return Ok(());
+    }
+    for worker in workers {
+        let host = worker.host.as_ref().unwrap();
+        println!(
+            "worker {}: {:?} {}:{} {:?}",
+            worker.id,
+            worker.r#type(),
+            host.host,
+            host.port,
+            worker.state()
+        );
+        for parallel_unit in &worker.parallel_units {
+            println!(
+                "  parallel unit {}: {:?}",
+                parallel_unit.id,
+                parallel_unit.r#type()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::{get_table_catalog, MetaServiceOpts};
+
+pub async fn list_fragments(mv_name: String, json: bool) -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let table = get_table_catalog(&meta_client, &mv_name).await?;
+    let table_fragments = meta_client
+        .list_table_fragments(&[table.id])
+        .await?
+        .pop()
+        .unwrap();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&table_fragments)?);
+        return Ok(());
+    }
+    println!(
+        "materialized view {} (id {})",
+        mv_name, table_fragments.table_id
+    );
+    let mut fragments = table_fragments.fragments.values().collect::<Vec<_>>();
+    fragments.sort_by_key(|fragment| fragment.fragment_id);
+    for fragment in fragments {
+        println!(
+            "fragment {}: {:?} {:?}",
+            fragment.fragment_id,
+            fragment.fragment_type(),
+            fragment.distribution_type()
+        );
+        if let Some(vnode_mapping) = &fragment.vnode_mapping {
+            println!(
+                "  vnode mapping: {:?}",
+                vnode_mapping
+                    .original_indices
+                    .iter()
+                    .zip(&vnode_mapping.data)
+                    .collect::<Vec<_>>()
+            );
+        }
+        for actor in &fragment.actors {
+            let parallel_unit = table_fragments
+                .actor_status
+                .get(&actor.actor_id)
+                .and_then(|status| status.parallel_unit.as_ref());
+            match parallel_unit {
+                Some(parallel_unit) => println!(
+                    "  actor {}: parallel unit {} on worker {}, upstream actors {:?}",
+                    actor.actor_id,
+                    parallel_unit.id,
+                    parallel_unit.worker_node_id,
+                    actor.upstream_actor_id
+                ),
+                None => println!(
+                    "  actor {}: upstream actors {:?}",
+                    actor.actor_id, actor.upstream_actor_id
+                ),
+            }
+            for dispatcher in &actor.dispatcher {
+                println!(
+                    "    dispatcher {}: {:?} to actors {:?}",
+                    dispatcher.dispatcher_id,
+                    dispatcher.r#type(),
+                    dispatcher.downstream_actor_id
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::Row;
+use risingwave_common::catalog::{ColumnDesc, TableId};
+use risingwave_common::types::{Datum, ScalarImpl};
+use risingwave_common::util::epoch::INVALID_EPOCH;
+use risingwave_rpc_client::HummockMetaClient;
+use risingwave_storage::cell_based_row_deserializer::CellBasedRowDeserializer;
+use risingwave_storage::Keyspace;
+use serde_json::Value;
+
+use crate::common::{get_table_catalog, HummockServiceOpts};
+
+fn datum_to_json(datum: &Datum) -> Value {
+    match datum {
+        None => Value::Null,
+        Some(ScalarImpl::Int16(v)) => Value::from(*v),
+        Some(ScalarImpl::Int32(v)) => Value::from(*v),
+        Some(ScalarImpl::Int64(v)) => Value::from(*v),
+        Some(ScalarImpl::Float32(v)) => Value::from(v.0),
+        Some(ScalarImpl::Float64(v)) => Value::from(v.0),
+        Some(ScalarImpl::Bool(v)) => Value::from(*v),
+        Some(scalar) => Value::from(scalar.to_string()),
+    }
+}
+
+fn print_row(row: &Row, column_descs: &[ColumnDesc], json: bool) -> anyhow::Result<()> {
+    if json {
+        let value: serde_json::Map<_, _> = column_descs
+            .iter()
+            .zip(&row.0)
+            .map(|(column_desc, datum)| (column_desc.name.clone(), datum_to_json(datum)))
+            .collect();
+        println!("{}", serde_json::to_string(&value)?);
+    } else {
+        let values = row
+            .0
+            .iter()
+            .map(|datum| match datum {
+                Some(scalar) => scalar.to_string(),
+                None => "NULL".to_string(),
+            })
+            .collect::<Vec<_>>();
+        println!("{}", values.join(" | "));
+    }
+    Ok(())
+}
+
+/// Scan all rows of a materialized view and decode them with its catalog. In JSON mode, each row
+/// is printed as an object per line.
+pub async fn scan(mv_name: String, json: bool) -> anyhow::Result<()> {
+    let hummock_opts = HummockServiceOpts::from_env()?;
+    let meta_client = hummock_opts.meta_opts.create_meta_client().await?;
+    let hummock = hummock_opts.create_hummock_store().await?;
+
+    let table = get_table_catalog(&meta_client, &mv_name).await?;
+    let column_descs = table
+        .columns
+        .iter()
+        .filter(|column| !column.is_hidden)
+        .map(|column| ColumnDesc::from(column.column_desc.as_ref().unwrap()))
+        .collect::<Vec<_>>();
+    if !json {
+        let names = column_descs
+            .iter()
+            .map(|column_desc| column_desc.name.as_str())
+            .collect::<Vec<_>>();
+        println!("{}", names.join(" | "));
+    }
+
+    let epoch = meta_client.pin_snapshot(INVALID_EPOCH).await?;
+    let keyspace = Keyspace::table_root(hummock, &TableId::new(table.id));
+    let kv_pairs = keyspace.scan(None, epoch).await;
+    meta_client.unpin_snapshot(&[epoch]).await?;
+
+    let mut deserializer = CellBasedRowDeserializer::new(column_descs.clone());
+    for (key, value) in kv_pairs? {
+        if let Some((_, row)) = deserializer.deserialize(&key, &value)? {
+            print_row(&row, &column_descs, json)?;
+        }
+    }
+    if let Some((_, row)) = deserializer.take() {
+        print_row(&row, &column_descs, json)?;
+    }
+    Ok(())
+}
@@ -16,3 +16,5 @@ mod meta_service;
 pub use meta_service::*;
 mod hummock_service;
 pub use hummock_service::*;
+mod table_catalog;
+pub use table_catalog::*;
@@ -0,0 +1,52 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use risingwave_pb::catalog::Table;
+use risingwave_rpc_client::MetaClient;
+
+/// Find the catalog of a materialized view by its name, which can be qualified by the schema name
+/// as `schema.name` to resolve ambiguity.
+pub async fn get_table_catalog(meta_client: &MetaClient, name: &str) -> Result<Table> {
+    let catalog = meta_client.get_catalog().await?;
+    let (schema_name, table_name) = match name.split_once('.') {
+        Some((schema_name, table_name)) => (Some(schema_name), table_name),
+        None => (None, name),
+    };
+    let schema_id = match schema_name {
+        Some(schema_name) => Some(
+            catalog
+                .schemas
+                .iter()
+                .find(|schema| schema.name == schema_name)
+                .ok_or_else(|| anyhow!("schema {} not found", schema_name))?
+                .id,
+        ),
+        None => None,
+    };
+
+    let mut tables = catalog.tables.into_iter().filter(|table| {
+        table.name == table_name && schema_id.map_or(true, |id| id == table.schema_id)
+    });
+    let table = tables
+        .next()
+        .ok_or_else(|| anyhow!("materialized view {} not found", name))?;
+    if tables.next().is_some() {
+        return Err(anyhow!(
+            "materialized view {} is ambiguous, please qualify it with the schema name",
+            name
+        ));
+    }
+    Ok(table)
+}
@@ -31,6 +31,15 @@ enum Commands {
     /// Commands for Hummock
     #[clap(subcommand)]
     Hummock(HummockCommands),
+    /// Commands for Meta
+    #[clap(subcommand)]
+    Meta(MetaCommands),
+    /// Commands for Catalog
+    #[clap(subcommand)]
+    Catalog(CatalogCommands),
+    /// Commands for Tables
+    #[clap(subcommand)]
+    Table(TableCommands),
 }
 
 #[derive(Subcommand)]
@@ -47,6 +56,62 @@ enum HummockCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum MetaCommands {
+    /// reschedule the actors of a fragment by adding and removing parallel units
+    Reschedule {
+        #[clap(short, long = "fragment-id")]
+        fragment_id: u32,
+
+        /// ids of the parallel units to add, separated by commas
+        #[clap(short, long = "add", use_value_delimiter = true)]
+        added_parallel_units: Vec<u32>,
+
+        /// ids of the parallel units to remove, separated by commas
+        #[clap(short, long = "remove", use_value_delimiter = true)]
+        removed_parallel_units: Vec<u32>,
+    },
+    /// show the workers in the cluster and their parallel units
+    ClusterInfo {
+        /// print in JSON format
+        #[clap(long)]
+        json: bool,
+    },
+    /// list the fragments of a materialized view, with their actors, dispatchers and vnode
+    /// mappings
+    ListFragments {
+        /// name of the materialized view, optionally qualified as `schema.name`
+        mv_name: String,
+
+        /// print in JSON format
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CatalogCommands {
+    /// list all databases, schemas, tables, sources, materialized views and sinks with their ids
+    List {
+        /// print in JSON format
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TableCommands {
+    /// scan all rows of a materialized view and decode them
+    Scan {
+        /// name of the materialized view, optionally qualified as `schema.name`
+        mv_name: String,
+
+        /// print each row as a JSON object
+        #[clap(long)]
+        json: bool,
+    },
+}
+
 pub async fn start(opts: CliOpts) {
     match &opts.command {
         Commands::Hummock(HummockCommands::ListVersion) => {
@@ -55,5 +120,30 @@ pub async fn start(opts: CliOpts) {
         Commands::Hummock(HummockCommands::ListKv { epoch, tableid }) => {
             cmd_impl::hummock::list_kv(*epoch, *tableid).await.unwrap()
         }
+        Commands::Meta(MetaCommands::Reschedule {
+            fragment_id,
+            added_parallel_units,
+            removed_parallel_units,
+        }) => cmd_impl::meta::reschedule(
+            *fragment_id,
+            added_parallel_units.clone(),
+            removed_parallel_units.clone(),
+        )
+        .await
+        .unwrap(),
+        Commands::Meta(MetaCommands::ClusterInfo { json }) => {
+            cmd_impl::meta::cluster_info(*json).await.unwrap()
+        }
+        Commands::Meta(MetaCommands::ListFragments { mv_name, json }) => {
+            cmd_impl::meta::list_fragments(mv_name.clone(), *json)
+                .await
+                .unwrap()
+        }
+        Commands::Catalog(CatalogCommands::List { json }) => {
+            cmd_impl::catalog::list_catalog(*json).await.unwrap()
+        }
+        Commands::Table(TableCommands::Scan { mv_name, json }) => {
+            cmd_impl::table::scan(mv_name.clone(), *json).await.unwrap()
+        }
     }
 }
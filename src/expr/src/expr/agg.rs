@@ -28,6 +28,16 @@ pub enum AggKind {
     Avg,
     StringAgg,
     SingleValue,
+    StddevPop,
+    StddevSamp,
+    VarPop,
+    VarSamp,
+    BoolAnd,
+    BoolOr,
+    BitAnd,
+    BitOr,
+    ArrayAgg,
+    ApproxCountDistinct,
 }
 
 impl std::fmt::Display for AggKind {
@@ -41,6 +51,16 @@ impl std::fmt::Display for AggKind {
             AggKind::Avg => write!(f, "avg"),
             AggKind::StringAgg => write!(f, "string_agg"),
             AggKind::SingleValue => write!(f, "single_value"),
+            AggKind::StddevPop => write!(f, "stddev_pop"),
+            AggKind::StddevSamp => write!(f, "stddev_samp"),
+            AggKind::VarPop => write!(f, "var_pop"),
+            AggKind::VarSamp => write!(f, "var_samp"),
+            AggKind::BoolAnd => write!(f, "bool_and"),
+            AggKind::BoolOr => write!(f, "bool_or"),
+            AggKind::BitAnd => write!(f, "bit_and"),
+            AggKind::BitOr => write!(f, "bit_or"),
+            AggKind::ArrayAgg => write!(f, "array_agg"),
+            AggKind::ApproxCountDistinct => write!(f, "approx_count_distinct"),
         }
     }
 }
@@ -57,6 +77,16 @@ impl TryFrom<Type> for AggKind {
             Type::Count => Ok(AggKind::Count),
             Type::StringAgg => Ok(AggKind::StringAgg),
             Type::SingleValue => Ok(AggKind::SingleValue),
+            Type::StddevPop => Ok(AggKind::StddevPop),
+            Type::StddevSamp => Ok(AggKind::StddevSamp),
+            Type::VarPop => Ok(AggKind::VarPop),
+            Type::VarSamp => Ok(AggKind::VarSamp),
+            Type::BoolAnd => Ok(AggKind::BoolAnd),
+            Type::BoolOr => Ok(AggKind::BoolOr),
+            Type::BitAnd => Ok(AggKind::BitAnd),
+            Type::BitOr => Ok(AggKind::BitOr),
+            Type::ArrayAgg => Ok(AggKind::ArrayAgg),
+            Type::ApproxCountDistinct => Ok(AggKind::ApproxCountDistinct),
             _ => Err(ErrorCode::InternalError("Unrecognized agg.".into()).into()),
         }
     }
@@ -72,6 +102,16 @@ impl AggKind {
             Self::Count => Type::Count,
             Self::StringAgg => Type::StringAgg,
             Self::SingleValue => Type::SingleValue,
+            Self::StddevPop => Type::StddevPop,
+            Self::StddevSamp => Type::StddevSamp,
+            Self::VarPop => Type::VarPop,
+            Self::VarSamp => Type::VarSamp,
+            Self::BoolAnd => Type::BoolAnd,
+            Self::BoolOr => Type::BoolOr,
+            Self::BitAnd => Type::BitAnd,
+            Self::BitOr => Type::BitOr,
+            Self::ArrayAgg => Type::ArrayAgg,
+            Self::ApproxCountDistinct => Type::ApproxCountDistinct,
             Self::RowCount => {
                 panic!("cannot convert RowCount to prost, TODO: remove RowCount from AggKind")
             }
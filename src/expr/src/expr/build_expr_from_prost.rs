@@ -12,25 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_common::array::DataChunk;
+use risingwave_common::array::{DataChunk, I64Array, NaiveDateTimeArray, Row, Utf8Array};
 use risingwave_common::ensure;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::{DataType, ToOwnedDatum};
-use risingwave_pb::expr::expr_node::RexNode;
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
+use risingwave_pb::expr::expr_node::{RexNode, Type};
 use risingwave_pb::expr::ExprNode;
 
-use crate::expr::expr_binary_bytes::new_substr_start;
-use crate::expr::expr_binary_nonnull::{new_binary_expr, new_like_default};
+use crate::expr::expr_binary_bytes::{new_substr_start, new_to_char};
+use crate::expr::expr_binary_nonnull::{new_binary_expr, new_ilike_default, new_like_default};
 use crate::expr::expr_binary_nullable::new_nullable_binary_expr;
 use crate::expr::expr_case::{CaseExpression, WhenClause};
 use crate::expr::expr_in::InExpression;
+use crate::expr::expr_regexp::{RegexpExpression, RegexpFunction};
 use crate::expr::expr_ternary_bytes::{
     new_replace_expr, new_split_part_expr, new_substr_start_end, new_translate_expr,
 };
 use crate::expr::expr_unary::{
     new_length_default, new_ltrim_expr, new_rtrim_expr, new_trim_expr, new_unary_expr,
 };
+use crate::expr::template::{UnaryBytesExpression, UnaryExpression};
 use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression};
+use crate::vector_op::timezone::{
+    parse_time_zone, timestamp_at_time_zone_spec, timestampz_at_time_zone_spec,
+};
+use crate::vector_op::to_char::{
+    to_char_timestamp_with_format, to_timestamp_with_format, DateTimeFormat,
+};
 
 fn get_children_and_return_type(prost: &ExprNode) -> Result<(Vec<ExprNode>, DataType)> {
     let ret_type = DataType::from(prost.get_return_type()?);
@@ -138,7 +146,56 @@ pub fn build_like_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     ensure!(children.len() == 2);
     let expr_ia1 = expr_build_from_prost(&children[0])?;
     let expr_ia2 = expr_build_from_prost(&children[1])?;
-    Ok(new_like_default(expr_ia1, expr_ia2, ret_type))
+    match prost.get_expr_type()? {
+        Type::ILike => Ok(new_ilike_default(expr_ia1, expr_ia2, ret_type)),
+        _ => Ok(new_like_default(expr_ia1, expr_ia2, ret_type)),
+    }
+}
+
+pub fn build_regexp_expr(prost: &ExprNode, func: RegexpFunction) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    // The flags are the last argument and optional.
+    let num_args = func.flags_index();
+    ensure!(children.len() == num_args || children.len() == num_args + 1);
+    let text = expr_build_from_prost(&children[0])?;
+    let replacement = if func == RegexpFunction::Replace {
+        Some(expr_build_from_prost(&children[2])?)
+    } else {
+        None
+    };
+    let pattern = &children[1];
+    let flags = children.get(func.flags_index());
+
+    // Compile the pattern only once if it does not depend on the input.
+    let is_const = |node: &ExprNode| matches!(node.get_expr_type(), Ok(Type::ConstantValue));
+    if is_const(pattern) && flags.map_or(true, is_const) {
+        let eval_const = |node: &ExprNode| -> Result<Option<String>> {
+            let datum = expr_build_from_prost(node)?.eval_row(&Row::new(vec![]))?;
+            Ok(datum.map(ScalarImpl::into_utf8))
+        };
+        let pattern = eval_const(pattern)?;
+        let flags = match flags {
+            Some(flags) => eval_const(flags)?,
+            None => Some(func.default_flags().to_string()),
+        };
+        Ok(Box::new(RegexpExpression::with_const_pattern(
+            func,
+            ret_type,
+            text,
+            pattern.as_deref(),
+            flags.as_deref(),
+            replacement,
+        )?))
+    } else {
+        Ok(Box::new(RegexpExpression::with_dynamic_pattern(
+            func,
+            ret_type,
+            text,
+            expr_build_from_prost(pattern)?,
+            flags.map(expr_build_from_prost).transpose()?,
+            replacement,
+        )))
+    }
 }
 
 pub fn build_in_expr(prost: &ExprNode) -> Result<BoxedExpression> {
@@ -212,6 +269,88 @@ pub fn build_translate_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     Ok(new_translate_expr(s, match_str, replace_str, ret_type))
 }
 
+/// The value of `node` if it is a non-`NULL` constant `varchar`, so that e.g. a format pattern
+/// can be compiled only once rather than for each row.
+fn eval_const_utf8(node: &ExprNode) -> Result<Option<String>> {
+    if !matches!(node.get_expr_type(), Ok(Type::ConstantValue)) {
+        return Ok(None);
+    }
+    let datum = expr_build_from_prost(node)?.eval_row(&Row::new(vec![]))?;
+    Ok(datum.map(ScalarImpl::into_utf8))
+}
+
+pub fn build_to_char_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 2);
+    let data_expr = expr_build_from_prost(&children[0])?;
+    if let Some(format) = eval_const_utf8(&children[1])? {
+        let format = DateTimeFormat::compile(&format);
+        return Ok(Box::new(
+            UnaryBytesExpression::<NaiveDateTimeArray, _>::new(
+                data_expr,
+                ret_type,
+                move |timestamp, writer| to_char_timestamp_with_format(timestamp, &format, writer),
+            ),
+        ));
+    }
+    let tmpl_expr = expr_build_from_prost(&children[1])?;
+    Ok(new_to_char(data_expr, tmpl_expr, ret_type))
+}
+
+pub fn build_to_timestamp_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 1 || children.len() == 2);
+    let first = expr_build_from_prost(&children[0])?;
+    if children.len() == 1 {
+        new_unary_expr(Type::ToTimestamp, ret_type, first)
+    } else if let Some(format) = eval_const_utf8(&children[1])? {
+        let format = DateTimeFormat::compile(&format);
+        Ok(Box::new(
+            UnaryExpression::<Utf8Array, NaiveDateTimeArray, _>::new(
+                first,
+                ret_type,
+                move |s: &str| to_timestamp_with_format(s, &format),
+            ),
+        ))
+    } else {
+        let format = expr_build_from_prost(&children[1])?;
+        Ok(new_binary_expr(Type::ToTimestamp, ret_type, first, format))
+    }
+}
+
+pub fn build_at_time_zone_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 2);
+    let timestamp = expr_build_from_prost(&children[0])?;
+    // Parse the time zone only once if it does not depend on the input.
+    let zone = match eval_const_utf8(&children[1])? {
+        Some(zone) => parse_time_zone(&zone)?,
+        None => {
+            let zone = expr_build_from_prost(&children[1])?;
+            return Ok(new_binary_expr(Type::AtTimeZone, ret_type, timestamp, zone));
+        }
+    };
+    let expr: BoxedExpression = match timestamp.return_type() {
+        DataType::Timestamp => Box::new(UnaryExpression::<NaiveDateTimeArray, I64Array, _>::new(
+            timestamp,
+            ret_type,
+            move |timestamp| Ok(timestamp_at_time_zone_spec(timestamp, &zone)),
+        )),
+        DataType::Timestampz => Box::new(UnaryExpression::<I64Array, NaiveDateTimeArray, _>::new(
+            timestamp,
+            ret_type,
+            move |timestampz| timestampz_at_time_zone_spec(timestampz, &zone),
+        )),
+        other => {
+            return Err(RwError::from(ErrorCode::InternalError(format!(
+                "AT TIME ZONE ( {:?} ) is not supported yet!",
+                other
+            ))))
+        }
+    };
+    Ok(expr)
+}
+
 pub fn build_split_part_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     let (children, ret_type) = get_children_and_return_type(prost)?;
     ensure!(children.len() == 3);
@@ -230,6 +369,8 @@ pub fn build_split_part_expr(prost: &ExprNode) -> Result<BoxedExpression> {
 mod tests {
     use std::vec;
 
+    use chrono::NaiveDateTime;
+    use risingwave_common::types::NaiveDateTimeWrapper;
     use risingwave_pb::data::data_type::TypeName;
     use risingwave_pb::data::DataType as ProstDataType;
     use risingwave_pb::expr::expr_node::{RexNode, Type};
@@ -372,4 +513,68 @@ mod tests {
         };
         assert!(build_binary_expr_prost(&expr).is_ok());
     }
+
+    #[test]
+    fn test_build_const_format_and_time_zone() {
+        let data_type = |type_name: TypeName| {
+            Some(ProstDataType {
+                type_name: type_name as i32,
+                ..Default::default()
+            })
+        };
+        let varchar = |s: &str| ExprNode {
+            expr_type: Type::ConstantValue as i32,
+            return_type: data_type(TypeName::Varchar),
+            rex_node: Some(RexNode::Constant(ConstantValue {
+                body: s.as_bytes().to_vec(),
+            })),
+        };
+        let func_call = |expr_type: Type, ret: TypeName, children: Vec<ExprNode>| ExprNode {
+            expr_type: expr_type as i32,
+            return_type: data_type(ret),
+            rex_node: Some(RexNode::FuncCall(FunctionCall { children })),
+        };
+        let timestamp = || ExprNode {
+            expr_type: Type::InputRef as i32,
+            return_type: data_type(TypeName::Timestamp),
+            rex_node: Some(RexNode::InputRef(InputRefExpr { column_idx: 0 })),
+        };
+        let row = Row::new(vec![Some(ScalarImpl::NaiveDateTime(
+            NaiveDateTimeWrapper::new(NaiveDateTime::from_timestamp(1640995200, 0)),
+        ))]);
+
+        let to_char = func_call(
+            Type::ToChar,
+            TypeName::Varchar,
+            vec![timestamp(), varchar("YYYY-MM-DD HH24:MI")],
+        );
+        assert_eq!(
+            build_to_char_expr(&to_char)
+                .unwrap()
+                .eval_row(&row)
+                .unwrap(),
+            Some(ScalarImpl::Utf8("2022-01-01 00:00".to_string()))
+        );
+
+        let at_time_zone = func_call(
+            Type::AtTimeZone,
+            TypeName::Timestampz,
+            vec![timestamp(), varchar("+08:00")],
+        );
+        assert_eq!(
+            build_at_time_zone_expr(&at_time_zone)
+                .unwrap()
+                .eval_row(&row)
+                .unwrap(),
+            Some(ScalarImpl::Int64((1640995200 - 8 * 3600) * 1_000_000))
+        );
+
+        // A constant time zone is parsed when the expression is built.
+        let at_time_zone = func_call(
+            Type::AtTimeZone,
+            TypeName::Timestampz,
+            vec![timestamp(), varchar("Mars/Olympus")],
+        );
+        assert!(build_at_time_zone_expr(&at_time_zone).is_err());
+    }
 }
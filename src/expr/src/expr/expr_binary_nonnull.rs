@@ -13,8 +13,9 @@
 // limitations under the License.
 
 use risingwave_common::array::{
-    Array, BoolArray, DecimalArray, I32Array, IntervalArray, ListArray, NaiveDateArray,
-    NaiveDateTimeArray, StructArray, Utf8Array,
+    Array, BoolArray, BytesArray, DecimalArray, F64Array, I32Array, I64Array, IntervalArray,
+    JsonbArray, ListArray, NaiveDateArray, NaiveDateTimeArray, NaiveTimeArray, StructArray,
+    Utf8Array,
 };
 use risingwave_common::types::*;
 use risingwave_pb::expr::expr_node::Type;
@@ -24,10 +25,18 @@ use crate::expr::BoxedExpression;
 use crate::for_all_cmp_variants;
 use crate::vector_op::arithmetic_op::*;
 use crate::vector_op::cmp::*;
+use crate::vector_op::date_part::{
+    date_part_from_date, date_part_from_interval, date_part_from_time, date_part_from_timestamp,
+};
+use crate::vector_op::date_trunc::{date_trunc_interval, date_trunc_timestamp};
 use crate::vector_op::extract::{extract_from_date, extract_from_timestamp};
-use crate::vector_op::like::like_default;
+use crate::vector_op::interval::age;
+use crate::vector_op::jsonb::{jsonb_contains, jsonb_exists};
+use crate::vector_op::like::{ilike_default, like_default};
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
+use crate::vector_op::timezone::{timestamp_at_time_zone, timestampz_at_time_zone};
+use crate::vector_op::to_char::to_timestamp;
 use crate::vector_op::tumble::{tumble_start_date, tumble_start_date_time};
 
 /// This macro helps create arithmetic expression.
@@ -121,6 +130,14 @@ macro_rules! gen_binary_expr_cmp {
                     gen_str_cmp($op),
                 ))
             }
+            (DataType::Bytea, DataType::Bytea) => Box::new(BinaryExpression::<
+                BytesArray,
+                BytesArray,
+                BoolArray,
+                _,
+            >::new(
+                $l, $r, $ret, gen_bytea_cmp($op)
+            )),
             (DataType::Struct { fields: _ }, DataType::Struct { fields: _ }) => Box::new(
                 BinaryExpression::<StructArray, StructArray, BoolArray, _>::new(
                     $l,
@@ -228,6 +245,91 @@ fn build_extract_expr(ret: DataType, l: BoxedExpression, r: BoxedExpression) ->
     }
 }
 
+fn build_date_trunc_expr(ret: DataType, l: BoxedExpression, r: BoxedExpression) -> BoxedExpression {
+    match r.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            Utf8Array,
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, date_trunc_timestamp)),
+        DataType::Interval => Box::new(BinaryExpression::<
+            Utf8Array,
+            IntervalArray,
+            IntervalArray,
+            _,
+        >::new(l, r, ret, date_trunc_interval)),
+        _ => {
+            unimplemented!("date_trunc ( {:?} ) is not supported yet!", r.return_type())
+        }
+    }
+}
+
+fn build_date_part_expr(ret: DataType, l: BoxedExpression, r: BoxedExpression) -> BoxedExpression {
+    match r.return_type() {
+        DataType::Date => Box::new(
+            BinaryExpression::<Utf8Array, NaiveDateArray, F64Array, _>::new(
+                l,
+                r,
+                ret,
+                date_part_from_date,
+            ),
+        ),
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            Utf8Array,
+            NaiveDateTimeArray,
+            F64Array,
+            _,
+        >::new(l, r, ret, date_part_from_timestamp)),
+        DataType::Time => Box::new(
+            BinaryExpression::<Utf8Array, NaiveTimeArray, F64Array, _>::new(
+                l,
+                r,
+                ret,
+                date_part_from_time,
+            ),
+        ),
+        DataType::Interval => Box::new(
+            BinaryExpression::<Utf8Array, IntervalArray, F64Array, _>::new(
+                l,
+                r,
+                ret,
+                date_part_from_interval,
+            ),
+        ),
+        _ => {
+            unimplemented!("date_part ( {:?} ) is not supported yet!", r.return_type())
+        }
+    }
+}
+
+fn build_at_time_zone_expr(
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> BoxedExpression {
+    match l.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            NaiveDateTimeArray,
+            Utf8Array,
+            I64Array,
+            _,
+        >::new(l, r, ret, timestamp_at_time_zone)),
+        DataType::Timestampz => Box::new(BinaryExpression::<
+            I64Array,
+            Utf8Array,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, timestampz_at_time_zone)),
+        _ => {
+            unimplemented!(
+                "AT TIME ZONE ( {:?} ) is not supported yet!",
+                l.return_type()
+            )
+        }
+    }
+}
+
 pub fn new_binary_expr(
     expr_type: Type,
     ret: DataType,
@@ -264,6 +366,14 @@ pub fn new_binary_expr(
                     { interval, timestamp, timestamp, interval_timestamp_add },
                     { interval, date, timestamp, interval_date_add },
                     { date, interval, timestamp, date_interval_add },
+                    { timestampz, interval, timestampz, timestampz_interval_add },
+                    { interval, timestampz, timestampz, interval_timestampz_add },
+                    { time, interval, time, time_interval_add },
+                    { interval, time, time, interval_time_add },
+                    { date, int32, date, date_int_add },
+                    { int32, date, date, int_date_add },
+                    { date, time, timestamp, date_time_add },
+                    { time, date, timestamp, time_date_add },
                     { interval, interval, interval, general_add },
                 },
             }
@@ -278,6 +388,11 @@ pub fn new_binary_expr(
                     { timestamp, interval, timestamp, timestamp_interval_sub },
                     { date, date, int32, date_date_sub },
                     { date, interval, timestamp, date_interval_sub },
+                    { timestampz, timestampz, interval, timestampz_timestampz_sub },
+                    { timestampz, interval, timestampz, timestampz_interval_sub },
+                    { time, time, interval, time_time_sub },
+                    { time, interval, time, time_interval_sub },
+                    { date, int32, date, date_int_sub },
                     { interval, interval, interval, general_sub },
                 },
             }
@@ -294,6 +409,12 @@ pub fn new_binary_expr(
                     { int16, interval, interval, int_interval_mul },
                     { int32, interval, interval, int_interval_mul },
                     { int64, interval, interval, int_interval_mul },
+                    { interval, float32, interval, interval_float_mul },
+                    { interval, float64, interval, interval_float_mul },
+                    { interval, decimal, interval, interval_float_mul },
+                    { float32, interval, interval, float_interval_mul },
+                    { float64, interval, interval, float_interval_mul },
+                    { decimal, interval, interval, float_interval_mul },
                 },
             }
         }
@@ -303,6 +424,12 @@ pub fn new_binary_expr(
                 l, r, ret,
                 general_div,
                 {
+                    { interval, int16, interval, interval_float_div },
+                    { interval, int32, interval, interval_float_div },
+                    { interval, int64, interval, interval_float_div },
+                    { interval, float32, interval, interval_float_div },
+                    { interval, float64, interval, interval_float_div },
+                    { interval, decimal, interval, interval_float_div },
                 },
             }
         }
@@ -328,6 +455,32 @@ pub fn new_binary_expr(
             l, r, ret, position,
         )),
         Type::TumbleStart => new_tumble_start(l, r, ret),
+        Type::DateTrunc => build_date_trunc_expr(ret, l, r),
+        Type::DatePart => build_date_part_expr(ret, l, r),
+        Type::AtTimeZone => build_at_time_zone_expr(ret, l, r),
+        Type::ToTimestamp => Box::new(BinaryExpression::<
+            Utf8Array,
+            Utf8Array,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, to_timestamp)),
+        Type::Age => Box::new(BinaryExpression::<
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            IntervalArray,
+            _,
+        >::new(l, r, ret, age)),
+        Type::JsonbContains => Box::new(
+            BinaryExpression::<JsonbArray, JsonbArray, BoolArray, _>::new(
+                l,
+                r,
+                ret,
+                jsonb_contains,
+            ),
+        ),
+        Type::JsonbExists => Box::new(
+            BinaryExpression::<JsonbArray, Utf8Array, BoolArray, _>::new(l, r, ret, jsonb_exists),
+        ),
         tp => {
             unimplemented!(
                 "The expression {:?} using vectorized expression framework is not supported yet!",
@@ -379,6 +532,19 @@ pub fn new_like_default(
     ))
 }
 
+pub fn new_ilike_default(
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryExpression::<Utf8Array, Utf8Array, BoolArray, _>::new(
+        expr_ia1,
+        expr_ia2,
+        return_type,
+        ilike_default,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::NaiveDate;
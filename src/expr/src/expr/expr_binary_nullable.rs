@@ -14,15 +14,20 @@
 
 //! For expression that only accept two nullable arguments as input.
 
-use risingwave_common::array::{Array, BoolArray, Utf8Array};
+use risingwave_common::array::{
+    Array, BoolArray, BytesArray, I32Array, JsonbArray, ListArray, Utf8Array,
+};
 use risingwave_common::types::DataType;
 use risingwave_pb::expr::expr_node::Type;
 
 use super::BoxedExpression;
 use crate::expr::template::BinaryNullableExpression;
 use crate::for_all_cmp_variants;
-use crate::vector_op::cmp::{general_is_distinct_from, str_is_distinct_from};
+use crate::vector_op::cmp::{
+    bytea_is_distinct_from, general_is_distinct_from, str_is_distinct_from,
+};
 use crate::vector_op::conjunction::{and, or};
+use crate::vector_op::jsonb::*;
 
 macro_rules! gen_nullable_cmp_impl {
     ([$l:expr, $r:expr, $ret:expr], $( { $i1:ident, $i2:ident, $cast:ident, $func:ident} ),*) => {
@@ -69,6 +74,10 @@ pub fn new_nullable_binary_expr(
             BinaryNullableExpression::<BoolArray, BoolArray, BoolArray, _>::new(l, r, ret, or),
         ),
         Type::IsDistinctFrom => new_distinct_from_expr(l, r, ret),
+        Type::JsonbAccessInner
+        | Type::JsonbAccessStr
+        | Type::JsonbExtractPath
+        | Type::JsonbExtractPathText => new_jsonb_access_expr(expr_type, l, r, ret),
         tp => {
             unimplemented!(
                 "The expression {:?} using vectorized expression framework is not supported yet!",
@@ -94,12 +103,102 @@ pub fn new_distinct_from_expr(
         >::new(
             l, r, ret, str_is_distinct_from
         )),
+        (DataType::Bytea, DataType::Bytea) => {
+            Box::new(BinaryNullableExpression::<
+                BytesArray,
+                BytesArray,
+                BoolArray,
+                _,
+            >::new(l, r, ret, bytea_is_distinct_from))
+        }
         _ => {
             for_all_cmp_variants! {gen_nullable_cmp_impl, l, r, ret, general_is_distinct_from}
         }
     }
 }
 
+/// Builds the `jsonb` access operators, which return `NULL` if the accessed value does not exist.
+pub fn new_jsonb_access_expr(
+    expr_type: Type,
+    l: BoxedExpression,
+    r: BoxedExpression,
+    ret: DataType,
+) -> BoxedExpression {
+    match (expr_type, r.return_type()) {
+        (Type::JsonbAccessInner, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_access_field))
+        }
+        (Type::JsonbAccessInner, DataType::Int32) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                I32Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_access_element))
+        }
+        (Type::JsonbAccessStr, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                Utf8Array,
+                _,
+            >::new(l, r, ret, jsonb_access_field_text))
+        }
+        (Type::JsonbAccessStr, DataType::Int32) => Box::new(BinaryNullableExpression::<
+            JsonbArray,
+            I32Array,
+            Utf8Array,
+            _,
+        >::new(
+            l, r, ret, jsonb_access_element_text
+        )),
+        (Type::JsonbExtractPath, DataType::List { .. }) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                ListArray,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_extract_path))
+        }
+        (Type::JsonbExtractPath, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_extract_str_path))
+        }
+        (Type::JsonbExtractPathText, DataType::List { .. }) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                ListArray,
+                Utf8Array,
+                _,
+            >::new(l, r, ret, jsonb_extract_path_text))
+        }
+        (Type::JsonbExtractPathText, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                Utf8Array,
+                _,
+            >::new(l, r, ret, jsonb_extract_str_path_text))
+        }
+        (tp, rt) => {
+            unimplemented!(
+                "The expression {:?} on (jsonb, {:?}) is not supported yet!",
+                tp,
+                rt
+            )
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::array::Row;
@@ -0,0 +1,184 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use risingwave_common::array::{ArrayRef, DataChunk, Row};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum, Scalar};
+use risingwave_common::{ensure, try_match_expand};
+use risingwave_pb::expr::expr_node::{RexNode, Type};
+use risingwave_pb::expr::ExprNode;
+
+use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression, Expression};
+use crate::vector_op::interval::make_interval;
+
+/// `make_interval(years, months, weeks, days, hours, mins, secs)`. The frontend always fills in
+/// all seven arguments, so omitted ones arrive as zero literals.
+#[derive(Debug)]
+pub struct MakeIntervalExpression {
+    return_type: DataType,
+    children: Vec<BoxedExpression>,
+}
+
+impl MakeIntervalExpression {
+    pub fn new(return_type: DataType, children: Vec<BoxedExpression>) -> Self {
+        MakeIntervalExpression {
+            return_type,
+            children,
+        }
+    }
+
+    fn eval_datums(&self, datums: Vec<Datum>) -> Result<Datum> {
+        let mut args = Vec::with_capacity(datums.len());
+        for datum in datums {
+            match datum {
+                Some(scalar) => args.push(scalar),
+                None => return Ok(None),
+            }
+        }
+        let mut args = args.into_iter();
+        let mut next_int = || args.next().unwrap().into_int32();
+        let (years, months, weeks, days, hours, mins) = (
+            next_int(),
+            next_int(),
+            next_int(),
+            next_int(),
+            next_int(),
+            next_int(),
+        );
+        let secs = args.next().unwrap().into_float64();
+        Ok(Some(
+            make_interval(years, months, weeks, days, hours, mins, secs)?.to_scalar_value(),
+        ))
+    }
+}
+
+impl Expression for MakeIntervalExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let children_array = self
+            .children
+            .iter()
+            .map(|c| c.eval(input))
+            .collect::<Result<Vec<_>>>()?;
+        let mut builder = self.return_type.create_array_builder(input.cardinality())?;
+
+        let len = children_array[0].len();
+        for i in 0..len {
+            let datums = children_array
+                .iter()
+                .map(|array| array.datum_at(i))
+                .collect();
+            builder.append_datum(&self.eval_datums(datums)?)?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let datums = self
+            .children
+            .iter()
+            .map(|c| c.eval_row(input))
+            .collect::<Result<Vec<_>>>()?;
+        self.eval_datums(datums)
+    }
+}
+
+impl<'a> TryFrom<&'a ExprNode> for MakeIntervalExpression {
+    type Error = RwError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type()? == Type::MakeInterval);
+
+        let ret_type = DataType::from(prost.get_return_type()?);
+        let func_call_node = try_match_expand!(prost.get_rex_node().unwrap(), RexNode::FuncCall)?;
+        ensure!(func_call_node.children.len() == 7);
+
+        let children = func_call_node
+            .children
+            .iter()
+            .map(expr_build_from_prost)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(MakeIntervalExpression::new(ret_type, children))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{DataChunk, Row};
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::{IntervalUnit, OrderedF64, ScalarImpl};
+    use risingwave_pb::data::data_type::TypeName;
+    use risingwave_pb::data::DataType as ProstDataType;
+    use risingwave_pb::expr::expr_node::RexNode;
+    use risingwave_pb::expr::expr_node::Type::MakeInterval;
+    use risingwave_pb::expr::{ExprNode, FunctionCall};
+
+    use super::*;
+    use crate::expr::test_utils::make_input_ref;
+
+    fn make_interval_function() -> ExprNode {
+        let mut children = (0..6)
+            .map(|idx| make_input_ref(idx, TypeName::Int32))
+            .collect::<Vec<_>>();
+        children.push(make_input_ref(6, TypeName::Double));
+        ExprNode {
+            expr_type: MakeInterval as i32,
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Interval as i32,
+                ..Default::default()
+            }),
+            rex_node: Some(RexNode::FuncCall(FunctionCall { children })),
+        }
+    }
+
+    #[test]
+    fn test_make_interval_expr() {
+        let expr = MakeIntervalExpression::try_from(&make_interval_function()).unwrap();
+        let data_chunk = DataChunk::from_pretty(
+            "i i i i i i F
+             1 2 1 3 4 5 6.5
+             0 0 0 0 0 . 1.0",
+        );
+        let res = expr.eval(&data_chunk).unwrap();
+        assert_eq!(
+            res.datum_at(0),
+            Some(ScalarImpl::Interval(IntervalUnit::new(
+                14,
+                10,
+                ((4 * 60 + 5) * 60) * 1000 + 6500
+            )))
+        );
+        assert_eq!(res.datum_at(1), None);
+
+        let row = Row::new(vec![
+            Some(ScalarImpl::Int32(0)),
+            Some(ScalarImpl::Int32(0)),
+            Some(ScalarImpl::Int32(0)),
+            Some(ScalarImpl::Int32(0)),
+            Some(ScalarImpl::Int32(0)),
+            Some(ScalarImpl::Int32(0)),
+            Some(ScalarImpl::Float64(OrderedF64::from(1.5))),
+        ]);
+        assert_eq!(
+            expr.eval_row(&row).unwrap(),
+            Some(ScalarImpl::Interval(IntervalUnit::new(0, 0, 1500)))
+        );
+    }
+}
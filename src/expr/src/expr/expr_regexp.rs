@@ -0,0 +1,385 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Regular expression functions. Constant patterns are compiled once when the expression is
+//! built, other patterns are compiled for each row.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{Array, ArrayRef, DataChunk, Row};
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+
+use crate::expr::{BoxedExpression, Expression};
+use crate::vector_op::regexp::{
+    regexp_like, regexp_match, regexp_replace, regexp_split_to_array, RegexpContext,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexpFunction {
+    /// `~`, `~*` and `regexp_like(text, pattern [, flags])`.
+    Like,
+    /// `text SIMILAR TO pattern [ESCAPE escape_char]`.
+    SimilarTo,
+    /// `regexp_match(text, pattern [, flags])`.
+    Match,
+    /// `regexp_replace(text, pattern, replacement [, flags])`.
+    Replace,
+    /// `regexp_split_to_array(text, pattern [, flags])`.
+    SplitToArray,
+}
+
+impl RegexpFunction {
+    fn name(self) -> &'static str {
+        match self {
+            RegexpFunction::Like => "regexp_like",
+            RegexpFunction::SimilarTo => "similar_to",
+            RegexpFunction::Match => "regexp_match",
+            RegexpFunction::Replace => "regexp_replace",
+            RegexpFunction::SplitToArray => "regexp_split_to_array",
+        }
+    }
+
+    /// The index of the optional flags argument. For `SIMILAR TO`, the escape character takes the
+    /// place of the flags.
+    pub fn flags_index(self) -> usize {
+        match self {
+            RegexpFunction::Replace => 3,
+            _ => 2,
+        }
+    }
+
+    /// The flags used if the flags argument is missing.
+    pub fn default_flags(self) -> &'static str {
+        match self {
+            RegexpFunction::SimilarTo => "\\",
+            _ => "",
+        }
+    }
+
+    fn compile(self, pattern: &str, flags: &str) -> Result<RegexpContext> {
+        let ctx = match self {
+            RegexpFunction::SimilarTo => RegexpContext::from_similar_to(pattern, flags)?,
+            _ => RegexpContext::new(pattern, flags)?,
+        };
+        if ctx.global && self != RegexpFunction::Replace {
+            return Err(RwError::from(InvalidInputSyntax(format!(
+                "{}() does not support the \"global\" option",
+                self.name()
+            ))));
+        }
+        Ok(ctx)
+    }
+
+    fn eval(
+        self,
+        text: Option<&str>,
+        ctx: Option<&RegexpContext>,
+        replacement: Option<&str>,
+    ) -> Datum {
+        let (text, ctx) = match (text, ctx) {
+            (Some(text), Some(ctx)) => (text, ctx),
+            _ => return None,
+        };
+        match self {
+            RegexpFunction::Like | RegexpFunction::SimilarTo => {
+                Some(ScalarImpl::Bool(regexp_like(text, ctx)))
+            }
+            RegexpFunction::Match => regexp_match(text, ctx).map(ScalarImpl::List),
+            RegexpFunction::Replace => {
+                replacement.map(|r| ScalarImpl::Utf8(regexp_replace(text, ctx, r)))
+            }
+            RegexpFunction::SplitToArray => {
+                Some(ScalarImpl::List(regexp_split_to_array(text, ctx)))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum RegexpPattern {
+    /// The pattern and the flags are constants and have been compiled. `None` if either of them
+    /// is `NULL`.
+    Const(Option<RegexpContext>),
+    /// The pattern is compiled for each row.
+    Dynamic {
+        pattern: BoxedExpression,
+        flags: Option<BoxedExpression>,
+    },
+}
+
+#[derive(Debug)]
+pub struct RegexpExpression {
+    func: RegexpFunction,
+    return_type: DataType,
+    text: BoxedExpression,
+    pattern: RegexpPattern,
+    /// Only for `regexp_replace`.
+    replacement: Option<BoxedExpression>,
+}
+
+impl RegexpExpression {
+    /// Builds the expression with a constant pattern and flags, which are compiled here.
+    pub fn with_const_pattern(
+        func: RegexpFunction,
+        return_type: DataType,
+        text: BoxedExpression,
+        pattern: Option<&str>,
+        flags: Option<&str>,
+        replacement: Option<BoxedExpression>,
+    ) -> Result<Self> {
+        let ctx = match (pattern, flags) {
+            (Some(pattern), Some(flags)) => Some(func.compile(pattern, flags)?),
+            _ => None,
+        };
+        Ok(Self {
+            func,
+            return_type,
+            text,
+            pattern: RegexpPattern::Const(ctx),
+            replacement,
+        })
+    }
+
+    pub fn with_dynamic_pattern(
+        func: RegexpFunction,
+        return_type: DataType,
+        text: BoxedExpression,
+        pattern: BoxedExpression,
+        flags: Option<BoxedExpression>,
+        replacement: Option<BoxedExpression>,
+    ) -> Self {
+        Self {
+            func,
+            return_type,
+            text,
+            pattern: RegexpPattern::Dynamic { pattern, flags },
+            replacement,
+        }
+    }
+
+    /// Evaluates the arguments of one row. `pattern` and `flags` are ignored if the pattern is a
+    /// constant.
+    fn eval_args(
+        &self,
+        text: Option<&str>,
+        pattern: Option<&str>,
+        flags: Option<&str>,
+        replacement: Option<&str>,
+    ) -> Result<Datum> {
+        let compiled;
+        let ctx = match &self.pattern {
+            RegexpPattern::Const(ctx) => ctx.as_ref(),
+            RegexpPattern::Dynamic {
+                flags: flags_expr, ..
+            } => {
+                let flags = if flags_expr.is_some() {
+                    flags
+                } else {
+                    Some(self.func.default_flags())
+                };
+                match (pattern, flags) {
+                    (Some(pattern), Some(flags)) => {
+                        compiled = self.func.compile(pattern, flags)?;
+                        Some(&compiled)
+                    }
+                    _ => None,
+                }
+            }
+        };
+        Ok(self.func.eval(text, ctx, replacement))
+    }
+}
+
+impl Expression for RegexpExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let text = self.text.eval(input)?;
+        let replacement = self
+            .replacement
+            .as_ref()
+            .map(|e| e.eval(input))
+            .transpose()?;
+        let (pattern, flags) = match &self.pattern {
+            RegexpPattern::Const(_) => (None, None),
+            RegexpPattern::Dynamic { pattern, flags } => (
+                Some(pattern.eval(input)?),
+                flags.as_ref().map(|e| e.eval(input)).transpose()?,
+            ),
+        };
+        fn value_at(array: &Option<ArrayRef>, idx: usize) -> Option<&str> {
+            array
+                .as_ref()
+                .and_then(|array| array.as_utf8().value_at(idx))
+        }
+
+        let mut builder = self.return_type.create_array_builder(input.capacity())?;
+        for idx in 0..input.capacity() {
+            if let Some(visibility) = input.get_visibility_ref() {
+                if !visibility.is_set(idx)? {
+                    continue;
+                }
+            }
+            let datum = self.eval_args(
+                text.as_utf8().value_at(idx),
+                value_at(&pattern, idx),
+                value_at(&flags, idx),
+                value_at(&replacement, idx),
+            )?;
+            builder.append_datum(&datum)?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let eval_str = |expr: Option<&BoxedExpression>| -> Result<Option<String>> {
+            Ok(match expr {
+                Some(expr) => expr.eval_row(input)?.map(ScalarImpl::into_utf8),
+                None => None,
+            })
+        };
+        let text = eval_str(Some(&self.text))?;
+        let replacement = eval_str(self.replacement.as_ref())?;
+        let (pattern, flags) = match &self.pattern {
+            RegexpPattern::Const(_) => (None, None),
+            RegexpPattern::Dynamic { pattern, flags } => {
+                (eval_str(Some(pattern))?, eval_str(flags.as_ref())?)
+            }
+        };
+        self.eval_args(
+            text.as_deref(),
+            pattern.as_deref(),
+            flags.as_deref(),
+            replacement.as_deref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::ListValue;
+
+    use super::*;
+    use crate::expr::LiteralExpression;
+
+    fn varchar(v: Option<&str>) -> BoxedExpression {
+        Box::new(LiteralExpression::new(
+            DataType::Varchar,
+            v.map(|v| ScalarImpl::Utf8(v.to_owned())),
+        ))
+    }
+
+    fn list(v: &str) -> Datum {
+        Some(ScalarImpl::List(ListValue::new(vec![Some(
+            ScalarImpl::Utf8(v.to_owned()),
+        )])))
+    }
+
+    #[test]
+    fn test_const_and_dynamic_pattern() {
+        let list_type = DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        };
+
+        let expr = RegexpExpression::with_const_pattern(
+            RegexpFunction::Match,
+            list_type.clone(),
+            varchar(Some("get /index.html")),
+            Some("^([a-z]+) "),
+            Some("i"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(expr.eval_row(&Row::new(vec![])).unwrap(), list("get"));
+
+        let expr = RegexpExpression::with_dynamic_pattern(
+            RegexpFunction::Match,
+            list_type,
+            varchar(Some("GET /index.html")),
+            varchar(Some("^([A-Z]+) ")),
+            None,
+            None,
+        );
+        assert_eq!(expr.eval_row(&Row::new(vec![])).unwrap(), list("GET"));
+        let array = expr.eval(&DataChunk::new_dummy(2)).unwrap();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.datum_at(1), list("GET"));
+    }
+
+    #[test]
+    fn test_null_and_invalid_pattern() {
+        let expr = RegexpExpression::with_const_pattern(
+            RegexpFunction::Like,
+            DataType::Boolean,
+            varchar(Some("abc")),
+            None,
+            Some(""),
+            None,
+        )
+        .unwrap();
+        assert_eq!(expr.eval_row(&Row::new(vec![])).unwrap(), None);
+
+        assert!(RegexpExpression::with_const_pattern(
+            RegexpFunction::Match,
+            DataType::Boolean,
+            varchar(Some("abc")),
+            Some("b"),
+            Some("g"),
+            None,
+        )
+        .is_err());
+
+        let expr = RegexpExpression::with_dynamic_pattern(
+            RegexpFunction::Replace,
+            DataType::Varchar,
+            varchar(Some("a-b-c")),
+            varchar(Some("-")),
+            Some(varchar(Some("g"))),
+            Some(varchar(Some("+"))),
+        );
+        assert_eq!(
+            expr.eval_row(&Row::new(vec![])).unwrap(),
+            Some(ScalarImpl::Utf8("a+b+c".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_similar_to_escape() {
+        let similar_to = |text: &str, pattern: &str, escape: Option<&str>| {
+            RegexpExpression::with_dynamic_pattern(
+                RegexpFunction::SimilarTo,
+                DataType::Boolean,
+                varchar(Some(text)),
+                varchar(Some(pattern)),
+                escape.map(|e| varchar(Some(e))),
+                None,
+            )
+            .eval_row(&Row::new(vec![]))
+        };
+        // `\` is the escape character if `ESCAPE` is not given.
+        assert_eq!(
+            similar_to("a%", "a\\%", None).unwrap(),
+            Some(ScalarImpl::Bool(true))
+        );
+        assert_eq!(
+            similar_to("ab", "a#%", Some("#")).unwrap(),
+            Some(ScalarImpl::Bool(false))
+        );
+        assert!(similar_to("ab", "a%", Some("##")).is_err());
+    }
+}
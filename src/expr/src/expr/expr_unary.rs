@@ -23,7 +23,7 @@ use super::template::{UnaryBytesExpression, UnaryExpression};
 use crate::expr::expr_is_null::{IsNotNullExpression, IsNullExpression};
 use crate::expr::template::UnaryNullableExpression;
 use crate::expr::BoxedExpression;
-use crate::vector_op::arithmetic_op::{decimal_abs, general_abs, general_neg};
+use crate::vector_op::arithmetic_op::{decimal_abs, general_abs, general_neg, sqrt_f64};
 use crate::vector_op::ascii::ascii;
 use crate::vector_op::cast::*;
 use crate::vector_op::cmp::{is_false, is_not_false, is_not_true, is_true};
@@ -32,6 +32,7 @@ use crate::vector_op::length::length_default;
 use crate::vector_op::lower::lower;
 use crate::vector_op::ltrim::ltrim;
 use crate::vector_op::rtrim::rtrim;
+use crate::vector_op::to_char::to_timestamp_from_epoch;
 use crate::vector_op::trim::trim;
 use crate::vector_op::upper::upper;
 
@@ -87,6 +88,9 @@ macro_rules! gen_cast {
             { time, time, |x| Ok(x) },
             { boolean, boolean, |x| Ok(x) },
             { varchar, varchar, |x| Ok(x.into()) },
+            { jsonb, jsonb, |x| Ok(x.to_owned_scalar()) },
+            { bytea, bytea, |x| Ok(x.to_owned_scalar()) },
+            { uuid, uuid, |x| Ok(x) },
 
             { varchar, date, str_to_date },
             { varchar, time, str_to_time },
@@ -99,6 +103,9 @@ macro_rules! gen_cast {
             { varchar, float64, str_parse },
             { varchar, decimal, str_parse },
             { varchar, boolean, str_to_bool },
+            { varchar, jsonb, str_parse },
+            { varchar, bytea, str_parse },
+            { varchar, uuid, str_parse },
 
             { boolean, varchar, general_to_string },
             { int16, varchar, general_to_string },
@@ -107,6 +114,9 @@ macro_rules! gen_cast {
             { float32, varchar, general_to_string },
             { float64, varchar, general_to_string },
             { decimal, varchar, general_to_string },
+            { jsonb, varchar, general_to_string },
+            { bytea, varchar, general_to_string },
+            { uuid, varchar, general_to_string },
 
             { boolean, int32, general_cast },
             { int32, boolean, int32_to_bool },
@@ -144,7 +154,9 @@ macro_rules! gen_cast {
             { decimal, float32, to_f32 },
             { decimal, float64, to_f64 },
 
-            { date, timestamp, date_to_timestamp }
+            { date, timestamp, date_to_timestamp },
+            { timestamp, date, timestamp_to_date },
+            { timestamp, time, timestamp_to_time }
         }
     };
 }
@@ -212,6 +224,18 @@ pub fn new_unary_expr(
     use crate::expr::data_types::*;
 
     let expr: BoxedExpression = match (expr_type, return_type.clone(), child_expr.return_type()) {
+        (ProstType::Cast, DataType::Decimal(Some((precision, scale))), _) => {
+            // Cast to an unconstrained decimal first, then enforce the type modifier.
+            let child_expr = match child_expr.return_type() {
+                DataType::Decimal(_) => child_expr,
+                _ => gen_cast! { child_expr, DataType::Decimal(None), },
+            };
+            Box::new(UnaryExpression::<DecimalArray, DecimalArray, _>::new(
+                child_expr,
+                return_type,
+                move |x| dec_with_typmod(x, precision, scale),
+            ))
+        }
         (ProstType::Cast, _, _) => gen_cast! { child_expr, return_type, },
         (ProstType::Not, _, _) => {
             Box::new(UnaryNullableExpression::<BoolArray, BoolArray, _>::new(
@@ -279,6 +303,16 @@ pub fn new_unary_expr(
                 }
             }
         }
+        (ProstType::Sqrt, _, _) => Box::new(UnaryExpression::<F64Array, F64Array, _>::new(
+            child_expr,
+            return_type,
+            sqrt_f64,
+        )),
+        (ProstType::ToTimestamp, _, _) => Box::new(UnaryExpression::<F64Array, I64Array, _>::new(
+            child_expr,
+            return_type,
+            to_timestamp_from_epoch,
+        )),
         (expr, ret, child) => {
             return Err(ErrorCode::NotImplemented(format!(
                 "The expression {:?}({:?}) ->{:?} using vectorized expression framework is not supported yet.",
@@ -325,13 +359,16 @@ pub fn new_rtrim_expr(expr_ia1: BoxedExpression, return_type: DataType) -> Boxed
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use chrono::NaiveDate;
     use itertools::Itertools;
     use num_traits::FromPrimitive;
     use risingwave_common::array::column::Column;
     use risingwave_common::array::*;
     use risingwave_common::types::{
-        Decimal, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, Scalar, ScalarImpl,
+        ByteaVal, Decimal, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, Scalar,
+        ScalarImpl, UuidWrapper,
     };
     use risingwave_pb::data::data_type::TypeName;
     use risingwave_pb::data::DataType;
@@ -608,8 +645,13 @@ mod tests {
         assert_castible(DataType::Boolean, true);
         assert_castible(DataType::Float32, 3.0_f32);
         assert_castible(DataType::Float64, 3.0_f64);
-        assert_castible(DataType::Decimal, Decimal::from_f32(3.15_f32).unwrap());
+        assert_castible(
+            DataType::Decimal(None),
+            Decimal::from_f32(3.15_f32).unwrap(),
+        );
         assert_castible(DataType::Varchar, "abc".to_string());
+        assert_castible(DataType::Bytea, ByteaVal::from(vec![1u8, 2, 3]));
+        assert_castible(DataType::Uuid, UuidWrapper::default());
         assert_castible(DataType::Date, NaiveDateWrapper::with_days(100).unwrap());
         assert_castible(
             DataType::Time,
@@ -620,4 +662,38 @@ mod tests {
             NaiveDateTimeWrapper::with_secs_nsecs(1, 1).unwrap(),
         );
     }
+
+    #[test]
+    fn test_decimal_typmod_cast() {
+        use risingwave_common::types::DataType;
+        use risingwave_pb::expr::expr_node::Type as ExprType;
+
+        let cast = |v: ScalarImpl, from: DataType| {
+            let expr = new_unary_expr(
+                ExprType::Cast,
+                DataType::Decimal(Some((5, 2))),
+                Box::new(LiteralExpression::new(from, Some(v))) as BoxedExpression,
+            )
+            .unwrap();
+            expr.eval_row(&Row::new(vec![]))
+        };
+
+        assert_eq!(
+            cast(
+                Decimal::from_str("123.456").unwrap().into(),
+                DataType::Decimal(None)
+            )
+            .unwrap(),
+            Some(Decimal::from_str("123.46").unwrap().into())
+        );
+        assert_eq!(
+            cast(1_i32.into(), DataType::Int32).unwrap(),
+            Some(Decimal::from_str("1.00").unwrap().into())
+        );
+        assert!(cast(
+            Decimal::from_str("1234.5").unwrap().into(),
+            DataType::Decimal(None)
+        )
+        .is_err());
+    }
 }
@@ -27,6 +27,8 @@ mod expr_in;
 mod expr_input_ref;
 mod expr_is_null;
 mod expr_literal;
+mod expr_make_interval;
+mod expr_regexp;
 mod expr_ternary_bytes;
 pub mod expr_unary;
 mod template;
@@ -49,6 +51,8 @@ use crate::expr::expr_array::ArrayExpression;
 use crate::expr::expr_coalesce::CoalesceExpression;
 use crate::expr::expr_concat_ws::ConcatWsExpression;
 use crate::expr::expr_field::FieldExpression;
+use crate::expr::expr_make_interval::MakeIntervalExpression;
+use crate::expr::expr_regexp::RegexpFunction;
 
 pub type ExpressionRef = Arc<dyn Expression>;
 
@@ -80,21 +84,35 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
 
     match prost.get_expr_type()? {
         Cast | Upper | Lower | Not | IsTrue | IsNotTrue | IsFalse | IsNotFalse | IsNull
-        | IsNotNull | Neg | Ascii | Abs => build_unary_expr_prost(prost),
+        | IsNotNull | Neg | Ascii | Abs | Sqrt => build_unary_expr_prost(prost),
         Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Add
         | Subtract | Multiply | Divide | Modulus | Extract | RoundDigit | TumbleStart
-        | Position => build_binary_expr_prost(prost),
-        And | Or | IsDistinctFrom => build_nullable_binary_expr_prost(prost),
+        | Position | JsonbContains | JsonbExists | DateTrunc | DatePart | Age => {
+            build_binary_expr_prost(prost)
+        }
+        AtTimeZone => build_at_time_zone_expr(prost),
+        And | Or | IsDistinctFrom | JsonbAccessInner | JsonbAccessStr | JsonbExtractPath
+        | JsonbExtractPathText => build_nullable_binary_expr_prost(prost),
         Coalesce => CoalesceExpression::try_from(prost).map(|d| Box::new(d) as BoxedExpression),
         Substr => build_substr_expr(prost),
         Length => build_length_expr(prost),
         Replace => build_replace_expr(prost),
-        Like => build_like_expr(prost),
+        Like | ILike => build_like_expr(prost),
+        SimilarTo => build_regexp_expr(prost, RegexpFunction::SimilarTo),
+        RegexpLike => build_regexp_expr(prost, RegexpFunction::Like),
+        RegexpMatch => build_regexp_expr(prost, RegexpFunction::Match),
+        RegexpReplace => build_regexp_expr(prost, RegexpFunction::Replace),
+        RegexpSplitToArray => build_regexp_expr(prost, RegexpFunction::SplitToArray),
         Trim => build_trim_expr(prost),
         Ltrim => build_ltrim_expr(prost),
         Rtrim => build_rtrim_expr(prost),
         ConcatWs => ConcatWsExpression::try_from(prost).map(|d| Box::new(d) as BoxedExpression),
         SplitPart => build_split_part_expr(prost),
+        ToChar => build_to_char_expr(prost),
+        ToTimestamp => build_to_timestamp_expr(prost),
+        MakeInterval => {
+            MakeIntervalExpression::try_from(prost).map(|d| Box::new(d) as BoxedExpression)
+        }
         ConstantValue => LiteralExpression::try_from(prost).map(|d| Box::new(d) as BoxedExpression),
         InputRef => InputRefExpression::try_from(prost).map(|d| Box::new(d) as BoxedExpression),
         Case => build_case_expr(prost),
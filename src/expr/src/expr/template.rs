@@ -386,7 +386,8 @@ macro_rules! for_all_cmp_variants {
             { date, date, date, $general_f },
             { boolean, boolean, boolean, $general_f },
             { timestamp, date, timestamp, $general_f },
-            { date, timestamp, timestamp, $general_f }
+            { date, timestamp, timestamp, $general_f },
+            { uuid, uuid, uuid, $general_f }
         }
     };
 }
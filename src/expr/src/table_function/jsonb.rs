@@ -0,0 +1,162 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::Row;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, JsonbRef, JsonbVal, ScalarImpl, ScalarRef};
+
+use super::TableFunction;
+use crate::expr::BoxedExpression;
+
+/// Evaluates the only argument of a jsonb table function, which is `None` if it is `NULL`.
+fn eval_jsonb_arg(args: &[BoxedExpression], input: &Row) -> Result<Option<JsonbVal>> {
+    match args[0].eval_row(input)? {
+        Some(ScalarImpl::Jsonb(v)) => Ok(Some(v)),
+        None => Ok(None),
+        Some(v) => {
+            Err(ErrorCode::InternalError(format!("expect jsonb argument, but got {:?}", v)).into())
+        }
+    }
+}
+
+fn check_args(name: &str, args: &[BoxedExpression]) -> Result<()> {
+    if args.len() != 1 || args[0].return_type() != DataType::Jsonb {
+        return Err(ErrorCode::InternalError(format!(
+            "{} expects exactly one jsonb argument",
+            name
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// `jsonb_array_elements(jsonb)`, which outputs the elements of a json array as `jsonb` values.
+#[derive(Debug)]
+pub struct JsonbArrayElements {
+    args: Vec<BoxedExpression>,
+    return_types: Vec<DataType>,
+}
+
+impl JsonbArrayElements {
+    pub fn new(args: Vec<BoxedExpression>, return_types: Vec<DataType>) -> Result<Self> {
+        check_args("jsonb_array_elements", &args)?;
+        Ok(Self { args, return_types })
+    }
+}
+
+impl TableFunction for JsonbArrayElements {
+    fn return_types(&self) -> Vec<DataType> {
+        self.return_types.clone()
+    }
+
+    fn eval(&self, input: &Row) -> Result<Vec<Row>> {
+        let json = match eval_jsonb_arg(&self.args, input)? {
+            Some(json) => json,
+            None => return Ok(vec![]),
+        };
+        let rows = JsonbRef::from(json.value())
+            .array_elements()?
+            .map(|elem| Row::new(vec![Some(ScalarImpl::Jsonb(elem.to_owned_scalar()))]))
+            .collect();
+        Ok(rows)
+    }
+}
+
+/// `jsonb_each(jsonb)`, which outputs the fields of a json object as `(key, value)` pairs.
+#[derive(Debug)]
+pub struct JsonbEach {
+    args: Vec<BoxedExpression>,
+    return_types: Vec<DataType>,
+}
+
+impl JsonbEach {
+    pub fn new(args: Vec<BoxedExpression>, return_types: Vec<DataType>) -> Result<Self> {
+        check_args("jsonb_each", &args)?;
+        Ok(Self { args, return_types })
+    }
+}
+
+impl TableFunction for JsonbEach {
+    fn return_types(&self) -> Vec<DataType> {
+        self.return_types.clone()
+    }
+
+    fn eval(&self, input: &Row) -> Result<Vec<Row>> {
+        let json = match eval_jsonb_arg(&self.args, input)? {
+            Some(json) => json,
+            None => return Ok(vec![]),
+        };
+        let rows = JsonbRef::from(json.value())
+            .object_key_values()?
+            .map(|(key, value)| {
+                Row::new(vec![
+                    Some(ScalarImpl::Utf8(key.to_string())),
+                    Some(ScalarImpl::Jsonb(value.to_owned_scalar())),
+                ])
+            })
+            .collect();
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::Scalar;
+
+    use super::*;
+    use crate::expr::LiteralExpression;
+
+    fn jsonb_literal(s: &str) -> BoxedExpression {
+        let json: JsonbVal = s.parse().unwrap();
+        Box::new(LiteralExpression::new(
+            DataType::Jsonb,
+            Some(json.to_scalar_value()),
+        ))
+    }
+
+    #[test]
+    fn test_jsonb_array_elements() {
+        let func = JsonbArrayElements::new(
+            vec![jsonb_literal(r#"[1, "a", {"b": null}]"#)],
+            vec![DataType::Jsonb],
+        )
+        .unwrap();
+        let rows = func.eval(&Row::new(vec![])).unwrap();
+        let elems = rows
+            .iter()
+            .map(|row| match &row[0] {
+                Some(ScalarImpl::Jsonb(v)) => v.to_string(),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(elems, vec!["1", r#""a""#, r#"{"b": null}"#]);
+
+        let func =
+            JsonbArrayElements::new(vec![jsonb_literal("{}")], vec![DataType::Jsonb]).unwrap();
+        assert!(func.eval(&Row::new(vec![])).is_err());
+    }
+
+    #[test]
+    fn test_jsonb_each() {
+        let func = JsonbEach::new(
+            vec![jsonb_literal(r#"{"b": [2], "a": 1}"#)],
+            vec![DataType::Varchar, DataType::Jsonb],
+        )
+        .unwrap();
+        let rows = func.eval(&Row::new(vec![])).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][0], Some(ScalarImpl::Utf8("a".to_string())));
+        assert_eq!(rows[1][1], Some(ScalarImpl::Jsonb("[2]".parse().unwrap())));
+    }
+}
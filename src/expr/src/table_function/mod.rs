@@ -0,0 +1,107 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Table functions, which return a set of rows for each row of arguments.
+
+mod jsonb;
+mod regexp;
+
+use std::fmt::Debug;
+
+use risingwave_common::array::Row;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_pb::expr::expr_node::Type as ExprType;
+use risingwave_pb::expr::table_function::Type as ProstTableFunctionType;
+use risingwave_pb::expr::TableFunction as ProstTableFunction;
+
+pub use self::jsonb::*;
+pub use self::regexp::*;
+use crate::expr::build_from_prost;
+
+pub trait TableFunction: Debug + Sync + Send {
+    /// The types of the output columns.
+    fn return_types(&self) -> Vec<DataType>;
+
+    /// Evaluates the arguments on `input` and returns the rows output by the function.
+    fn eval(&self, input: &Row) -> Result<Vec<Row>>;
+}
+
+pub type BoxedTableFunction = Box<dyn TableFunction>;
+
+/// Evaluates `func` on `input`, and returns `input` followed by each row returned by `func` and
+/// its 1-based ordinality, which is how a table function referencing the columns of the preceding
+/// `FROM` items outputs its rows.
+pub fn eval_lateral(func: &dyn TableFunction, input: &Row) -> Result<Vec<Row>> {
+    let rows = func
+        .eval(input)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut values = Vec::with_capacity(input.size() + row.size() + 1);
+            values.extend(input.values().cloned());
+            values.extend(row.0);
+            values.push(Some(ScalarImpl::Int64(i as i64 + 1)));
+            Row::new(values)
+        })
+        .collect();
+    Ok(rows)
+}
+
+/// The types of the output columns of a lateral table function on an input with `input_types`.
+/// See [`eval_lateral`].
+pub fn lateral_return_types(func: &dyn TableFunction, input_types: &[DataType]) -> Vec<DataType> {
+    input_types
+        .iter()
+        .cloned()
+        .chain(func.return_types())
+        .chain(std::iter::once(DataType::Int64))
+        .collect()
+}
+
+pub fn build_table_function_from_prost(prost: &ProstTableFunction) -> Result<BoxedTableFunction> {
+    let args = prost
+        .get_args()
+        .iter()
+        .map(build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    let return_types = prost
+        .get_return_types()
+        .iter()
+        .map(DataType::from)
+        .collect::<Vec<_>>();
+    let func: BoxedTableFunction = match prost.get_function_type()? {
+        ProstTableFunctionType::JsonbArrayElements => {
+            Box::new(JsonbArrayElements::new(args, return_types)?)
+        }
+        ProstTableFunctionType::JsonbEach => Box::new(JsonbEach::new(args, return_types)?),
+        ProstTableFunctionType::RegexpMatches => {
+            // Compile the pattern only once if it does not depend on the input.
+            let is_const = prost
+                .get_args()
+                .iter()
+                .skip(1)
+                .all(|arg| matches!(arg.get_expr_type(), Ok(ExprType::ConstantValue)));
+            if is_const {
+                Box::new(RegexpMatches::with_const_pattern(args, return_types)?)
+            } else {
+                Box::new(RegexpMatches::with_dynamic_pattern(args, return_types)?)
+            }
+        }
+        ProstTableFunctionType::Invalid => {
+            return Err(ErrorCode::InternalError("Unrecognized table function.".to_string()).into())
+        }
+    };
+    Ok(func)
+}
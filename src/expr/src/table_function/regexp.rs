@@ -0,0 +1,196 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::Row;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use super::TableFunction;
+use crate::expr::BoxedExpression;
+use crate::vector_op::regexp::{regexp_matches, RegexpContext};
+
+/// `regexp_matches(text, pattern [, flags])`, which outputs the captured substrings of each match
+/// as a `varchar[]`. Only the first match is output without the `g` flag.
+#[derive(Debug)]
+pub struct RegexpMatches {
+    text: BoxedExpression,
+    pattern: MatchesPattern,
+    return_types: Vec<DataType>,
+}
+
+#[derive(Debug)]
+enum MatchesPattern {
+    /// The pattern and the flags are constants and have been compiled. `None` if either of them
+    /// is `NULL`.
+    Const(Option<RegexpContext>),
+    /// The pattern is compiled for each row.
+    Dynamic {
+        pattern: BoxedExpression,
+        flags: Option<BoxedExpression>,
+    },
+}
+
+/// Evaluates a `varchar` argument on `input`. A missing argument is an empty string.
+fn eval_str(expr: Option<&BoxedExpression>, input: &Row) -> Result<Option<String>> {
+    match expr {
+        Some(expr) => Ok(expr.eval_row(input)?.map(ScalarImpl::into_utf8)),
+        None => Ok(Some(String::new())),
+    }
+}
+
+fn compile(pattern: Option<String>, flags: Option<String>) -> Result<Option<RegexpContext>> {
+    match (pattern, flags) {
+        (Some(pattern), Some(flags)) => Ok(Some(RegexpContext::new(&pattern, &flags)?)),
+        _ => Ok(None),
+    }
+}
+
+impl RegexpMatches {
+    /// Builds the function with a constant pattern and flags, which are compiled here.
+    pub fn with_const_pattern(
+        args: Vec<BoxedExpression>,
+        return_types: Vec<DataType>,
+    ) -> Result<Self> {
+        let (text, pattern, flags) = Self::split_args(args)?;
+        let empty = Row::new(vec![]);
+        let ctx = compile(
+            eval_str(Some(&pattern), &empty)?,
+            eval_str(flags.as_ref(), &empty)?,
+        )?;
+        Ok(Self {
+            text,
+            pattern: MatchesPattern::Const(ctx),
+            return_types,
+        })
+    }
+
+    /// Builds the function with a pattern or flags depending on the input row.
+    pub fn with_dynamic_pattern(
+        args: Vec<BoxedExpression>,
+        return_types: Vec<DataType>,
+    ) -> Result<Self> {
+        let (text, pattern, flags) = Self::split_args(args)?;
+        Ok(Self {
+            text,
+            pattern: MatchesPattern::Dynamic { pattern, flags },
+            return_types,
+        })
+    }
+
+    fn split_args(
+        args: Vec<BoxedExpression>,
+    ) -> Result<(BoxedExpression, BoxedExpression, Option<BoxedExpression>)> {
+        if !(2..=3).contains(&args.len()) {
+            return Err(ErrorCode::InternalError(
+                "regexp_matches expects 2 or 3 arguments".to_string(),
+            )
+            .into());
+        }
+        let mut args = args.into_iter();
+        let text = args.next().unwrap();
+        let pattern = args.next().unwrap();
+        Ok((text, pattern, args.next()))
+    }
+}
+
+impl TableFunction for RegexpMatches {
+    fn return_types(&self) -> Vec<DataType> {
+        self.return_types.clone()
+    }
+
+    fn eval(&self, input: &Row) -> Result<Vec<Row>> {
+        let compiled;
+        let ctx = match &self.pattern {
+            MatchesPattern::Const(ctx) => ctx.as_ref(),
+            MatchesPattern::Dynamic { pattern, flags } => {
+                compiled = compile(
+                    eval_str(Some(pattern), input)?,
+                    eval_str(flags.as_ref(), input)?,
+                )?;
+                compiled.as_ref()
+            }
+        };
+        let (text, ctx) = match (self.text.eval_row(input)?, ctx) {
+            (Some(text), Some(ctx)) => (text.into_utf8(), ctx),
+            _ => return Ok(vec![]),
+        };
+        let rows = regexp_matches(&text, ctx)
+            .into_iter()
+            .map(|list| Row::new(vec![Some(ScalarImpl::List(list))]))
+            .collect();
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{InputRefExpression, LiteralExpression};
+
+    fn varchar_literal(s: &str) -> BoxedExpression {
+        Box::new(LiteralExpression::new(
+            DataType::Varchar,
+            Some(ScalarImpl::Utf8(s.to_owned())),
+        ))
+    }
+
+    #[test]
+    fn test_regexp_matches() {
+        let return_types = vec![DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        }];
+        let args = || {
+            vec![
+                varchar_literal("level=warn code=42 level=error code=7"),
+                varchar_literal(r"level=(\w+) code=(\d+)"),
+            ]
+        };
+
+        let func = RegexpMatches::with_const_pattern(args(), return_types.clone()).unwrap();
+        assert_eq!(func.eval(&Row::new(vec![])).unwrap().len(), 1);
+
+        let mut args = args();
+        args.push(varchar_literal("g"));
+        let func = RegexpMatches::with_const_pattern(args, return_types).unwrap();
+        let rows = func.eval(&Row::new(vec![])).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][0].clone().unwrap().into_list().values().len(), 2);
+    }
+
+    #[test]
+    fn test_regexp_matches_dynamic_pattern() {
+        let return_types = vec![DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        }];
+        // regexp_matches('a1b22', $0, 'g')
+        let func = RegexpMatches::with_dynamic_pattern(
+            vec![
+                varchar_literal("a1b22"),
+                Box::new(InputRefExpression::new(DataType::Varchar, 0)),
+                varchar_literal("g"),
+            ],
+            return_types,
+        )
+        .unwrap();
+        let eval = |pattern: Option<&str>| {
+            func.eval(&Row::new(vec![
+                pattern.map(|p| ScalarImpl::Utf8(p.to_owned()))
+            ]))
+        };
+        assert_eq!(eval(Some(r"\d")).unwrap().len(), 3);
+        assert_eq!(eval(Some(r"\d+")).unwrap().len(), 2);
+        assert!(eval(None).unwrap().is_empty());
+        assert!(eval(Some("(")).is_err());
+    }
+}
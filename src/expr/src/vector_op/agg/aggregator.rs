@@ -18,6 +18,8 @@ use risingwave_common::types::*;
 use risingwave_pb::expr::AggCall;
 
 use crate::expr::AggKind;
+use crate::vector_op::agg::approx_count_distinct::ApproxCountDistinct;
+use crate::vector_op::agg::array_agg::ArrayAgg;
 use crate::vector_op::agg::count_star::CountStar;
 use crate::vector_op::agg::functions::*;
 use crate::vector_op::agg::general_agg::*;
@@ -56,6 +58,7 @@ pub trait Aggregator: Send + 'static {
 
 pub type BoxedAggState = Box<dyn Aggregator>;
 
+#[derive(Clone)]
 pub struct AggStateFactory {
     // When agg func is count(*), the args is empty and input type is None.
     input_type: Option<DataType>,
@@ -132,6 +135,24 @@ pub fn create_agg_state_unary(
 ) -> Result<Box<dyn Aggregator>> {
     use crate::expr::data_types::*;
 
+    // These aggregators work on datums of any input type.
+    match agg_type {
+        AggKind::ArrayAgg => {
+            return Ok(Box::new(ArrayAgg::new(
+                return_type,
+                input_col_idx,
+                distinct,
+            )));
+        }
+        AggKind::ApproxCountDistinct => {
+            return Ok(Box::new(ApproxCountDistinct::new(
+                return_type,
+                input_col_idx,
+            )));
+        }
+        _ => {}
+    }
+
     macro_rules! gen_arms {
         [$(($agg:ident, $fn:expr, $in:tt, $ret:tt, $init_result:expr)),* $(,)?] => {
             match (
@@ -205,6 +226,12 @@ pub fn create_agg_state_unary(
         (Max, max_struct, struct_type, struct_type, None),
         (Max, max_str, varchar, varchar, None),
         (Max, max_list, list, list, None),
+        (BitAnd, bit_and, int16, int16, None),
+        (BitAnd, bit_and, int32, int32, None),
+        (BitAnd, bit_and, int64, int64, None),
+        (BitOr, bit_or, int16, int16, None),
+        (BitOr, bit_or, int32, int32, None),
+        (BitOr, bit_or, int64, int64, None),
         // Global Agg
         (Sum, sum, int64, int64, None),
         // We remark that SingleValue does not produce a runtime error when it receives zero row.
@@ -230,7 +257,7 @@ mod tests {
     #[test]
     fn test_create_agg_state() {
         let int64_type = DataType::Int64;
-        let decimal_type = DataType::Decimal;
+        let decimal_type = DataType::Decimal(None);
         let bool_type = DataType::Boolean;
         let char_type = DataType::Varchar;
 
@@ -274,5 +301,14 @@ mod tests {
         test_create! { decimal_type, SingleValue, decimal_type, is_ok }
         test_create! { bool_type, SingleValue, bool_type, is_ok }
         test_create! { char_type, SingleValue, char_type, is_ok }
+
+        test_create! { int64_type, BitAnd, int64_type, is_ok }
+        test_create! { decimal_type, BitOr, decimal_type, is_err }
+
+        let list_type = DataType::List {
+            datatype: Box::new(char_type.clone()),
+        };
+        test_create! { char_type, ArrayAgg, list_type, is_ok }
+        test_create! { char_type, ApproxCountDistinct, int64_type, is_ok }
     }
 }
@@ -0,0 +1,180 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use risingwave_common::array::*;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::*;
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// Number of hash bits used to pick a register.
+const INDEX_BITS: u32 = 12;
+const NUM_REGISTERS: usize = 1 << INDEX_BITS;
+
+/// A `HyperLogLog` sketch estimating the number of distinct non-null values, with a standard error
+/// of about 1.6%. Small cardinalities are estimated with linear counting and are nearly exact.
+#[derive(Clone)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self {
+            registers: vec![0; NUM_REGISTERS],
+        }
+    }
+
+    pub fn add(&mut self, scalar: &ScalarImpl) {
+        let mut hasher = DefaultHasher::new();
+        scalar.hash(&mut hasher);
+        let hash = hasher.finish();
+        let index = (hash >> (64 - INDEX_BITS)) as usize;
+        // The sentinel bit bounds the rank by `64 - INDEX_BITS + 1`.
+        let rank = ((hash << INDEX_BITS) | (1 << (INDEX_BITS - 1))).leading_zeros() + 1;
+        self.registers[index] = self.registers[index].max(rank as u8);
+    }
+
+    pub fn count(&self) -> i64 {
+        let m = NUM_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|rank| 2f64.powi(-(*rank as i32)))
+            .sum();
+        let estimate = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|rank| **rank == 0).count();
+        let estimate = if estimate <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            estimate
+        };
+        estimate.round() as i64
+    }
+}
+
+pub struct ApproxCountDistinct {
+    return_type: DataType,
+    input_col_idx: usize,
+    sketch: HyperLogLog,
+}
+
+impl ApproxCountDistinct {
+    pub fn new(return_type: DataType, input_col_idx: usize) -> Self {
+        Self {
+            return_type,
+            input_col_idx,
+            sketch: HyperLogLog::new(),
+        }
+    }
+
+    fn add_datum(&mut self, datum: Datum) {
+        if let Some(scalar) = datum {
+            self.sketch.add(&scalar);
+        }
+    }
+}
+
+impl Aggregator for ApproxCountDistinct {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        let datum = input
+            .column_at(self.input_col_idx)
+            .array_ref()
+            .datum_at(row_id);
+        self.add_datum(datum);
+        Ok(())
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        for row_id in 0..array.len() {
+            self.add_datum(array.datum_at(row_id));
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        match builder {
+            ArrayBuilderImpl::Int64(b) => b.append(Some(self.sketch.count())),
+            _ => Err(ErrorCode::InternalError(
+                "Unexpected builder for approx_count_distinct.".into(),
+            )
+            .into()),
+        }
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        let mut group_cnt = 0;
+        let mut groups_iter = groups.starting_indices().iter().peekable();
+        for row_id in groups.chunk_offset()..array.len() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                group_cnt += 1;
+                self.output(builder)?;
+                self.sketch = HyperLogLog::new();
+            }
+            self.add_datum(array.datum_at(row_id));
+
+            // reset state and exit when reach limit
+            if groups.is_reach_limit(group_cnt) {
+                self.sketch = HyperLogLog::new();
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyper_log_log() {
+        let mut sketch = HyperLogLog::new();
+        assert_eq!(sketch.count(), 0);
+        for i in 0..1000 {
+            sketch.add(&ScalarImpl::Int64(i % 100));
+        }
+        assert!((sketch.count() - 100).abs() <= 2);
+
+        let mut sketch = HyperLogLog::new();
+        for i in 0..100_000 {
+            sketch.add(&ScalarImpl::Int64(i));
+        }
+        let error = (sketch.count() - 100_000).abs() as f64 / 100_000.0;
+        assert!(error < 0.05, "error too large: {}", error);
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use risingwave_common::array::*;
+use risingwave_common::error::Result;
+use risingwave_common::types::*;
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// `array_agg` collects the input values, nulls included, into a list. With `distinct`, only the
+/// first occurrence of each value is kept.
+pub struct ArrayAgg {
+    return_type: DataType,
+    input_col_idx: usize,
+    distinct: bool,
+    values: Vec<Datum>,
+    seen: HashSet<Datum>,
+}
+
+impl ArrayAgg {
+    pub fn new(return_type: DataType, input_col_idx: usize, distinct: bool) -> Self {
+        Self {
+            return_type,
+            input_col_idx,
+            distinct,
+            values: vec![],
+            seen: HashSet::new(),
+        }
+    }
+
+    fn push(&mut self, datum: Datum) {
+        if !self.distinct || self.seen.insert(datum.clone()) {
+            self.values.push(datum);
+        }
+    }
+
+    fn result(&self) -> Datum {
+        if self.values.is_empty() {
+            None
+        } else {
+            Some(ListValue::new(self.values.clone()).into())
+        }
+    }
+
+    fn take_result(&mut self) -> Datum {
+        let result = self.result();
+        self.values.clear();
+        self.seen.clear();
+        result
+    }
+}
+
+impl Aggregator for ArrayAgg {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        let datum = input
+            .column_at(self.input_col_idx)
+            .array_ref()
+            .datum_at(row_id);
+        self.push(datum);
+        Ok(())
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        for row_id in 0..array.len() {
+            self.push(array.datum_at(row_id));
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        builder.append_datum(&self.result())
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let array = input.column_at(self.input_col_idx).array_ref();
+        let mut group_cnt = 0;
+        let mut groups_iter = groups.starting_indices().iter().peekable();
+        for row_id in groups.chunk_offset()..array.len() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                group_cnt += 1;
+                builder.append_datum(&self.take_result())?;
+            }
+            self.push(array.datum_at(row_id));
+
+            // reset state and exit when reach limit
+            if groups.is_reach_limit(group_cnt) {
+                self.take_result();
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_common::array::column::Column;
+
+    use super::*;
+
+    #[test]
+    fn test_array_agg() -> Result<()> {
+        let test_case = |distinct: bool, expected: Vec<Datum>| -> Result<()> {
+            let input = I32Array::from_slice(&[Some(1), None, Some(2), Some(1)])?;
+            let input_chunk = DataChunk::builder()
+                .columns(vec![Column::new(Arc::new(input.into()))])
+                .build();
+            let return_type = DataType::List {
+                datatype: Box::new(DataType::Int32),
+            };
+            let mut agg = ArrayAgg::new(return_type.clone(), 0, distinct);
+            agg.update(&input_chunk)?;
+            let mut builder = return_type.create_array_builder(1)?;
+            agg.output(&mut builder)?;
+            let output = builder.finish()?;
+            assert_eq!(output.datum_at(0), Some(ListValue::new(expected).into()));
+            Ok(())
+        };
+        let int = |v| Some(ScalarImpl::Int32(v));
+        test_case(false, vec![int(1), None, int(2), int(1)])?;
+        test_case(true, vec![int(1), None, int(2)])
+    }
+}
@@ -52,7 +52,7 @@ where
 }
 
 use std::convert::From;
-use std::ops::Add;
+use std::ops::{Add, BitAnd, BitOr};
 
 use risingwave_common::types::ScalarRef;
 
@@ -68,6 +68,30 @@ where
     Ok(res)
 }
 
+pub fn bit_and<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitAnd<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r & i),
+    };
+    Ok(res)
+}
+
+pub fn bit_or<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitOr<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r | i),
+    };
+    Ok(res)
+}
+
 pub fn min<'a, T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
 where
     T: ScalarRef<'a> + PartialOrd,
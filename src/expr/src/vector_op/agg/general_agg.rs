@@ -293,7 +293,7 @@ mod tests {
         let input = I64Array::from_slice(&[Some(1), Some(2), Some(3)])?;
         let agg_type = AggKind::Sum;
         let input_type = DataType::Int64;
-        let return_type = DataType::Decimal;
+        let return_type = DataType::Decimal(None);
         let actual = eval_agg(
             input_type,
             Arc::new(input.into()),
@@ -365,6 +365,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn vec_bit_and_or_int32() -> Result<()> {
+        let test_case = |agg_type: AggKind, expected: Option<i32>| -> Result<()> {
+            let input = I32Array::from_slice(&[Some(0b1100), None, Some(0b1010)]).unwrap();
+            let actual = eval_agg(
+                DataType::Int32,
+                Arc::new(input.into()),
+                &agg_type,
+                DataType::Int32,
+                ArrayBuilderImpl::Int32(I32ArrayBuilder::new(0)?),
+            )?;
+            let actual = actual.as_int32();
+            let actual = actual.iter().collect::<Vec<_>>();
+            assert_eq!(actual, &[expected]);
+            Ok(())
+        };
+        test_case(AggKind::BitAnd, Some(0b1000))?;
+        test_case(AggKind::BitOr, Some(0b1110))
+    }
+
     #[test]
     fn vec_count_int32() -> Result<()> {
         let test_case = |input: ArrayImpl, expected: &[Option<i64>]| -> Result<()> {
@@ -16,15 +16,21 @@ use std::any::type_name;
 use std::convert::TryInto;
 use std::fmt::Debug;
 
-use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, Signed};
-use risingwave_common::error::ErrorCode::{InternalError, NumericValueOutOfRange};
+use chrono::Duration;
+use num_traits::{
+    CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, Signed, ToPrimitive,
+};
+use risingwave_common::error::ErrorCode::{
+    InternalError, InvalidInputSyntax, NumericValueOutOfRange,
+};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{
     CheckedAdd as NaiveDateTimeCheckedAdd, Decimal, IntervalUnit, NaiveDateTimeWrapper,
-    NaiveDateWrapper,
+    NaiveDateWrapper, NaiveTimeWrapper, OrderedF64,
 };
 
 use super::cast::date_to_timestamp;
+use super::timezone::{timestampz_to_utc, utc_to_timestampz};
 
 #[inline(always)]
 pub fn general_add<T1, T2, T3>(l: T1, r: T2) -> Result<T3>
@@ -112,6 +118,15 @@ pub fn decimal_abs(decimal: Decimal) -> Result<Decimal> {
     Ok(Decimal::abs(&decimal).unwrap())
 }
 
+pub fn sqrt_f64(expr: OrderedF64) -> Result<OrderedF64> {
+    if expr.0 < 0.0 {
+        return Err(RwError::from(InvalidInputSyntax(
+            "cannot take square root of a negative number".to_string(),
+        )));
+    }
+    Ok(expr.0.sqrt().into())
+}
+
 #[inline(always)]
 pub fn general_atm<T1, T2, T3, F>(l: T1, r: T2, atm: F) -> Result<T3>
 where
@@ -137,13 +152,30 @@ where
     atm(l, r)
 }
 
+/// `timestamp - timestamp`, which returns whole days plus the remaining time as in PG, e.g.
+/// `1 day 12:00:00`. It used to return only the whole days, dropping the time part.
 #[inline(always)]
 pub fn timestamp_timestamp_sub<T1, T2, T3>(
     l: NaiveDateTimeWrapper,
     r: NaiveDateTimeWrapper,
 ) -> Result<IntervalUnit> {
     let tmp = l.0 - r.0;
-    Ok(IntervalUnit::new(0, tmp.num_days() as i32, 0))
+    let days = tmp.num_days();
+    let ms = (tmp - Duration::days(days)).num_milliseconds();
+    Ok(IntervalUnit::new(0, days as i32, ms))
+}
+
+#[inline(always)]
+pub fn timestampz_timestampz_sub<T1, T2, T3>(l: i64, r: i64) -> Result<IntervalUnit> {
+    const MICROS_PER_DAY: i64 = 24 * 3600 * 1_000_000;
+    let micros = l
+        .checked_sub(r)
+        .ok_or_else(|| RwError::from(NumericValueOutOfRange))?;
+    Ok(IntervalUnit::new(
+        0,
+        (micros / MICROS_PER_DAY) as i32,
+        micros % MICROS_PER_DAY / 1000,
+    ))
 }
 
 #[inline(always)]
@@ -199,6 +231,91 @@ pub fn timestamp_interval_sub<T1, T2, T3>(
     interval_timestamp_add::<T1, T2, T3>(r.negative(), l)
 }
 
+/// Adds an interval to a `timestamp with time zone`, where days are always 24 hours long.
+#[inline(always)]
+pub fn timestampz_interval_add<T1, T2, T3>(l: i64, r: IntervalUnit) -> Result<i64> {
+    let utc = NaiveDateTimeWrapper::new(timestampz_to_utc(l)?);
+    Ok(utc_to_timestampz(&utc.checked_add(r)?.0))
+}
+
+#[inline(always)]
+pub fn interval_timestampz_add<T1, T2, T3>(l: IntervalUnit, r: i64) -> Result<i64> {
+    timestampz_interval_add::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn timestampz_interval_sub<T1, T2, T3>(l: i64, r: IntervalUnit) -> Result<i64> {
+    timestampz_interval_add::<T1, T2, T3>(l, r.negative())
+}
+
+/// Adds an interval to a time, wrapping around midnight. Like PG, the months and days of the
+/// interval are ignored.
+#[inline(always)]
+pub fn time_interval_add<T1, T2, T3>(
+    l: NaiveTimeWrapper,
+    r: IntervalUnit,
+) -> Result<NaiveTimeWrapper> {
+    let (time, _) =
+        l.0.overflowing_add_signed(Duration::milliseconds(r.get_ms()));
+    Ok(NaiveTimeWrapper::new(time))
+}
+
+#[inline(always)]
+pub fn interval_time_add<T1, T2, T3>(
+    l: IntervalUnit,
+    r: NaiveTimeWrapper,
+) -> Result<NaiveTimeWrapper> {
+    time_interval_add::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn time_interval_sub<T1, T2, T3>(
+    l: NaiveTimeWrapper,
+    r: IntervalUnit,
+) -> Result<NaiveTimeWrapper> {
+    time_interval_add::<T1, T2, T3>(l, r.negative())
+}
+
+#[inline(always)]
+pub fn time_time_sub<T1, T2, T3>(l: NaiveTimeWrapper, r: NaiveTimeWrapper) -> Result<IntervalUnit> {
+    Ok(IntervalUnit::from_millis((l.0 - r.0).num_milliseconds()))
+}
+
+#[inline(always)]
+pub fn date_int_add<T1, T2, T3>(l: NaiveDateWrapper, r: i32) -> Result<NaiveDateWrapper> {
+    l.0.checked_add_signed(Duration::days(r as i64))
+        .map(NaiveDateWrapper::new)
+        .ok_or_else(|| InternalError("Date out of range".to_string()).into())
+}
+
+#[inline(always)]
+pub fn int_date_add<T1, T2, T3>(l: i32, r: NaiveDateWrapper) -> Result<NaiveDateWrapper> {
+    date_int_add::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn date_int_sub<T1, T2, T3>(l: NaiveDateWrapper, r: i32) -> Result<NaiveDateWrapper> {
+    l.0.checked_sub_signed(Duration::days(r as i64))
+        .map(NaiveDateWrapper::new)
+        .ok_or_else(|| InternalError("Date out of range".to_string()).into())
+}
+
+#[inline(always)]
+pub fn date_time_add<T1, T2, T3>(
+    l: NaiveDateWrapper,
+    r: NaiveTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    Ok(NaiveDateTimeWrapper::new(l.0.and_time(r.0)))
+}
+
+#[inline(always)]
+pub fn time_date_add<T1, T2, T3>(
+    l: NaiveTimeWrapper,
+    r: NaiveDateWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    date_time_add::<T2, T1, T3>(r, l)
+}
+
 #[inline(always)]
 pub fn interval_int_mul<T1, T2, T3>(l: IntervalUnit, r: T2) -> Result<IntervalUnit>
 where
@@ -216,13 +333,43 @@ where
     interval_int_mul::<T2, T1, T3>(r, l)
 }
 
+#[inline(always)]
+pub fn interval_float_mul<T1, T2, T3>(l: IntervalUnit, r: T2) -> Result<IntervalUnit>
+where
+    T2: ToPrimitive + Debug,
+{
+    r.to_f64()
+        .and_then(|r| l.checked_mul_float(r))
+        .ok_or_else(|| NumericValueOutOfRange.into())
+}
+
+#[inline(always)]
+pub fn float_interval_mul<T1, T2, T3>(l: T1, r: IntervalUnit) -> Result<IntervalUnit>
+where
+    T1: ToPrimitive + Debug,
+{
+    interval_float_mul::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn interval_float_div<T1, T2, T3>(l: IntervalUnit, r: T2) -> Result<IntervalUnit>
+where
+    T2: ToPrimitive + Debug,
+{
+    r.to_f64()
+        .filter(|r| *r != 0.0)
+        .and_then(|r| l.checked_mul_float(1.0 / r))
+        .ok_or_else(|| NumericValueOutOfRange.into())
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use risingwave_common::types::Decimal;
+    use chrono::{NaiveDateTime, NaiveTime};
+    use risingwave_common::types::{Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveTimeWrapper};
 
-    use crate::vector_op::arithmetic_op::general_add;
+    use crate::vector_op::arithmetic_op::*;
 
     #[test]
     fn test() {
@@ -231,4 +378,69 @@ mod tests {
             Decimal::from_str("2").unwrap()
         );
     }
+
+    #[test]
+    fn test_temporal() {
+        let ts = |s| {
+            NaiveDateTimeWrapper::new(
+                NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap(),
+            )
+        };
+        let hours = |h| IntervalUnit::from_millis(h * 3600 * 1000);
+        assert_eq!(
+            timestamp_timestamp_sub::<(), (), ()>(
+                ts("2022-01-02 12:00:00"),
+                ts("2022-01-01 00:00:00")
+            )
+            .unwrap(),
+            IntervalUnit::new(0, 1, 12 * 3600 * 1000)
+        );
+        assert_eq!(
+            timestamp_timestamp_sub::<(), (), ()>(
+                ts("2022-01-01 00:00:00"),
+                ts("2022-01-02 12:00:01")
+            )
+            .unwrap(),
+            IntervalUnit::new(0, -1, -(12 * 3600 + 1) * 1000)
+        );
+        assert_eq!(
+            timestamp_timestamp_sub::<(), (), ()>(
+                ts("2022-01-01 23:00:00"),
+                ts("2022-01-01 01:00:00")
+            )
+            .unwrap(),
+            hours(22)
+        );
+        assert_eq!(
+            timestampz_interval_add::<(), (), ()>(0, IntervalUnit::from_ymd(0, 1, 1)).unwrap(),
+            (31 + 1) * 24 * 3600 * 1_000_000
+        );
+        assert_eq!(
+            timestampz_timestampz_sub::<(), (), ()>(-36 * 3600 * 1_000_000, 0).unwrap(),
+            IntervalUnit::new(0, -1, -12 * 3600 * 1000)
+        );
+        let time = NaiveTimeWrapper::new(NaiveTime::from_hms(23, 0, 0));
+        assert_eq!(
+            time_interval_add::<(), (), ()>(time, hours(2)).unwrap(),
+            NaiveTimeWrapper::new(NaiveTime::from_hms(1, 0, 0))
+        );
+        assert_eq!(
+            interval_float_div::<(), _, ()>(IntervalUnit::from_days(1), 3).unwrap(),
+            hours(8)
+        );
+        assert!(interval_float_div::<(), _, ()>(IntervalUnit::from_days(1), 0.0).is_err());
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(
+            sqrt_f64(OrderedF64::from(6.25)).unwrap(),
+            OrderedF64::from(2.5)
+        );
+        assert_eq!(
+            sqrt_f64(OrderedF64::from(0.0)).unwrap(),
+            OrderedF64::from(0.0)
+        );
+        assert!(sqrt_f64(OrderedF64::from(-1.0)).is_err());
+    }
 }
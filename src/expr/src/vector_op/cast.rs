@@ -18,7 +18,9 @@ use std::str::FromStr;
 
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
 use num_traits::ToPrimitive;
-use risingwave_common::error::ErrorCode::{InternalError, InvalidInputSyntax};
+use risingwave_common::error::ErrorCode::{
+    InternalError, InvalidInputSyntax, NumericValueOutOfRange,
+};
 use risingwave_common::error::{parse_error, Result, RwError};
 use risingwave_common::types::{
     Decimal, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, OrderedF32, OrderedF64,
@@ -122,6 +124,16 @@ pub fn date_to_timestamp(elem: NaiveDateWrapper) -> Result<NaiveDateTimeWrapper>
     Ok(NaiveDateTimeWrapper::new(elem.0.and_hms(0, 0, 0)))
 }
 
+#[inline(always)]
+pub fn timestamp_to_date(elem: NaiveDateTimeWrapper) -> Result<NaiveDateWrapper> {
+    Ok(NaiveDateWrapper::new(elem.0.date()))
+}
+
+#[inline(always)]
+pub fn timestamp_to_time(elem: NaiveDateTimeWrapper) -> Result<NaiveTimeWrapper> {
+    Ok(NaiveTimeWrapper::new(elem.0.time()))
+}
+
 /// Define the cast function to primitive types.
 ///
 /// Due to the orphan rule, some data can't implement `TryFrom` trait for basic type.
@@ -179,6 +191,14 @@ pub fn dec_to_i64(elem: Decimal) -> Result<i64> {
     to_i64(elem.round_dp(0))
 }
 
+/// Coerces a decimal to `NUMERIC(precision, scale)`, rounding to `scale` fractional digits and
+/// rejecting values whose integral part does not fit.
+#[inline(always)]
+pub fn dec_with_typmod(elem: Decimal, precision: u32, scale: u32) -> Result<Decimal> {
+    elem.with_typmod(precision, scale)
+        .ok_or_else(|| RwError::from(NumericValueOutOfRange))
+}
+
 #[inline(always)]
 pub fn general_cast<T1, T2>(elem: T1) -> Result<T2>
 where
@@ -19,6 +19,7 @@ use std::fmt::Debug;
 use risingwave_common::array::{ListRef, StructRef};
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::ByteaRef;
 
 fn general_cmp<T1, T2, T3, F>(l: T1, r: T2, cmp: F) -> Result<bool>
 where
@@ -153,6 +154,12 @@ pub fn gen_str_cmp(op: Comparison) -> fn(&str, &str) -> Result<bool> {
     gen_cmp!(op)
 }
 
+#[inline(always)]
+pub fn gen_bytea_cmp(op: Comparison) -> fn(ByteaRef, ByteaRef) -> Result<bool> {
+    use crate::gen_cmp;
+    gen_cmp!(op)
+}
+
 #[macro_export]
 macro_rules! gen_cmp {
     ($op:expr) => {
@@ -176,6 +183,10 @@ pub fn str_is_distinct_from(l: Option<&str>, r: Option<&str>) -> Result<Option<b
     }
 }
 
+pub fn bytea_is_distinct_from(l: Option<ByteaRef>, r: Option<ByteaRef>) -> Result<Option<bool>> {
+    Ok(Some(l != r))
+}
+
 #[inline(always)]
 pub fn is_true(v: Option<bool>) -> Result<Option<bool>> {
     Ok(Some(v == Some(true)))
@@ -0,0 +1,266 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use risingwave_common::error::ErrorCode::InvalidParameterValue;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{
+    IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper,
+};
+
+use super::cast::date_to_timestamp;
+
+/// A field of a date/time value accepted by `date_part` and `date_trunc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeField {
+    Microseconds,
+    Milliseconds,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+    /// Day of the week, Sunday (0) to Saturday (6).
+    Dow,
+    /// Day of the week, Monday (1) to Sunday (7).
+    IsoDow,
+    /// Day of the year, from 1.
+    Doy,
+    /// Seconds since 1970-01-01 00:00:00.
+    Epoch,
+}
+
+impl DateTimeField {
+    /// Parses a field name case-insensitively, accepting the plural forms as PG does.
+    pub fn parse(name: &str) -> Result<Self> {
+        use DateTimeField::*;
+        const FIELDS: &[(&str, DateTimeField)] = &[
+            ("microseconds", Microseconds),
+            ("microsecond", Microseconds),
+            ("milliseconds", Milliseconds),
+            ("millisecond", Milliseconds),
+            ("second", Second),
+            ("seconds", Second),
+            ("minute", Minute),
+            ("minutes", Minute),
+            ("hour", Hour),
+            ("hours", Hour),
+            ("day", Day),
+            ("days", Day),
+            ("week", Week),
+            ("weeks", Week),
+            ("month", Month),
+            ("months", Month),
+            ("quarter", Quarter),
+            ("year", Year),
+            ("years", Year),
+            ("decade", Decade),
+            ("decades", Decade),
+            ("century", Century),
+            ("centuries", Century),
+            ("millennium", Millennium),
+            ("millennia", Millennium),
+            ("dow", Dow),
+            ("isodow", IsoDow),
+            ("doy", Doy),
+            ("epoch", Epoch),
+        ];
+        let name = name.trim();
+        FIELDS
+            .iter()
+            .find(|(field_name, _)| field_name.eq_ignore_ascii_case(name))
+            .map(|(_, field)| *field)
+            .ok_or_else(|| {
+                RwError::from(InvalidParameterValue(format!(
+                    "unit \"{}\" not recognized",
+                    name
+                )))
+            })
+    }
+}
+
+pub(super) fn unsupported_field(field: &str, type_name: &str) -> RwError {
+    RwError::from(InvalidParameterValue(format!(
+        "unit \"{}\" not supported for type {}",
+        field, type_name
+    )))
+}
+
+/// The seconds of a time, including the fractional part.
+fn seconds_of<T: Timelike>(time: &T) -> f64 {
+    // Leap seconds are folded into the 59th second.
+    time.second() as f64 + (time.nanosecond() % 1_000_000_000) as f64 / 1e9
+}
+
+/// The number of the `period`-year period containing `year`, where the first period starts at
+/// year 1, e.g. the 21st century starts at 2001.
+pub(super) fn first_based(year: i32, period: i32) -> i32 {
+    if year > 0 {
+        (year + period - 1) / period
+    } else {
+        -((period - 1 - (year - 1)) / period)
+    }
+}
+
+fn date_part_of_datetime(field: &str, datetime: NaiveDateTime) -> Result<f64> {
+    use DateTimeField::*;
+    let year = datetime.year();
+    let value = match DateTimeField::parse(field)? {
+        Microseconds => seconds_of(&datetime) * 1e6,
+        Milliseconds => seconds_of(&datetime) * 1e3,
+        Second => seconds_of(&datetime),
+        Minute => datetime.minute() as f64,
+        Hour => datetime.hour() as f64,
+        Day => datetime.day() as f64,
+        Week => datetime.iso_week().week() as f64,
+        Month => datetime.month() as f64,
+        Quarter => ((datetime.month() - 1) / 3 + 1) as f64,
+        Year => year as f64,
+        Decade => year.div_euclid(10) as f64,
+        // The first century starts at 0001-01-01.
+        Century => first_based(year, 100) as f64,
+        Millennium => first_based(year, 1000) as f64,
+        Dow => datetime.weekday().num_days_from_sunday() as f64,
+        IsoDow => datetime.weekday().number_from_monday() as f64,
+        Doy => datetime.ordinal() as f64,
+        Epoch => datetime.timestamp() as f64 + datetime.timestamp_subsec_micros() as f64 / 1e6,
+    };
+    Ok(value)
+}
+
+#[inline(always)]
+pub fn date_part_from_date(field: &str, date: NaiveDateWrapper) -> Result<f64> {
+    date_part_of_datetime(field, date_to_timestamp(date)?.0)
+}
+
+#[inline(always)]
+pub fn date_part_from_timestamp(field: &str, timestamp: NaiveDateTimeWrapper) -> Result<f64> {
+    date_part_of_datetime(field, timestamp.0)
+}
+
+#[inline(always)]
+pub fn date_part_from_time(field: &str, time: NaiveTimeWrapper) -> Result<f64> {
+    use DateTimeField::*;
+    let time = time.0;
+    let value = match DateTimeField::parse(field)? {
+        Microseconds => seconds_of(&time) * 1e6,
+        Milliseconds => seconds_of(&time) * 1e3,
+        Second => seconds_of(&time),
+        Minute => time.minute() as f64,
+        Hour => time.hour() as f64,
+        Epoch => time.num_seconds_from_midnight() as f64 + seconds_of(&time).fract(),
+        _ => return Err(unsupported_field(field, "time without time zone")),
+    };
+    Ok(value)
+}
+
+#[inline(always)]
+pub fn date_part_from_interval(field: &str, interval: IntervalUnit) -> Result<f64> {
+    use DateTimeField::*;
+    let months = interval.get_months();
+    let ms = interval.get_ms();
+    let value = match DateTimeField::parse(field)? {
+        Microseconds => (ms % (60 * 1000)) as f64 * 1e3,
+        Milliseconds => (ms % (60 * 1000)) as f64,
+        Second => (ms % (60 * 1000)) as f64 / 1e3,
+        Minute => (ms / (60 * 1000) % 60) as f64,
+        Hour => (ms / (3600 * 1000)) as f64,
+        Day => interval.get_days() as f64,
+        Month => (months % 12) as f64,
+        Quarter => ((months % 12) / 3 + 1) as f64,
+        Year => (months / 12) as f64,
+        Decade => (months / 120) as f64,
+        Century => (months / 1200) as f64,
+        Millennium => (months / 12000) as f64,
+        // PG counts 365.25 days per year and 30 days per month.
+        Epoch => {
+            (months / 12) as f64 * 365.25 * 86400.0
+                + (months % 12) as f64 * 30.0 * 86400.0
+                + interval.get_days() as f64 * 86400.0
+                + ms as f64 / 1e3
+        }
+        _ => return Err(unsupported_field(field, "interval")),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_field() {
+        assert_eq!(DateTimeField::parse("Hours").unwrap(), DateTimeField::Hour);
+        assert_eq!(DateTimeField::parse("EPOCH").unwrap(), DateTimeField::Epoch);
+        assert!(DateTimeField::parse("fortnight").is_err());
+    }
+
+    #[test]
+    fn test_date_part_from_timestamp() {
+        let ts = NaiveDateTimeWrapper::new(
+            NaiveDateTime::parse_from_str("2001-02-16 20:38:40.5", "%Y-%m-%d %H:%M:%S%.f").unwrap(),
+        );
+        let part = |field| date_part_from_timestamp(field, ts).unwrap();
+        assert_eq!(part("year"), 2001.0);
+        assert_eq!(part("quarter"), 1.0);
+        assert_eq!(part("month"), 2.0);
+        assert_eq!(part("week"), 7.0);
+        assert_eq!(part("day"), 16.0);
+        assert_eq!(part("hour"), 20.0);
+        assert_eq!(part("minute"), 38.0);
+        assert_eq!(part("second"), 40.5);
+        assert_eq!(part("milliseconds"), 40500.0);
+        assert_eq!(part("dow"), 5.0);
+        assert_eq!(part("isodow"), 5.0);
+        assert_eq!(part("doy"), 47.0);
+        assert_eq!(part("decade"), 200.0);
+        assert_eq!(part("century"), 21.0);
+        assert_eq!(part("millennium"), 3.0);
+        assert_eq!(part("epoch"), 982355920.5);
+
+        let date = NaiveDateWrapper::new(NaiveDate::from_ymd(2000, 12, 31));
+        assert_eq!(date_part_from_date("century", date).unwrap(), 20.0);
+    }
+
+    #[test]
+    fn test_date_part_from_time() {
+        let time = NaiveTimeWrapper::new(NaiveTime::from_hms_milli(13, 30, 15, 250));
+        assert_eq!(date_part_from_time("hour", time).unwrap(), 13.0);
+        assert_eq!(date_part_from_time("second", time).unwrap(), 15.25);
+        assert_eq!(date_part_from_time("epoch", time).unwrap(), 48615.25);
+        assert!(date_part_from_time("day", time).is_err());
+    }
+
+    #[test]
+    fn test_date_part_from_interval() {
+        // 1 year 14 months 3 days 04:05:06.5
+        let interval = IntervalUnit::new(26, 3, ((4 * 60 + 5) * 60 + 6) * 1000 + 500);
+        let part = |field| date_part_from_interval(field, interval).unwrap();
+        assert_eq!(part("year"), 2.0);
+        assert_eq!(part("month"), 2.0);
+        assert_eq!(part("day"), 3.0);
+        assert_eq!(part("hour"), 4.0);
+        assert_eq!(part("minute"), 5.0);
+        assert_eq!(part("second"), 6.5);
+        assert!(date_part_from_interval("dow", interval).is_err());
+    }
+}
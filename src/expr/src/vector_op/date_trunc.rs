@@ -0,0 +1,157 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use risingwave_common::error::Result;
+use risingwave_common::types::{IntervalUnit, NaiveDateTimeWrapper};
+
+use super::date_part::{first_based, unsupported_field, DateTimeField};
+
+/// The first year of the `period`-year period containing `year`. See [`first_based`].
+fn period_start(year: i32, period: i32) -> i32 {
+    let n = first_based(year, period);
+    if n > 0 {
+        (n - 1) * period + 1
+    } else {
+        n * period + 1
+    }
+}
+
+#[inline(always)]
+pub fn date_trunc_timestamp(
+    field: &str,
+    timestamp: NaiveDateTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    use DateTimeField::*;
+    let datetime = timestamp.0;
+    let date = datetime.date();
+    let (year, month) = (date.year(), date.month());
+    let nanos = datetime.nanosecond();
+    let with_nanos = |nanos| {
+        NaiveDateTime::new(
+            date,
+            NaiveTime::from_hms_nano(datetime.hour(), datetime.minute(), datetime.second(), nanos),
+        )
+    };
+    let truncated = match DateTimeField::parse(field)? {
+        Microseconds => with_nanos(nanos / 1000 * 1000),
+        Milliseconds => with_nanos(nanos / 1_000_000 * 1_000_000),
+        Second => with_nanos(0),
+        Minute => date.and_hms(datetime.hour(), datetime.minute(), 0),
+        Hour => date.and_hms(datetime.hour(), 0, 0),
+        Day => date.and_hms(0, 0, 0),
+        // Weeks start on Monday.
+        Week => {
+            (date - Duration::days(date.weekday().num_days_from_monday() as i64)).and_hms(0, 0, 0)
+        }
+        Month => NaiveDate::from_ymd(year, month, 1).and_hms(0, 0, 0),
+        Quarter => NaiveDate::from_ymd(year, (month - 1) / 3 * 3 + 1, 1).and_hms(0, 0, 0),
+        Year => NaiveDate::from_ymd(year, 1, 1).and_hms(0, 0, 0),
+        Decade => NaiveDate::from_ymd(year.div_euclid(10) * 10, 1, 1).and_hms(0, 0, 0),
+        Century => NaiveDate::from_ymd(period_start(year, 100), 1, 1).and_hms(0, 0, 0),
+        Millennium => NaiveDate::from_ymd(period_start(year, 1000), 1, 1).and_hms(0, 0, 0),
+        Dow | IsoDow | Doy | Epoch => {
+            return Err(unsupported_field(field, "timestamp without time zone"))
+        }
+    };
+    Ok(NaiveDateTimeWrapper::new(truncated))
+}
+
+#[inline(always)]
+pub fn date_trunc_interval(field: &str, interval: IntervalUnit) -> Result<IntervalUnit> {
+    use DateTimeField::*;
+    let months = interval.get_months();
+    let days = interval.get_days();
+    let ms = interval.get_ms();
+    // Like PG, the fields are truncated towards zero.
+    let truncated = match DateTimeField::parse(field)? {
+        Microseconds | Milliseconds => interval,
+        Second => IntervalUnit::new(months, days, ms - ms % 1000),
+        Minute => IntervalUnit::new(months, days, ms - ms % (60 * 1000)),
+        Hour => IntervalUnit::new(months, days, ms - ms % (3600 * 1000)),
+        Day => IntervalUnit::new(months, days, 0),
+        Month => IntervalUnit::from_month(months),
+        Quarter => IntervalUnit::from_month(months - months % 3),
+        Year => IntervalUnit::from_month(months - months % 12),
+        Decade => IntervalUnit::from_month(months - months % 120),
+        Century => IntervalUnit::from_month(months - months % 1200),
+        Millennium => IntervalUnit::from_month(months - months % 12000),
+        Week | Dow | IsoDow | Doy | Epoch => return Err(unsupported_field(field, "interval")),
+    };
+    Ok(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(s: &str) -> NaiveDateTimeWrapper {
+        NaiveDateTimeWrapper::new(NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").unwrap())
+    }
+
+    #[test]
+    fn test_date_trunc_timestamp() {
+        let ts = timestamp("2001-02-16 20:38:40.123456");
+        let cases = [
+            ("microseconds", "2001-02-16 20:38:40.123456"),
+            ("milliseconds", "2001-02-16 20:38:40.123"),
+            ("second", "2001-02-16 20:38:40"),
+            ("minute", "2001-02-16 20:38:00"),
+            ("HOUR", "2001-02-16 20:00:00"),
+            ("day", "2001-02-16 00:00:00"),
+            ("week", "2001-02-12 00:00:00"),
+            ("month", "2001-02-01 00:00:00"),
+            ("quarter", "2001-01-01 00:00:00"),
+            ("year", "2001-01-01 00:00:00"),
+            ("decade", "2000-01-01 00:00:00"),
+            ("century", "2001-01-01 00:00:00"),
+            ("millennium", "2001-01-01 00:00:00"),
+        ];
+        for (field, expected) in cases {
+            assert_eq!(
+                date_trunc_timestamp(field, ts).unwrap(),
+                timestamp(expected)
+            );
+        }
+        assert_eq!(
+            date_trunc_timestamp("century", timestamp("2000-12-31 00:00:00")).unwrap(),
+            timestamp("1901-01-01 00:00:00")
+        );
+        assert!(date_trunc_timestamp("dow", ts).is_err());
+        assert!(date_trunc_timestamp("fortnight", ts).is_err());
+    }
+
+    #[test]
+    fn test_date_trunc_interval() {
+        // 3 years 5 mons 6 days 07:08:09.5
+        let interval = IntervalUnit::new(41, 6, ((7 * 60 + 8) * 60 + 9) * 1000 + 500);
+        assert_eq!(
+            date_trunc_interval("minute", interval).unwrap(),
+            IntervalUnit::new(41, 6, (7 * 60 + 8) * 60 * 1000)
+        );
+        assert_eq!(
+            date_trunc_interval("day", interval).unwrap(),
+            IntervalUnit::new(41, 6, 0)
+        );
+        assert_eq!(
+            date_trunc_interval("quarter", interval).unwrap(),
+            IntervalUnit::from_month(39)
+        );
+        assert_eq!(
+            date_trunc_interval("year", interval.negative()).unwrap(),
+            IntervalUnit::from_month(-36)
+        );
+        assert!(date_trunc_interval("week", interval).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functions that build intervals.
+
+use chrono::{Datelike, NaiveDate};
+use risingwave_common::error::ErrorCode::NumericValueOutOfRange;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{IntervalUnit, NaiveDateTimeWrapper, OrderedF64};
+
+/// `make_interval(years, months, weeks, days, hours, mins, secs)`.
+#[inline(always)]
+pub fn make_interval(
+    years: i32,
+    months: i32,
+    weeks: i32,
+    days: i32,
+    hours: i32,
+    mins: i32,
+    secs: OrderedF64,
+) -> Result<IntervalUnit> {
+    let overflow = || RwError::from(NumericValueOutOfRange);
+    let months = years
+        .checked_mul(12)
+        .and_then(|years| years.checked_add(months))
+        .ok_or_else(overflow)?;
+    let days = weeks
+        .checked_mul(7)
+        .and_then(|weeks| weeks.checked_add(days))
+        .ok_or_else(overflow)?;
+    let secs_ms = (secs.0 * 1000.0).round();
+    if !(i64::MIN as f64..=i64::MAX as f64).contains(&secs_ms) {
+        return Err(overflow());
+    }
+    let ms = (hours as i64 * 60 + mins as i64) * 60 * 1000;
+    let ms = ms.checked_add(secs_ms as i64).ok_or_else(overflow)?;
+    Ok(IntervalUnit::new(months, days, ms))
+}
+
+fn days_in_month(year: i32, month: u32) -> i32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd(next_year, next_month, 1).pred().day() as i32
+}
+
+/// `age(l, r)`: subtracts `r` from `l` field by field, producing a "symbolic" result in years,
+/// months and days rather than just days, e.g. `age('2001-04-10', '1957-06-13')` is
+/// `43 years 9 mons 27 days`.
+pub fn age(l: NaiveDateTimeWrapper, r: NaiveDateTimeWrapper) -> Result<IntervalUnit> {
+    if l < r {
+        return age(r, l).map(|interval| interval.negative());
+    }
+    let (l, r) = (l.0, r.0);
+    let mut months = (l.year() - r.year()) * 12 + l.month() as i32 - r.month() as i32;
+    let mut days = l.day() as i32 - r.day() as i32;
+    let mut ms = (l.time() - r.time()).num_milliseconds();
+    // Borrow from the next higher field. Like PG, a month borrowed is as long as the month of the
+    // smaller timestamp.
+    if ms < 0 {
+        ms += 24 * 3600 * 1000;
+        days -= 1;
+    }
+    if days < 0 {
+        days += days_in_month(r.year(), r.month());
+        months -= 1;
+    }
+    Ok(IntervalUnit::new(months, days, ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    #[test]
+    fn test_make_interval() {
+        assert_eq!(
+            make_interval(1, 2, 1, 3, 4, 5, OrderedF64::from(6.5)).unwrap(),
+            IntervalUnit::new(14, 10, ((4 * 60 + 5) * 60) * 1000 + 6500)
+        );
+        assert!(make_interval(i32::MAX, 0, 0, 0, 0, 0, OrderedF64::from(0.0)).is_err());
+        assert!(make_interval(0, 0, 0, 0, 0, 0, OrderedF64::from(f64::INFINITY)).is_err());
+    }
+
+    #[test]
+    fn test_age() {
+        let ts = |s| {
+            NaiveDateTimeWrapper::new(
+                NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap(),
+            )
+        };
+        assert_eq!(
+            age(ts("2001-04-10 00:00:00"), ts("1957-06-13 00:00:00")).unwrap(),
+            IntervalUnit::new(43 * 12 + 9, 27, 0)
+        );
+        assert_eq!(
+            age(ts("1957-06-13 00:00:00"), ts("2001-04-10 00:00:00")).unwrap(),
+            IntervalUnit::new(-(43 * 12 + 9), -27, 0)
+        );
+        // Borrows a day, and then a month of 31 days from January.
+        assert_eq!(
+            age(ts("2022-03-01 01:00:00"), ts("2022-01-31 02:00:00")).unwrap(),
+            IntervalUnit::new(1, 0, 23 * 3600 * 1000)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functions for the `jsonb` operators. The access operators return `NULL` instead of an error if
+//! the field, element or path does not exist, as PostgreSQL does.
+
+use risingwave_common::array::ListRef;
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{JsonbRef, JsonbVal, ScalarRef, ScalarRefImpl};
+
+/// `jsonb -> text`
+pub fn jsonb_access_field(json: Option<JsonbRef>, key: Option<&str>) -> Result<Option<JsonbVal>> {
+    Ok(json
+        .zip(key)
+        .and_then(|(json, key)| json.access_object_field(key))
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb -> int`
+pub fn jsonb_access_element(json: Option<JsonbRef>, idx: Option<i32>) -> Result<Option<JsonbVal>> {
+    Ok(json
+        .zip(idx)
+        .and_then(|(json, idx)| json.access_array_element(idx))
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb ->> text`
+pub fn jsonb_access_field_text(
+    json: Option<JsonbRef>,
+    key: Option<&str>,
+) -> Result<Option<String>> {
+    Ok(json
+        .zip(key)
+        .and_then(|(json, key)| json.access_object_field(key))
+        .and_then(|v| v.to_text()))
+}
+
+/// `jsonb ->> int`
+pub fn jsonb_access_element_text(
+    json: Option<JsonbRef>,
+    idx: Option<i32>,
+) -> Result<Option<String>> {
+    Ok(json
+        .zip(idx)
+        .and_then(|(json, idx)| json.access_array_element(idx))
+        .and_then(|v| v.to_text()))
+}
+
+/// Collects the path elements of a `text[]`. Returns `None` if any of them is `NULL`.
+fn list_path(path: ListRef) -> Option<Vec<&str>> {
+    path.values_ref()
+        .into_iter()
+        .map(|v| match v {
+            Some(ScalarRefImpl::Utf8(s)) => Some(s),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parses the path from its text form of an array, e.g., `{a,0,b}`.
+fn parse_text_path(path: &str) -> Result<Vec<&str>> {
+    let inner = path
+        .trim()
+        .strip_prefix('{')
+        .and_then(|p| p.strip_suffix('}'))
+        .ok_or_else(|| {
+            RwError::from(InvalidInputSyntax(format!(
+                "malformed array literal: \"{}\"",
+                path
+            )))
+        })?;
+    if inner.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    Ok(inner.split(',').map(|s| s.trim()).collect())
+}
+
+/// `jsonb #> text[]`
+pub fn jsonb_extract_path(
+    json: Option<JsonbRef>,
+    path: Option<ListRef>,
+) -> Result<Option<JsonbVal>> {
+    Ok(json
+        .zip(path.and_then(list_path))
+        .and_then(|(json, path)| json.access_path(path))
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb #>> text[]`
+pub fn jsonb_extract_path_text(
+    json: Option<JsonbRef>,
+    path: Option<ListRef>,
+) -> Result<Option<String>> {
+    Ok(json
+        .zip(path.and_then(list_path))
+        .and_then(|(json, path)| json.access_path(path))
+        .and_then(|v| v.to_text()))
+}
+
+/// `jsonb #> '{a,b}'`, where the path is given as a string literal.
+pub fn jsonb_extract_str_path(
+    json: Option<JsonbRef>,
+    path: Option<&str>,
+) -> Result<Option<JsonbVal>> {
+    let (json, path) = match json.zip(path) {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    Ok(json
+        .access_path(parse_text_path(path)?)
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb #>> '{a,b}'`, where the path is given as a string literal.
+pub fn jsonb_extract_str_path_text(
+    json: Option<JsonbRef>,
+    path: Option<&str>,
+) -> Result<Option<String>> {
+    let (json, path) = match json.zip(path) {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    Ok(json
+        .access_path(parse_text_path(path)?)
+        .and_then(|v| v.to_text()))
+}
+
+/// `jsonb @> jsonb`
+pub fn jsonb_contains(left: JsonbRef, right: JsonbRef) -> Result<bool> {
+    Ok(left.contains(right))
+}
+
+/// `jsonb ? text`
+pub fn jsonb_exists(json: JsonbRef, key: &str) -> Result<bool> {
+    Ok(json.exists(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::Scalar;
+
+    use super::*;
+
+    #[test]
+    fn test_jsonb_access() {
+        let json: JsonbVal = r#"{"a": {"b": [1, "x", null]}}"#.parse().unwrap();
+        let json = Some(json.as_scalar_ref());
+
+        let inner = jsonb_access_field(json, Some("a")).unwrap().unwrap();
+        assert_eq!(inner.to_string(), r#"{"b": [1, "x", null]}"#);
+        assert_eq!(jsonb_access_field(json, Some("c")).unwrap(), None);
+        assert_eq!(jsonb_access_field(json, None).unwrap(), None);
+
+        let path = "{a,b,1}";
+        assert_eq!(
+            jsonb_extract_str_path_text(json, Some(path)).unwrap(),
+            Some("x".to_string())
+        );
+        assert_eq!(
+            jsonb_extract_str_path(json, Some("{a,b,-3}"))
+                .unwrap()
+                .unwrap()
+                .to_string(),
+            "1"
+        );
+        // A json `null` is `NULL` as text.
+        assert_eq!(
+            jsonb_extract_str_path_text(json, Some("{a,b,2}")).unwrap(),
+            None
+        );
+        assert!(jsonb_extract_str_path(json, Some("a,b")).is_err());
+
+        let arr: JsonbVal = "[1, 2]".parse().unwrap();
+        let arr = Some(arr.as_scalar_ref());
+        assert_eq!(
+            jsonb_access_element_text(arr, Some(-1)).unwrap(),
+            Some("2".to_string())
+        );
+        assert_eq!(jsonb_access_element(arr, Some(2)).unwrap(), None);
+    }
+}
@@ -55,9 +55,14 @@ pub fn like_default(s: &str, p: &str) -> Result<bool> {
     Ok(true)
 }
 
+#[inline(always)]
+pub fn ilike_default(s: &str, p: &str) -> Result<bool> {
+    like_default(&s.to_lowercase(), &p.to_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::like_default;
+    use super::{ilike_default, like_default};
 
     static CASES: &[(&str, &str, std::option::Option<bool>)] = &[
         (r#"ABCDE"#, r#"%abcde%"#, Some(false)),
@@ -94,4 +99,10 @@ mod tests {
             );
         }
     }
+    #[test]
+    fn test_ilike() {
+        assert!(ilike_default("ABCDE", "%abcde%").unwrap());
+        assert!(ilike_default("Like, Expression", "like, %").unwrap());
+        assert!(!ilike_default("like", "l_k").unwrap());
+    }
 }
@@ -18,17 +18,24 @@ pub mod ascii;
 pub mod cast;
 pub mod cmp;
 pub mod conjunction;
+pub mod date_part;
+pub mod date_trunc;
 pub mod extract;
+pub mod interval;
+pub mod jsonb;
 pub mod length;
 pub mod like;
 pub mod lower;
 pub mod ltrim;
 pub mod position;
+pub mod regexp;
 pub mod replace;
 pub mod round;
 pub mod rtrim;
 pub mod split_part;
 pub mod substr;
+pub mod timezone;
+pub mod to_char;
 pub mod translate;
 pub mod trim;
 pub mod tumble;
@@ -0,0 +1,333 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Regular expression functions, e.g. `~`, `regexp_match` and `SIMILAR TO`.
+//!
+//! Patterns are compiled with the `regex` crate, which covers most of the PG syntax except
+//! back-references and look-around.
+
+use regex::{Captures, Regex, RegexBuilder};
+use risingwave_common::array::ListValue;
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{Datum, ScalarImpl};
+
+/// A compiled pattern together with the options given by its flags.
+#[derive(Debug, Clone)]
+pub struct RegexpContext {
+    pub regex: Regex,
+    /// The `g` flag: handle every match rather than only the first one.
+    pub global: bool,
+}
+
+impl RegexpContext {
+    /// Compiles `pattern` with PG flags like `'gi'`.
+    pub fn new(pattern: &str, flags: &str) -> Result<Self> {
+        let mut builder = RegexBuilder::new(pattern);
+        // As in PG, `.` matches newlines and `^`/`$` only match at the ends of the string, unless
+        // the newline-sensitive flag is given.
+        builder.dot_matches_new_line(true);
+        let mut global = false;
+        for flag in flags.chars() {
+            match flag {
+                'i' => {
+                    builder.case_insensitive(true);
+                }
+                'c' => {
+                    builder.case_insensitive(false);
+                }
+                'n' | 'm' => {
+                    builder.multi_line(true).dot_matches_new_line(false);
+                }
+                's' => {
+                    builder.multi_line(false).dot_matches_new_line(true);
+                }
+                'x' => {
+                    builder.ignore_whitespace(true);
+                }
+                'g' => global = true,
+                _ => {
+                    return Err(RwError::from(InvalidInputSyntax(format!(
+                        "invalid regular expression option: \"{}\"",
+                        flag
+                    ))))
+                }
+            }
+        }
+        let regex = builder.build().map_err(|e| {
+            RwError::from(InvalidInputSyntax(format!(
+                "invalid regular expression: {}",
+                e
+            )))
+        })?;
+        Ok(Self { regex, global })
+    }
+
+    /// Compiles a `SIMILAR TO` pattern with the escape string given by `ESCAPE`, which must be
+    /// empty or one character. An empty escape string disables escaping.
+    pub fn from_similar_to(pattern: &str, escape: &str) -> Result<Self> {
+        let mut chars = escape.chars();
+        let escape = match (chars.next(), chars.next()) {
+            (escape, None) => escape,
+            _ => {
+                return Err(RwError::from(InvalidInputSyntax(
+                    "invalid escape string: must be empty or one character".to_string(),
+                )))
+            }
+        };
+        Self::new(&similar_to_regex(pattern, escape)?, "")
+    }
+}
+
+/// Translates a `SIMILAR TO` pattern to a regular expression matching the whole string.
+fn similar_to_regex(pattern: &str, escape: Option<char>) -> Result<String> {
+    let mut regex = String::with_capacity(pattern.len() + 8);
+    regex.push_str("^(?:");
+    let mut chars = pattern.chars();
+    let mut in_bracket = false;
+    while let Some(c) = chars.next() {
+        if in_bracket {
+            // Bracket expressions are copied as they are.
+            in_bracket = c != ']';
+            regex.push(c);
+            continue;
+        }
+        match c {
+            c if Some(c) == escape => match chars.next() {
+                Some(c) => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                None => {
+                    return Err(RwError::from(InvalidInputSyntax(
+                        "SIMILAR TO pattern must not end with escape character".to_string(),
+                    )))
+                }
+            },
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '[' => {
+                in_bracket = true;
+                regex.push(c);
+            }
+            '|' | '*' | '+' | '?' | '(' | ')' | '{' | '}' => regex.push(c),
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    regex.push_str(")$");
+    Ok(regex)
+}
+
+#[inline(always)]
+pub fn regexp_like(text: &str, ctx: &RegexpContext) -> bool {
+    ctx.regex.is_match(text)
+}
+
+/// The captured substrings of a match, or the whole match if the pattern has no capture groups.
+fn captures_to_list(caps: &Captures<'_>) -> ListValue {
+    let values = if caps.len() == 1 {
+        vec![caps.get(0)]
+    } else {
+        caps.iter().skip(1).collect()
+    };
+    ListValue::new(
+        values
+            .into_iter()
+            .map(|m| m.map(|m| ScalarImpl::Utf8(m.as_str().to_owned())))
+            .collect(),
+    )
+}
+
+/// `regexp_match`, which returns `None` if there is no match.
+pub fn regexp_match(text: &str, ctx: &RegexpContext) -> Option<ListValue> {
+    ctx.regex.captures(text).map(|caps| captures_to_list(&caps))
+}
+
+/// `regexp_matches`, which returns every match with the `g` flag and at most one otherwise.
+pub fn regexp_matches(text: &str, ctx: &RegexpContext) -> Vec<ListValue> {
+    let matches = ctx
+        .regex
+        .captures_iter(text)
+        .map(|caps| captures_to_list(&caps));
+    if ctx.global {
+        matches.collect()
+    } else {
+        matches.take(1).collect()
+    }
+}
+
+/// `regexp_replace`, which replaces the first match, or every match with the `g` flag. In the
+/// replacement, `\1` to `\9` refer to the captured substrings, `\&` to the whole match and `\\` to
+/// a backslash.
+pub fn regexp_replace(text: &str, ctx: &RegexpContext, replacement: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in ctx.regex.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        result.push_str(&text[last..whole.start()]);
+        expand_replacement(&caps, replacement, &mut result);
+        last = whole.end();
+        if !ctx.global {
+            break;
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+fn expand_replacement(caps: &Captures<'_>, replacement: &str, dst: &mut String) {
+    let mut chars = replacement.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            dst.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(n @ '1'..='9') => {
+                if let Some(m) = caps.get(n as usize - '0' as usize) {
+                    dst.push_str(m.as_str());
+                }
+            }
+            Some('&') => dst.push_str(&caps[0]),
+            Some('\\') => dst.push('\\'),
+            Some(other) => {
+                dst.push('\\');
+                dst.push(other);
+            }
+            None => dst.push('\\'),
+        }
+    }
+}
+
+/// `regexp_split_to_array`. As in PG, a zero-length match at the beginning or the end of the
+/// string, or right after the previous match, does not split.
+pub fn regexp_split_to_array(text: &str, ctx: &RegexpContext) -> ListValue {
+    let mut values: Vec<Datum> = vec![];
+    let mut start = 0;
+    for m in ctx.regex.find_iter(text) {
+        if m.start() == m.end() && (m.start() == start || m.end() == text.len()) {
+            continue;
+        }
+        values.push(Some(ScalarImpl::Utf8(text[start..m.start()].to_owned())));
+        start = m.end();
+    }
+    values.push(Some(ScalarImpl::Utf8(text[start..].to_owned())));
+    ListValue::new(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[Option<&str>]) -> ListValue {
+        ListValue::new(
+            values
+                .iter()
+                .map(|v| v.map(|v| ScalarImpl::Utf8(v.to_owned())))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_regexp_like() {
+        let ctx = RegexpContext::new("^err(or)?:", "").unwrap();
+        assert!(regexp_like("error: disk full", &ctx));
+        assert!(!regexp_like("ERROR: disk full", &ctx));
+        let ctx = RegexpContext::new("^err(or)?:", "i").unwrap();
+        assert!(regexp_like("ERROR: disk full", &ctx));
+        assert!(RegexpContext::new("(", "").is_err());
+        assert!(RegexpContext::new("a", "q").is_err());
+    }
+
+    #[test]
+    fn test_similar_to() {
+        for (text, pattern, expected) in [
+            ("abc", "abc", true),
+            ("abc", "a", false),
+            ("abc", "%(b|d)%", true),
+            ("abc", "(b|c)%", false),
+            ("abd", "ab[cd]", true),
+            ("a.c", "a.c", true),
+            ("abc", "a.c", false),
+            ("a_c", "a\\_c", true),
+            ("abbbc", "ab+c", true),
+            ("ac", "a_c", false),
+        ] {
+            let ctx = RegexpContext::from_similar_to(pattern, "\\").unwrap();
+            assert_eq!(regexp_like(text, &ctx), expected, "{} {}", text, pattern);
+        }
+        assert!(RegexpContext::from_similar_to("a\\", "\\").is_err());
+
+        for (text, pattern, escape, expected) in [
+            ("abcdefg", "_bcd#%", "#", false),
+            ("abcd%", "_bcd#%", "#", true),
+            ("abcd\\efg", "_bcd\\%", "", true),
+            ("a#b", "a##b", "#", true),
+        ] {
+            let ctx = RegexpContext::from_similar_to(pattern, escape).unwrap();
+            assert_eq!(regexp_like(text, &ctx), expected, "{} {}", text, pattern);
+        }
+        assert!(RegexpContext::from_similar_to("a", "##").is_err());
+    }
+
+    #[test]
+    fn test_regexp_match() {
+        let ctx = RegexpContext::new(r"(\d+)-(\d+)?", "").unwrap();
+        assert_eq!(
+            regexp_match("port 80-", &ctx),
+            Some(list(&[Some("80"), None]))
+        );
+        assert_eq!(regexp_match("no ports", &ctx), None);
+        let ctx = RegexpContext::new(r"\d+", "").unwrap();
+        assert_eq!(regexp_match("a1b22", &ctx), Some(list(&[Some("1")])));
+
+        let ctx = RegexpContext::new(r"(\w)=(\d)", "g").unwrap();
+        assert_eq!(
+            regexp_matches("a=1, b=2", &ctx),
+            vec![list(&[Some("a"), Some("1")]), list(&[Some("b"), Some("2")])]
+        );
+        let ctx = RegexpContext::new(r"(\w)=(\d)", "").unwrap();
+        assert_eq!(regexp_matches("a=1, b=2", &ctx).len(), 1);
+    }
+
+    #[test]
+    fn test_regexp_replace() {
+        let ctx = RegexpContext::new(r"(\w+)@(\w+)", "").unwrap();
+        assert_eq!(
+            regexp_replace("alice@home bob@work", &ctx, r"\2:\1"),
+            "home:alice bob@work"
+        );
+        let ctx = RegexpContext::new(r"(\w+)@(\w+)", "g").unwrap();
+        assert_eq!(
+            regexp_replace("alice@home bob@work", &ctx, r"[\&]\\"),
+            r"[alice@home]\ [bob@work]\"
+        );
+    }
+
+    #[test]
+    fn test_regexp_split_to_array() {
+        let ctx = RegexpContext::new(r"\s+", "").unwrap();
+        assert_eq!(
+            regexp_split_to_array("the quick  fox", &ctx),
+            list(&[Some("the"), Some("quick"), Some("fox")])
+        );
+        let ctx = RegexpContext::new(",", "").unwrap();
+        assert_eq!(
+            regexp_split_to_array(",a,", &ctx),
+            list(&[Some(""), Some("a"), Some("")])
+        );
+        let ctx = RegexpContext::new("", "").unwrap();
+        assert_eq!(
+            regexp_split_to_array("abc", &ctx),
+            list(&[Some("a"), Some("b"), Some("c")])
+        );
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Duration, FixedOffset, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use risingwave_common::error::ErrorCode::{InternalError, InvalidParameterValue};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+/// A time zone given to `AT TIME ZONE` or the `timezone` session variable. It is either an IANA
+/// name like `Asia/Shanghai`, or a fixed ISO 8601 offset like `+08:00`, which is east of
+/// Greenwich.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeZoneSpec {
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+impl TimeZoneSpec {
+    /// The offset at the given UTC time.
+    fn offset_from_utc(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            TimeZoneSpec::Named(tz) => tz.offset_from_utc_datetime(utc).fix(),
+            TimeZoneSpec::Fixed(offset) => *offset,
+        }
+    }
+
+    /// The offset at the given local time. An ambiguous local time takes the earlier offset, and a
+    /// local time skipped by a DST transition takes the offset in effect before the transition.
+    fn offset_from_local(&self, local: &NaiveDateTime) -> FixedOffset {
+        match self {
+            TimeZoneSpec::Named(tz) => match tz.offset_from_local_datetime(local).earliest() {
+                Some(offset) => offset.fix(),
+                None => tz.offset_from_utc_datetime(local).fix(),
+            },
+            TimeZoneSpec::Fixed(offset) => *offset,
+        }
+    }
+}
+
+/// Parses a time zone name or offset, e.g. `UTC`, `America/New_York`, `+08`, `-05:30`.
+pub fn parse_time_zone(zone: &str) -> Result<TimeZoneSpec> {
+    let zone = zone.trim();
+    let invalid = || {
+        RwError::from(InvalidParameterValue(format!(
+            "time zone \"{}\" not recognized",
+            zone
+        )))
+    };
+    if ["UTC", "GMT", "Z"]
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(zone))
+    {
+        return Ok(TimeZoneSpec::Fixed(FixedOffset::east(0)));
+    }
+    let sign = match zone.as_bytes().first() {
+        Some(b'+') => Some(1),
+        Some(b'-') => Some(-1),
+        _ => None,
+    };
+    if let Some(sign) = sign {
+        let digits = zone[1..].replace(':', "");
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let (hours, minutes) = match digits.len() {
+            1 | 2 => (digits.parse::<i32>().unwrap(), 0),
+            4 => (
+                digits[..2].parse::<i32>().unwrap(),
+                digits[2..].parse::<i32>().unwrap(),
+            ),
+            _ => return Err(invalid()),
+        };
+        if hours > 15 || minutes > 59 {
+            return Err(invalid());
+        }
+        return Ok(TimeZoneSpec::Fixed(FixedOffset::east(
+            sign * (hours * 60 + minutes) * 60,
+        )));
+    }
+    zone.parse::<Tz>()
+        .map(TimeZoneSpec::Named)
+        .map_err(|_| invalid())
+}
+
+/// Converts a `timestamp with time zone`, i.e. microseconds since the Unix epoch, to UTC time.
+pub fn timestampz_to_utc(timestampz: i64) -> Result<NaiveDateTime> {
+    NaiveDateTime::from_timestamp_opt(
+        timestampz.div_euclid(1_000_000),
+        timestampz.rem_euclid(1_000_000) as u32 * 1000,
+    )
+    .ok_or_else(|| RwError::from(InternalError("Timestamp out of range".to_string())))
+}
+
+/// Converts UTC time to a `timestamp with time zone`.
+pub fn utc_to_timestampz(utc: &NaiveDateTime) -> i64 {
+    utc.timestamp() * 1_000_000 + utc.timestamp_subsec_micros() as i64
+}
+
+/// `timestamp AT TIME ZONE zone`: takes the timestamp as local time in `zone`.
+#[inline(always)]
+pub fn timestamp_at_time_zone(timestamp: NaiveDateTimeWrapper, zone: &str) -> Result<i64> {
+    Ok(timestamp_at_time_zone_spec(
+        timestamp,
+        &parse_time_zone(zone)?,
+    ))
+}
+
+/// [`timestamp_at_time_zone`] with a parsed time zone, for a constant time zone.
+#[inline(always)]
+pub fn timestamp_at_time_zone_spec(timestamp: NaiveDateTimeWrapper, zone: &TimeZoneSpec) -> i64 {
+    let offset = zone.offset_from_local(&timestamp.0);
+    let utc = timestamp.0 - Duration::seconds(offset.local_minus_utc() as i64);
+    utc_to_timestampz(&utc)
+}
+
+/// `timestamptz AT TIME ZONE zone`: the local time in `zone`.
+#[inline(always)]
+pub fn timestampz_at_time_zone(timestampz: i64, zone: &str) -> Result<NaiveDateTimeWrapper> {
+    timestampz_at_time_zone_spec(timestampz, &parse_time_zone(zone)?)
+}
+
+/// [`timestampz_at_time_zone`] with a parsed time zone, for a constant time zone.
+#[inline(always)]
+pub fn timestampz_at_time_zone_spec(
+    timestampz: i64,
+    zone: &TimeZoneSpec,
+) -> Result<NaiveDateTimeWrapper> {
+    let utc = timestampz_to_utc(timestampz)?;
+    let offset = zone.offset_from_utc(&utc);
+    Ok(NaiveDateTimeWrapper::new(
+        utc + Duration::seconds(offset.local_minus_utc() as i64),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(s: &str) -> NaiveDateTimeWrapper {
+        NaiveDateTimeWrapper::new(NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap())
+    }
+
+    #[test]
+    fn test_parse_time_zone() {
+        assert_eq!(
+            parse_time_zone("utc").unwrap(),
+            TimeZoneSpec::Fixed(FixedOffset::east(0))
+        );
+        assert_eq!(
+            parse_time_zone("+08").unwrap(),
+            TimeZoneSpec::Fixed(FixedOffset::east(8 * 3600))
+        );
+        assert_eq!(
+            parse_time_zone("-05:30").unwrap(),
+            TimeZoneSpec::Fixed(FixedOffset::west(5 * 3600 + 30 * 60))
+        );
+        assert_eq!(
+            parse_time_zone("Asia/Shanghai").unwrap(),
+            TimeZoneSpec::Named(Tz::Asia__Shanghai)
+        );
+        assert!(parse_time_zone("+8:3").is_err());
+        assert!(parse_time_zone("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_at_time_zone() {
+        let ts = timestamp("2022-01-01 08:00:00");
+        let tstz = timestamp_at_time_zone(ts, "Asia/Shanghai").unwrap();
+        assert_eq!(tstz, 1640995200 * 1_000_000);
+        assert_eq!(timestampz_at_time_zone(tstz, "+08:00").unwrap(), ts);
+        assert_eq!(
+            timestampz_at_time_zone(tstz, "UTC").unwrap(),
+            timestamp("2022-01-01 00:00:00")
+        );
+        // Daylight saving time in New York.
+        let tstz = timestamp_at_time_zone(timestamp("2022-07-01 12:00:00"), "America/New_York");
+        assert_eq!(
+            timestampz_at_time_zone(tstz.unwrap(), "UTC").unwrap(),
+            timestamp("2022-07-01 16:00:00")
+        );
+        // A negative timestamp with time zone.
+        assert_eq!(
+            timestampz_at_time_zone(-1, "UTC").unwrap(),
+            NaiveDateTimeWrapper::new(NaiveDateTime::from_timestamp(-1, 999_999_000))
+        );
+    }
+}
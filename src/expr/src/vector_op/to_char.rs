@@ -0,0 +1,637 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `to_char` and `to_timestamp` with the date/time format patterns of PG, e.g.
+//! `YYYY-MM-DD HH24:MI:SS`. See <https://www.postgresql.org/docs/current/functions-formatting.html>.
+
+use std::fmt::Write;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use risingwave_common::array::{BytesGuard, BytesWriter};
+use risingwave_common::error::ErrorCode::InvalidParameterValue;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{NaiveDateTimeWrapper, OrderedF64};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Indexed by the number of days from Sunday.
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Case {
+    Upper,
+    Capitalized,
+    Lower,
+}
+
+impl Case {
+    fn apply(self, name: &str, out: &mut String) {
+        match self {
+            Case::Upper => out.extend(name.chars().map(|c| c.to_ascii_uppercase())),
+            Case::Capitalized => out.push_str(name),
+            Case::Lower => out.extend(name.chars().map(|c| c.to_ascii_lowercase())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Year,
+    ShortYear,
+    Month,
+    MonthName(Case),
+    MonthAbbr(Case),
+    Day,
+    DayOfYear,
+    DayOfWeek,
+    DayName(Case),
+    DayAbbr(Case),
+    IsoWeek,
+    Quarter,
+    Hour24,
+    Hour12,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+    Meridiem { upper: bool, dots: bool },
+}
+
+/// Patterns listed earlier take precedence. The patterns of numbers are case-insensitive, while
+/// the case of a name pattern is the case of the name.
+const PATTERNS: &[(&str, Field)] = {
+    use Case::*;
+    use Field::*;
+    &[
+        ("HH24", Hour24),
+        ("HH12", Hour12),
+        ("HH", Hour12),
+        ("MI", Minute),
+        ("SS", Second),
+        ("MS", Millisecond),
+        ("US", Microsecond),
+        ("YYYY", Year),
+        ("YY", ShortYear),
+        ("MONTH", MonthName(Upper)),
+        ("Month", MonthName(Capitalized)),
+        ("month", MonthName(Lower)),
+        ("MON", MonthAbbr(Upper)),
+        ("Mon", MonthAbbr(Capitalized)),
+        ("mon", MonthAbbr(Lower)),
+        ("MM", Month),
+        ("DAY", DayName(Upper)),
+        ("Day", DayName(Capitalized)),
+        ("day", DayName(Lower)),
+        ("DDD", DayOfYear),
+        ("DD", Day),
+        ("DY", DayAbbr(Upper)),
+        ("Dy", DayAbbr(Capitalized)),
+        ("dy", DayAbbr(Lower)),
+        ("D", DayOfWeek),
+        ("IW", IsoWeek),
+        ("Q", Quarter),
+        (
+            "A.M.",
+            Meridiem {
+                upper: true,
+                dots: true,
+            },
+        ),
+        (
+            "P.M.",
+            Meridiem {
+                upper: true,
+                dots: true,
+            },
+        ),
+        (
+            "a.m.",
+            Meridiem {
+                upper: false,
+                dots: true,
+            },
+        ),
+        (
+            "p.m.",
+            Meridiem {
+                upper: false,
+                dots: true,
+            },
+        ),
+        (
+            "AM",
+            Meridiem {
+                upper: true,
+                dots: false,
+            },
+        ),
+        (
+            "PM",
+            Meridiem {
+                upper: true,
+                dots: false,
+            },
+        ),
+        (
+            "am",
+            Meridiem {
+                upper: false,
+                dots: false,
+            },
+        ),
+        (
+            "pm",
+            Meridiem {
+                upper: false,
+                dots: false,
+            },
+        ),
+    ]
+};
+
+impl Field {
+    fn is_name(self) -> bool {
+        matches!(
+            self,
+            Field::MonthName(_)
+                | Field::MonthAbbr(_)
+                | Field::DayName(_)
+                | Field::DayAbbr(_)
+                | Field::Meridiem { .. }
+        )
+    }
+
+    fn matches(self, pattern: &str, format: &str) -> bool {
+        format.get(..pattern.len()).map_or(false, |prefix| {
+            if self.is_name() {
+                prefix == pattern
+            } else {
+                prefix.eq_ignore_ascii_case(pattern)
+            }
+        })
+    }
+
+    /// The pattern of the field, for error messages.
+    fn pattern(self) -> &'static str {
+        PATTERNS.iter().find(|(_, field)| *field == self).unwrap().0
+    }
+
+    /// The maximum number of digits of a numeric field in the input of `to_timestamp`.
+    fn max_digits(self) -> usize {
+        match self {
+            Field::Year => 4,
+            Field::DayOfYear | Field::Millisecond => 3,
+            Field::Microsecond => 6,
+            Field::DayOfWeek | Field::Quarter => 1,
+            _ => 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Item {
+    Literal(char),
+    /// A field, whose padding is suppressed by the `FM` prefix when `fill_mode` is set.
+    Field {
+        field: Field,
+        fill_mode: bool,
+    },
+}
+
+/// A compiled date/time format pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateTimeFormat {
+    items: Vec<Item>,
+}
+
+impl DateTimeFormat {
+    pub fn compile(format: &str) -> Self {
+        let mut items = vec![];
+        let mut rest = format;
+        while !rest.is_empty() {
+            let (fill_mode, unprefixed) = match rest.get(..2) {
+                Some(prefix) if prefix.eq_ignore_ascii_case("FM") => (true, &rest[2..]),
+                _ => (false, rest),
+            };
+            if let Some((pattern, field)) = PATTERNS
+                .iter()
+                .find(|(pattern, field)| field.matches(pattern, unprefixed))
+            {
+                items.push(Item::Field {
+                    field: *field,
+                    fill_mode,
+                });
+                rest = &unprefixed[pattern.len()..];
+                continue;
+            }
+            let mut chars = rest.chars();
+            match chars.next().unwrap() {
+                // Text in double quotes is copied literally, where `\` escapes the next character.
+                '"' => {
+                    let mut escaped = false;
+                    for c in chars.by_ref() {
+                        match c {
+                            '\\' if !escaped => escaped = true,
+                            '"' if !escaped => break,
+                            _ => {
+                                items.push(Item::Literal(c));
+                                escaped = false;
+                            }
+                        }
+                    }
+                }
+                '\\' => {
+                    if let Some(c) = chars.next() {
+                        items.push(Item::Literal(c));
+                    }
+                }
+                c => items.push(Item::Literal(c)),
+            }
+            rest = chars.as_str();
+        }
+        Self { items }
+    }
+
+    pub fn format(&self, datetime: &NaiveDateTime, out: &mut String) {
+        for item in &self.items {
+            match item {
+                Item::Literal(c) => out.push(*c),
+                Item::Field { field, fill_mode } => {
+                    format_field(*field, *fill_mode, datetime, out);
+                }
+            }
+        }
+    }
+
+    /// Parses a timestamp. Like PG without the `FX` prefix, spaces around the fields are skipped,
+    /// a separator in the format skips any single separator in the input, and any other literal
+    /// character skips any single character. Fields that are missing default to
+    /// `0001-01-01 00:00:00`.
+    pub fn parse(&self, s: &str) -> Result<NaiveDateTime> {
+        let mut fields = ParsedFields::default();
+        let mut rest = s;
+        for item in &self.items {
+            match item {
+                Item::Literal(c) if c.is_whitespace() => rest = rest.trim_start(),
+                Item::Literal(c) => {
+                    let mut chars = rest.chars();
+                    match chars.next() {
+                        Some(next) if c.is_alphanumeric() || !next.is_alphanumeric() => {
+                            rest = chars.as_str()
+                        }
+                        _ => {}
+                    }
+                }
+                Item::Field { field, .. } => {
+                    rest = fields.parse_field(*field, rest.trim_start())?;
+                }
+            }
+        }
+        fields.into_datetime()
+    }
+}
+
+fn format_field(field: Field, fill_mode: bool, datetime: &NaiveDateTime, out: &mut String) {
+    // Writing to a `String` never fails.
+    let number = |out: &mut String, value: u32, width: usize| {
+        if fill_mode {
+            write!(out, "{}", value).unwrap();
+        } else {
+            write!(out, "{:0width$}", value, width = width).unwrap();
+        }
+    };
+    // Full names are padded to 9 characters, which is the length of the longest one.
+    let name = |out: &mut String, name: &str, case: Case, padded: bool| {
+        case.apply(name, out);
+        if padded && !fill_mode {
+            out.extend(std::iter::repeat(' ').take(9 - name.len()));
+        }
+    };
+    let month_name = MONTH_NAMES[datetime.month0() as usize];
+    let day_name = DAY_NAMES[datetime.weekday().num_days_from_sunday() as usize];
+    let nanos = datetime.nanosecond() % 1_000_000_000;
+    match field {
+        Field::Year => {
+            if fill_mode {
+                write!(out, "{}", datetime.year()).unwrap();
+            } else {
+                write!(out, "{:04}", datetime.year()).unwrap();
+            }
+        }
+        Field::ShortYear => number(out, datetime.year().rem_euclid(100) as u32, 2),
+        Field::Month => number(out, datetime.month(), 2),
+        Field::MonthName(case) => name(out, month_name, case, true),
+        Field::MonthAbbr(case) => name(out, &month_name[..3], case, false),
+        Field::Day => number(out, datetime.day(), 2),
+        Field::DayOfYear => number(out, datetime.ordinal(), 3),
+        Field::DayOfWeek => number(out, datetime.weekday().number_from_sunday(), 1),
+        Field::DayName(case) => name(out, day_name, case, true),
+        Field::DayAbbr(case) => name(out, &day_name[..3], case, false),
+        Field::IsoWeek => number(out, datetime.iso_week().week(), 2),
+        Field::Quarter => number(out, datetime.month0() / 3 + 1, 1),
+        Field::Hour24 => number(out, datetime.hour(), 2),
+        Field::Hour12 => number(out, datetime.hour12().1, 2),
+        Field::Minute => number(out, datetime.minute(), 2),
+        Field::Second => number(out, datetime.second(), 2),
+        Field::Millisecond => number(out, nanos / 1_000_000, 3),
+        Field::Microsecond => number(out, nanos / 1000, 6),
+        Field::Meridiem { upper, dots } => {
+            let meridiem = match (datetime.hour12().0, dots) {
+                (false, false) => "AM",
+                (true, false) => "PM",
+                (false, true) => "A.M.",
+                (true, true) => "P.M.",
+            };
+            let case = if upper { Case::Upper } else { Case::Lower };
+            name(out, meridiem, case, false);
+        }
+    }
+}
+
+/// The fields parsed by [`DateTimeFormat::parse`].
+#[derive(Default)]
+struct ParsedFields {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    day_of_year: Option<u32>,
+    hour: u32,
+    /// Whether the hour is on the 12-hour clock, which is set by `HH12` or a meridiem.
+    twelve_hour: bool,
+    pm: bool,
+    minute: u32,
+    second: u32,
+    microsecond: u32,
+}
+
+impl ParsedFields {
+    /// Parses a field at the start of `s`, returning the rest.
+    fn parse_field<'a>(&mut self, field: Field, s: &'a str) -> Result<&'a str> {
+        let invalid = || {
+            RwError::from(InvalidParameterValue(format!(
+                "invalid value \"{}\" for \"{}\"",
+                s.chars().take(field.pattern().len()).collect::<String>(),
+                field.pattern()
+            )))
+        };
+        // Returns the index of the name that `s` starts with, ignoring case.
+        let find_name = |names: &mut dyn Iterator<Item = &str>| {
+            names
+                .enumerate()
+                .find(|(_, name)| {
+                    s.get(..name.len())
+                        .map_or(false, |prefix| prefix.eq_ignore_ascii_case(name))
+                })
+                .map(|(i, name)| (i, &s[name.len()..]))
+                .ok_or_else(invalid)
+        };
+        match field {
+            Field::MonthName(_) => {
+                let (month, rest) = find_name(&mut MONTH_NAMES.iter().copied())?;
+                self.month = Some(month as u32 + 1);
+                return Ok(rest);
+            }
+            Field::MonthAbbr(_) => {
+                let (month, rest) = find_name(&mut MONTH_NAMES.iter().map(|name| &name[..3]))?;
+                self.month = Some(month as u32 + 1);
+                return Ok(rest);
+            }
+            // The day of the week is ignored, as in PG.
+            Field::DayName(_) => return Ok(find_name(&mut DAY_NAMES.iter().copied())?.1),
+            Field::DayAbbr(_) => {
+                return Ok(find_name(&mut DAY_NAMES.iter().map(|name| &name[..3]))?.1)
+            }
+            Field::Meridiem { .. } => {
+                let (meridiem, rest) =
+                    find_name(&mut ["AM", "PM", "A.M.", "P.M."].iter().copied())?;
+                self.twelve_hour = true;
+                self.pm = meridiem % 2 == 1;
+                return Ok(rest);
+            }
+            _ => {}
+        }
+
+        let digits = s
+            .bytes()
+            .take(field.max_digits())
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if digits == 0 {
+            return Err(invalid());
+        }
+        let value: u32 = s[..digits].parse().unwrap();
+        match field {
+            Field::Year => self.year = Some(value as i32),
+            // Two-digit years are adjusted to the nearest year to 2020.
+            Field::ShortYear => {
+                let century = if value < 70 { 2000 } else { 1900 };
+                self.year = Some(century + value as i32);
+            }
+            Field::Month => self.month = Some(value),
+            Field::Day => self.day = Some(value),
+            Field::DayOfYear => self.day_of_year = Some(value),
+            Field::Hour24 => self.hour = value,
+            Field::Hour12 => {
+                self.hour = value;
+                self.twelve_hour = true;
+            }
+            Field::Minute => self.minute = value,
+            Field::Second => self.second = value,
+            // `MS` and `US` are the digits after the decimal point, e.g. `12.3` for `SS.MS` is 12
+            // seconds and 300 milliseconds.
+            Field::Millisecond => self.microsecond += value * 10u32.pow((3 - digits) as u32) * 1000,
+            Field::Microsecond => self.microsecond += value * 10u32.pow((6 - digits) as u32),
+            // Ignored, as in PG.
+            Field::DayOfWeek | Field::IsoWeek | Field::Quarter => {}
+            _ => unreachable!(),
+        }
+        Ok(&s[digits..])
+    }
+
+    fn into_datetime(self) -> Result<NaiveDateTime> {
+        let out_of_range = |what: &str| {
+            RwError::from(InvalidParameterValue(format!(
+                "{} out of range in to_timestamp",
+                what
+            )))
+        };
+        let year = self.year.unwrap_or(1);
+        let date = match (self.day_of_year, self.month, self.day) {
+            (Some(day_of_year), None, None) => NaiveDate::from_yo_opt(year, day_of_year),
+            (_, month, day) => NaiveDate::from_ymd_opt(year, month.unwrap_or(1), day.unwrap_or(1)),
+        }
+        .ok_or_else(|| out_of_range("date"))?;
+
+        let mut hour = self.hour;
+        if self.twelve_hour {
+            if !(1..=12).contains(&hour) {
+                return Err(RwError::from(InvalidParameterValue(format!(
+                    "hour \"{}\" is invalid for the 12-hour clock",
+                    hour
+                ))));
+            }
+            hour = hour % 12 + if self.pm { 12 } else { 0 };
+        }
+        let time = NaiveTime::from_hms_micro_opt(hour, self.minute, self.second, self.microsecond)
+            .ok_or_else(|| out_of_range("time"))?;
+        Ok(NaiveDateTime::new(date, time))
+    }
+}
+
+#[inline(always)]
+pub fn to_char_timestamp(
+    timestamp: NaiveDateTimeWrapper,
+    format: &str,
+    writer: BytesWriter,
+) -> Result<BytesGuard> {
+    to_char_timestamp_with_format(timestamp, &DateTimeFormat::compile(format), writer)
+}
+
+/// [`to_char_timestamp`] with a compiled format, for a constant format.
+#[inline(always)]
+pub fn to_char_timestamp_with_format(
+    timestamp: NaiveDateTimeWrapper,
+    format: &DateTimeFormat,
+    writer: BytesWriter,
+) -> Result<BytesGuard> {
+    let mut result = String::new();
+    format.format(&timestamp.0, &mut result);
+    writer.write_ref(&result)
+}
+
+/// `to_timestamp(text, format)`. The result is in local time, which the caller converts to a
+/// `timestamp with time zone`.
+#[inline(always)]
+pub fn to_timestamp(s: &str, format: &str) -> Result<NaiveDateTimeWrapper> {
+    to_timestamp_with_format(s, &DateTimeFormat::compile(format))
+}
+
+/// [`to_timestamp`] with a compiled format, for a constant format.
+#[inline(always)]
+pub fn to_timestamp_with_format(s: &str, format: &DateTimeFormat) -> Result<NaiveDateTimeWrapper> {
+    format.parse(s).map(NaiveDateTimeWrapper::new)
+}
+
+/// `to_timestamp(double precision)`: converts seconds since the Unix epoch to a
+/// `timestamp with time zone`.
+#[inline(always)]
+pub fn to_timestamp_from_epoch(seconds: OrderedF64) -> Result<i64> {
+    let micros = (seconds.0 * 1e6).round();
+    if !(i64::MIN as f64..=i64::MAX as f64).contains(&micros) {
+        return Err(RwError::from(InvalidParameterValue(format!(
+            "timestamp out of range: \"{}\"",
+            seconds
+        ))));
+    }
+    Ok(micros as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").unwrap()
+    }
+
+    fn to_char(s: &str, format: &str) -> String {
+        let mut out = String::new();
+        DateTimeFormat::compile(format).format(&datetime(s), &mut out);
+        out
+    }
+
+    #[test]
+    fn test_to_char() {
+        let ts = "2022-03-07 15:04:05.012345";
+        assert_eq!(
+            to_char(ts, "YYYY-MM-DD HH24:MI:SS.US"),
+            "2022-03-07 15:04:05.012345"
+        );
+        assert_eq!(
+            to_char(ts, "yyyy/mm/dd hh12:mi:ss.ms am"),
+            "2022/03/07 03:04:05.012 pm"
+        );
+        assert_eq!(
+            to_char(ts, "Day, DD Month YY"),
+            "Monday   , 07 March     22"
+        );
+        assert_eq!(to_char(ts, "FMDay, FMDD FMMonth"), "Monday, 7 March");
+        assert_eq!(to_char(ts, "DY MON dy mon"), "MON MAR mon mar");
+        assert_eq!(to_char(ts, "DDD D IW Q P.M."), "066 2 10 1 P.M.");
+        assert_eq!(to_char(ts, "\"Year\" YYYY \\Q Q"), "Year 2022 Q 1");
+        assert_eq!(to_char("2022-03-07 00:30:00", "HH12 AM"), "12 AM");
+    }
+
+    #[test]
+    fn test_to_timestamp() {
+        let parse = |s: &str, format: &str| to_timestamp(s, format).map(|ts| ts.0);
+        assert_eq!(
+            parse("2022-03-07 15:04:05", "YYYY-MM-DD HH24:MI:SS").unwrap(),
+            datetime("2022-03-07 15:04:05")
+        );
+        assert_eq!(
+            parse("07 Mar 2022  3:04 pm", "DD Mon YYYY HH12:MI AM").unwrap(),
+            datetime("2022-03-07 15:04:00")
+        );
+        assert_eq!(
+            parse("20220307", "YYYYMMDD").unwrap(),
+            datetime("2022-03-07 00:00:00")
+        );
+        assert_eq!(
+            parse("March 7, 99", "Month DD, YY").unwrap(),
+            datetime("1999-03-07 00:00:00")
+        );
+        assert_eq!(
+            parse("2022/066 12.3", "YYYY/DDD SS.MS").unwrap(),
+            datetime("2022-03-07 00:00:12.3")
+        );
+        assert_eq!(
+            parse("12:00 AM", "HH:MI AM").unwrap(),
+            datetime("0001-01-01 00:00:00")
+        );
+        assert!(parse("2022-13-01", "YYYY-MM-DD").is_err());
+        assert!(parse("2022-xx-01", "YYYY-MM-DD").is_err());
+        assert!(parse("13:00 PM", "HH12:MI PM").is_err());
+    }
+
+    #[test]
+    fn test_to_timestamp_from_epoch() {
+        assert_eq!(
+            to_timestamp_from_epoch(OrderedF64::from(1.5)).unwrap(),
+            1_500_000
+        );
+        assert!(to_timestamp_from_epoch(OrderedF64::from(f64::NAN)).is_err());
+    }
+}
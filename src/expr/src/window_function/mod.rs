@@ -0,0 +1,257 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Window functions, which are evaluated over the sorted rows of a partition by both the batch and
+//! the streaming `OverAgg` executors.
+
+mod partition;
+
+use std::fmt;
+
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::window_frame::{Bound as ProstBound, BoundType, Type as ProstFrameType};
+use risingwave_pb::expr::window_function::{GeneralType, Type as ProstWindowFuncType};
+use risingwave_pb::expr::{WindowFrame as ProstWindowFrame, WindowFunction};
+
+pub use self::partition::evaluate_partition;
+use crate::expr::AggKind;
+
+/// Kind of window function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WindowFuncKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    /// An aggregate function evaluated over the window frame.
+    Aggregate(AggKind),
+}
+
+impl fmt::Display for WindowFuncKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFuncKind::RowNumber => write!(f, "row_number"),
+            WindowFuncKind::Rank => write!(f, "rank"),
+            WindowFuncKind::DenseRank => write!(f, "dense_rank"),
+            WindowFuncKind::Lag => write!(f, "lag"),
+            WindowFuncKind::Lead => write!(f, "lead"),
+            WindowFuncKind::Aggregate(agg_kind) => write!(f, "{}", agg_kind),
+        }
+    }
+}
+
+impl WindowFuncKind {
+    /// Whether the function is evaluated over the window frame. The other functions only depend
+    /// on the partition and the position of the current row.
+    pub fn uses_frame(&self) -> bool {
+        matches!(self, WindowFuncKind::Aggregate(_))
+    }
+
+    pub fn from_protobuf(prost: &ProstWindowFuncType) -> Result<Self> {
+        let kind = match prost {
+            ProstWindowFuncType::General(general) => match GeneralType::from_i32(*general) {
+                Some(GeneralType::RowNumber) => WindowFuncKind::RowNumber,
+                Some(GeneralType::Rank) => WindowFuncKind::Rank,
+                Some(GeneralType::DenseRank) => WindowFuncKind::DenseRank,
+                Some(GeneralType::Lag) => WindowFuncKind::Lag,
+                Some(GeneralType::Lead) => WindowFuncKind::Lead,
+                Some(GeneralType::Invalid) | None => {
+                    return Err(
+                        ErrorCode::InternalError("Unrecognized window function.".into()).into(),
+                    )
+                }
+            },
+            ProstWindowFuncType::Aggregate(agg_type) => {
+                let agg_type = risingwave_pb::expr::agg_call::Type::from_i32(*agg_type)
+                    .ok_or_else(|| {
+                        RwError::from(ErrorCode::InternalError("Unrecognized agg.".into()))
+                    })?;
+                WindowFuncKind::Aggregate(AggKind::try_from(agg_type)?)
+            }
+        };
+        Ok(kind)
+    }
+
+    pub fn to_protobuf(&self) -> ProstWindowFuncType {
+        let general = match self {
+            WindowFuncKind::RowNumber => GeneralType::RowNumber,
+            WindowFuncKind::Rank => GeneralType::Rank,
+            WindowFuncKind::DenseRank => GeneralType::DenseRank,
+            WindowFuncKind::Lag => GeneralType::Lag,
+            WindowFuncKind::Lead => GeneralType::Lead,
+            WindowFuncKind::Aggregate(agg_kind) => {
+                return ProstWindowFuncType::Aggregate(agg_kind.to_prost() as i32)
+            }
+        };
+        ProstWindowFuncType::General(general as i32)
+    }
+}
+
+/// Unit of the offsets in a [`Frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameUnits {
+    /// Offsets count physical rows.
+    Rows,
+    /// The frame is made up of peer groups, i.e. rows with the same `ORDER BY` values. Only
+    /// unbounded and `CURRENT ROW` bounds are supported.
+    Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(usize),
+    CurrentRow,
+    Following(usize),
+    UnboundedFollowing,
+}
+
+impl fmt::Display for FrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameBound::UnboundedPreceding => write!(f, "UNBOUNDED PRECEDING"),
+            FrameBound::Preceding(n) => write!(f, "{} PRECEDING", n),
+            FrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            FrameBound::Following(n) => write!(f, "{} FOLLOWING", n),
+            FrameBound::UnboundedFollowing => write!(f, "UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+impl FrameBound {
+    fn from_protobuf(prost: &ProstBound) -> Result<Self> {
+        let bound = match prost.get_type()? {
+            BoundType::UnboundedPreceding => FrameBound::UnboundedPreceding,
+            BoundType::Preceding => FrameBound::Preceding(prost.offset as usize),
+            BoundType::CurrentRow => FrameBound::CurrentRow,
+            BoundType::Following => FrameBound::Following(prost.offset as usize),
+            BoundType::UnboundedFollowing => FrameBound::UnboundedFollowing,
+            BoundType::BoundInvalid => {
+                return Err(ErrorCode::InternalError("Unrecognized frame bound.".into()).into())
+            }
+        };
+        Ok(bound)
+    }
+
+    fn to_protobuf(self) -> ProstBound {
+        let (r#type, offset) = match self {
+            FrameBound::UnboundedPreceding => (BoundType::UnboundedPreceding, 0),
+            FrameBound::Preceding(n) => (BoundType::Preceding, n as u64),
+            FrameBound::CurrentRow => (BoundType::CurrentRow, 0),
+            FrameBound::Following(n) => (BoundType::Following, n as u64),
+            FrameBound::UnboundedFollowing => (BoundType::UnboundedFollowing, 0),
+        };
+        ProstBound {
+            r#type: r#type as i32,
+            offset,
+        }
+    }
+}
+
+/// The frame of a window function, i.e. the rows of the partition the function is evaluated on
+/// for the current row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Frame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+impl Default for Frame {
+    /// The default frame `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, which includes the
+    /// peers of the current row. Without `ORDER BY`, all rows of the partition are peers.
+    fn default() -> Self {
+        Self {
+            units: FrameUnits::Range,
+            start: FrameBound::UnboundedPreceding,
+            end: FrameBound::CurrentRow,
+        }
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = match self.units {
+            FrameUnits::Rows => "ROWS",
+            FrameUnits::Range => "RANGE",
+        };
+        write!(f, "{} BETWEEN {} AND {}", units, self.start, self.end)
+    }
+}
+
+impl Frame {
+    pub fn from_protobuf(prost: &ProstWindowFrame) -> Result<Self> {
+        let units = match prost.get_type()? {
+            ProstFrameType::Rows => FrameUnits::Rows,
+            ProstFrameType::Range => FrameUnits::Range,
+            ProstFrameType::Invalid => {
+                return Err(ErrorCode::InternalError("Unrecognized frame type.".into()).into())
+            }
+        };
+        Ok(Self {
+            units,
+            start: FrameBound::from_protobuf(prost.get_start()?)?,
+            end: FrameBound::from_protobuf(prost.get_end()?)?,
+        })
+    }
+
+    pub fn to_protobuf(&self) -> ProstWindowFrame {
+        let r#type = match self.units {
+            FrameUnits::Rows => ProstFrameType::Rows,
+            FrameUnits::Range => ProstFrameType::Range,
+        };
+        ProstWindowFrame {
+            r#type: r#type as i32,
+            start: Some(self.start.to_protobuf()),
+            end: Some(self.end.to_protobuf()),
+        }
+    }
+}
+
+/// A window function call to evaluate, whose arguments are columns of the input.
+#[derive(Debug, Clone)]
+pub struct WindowFuncCall {
+    pub kind: WindowFuncKind,
+    /// Indices and types of the argument columns.
+    pub args: Vec<(usize, DataType)>,
+    pub return_type: DataType,
+    pub frame: Frame,
+    /// Offset of `LAG` and `LEAD`.
+    pub offset: usize,
+}
+
+impl WindowFuncCall {
+    pub fn from_protobuf(prost: &WindowFunction) -> Result<Self> {
+        let kind = WindowFuncKind::from_protobuf(prost.get_type()?)?;
+        let args = prost
+            .get_args()
+            .iter()
+            .map(|arg| {
+                Ok((
+                    arg.get_input()?.get_column_idx() as usize,
+                    DataType::from(arg.get_type()?),
+                ))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            kind,
+            args,
+            return_type: DataType::from(prost.get_return_type()?),
+            frame: Frame::from_protobuf(prost.get_frame()?)?,
+            offset: prost.offset as usize,
+        })
+    }
+}
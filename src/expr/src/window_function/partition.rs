@@ -0,0 +1,315 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Range;
+
+use itertools::Itertools;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_pb::expr::agg_call::Arg;
+use risingwave_pb::expr::{AggCall, InputRefExpr};
+
+use super::{Frame, FrameBound, FrameUnits, WindowFuncCall, WindowFuncKind};
+use crate::expr::AggKind;
+use crate::vector_op::agg::{AggStateFactory, BoxedAggState};
+
+/// Evaluates the window function `calls` over the `rows` of a partition, which must be sorted by
+/// the `ORDER BY` of the window. Rows with the same values on `order_key_indices` are peers.
+///
+/// Returns the results of all calls for each row.
+pub fn evaluate_partition(
+    calls: &[WindowFuncCall],
+    rows: &[Row],
+    input_types: &[DataType],
+    order_key_indices: &[usize],
+) -> Result<Vec<Row>> {
+    let peer_groups = PeerGroups::new(rows, order_key_indices);
+    // Aggregators consume data chunks, so the chunk is only built when there are aggregates.
+    let chunk = if calls.iter().any(|call| call.kind.uses_frame()) {
+        Some(DataChunk::from_rows(rows, input_types)?)
+    } else {
+        None
+    };
+
+    let mut outputs = vec![Vec::with_capacity(calls.len()); rows.len()];
+    for call in calls {
+        let results: Vec<Datum> = match &call.kind {
+            WindowFuncKind::RowNumber => (0..rows.len())
+                .map(|i| Some(ScalarImpl::Int64(i as i64 + 1)))
+                .collect(),
+            WindowFuncKind::Rank => (0..rows.len())
+                .map(|i| Some(ScalarImpl::Int64(peer_groups.start(i) as i64 + 1)))
+                .collect(),
+            WindowFuncKind::DenseRank => (0..rows.len())
+                .map(|i| Some(ScalarImpl::Int64(peer_groups.group(i) as i64 + 1)))
+                .collect(),
+            WindowFuncKind::Lag => {
+                let arg = single_arg(call)?;
+                (0..rows.len())
+                    .map(|i| {
+                        i.checked_sub(call.offset)
+                            .and_then(|j| rows[j][arg].clone())
+                    })
+                    .collect()
+            }
+            WindowFuncKind::Lead => {
+                let arg = single_arg(call)?;
+                (0..rows.len())
+                    .map(|i| rows.get(i + call.offset).and_then(|row| row[arg].clone()))
+                    .collect()
+            }
+            WindowFuncKind::Aggregate(agg_kind) => {
+                evaluate_aggregate(agg_kind, call, chunk.as_ref().unwrap(), &peer_groups)?
+            }
+        };
+        outputs
+            .iter_mut()
+            .zip_eq(results)
+            .for_each(|(output, result)| output.push(result));
+    }
+    Ok(outputs.into_iter().map(Row).collect())
+}
+
+fn single_arg(call: &WindowFuncCall) -> Result<usize> {
+    match &call.args[..] {
+        [(idx, _)] => Ok(*idx),
+        _ => Err(ErrorCode::InternalError(format!(
+            "{} requires exactly 1 argument, got {}",
+            call.kind,
+            call.args.len()
+        ))
+        .into()),
+    }
+}
+
+/// Groups of consecutive peer rows in a sorted partition.
+struct PeerGroups {
+    /// Index of the group of each row.
+    groups: Vec<usize>,
+    /// Index of the first row of each group, followed by the number of rows.
+    starts: Vec<usize>,
+}
+
+impl PeerGroups {
+    fn new(rows: &[Row], order_key_indices: &[usize]) -> Self {
+        let mut groups = Vec::with_capacity(rows.len());
+        let mut starts = vec![];
+        for (i, row) in rows.iter().enumerate() {
+            if i == 0
+                || order_key_indices
+                    .iter()
+                    .any(|&key| row[key] != rows[i - 1][key])
+            {
+                starts.push(i);
+            }
+            groups.push(starts.len() - 1);
+        }
+        starts.push(rows.len());
+        Self { groups, starts }
+    }
+
+    fn group(&self, row_idx: usize) -> usize {
+        self.groups[row_idx]
+    }
+
+    /// Index of the first peer of the row.
+    fn start(&self, row_idx: usize) -> usize {
+        self.starts[self.group(row_idx)]
+    }
+
+    /// Index after the last peer of the row.
+    fn end(&self, row_idx: usize) -> usize {
+        self.starts[self.group(row_idx) + 1]
+    }
+}
+
+/// Returns the range of rows in the frame of the `row_idx`-th row of a partition with `len` rows.
+fn frame_range(
+    frame: &Frame,
+    row_idx: usize,
+    len: usize,
+    peer_groups: &PeerGroups,
+) -> Result<Range<usize>> {
+    let start = match (frame.units, frame.start) {
+        (_, FrameBound::UnboundedPreceding) => 0,
+        (FrameUnits::Rows, FrameBound::Preceding(n)) => row_idx.saturating_sub(n),
+        (FrameUnits::Rows, FrameBound::CurrentRow) => row_idx,
+        (FrameUnits::Rows, FrameBound::Following(n)) => (row_idx + n).min(len),
+        (FrameUnits::Range, FrameBound::CurrentRow) => peer_groups.start(row_idx),
+        (_, FrameBound::UnboundedFollowing) => len,
+        (FrameUnits::Range, bound) => return Err(unsupported_range_bound(bound)),
+    };
+    let end = match (frame.units, frame.end) {
+        (_, FrameBound::UnboundedPreceding) => 0,
+        (FrameUnits::Rows, FrameBound::Preceding(n)) => (row_idx + 1).saturating_sub(n),
+        (FrameUnits::Rows, FrameBound::CurrentRow) => row_idx + 1,
+        (FrameUnits::Rows, FrameBound::Following(n)) => (row_idx + n + 1).min(len),
+        (FrameUnits::Range, FrameBound::CurrentRow) => peer_groups.end(row_idx),
+        (_, FrameBound::UnboundedFollowing) => len,
+        (FrameUnits::Range, bound) => return Err(unsupported_range_bound(bound)),
+    };
+    Ok(start..end.max(start))
+}
+
+fn unsupported_range_bound(bound: FrameBound) -> RwError {
+    ErrorCode::InternalError(format!(
+        "frame bound {} is not supported in RANGE mode",
+        bound
+    ))
+    .into()
+}
+
+fn evaluate_aggregate(
+    agg_kind: &AggKind,
+    call: &WindowFuncCall,
+    chunk: &DataChunk,
+    peer_groups: &PeerGroups,
+) -> Result<Vec<Datum>> {
+    let len = chunk.cardinality();
+    let mut results = Vec::with_capacity(len);
+    if call.frame.start == FrameBound::UnboundedPreceding {
+        // The end of the frame never moves backwards, so the rows can be accumulated in one state.
+        let mut state = create_agg_state(agg_kind, call)?;
+        let mut accumulated = 0;
+        for row_idx in 0..len {
+            let frame = frame_range(&call.frame, row_idx, len, peer_groups)?;
+            while accumulated < frame.end {
+                state.update_with_row(chunk, accumulated)?;
+                accumulated += 1;
+            }
+            results.push(output_agg_state(&state, &call.return_type)?);
+        }
+    } else {
+        for row_idx in 0..len {
+            let mut state = create_agg_state(agg_kind, call)?;
+            for i in frame_range(&call.frame, row_idx, len, peer_groups)? {
+                state.update_with_row(chunk, i)?;
+            }
+            results.push(output_agg_state(&state, &call.return_type)?);
+        }
+    }
+    Ok(results)
+}
+
+fn create_agg_state(agg_kind: &AggKind, call: &WindowFuncCall) -> Result<BoxedAggState> {
+    let agg_call = AggCall {
+        r#type: agg_kind.to_prost() as i32,
+        args: call
+            .args
+            .iter()
+            .map(|(idx, data_type)| Arg {
+                input: Some(InputRefExpr {
+                    column_idx: *idx as i32,
+                }),
+                r#type: Some(data_type.to_protobuf()),
+            })
+            .collect(),
+        return_type: Some(call.return_type.to_protobuf()),
+        distinct: false,
+    };
+    AggStateFactory::new(&agg_call)?.create_agg_state()
+}
+
+fn output_agg_state(state: &BoxedAggState, return_type: &DataType) -> Result<Datum> {
+    let mut builder = return_type.create_array_builder(1)?;
+    state.output(&mut builder)?;
+    Ok(builder.finish()?.datum_at(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_call(kind: WindowFuncKind, args: Vec<usize>, return_type: DataType) -> WindowFuncCall {
+        WindowFuncCall {
+            kind,
+            args: args.into_iter().map(|idx| (idx, DataType::Int32)).collect(),
+            return_type,
+            frame: Frame::default(),
+            offset: 1,
+        }
+    }
+
+    fn column(outputs: &[Row], idx: usize) -> Vec<Datum> {
+        outputs.iter().map(|row| row[idx].clone()).collect()
+    }
+
+    fn int64_datums<const N: usize>(values: [i64; N]) -> Vec<Datum> {
+        values.map(|v| Some(ScalarImpl::Int64(v))).to_vec()
+    }
+
+    fn int32_datums<const N: usize>(values: [Option<i32>; N]) -> Vec<Datum> {
+        values.map(|v| v.map(ScalarImpl::Int32)).to_vec()
+    }
+
+    #[test]
+    fn test_evaluate_partition() {
+        let rows = [(1, 10), (2, 20), (2, 30), (3, 40)]
+            .into_iter()
+            .map(|(k, v)| Row(vec![Some(ScalarImpl::Int32(k)), Some(ScalarImpl::Int32(v))]))
+            .collect_vec();
+        let input_types = [DataType::Int32, DataType::Int32];
+
+        let mut lead = make_call(WindowFuncKind::Lead, vec![1], DataType::Int32);
+        lead.offset = 2;
+        let mut sliding_sum = make_call(
+            WindowFuncKind::Aggregate(AggKind::Sum),
+            vec![1],
+            DataType::Int64,
+        );
+        sliding_sum.frame = Frame {
+            units: FrameUnits::Rows,
+            start: FrameBound::Preceding(1),
+            end: FrameBound::CurrentRow,
+        };
+        let mut count_all = make_call(
+            WindowFuncKind::Aggregate(AggKind::Count),
+            vec![],
+            DataType::Int64,
+        );
+        count_all.frame.end = FrameBound::UnboundedFollowing;
+        let calls = vec![
+            make_call(WindowFuncKind::RowNumber, vec![], DataType::Int64),
+            make_call(WindowFuncKind::Rank, vec![], DataType::Int64),
+            make_call(WindowFuncKind::DenseRank, vec![], DataType::Int64),
+            make_call(WindowFuncKind::Lag, vec![1], DataType::Int32),
+            lead,
+            make_call(
+                WindowFuncKind::Aggregate(AggKind::Sum),
+                vec![1],
+                DataType::Int64,
+            ),
+            sliding_sum,
+            count_all,
+        ];
+
+        let outputs = evaluate_partition(&calls, &rows, &input_types, &[0]).unwrap();
+        assert_eq!(column(&outputs, 0), int64_datums([1, 2, 3, 4]));
+        assert_eq!(column(&outputs, 1), int64_datums([1, 2, 2, 4]));
+        assert_eq!(column(&outputs, 2), int64_datums([1, 2, 2, 3]));
+        assert_eq!(
+            column(&outputs, 3),
+            int32_datums([None, Some(10), Some(20), Some(30)])
+        );
+        assert_eq!(
+            column(&outputs, 4),
+            int32_datums([Some(30), Some(40), None, None])
+        );
+        // The default frame includes the peers of the current row.
+        assert_eq!(column(&outputs, 5), int64_datums([10, 60, 60, 100]));
+        assert_eq!(column(&outputs, 6), int64_datums([10, 30, 50, 70]));
+        assert_eq!(column(&outputs, 7), int64_datums([4, 4, 4, 4]));
+    }
+}
@@ -13,10 +13,11 @@
 // limitations under the License.
 
 use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{BinaryOperator, Expr};
 
 use crate::binder::Binder;
-use crate::expr::{ExprImpl, ExprType, FunctionCall};
+use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall};
 
 impl Binder {
     pub(super) fn bind_binary_op(
@@ -25,8 +26,7 @@ impl Binder {
         op: BinaryOperator,
         right: Expr,
     ) -> Result<ExprImpl> {
-        let bound_left = self.bind_expr(left)?;
-        let bound_right = self.bind_expr(right)?;
+        let (bound_left, bound_right) = self.bind_binary_operands(left, right)?;
         let func_type = match op {
             BinaryOperator::Plus => ExprType::Add,
             BinaryOperator::Minus => ExprType::Subtract,
@@ -42,18 +42,96 @@ impl Binder {
             BinaryOperator::And => ExprType::And,
             BinaryOperator::Or => ExprType::Or,
             BinaryOperator::Like => ExprType::Like,
-            BinaryOperator::NotLike => return self.bind_not_like(bound_left, bound_right),
+            BinaryOperator::NotLike => {
+                return Self::bind_negated(ExprType::Like, vec![bound_left, bound_right])
+            }
+            BinaryOperator::ILike => ExprType::ILike,
+            BinaryOperator::NotILike => {
+                return Self::bind_negated(ExprType::ILike, vec![bound_left, bound_right])
+            }
+            BinaryOperator::PGRegexMatch => ExprType::RegexpLike,
+            BinaryOperator::PGRegexNotMatch => {
+                return Self::bind_negated(ExprType::RegexpLike, vec![bound_left, bound_right])
+            }
+            BinaryOperator::PGRegexIMatch => {
+                return Ok(FunctionCall::new(
+                    ExprType::RegexpLike,
+                    vec![bound_left, bound_right, Self::case_insensitive_flag()],
+                )?
+                .into())
+            }
+            BinaryOperator::PGRegexNotIMatch => {
+                return Self::bind_negated(
+                    ExprType::RegexpLike,
+                    vec![bound_left, bound_right, Self::case_insensitive_flag()],
+                )
+            }
+            BinaryOperator::PGJsonAccess => ExprType::JsonbAccessInner,
+            BinaryOperator::PGJsonAccessText => ExprType::JsonbAccessStr,
+            BinaryOperator::PGJsonPathAccess => ExprType::JsonbExtractPath,
+            BinaryOperator::PGJsonPathAccessText => ExprType::JsonbExtractPathText,
+            BinaryOperator::PGJsonContains => {
+                // Allows a string literal on the right side, e.g. `v @> '{"a": 1}'`.
+                let bound_right = bound_right.cast_assign(DataType::Jsonb)?;
+                return Ok(FunctionCall::new(
+                    ExprType::JsonbContains,
+                    vec![bound_left, bound_right],
+                )?
+                .into());
+            }
+            BinaryOperator::PGJsonExists => ExprType::JsonbExists,
             _ => return Err(ErrorCode::NotImplemented(format!("{:?}", op), 112.into()).into()),
         };
         Ok(FunctionCall::new(func_type, vec![bound_left, bound_right])?.into())
     }
 
-    /// Apply a NOT on top of LIKE.
-    fn bind_not_like(&mut self, left: ExprImpl, right: ExprImpl) -> Result<ExprImpl> {
+    /// Bind `[ NOT ] SIMILAR TO`. The escape character, if given, is passed as the third argument.
+    pub(super) fn bind_similar_to(
+        &mut self,
+        expr: Expr,
+        negated: bool,
+        pattern: Expr,
+        escape_char: Option<Box<Expr>>,
+    ) -> Result<ExprImpl> {
+        let (bound_expr, bound_pattern) = self.bind_binary_operands(expr, pattern)?;
+        let mut inputs = vec![bound_expr, bound_pattern];
+        if let Some(escape_char) = escape_char {
+            inputs.push(self.bind_expr(*escape_char)?);
+        }
+        if negated {
+            Self::bind_negated(ExprType::SimilarTo, inputs)
+        } else {
+            Ok(FunctionCall::new(ExprType::SimilarTo, inputs)?.into())
+        }
+    }
+
+    /// Bind the operands of a binary operator. A `$n` parameter of unknown type takes the type of
+    /// the other operand, e.g. `$1` in `v1 = $1` takes the type of `v1`.
+    fn bind_binary_operands(&mut self, left: Expr, right: Expr) -> Result<(ExprImpl, ExprImpl)> {
+        if let Expr::Parameter { index } = left {
+            let bound_right = self.bind_expr(right)?;
+            self.infer_param_type(index, bound_right.return_type());
+            Ok((self.bind_expr(left)?, bound_right))
+        } else {
+            let bound_left = self.bind_expr(left)?;
+            if let Expr::Parameter { index } = right {
+                self.infer_param_type(index, bound_left.return_type());
+            }
+            Ok((bound_left, self.bind_expr(right)?))
+        }
+    }
+
+    /// Apply a NOT on top of a pattern matching operator, e.g. `NOT LIKE` and `!~`.
+    fn bind_negated(func_type: ExprType, inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
         Ok(FunctionCall::new(
             ExprType::Not,
-            vec![FunctionCall::new(ExprType::Like, vec![left, right])?.into()],
+            vec![FunctionCall::new(func_type, inputs)?.into()],
         )?
         .into())
     }
+
+    /// The flags argument of `regexp_like` for `~*` and `!~*`.
+    fn case_insensitive_flag() -> ExprImpl {
+        ExprImpl::literal_varchar("i".to_string())
+    }
 }
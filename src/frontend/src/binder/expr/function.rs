@@ -13,17 +13,29 @@
 // limitations under the License.
 
 use itertools::Itertools;
-use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_expr::expr::AggKind;
-use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr};
+use risingwave_expr::window_function::{Frame, FrameBound, FrameUnits, WindowFuncKind};
+use risingwave_sqlparser::ast::{
+    Function, FunctionArg, FunctionArgExpr, WindowFrame, WindowFrameBound, WindowFrameUnits,
+    WindowSpec,
+};
 
 use crate::binder::bind_context::Clause;
 use crate::binder::Binder;
-use crate::expr::{AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal};
+use crate::expr::{AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal, WindowFunction};
+use crate::optimizer::property::Direction;
 
 impl Binder {
     pub(super) fn bind_function(&mut self, f: Function) -> Result<ExprImpl> {
+        // `make_interval` is the only function taking named arguments.
+        if let [name] = f.name.0.as_slice()
+            && name.value.eq_ignore_ascii_case("make_interval")
+            && f.over.is_none()
+        {
+            return self.bind_make_interval(f.args);
+        }
         let mut inputs = f
             .args
             .into_iter()
@@ -34,17 +46,17 @@ impl Binder {
         if f.name.0.len() == 1 {
             let function_name = f.name.0.get(0).unwrap().value.as_str();
             let function_name = function_name.to_lowercase();
-            let agg_kind = match function_name.as_str() {
-                "count" => Some(AggKind::Count),
-                "sum" => Some(AggKind::Sum),
-                "min" => Some(AggKind::Min),
-                "max" => Some(AggKind::Max),
-                "avg" => Some(AggKind::Avg),
-                "string_agg" => Some(AggKind::StringAgg),
-                "single_value" => Some(AggKind::SingleValue),
-                _ => None,
-            };
-            if let Some(kind) = agg_kind {
+            if let Some(window_spec) = f.over {
+                return self.bind_window_function(&function_name, inputs, f.distinct, window_spec);
+            }
+            if let "row_number" | "rank" | "dense_rank" | "lag" | "lead" = function_name.as_str() {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "window function {} requires an OVER clause",
+                    function_name
+                ))
+                .into());
+            }
+            if let Some(kind) = Self::agg_kind(&function_name) {
                 self.ensure_aggregate_allowed()?;
                 return Ok(ExprImpl::AggCall(Box::new(AggCall::new(
                     kind, inputs, f.distinct,
@@ -66,12 +78,34 @@ impl Binder {
                 }
                 "concat_ws" => ExprType::ConcatWs,
                 "split_part" => ExprType::SplitPart,
+                "regexp_like" => ExprType::RegexpLike,
+                "regexp_match" => ExprType::RegexpMatch,
+                "regexp_replace" => ExprType::RegexpReplace,
+                "regexp_split_to_array" => ExprType::RegexpSplitToArray,
                 "coalesce" => ExprType::Coalesce,
                 "round" => {
                     inputs = Self::rewrite_round_args(inputs);
                     ExprType::RoundDigit
                 }
                 "abs" => ExprType::Abs,
+                "sqrt" => ExprType::Sqrt,
+                "date_trunc" => return self.bind_date_trunc(inputs),
+                "date_part" => return self.bind_date_part(inputs),
+                "to_char" => return self.bind_to_char(inputs),
+                "to_timestamp" => return self.bind_to_timestamp(inputs),
+                "age" => return self.bind_age(inputs),
+                "timezone" => {
+                    // `timezone(zone, timestamp)` is the same as `timestamp AT TIME ZONE zone`.
+                    if inputs.len() != 2 {
+                        return Err(ErrorCode::BindError(
+                            "timezone function must contain 2 arguments".to_string(),
+                        )
+                        .into());
+                    }
+                    let timestamp = inputs.pop().unwrap();
+                    let time_zone = inputs.pop().unwrap();
+                    return Self::at_time_zone(timestamp, time_zone);
+                }
                 "booleq" => {
                     inputs = Self::rewrite_two_bool_inputs(inputs)?;
                     ExprType::Equal
@@ -98,6 +132,203 @@ impl Binder {
         }
     }
 
+    fn agg_kind(function_name: &str) -> Option<AggKind> {
+        match function_name {
+            "count" => Some(AggKind::Count),
+            "sum" => Some(AggKind::Sum),
+            "min" => Some(AggKind::Min),
+            "max" => Some(AggKind::Max),
+            "avg" => Some(AggKind::Avg),
+            "string_agg" => Some(AggKind::StringAgg),
+            "single_value" => Some(AggKind::SingleValue),
+            "stddev_pop" => Some(AggKind::StddevPop),
+            "stddev_samp" | "stddev" => Some(AggKind::StddevSamp),
+            "var_pop" => Some(AggKind::VarPop),
+            "var_samp" | "variance" => Some(AggKind::VarSamp),
+            "bool_and" | "every" => Some(AggKind::BoolAnd),
+            "bool_or" => Some(AggKind::BoolOr),
+            "bit_and" => Some(AggKind::BitAnd),
+            "bit_or" => Some(AggKind::BitOr),
+            "array_agg" => Some(AggKind::ArrayAgg),
+            "approx_count_distinct" => Some(AggKind::ApproxCountDistinct),
+            _ => None,
+        }
+    }
+
+    /// Bind a function call with an `OVER` clause.
+    fn bind_window_function(
+        &mut self,
+        function_name: &str,
+        mut inputs: Vec<ExprImpl>,
+        distinct: bool,
+        window_spec: WindowSpec,
+    ) -> Result<ExprImpl> {
+        self.ensure_window_function_allowed()?;
+        if distinct {
+            return Err(ErrorCode::NotImplemented(
+                "DISTINCT in window functions".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+        let kind = match function_name {
+            "row_number" => WindowFuncKind::RowNumber,
+            "rank" => WindowFuncKind::Rank,
+            "dense_rank" => WindowFuncKind::DenseRank,
+            "lag" => WindowFuncKind::Lag,
+            "lead" => WindowFuncKind::Lead,
+            _ => match Self::agg_kind(function_name) {
+                Some(
+                    agg_kind @ (AggKind::Count
+                    | AggKind::Sum
+                    | AggKind::Min
+                    | AggKind::Max
+                    | AggKind::Avg),
+                ) => WindowFuncKind::Aggregate(agg_kind),
+                _ => {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("unsupported window function: {}", function_name),
+                        None.into(),
+                    )
+                    .into())
+                }
+            },
+        };
+        let offset = match kind {
+            WindowFuncKind::Lag | WindowFuncKind::Lead if inputs.len() == 2 => {
+                Self::bind_window_function_offset(&kind, inputs.pop().unwrap())?
+            }
+            WindowFuncKind::Lag | WindowFuncKind::Lead if inputs.len() > 2 => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("default value of {}", kind),
+                    None.into(),
+                )
+                .into())
+            }
+            _ => 1,
+        };
+
+        let partition_by = window_spec
+            .partition_by
+            .into_iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+        let order_by = window_spec
+            .order_by
+            .into_iter()
+            .map(|order_by_expr| {
+                let direct = match order_by_expr.asc {
+                    None | Some(true) => Direction::Asc,
+                    Some(false) => Direction::Desc,
+                };
+                Ok((self.bind_expr(order_by_expr.expr)?, direct))
+            })
+            .collect::<Result<_>>()?;
+        let mut window_function = WindowFunction::new(kind, inputs, partition_by, order_by)?;
+        if let Some(window_frame) = window_spec.window_frame {
+            window_function.frame = Self::bind_window_frame(window_frame)?;
+        }
+        window_function.offset = offset;
+
+        let nested = window_function
+            .args
+            .iter()
+            .chain(window_function.partition_by.iter())
+            .chain(window_function.order_by.iter().map(|(expr, _)| expr))
+            .any(|expr| expr.has_window_function());
+        if nested {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "window function calls cannot be nested".to_string(),
+            )
+            .into());
+        }
+        Ok(window_function.into())
+    }
+
+    /// The offset of `lag` and `lead` must be a non-negative integer constant.
+    fn bind_window_function_offset(kind: &WindowFuncKind, offset: ExprImpl) -> Result<usize> {
+        let offset = match offset.as_literal().map(|literal| literal.get_data()) {
+            Some(Some(ScalarImpl::Int16(v))) => Some(*v as i64),
+            Some(Some(ScalarImpl::Int32(v))) => Some(*v as i64),
+            Some(Some(ScalarImpl::Int64(v))) => Some(*v),
+            _ => None,
+        };
+        match offset {
+            Some(offset) if offset >= 0 => Ok(offset as usize),
+            _ => Err(ErrorCode::NotImplemented(
+                format!("offset of {} must be a non-negative integer constant", kind),
+                None.into(),
+            )
+            .into()),
+        }
+    }
+
+    fn bind_window_frame(window_frame: WindowFrame) -> Result<Frame> {
+        let units = match window_frame.units {
+            WindowFrameUnits::Rows => FrameUnits::Rows,
+            WindowFrameUnits::Range => FrameUnits::Range,
+            WindowFrameUnits::Groups => {
+                return Err(ErrorCode::NotImplemented(
+                    "GROUPS mode of window frame".to_string(),
+                    None.into(),
+                )
+                .into())
+            }
+        };
+        let bind_bound = |bound: WindowFrameBound| {
+            let bound = match bound {
+                WindowFrameBound::CurrentRow => FrameBound::CurrentRow,
+                WindowFrameBound::Preceding(None) => FrameBound::UnboundedPreceding,
+                WindowFrameBound::Preceding(Some(n)) => FrameBound::Preceding(n as usize),
+                WindowFrameBound::Following(None) => FrameBound::UnboundedFollowing,
+                WindowFrameBound::Following(Some(n)) => FrameBound::Following(n as usize),
+            };
+            if units == FrameUnits::Range
+                && let FrameBound::Preceding(_) | FrameBound::Following(_) = bound
+            {
+                return Err(RwError::from(ErrorCode::NotImplemented(
+                    "RANGE mode of window frame with offset".to_string(),
+                    None.into(),
+                )));
+            }
+            Ok(bound)
+        };
+        let start = bind_bound(window_frame.start_bound)?;
+        let end = match window_frame.end_bound {
+            Some(end_bound) => bind_bound(end_bound)?,
+            None => FrameBound::CurrentRow,
+        };
+
+        // The same rules as postgres.
+        let position = |bound: FrameBound| match bound {
+            FrameBound::UnboundedPreceding => 0,
+            FrameBound::Preceding(_) => 1,
+            FrameBound::CurrentRow => 2,
+            FrameBound::Following(_) => 3,
+            FrameBound::UnboundedFollowing => 4,
+        };
+        if start == FrameBound::UnboundedFollowing {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "frame start cannot be UNBOUNDED FOLLOWING".to_string(),
+            )
+            .into());
+        }
+        if end == FrameBound::UnboundedPreceding {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "frame end cannot be UNBOUNDED PRECEDING".to_string(),
+            )
+            .into());
+        }
+        if position(start) > position(end) {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "frame starting from {} cannot end with {}",
+                start, end
+            ))
+            .into());
+        }
+        Ok(Frame { units, start, end })
+    }
+
     /// Make sure inputs only have 2 value and rewrite the arguments.
     /// Nullif(expr1,expr2) -> Case(Equal(expr1 = expr2),null,expr1).
     fn rewrite_nullif_to_case_when(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
@@ -123,7 +354,7 @@ impl Binder {
             vec![
                 input
                     .clone()
-                    .cast_implicit(DataType::Decimal)
+                    .cast_implicit(DataType::Decimal(None))
                     .unwrap_or(input),
                 Literal::new(Some(0.into()), DataType::Int32).into(),
             ]
@@ -133,7 +364,7 @@ impl Binder {
             vec![
                 input
                     .clone()
-                    .cast_implicit(DataType::Decimal)
+                    .cast_implicit(DataType::Decimal(None))
                     .unwrap_or(input),
                 digits
                     .clone()
@@ -172,6 +403,17 @@ impl Binder {
         Ok(())
     }
 
+    fn ensure_window_function_allowed(&self) -> Result<()> {
+        if let Some(clause) = self.context.clause {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "window functions are not allowed in {}",
+                clause
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
     pub(in crate::binder) fn bind_function_expr_arg(
         &mut self,
         arg_expr: FunctionArgExpr,
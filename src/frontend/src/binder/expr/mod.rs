@@ -28,6 +28,7 @@ mod binary_op;
 mod column;
 mod function;
 mod subquery;
+mod temporal;
 mod value;
 
 impl Binder {
@@ -40,6 +41,7 @@ impl Binder {
                 s.cast_explicit(bind_data_type(&data_type)?)
             }
             Expr::Row(exprs) => Ok(ExprImpl::Literal(Box::new(self.bind_row(&exprs)?))),
+            Expr::Parameter { index } => self.bind_parameter(index),
             // input ref
             Expr::Identifier(ident) => self.bind_column(&[ident]),
             Expr::CompoundIdentifier(idents) => self.bind_column(&idents),
@@ -60,6 +62,12 @@ impl Binder {
                 subquery,
                 negated,
             } => self.bind_in_subquery(*expr, *subquery, negated),
+            Expr::AnySubquery { expr, op, subquery } => {
+                self.bind_quantified_subquery(*expr, op, *subquery, false)
+            }
+            Expr::AllSubquery { expr, op, subquery } => {
+                self.bind_quantified_subquery(*expr, op, *subquery, true)
+            }
             // special syntax (except date/time or string)
             Expr::Cast { expr, data_type } => self.bind_cast(*expr, data_type),
             Expr::IsNull(expr) => self.bind_is_operator(ExprType::IsNull, *expr),
@@ -82,6 +90,12 @@ impl Binder {
                 low,
                 high,
             } => self.bind_between(*expr, negated, *low, *high),
+            Expr::SimilarTo {
+                expr,
+                negated,
+                pattern,
+                escape_char,
+            } => self.bind_similar_to(*expr, negated, *pattern, escape_char),
             Expr::InList {
                 expr,
                 list,
@@ -89,6 +103,10 @@ impl Binder {
             } => self.bind_in_list(*expr, list, negated),
             // special syntax for date/time
             Expr::Extract { field, expr } => self.bind_extract(field, *expr),
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => self.bind_at_time_zone(*timestamp, *time_zone),
             // special syntaxt for string
             Expr::Trim { expr, trim_where } => self.bind_trim(*expr, trim_where),
             Expr::Substring {
@@ -339,8 +357,8 @@ impl Binder {
     }
 
     pub(super) fn bind_cast(&mut self, expr: Expr, data_type: AstDataType) -> Result<ExprImpl> {
-        self.bind_expr(expr)?
-            .cast_explicit(bind_data_type(&data_type)?)
+        let expr = self.bind_expr(expr)?;
+        self.bind_temporal_cast(expr, bind_data_type(&data_type)?)
     }
 }
 
@@ -356,6 +374,7 @@ pub fn bind_struct_field(column_def: &StructField) -> Result<ColumnDesc> {
                     name: f.name.value.clone(),
                     field_descs: vec![],
                     type_name: "".to_string(),
+                    default_value: None,
                 })
             })
             .collect::<Result<Vec<_>>>()?
@@ -368,9 +387,14 @@ pub fn bind_struct_field(column_def: &StructField) -> Result<ColumnDesc> {
         name: column_def.name.value.clone(),
         field_descs,
         type_name: "".to_string(),
+        default_value: None,
     })
 }
 
+/// The largest precision of a constrained `NUMERIC`, which is limited by the 96-bit mantissa of
+/// `Decimal`.
+const MAX_NUMERIC_PRECISION: u64 = 28;
+
 pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
     let data_type = match data_type {
         AstDataType::Boolean => DataType::Boolean,
@@ -379,13 +403,34 @@ pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
         AstDataType::BigInt(None) => DataType::Int64,
         AstDataType::Real | AstDataType::Float(Some(1..=24)) => DataType::Float32,
         AstDataType::Double | AstDataType::Float(Some(25..=53) | None) => DataType::Float64,
-        AstDataType::Decimal(None, None) => DataType::Decimal,
+        AstDataType::Decimal(None, None) => DataType::Decimal(None),
+        AstDataType::Decimal(Some(precision), scale) => {
+            let scale = scale.unwrap_or(0);
+            if !(1..=MAX_NUMERIC_PRECISION).contains(precision) {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "NUMERIC precision {} must be between 1 and {}",
+                    precision, MAX_NUMERIC_PRECISION
+                ))
+                .into());
+            }
+            if scale > *precision {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "NUMERIC scale {} must be between 0 and precision {}",
+                    scale, precision
+                ))
+                .into());
+            }
+            DataType::Decimal(Some((*precision as u32, scale as u32)))
+        }
         AstDataType::Varchar(_) => DataType::Varchar,
         AstDataType::Date => DataType::Date,
         AstDataType::Time(false) => DataType::Time,
         AstDataType::Timestamp(false) => DataType::Timestamp,
         AstDataType::Timestamp(true) => DataType::Timestampz,
         AstDataType::Interval => DataType::Interval,
+        AstDataType::Jsonb => DataType::Jsonb,
+        AstDataType::Bytea => DataType::Bytea,
+        AstDataType::Uuid => DataType::Uuid,
         AstDataType::Array(datatype) => DataType::List {
             datatype: Box::new(bind_data_type(datatype)?),
         },
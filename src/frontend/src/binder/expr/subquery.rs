@@ -13,10 +13,10 @@
 // limitations under the License.
 
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::Query;
+use risingwave_sqlparser::ast::{BinaryOperator, Expr, Query};
 
 use crate::binder::Binder;
-use crate::expr::{ExprImpl, Subquery, SubqueryKind};
+use crate::expr::{ExprImpl, ExprType, Subquery, SubqueryKind};
 
 impl Binder {
     pub(super) fn bind_subquery_expr(
@@ -32,4 +32,36 @@ impl Binder {
         }
         Ok(Subquery::new(query, kind).into())
     }
+
+    /// Binds `expr op ANY | SOME (subquery)`, or `expr op ALL (subquery)` if `all` is set.
+    pub(super) fn bind_quantified_subquery(
+        &mut self,
+        expr: Expr,
+        op: BinaryOperator,
+        subquery: Query,
+        all: bool,
+    ) -> Result<ExprImpl> {
+        let func_type = match op {
+            BinaryOperator::Eq => ExprType::Equal,
+            BinaryOperator::NotEq => ExprType::NotEqual,
+            BinaryOperator::Lt => ExprType::LessThan,
+            BinaryOperator::LtEq => ExprType::LessThanOrEqual,
+            BinaryOperator::Gt => ExprType::GreaterThan,
+            BinaryOperator::GtEq => ExprType::GreaterThanOrEqual,
+            _ => {
+                return Err(ErrorCode::BindError(format!(
+                    "operator {} is not supported with ANY or ALL",
+                    op
+                ))
+                .into())
+            }
+        };
+        let bound_expr = self.bind_expr(expr)?;
+        let kind = if all {
+            SubqueryKind::All(bound_expr, func_type)
+        } else {
+            SubqueryKind::Some(bound_expr, func_type)
+        };
+        self.bind_subquery_expr(subquery, kind)
+    }
 }
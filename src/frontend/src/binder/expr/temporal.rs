@@ -0,0 +1,238 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Date/time functions whose binding depends on the `timezone` of the session.
+//!
+//! `timestamptz` is stored as microseconds since the UTC epoch, so functions that work on the
+//! calendar fields (`date_trunc`, `to_char`, ...) first convert it to the local `timestamp` in the
+//! session time zone with `AT TIME ZONE`, and convert the result back when it is a timestamp.
+//!
+//! The session time zone is bound as a constant. A materialized view therefore keeps using the
+//! time zone of the session that created it, even after `SET timezone` or when it is queried from
+//! a session in another time zone.
+
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_sqlparser::ast::{Expr, FunctionArg};
+
+use crate::binder::Binder;
+use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall, Literal};
+
+/// Parameters of `make_interval`, in positional order.
+const MAKE_INTERVAL_PARAMS: [&str; 7] =
+    ["years", "months", "weeks", "days", "hours", "mins", "secs"];
+
+impl Binder {
+    pub(super) fn bind_at_time_zone(
+        &mut self,
+        timestamp: Expr,
+        time_zone: Expr,
+    ) -> Result<ExprImpl> {
+        let timestamp = self.bind_expr(timestamp)?;
+        let time_zone = self.bind_expr(time_zone)?;
+        Self::at_time_zone(timestamp, time_zone)
+    }
+
+    /// `timestamp AT TIME ZONE zone` gives the `timestamptz` of the local time in `zone`, and
+    /// `timestamptz AT TIME ZONE zone` gives the local time in `zone`.
+    pub(super) fn at_time_zone(timestamp: ExprImpl, time_zone: ExprImpl) -> Result<ExprImpl> {
+        let timestamp = Self::date_to_timestamp(timestamp)?;
+        Ok(FunctionCall::new(ExprType::AtTimeZone, vec![timestamp, time_zone])?.into())
+    }
+
+    /// Converts between `timestamp` and `timestamptz` in the session time zone, which is bound as
+    /// a constant. See the module doc.
+    pub(super) fn at_session_time_zone(&self, timestamp: ExprImpl) -> Result<ExprImpl> {
+        Self::at_time_zone(timestamp, ExprImpl::literal_varchar(self.time_zone.clone()))
+    }
+
+    /// Converts a `timestamptz` to the local `timestamp` in the session time zone, and a `date` to
+    /// the `timestamp` of its midnight. Other types are kept as is.
+    fn to_local_timestamp(&self, input: ExprImpl) -> Result<ExprImpl> {
+        match input.return_type() {
+            DataType::Timestampz => self.at_session_time_zone(input),
+            _ => Self::date_to_timestamp(input),
+        }
+    }
+
+    fn date_to_timestamp(input: ExprImpl) -> Result<ExprImpl> {
+        match input.return_type() {
+            DataType::Date => input.cast_implicit(DataType::Timestamp),
+            _ => Ok(input),
+        }
+    }
+
+    /// Explicit casts between `timestamptz` and the local date/time types are done in the session
+    /// time zone.
+    pub(super) fn bind_temporal_cast(&self, input: ExprImpl, target: DataType) -> Result<ExprImpl> {
+        match (input.return_type(), &target) {
+            (DataType::Date | DataType::Timestamp, DataType::Timestampz) => {
+                self.at_session_time_zone(input)
+            }
+            (DataType::Timestampz, DataType::Date | DataType::Time | DataType::Timestamp) => {
+                self.at_session_time_zone(input)?.cast_explicit(target)
+            }
+            _ => input.cast_explicit(target),
+        }
+    }
+
+    /// `date_trunc(field, source)`. A `timestamptz` is truncated in the session time zone.
+    pub(super) fn bind_date_trunc(&self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        let with_time_zone =
+            Self::convert_last_input(&mut inputs, |input| self.to_local_timestamp(input))?;
+        let truncated = FunctionCall::new(ExprType::DateTrunc, inputs)?.into();
+        if with_time_zone {
+            self.at_session_time_zone(truncated)
+        } else {
+            Ok(truncated)
+        }
+    }
+
+    /// `date_part(field, source)`. The fields of a `timestamptz` are those of the local time in the
+    /// session time zone, except that its `epoch` is taken in UTC.
+    pub(super) fn bind_date_part(&self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        let is_epoch = match inputs.first().and_then(|field| field.as_literal()) {
+            Some(literal) => matches!(
+                literal.get_data(),
+                Some(ScalarImpl::Utf8(field)) if field.eq_ignore_ascii_case("epoch")
+            ),
+            None => false,
+        };
+        Self::convert_last_input(&mut inputs, |input| match input.return_type() {
+            DataType::Timestampz if is_epoch => {
+                Self::at_time_zone(input, ExprImpl::literal_varchar("UTC".to_string()))
+            }
+            _ => self.to_local_timestamp(input),
+        })?;
+        Ok(FunctionCall::new(ExprType::DatePart, inputs)?.into())
+    }
+
+    /// `to_char(timestamp, format)`, where a `timestamptz` is formatted as the local time in the
+    /// session time zone.
+    pub(super) fn bind_to_char(&self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        if !inputs.is_empty() {
+            let timestamp = inputs.remove(0);
+            inputs.insert(0, self.to_local_timestamp(timestamp)?);
+        }
+        Ok(FunctionCall::new(ExprType::ToChar, inputs)?.into())
+    }
+
+    /// `age(l, r)`, where `timestamptz` arguments are compared in the session time zone.
+    pub(super) fn bind_age(&self, inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        let inputs = inputs
+            .into_iter()
+            .map(|input| self.to_local_timestamp(input))
+            .collect::<Result<_>>()?;
+        Ok(FunctionCall::new(ExprType::Age, inputs)?.into())
+    }
+
+    /// `to_timestamp(epoch_seconds)` or `to_timestamp(text, format)`, both returning a
+    /// `timestamptz`. The text is parsed as a local time in the session time zone.
+    pub(super) fn bind_to_timestamp(&self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        if inputs.len() == 1 {
+            let seconds = inputs.pop().unwrap().cast_implicit(DataType::Float64)?;
+            return Ok(FunctionCall::new(ExprType::ToTimestamp, vec![seconds])?.into());
+        }
+        let local = FunctionCall::new(ExprType::ToTimestamp, inputs)?.into();
+        self.at_session_time_zone(local)
+    }
+
+    /// `make_interval(years, months, weeks, days, hours, mins, secs)`. Every argument defaults to
+    /// zero, and can also be given by name, e.g. `make_interval(days => 10)`.
+    pub(super) fn bind_make_interval(&mut self, args: Vec<FunctionArg>) -> Result<ExprImpl> {
+        let mut inputs: Vec<Option<ExprImpl>> = vec![None; MAKE_INTERVAL_PARAMS.len()];
+        let mut seen_named = false;
+        for (position, arg) in args.into_iter().enumerate() {
+            let (index, arg) = match arg {
+                FunctionArg::Unnamed(_) if seen_named => {
+                    return Err(ErrorCode::BindError(
+                        "positional argument cannot follow named argument".to_string(),
+                    )
+                    .into());
+                }
+                FunctionArg::Unnamed(arg) => (position, arg),
+                FunctionArg::Named { name, arg } => {
+                    seen_named = true;
+                    let index = MAKE_INTERVAL_PARAMS
+                        .iter()
+                        .position(|param| name.value.eq_ignore_ascii_case(param))
+                        .ok_or_else(|| {
+                            ErrorCode::BindError(format!(
+                                "make_interval has no parameter named \"{}\"",
+                                name.value
+                            ))
+                        })?;
+                    (index, arg)
+                }
+            };
+            if index >= MAKE_INTERVAL_PARAMS.len() {
+                return Err(ErrorCode::BindError(format!(
+                    "make_interval takes at most {} arguments",
+                    MAKE_INTERVAL_PARAMS.len()
+                ))
+                .into());
+            }
+            if inputs[index].is_some() {
+                return Err(ErrorCode::BindError(format!(
+                    "argument \"{}\" of make_interval is specified more than once",
+                    MAKE_INTERVAL_PARAMS[index]
+                ))
+                .into());
+            }
+            let mut bound = self.bind_function_expr_arg(arg)?;
+            if bound.len() != 1 {
+                return Err(ErrorCode::BindError(
+                    "make_interval takes scalar arguments".to_string(),
+                )
+                .into());
+            }
+            inputs[index] = Some(bound.pop().unwrap());
+        }
+
+        let inputs = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| {
+                // All but `secs` are integers.
+                let is_secs = index == MAKE_INTERVAL_PARAMS.len() - 1;
+                match (input, is_secs) {
+                    (Some(input), false) => input.cast_implicit(DataType::Int32),
+                    (Some(input), true) => input.cast_implicit(DataType::Float64),
+                    (None, false) => Ok(ExprImpl::literal_int(0)),
+                    (None, true) => Ok(Literal::new(
+                        Some(ScalarImpl::Float64(0.0.into())),
+                        DataType::Float64,
+                    )
+                    .into()),
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok(FunctionCall::new(ExprType::MakeInterval, inputs)?.into())
+    }
+
+    /// Applies `convert` to the last of the two inputs of `date_trunc` or `date_part`, returning
+    /// whether it was a `timestamptz`.
+    fn convert_last_input(
+        inputs: &mut Vec<ExprImpl>,
+        convert: impl FnOnce(ExprImpl) -> Result<ExprImpl>,
+    ) -> Result<bool> {
+        if inputs.len() != 2 {
+            return Ok(false);
+        }
+        let input = inputs.pop().unwrap();
+        let with_time_zone = input.return_type() == DataType::Timestampz;
+        inputs.push(convert(input)?);
+        Ok(with_time_zone)
+    }
+}
@@ -41,6 +41,57 @@ impl Binder {
         }
     }
 
+    /// Bind a `$n` parameter. When binding a prepared statement without values, it is bound as a
+    /// NULL of its (possibly inferred) type. Otherwise, its text-format value is cast to its type.
+    pub(super) fn bind_parameter(&mut self, index: u64) -> Result<ExprImpl> {
+        let idx = Self::param_index(index)?;
+        let Some(values) = &self.param_values else {
+            if idx >= self.param_types.len() {
+                self.param_types.resize(idx + 1, None);
+            }
+            let data_type = self.param_types[idx].get_or_insert(DataType::Varchar).clone();
+            return Ok(Literal::new(None, data_type).into());
+        };
+
+        let value = values
+            .get(idx)
+            .cloned()
+            .ok_or_else(|| ErrorCode::BindError(format!("there is no parameter ${}", index)))?;
+        let data_type = self
+            .param_types
+            .get(idx)
+            .cloned()
+            .flatten()
+            .unwrap_or(DataType::Varchar);
+        match value {
+            Some(value) => ExprImpl::from(self.bind_string(value)?).cast_explicit(data_type),
+            None => Ok(Literal::new(None, data_type).into()),
+        }
+    }
+
+    /// Set the type of parameter `$index` to `data_type`, if it is neither specified by the
+    /// client nor inferred before. Only takes effect when binding a prepared statement.
+    pub(super) fn infer_param_type(&mut self, index: u64, data_type: DataType) {
+        if self.param_values.is_some() {
+            return;
+        }
+        let Ok(idx) = Self::param_index(index) else {
+            return;
+        };
+        if idx >= self.param_types.len() {
+            self.param_types.resize(idx + 1, None);
+        }
+        self.param_types[idx].get_or_insert(data_type);
+    }
+
+    /// Convert `$index` to a 0-based index, checking it is within the range allowed by PG.
+    fn param_index(index: u64) -> Result<usize> {
+        if index == 0 || index > u16::MAX as u64 {
+            return Err(ErrorCode::BindError(format!("there is no parameter ${}", index)).into());
+        }
+        Ok(index as usize - 1)
+    }
+
     pub(super) fn bind_string(&mut self, s: String) -> Result<Literal> {
         Ok(Literal::new(Some(ScalarImpl::Utf8(s)), DataType::Varchar))
     }
@@ -57,7 +108,7 @@ impl Binder {
         } else {
             // Notice: when the length of decimal exceeds 29(>= 30), it will be rounded up.
             let decimal = str_parse::<Decimal>(&s)?;
-            (Some(ScalarImpl::Decimal(decimal)), DataType::Decimal)
+            (Some(ScalarImpl::Decimal(decimal)), DataType::Decimal(None))
         };
         Ok(Literal::new(data, data_type))
     }
@@ -172,10 +223,10 @@ mod tests {
         let data_type = vec![
             DataType::Int32,
             DataType::Int64,
-            DataType::Decimal,
-            DataType::Decimal,
-            DataType::Decimal,
-            DataType::Decimal,
+            DataType::Decimal(None),
+            DataType::Decimal(None),
+            DataType::Decimal(None),
+            DataType::Decimal(None),
         ];
 
         for i in 0..values.len() {
@@ -205,4 +256,34 @@ mod tests {
             _ => panic!("unexpected type"),
         };
     }
+
+    #[test]
+    fn test_bind_parameter() {
+        let mut binder = mock_binder();
+        // A simple query has no parameter.
+        assert!(binder.bind_parameter(1).is_err());
+
+        // Parameters with values are cast to their types.
+        binder.param_types = vec![Some(DataType::Int32), None];
+        binder.param_values = Some(vec![Some("42".to_owned()), None]);
+        let expr = binder.bind_parameter(1).unwrap();
+        assert_eq!(expr.return_type(), DataType::Int32);
+        assert!(!expr.is_null());
+        let expr = binder.bind_parameter(2).unwrap();
+        assert_eq!(expr.return_type(), DataType::Varchar);
+        assert!(expr.is_null());
+        assert!(binder.bind_parameter(3).is_err());
+        assert!(binder.bind_parameter(0).is_err());
+
+        // Without values, parameter types are inferred and default to varchar.
+        binder.param_types = vec![];
+        binder.param_values = None;
+        binder.infer_param_type(2, DataType::Int64);
+        let expr = binder.bind_parameter(1).unwrap();
+        assert_eq!(expr.return_type(), DataType::Varchar);
+        assert_eq!(
+            binder.export_param_types(),
+            vec![DataType::Varchar, DataType::Int64]
+        );
+    }
 }
@@ -16,6 +16,7 @@ use itertools::Itertools;
 use risingwave_common::array::StructValue;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_pb::user::grant_privilege::Privilege;
 use risingwave_sqlparser::ast::{Ident, ObjectName, Query, SetExpr};
 
 use super::{BoundQuery, BoundSetExpr};
@@ -41,7 +42,7 @@ impl Binder {
         _columns: Vec<Ident>,
         source: Query,
     ) -> Result<BoundInsert> {
-        let table_source = self.bind_table_source(source_name)?;
+        let table_source = self.bind_table_source(source_name, Privilege::Insert)?;
 
         let expected_types = table_source
             .columns
@@ -14,8 +14,11 @@
 
 use std::collections::HashMap;
 
+use risingwave_common::catalog::Field;
 use risingwave_common::error::Result;
-use risingwave_sqlparser::ast::{Statement, TableAlias};
+use risingwave_common::types::DataType;
+use risingwave_pb::user::UserInfo;
+use risingwave_sqlparser::ast::{Expr, Statement, TableAlias};
 
 pub mod bind_context;
 mod delete;
@@ -35,22 +38,36 @@ pub use delete::BoundDelete;
 pub use insert::BoundInsert;
 pub use query::BoundQuery;
 pub use relation::{
-    BoundBaseTable, BoundGenerateSeriesFunction, BoundJoin, BoundSource, BoundTableSource,
-    BoundWindowTableFunction, Relation, WindowTableFunctionKind,
+    BoundBaseTable, BoundGenerateSeriesFunction, BoundJoin, BoundSource, BoundSystemTable,
+    BoundTableFunction, BoundTableSource, BoundWindowTableFunction, Relation, TableFunctionKind,
+    WindowTableFunctionKind,
 };
 pub use select::BoundSelect;
-pub use set_expr::BoundSetExpr;
+pub use set_expr::{BoundSetExpr, BoundSetOperation};
 pub use statement::BoundStatement;
 pub use update::BoundUpdate;
 pub use values::BoundValues;
 
 use crate::catalog::catalog_service::CatalogReadGuard;
+use crate::config::{TimeZone, TIMEZONE};
+use crate::expr::ExprImpl;
+use crate::session::SessionImpl;
+
+/// Types and text-format values of the `$n` parameters bound to a prepared statement.
+#[derive(Debug, Clone, Default)]
+pub struct ParameterValues {
+    pub types: Vec<DataType>,
+    pub values: Vec<Option<String>>,
+}
 
 /// `Binder` binds the identifiers in AST to columns in relations
 pub struct Binder {
     // TODO: maybe we can only lock the database, but not the whole catalog.
     catalog: CatalogReadGuard,
     db_name: String,
+    /// The session user, whose privileges are checked on the bound relations. It is `None` if the
+    /// user has been dropped, in which case no privilege is granted.
+    session_user: Option<UserInfo>,
     context: BindContext,
     /// A stack holding contexts of outer queries when binding a subquery.
     ///
@@ -60,20 +77,110 @@ pub struct Binder {
     next_subquery_id: usize,
     /// Map the cte's name to its Relation::Subquery.
     cte_to_relation: HashMap<String, (BoundQuery, TableAlias)>,
+
+    /// Types of the `$n` parameters, where `param_types[n - 1]` is the type of `$n`. A `None`
+    /// type is inferred from where the parameter is first used.
+    param_types: Vec<Option<DataType>>,
+    /// Values of the `$n` parameters in text format. It is `None` when binding a prepared
+    /// statement without values, in which case parameters are bound as typed NULLs.
+    param_values: Option<Vec<Option<String>>>,
+    /// The `timezone` of the session, used to convert between `timestamp` and `timestamptz`.
+    time_zone: String,
 }
 
 impl Binder {
-    pub fn new(catalog: CatalogReadGuard, db_name: String) -> Binder {
+    pub fn new(session: &SessionImpl) -> Binder {
+        Self::new_inner(session, vec![], Some(vec![]))
+    }
+
+    /// Create a binder for a prepared statement, whose `$n` parameters have the given types. The
+    /// inferred types can be exported by [`Binder::export_param_types`] after binding.
+    pub fn new_with_param_types(
+        session: &SessionImpl,
+        param_types: Vec<Option<DataType>>,
+    ) -> Binder {
+        Self::new_inner(session, param_types, None)
+    }
+
+    /// Create a binder for a portal, where the `$n` parameters are substituted with the given
+    /// values.
+    pub fn new_with_params(session: &SessionImpl, params: ParameterValues) -> Binder {
+        Self::new_inner(
+            session,
+            params.types.into_iter().map(Some).collect(),
+            Some(params.values),
+        )
+    }
+
+    fn new_inner(
+        session: &SessionImpl,
+        param_types: Vec<Option<DataType>>,
+        param_values: Option<Vec<Option<String>>>,
+    ) -> Binder {
+        let session_user = session
+            .env()
+            .user_info_reader()
+            .read_guard()
+            .get_user_by_name(session.user_name())
+            .cloned();
+        let time_zone = session
+            .get_config(TIMEZONE)
+            .map(|entry| entry.get_val(TimeZone::default()))
+            .unwrap_or_default()
+            .0;
+        Self::new_with_catalog(
+            session.env().catalog_reader().read_guard(),
+            session.database().to_string(),
+            session_user,
+            param_types,
+            param_values,
+            time_zone,
+        )
+    }
+
+    fn new_with_catalog(
+        catalog: CatalogReadGuard,
+        db_name: String,
+        session_user: Option<UserInfo>,
+        param_types: Vec<Option<DataType>>,
+        param_values: Option<Vec<Option<String>>>,
+        time_zone: String,
+    ) -> Binder {
         Binder {
             catalog,
             db_name,
+            session_user,
             context: BindContext::new(),
             upper_contexts: vec![],
             next_subquery_id: 0,
             cte_to_relation: HashMap::new(),
+            param_types,
+            param_values,
+            time_zone,
         }
     }
 
+    /// Types of the `$n` parameters after binding. A parameter whose type can not be inferred
+    /// is of `VARCHAR`, same as PG's `unknown` literal.
+    pub fn export_param_types(&self) -> Vec<DataType> {
+        self.param_types
+            .iter()
+            .map(|t| t.clone().unwrap_or(DataType::Varchar))
+            .collect()
+    }
+
+    /// Bind a standalone [`Expr`] on the columns of a relation that is being created, e.g. the
+    /// watermark of a source. Each column is given as `(is_hidden, field)`.
+    pub fn bind_expr_on_columns(
+        &mut self,
+        expr: Expr,
+        table_name: String,
+        columns: impl IntoIterator<Item = (bool, Field)>,
+    ) -> Result<ExprImpl> {
+        self.bind_context(columns, table_name, None)?;
+        self.bind_expr(expr)
+    }
+
     /// Bind a [`Statement`].
     pub fn bind(&mut self, stmt: Statement) -> Result<BoundStatement> {
         self.bind_statement(stmt)
@@ -101,16 +208,29 @@ pub mod test_utils {
     use std::sync::Arc;
 
     use parking_lot::RwLock;
+    use risingwave_pb::user::UserInfo;
 
     use super::Binder;
     use crate::catalog::catalog_service::CatalogReader;
     use crate::catalog::root_catalog::Catalog;
+    use crate::config::TimeZone;
 
     #[cfg(test)]
     pub fn mock_binder_with_catalog(catalog: Catalog, db_name: String) -> Binder {
         let catalog = Arc::new(RwLock::new(catalog));
         let catalog_reader = CatalogReader::new(catalog);
-        Binder::new(catalog_reader.read_guard(), db_name)
+        let super_user = UserInfo {
+            is_supper: true,
+            ..Default::default()
+        };
+        Binder::new_with_catalog(
+            catalog_reader.read_guard(),
+            db_name,
+            Some(super_user),
+            vec![],
+            Some(vec![]),
+            TimeZone::default().0,
+        )
     }
     #[cfg(test)]
     pub fn mock_binder() -> Binder {
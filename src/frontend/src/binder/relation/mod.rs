@@ -25,11 +25,15 @@ use crate::binder::Binder;
 mod generate_series;
 mod join;
 mod subquery;
+mod system_table;
+mod table_function;
 mod table_or_source;
 mod window_table_function;
 pub use generate_series::BoundGenerateSeriesFunction;
 pub use join::BoundJoin;
 pub use subquery::BoundSubquery;
+pub use system_table::BoundSystemTable;
+pub use table_function::{BoundTableFunction, TableFunctionKind};
 pub use table_or_source::{BoundBaseTable, BoundSource, BoundTableSource};
 pub use window_table_function::{BoundWindowTableFunction, WindowTableFunctionKind};
 
@@ -43,6 +47,8 @@ pub enum Relation {
     Join(Box<BoundJoin>),
     WindowTableFunction(Box<BoundWindowTableFunction>),
     GenerateSeriesFunction(Box<BoundGenerateSeriesFunction>),
+    TableFunction(Box<BoundTableFunction>),
+    SystemTable(Box<BoundSystemTable>),
 }
 
 impl Binder {
@@ -93,6 +99,20 @@ impl Binder {
         Ok(database_name)
     }
 
+    /// return the `user_name`
+    pub fn resolve_user_name(name: ObjectName) -> Result<String> {
+        let mut identifiers = name.0;
+        if identifiers.len() > 1 {
+            return Err(internal_error("user name must contain 1 argument"));
+        }
+        let user_name = identifiers
+            .pop()
+            .ok_or_else(|| internal_error("empty user name"))?
+            .value;
+
+        Ok(user_name)
+    }
+
     /// Fill the [`BindContext`](super::BindContext) for table.
     pub(super) fn bind_context(
         &mut self,
@@ -181,6 +201,11 @@ impl Binder {
                             self.bind_generate_series_function(args)?,
                         )));
                     }
+                    if let Ok(kind) = TableFunctionKind::from_str(func_name) {
+                        return Ok(Relation::TableFunction(Box::new(
+                            self.bind_table_function(kind, args, alias)?,
+                        )));
+                    }
                     let kind = WindowTableFunctionKind::from_str(func_name).map_err(|_| {
                         ErrorCode::NotImplemented(
                             format!("unknown window function kind: {}", name.0[0].value),
@@ -0,0 +1,60 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::Row;
+use risingwave_common::catalog::Field;
+use risingwave_common::error::{Result, RwError};
+use risingwave_sqlparser::ast::TableAlias;
+
+use crate::binder::{Binder, Relation};
+use crate::catalog::system_catalog::get_sys_catalog_by_name;
+use crate::catalog::CatalogError;
+
+/// A system table of `pg_catalog` or `information_schema`, with its rows generated from the
+/// catalog at bind time.
+#[derive(Debug, Clone)]
+pub struct BoundSystemTable {
+    pub name: String, // explain-only
+    pub fields: Vec<Field>,
+    pub rows: Vec<Row>,
+}
+
+impl Binder {
+    pub(super) fn bind_system_table(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        alias: Option<TableAlias>,
+    ) -> Result<Relation> {
+        let sys_catalog = get_sys_catalog_by_name(schema_name, table_name).ok_or_else(|| {
+            RwError::from(CatalogError::NotFound(
+                "system table",
+                format!("{}.{}", schema_name, table_name),
+            ))
+        })?;
+        let fields = sys_catalog.fields();
+        let rows = sys_catalog.rows(&self.catalog, &self.db_name)?;
+
+        self.bind_context(
+            fields.iter().map(|f| (false, f.clone())),
+            table_name.to_string(),
+            alias,
+        )?;
+        Ok(Relation::SystemTable(Box::new(BoundSystemTable {
+            name: format!("{}.{}", schema_name, table_name),
+            fields,
+            rows,
+        })))
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use itertools::Itertools;
+use risingwave_common::catalog::Field;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::table_function::Type as ProstTableFunctionType;
+use risingwave_sqlparser::ast::{FunctionArg, TableAlias};
+
+use super::Binder;
+use crate::expr::ExprImpl;
+
+/// The table functions which return a set of rows, apart from `generate_series` and the window
+/// table functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableFunctionKind {
+    JsonbArrayElements,
+    JsonbEach,
+    RegexpMatches,
+}
+
+impl FromStr for TableFunctionKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("jsonb_array_elements") {
+            Ok(TableFunctionKind::JsonbArrayElements)
+        } else if s.eq_ignore_ascii_case("jsonb_each") {
+            Ok(TableFunctionKind::JsonbEach)
+        } else if s.eq_ignore_ascii_case("regexp_matches") {
+            Ok(TableFunctionKind::RegexpMatches)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl TableFunctionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TableFunctionKind::JsonbArrayElements => "jsonb_array_elements",
+            TableFunctionKind::JsonbEach => "jsonb_each",
+            TableFunctionKind::RegexpMatches => "regexp_matches",
+        }
+    }
+
+    pub fn to_protobuf(self) -> ProstTableFunctionType {
+        match self {
+            TableFunctionKind::JsonbArrayElements => ProstTableFunctionType::JsonbArrayElements,
+            TableFunctionKind::JsonbEach => ProstTableFunctionType::JsonbEach,
+            TableFunctionKind::RegexpMatches => ProstTableFunctionType::RegexpMatches,
+        }
+    }
+
+    /// The types of the arguments, to which the bound arguments are assign-casted.
+    fn arg_types(&self) -> Vec<DataType> {
+        match self {
+            TableFunctionKind::JsonbArrayElements | TableFunctionKind::JsonbEach => {
+                vec![DataType::Jsonb]
+            }
+            TableFunctionKind::RegexpMatches => vec![DataType::Varchar; 3],
+        }
+    }
+
+    /// The number of trailing arguments in `arg_types` that can be omitted.
+    fn num_optional_args(&self) -> usize {
+        match self {
+            TableFunctionKind::JsonbArrayElements | TableFunctionKind::JsonbEach => 0,
+            // The flags.
+            TableFunctionKind::RegexpMatches => 1,
+        }
+    }
+
+    /// The output columns of the function.
+    pub fn output_fields(&self) -> Vec<Field> {
+        match self {
+            TableFunctionKind::JsonbArrayElements => {
+                vec![Field::with_name(DataType::Jsonb, "value")]
+            }
+            TableFunctionKind::JsonbEach => vec![
+                Field::with_name(DataType::Varchar, "key"),
+                Field::with_name(DataType::Jsonb, "value"),
+            ],
+            TableFunctionKind::RegexpMatches => vec![Field::with_name(
+                DataType::List {
+                    datatype: Box::new(DataType::Varchar),
+                },
+                "regexp_matches",
+            )],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BoundTableFunction {
+    pub(crate) kind: TableFunctionKind,
+    pub(crate) args: Vec<ExprImpl>,
+    /// The number of columns of the preceding `FROM` items, if the arguments reference them. The
+    /// function is then evaluated on each row of the `FROM` items joined before it, whose columns
+    /// must be exactly these ones.
+    pub(crate) lateral_input_col_num: Option<usize>,
+}
+
+impl Binder {
+    pub(super) fn bind_table_function(
+        &mut self,
+        kind: TableFunctionKind,
+        args: Vec<FunctionArg>,
+        alias: Option<TableAlias>,
+    ) -> Result<BoundTableFunction> {
+        let args: Vec<ExprImpl> = args
+            .into_iter()
+            .map(|arg| self.bind_function_arg(arg))
+            .flatten_ok()
+            .try_collect()?;
+        let arg_types = kind.arg_types();
+        let min_args = arg_types.len() - kind.num_optional_args();
+        if args.len() < min_args || args.len() > arg_types.len() {
+            let expected = if min_args == arg_types.len() {
+                min_args.to_string()
+            } else {
+                format!("{} to {}", min_args, arg_types.len())
+            };
+            return Err(ErrorCode::BindError(format!(
+                "function {} expects {} arguments, but got {}",
+                kind.name(),
+                expected,
+                args.len()
+            ))
+            .into());
+        }
+        let args: Vec<ExprImpl> = args
+            .into_iter()
+            .zip(arg_types)
+            .map(|(arg, ty)| arg.cast_assign(ty))
+            .try_collect()?;
+        if args
+            .iter()
+            .any(|arg| arg.has_subquery() || arg.has_correlated_input_ref())
+        {
+            return Err(ErrorCode::NotImplemented(
+                format!(
+                    "subqueries or outer columns in the arguments of function {}",
+                    kind.name()
+                ),
+                None.into(),
+            )
+            .into());
+        }
+        // The arguments can only reference the columns bound so far, i.e. those of the preceding
+        // `FROM` items.
+        let lateral_input_col_num = if args.iter().all(|arg| arg.is_const()) {
+            None
+        } else {
+            Some(self.context.columns.len())
+        };
+
+        let columns = kind.output_fields().into_iter().map(|f| (false, f));
+        self.bind_context(columns, kind.name().to_string(), alias)?;
+
+        Ok(BoundTableFunction {
+            kind,
+            args,
+            lateral_input_col_num,
+        })
+    }
+}
@@ -16,12 +16,15 @@ use std::sync::Arc;
 
 use risingwave_common::catalog::ColumnDesc;
 use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_pb::user::grant_privilege::Privilege;
 use risingwave_sqlparser::ast::{ObjectName, TableAlias};
 
 use crate::binder::{Binder, Relation};
 use crate::catalog::source_catalog::SourceCatalog;
+use crate::catalog::system_catalog::is_system_schema;
 use crate::catalog::table_catalog::TableCatalog;
 use crate::catalog::{CatalogError, TableId};
+use crate::user::user_privilege::{has_privilege, relation_object};
 
 #[derive(Debug, Clone)]
 pub struct BoundBaseTable {
@@ -32,7 +35,7 @@ pub struct BoundBaseTable {
 }
 
 /// `BoundTableSource` is used by DML statement on table source like insert, update.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BoundTableSource {
     pub name: String,       // explain-only
     pub source_id: TableId, // TODO: refactor to source id
@@ -57,22 +60,12 @@ impl Binder {
         table_name: &str,
         alias: Option<TableAlias>,
     ) -> Result<Relation> {
-        if schema_name == "pg_catalog" {
-            // TODO: support pg_catalog.
-            return Err(ErrorCode::NotImplemented(
-                // TODO: We can ref the document of `SHOW` commands here if ready.
-                r###"pg_catalog is not supported, please use `SHOW` commands for now.
-`SHOW TABLES`,
-`SHOW MATERIALIZED VIEWS`,
-`DESCRIBE <table>`,
-`SHOW COLUMNS FROM [table]`
-"###
-                .into(),
-                1695.into(),
-            )
-            .into());
+        if is_system_schema(schema_name) {
+            return self.bind_system_table(schema_name, table_name, alias);
         }
 
+        self.check_relation_privilege(schema_name, table_name, Privilege::Select)?;
+
         let (ret, columns) = {
             let catalog = &self.catalog;
             if let Ok(table_catalog) =
@@ -158,8 +151,15 @@ impl Binder {
         })
     }
 
-    pub(crate) fn bind_table_source(&mut self, name: ObjectName) -> Result<BoundTableSource> {
+    /// Binds the table source to write into, checking that the session user has the `action`
+    /// privilege on the table.
+    pub(crate) fn bind_table_source(
+        &mut self,
+        name: ObjectName,
+        action: Privilege,
+    ) -> Result<BoundTableSource> {
         let (schema_name, source_name) = Self::resolve_table_name(name)?;
+        self.check_relation_privilege(&schema_name, &source_name, action)?;
         let source = self
             .catalog
             .get_source_by_name(&self.db_name, &schema_name, &source_name)?;
@@ -181,4 +181,35 @@ impl Binder {
             columns,
         })
     }
+
+    /// Checks whether the session user has the `action` privilege on the relation. A relation that
+    /// does not exist is left to be reported by the caller.
+    fn check_relation_privilege(
+        &self,
+        schema_name: &str,
+        name: &str,
+        action: Privilege,
+    ) -> Result<()> {
+        let (Ok(database), Ok(schema)) = (
+            self.catalog.get_database_by_name(&self.db_name),
+            self.catalog.get_schema_by_name(&self.db_name, schema_name),
+        ) else {
+            return Ok(());
+        };
+        let Some((object, owner)) = relation_object(database.id(), schema, name) else {
+            return Ok(());
+        };
+        let permitted = self
+            .session_user
+            .as_ref()
+            .map_or(false, |user| has_privilege(user, owner, &object, action));
+        if !permitted {
+            return Err(ErrorCode::PermissionDenied(format!(
+                "permission denied for relation {}",
+                name
+            ))
+            .into());
+        }
+        Ok(())
+    }
 }
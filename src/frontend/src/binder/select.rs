@@ -49,6 +49,7 @@ impl BoundSelect {
             .iter()
             .chain(self.group_by.iter())
             .chain(self.where_clause.iter())
+            .chain(self.having.iter())
             .any(|expr| expr.has_correlated_input_ref())
     }
 }
@@ -69,14 +70,18 @@ impl Binder {
         Self::require_bool_clause(&selection, "WHERE")?;
 
         // Bind GROUP BY clause.
+        self.context.clause = Some(Clause::GroupBy);
         let group_by = select
             .group_by
             .into_iter()
             .map(|expr| self.bind_expr(expr))
             .try_collect()?;
+        self.context.clause = None;
 
         // Bind HAVING clause.
+        self.context.clause = Some(Clause::Having);
         let having = select.having.map(|expr| self.bind_expr(expr)).transpose()?;
+        self.context.clause = None;
         Self::require_bool_clause(&having, "HAVING")?;
 
         // Bind SELECT clause.
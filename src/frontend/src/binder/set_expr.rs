@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_common::catalog::Schema;
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::SetExpr;
+use risingwave_sqlparser::ast::{SetExpr, SetOperator};
 
-use crate::binder::{Binder, BoundSelect, BoundValues};
+use crate::binder::{Binder, BoundQuery, BoundSelect, BoundValues};
+use crate::expr::least_restrictive;
 
 /// Part of a validated query, without order or limit clause. It may be composed of smaller
 /// `BoundSetExpr`s via set operators (e.g. union).
@@ -24,6 +26,9 @@ use crate::binder::{Binder, BoundSelect, BoundValues};
 pub enum BoundSetExpr {
     Select(Box<BoundSelect>),
     Values(Box<BoundValues>),
+    /// A parenthesized query, which may have its own order and limit clause.
+    Query(Box<BoundQuery>),
+    SetOperation(Box<BoundSetOperation>),
 }
 
 impl BoundSetExpr {
@@ -33,6 +38,8 @@ impl BoundSetExpr {
         match self {
             BoundSetExpr::Select(s) => s.schema(),
             BoundSetExpr::Values(v) => v.schema(),
+            BoundSetExpr::Query(q) => q.schema(),
+            BoundSetExpr::SetOperation(s) => s.schema(),
         }
     }
 
@@ -40,16 +47,96 @@ impl BoundSetExpr {
         match self {
             BoundSetExpr::Select(s) => s.is_correlated(),
             BoundSetExpr::Values(_) => false,
+            BoundSetExpr::Query(q) => q.is_correlated(),
+            BoundSetExpr::SetOperation(s) => s.left.is_correlated() || s.right.is_correlated(),
         }
     }
 }
 
+/// `left UNION/INTERSECT/EXCEPT [ALL] right`. The output columns are named after the left side,
+/// and typed with the least restrictive type of both sides.
+#[derive(Debug, Clone)]
+pub struct BoundSetOperation {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left: BoundSetExpr,
+    pub right: BoundSetExpr,
+    schema: Schema,
+}
+
+impl BoundSetOperation {
+    /// The schema returned by this [`BoundSetOperation`].
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
 impl Binder {
     pub(super) fn bind_set_expr(&mut self, set_expr: SetExpr) -> Result<BoundSetExpr> {
         match set_expr {
             SetExpr::Select(s) => Ok(BoundSetExpr::Select(Box::new(self.bind_select(*s)?))),
             SetExpr::Values(v) => Ok(BoundSetExpr::Values(Box::new(self.bind_values(v, None)?))),
+            SetExpr::Query(q) => Ok(BoundSetExpr::Query(Box::new(self.bind_query_inner(*q)?))),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => Ok(BoundSetExpr::SetOperation(Box::new(
+                self.bind_set_operation(op, all, *left, *right)?,
+            ))),
             _ => Err(ErrorCode::NotImplemented(format!("{:?}", set_expr), None.into()).into()),
         }
     }
+
+    fn bind_set_operation(
+        &mut self,
+        op: SetOperator,
+        all: bool,
+        left: SetExpr,
+        right: SetExpr,
+    ) -> Result<BoundSetOperation> {
+        if all && op != SetOperator::Union {
+            return Err(ErrorCode::NotImplemented(format!("{} ALL", op), None.into()).into());
+        }
+        let left = self.bind_set_operation_input(left)?;
+        let right = self.bind_set_operation_input(right)?;
+
+        let (left_fields, right_fields) = (&left.schema().fields, &right.schema().fields);
+        if left_fields.len() != right_fields.len() {
+            return Err(ErrorCode::BindError(format!(
+                "each {} query must have the same number of columns",
+                op
+            ))
+            .into());
+        }
+        let fields = left_fields
+            .iter()
+            .zip_eq(right_fields)
+            .map(|(l, r)| {
+                Ok(Field {
+                    data_type: least_restrictive(l.data_type(), r.data_type())?,
+                    ..l.clone()
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(BoundSetOperation {
+            op,
+            all,
+            left,
+            right,
+            schema: Schema::new(fields),
+        })
+    }
+
+    /// Binds an input of a set operation. Each input has its own scope of relations, so it's
+    /// bound with an empty [`BindContext`](super::BindContext) at the same depth, and the current
+    /// context is restored afterwards.
+    fn bind_set_operation_input(&mut self, set_expr: SetExpr) -> Result<BoundSetExpr> {
+        let context = std::mem::take(&mut self.context);
+        let result = self.bind_set_expr(set_expr);
+        self.context = context;
+        result
+    }
 }
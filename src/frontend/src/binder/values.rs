@@ -50,7 +50,20 @@ impl Binder {
         let vec2d = values.0;
         let mut bound = vec2d
             .into_iter()
-            .map(|vec| vec.into_iter().map(|expr| self.bind_expr(expr)).collect())
+            .map(|vec| {
+                vec.into_iter()
+                    .enumerate()
+                    .map(|(i, expr)| {
+                        // A parameter takes the type of the column it is inserted into.
+                        if let Expr::Parameter { index } = expr
+                            && let Some(t) = expected_types.as_ref().and_then(|types| types.get(i))
+                        {
+                            self.infer_param_type(index, t.clone());
+                        }
+                        self.bind_expr(expr)
+                    })
+                    .collect()
+            })
             .collect::<Result<Vec<Vec<_>>>>()?;
         self.context.clause = None;
 
@@ -130,7 +143,7 @@ mod tests {
         let values = Values(vec![vec![expr1], vec![expr2]]);
         let res = binder.bind_values(values, None).unwrap();
 
-        let types = vec![DataType::Decimal];
+        let types = vec![DataType::Decimal(None)];
         let schema = Schema::new(types.into_iter().map(Field::unnamed).collect());
 
         assert_eq!(res.schema, schema);
@@ -20,14 +20,17 @@ use risingwave_common::catalog::{CatalogVersion, TableId};
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
+    Database as ProstDatabase, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
+    Table as ProstTable,
 };
+use risingwave_pb::ddl_service::alter_source_request::Change;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_rpc_client::MetaClient;
 use tokio::sync::watch::Receiver;
 
 use super::root_catalog::Catalog;
 use super::DatabaseId;
+use crate::user::UserId;
 
 pub type CatalogReadGuard = ArcRwLockReadGuard<RawRwLock, Catalog>;
 
@@ -49,9 +52,14 @@ impl CatalogReader {
 /// the version.
 #[async_trait::async_trait]
 pub trait CatalogWriter: Send + Sync {
-    async fn create_database(&self, db_name: &str) -> Result<()>;
+    async fn create_database(&self, db_name: &str, owner: UserId) -> Result<()>;
 
-    async fn create_schema(&self, db_id: DatabaseId, schema_name: &str) -> Result<()>;
+    async fn create_schema(
+        &self,
+        db_id: DatabaseId,
+        schema_name: &str,
+        owner: UserId,
+    ) -> Result<()>;
 
     async fn create_materialized_view(
         &self,
@@ -68,12 +76,20 @@ pub trait CatalogWriter: Send + Sync {
 
     async fn create_source(&self, source: ProstSource) -> Result<()>;
 
+    async fn create_sink(&self, sink: ProstSink, graph: StreamFragmentGraph) -> Result<()>;
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()>;
 
     async fn drop_materialized_view(&self, table_id: TableId) -> Result<()>;
 
     async fn drop_source(&self, source_id: u32) -> Result<()>;
 
+    /// Adds or drops a column of a source and its associated table. `version` is the version of
+    /// the source that the change is made on.
+    async fn alter_source(&self, source_id: u32, version: u64, change: Change) -> Result<()>;
+
+    async fn drop_sink(&self, sink_id: u32) -> Result<()>;
+
     async fn drop_database(&self, database_id: u32) -> Result<()>;
 
     async fn drop_schema(&self, schema_id: u32) -> Result<()>;
@@ -87,24 +103,31 @@ pub struct CatalogWriterImpl {
 
 #[async_trait::async_trait]
 impl CatalogWriter for CatalogWriterImpl {
-    async fn create_database(&self, db_name: &str) -> Result<()> {
+    async fn create_database(&self, db_name: &str, owner: UserId) -> Result<()> {
         let (_, version) = self
             .meta_client
             .create_database(ProstDatabase {
                 name: db_name.to_string(),
                 id: 0,
+                owner,
             })
             .await?;
         self.wait_version(version).await
     }
 
-    async fn create_schema(&self, db_id: DatabaseId, schema_name: &str) -> Result<()> {
+    async fn create_schema(
+        &self,
+        db_id: DatabaseId,
+        schema_name: &str,
+        owner: UserId,
+    ) -> Result<()> {
         let (_, version) = self
             .meta_client
             .create_schema(ProstSchema {
                 id: 0,
                 name: schema_name.to_string(),
                 database_id: db_id,
+                owner,
             })
             .await?;
         self.wait_version(version).await
@@ -141,6 +164,11 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn create_sink(&self, sink: ProstSink, graph: StreamFragmentGraph) -> Result<()> {
+        let (_id, version) = self.meta_client.create_sink(sink, graph).await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()> {
         let version = self
             .meta_client
@@ -159,6 +187,19 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn alter_source(&self, source_id: u32, version: u64, change: Change) -> Result<()> {
+        let version = self
+            .meta_client
+            .alter_source(source_id, version, change)
+            .await?;
+        self.wait_version(version).await
+    }
+
+    async fn drop_sink(&self, sink_id: u32) -> Result<()> {
+        let version = self.meta_client.drop_sink(sink_id).await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_schema(&self, schema_id: u32) -> Result<()> {
         let version = self.meta_client.drop_schema(schema_id).await?;
         self.wait_version(version).await
@@ -15,24 +15,30 @@
 use std::collections::HashMap;
 
 use risingwave_common::catalog::TableId;
-use risingwave_pb::catalog::{Schema as ProstSchema, Source as ProstSource, Table as ProstTable};
+use risingwave_pb::catalog::{
+    Schema as ProstSchema, Sink as ProstSink, Source as ProstSource, Table as ProstTable,
+};
 use risingwave_pb::stream_plan::source_node::SourceType;
 
+use super::sink_catalog::SinkCatalog;
 use super::source_catalog::SourceCatalog;
 use crate::catalog::table_catalog::TableCatalog;
-use crate::catalog::SchemaId;
+use crate::catalog::{SchemaId, SinkId};
+use crate::user::UserId;
 
 pub type SourceId = u32;
 
 #[derive(Clone, Debug)]
 pub struct SchemaCatalog {
     id: SchemaId,
-    #[allow(dead_code)]
     name: String,
     table_by_name: HashMap<String, TableCatalog>,
     table_name_by_id: HashMap<TableId, String>,
     source_by_name: HashMap<String, SourceCatalog>,
     source_name_by_id: HashMap<SourceId, String>,
+    sink_by_name: HashMap<String, SinkCatalog>,
+    sink_name_by_id: HashMap<SinkId, String>,
+    owner: UserId,
 }
 
 impl SchemaCatalog {
@@ -50,6 +56,11 @@ impl SchemaCatalog {
         self.table_by_name.remove(&name).unwrap();
     }
 
+    pub fn update_table(&mut self, prost: &ProstTable) {
+        self.drop_table(prost.id.into());
+        self.create_table(prost);
+    }
+
     pub fn create_source(&mut self, prost: ProstSource) {
         let name = prost.name.clone();
         let id = prost.id;
@@ -65,6 +76,26 @@ impl SchemaCatalog {
         self.source_by_name.remove(&name).unwrap();
     }
 
+    pub fn update_source(&mut self, prost: ProstSource) {
+        self.drop_source(prost.id);
+        self.create_source(prost);
+    }
+
+    pub fn create_sink(&mut self, prost: &ProstSink) {
+        let name = prost.name.clone();
+        let id = prost.id;
+
+        self.sink_by_name
+            .try_insert(name.clone(), SinkCatalog::from(prost))
+            .unwrap();
+        self.sink_name_by_id.try_insert(id, name).unwrap();
+    }
+
+    pub fn drop_sink(&mut self, id: SinkId) {
+        let name = self.sink_name_by_id.remove(&id).unwrap();
+        self.sink_by_name.remove(&name).unwrap();
+    }
+
     pub fn iter_table(&self) -> impl Iterator<Item = &TableCatalog> {
         self.table_by_name
             .iter()
@@ -120,9 +151,21 @@ impl SchemaCatalog {
         self.source_by_name.get(source_name)
     }
 
+    pub fn get_sink_by_name(&self, sink_name: &str) -> Option<&SinkCatalog> {
+        self.sink_by_name.get(sink_name)
+    }
+
     pub fn id(&self) -> SchemaId {
         self.id
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn owner(&self) -> UserId {
+        self.owner
+    }
 }
 
 impl From<&ProstSchema> for SchemaCatalog {
@@ -134,6 +177,9 @@ impl From<&ProstSchema> for SchemaCatalog {
             table_name_by_id: HashMap::new(),
             source_by_name: HashMap::new(),
             source_name_by_id: HashMap::new(),
+            sink_by_name: HashMap::new(),
+            sink_name_by_id: HashMap::new(),
+            owner: schema.owner,
         }
     }
 }
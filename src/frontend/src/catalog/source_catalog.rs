@@ -13,11 +13,12 @@
 // limitations under the License.
 use itertools::Itertools;
 use risingwave_pb::catalog::source::Info;
-use risingwave_pb::catalog::Source as ProstSource;
+use risingwave_pb::catalog::{Source as ProstSource, WatermarkDesc};
 use risingwave_pb::stream_plan::source_node::SourceType;
 
 use super::column_catalog::ColumnCatalog;
 use super::{ColumnId, SourceId, TABLE_SOURCE_PK_COLID};
+use crate::user::UserId;
 /// this struct `SourceCatalog` is used in frontend and compared with `ProstSource` it only maintain
 /// information which will be used during optimization.
 #[derive(Clone, Debug)]
@@ -27,6 +28,10 @@ pub struct SourceCatalog {
     pub columns: Vec<ColumnCatalog>,
     pub pk_col_ids: Vec<ColumnId>,
     pub source_type: SourceType,
+    pub owner: UserId,
+    pub watermark_descs: Vec<WatermarkDesc>,
+    /// Version of the source, which is bumped by every `ALTER` on it.
+    pub version: u64,
 }
 
 impl SourceCatalog {
@@ -56,7 +61,7 @@ impl From<&ProstSource> for SourceCatalog {
     fn from(prost: &ProstSource) -> Self {
         let id = prost.id;
         let name = prost.name.clone();
-        let (source_type, prost_columns, pk_col_ids) = match &prost.info {
+        let (source_type, prost_columns, pk_col_ids, watermark_descs) = match &prost.info {
             Some(Info::StreamSource(source)) => (
                 SourceType::Source,
                 source.columns.clone(),
@@ -65,11 +70,13 @@ impl From<&ProstSource> for SourceCatalog {
                     .iter()
                     .map(|id| ColumnId::new(*id))
                     .collect(),
+                source.watermark_descs.clone(),
             ),
             Some(Info::TableSource(source)) => (
                 SourceType::Table,
                 source.columns.clone(),
                 vec![TABLE_SOURCE_PK_COLID],
+                vec![],
             ),
             None => unreachable!(),
         };
@@ -80,6 +87,9 @@ impl From<&ProstSource> for SourceCatalog {
             columns,
             pk_col_ids,
             source_type,
+            owner: prost.owner,
+            watermark_descs,
+            version: prost.version,
         }
     }
 }
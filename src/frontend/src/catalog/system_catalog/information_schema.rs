@@ -0,0 +1,123 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! System tables of `information_schema`. Only the commonly used columns of them are provided.
+
+use itertools::Itertools;
+use risingwave_common::array::Row;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use super::{list_relations, list_schemas, SystemCatalog, INFORMATION_SCHEMA_SCHEMA_NAME};
+use crate::catalog::root_catalog::Catalog;
+
+/// The name of the type in `information_schema.columns.data_type`, as the SQL standard names it.
+fn sql_type_name(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Boolean => "boolean",
+        DataType::Int16 => "smallint",
+        DataType::Int32 => "integer",
+        DataType::Int64 => "bigint",
+        DataType::Float32 => "real",
+        DataType::Float64 => "double precision",
+        DataType::Decimal(_) => "numeric",
+        DataType::Date => "date",
+        DataType::Varchar => "character varying",
+        DataType::Time => "time without time zone",
+        DataType::Timestamp => "timestamp without time zone",
+        DataType::Timestampz => "timestamp with time zone",
+        DataType::Interval => "interval",
+        DataType::Jsonb => "jsonb",
+        DataType::Bytea => "bytea",
+        DataType::Uuid => "uuid",
+        DataType::Struct { .. } => "record",
+        DataType::List { .. } => "ARRAY",
+    }
+}
+
+pub const TABLES: SystemCatalog = SystemCatalog {
+    schema_name: INFORMATION_SCHEMA_SCHEMA_NAME,
+    name: "tables",
+    columns: &[
+        (DataType::Varchar, "table_catalog"),
+        (DataType::Varchar, "table_schema"),
+        (DataType::Varchar, "table_name"),
+        (DataType::Varchar, "table_type"),
+    ],
+    rows: tables_rows,
+};
+
+fn tables_rows(catalog: &Catalog, db_name: &str) -> Result<Vec<Row>> {
+    Ok(list_schemas(catalog, db_name)?
+        .into_iter()
+        .flat_map(|schema| {
+            list_relations(schema)
+                .into_iter()
+                .filter_map(|relation| {
+                    relation.kind.table_type().map(|table_type| {
+                        Row::new(vec![
+                            Some(ScalarImpl::Utf8(db_name.to_string())),
+                            Some(ScalarImpl::Utf8(schema.name().to_string())),
+                            Some(ScalarImpl::Utf8(relation.name.to_string())),
+                            Some(ScalarImpl::Utf8(table_type.to_string())),
+                        ])
+                    })
+                })
+                .collect_vec()
+        })
+        .collect())
+}
+
+pub const COLUMNS: SystemCatalog = SystemCatalog {
+    schema_name: INFORMATION_SCHEMA_SCHEMA_NAME,
+    name: "columns",
+    columns: &[
+        (DataType::Varchar, "table_catalog"),
+        (DataType::Varchar, "table_schema"),
+        (DataType::Varchar, "table_name"),
+        (DataType::Varchar, "column_name"),
+        (DataType::Int32, "ordinal_position"),
+        (DataType::Varchar, "is_nullable"),
+        (DataType::Varchar, "data_type"),
+    ],
+    rows: columns_rows,
+};
+
+fn columns_rows(catalog: &Catalog, db_name: &str) -> Result<Vec<Row>> {
+    Ok(list_schemas(catalog, db_name)?
+        .into_iter()
+        .flat_map(|schema| {
+            list_relations(schema)
+                .into_iter()
+                .filter(|relation| relation.kind.table_type().is_some())
+                .flat_map(|relation| {
+                    relation
+                        .visible_columns()
+                        .map(|(position, column)| {
+                            Row::new(vec![
+                                Some(ScalarImpl::Utf8(db_name.to_string())),
+                                Some(ScalarImpl::Utf8(schema.name().to_string())),
+                                Some(ScalarImpl::Utf8(relation.name.to_string())),
+                                Some(ScalarImpl::Utf8(column.name().to_string())),
+                                Some(ScalarImpl::Int32(position as i32)),
+                                Some(ScalarImpl::Utf8("YES".to_string())),
+                                Some(ScalarImpl::Utf8(sql_type_name(column.data_type()).into())),
+                            ])
+                        })
+                        .collect_vec()
+                })
+                .collect_vec()
+        })
+        .collect())
+}
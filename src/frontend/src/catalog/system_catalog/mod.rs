@@ -0,0 +1,250 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-only system tables of `pg_catalog` and `information_schema`, which are widely used by
+//! Postgres clients and tools to discover the relations and their columns. Their rows are generated
+//! from the frontend [`Catalog`] when a query is bound.
+
+pub mod information_schema;
+pub mod pg_catalog;
+
+use itertools::Itertools;
+use risingwave_common::array::Row;
+use risingwave_common::catalog::Field;
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+
+use super::column_catalog::ColumnCatalog;
+use super::root_catalog::Catalog;
+use super::schema_catalog::SchemaCatalog;
+use crate::user::UserId;
+
+pub const PG_CATALOG_SCHEMA_NAME: &str = "pg_catalog";
+pub const INFORMATION_SCHEMA_SCHEMA_NAME: &str = "information_schema";
+
+pub type SystemCatalogColumn = (DataType, &'static str);
+
+/// A system table, whose rows are generated by `rows` from the catalog of the given database.
+pub struct SystemCatalog {
+    pub schema_name: &'static str,
+    pub name: &'static str,
+    pub columns: &'static [SystemCatalogColumn],
+    rows: fn(&Catalog, &str) -> Result<Vec<Row>>,
+}
+
+impl SystemCatalog {
+    pub fn fields(&self) -> Vec<Field> {
+        self.columns
+            .iter()
+            .map(|(data_type, name)| Field::with_name(data_type.clone(), *name))
+            .collect()
+    }
+
+    /// Generates the rows of the system table from the catalog of the database `db_name`.
+    pub fn rows(&self, catalog: &Catalog, db_name: &str) -> Result<Vec<Row>> {
+        (self.rows)(catalog, db_name)
+    }
+}
+
+const SYSTEM_CATALOGS: &[&SystemCatalog] = &[
+    &pg_catalog::PG_NAMESPACE,
+    &pg_catalog::PG_CLASS,
+    &pg_catalog::PG_ATTRIBUTE,
+    &pg_catalog::PG_TYPE,
+    &pg_catalog::PG_DATABASE,
+    &information_schema::TABLES,
+    &information_schema::COLUMNS,
+];
+
+pub fn is_system_schema(schema_name: &str) -> bool {
+    schema_name == PG_CATALOG_SCHEMA_NAME || schema_name == INFORMATION_SCHEMA_SCHEMA_NAME
+}
+
+pub fn get_sys_catalog_by_name(
+    schema_name: &str,
+    table_name: &str,
+) -> Option<&'static SystemCatalog> {
+    SYSTEM_CATALOGS
+        .iter()
+        .find(|sys_catalog| {
+            sys_catalog.schema_name == schema_name && sys_catalog.name == table_name
+        })
+        .copied()
+}
+
+/// The kind of a relation listed in the system tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RelationKind {
+    Table,
+    MaterializedView,
+    Index,
+    Source,
+}
+
+impl RelationKind {
+    /// `relkind` of `pg_class`.
+    fn pg_relkind(self) -> &'static str {
+        match self {
+            RelationKind::Table => "r",
+            RelationKind::MaterializedView => "m",
+            RelationKind::Index => "i",
+            RelationKind::Source => "f",
+        }
+    }
+
+    /// `table_type` of `information_schema.tables`, which does not list the indexes.
+    fn table_type(self) -> Option<&'static str> {
+        match self {
+            RelationKind::Table => Some("BASE TABLE"),
+            RelationKind::MaterializedView => Some("VIEW"),
+            RelationKind::Index => None,
+            RelationKind::Source => Some("FOREIGN"),
+        }
+    }
+}
+
+struct SystemRelation<'a> {
+    id: u32,
+    name: &'a str,
+    kind: RelationKind,
+    owner: UserId,
+    columns: &'a [ColumnCatalog],
+}
+
+impl<'a> SystemRelation<'a> {
+    /// The columns visible to the users, with their 1-based ordinal positions.
+    fn visible_columns(&self) -> impl Iterator<Item = (usize, &'a ColumnCatalog)> {
+        self.columns
+            .iter()
+            .filter(|c| !c.is_hidden)
+            .enumerate()
+            .map(|(i, c)| (i + 1, c))
+    }
+}
+
+/// Lists the schemas of the database `db_name`, ordered by name.
+fn list_schemas<'a>(catalog: &'a Catalog, db_name: &str) -> Result<Vec<&'a SchemaCatalog>> {
+    Ok(catalog
+        .get_database_by_name(db_name)?
+        .iter_schemas()
+        .sorted_by_key(|schema| schema.name())
+        .collect())
+}
+
+/// Lists the relations of the schema, ordered by name.
+fn list_relations(schema: &SchemaCatalog) -> Vec<SystemRelation<'_>> {
+    let tables = schema
+        .iter_table()
+        .map(|t| (t, RelationKind::Table))
+        .chain(
+            schema
+                .iter_mv()
+                .map(|t| (t, RelationKind::MaterializedView)),
+        )
+        .chain(schema.iter_index().map(|t| (t, RelationKind::Index)))
+        .map(|(t, kind)| SystemRelation {
+            id: t.id().table_id(),
+            name: t.name(),
+            kind,
+            owner: t.owner,
+            columns: t.columns(),
+        });
+    let sources = schema.iter_source().map(|s| SystemRelation {
+        id: s.id,
+        name: &s.name,
+        kind: RelationKind::Source,
+        owner: s.owner,
+        columns: &s.columns,
+    });
+    tables
+        .chain(sources)
+        .sorted_by_key(|relation| relation.name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_system_catalogs() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1 from t")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog = session.env().catalog_reader().read_guard();
+        let db_name = session.database();
+
+        let rows = get_sys_catalog_by_name(PG_CATALOG_SCHEMA_NAME, "pg_class")
+            .unwrap()
+            .rows(&catalog, db_name)
+            .unwrap();
+        let relations = rows
+            .iter()
+            .map(|row| (row.0[1].clone().unwrap(), row.0[3].clone().unwrap()))
+            .collect_vec();
+        assert_eq!(
+            relations,
+            vec![
+                (ScalarImpl::Utf8("mv".into()), ScalarImpl::Utf8("m".into())),
+                (ScalarImpl::Utf8("t".into()), ScalarImpl::Utf8("r".into())),
+            ]
+        );
+
+        let rows = get_sys_catalog_by_name(INFORMATION_SCHEMA_SCHEMA_NAME, "columns")
+            .unwrap()
+            .rows(&catalog, db_name)
+            .unwrap();
+        let columns = rows
+            .iter()
+            .map(|row| {
+                (
+                    row.0[2].clone().unwrap(),
+                    row.0[3].clone().unwrap(),
+                    row.0[4].clone().unwrap(),
+                )
+            })
+            .collect_vec();
+        assert_eq!(
+            columns,
+            vec![
+                (
+                    ScalarImpl::Utf8("mv".into()),
+                    ScalarImpl::Utf8("v1".into()),
+                    ScalarImpl::Int32(1)
+                ),
+                (
+                    ScalarImpl::Utf8("t".into()),
+                    ScalarImpl::Utf8("v1".into()),
+                    ScalarImpl::Int32(1)
+                ),
+                (
+                    ScalarImpl::Utf8("t".into()),
+                    ScalarImpl::Utf8("v2".into()),
+                    ScalarImpl::Int32(2)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! System tables of `pg_catalog`. Only the commonly used columns of them are provided.
+
+use itertools::Itertools;
+use risingwave_common::array::Row;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use super::{list_relations, list_schemas, SystemCatalog, PG_CATALOG_SCHEMA_NAME};
+use crate::catalog::root_catalog::Catalog;
+use crate::handler::util::data_type_to_type_oid;
+
+/// The types listed in `pg_type`, with their names and lengths.
+const PG_TYPES: &[(DataType, &str, i16)] = &[
+    (DataType::Boolean, "bool", 1),
+    (DataType::Int16, "int2", 2),
+    (DataType::Int32, "int4", 4),
+    (DataType::Int64, "int8", 8),
+    (DataType::Float32, "float4", 4),
+    (DataType::Float64, "float8", 8),
+    (DataType::Decimal(None), "numeric", -1),
+    (DataType::Date, "date", 4),
+    (DataType::Varchar, "varchar", -1),
+    (DataType::Time, "time", 8),
+    (DataType::Timestamp, "timestamp", 8),
+    (DataType::Timestampz, "timestamptz", 8),
+    (DataType::Bytea, "bytea", -1),
+    (DataType::Uuid, "uuid", 16),
+];
+
+fn type_oid(data_type: &DataType) -> i32 {
+    data_type_to_type_oid(data_type.clone()).as_number()
+}
+
+/// The length of the type in `pg_type.typlen`, which is `-1` for the types of variable length.
+fn type_len(data_type: &DataType) -> i16 {
+    PG_TYPES
+        .iter()
+        .find(|(t, _, _)| t == data_type)
+        .map_or(-1, |(_, _, len)| *len)
+}
+
+pub const PG_NAMESPACE: SystemCatalog = SystemCatalog {
+    schema_name: PG_CATALOG_SCHEMA_NAME,
+    name: "pg_namespace",
+    columns: &[
+        (DataType::Int32, "oid"),
+        (DataType::Varchar, "nspname"),
+        (DataType::Int32, "nspowner"),
+    ],
+    rows: pg_namespace_rows,
+};
+
+fn pg_namespace_rows(catalog: &Catalog, db_name: &str) -> Result<Vec<Row>> {
+    Ok(list_schemas(catalog, db_name)?
+        .into_iter()
+        .map(|schema| {
+            Row::new(vec![
+                Some(ScalarImpl::Int32(schema.id() as i32)),
+                Some(ScalarImpl::Utf8(schema.name().to_string())),
+                Some(ScalarImpl::Int32(schema.owner() as i32)),
+            ])
+        })
+        .collect())
+}
+
+pub const PG_CLASS: SystemCatalog = SystemCatalog {
+    schema_name: PG_CATALOG_SCHEMA_NAME,
+    name: "pg_class",
+    columns: &[
+        (DataType::Int32, "oid"),
+        (DataType::Varchar, "relname"),
+        (DataType::Int32, "relnamespace"),
+        (DataType::Varchar, "relkind"),
+        (DataType::Int32, "relowner"),
+        (DataType::Int16, "relnatts"),
+    ],
+    rows: pg_class_rows,
+};
+
+fn pg_class_rows(catalog: &Catalog, db_name: &str) -> Result<Vec<Row>> {
+    Ok(list_schemas(catalog, db_name)?
+        .into_iter()
+        .flat_map(|schema| {
+            list_relations(schema).into_iter().map(move |relation| {
+                Row::new(vec![
+                    Some(ScalarImpl::Int32(relation.id as i32)),
+                    Some(ScalarImpl::Utf8(relation.name.to_string())),
+                    Some(ScalarImpl::Int32(schema.id() as i32)),
+                    Some(ScalarImpl::Utf8(relation.kind.pg_relkind().to_string())),
+                    Some(ScalarImpl::Int32(relation.owner as i32)),
+                    Some(ScalarImpl::Int16(relation.visible_columns().count() as i16)),
+                ])
+            })
+        })
+        .collect())
+}
+
+pub const PG_ATTRIBUTE: SystemCatalog = SystemCatalog {
+    schema_name: PG_CATALOG_SCHEMA_NAME,
+    name: "pg_attribute",
+    columns: &[
+        (DataType::Int32, "attrelid"),
+        (DataType::Varchar, "attname"),
+        (DataType::Int32, "atttypid"),
+        (DataType::Int16, "attlen"),
+        (DataType::Int16, "attnum"),
+        (DataType::Boolean, "attnotnull"),
+        (DataType::Boolean, "attisdropped"),
+    ],
+    rows: pg_attribute_rows,
+};
+
+fn pg_attribute_rows(catalog: &Catalog, db_name: &str) -> Result<Vec<Row>> {
+    Ok(list_schemas(catalog, db_name)?
+        .into_iter()
+        .flat_map(list_relations)
+        .flat_map(|relation| {
+            relation
+                .visible_columns()
+                .map(|(position, column)| {
+                    Row::new(vec![
+                        Some(ScalarImpl::Int32(relation.id as i32)),
+                        Some(ScalarImpl::Utf8(column.name().to_string())),
+                        Some(ScalarImpl::Int32(type_oid(column.data_type()))),
+                        Some(ScalarImpl::Int16(type_len(column.data_type()))),
+                        Some(ScalarImpl::Int16(position as i16)),
+                        Some(ScalarImpl::Bool(false)),
+                        Some(ScalarImpl::Bool(false)),
+                    ])
+                })
+                .collect_vec()
+        })
+        .collect())
+}
+
+pub const PG_TYPE: SystemCatalog = SystemCatalog {
+    schema_name: PG_CATALOG_SCHEMA_NAME,
+    name: "pg_type",
+    columns: &[
+        (DataType::Int32, "oid"),
+        (DataType::Varchar, "typname"),
+        (DataType::Int16, "typlen"),
+    ],
+    rows: pg_type_rows,
+};
+
+fn pg_type_rows(_catalog: &Catalog, _db_name: &str) -> Result<Vec<Row>> {
+    Ok(PG_TYPES
+        .iter()
+        .map(|(data_type, name, len)| {
+            Row::new(vec![
+                Some(ScalarImpl::Int32(type_oid(data_type))),
+                Some(ScalarImpl::Utf8(name.to_string())),
+                Some(ScalarImpl::Int16(*len)),
+            ])
+        })
+        .collect())
+}
+
+pub const PG_DATABASE: SystemCatalog = SystemCatalog {
+    schema_name: PG_CATALOG_SCHEMA_NAME,
+    name: "pg_database",
+    columns: &[
+        (DataType::Int32, "oid"),
+        (DataType::Varchar, "datname"),
+        (DataType::Int32, "datdba"),
+    ],
+    rows: pg_database_rows,
+};
+
+fn pg_database_rows(catalog: &Catalog, _db_name: &str) -> Result<Vec<Row>> {
+    Ok(catalog
+        .iter_databases()
+        .sorted_by_key(|db| db.name())
+        .map(|db| {
+            Row::new(vec![
+                Some(ScalarImpl::Int32(db.id() as i32)),
+                Some(ScalarImpl::Utf8(db.name().to_string())),
+                Some(ScalarImpl::Int32(db.owner() as i32)),
+            ])
+        })
+        .collect())
+}
@@ -24,6 +24,7 @@ use risingwave_pb::plan_common::OrderType as ProstOrderType;
 use super::column_catalog::ColumnCatalog;
 use super::{DatabaseId, SchemaId};
 use crate::catalog::TableId;
+use crate::user::UserId;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct TableCatalog {
@@ -47,6 +48,12 @@ pub struct TableCatalog {
 
     /// If set to Some(TableId), then this table is an index on another table.
     pub is_index_on: Option<TableId>,
+
+    /// Owner of the table.
+    pub owner: UserId,
+
+    /// Version of the table, which is bumped by every `ALTER` on its associated source.
+    pub version: u64,
 }
 
 impl TableCatalog {
@@ -123,6 +130,8 @@ impl TableCatalog {
                 .iter()
                 .map(|k| *k as i32)
                 .collect_vec(),
+            owner: self.owner,
+            version: self.version,
         }
     }
 }
@@ -180,6 +189,8 @@ impl From<ProstTable> for TableCatalog {
                 .map(|k| *k as usize)
                 .collect_vec(),
             pks: tb.pk.iter().map(|x| *x as _).collect(),
+            owner: tb.owner,
+            version: tb.version,
         }
     }
 }
@@ -192,7 +203,9 @@ impl From<&ProstTable> for TableCatalog {
 
 #[cfg(test)]
 mod tests {
-    use risingwave_common::catalog::{ColumnDesc, ColumnId, OrderedColumnDesc, TableId};
+    use risingwave_common::catalog::{
+        ColumnDesc, ColumnId, OrderedColumnDesc, TableId, DEFAULT_SUPER_USER_ID,
+    };
     use risingwave_common::test_prelude::*;
     use risingwave_common::types::*;
     use risingwave_common::util::sort_util::OrderType;
@@ -248,6 +261,8 @@ mod tests {
             distribution_keys: vec![],
             optional_associated_source_id: OptionalAssociatedSourceId::AssociatedSourceId(233)
                 .into(),
+            owner: DEFAULT_SUPER_USER_ID,
+            version: 0,
         }
         .into();
 
@@ -274,6 +289,7 @@ mod tests {
                                     name: "country.address".to_string(),
                                     field_descs: vec![],
                                     type_name: String::new(),
+                                    default_value: None,
                                 },
                                 ColumnDesc {
                                     data_type: DataType::Varchar,
@@ -281,9 +297,11 @@ mod tests {
                                     name: "country.zipcode".to_string(),
                                     field_descs: vec![],
                                     type_name: String::new(),
+                                    default_value: None,
                                 }
                             ],
-                            type_name: ".test.Country".to_string()
+                            type_name: ".test.Country".to_string(),
+                            default_value: None
                         },
                         is_hidden: false
                     }
@@ -294,6 +312,8 @@ mod tests {
                     order: OrderType::Ascending
                 }],
                 distribution_keys: vec![],
+                owner: DEFAULT_SUPER_USER_ID,
+                version: 0,
             }
         );
     }
@@ -14,13 +14,24 @@
 
 //! Contains configurations that could be accessed via "set" command.
 
+use std::time::Duration;
+
 use risingwave_common::error::ErrorCode::InvalidConfigValue;
 use risingwave_common::error::RwError;
+use risingwave_expr::vector_op::timezone::parse_time_zone;
 
 use crate::config::QueryMode::{Distributed, Local};
 
 pub static QUERY_MODE: &str = "query_mode";
 
+/// Aborts any statement that runs longer than the specified duration. Zero, the default, disables
+/// the timeout.
+pub static STATEMENT_TIMEOUT: &str = "statement_timeout";
+
+/// The time zone used to convert between `timestamp` and `timestamp with time zone`, e.g. by
+/// `AT TIME ZONE` without an explicit zone. Also settable by `SET TIME ZONE`.
+pub static TIMEZONE: &str = "timezone";
+
 #[derive(Debug, Clone)]
 pub enum QueryMode {
     Local,
@@ -51,11 +62,79 @@ impl<'a> TryFrom<&'a str> for QueryMode {
     }
 }
 
+/// The value of `statement_timeout`. `None` means there is no timeout.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatementTimeout(pub Option<Duration>);
+
+/// Parse statement timeout from string, which is in milliseconds unless a unit of `ms`, `s` or
+/// `min` is given, e.g. `'5s'`.
+impl<'a> TryFrom<&'a str> for StatementTimeout {
+    type Error = RwError;
+
+    fn try_from(s: &'a str) -> Result<Self, RwError> {
+        let invalid = || InvalidConfigValue {
+            config_entry: STATEMENT_TIMEOUT.to_string(),
+            config_value: s.to_string(),
+        };
+        let value = s.trim_matches('\'').trim();
+        let (number, millis_per_unit) = if let Some(number) = value.strip_suffix("ms") {
+            (number, 1)
+        } else if let Some(number) = value.strip_suffix("min") {
+            (number, 60 * 1000)
+        } else if let Some(number) = value.strip_suffix('s') {
+            (number, 1000)
+        } else {
+            (value, 1)
+        };
+        let millis = number
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| invalid())?
+            .checked_mul(millis_per_unit)
+            .ok_or_else(invalid)?;
+        if millis == 0 {
+            Ok(Self(None))
+        } else {
+            Ok(Self(Some(Duration::from_millis(millis))))
+        }
+    }
+}
+
+/// The value of `timezone`: a zone name like `'Asia/Shanghai'` or a fixed offset like `'+08:00'`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeZone(pub String);
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self("UTC".to_string())
+    }
+}
+
+/// Parse time zone from string. `DEFAULT` and `LOCAL` both reset it to UTC.
+impl<'a> TryFrom<&'a str> for TimeZone {
+    type Error = RwError;
+
+    fn try_from(s: &'a str) -> Result<Self, RwError> {
+        let value = s.trim_matches('\'').trim();
+        if value.eq_ignore_ascii_case("default") || value.eq_ignore_ascii_case("local") {
+            return Ok(Self::default());
+        }
+        match parse_time_zone(value) {
+            Ok(_) => Ok(Self(value.to_string())),
+            Err(_) => Err(InvalidConfigValue {
+                config_entry: TIMEZONE.to_string(),
+                config_value: s.to_string(),
+            })?,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
+    use std::time::Duration;
 
-    use crate::config::QueryMode;
+    use crate::config::{QueryMode, StatementTimeout, TimeZone};
 
     #[test]
     fn parse_query_mode() {
@@ -65,4 +144,27 @@ mod tests {
         assert_matches!("diStributed".try_into().unwrap(), QueryMode::Distributed);
         assert!(QueryMode::try_from("ab").is_err());
     }
+
+    #[test]
+    fn parse_statement_timeout() {
+        let timeout = |s: &str| StatementTimeout::try_from(s).unwrap().0;
+        assert_eq!(timeout("0"), None);
+        assert_eq!(timeout("1500"), Some(Duration::from_millis(1500)));
+        assert_eq!(timeout("'100ms'"), Some(Duration::from_millis(100)));
+        assert_eq!(timeout("'5s'"), Some(Duration::from_secs(5)));
+        assert_eq!(timeout("'2min'"), Some(Duration::from_secs(120)));
+        assert!(StatementTimeout::try_from("-1").is_err());
+        assert!(StatementTimeout::try_from("'5h'").is_err());
+    }
+
+    #[test]
+    fn parse_time_zone() {
+        let zone = |s: &str| TimeZone::try_from(s).unwrap().0;
+        assert_eq!(zone("'Asia/Shanghai'"), "Asia/Shanghai");
+        assert_eq!(zone("'+08:00'"), "+08:00");
+        assert_eq!(zone("utc"), "utc");
+        assert_eq!(zone("LOCAL"), "UTC");
+        assert_eq!(zone("DEFAULT"), "UTC");
+        assert!(TimeZone::try_from("'Mars/Olympus'").is_err());
+    }
 }
@@ -73,8 +73,8 @@ impl AggCall {
 
             // Avg
             (AggKind::Avg, [input]) => match input {
-                DataType::Int16 | DataType::Int32 | DataType::Int64 | DataType::Decimal => {
-                    DataType::Decimal
+                DataType::Int16 | DataType::Int32 | DataType::Int64 | DataType::Decimal(_) => {
+                    DataType::Decimal(None)
                 }
                 DataType::Float32 | DataType::Float64 => DataType::Float64,
                 DataType::Interval => DataType::Interval,
@@ -86,8 +86,8 @@ impl AggCall {
             (AggKind::Sum, [input]) => match input {
                 DataType::Int16 => DataType::Int64,
                 DataType::Int32 => DataType::Int64,
-                DataType::Int64 => DataType::Decimal,
-                DataType::Decimal => DataType::Decimal,
+                DataType::Int64 => DataType::Decimal(None),
+                DataType::Decimal(_) => DataType::Decimal(None),
                 DataType::Float32 => DataType::Float32,
                 DataType::Float64 => DataType::Float64,
                 DataType::Interval => DataType::Interval,
@@ -105,6 +105,46 @@ impl AggCall {
             (AggKind::SingleValue, [input]) => input.clone(),
             (AggKind::SingleValue, _) => return invalid(),
 
+            // Statistics are computed in double precision except for numerics, see `LogicalAgg`
+            // for the rewriting.
+            (
+                AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp,
+                [input],
+            ) => match input {
+                DataType::Decimal(_) => DataType::Decimal(None),
+                DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float32
+                | DataType::Float64 => DataType::Float64,
+                _ => return invalid(),
+            },
+            (AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp, _) => {
+                return invalid()
+            }
+
+            // BoolAnd, BoolOr
+            (AggKind::BoolAnd | AggKind::BoolOr, [DataType::Boolean]) => DataType::Boolean,
+            (AggKind::BoolAnd | AggKind::BoolOr, _) => return invalid(),
+
+            // BitAnd, BitOr
+            (
+                AggKind::BitAnd | AggKind::BitOr,
+                [input @ (DataType::Int16 | DataType::Int32 | DataType::Int64)],
+            ) => input.clone(),
+            (AggKind::BitAnd | AggKind::BitOr, _) => return invalid(),
+
+            // ArrayAgg
+            (AggKind::ArrayAgg, [DataType::List { .. }]) => return unsupported(),
+            (AggKind::ArrayAgg, [input]) => DataType::List {
+                datatype: Box::new(input.clone()),
+            },
+            (AggKind::ArrayAgg, _) => return invalid(),
+
+            // ApproxCountDistinct
+            (AggKind::ApproxCountDistinct, [_]) => DataType::Int64,
+            (AggKind::ApproxCountDistinct, _) => return invalid(),
+
             // Others
             _ => return unsupported(),
         };
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery};
+use super::{
+    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery,
+    WindowFunction,
+};
 
 /// By default, `ExprRewriter` simply traverses the expression tree and leaves nodes unchanged.
 /// Implementations can override a subset of methods and perform transformation on some particular
@@ -26,6 +29,7 @@ pub trait ExprRewriter {
             ExprImpl::AggCall(inner) => self.rewrite_agg_call(*inner),
             ExprImpl::Subquery(inner) => self.rewrite_subquery(*inner),
             ExprImpl::CorrelatedInputRef(inner) => self.rewrite_correlated_input_ref(*inner),
+            ExprImpl::WindowFunction(inner) => self.rewrite_window_function(*inner),
         }
     }
     fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
@@ -44,6 +48,21 @@ pub trait ExprRewriter {
             .collect();
         AggCall::new(func_type, inputs, distinct).unwrap().into()
     }
+    fn rewrite_window_function(&mut self, mut window_function: WindowFunction) -> ExprImpl {
+        window_function.args = std::mem::take(&mut window_function.args)
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        window_function.partition_by = std::mem::take(&mut window_function.partition_by)
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        window_function.order_by = std::mem::take(&mut window_function.order_by)
+            .into_iter()
+            .map(|(expr, direct)| (self.rewrite_expr(expr), direct))
+            .collect();
+        window_function.into()
+    }
     fn rewrite_literal(&mut self, literal: Literal) -> ExprImpl {
         literal.into()
     }
@@ -143,6 +143,26 @@ impl FunctionCall {
                 Ok(DataType::Varchar)
             }
 
+            // `regexp_match` and `regexp_split_to_array` take an optional flags argument and return
+            // a `varchar[]`.
+            ExprType::RegexpMatch | ExprType::RegexpSplitToArray => {
+                let actual = inputs.len();
+                if !(2..=3).contains(&actual) {
+                    return Err(ErrorCode::BindError(format!(
+                        "Function `{:?}` takes 2 or 3 arguments ({} given)",
+                        func_type, actual
+                    ))
+                    .into());
+                }
+                inputs = inputs
+                    .into_iter()
+                    .map(|input| input.cast_implicit(DataType::Varchar))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(DataType::List {
+                    datatype: Box::new(DataType::Varchar),
+                })
+            }
+
             _ => infer_type(
                 func_type,
                 inputs.iter().map(|expr| expr.return_type()).collect(),
@@ -36,6 +36,9 @@ impl std::fmt::Debug for Literal {
                 // Add single quotation marks for string and interval literals
                 Some(ScalarImpl::Utf8(v)) => write!(f, "'{}'", v),
                 Some(ScalarImpl::Interval(v)) => write!(f, "'{}'", v),
+                Some(ScalarImpl::Jsonb(v)) => write!(f, "'{}'", v),
+                Some(ScalarImpl::Bytea(v)) => write!(f, "'{}'", v),
+                Some(ScalarImpl::Uuid(v)) => write!(f, "'{}'", v),
                 Some(v) => write!(f, "{}", v),
             }?;
             write!(f, ":{:?}", self.data_type)
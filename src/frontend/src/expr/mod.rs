@@ -15,9 +15,10 @@
 use enum_as_inner::EnumAsInner;
 use fixedbitset::FixedBitSet;
 use paste::paste;
+use risingwave_common::array::Row;
 use risingwave_common::error::Result;
-use risingwave_common::types::{DataType, Scalar};
-use risingwave_expr::expr::AggKind;
+use risingwave_common::types::{DataType, Datum, Scalar};
+use risingwave_expr::expr::{build_from_prost, AggKind};
 use risingwave_pb::expr::ExprNode;
 
 mod agg_call;
@@ -26,6 +27,7 @@ mod function_call;
 mod input_ref;
 mod literal;
 mod subquery;
+mod window_function;
 
 mod expr_rewriter;
 mod expr_visitor;
@@ -38,6 +40,7 @@ pub use function_call::FunctionCall;
 pub use input_ref::{as_alias_display, input_ref_to_column_indices, InputRef, InputRefDisplay};
 pub use literal::Literal;
 pub use subquery::{Subquery, SubqueryKind};
+pub use window_function::WindowFunction;
 
 pub type ExprType = risingwave_pb::expr::expr_node::Type;
 
@@ -64,6 +67,7 @@ pub enum ExprImpl {
     FunctionCall(Box<FunctionCall>),
     AggCall(Box<AggCall>),
     Subquery(Box<Subquery>),
+    WindowFunction(Box<WindowFunction>),
 }
 
 impl ExprImpl {
@@ -79,6 +83,12 @@ impl ExprImpl {
         Literal::new(Some(v.to_scalar_value()), DataType::Boolean).into()
     }
 
+    /// A literal varchar value.
+    #[inline(always)]
+    pub fn literal_varchar(v: String) -> Self {
+        Literal::new(Some(v.to_scalar_value()), DataType::Varchar).into()
+    }
+
     /// A `count(*)` aggregate function.
     #[inline(always)]
     pub fn count_star() -> Self {
@@ -148,11 +158,17 @@ macro_rules! impl_has_variant {
     };
 }
 
-impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery}
+impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery, WindowFunction}
 
 impl ExprImpl {
     // We need to traverse inside subqueries.
     pub fn has_correlated_input_ref(&self) -> bool {
+        self.has_correlated_input_ref_by_depth(1)
+    }
+
+    /// Checks whether this expr has a [`CorrelatedInputRef`] whose depth is at least `depth`, i.e.
+    /// referring to the relation `depth` or more levels out.
+    pub fn has_correlated_input_ref_by_depth(&self, depth: usize) -> bool {
         struct Has {
             has: bool,
             depth: usize,
@@ -166,26 +182,41 @@ impl ExprImpl {
             }
 
             fn visit_subquery(&mut self, subquery: &Subquery) {
+                match &subquery.kind {
+                    SubqueryKind::In(expr)
+                    | SubqueryKind::Some(expr, _)
+                    | SubqueryKind::All(expr, _) => self.visit_expr(expr),
+                    SubqueryKind::Scalar | SubqueryKind::Existential => {}
+                }
+                self.depth += 1;
+                self.visit_set_expr(&subquery.query.body);
+                self.depth -= 1;
+            }
+        }
+
+        impl Has {
+            fn visit_set_expr(&mut self, set_expr: &crate::binder::BoundSetExpr) {
                 use crate::binder::BoundSetExpr;
 
-                self.depth += 1;
-                match &subquery.query.body {
+                match set_expr {
                     BoundSetExpr::Select(select) => select
                         .select_items
                         .iter()
                         .chain(select.group_by.iter())
                         .chain(select.where_clause.iter())
+                        .chain(select.having.iter())
                         .for_each(|expr| self.visit_expr(expr)),
                     BoundSetExpr::Values(_) => {}
+                    BoundSetExpr::Query(query) => self.visit_set_expr(&query.body),
+                    BoundSetExpr::SetOperation(set_operation) => {
+                        self.visit_set_expr(&set_operation.left);
+                        self.visit_set_expr(&set_operation.right);
+                    }
                 }
-                self.depth -= 1;
             }
         }
 
-        let mut visitor = Has {
-            has: false,
-            depth: 1,
-        };
+        let mut visitor = Has { has: false, depth };
         visitor.visit_expr(self);
         visitor.has
     }
@@ -210,6 +241,13 @@ impl ExprImpl {
         visitor.visit_expr(self);
         !visitor.has
     }
+
+    /// Evaluates a constant expr, see [`ExprImpl::is_const`].
+    pub fn eval_row_const(&self) -> Result<Datum> {
+        assert!(self.is_const());
+        let backend_expr = build_from_prost(&self.to_expr_proto())?;
+        backend_expr.eval_row(&Row::new(vec![]))
+    }
 }
 
 impl Expr for ExprImpl {
@@ -221,6 +259,7 @@ impl Expr for ExprImpl {
             ExprImpl::AggCall(expr) => expr.return_type(),
             ExprImpl::Subquery(expr) => expr.return_type(),
             ExprImpl::CorrelatedInputRef(expr) => expr.return_type(),
+            ExprImpl::WindowFunction(expr) => expr.return_type(),
         }
     }
 
@@ -232,6 +271,7 @@ impl Expr for ExprImpl {
             ExprImpl::AggCall(e) => e.to_expr_proto(),
             ExprImpl::Subquery(e) => e.to_expr_proto(),
             ExprImpl::CorrelatedInputRef(e) => e.to_expr_proto(),
+            ExprImpl::WindowFunction(e) => e.to_expr_proto(),
         }
     }
 }
@@ -272,6 +312,12 @@ impl From<CorrelatedInputRef> for ExprImpl {
     }
 }
 
+impl From<WindowFunction> for ExprImpl {
+    fn from(window_function: WindowFunction) -> Self {
+        ExprImpl::WindowFunction(Box::new(window_function))
+    }
+}
+
 impl From<Condition> for ExprImpl {
     fn from(c: Condition) -> Self {
         merge_expr_by_binary(
@@ -297,6 +343,7 @@ impl std::fmt::Debug for ExprImpl {
                 Self::CorrelatedInputRef(arg0) => {
                     f.debug_tuple("CorrelatedInputRef").field(arg0).finish()
                 }
+                Self::WindowFunction(arg0) => f.debug_tuple("WindowFunction").field(arg0).finish(),
             };
         }
         match self {
@@ -306,6 +353,7 @@ impl std::fmt::Debug for ExprImpl {
             Self::AggCall(x) => write!(f, "{:?}", x),
             Self::Subquery(x) => write!(f, "{:?}", x),
             Self::CorrelatedInputRef(x) => write!(f, "{:?}", x),
+            Self::WindowFunction(x) => write!(f, "{:?}", x),
         }
     }
 }
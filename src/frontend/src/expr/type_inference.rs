@@ -40,6 +40,9 @@ enum DataTypeName {
     Timestampz,
     Time,
     Interval,
+    Jsonb,
+    Bytea,
+    Uuid,
     Struct,
     List,
 }
@@ -50,7 +53,7 @@ fn name_of(ty: &DataType) -> DataTypeName {
         DataType::Int16 => DataTypeName::Int16,
         DataType::Int32 => DataTypeName::Int32,
         DataType::Int64 => DataTypeName::Int64,
-        DataType::Decimal => DataTypeName::Decimal,
+        DataType::Decimal(_) => DataTypeName::Decimal,
         DataType::Float32 => DataTypeName::Float32,
         DataType::Float64 => DataTypeName::Float64,
         DataType::Varchar => DataTypeName::Varchar,
@@ -59,6 +62,9 @@ fn name_of(ty: &DataType) -> DataTypeName {
         DataType::Timestampz => DataTypeName::Timestampz,
         DataType::Time => DataTypeName::Time,
         DataType::Interval => DataTypeName::Interval,
+        DataType::Jsonb => DataTypeName::Jsonb,
+        DataType::Bytea => DataTypeName::Bytea,
+        DataType::Uuid => DataTypeName::Uuid,
         DataType::Struct { .. } => DataTypeName::Struct,
         DataType::List { .. } => DataTypeName::List,
     }
@@ -75,7 +81,7 @@ pub fn infer_type(func_type: ExprType, inputs_type: Vec<DataType>) -> Result<Dat
         DataTypeName::Int16 => DataType::Int16,
         DataTypeName::Int32 => DataType::Int32,
         DataTypeName::Int64 => DataType::Int64,
-        DataTypeName::Decimal => DataType::Decimal,
+        DataTypeName::Decimal => DataType::Decimal(None),
         DataTypeName::Float32 => DataType::Float32,
         DataTypeName::Float64 => DataType::Float64,
         DataTypeName::Varchar => DataType::Varchar,
@@ -84,6 +90,9 @@ pub fn infer_type(func_type: ExprType, inputs_type: Vec<DataType>) -> Result<Dat
         DataTypeName::Timestampz => DataType::Timestampz,
         DataTypeName::Time => DataType::Time,
         DataTypeName::Interval => DataType::Interval,
+        DataTypeName::Jsonb => DataType::Jsonb,
+        DataTypeName::Bytea => DataType::Bytea,
+        DataTypeName::Uuid => DataType::Uuid,
         DataTypeName::Struct | DataTypeName::List => {
             panic!("Functions returning struct or list can not be inferred. Please use `FunctionCall::new_unchecked`.")
         }
@@ -184,6 +193,8 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         T::Timestampz,
         T::Time,
         T::Interval,
+        T::Bytea,
+        T::Uuid,
     ];
     let num_types = [
         T::Int16,
@@ -222,7 +233,7 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
     build_binary_cmp_funcs(&mut map, cmp_exprs, &[T::Date, T::Timestamp, T::Timestampz]);
     build_binary_cmp_funcs(&mut map, cmp_exprs, &[T::Time, T::Interval]);
     for e in cmp_exprs {
-        for t in [T::Boolean, T::Varchar] {
+        for t in [T::Boolean, T::Varchar, T::Bytea, T::Uuid] {
             map.insert(FuncSign::new(*e, vec![t, t]), T::Boolean);
         }
     }
@@ -244,6 +255,7 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         FuncSign::new(E::RoundDigit, vec![T::Decimal, T::Int32]),
         T::Decimal,
     );
+    map.insert(FuncSign::new(E::Sqrt, vec![T::Float64]), T::Float64);
 
     // temporal expressions
     for (base, delta) in [
@@ -281,6 +293,51 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
             T::Timestamp,
         );
     }
+    for t in [T::Timestamp, T::Interval] {
+        map.insert(FuncSign::new(E::DateTrunc, vec![T::Varchar, t]), t);
+    }
+    for t in [T::Date, T::Timestamp, T::Time, T::Interval] {
+        map.insert(FuncSign::new(E::DatePart, vec![T::Varchar, t]), T::Float64);
+    }
+    map.insert(
+        FuncSign::new(E::ToChar, vec![T::Timestamp, T::Varchar]),
+        T::Varchar,
+    );
+    map.insert(
+        FuncSign::new(E::ToTimestamp, vec![T::Varchar, T::Varchar]),
+        T::Timestamp,
+    );
+    map.insert(
+        FuncSign::new(E::ToTimestamp, vec![T::Float64]),
+        T::Timestampz,
+    );
+    map.insert(
+        FuncSign::new(
+            E::MakeInterval,
+            vec![
+                T::Int32,
+                T::Int32,
+                T::Int32,
+                T::Int32,
+                T::Int32,
+                T::Int32,
+                T::Float64,
+            ],
+        ),
+        T::Interval,
+    );
+    map.insert(
+        FuncSign::new(E::Age, vec![T::Timestamp, T::Timestamp]),
+        T::Interval,
+    );
+    map.insert(
+        FuncSign::new(E::AtTimeZone, vec![T::Timestamp, T::Varchar]),
+        T::Timestampz,
+    );
+    map.insert(
+        FuncSign::new(E::AtTimeZone, vec![T::Timestampz, T::Varchar]),
+        T::Timestamp,
+    );
 
     // string expressions
     for e in [E::Trim, E::Ltrim, E::Rtrim, E::Lower, E::Upper] {
@@ -310,13 +367,52 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         FuncSign::new(E::Position, vec![T::Varchar, T::Varchar]),
         T::Int32,
     );
+    for e in [E::Like, E::ILike, E::SimilarTo, E::RegexpLike] {
+        map.insert(FuncSign::new(e, vec![T::Varchar, T::Varchar]), T::Boolean);
+    }
+    for e in [E::SimilarTo, E::RegexpLike] {
+        map.insert(
+            FuncSign::new(e, vec![T::Varchar, T::Varchar, T::Varchar]),
+            T::Boolean,
+        );
+    }
+    for args in [3, 4] {
+        map.insert(
+            FuncSign::new(E::RegexpReplace, vec![T::Varchar; args]),
+            T::Varchar,
+        );
+    }
+    map.insert(
+        FuncSign::new(E::SplitPart, vec![T::Varchar, T::Varchar, T::Int32]),
+        T::Varchar,
+    );
+
+    // The path of `#>` and `#>>` can be either a `varchar[]` or its text form like `'{a,b}'`.
+    for (key, path) in [(T::Varchar, T::List), (T::Int32, T::Varchar)] {
+        map.insert(
+            FuncSign::new(E::JsonbAccessInner, vec![T::Jsonb, key]),
+            T::Jsonb,
+        );
+        map.insert(
+            FuncSign::new(E::JsonbAccessStr, vec![T::Jsonb, key]),
+            T::Varchar,
+        );
+        map.insert(
+            FuncSign::new(E::JsonbExtractPath, vec![T::Jsonb, path]),
+            T::Jsonb,
+        );
+        map.insert(
+            FuncSign::new(E::JsonbExtractPathText, vec![T::Jsonb, path]),
+            T::Varchar,
+        );
+    }
     map.insert(
-        FuncSign::new(E::Like, vec![T::Varchar, T::Varchar]),
+        FuncSign::new(E::JsonbContains, vec![T::Jsonb, T::Jsonb]),
         T::Boolean,
     );
     map.insert(
-        FuncSign::new(E::SplitPart, vec![T::Varchar, T::Varchar, T::Int32]),
-        T::Varchar,
+        FuncSign::new(E::JsonbExists, vec![T::Jsonb, T::Varchar]),
+        T::Boolean,
     );
 
     map
@@ -337,6 +433,10 @@ lazy_static::lazy_static! {
 pub fn least_restrictive(lhs: DataType, rhs: DataType) -> Result<DataType> {
     if lhs == rhs {
         Ok(lhs)
+    } else if matches!((&lhs, &rhs), (DataType::Decimal(_), DataType::Decimal(_))) {
+        // `NUMERIC` with different type modifiers: fall back to the unconstrained one so that no
+        // value is rounded or rejected.
+        Ok(DataType::Decimal(None))
     } else if cast_ok(&lhs, &rhs, &CastContext::Implicit) {
         Ok(rhs)
     } else if cast_ok(&rhs, &lhs, &CastContext::Implicit) {
@@ -384,6 +484,15 @@ pub enum CastContext {
 
 /// Checks whether casting from `source` to `target` is ok in `allows` context.
 pub fn cast_ok(source: &DataType, target: &DataType, allows: &CastContext) -> bool {
+    // Sizing casts between `NUMERIC` with different type modifiers are implicit.
+    if source != target
+        && matches!(
+            (source, target),
+            (DataType::Decimal(_), DataType::Decimal(_))
+        )
+    {
+        return true;
+    }
     let k = (name_of(source), name_of(target));
     matches!(CAST_MAP.get(&k), Some(context) if context <= allows)
 }
@@ -417,9 +526,13 @@ fn build_cast_map() -> HashMap<(DataTypeName, DataTypeName), CastContext> {
     // to `NUMERIC(20,4)`). Sizing casts are only available for `numeric`, `timestamp`,
     // `timestamptz`, `time`, `interval` and these are implicit. https://www.postgresql.org/docs/current/typeconv-query.html
     //
-    // As we do not support size parameters in types, there are no sizing casts.
+    // `NUMERIC(p, s)` is the only type with size parameters that we support, and its sizing casts
+    // are handled in `cast_ok` directly.
     m.insert((T::Boolean, T::Boolean), CastContext::Explicit);
     m.insert((T::Varchar, T::Varchar), CastContext::Explicit);
+    m.insert((T::Jsonb, T::Jsonb), CastContext::Explicit);
+    m.insert((T::Bytea, T::Bytea), CastContext::Explicit);
+    m.insert((T::Uuid, T::Uuid), CastContext::Explicit);
 
     // Casting to and from string type.
     for t in [
@@ -435,6 +548,9 @@ fn build_cast_map() -> HashMap<(DataTypeName, DataTypeName), CastContext> {
         T::Timestampz,
         T::Time,
         T::Interval,
+        T::Jsonb,
+        T::Bytea,
+        T::Uuid,
     ] {
         m.insert((t, T::Varchar), CastContext::Assign);
         // Casting from string is explicit-only in PG.
@@ -508,37 +624,37 @@ mod tests {
             (Int16, Int16, Int16),
             (Int16, Int32, Int32),
             (Int16, Int64, Int64),
-            (Int16, Decimal, Decimal),
+            (Int16, Decimal(None), Decimal(None)),
             (Int16, Float32, Float32),
             (Int16, Float64, Float64),
             (Int32, Int16, Int32),
             (Int32, Int32, Int32),
             (Int32, Int64, Int64),
-            (Int32, Decimal, Decimal),
+            (Int32, Decimal(None), Decimal(None)),
             (Int32, Float32, Float32),
             (Int32, Float64, Float64),
             (Int64, Int16, Int64),
             (Int64, Int32, Int64),
             (Int64, Int64, Int64),
-            (Int64, Decimal, Decimal),
+            (Int64, Decimal(None), Decimal(None)),
             (Int64, Float32, Float32),
             (Int64, Float64, Float64),
-            (Decimal, Int16, Decimal),
-            (Decimal, Int32, Decimal),
-            (Decimal, Int64, Decimal),
-            (Decimal, Decimal, Decimal),
-            (Decimal, Float32, Float32),
-            (Decimal, Float64, Float64),
+            (Decimal(None), Int16, Decimal(None)),
+            (Decimal(None), Int32, Decimal(None)),
+            (Decimal(None), Int64, Decimal(None)),
+            (Decimal(None), Decimal(None), Decimal(None)),
+            (Decimal(None), Float32, Float32),
+            (Decimal(None), Float64, Float64),
             (Float32, Int16, Float32),
             (Float32, Int32, Float32),
             (Float32, Int64, Float32),
-            (Float32, Decimal, Float32),
+            (Float32, Decimal(None), Float32),
             (Float32, Float32, Float32),
             (Float32, Float64, Float64),
             (Float64, Int16, Float64),
             (Float64, Int32, Float64),
             (Float64, Int64, Float64),
-            (Float64, Decimal, Float64),
+            (Float64, Decimal(None), Float64),
             (Float64, Float32, Float64),
             (Float64, Float64, Float64),
         ];
@@ -547,6 +663,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_temporal() {
+        use DataType::*;
+        test_simple_infer_type(ExprType::Add, vec![Timestampz, Interval], Timestampz);
+        test_simple_infer_type(ExprType::Subtract, vec![Time, Time], Interval);
+        test_simple_infer_type(ExprType::Multiply, vec![Float64, Interval], Interval);
+        test_simple_infer_type(ExprType::DateTrunc, vec![Varchar, Interval], Interval);
+        test_simple_infer_type(ExprType::DatePart, vec![Varchar, Date], Float64);
+        test_simple_infer_type(ExprType::AtTimeZone, vec![Timestamp, Varchar], Timestampz);
+        test_simple_infer_type(ExprType::AtTimeZone, vec![Timestampz, Varchar], Timestamp);
+        test_infer_type_not_exist(ExprType::DateTrunc, vec![Varchar, Timestampz]);
+    }
+
     #[test]
     fn test_bool_num_not_exist() {
         let exprs = vec![
@@ -571,7 +700,7 @@ mod tests {
             DataType::Int64,
             DataType::Float32,
             DataType::Float64,
-            DataType::Decimal,
+            DataType::Decimal(None),
         ];
 
         for (expr, num_t) in iproduct!(exprs, num_types) {
@@ -587,7 +716,7 @@ mod tests {
             T::Int16,
             T::Int32,
             T::Int64,
-            T::Decimal,
+            T::Decimal(None),
             T::Float32,
             T::Float64,
             T::Varchar,
@@ -675,4 +804,25 @@ mod tests {
             ]
         );
     }
+    #[test]
+    fn test_decimal_typmod() {
+        let narrow = DataType::Decimal(Some((5, 2)));
+        let wide = DataType::Decimal(Some((10, 4)));
+        assert!(cast_ok(
+            &DataType::Decimal(None),
+            &narrow,
+            &CastContext::Implicit
+        ));
+        assert!(cast_ok(&wide, &narrow, &CastContext::Implicit));
+        assert!(cast_ok(&DataType::Int32, &narrow, &CastContext::Implicit));
+        assert!(!cast_ok(&narrow, &narrow, &CastContext::Implicit));
+        assert_eq!(
+            least_restrictive(narrow.clone(), wide).unwrap(),
+            DataType::Decimal(None)
+        );
+        assert_eq!(
+            least_restrictive(narrow.clone(), narrow.clone()).unwrap(),
+            narrow
+        );
+    }
 }
@@ -0,0 +1,116 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::DataType;
+use risingwave_expr::window_function::{Frame, WindowFuncKind};
+
+use super::{AggCall, Expr, ExprImpl};
+use crate::optimizer::property::Direction;
+
+/// A window function call, e.g. `row_number() OVER (PARTITION BY a ORDER BY b)`.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct WindowFunction {
+    pub kind: WindowFuncKind,
+    pub args: Vec<ExprImpl>,
+    pub return_type: DataType,
+    pub partition_by: Vec<ExprImpl>,
+    pub order_by: Vec<(ExprImpl, Direction)>,
+    pub frame: Frame,
+    /// Offset of `lag` and `lead`.
+    pub offset: usize,
+}
+
+impl std::fmt::Debug for WindowFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.debug_struct("WindowFunction")
+                .field("kind", &self.kind)
+                .field("return_type", &self.return_type)
+                .field("args", &self.args)
+                .field("partition_by", &self.partition_by)
+                .field("order_by", &self.order_by)
+                .field("frame", &self.frame)
+                .field("offset", &self.offset)
+                .finish()
+        } else {
+            let mut builder = f.debug_tuple(&format!("{}", self.kind));
+            self.args.iter().for_each(|child| {
+                builder.field(child);
+            });
+            builder.finish()
+        }
+    }
+}
+
+impl WindowFunction {
+    /// Creates a window function call with the default frame. Returns error if the arguments are
+    /// invalid.
+    pub fn new(
+        kind: WindowFuncKind,
+        args: Vec<ExprImpl>,
+        partition_by: Vec<ExprImpl>,
+        order_by: Vec<(ExprImpl, Direction)>,
+    ) -> Result<Self> {
+        let return_type = Self::infer_return_type(&kind, &args)?;
+        Ok(Self {
+            kind,
+            args,
+            return_type,
+            partition_by,
+            order_by,
+            frame: Frame::default(),
+            offset: 1,
+        })
+    }
+
+    fn infer_return_type(kind: &WindowFuncKind, args: &[ExprImpl]) -> Result<DataType> {
+        let invalid = || {
+            let args = args
+                .iter()
+                .map(|arg| format!("{:?}", arg.return_type()))
+                .join(", ");
+            Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "Invalid window function: {}({})",
+                kind, args
+            ))))
+        };
+        match (kind, args) {
+            (WindowFuncKind::RowNumber | WindowFuncKind::Rank | WindowFuncKind::DenseRank, []) => {
+                Ok(DataType::Int64)
+            }
+            (WindowFuncKind::Lag | WindowFuncKind::Lead, [arg]) => Ok(arg.return_type()),
+            (WindowFuncKind::Aggregate(agg_kind), args) => AggCall::infer_return_type(
+                agg_kind,
+                &args.iter().map(ExprImpl::return_type).collect_vec(),
+            ),
+            _ => invalid(),
+        }
+    }
+}
+
+impl Expr for WindowFunction {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn to_expr_proto(&self) -> risingwave_pb::expr::ExprNode {
+        // Window functions are always rewritten to `OverAgg` operators in the planner.
+        unreachable!(
+            "WindowFunction {:?} has not been rewritten to OverAgg operators",
+            self
+        )
+    }
+}
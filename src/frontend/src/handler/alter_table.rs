@@ -0,0 +1,307 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::catalog::{ColumnDesc, ColumnId};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::DataType;
+use risingwave_pb::ddl_service::alter_source_request::Change;
+use risingwave_pb::plan_common::ColumnCatalog;
+use risingwave_pb::stream_plan::source_node::SourceType;
+use risingwave_sqlparser::ast::{
+    AlterTableOperation, ColumnDef, ColumnOption, DataType as AstDataType, Expr, ObjectName,
+};
+
+use crate::binder::expr::bind_data_type;
+use crate::binder::Binder;
+use crate::catalog::check_valid_column_name;
+use crate::catalog::source_catalog::SourceCatalog;
+use crate::handler::privilege::check_owner;
+use crate::session::{OptimizerContext, SessionImpl};
+
+/// Handles `ALTER TABLE ADD COLUMN` and `ALTER TABLE DROP COLUMN`. The default value of an added
+/// column is stored in its catalog, and the existing rows read it when they are scanned.
+pub async fn handle_alter_table(
+    context: OptimizerContext,
+    name: ObjectName,
+    operation: AlterTableOperation,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, table_name) = Binder::resolve_table_name(name)?;
+
+    let source = {
+        let reader = session.env().catalog_reader().read_guard();
+        let table = reader.get_table_by_name(session.database(), &schema_name, &table_name)?;
+        if table.associated_source_id().is_none() {
+            return Err(RwError::from(ErrorCode::NotImplemented(
+                "ALTER TABLE on a materialized view".to_string(),
+                None.into(),
+            )));
+        }
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)?
+            .owner();
+        check_owner(&session, &[table.owner, schema_owner], "table", &table_name)?;
+
+        reader
+            .get_source_by_name(session.database(), &schema_name, &table_name)?
+            .clone()
+    };
+
+    alter_source_columns(&session, &source, operation).await?;
+
+    Ok(PgResponse::empty_result(StatementType::ALTER_TABLE))
+}
+
+/// Handles `ALTER SOURCE ADD COLUMN` and `ALTER SOURCE DROP COLUMN`. Only the tasks created
+/// afterwards see the change of the columns.
+pub async fn handle_alter_source(
+    context: OptimizerContext,
+    name: ObjectName,
+    operation: AlterTableOperation,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, source_name) = Binder::resolve_table_name(name)?;
+
+    let source = {
+        let reader = session.env().catalog_reader().read_guard();
+        let source = reader.get_source_by_name(session.database(), &schema_name, &source_name)?;
+        if source.source_type == SourceType::Table {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(
+                "Use `ALTER TABLE` to alter a table.".to_owned(),
+            )));
+        }
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)?
+            .owner();
+        check_owner(
+            &session,
+            &[source.owner, schema_owner],
+            "source",
+            &source_name,
+        )?;
+
+        source.clone()
+    };
+
+    if let AlterTableOperation::AddColumn { column_def } = &operation {
+        if default_value(column_def).is_some() {
+            return Err(RwError::from(ErrorCode::NotImplemented(
+                "DEFAULT of a source column".to_string(),
+                None.into(),
+            )));
+        }
+    }
+    alter_source_columns(&session, &source, operation).await?;
+
+    Ok(PgResponse::empty_result(StatementType::ALTER_SOURCE))
+}
+
+/// Adds or drops a column of the source.
+async fn alter_source_columns(
+    session: &SessionImpl,
+    source: &SourceCatalog,
+    operation: AlterTableOperation,
+) -> Result<()> {
+    // The id of an added column is its position, which doesn't hold when the fields of struct
+    // columns have ids.
+    if source
+        .columns
+        .iter()
+        .any(|column| matches!(column.data_type(), DataType::Struct { .. }))
+    {
+        return Err(RwError::from(ErrorCode::NotImplemented(
+            "ALTER on a relation with struct columns".to_string(),
+            None.into(),
+        )));
+    }
+
+    let change = match operation {
+        AlterTableOperation::AddColumn { column_def } => {
+            let name = &column_def.name.value;
+            check_valid_column_name(name)?;
+            if source.columns.iter().any(|column| column.name() == name) {
+                return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                    "column \"{}\" already exists",
+                    name
+                ))));
+            }
+            if let AstDataType::Struct(_) = column_def.data_type {
+                return Err(RwError::from(ErrorCode::NotImplemented(
+                    "add a struct column".to_string(),
+                    None.into(),
+                )));
+            }
+
+            let data_type = bind_data_type(&column_def.data_type)?;
+            let default_value = match default_value(&column_def) {
+                Some(value) => {
+                    let expr = Binder::new(session)
+                        .bind_expr_on_columns(value, source.name.clone(), [])?
+                        .cast_assign(data_type.clone())?;
+                    if !expr.is_const() {
+                        return Err(RwError::from(ErrorCode::NotImplemented(
+                            "non-constant DEFAULT of an added column".to_string(),
+                            None.into(),
+                        )));
+                    }
+                    expr.eval_row_const()?
+                }
+                None => None,
+            };
+
+            let column = ColumnCatalog {
+                column_desc: Some(
+                    ColumnDesc {
+                        data_type,
+                        // The id is assigned by meta.
+                        column_id: ColumnId::new(0),
+                        name: name.clone(),
+                        field_descs: vec![],
+                        type_name: "".to_string(),
+                        default_value,
+                    }
+                    .to_protobuf(),
+                ),
+                is_hidden: false,
+            };
+            Change::AddColumn(column)
+        }
+        AlterTableOperation::DropColumn {
+            column_name,
+            if_exists,
+            cascade,
+        } => {
+            if cascade {
+                return Err(RwError::from(ErrorCode::NotImplemented(
+                    "DROP COLUMN CASCADE".to_string(),
+                    None.into(),
+                )));
+            }
+            let column = source
+                .columns
+                .iter()
+                .find(|column| !column.is_hidden() && column.name() == column_name.value);
+            match column {
+                Some(column) => Change::DropColumnId(column.column_id().get_id()),
+                None if if_exists => return Ok(()),
+                None => {
+                    return Err(RwError::from(ErrorCode::ItemNotFound(format!(
+                        "column \"{}\"",
+                        column_name.value
+                    ))))
+                }
+            }
+        }
+        operation => {
+            return Err(RwError::from(ErrorCode::NotImplemented(
+                format!("ALTER {}", operation),
+                None.into(),
+            )))
+        }
+    };
+
+    session
+        .env()
+        .catalog_writer()
+        .alter_source(source.id, source.version, change)
+        .await?;
+
+    Ok(())
+}
+
+fn default_value(column_def: &ColumnDef) -> Option<Expr> {
+    column_def
+        .options
+        .iter()
+        .find_map(|option| match &option.option {
+            ColumnOption::Default(value) => Some(value.clone()),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_alter_table_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t drop column v1;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t add column v1 double;")
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql("alter table t add column v2 int;")
+            .await
+            .is_err());
+        frontend
+            .run_sql("alter table t drop column if exists v3;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t add column v3 int default 1 + 1;")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let table = catalog_reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "t")
+            .unwrap();
+
+        // The dropped column is kept hidden, and the added columns are appended with their
+        // positions as ids.
+        let columns = table
+            .columns()
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .map(|column| (column.name().to_string(), column.column_id().get_id()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            vec![
+                ("v2".to_string(), 2),
+                ("v1".to_string(), 3),
+                ("v3".to_string(), 4)
+            ]
+        );
+        assert_eq!(table.columns().len(), 5);
+        assert_eq!(table.columns()[3].data_type(), &DataType::Float64);
+        assert_eq!(table.columns()[3].column_desc.default_value, None);
+        // The default value is evaluated and stored in the catalog of the added column.
+        assert_eq!(
+            table.columns()[4].column_desc.default_value,
+            Some(ScalarImpl::Int32(2))
+        );
+        assert_eq!(table.version, 3);
+
+        let source = catalog_reader
+            .get_source_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "t")
+            .unwrap();
+        assert_eq!(source.columns.len(), 5);
+        assert_eq!(source.version, 3);
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::{InternalError, PermissionDenied};
+use risingwave_common::error::Result;
+use risingwave_pb::user::update_user_request::UpdateField;
+use risingwave_pb::user::UserInfo;
+use risingwave_sqlparser::ast::{AlterUserMode, AlterUserStatement, ObjectName, UserOption};
+
+use crate::binder::Binder;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+use crate::user::user_authentication::encrypted_password;
+use crate::user::user_service::UserInfoReadGuard;
+
+fn alter_prost_user_info(
+    mut user_info: UserInfo,
+    options: &[UserOption],
+    session_user: &UserInfo,
+) -> Result<(UserInfo, Vec<UpdateField>)> {
+    if !session_user.is_supper {
+        // A user without privileges can only change its own password.
+        let only_password = options.iter().all(|option| {
+            matches!(
+                option,
+                UserOption::EncryptedPassword(_) | UserOption::Password(_)
+            )
+        });
+        if session_user.id != user_info.id || !only_password {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+    }
+
+    let mut update_fields = Vec::new();
+    for option in options {
+        match option {
+            UserOption::SuperUser => {
+                user_info.is_supper = true;
+                update_fields.push(UpdateField::Super);
+            }
+            UserOption::NoSuperUser => {
+                user_info.is_supper = false;
+                update_fields.push(UpdateField::Super);
+            }
+            UserOption::CreateDB => {
+                user_info.can_create_db = true;
+                update_fields.push(UpdateField::CreateDb);
+            }
+            UserOption::NoCreateDB => {
+                user_info.can_create_db = false;
+                update_fields.push(UpdateField::CreateDb);
+            }
+            UserOption::Login => {
+                user_info.can_login = true;
+                update_fields.push(UpdateField::Login);
+            }
+            UserOption::NoLogin => {
+                user_info.can_login = false;
+                update_fields.push(UpdateField::Login);
+            }
+            UserOption::EncryptedPassword(password) | UserOption::Password(Some(password)) => {
+                user_info.auth_info = encrypted_password(&user_info.name, &password.0);
+                update_fields.push(UpdateField::AuthInfo);
+            }
+            UserOption::Password(None) => {
+                user_info.auth_info = None;
+                update_fields.push(UpdateField::AuthInfo);
+            }
+        }
+    }
+    Ok((user_info, update_fields))
+}
+
+fn alter_rename_prost_user_info(
+    mut user_info: UserInfo,
+    new_name: ObjectName,
+    session_user: &UserInfo,
+    reader: &UserInfoReadGuard,
+) -> Result<(UserInfo, Vec<UpdateField>)> {
+    if session_user.id == user_info.id {
+        return Err(InternalError("session user cannot be renamed".to_string()).into());
+    }
+    if !session_user.is_supper {
+        return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+    }
+
+    let new_name = Binder::resolve_user_name(new_name)?;
+    if reader.get_user_by_name(&new_name).is_some() {
+        return Err(CatalogError::Duplicated("user", new_name).into());
+    }
+
+    user_info.name = new_name;
+    // The MD5 password is salted with the user name, so it is cleared on rename like PostgreSQL
+    // does.
+    user_info.auth_info = None;
+    Ok((user_info, vec![UpdateField::Rename, UpdateField::AuthInfo]))
+}
+
+pub async fn handle_alter_user(
+    context: OptimizerContext,
+    stmt: AlterUserStatement,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (user_info, update_fields) = {
+        let user_name = Binder::resolve_user_name(stmt.user_name.clone())?;
+        let user_reader = session.env().user_info_reader();
+        let reader = user_reader.read_guard();
+
+        let old_info = reader
+            .get_user_by_name(&user_name)
+            .ok_or_else(|| CatalogError::NotFound("user", user_name))?
+            .clone();
+        let session_user = reader
+            .get_user_by_name(session.user_name())
+            .ok_or_else(|| CatalogError::NotFound("user", session.user_name().to_string()))?;
+
+        match stmt.mode {
+            AlterUserMode::Options(options) => {
+                alter_prost_user_info(old_info, &options.0, session_user)?
+            }
+            AlterUserMode::Rename(new_name) => {
+                alter_rename_prost_user_info(old_info, new_name, session_user, &reader)?
+            }
+        }
+    };
+
+    let user_info_writer = session.env().user_info_writer();
+    user_info_writer
+        .update_user(user_info, update_fields)
+        .await?;
+    Ok(PgResponse::empty_result(StatementType::ALTER_USER))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::user::auth_info::EncryptionType;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_alter_user() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        let user_info_reader = session.env().user_info_reader();
+
+        frontend
+            .run_sql("CREATE USER user_b WITH NOSUPERUSER PASSWORD 'password'")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("ALTER USER user_b WITH SUPERUSER NOLOGIN")
+            .await
+            .unwrap();
+
+        let user_info = user_info_reader
+            .read_guard()
+            .get_user_by_name("user_b")
+            .cloned()
+            .unwrap();
+        assert!(user_info.is_supper);
+        assert!(!user_info.can_login);
+        assert_eq!(
+            user_info.auth_info.unwrap().encryption_type(),
+            EncryptionType::Md5
+        );
+
+        frontend
+            .run_sql("ALTER USER user_b RENAME TO user_c")
+            .await
+            .unwrap();
+        let reader = user_info_reader.read_guard();
+        assert!(reader.get_user_by_name("user_b").is_none());
+        let user_info = reader.get_user_by_name("user_c").unwrap();
+        assert!(user_info.is_supper);
+        assert!(user_info.auth_info.is_none());
+    }
+}
@@ -0,0 +1,510 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use pgwire::copy::{CopyFormat, CopyOptions};
+use pgwire::pg_field_descriptor::PgFieldDescriptor;
+use pgwire::pg_response::{CopyInSink, PgResponse, StatementType};
+use pgwire::pg_server::BoxedError;
+use pgwire::types::Row;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_pb::batch_plan::PlanNode;
+use risingwave_pb::user::grant_privilege::Privilege;
+use risingwave_sqlparser::ast::{
+    CopyOption, CopySource, Expr, Ident, ObjectName, Query, Select, SelectItem, SetExpr, Statement,
+    TableFactor, TableWithJoins,
+};
+
+use crate::binder::{
+    Binder, BoundInsert, BoundQuery, BoundSetExpr, BoundStatement, BoundTableSource, BoundValues,
+};
+use crate::expr::{ExprImpl, Literal};
+use crate::handler::dml::{execute_dml_plan, implicit_flush_for_write};
+use crate::handler::query::execute_query;
+use crate::handler::util::to_pg_field;
+use crate::planner::Planner;
+use crate::session::{OptimizerContext, SessionImpl};
+
+pub async fn handle_copy(
+    context: OptimizerContext,
+    source: CopySource,
+    to: bool,
+    options: Vec<CopyOption>,
+    values: Vec<Option<String>>,
+) -> Result<PgResponse> {
+    if !values.is_empty() {
+        return Err(ErrorCode::NotImplemented(
+            "COPY with data inlined in the statement".into(),
+            None.into(),
+        )
+        .into());
+    }
+    let options = resolve_copy_options(options)?;
+    if to {
+        handle_copy_to(context, source, options).await
+    } else {
+        match source {
+            CopySource::Table {
+                table_name,
+                columns,
+            } => handle_copy_from(context, table_name, columns, options),
+            CopySource::Query(_) => unreachable!("COPY FROM a query is rejected by the parser"),
+        }
+    }
+}
+
+/// Executes the query, or scans the table, and sends the rows as the copied data.
+async fn handle_copy_to(
+    context: OptimizerContext,
+    source: CopySource,
+    options: CopyOptions,
+) -> Result<PgResponse> {
+    let query = match source {
+        CopySource::Table {
+            table_name,
+            columns,
+        } => select_from_table(table_name, columns),
+        CopySource::Query(query) => *query,
+    };
+    let bound = {
+        let mut binder = Binder::new(&context.session_ctx);
+        binder.bind(Statement::Query(Box::new(query)))?
+    };
+    let (rows, pg_descs) = execute_query(context, bound).await?;
+    Ok(PgResponse::copy_out(rows, pg_descs, options))
+}
+
+/// Builds `SELECT <columns> FROM <table_name>`, selecting all columns if none is given.
+fn select_from_table(table_name: ObjectName, columns: Vec<Ident>) -> Query {
+    let projection = if columns.is_empty() {
+        vec![SelectItem::Wildcard]
+    } else {
+        columns
+            .into_iter()
+            .map(|column| SelectItem::UnnamedExpr(Expr::Identifier(column)))
+            .collect()
+    };
+    let select = Select {
+        distinct: false,
+        projection,
+        from: vec![TableWithJoins {
+            relation: TableFactor::Table {
+                name: table_name,
+                alias: None,
+                args: vec![],
+            },
+            joins: vec![],
+        }],
+        lateral_views: vec![],
+        selection: None,
+        group_by: vec![],
+        having: None,
+    };
+    Query {
+        with: None,
+        body: SetExpr::Select(Box::new(select)),
+        order_by: vec![],
+        limit: None,
+        offset: None,
+        fetch: None,
+    }
+}
+
+/// Prepares to receive the copied data, which is inserted into the table source once all of it
+/// arrives.
+fn handle_copy_from(
+    context: OptimizerContext,
+    table_name: ObjectName,
+    columns: Vec<Ident>,
+    options: CopyOptions,
+) -> Result<PgResponse> {
+    let session = context.session_ctx.clone();
+    let table_source = {
+        let mut binder = Binder::new(&session);
+        binder.bind_table_source(table_name, Privilege::Insert)?
+    };
+
+    // The columns not copied are filled with `NULL`.
+    let col_indices = if columns.is_empty() {
+        (0..table_source.columns.len()).collect_vec()
+    } else {
+        let mut col_indices = Vec::with_capacity(columns.len());
+        for column in columns {
+            let name = column.value;
+            let idx = table_source
+                .columns
+                .iter()
+                .position(|c| c.name == name)
+                .ok_or_else(|| {
+                    ErrorCode::BindError(format!(
+                        "column \"{}\" of relation \"{}\" does not exist",
+                        name, table_source.name
+                    ))
+                })?;
+            if col_indices.contains(&idx) {
+                return Err(ErrorCode::BindError(format!(
+                    "column \"{}\" specified more than once",
+                    name
+                ))
+                .into());
+            }
+            col_indices.push(idx);
+        }
+        col_indices
+    };
+
+    let row_desc: Vec<PgFieldDescriptor> = col_indices
+        .iter()
+        .map(|&idx| to_pg_field(&Field::from(&table_source.columns[idx])))
+        .collect();
+    let sink = CopyInToTable {
+        session,
+        table_source,
+        col_indices,
+        rows: vec![],
+    };
+    Ok(PgResponse::copy_in(row_desc, options, Box::new(sink)))
+}
+
+fn resolve_copy_options(options: Vec<CopyOption>) -> Result<CopyOptions> {
+    let mut format = CopyFormat::Text;
+    let mut delimiter = None;
+    let mut null = None;
+    let mut header = false;
+    for option in options {
+        match option {
+            CopyOption::Format(name) => {
+                format = match name.value.to_lowercase().as_str() {
+                    "text" => CopyFormat::Text,
+                    "csv" => CopyFormat::Csv,
+                    "binary" => {
+                        return Err(ErrorCode::NotImplemented(
+                            "COPY in binary format".into(),
+                            None.into(),
+                        )
+                        .into())
+                    }
+                    other => {
+                        return Err(ErrorCode::InvalidInputSyntax(format!(
+                            "COPY format \"{}\" not recognized",
+                            other
+                        ))
+                        .into())
+                    }
+                }
+            }
+            CopyOption::Delimiter(c) => {
+                if !c.is_ascii() || matches!(c, '\\' | '\r' | '\n' | '"') {
+                    return Err(ErrorCode::InvalidInputSyntax(format!(
+                        "COPY delimiter cannot be \"{}\"",
+                        c.escape_default()
+                    ))
+                    .into());
+                }
+                delimiter = Some(c as u8);
+            }
+            CopyOption::Null(s) => null = Some(s),
+            CopyOption::Header(h) => header = h,
+        }
+    }
+    if header && format != CopyFormat::Csv {
+        return Err(ErrorCode::InvalidInputSyntax(
+            "COPY HEADER available only in CSV mode".to_string(),
+        )
+        .into());
+    }
+
+    let mut options = CopyOptions::new(format);
+    if let Some(delimiter) = delimiter {
+        options.delimiter = delimiter;
+    }
+    if let Some(null) = null {
+        options.null = null;
+    }
+    options.header = header;
+    Ok(options)
+}
+
+/// Inserts the rows copied by the client into a table source. The rows are casted to the column
+/// types as they arrive, and staged until the copy is done, so that a copy failed by a bad row or
+/// aborted by the client inserts nothing.
+struct CopyInToTable {
+    session: Arc<SessionImpl>,
+    table_source: BoundTableSource,
+    /// The index in `table_source.columns` of each copied column.
+    col_indices: Vec<usize>,
+    /// The staged rows, with a value for each column of `table_source`.
+    rows: Vec<Vec<Datum>>,
+}
+
+impl CopyInToTable {
+    fn check_row(&self, row: &Row) -> Result<()> {
+        let msg = match row.len().cmp(&self.col_indices.len()) {
+            std::cmp::Ordering::Equal => return Ok(()),
+            std::cmp::Ordering::Less => {
+                let column = &self.table_source.columns[self.col_indices[row.len()]];
+                format!("missing data for column \"{}\"", column.name)
+            }
+            std::cmp::Ordering::Greater => "extra data after last expected column".to_string(),
+        };
+        Err(ErrorCode::InvalidInputSyntax(msg).into())
+    }
+
+    /// Casts the text of each copied value to the column type. The columns not copied are
+    /// `NULL`s.
+    fn cast_row(&self, row: &Row) -> Result<Vec<Datum>> {
+        let columns = &self.table_source.columns;
+        let mut datums = vec![None; columns.len()];
+        for (value, &idx) in row.values().iter().zip_eq(&self.col_indices) {
+            if let Some(value) = value {
+                let text = Literal::new(Some(ScalarImpl::Utf8(value.clone())), DataType::Varchar);
+                datums[idx] = ExprImpl::from(text)
+                    .cast_explicit(columns[idx].data_type.clone())?
+                    .eval_row_const()?;
+            }
+        }
+        Ok(datums)
+    }
+
+    /// Inserts the staged rows at once.
+    async fn insert_rows(&mut self) -> Result<()> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let plan = self.plan_insert()?;
+        execute_dml_plan(self.session.clone(), plan).await?;
+        Ok(())
+    }
+
+    /// Plans the insert of the staged rows with a `VALUES` of their casted values.
+    fn plan_insert(&mut self) -> Result<PlanNode> {
+        let rows = std::mem::take(&mut self.rows);
+        let bound = self.bind_insert(rows);
+        let context = OptimizerContext::new(self.session.clone());
+        let root = Planner::new(context.into()).plan(bound)?;
+        Ok(root.gen_batch_query_plan()?.to_batch_prost())
+    }
+
+    fn bind_insert(&self, rows: Vec<Vec<Datum>>) -> BoundStatement {
+        let columns = &self.table_source.columns;
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .zip_eq(columns)
+                    .map(|(datum, c)| Literal::new(datum, c.data_type.clone()).into())
+                    .collect_vec()
+            })
+            .collect_vec();
+        let values = BoundValues {
+            rows,
+            schema: Schema::new(columns.iter().map(Field::from).collect()),
+        };
+        let insert = BoundInsert {
+            table_source: self.table_source.clone(),
+            source: BoundQuery {
+                body: BoundSetExpr::Values(values.into()),
+                order: vec![],
+                limit: None,
+                offset: None,
+                extra_order_exprs: vec![],
+            },
+            cast_exprs: vec![],
+        };
+        BoundStatement::Insert(insert.into())
+    }
+}
+
+#[async_trait::async_trait]
+impl CopyInSink for CopyInToTable {
+    async fn write(&mut self, rows: Vec<Row>) -> std::result::Result<(), BoxedError> {
+        for row in rows {
+            self.check_row(&row)?;
+            let datums = self.cast_row(&row)?;
+            self.rows.push(datums);
+        }
+        Ok(())
+    }
+
+    async fn finish(mut self: Box<Self>) -> std::result::Result<i32, BoxedError> {
+        let rows_cnt = self.rows.len() as i32;
+        self.insert_rows().await?;
+        implicit_flush_for_write(&self.session, StatementType::COPY).await?;
+        Ok(rows_cnt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgwire::copy::{CopyFormat, CopyOptions};
+    use pgwire::pg_response::{CopyInSink, CopyTransfer};
+    use pgwire::pg_server::Session;
+    use pgwire::types::Row;
+    use risingwave_common::types::ScalarImpl;
+    use risingwave_pb::batch_plan::plan_node::NodeBody;
+    use risingwave_pb::batch_plan::{PlanNode, ValuesNode};
+    use risingwave_pb::user::grant_privilege::Privilege;
+    use risingwave_sqlparser::ast::{Ident, ObjectName};
+
+    use super::CopyInToTable;
+    use crate::binder::Binder;
+    use crate::handler::query::QUERY_MODE;
+    use crate::test_utils::LocalFrontend;
+
+    fn find_values(plan: &PlanNode) -> Option<&ValuesNode> {
+        match plan.node_body.as_ref().unwrap() {
+            NodeBody::Values(values) => Some(values),
+            _ => plan.children.iter().find_map(find_values),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_copy_from_stdin_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar, v3 double);")
+            .await
+            .unwrap();
+
+        let mut res = frontend
+            .run_sql("copy t (v3, v1) from stdin (format csv, delimiter '|', header)")
+            .await
+            .unwrap();
+        let names = res
+            .get_row_desc()
+            .iter()
+            .map(|field| field.get_name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["v3", "v1"]);
+        let mut sink = match res.take_copy() {
+            Some(CopyTransfer::In { options, sink }) => {
+                assert_eq!(
+                    options,
+                    CopyOptions {
+                        delimiter: b'|',
+                        header: true,
+                        ..CopyOptions::new(CopyFormat::Csv)
+                    }
+                );
+                sink
+            }
+            _ => panic!("expect copy in"),
+        };
+        // Each row must have a value for each copied column.
+        assert!(sink
+            .write(vec![Row::new(vec![Some("1.5".to_string())])])
+            .await
+            .is_err());
+
+        for sql in [
+            "copy t (v4) from stdin",
+            "copy t (v1, v1) from stdin",
+            "copy t from stdin (format binary)",
+            "copy t from stdin (header)",
+            "copy t from stdin (delimiter '\\')",
+        ] {
+            assert!(frontend.run_sql(sql).await.is_err(), "{}", sql);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_copy_in_to_table() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar, v3 double);")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let table_source = Binder::new(&session)
+            .bind_table_source(ObjectName(vec![Ident::new("t")]), Privilege::Insert)
+            .unwrap();
+        let position = |name: &str| {
+            table_source
+                .columns
+                .iter()
+                .position(|c| c.name == name)
+                .unwrap()
+        };
+        let (v1, v2, v3) = (position("v1"), position("v2"), position("v3"));
+        let mut sink = CopyInToTable {
+            session,
+            col_indices: vec![v3, v1],
+            table_source,
+            rows: vec![],
+        };
+
+        sink.write(vec![
+            Row::new(vec![Some("1.5".to_string()), Some("1".to_string())]),
+            Row::new(vec![None, Some("2".to_string())]),
+        ])
+        .await
+        .unwrap();
+        // A bad value fails the copy, and the rows are not inserted until it is done.
+        assert!(sink
+            .write(vec![Row::new(vec![
+                Some("x".to_string()),
+                Some("3".to_string())
+            ])])
+            .await
+            .is_err());
+
+        // The valid rows are casted to the column types and staged.
+        assert_eq!(sink.rows.len(), 2);
+        assert_eq!(sink.rows[0][v1], Some(ScalarImpl::Int32(1)));
+        assert_eq!(sink.rows[0][v2], None);
+        assert_eq!(sink.rows[0][v3], Some(ScalarImpl::Float64(1.5f64.into())));
+        assert_eq!(sink.rows[1][v1], Some(ScalarImpl::Int32(2)));
+        assert_eq!(sink.rows[1][v3], None);
+
+        // The staged rows reaching `finish` are inserted by a single plan.
+        let plan = sink.plan_insert().unwrap();
+        assert!(sink.rows.is_empty());
+        assert_eq!(find_values(&plan).unwrap().tuples.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_copy_to_stdout_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        // The query of constants is executed in the frontend.
+        session.set_config(QUERY_MODE, "local");
+
+        let mut res = session
+            .run_statement("copy (values (1, 'a'), (2, null)) to stdout (format csv)")
+            .await
+            .unwrap();
+        match res.take_copy() {
+            Some(CopyTransfer::Out { options }) => {
+                assert_eq!(options, CopyOptions::new(CopyFormat::Csv))
+            }
+            _ => panic!("expect copy out"),
+        }
+        let rows = res
+            .iter()
+            .map(|row| row.values().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                vec![Some("1".to_string()), Some("a".to_string())],
+                vec![Some("2".to_string()), None],
+            ]
+        );
+    }
+}
@@ -13,11 +13,13 @@
 // limitations under the License.
 
 use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
 use risingwave_common::error::Result;
 use risingwave_sqlparser::ast::ObjectName;
 
 use crate::binder::Binder;
 use crate::catalog::CatalogError;
+use crate::handler::privilege::session_user;
 use crate::session::OptimizerContext;
 
 pub async fn handle_create_database(
@@ -44,8 +46,15 @@ pub async fn handle_create_database(
         }
     }
 
+    let user = session_user(&session)?;
+    if !user.is_supper && !user.can_create_db {
+        return Err(PermissionDenied("permission denied to create database".to_string()).into());
+    }
+
     let catalog_writer = session.env().catalog_writer();
-    catalog_writer.create_database(&database_name).await?;
+    catalog_writer
+        .create_database(&database_name, user.id)
+        .await?;
     Ok(PgResponse::empty_result(StatementType::CREATE_DATABASE))
 }
 
@@ -23,6 +23,7 @@ use risingwave_pb::catalog::Table as ProstTable;
 use risingwave_sqlparser::ast::{ObjectName, OrderByExpr};
 
 use crate::binder::Binder;
+use crate::handler::privilege::check_owner;
 use crate::optimizer::plan_node::{LogicalScan, StreamTableScan};
 use crate::optimizer::property::{FieldOrder, Order, RequiredDist};
 use crate::optimizer::{PlanRef, PlanRoot};
@@ -73,6 +74,7 @@ pub(crate) fn gen_create_index_plan(
         .read_guard()
         .get_table_by_name(session.database(), &schema_name, &table_name)?
         .clone();
+    check_owner(session, &[table.owner], "relation", &table_name)?;
 
     let table_desc = Rc::new(table.table_desc());
     let table_desc_map = table_desc
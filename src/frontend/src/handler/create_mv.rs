@@ -18,6 +18,7 @@ use risingwave_pb::catalog::Table as ProstTable;
 use risingwave_sqlparser::ast::{ObjectName, Query};
 
 use crate::binder::{Binder, BoundSetExpr};
+use crate::handler::privilege::check_schema_create_privilege;
 use crate::optimizer::property::RequiredDist;
 use crate::optimizer::PlanRef;
 use crate::planner::Planner;
@@ -32,6 +33,7 @@ pub fn gen_create_mv_plan(
     name: ObjectName,
 ) -> Result<(PlanRef, ProstTable)> {
     let (schema_name, table_name) = Binder::resolve_table_name(name)?;
+    check_schema_create_privilege(session, &schema_name)?;
     let (database_id, schema_id) = session
         .env()
         .catalog_reader()
@@ -39,10 +41,7 @@ pub fn gen_create_mv_plan(
         .check_relation_name_duplicated(session.database(), &schema_name, &table_name)?;
 
     let bound = {
-        let mut binder = Binder::new(
-            session.env().catalog_reader().read_guard(),
-            session.database().to_string(),
-        );
+        let mut binder = Binder::new(session);
         binder.bind_query(*query)?
     };
 
@@ -14,10 +14,12 @@
 
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::Result;
+use risingwave_pb::user::grant_privilege::{GrantDatabase, Privilege, Target};
 use risingwave_sqlparser::ast::ObjectName;
 
 use crate::binder::Binder;
 use crate::catalog::CatalogError;
+use crate::handler::privilege::check_privilege;
 use crate::session::OptimizerContext;
 
 pub async fn handle_create_schema(
@@ -29,7 +31,7 @@ pub async fn handle_create_schema(
     let (database_name, schema_name) =
         Binder::resolve_schema_name(session.database(), schema_name)?;
 
-    let db_id = {
+    let (db_id, db_owner) = {
         let catalog_reader = session.env().catalog_reader();
         let reader = catalog_reader.read_guard();
         if reader
@@ -46,11 +48,22 @@ pub async fn handle_create_schema(
                 Err(CatalogError::Duplicated("schema", schema_name).into())
             };
         }
-        reader.get_database_by_name(&database_name)?.id()
+        let database = reader.get_database_by_name(&database_name)?;
+        (database.id(), database.owner())
     };
 
+    check_privilege(
+        &session,
+        db_owner,
+        &Target::GrantDatabase(GrantDatabase { database_id: db_id }),
+        Privilege::Create,
+        &database_name,
+    )?;
+
     let catalog_writer = session.env().catalog_writer();
-    catalog_writer.create_schema(db_id, &schema_name).await?;
+    catalog_writer
+        .create_schema(db_id, &schema_name, session.user_id())
+        .await?;
     Ok(PgResponse::empty_result(StatementType::CREATE_SCHEMA))
 }
 
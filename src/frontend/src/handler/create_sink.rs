@@ -0,0 +1,216 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::Result;
+use risingwave_pb::catalog::Sink as ProstSink;
+use risingwave_pb::user::grant_privilege::Privilege;
+use risingwave_sqlparser::ast::CreateSinkStatement;
+
+use super::create_source::handle_source_with_properties;
+use crate::binder::Binder;
+use crate::catalog::CatalogError;
+use crate::handler::privilege::{check_privilege, check_schema_create_privilege};
+use crate::optimizer::plan_node::{LogicalScan, StreamSink, StreamTableScan};
+use crate::optimizer::property::{Order, RequiredDist};
+use crate::optimizer::PlanRef;
+use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
+use crate::stream_fragmenter::StreamFragmenter;
+use crate::user::user_privilege::relation_object;
+
+pub(crate) fn gen_sink_plan(
+    session: &SessionImpl,
+    context: OptimizerContextRef,
+    stmt: CreateSinkStatement,
+) -> Result<(PlanRef, ProstSink)> {
+    let properties = handle_source_with_properties(stmt.with_properties.0)?;
+
+    let (schema_name, mv_name) = Binder::resolve_table_name(stmt.materialized_view)?;
+    let (table, mv_object, mv_owner) = {
+        let catalog_reader = session.env().catalog_reader();
+        let reader = catalog_reader.read_guard();
+        let table = reader
+            .get_table_by_name(session.database(), &schema_name, &mv_name)?
+            .clone();
+        let database_id = reader.get_database_by_name(session.database())?.id();
+        let schema = reader.get_schema_by_name(session.database(), &schema_name)?;
+        let (object, owner) = relation_object(database_id, schema, &mv_name)
+            .ok_or_else(|| CatalogError::NotFound("materialized view", mv_name.clone()))?;
+        (table, object, owner)
+    };
+    check_privilege(session, mv_owner, &mv_object, Privilege::Select, &mv_name)?;
+
+    let (sink_schema_name, sink_name) = Binder::resolve_table_name(stmt.sink_name)?;
+    check_schema_create_privilege(session, &sink_schema_name)?;
+    let (database_id, schema_id) = session
+        .env()
+        .catalog_reader()
+        .read_guard()
+        .check_relation_name_duplicated(session.database(), &sink_schema_name, &sink_name)?;
+
+    // Scan the visible columns of the materialized view. Hidden pk columns are kept so that an
+    // upsert sink can identify each row.
+    let required_col_idx = table
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(idx, col)| !col.is_hidden || table.pks.contains(idx))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    let scan_node = StreamTableScan::new(LogicalScan::new(
+        mv_name,
+        required_col_idx,
+        Rc::new(table.table_desc()),
+        // indexes are only used by DeltaJoin rule, and we don't need to provide them here.
+        vec![],
+        context,
+    ));
+    // All actors of a file sink would append to the same file, so it is written by a single actor.
+    let input = if properties.get("connector").map(|s| s.as_str()) == Some("file") {
+        RequiredDist::single().enforce_if_not_satisfies(scan_node.into(), Order::any())?
+    } else {
+        scan_node.into()
+    };
+    let sink_node = StreamSink::new(input, table.id(), properties.clone());
+
+    let sink = ProstSink {
+        id: 0,
+        schema_id,
+        database_id,
+        name: sink_name,
+        associated_table_id: table.id().table_id(),
+        properties,
+        owner: session.user_id(),
+    };
+
+    Ok((sink_node.into(), sink))
+}
+
+pub async fn handle_create_sink(
+    context: OptimizerContext,
+    stmt: CreateSinkStatement,
+) -> Result<PgResponse> {
+    let session = context.session_ctx.clone();
+
+    if stmt.if_not_exists {
+        let (schema_name, sink_name) = Binder::resolve_table_name(stmt.sink_name.clone())?;
+        if session
+            .env()
+            .catalog_reader()
+            .read_guard()
+            .get_sink_by_name(session.database(), &schema_name, &sink_name)
+            .is_ok()
+        {
+            return Ok(PgResponse::empty_result_with_notice(
+                StatementType::CREATE_SINK,
+                format!("sink {} exists, skipping", sink_name),
+            ));
+        }
+    }
+
+    let (graph, sink) = {
+        let (plan, sink) = gen_sink_plan(&session, context.into(), stmt)?;
+        let plan = plan.to_stream_prost();
+        let graph = StreamFragmenter::build_graph(plan);
+
+        (graph, sink)
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.create_sink(sink, graph).await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_SINK))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+    use risingwave_sqlparser::ast::Statement;
+    use risingwave_sqlparser::parser::Parser;
+
+    use super::*;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_sink_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 smallint, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1, v2 from t;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create sink snk from mv with (connector = 'file', 'file.path' = '/tmp/snk');")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader();
+        let reader = catalog_reader.read_guard();
+        let mv = reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "mv")
+            .unwrap();
+        let sink = reader
+            .get_sink_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "snk")
+            .unwrap();
+        assert_eq!(sink.associated_table_id, mv.id());
+        assert_eq!(sink.properties["connector"], "file");
+        assert_eq!(sink.properties["file.path"], "/tmp/snk");
+
+        // The sink name conflicts with existing relations.
+        assert!(frontend
+            .run_sql("create sink mv from mv with (connector = 'file', 'file.path' = '/tmp/snk');")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_plan_is_singleton() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 smallint, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1, v2 from t;")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let sink_plan = |sql: &str| {
+            let stmt = match Parser::parse_sql(sql).unwrap().pop().unwrap() {
+                Statement::CreateSink { stmt } => stmt,
+                _ => unreachable!(),
+            };
+            let context = OptimizerContext::new(session.clone()).into();
+            let (plan, _) = gen_sink_plan(&session, context, stmt).unwrap();
+            plan.explain_to_string().unwrap()
+        };
+
+        // The file is written by a single actor.
+        let plan = sink_plan(
+            "create sink snk from mv with (connector = 'file', 'file.path' = '/tmp/snk');",
+        );
+        assert!(plan.contains("StreamExchange { dist: Single }"), "{}", plan);
+
+        let plan = sink_plan("create sink snk from mv with (connector = 'kafka', 'kafka.brokers' = 'localhost:9092', 'kafka.topic' = 'snk');");
+        assert!(!plan.contains("StreamExchange"), "{}", plan);
+    }
+}
@@ -16,19 +16,24 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
-use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::catalog::Field;
+use risingwave_common::error::ErrorCode::{self, ProtocolError};
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::DataType;
 use risingwave_pb::catalog::source::Info;
-use risingwave_pb::catalog::{Source as ProstSource, StreamSourceInfo};
+use risingwave_pb::catalog::{Source as ProstSource, StreamSourceInfo, WatermarkDesc};
 use risingwave_pb::plan_common::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
-use risingwave_source::ProtobufParser;
+use risingwave_source::{ProtobufParser, JSON_PAYLOAD_COLUMN_KEY};
 use risingwave_sqlparser::ast::{
-    CreateSourceStatement, ObjectName, ProtobufSchema, SourceSchema, SqlOption, Value,
+    CreateSourceStatement, ObjectName, ProtobufSchema, SourceSchema, SourceWatermark, SqlOption,
+    Value,
 };
 
 use super::create_table::{bind_sql_columns, gen_materialized_source_plan};
 use crate::binder::Binder;
 use crate::catalog::column_catalog::ColumnCatalog;
+use crate::expr::Expr;
+use crate::handler::privilege::check_schema_create_privilege;
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::stream_fragmenter::StreamFragmenter;
 
@@ -38,6 +43,7 @@ pub(crate) fn make_prost_source(
     source_info: Info,
 ) -> Result<ProstSource> {
     let (schema_name, name) = Binder::resolve_table_name(name)?;
+    check_schema_create_privilege(session, &schema_name)?;
 
     let (database_id, schema_id) = session
         .env()
@@ -51,6 +57,8 @@ pub(crate) fn make_prost_source(
         database_id,
         name,
         info: Some(source_info),
+        owner: session.user_id(),
+        version: 0,
     })
 }
 
@@ -68,7 +76,9 @@ fn extract_protobuf_table_schema(schema: &ProtobufSchema) -> Result<Vec<ProstCol
         .collect_vec())
 }
 
-fn handle_source_with_properties(options: Vec<SqlOption>) -> Result<HashMap<String, String>> {
+pub(crate) fn handle_source_with_properties(
+    options: Vec<SqlOption>,
+) -> Result<HashMap<String, String>> {
     options
         .into_iter()
         .map(|x| match x.value {
@@ -80,12 +90,113 @@ fn handle_source_with_properties(options: Vec<SqlOption>) -> Result<HashMap<Stri
         .collect()
 }
 
+/// Bind the `WATERMARK FOR column AS expr` clauses on the columns of the source. The expression
+/// must return the type of the column.
+fn bind_source_watermark(
+    session: &SessionImpl,
+    name: String,
+    source_watermarks: Vec<SourceWatermark>,
+    column_catalogs: &[ProstColumnCatalog],
+) -> Result<Vec<WatermarkDesc>> {
+    let columns = column_catalogs
+        .iter()
+        .cloned()
+        .map(ColumnCatalog::from)
+        .collect_vec();
+
+    let mut watermark_descs: Vec<WatermarkDesc> = vec![];
+    for source_watermark in source_watermarks {
+        let column_name = source_watermark.column.value;
+        let watermark_idx = columns
+            .iter()
+            .position(|c| c.name() == column_name)
+            .ok_or_else(|| {
+                ErrorCode::BindError(format!(
+                    "column \"{}\" of the watermark does not exist",
+                    column_name
+                ))
+            })?;
+        if watermark_descs
+            .iter()
+            .any(|desc| desc.watermark_idx as usize == watermark_idx)
+        {
+            return Err(ErrorCode::BindError(format!(
+                "multiple watermarks on column \"{}\"",
+                column_name
+            ))
+            .into());
+        }
+
+        let expr = Binder::new(session).bind_expr_on_columns(
+            source_watermark.expr,
+            name.clone(),
+            columns.iter().map(|c| {
+                (
+                    c.is_hidden(),
+                    Field::with_name(c.data_type().clone(), c.name()),
+                )
+            }),
+        )?;
+        let column_type = columns[watermark_idx].data_type();
+        if &expr.return_type() != column_type {
+            return Err(ErrorCode::BindError(format!(
+                "the watermark expression of column \"{}\" must return {:?}, but returns {:?}",
+                column_name,
+                column_type,
+                expr.return_type()
+            ))
+            .into());
+        }
+
+        watermark_descs.push(WatermarkDesc {
+            watermark_idx: watermark_idx as u32,
+            expr: Some(expr.to_expr_proto()),
+        });
+    }
+    Ok(watermark_descs)
+}
+
+/// Checks that the column named by the `json.payload.column` option of a JSON source, which keeps
+/// the whole payload, exists and is of type `jsonb`.
+fn check_json_payload_column(source: &StreamSourceInfo) -> Result<()> {
+    let Some(column_name) = source.properties.get(JSON_PAYLOAD_COLUMN_KEY) else {
+        return Ok(());
+    };
+    if source.row_format != RowFormatType::Json as i32 {
+        return Err(RwError::from(ProtocolError(format!(
+            "'{}' is only supported by ROW FORMAT JSON",
+            JSON_PAYLOAD_COLUMN_KEY
+        ))));
+    }
+    let column = source
+        .columns
+        .iter()
+        .filter_map(|c| c.column_desc.as_ref())
+        .find(|c| &c.name == column_name)
+        .ok_or_else(|| {
+            ErrorCode::BindError(format!(
+                "column \"{}\" of '{}' does not exist",
+                column_name, JSON_PAYLOAD_COLUMN_KEY
+            ))
+        })?;
+    let column_type = DataType::from(column.get_column_type()?);
+    if column_type != DataType::Jsonb {
+        return Err(ErrorCode::BindError(format!(
+            "column \"{}\" of '{}' must be of type jsonb, but is {:?}",
+            column_name, JSON_PAYLOAD_COLUMN_KEY, column_type
+        ))
+        .into());
+    }
+    Ok(())
+}
+
 pub async fn handle_create_source(
     context: OptimizerContext,
     is_materialized: bool,
     stmt: CreateSourceStatement,
 ) -> Result<PgResponse> {
-    let source = match &stmt.source_schema {
+    let session = context.session_ctx.clone();
+    let mut source = match &stmt.source_schema {
         SourceSchema::Protobuf(protobuf_schema) => {
             let mut columns = vec![ColumnCatalog::row_id_column().to_protobuf()];
             columns.extend(extract_protobuf_table_schema(protobuf_schema)?.into_iter());
@@ -96,6 +207,7 @@ pub async fn handle_create_source(
                 row_id_index: 0,
                 columns,
                 pk_column_ids: vec![0],
+                watermark_descs: vec![],
             }
         }
         SourceSchema::Json => StreamSourceInfo {
@@ -105,10 +217,17 @@ pub async fn handle_create_source(
             row_id_index: 0,
             columns: bind_sql_columns(stmt.columns)?,
             pk_column_ids: vec![0],
+            watermark_descs: vec![],
         },
     };
+    check_json_payload_column(&source)?;
+    source.watermark_descs = bind_source_watermark(
+        &session,
+        stmt.source_name.to_string(),
+        stmt.source_watermarks,
+        &source.columns,
+    )?;
 
-    let session = context.session_ctx.clone();
     let source = make_prost_source(&session, stmt.source_name, Info::StreamSource(source))?;
     let catalog_writer = session.env().catalog_writer();
     if is_materialized {
@@ -193,4 +312,42 @@ pub mod tests {
         };
         assert_eq!(columns, expected_columns);
     }
+
+    #[tokio::test]
+    async fn test_create_source_with_watermark() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let sql = "CREATE SOURCE t (v1 INT, ts TIMESTAMP, WATERMARK FOR ts AS ts - INTERVAL '5' SECOND) ROW FORMAT JSON";
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader();
+        let source = catalog_reader
+            .read_guard()
+            .get_source_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "t")
+            .unwrap()
+            .clone();
+        // The hidden row id column comes first.
+        assert_eq!(source.watermark_descs.len(), 1);
+        assert_eq!(source.watermark_descs[0].watermark_idx, 2);
+
+        let sql = "CREATE SOURCE t2 (ts TIMESTAMP, WATERMARK FOR ts AS 1) ROW FORMAT JSON";
+        assert!(frontend.run_sql(sql).await.is_err());
+        let sql = "CREATE SOURCE t3 (ts TIMESTAMP, WATERMARK FOR v AS ts) ROW FORMAT JSON";
+        assert!(frontend.run_sql(sql).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_source_with_json_payload_column() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let sql = "CREATE SOURCE t (id INT, payload JSONB) WITH ('json.payload.column' = 'payload') ROW FORMAT JSON";
+        frontend.run_sql(sql).await.unwrap();
+
+        let sql =
+            "CREATE SOURCE t2 (id INT) WITH ('json.payload.column' = 'payload') ROW FORMAT JSON";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert!(err.to_string().contains("does not exist"), "{}", err);
+        let sql = "CREATE SOURCE t3 (id INT) WITH ('json.payload.column' = 'id') ROW FORMAT JSON";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert!(err.to_string().contains("must be of type jsonb"), "{}", err);
+    }
 }
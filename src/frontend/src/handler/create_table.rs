@@ -58,6 +58,7 @@ pub fn bind_sql_columns(columns: Vec<ColumnDef>) -> Result<Vec<ColumnCatalog>> {
                 name: column.name.value,
                 field_descs,
                 type_name: "".to_string(),
+                default_value: None,
             });
         }
         column_descs
@@ -12,12 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use futures_async_stream::for_await;
 use pgwire::pg_response::{PgResponse, StatementType};
+use pgwire::types::Row;
 use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::PlanNode;
 use risingwave_sqlparser::ast::Statement;
 
-use crate::binder::Binder;
+use crate::binder::{Binder, ParameterValues};
 use crate::handler::util::{to_pg_field, to_pg_rows};
 use crate::planner::Planner;
 use crate::scheduler::{ExecutionContext, ExecutionContextRef};
@@ -28,15 +32,16 @@ use crate::session::{OptimizerContext, SessionImpl};
 /// be able to see the write.
 pub static IMPLICIT_FLUSH: &str = "RW_IMPLICIT_FLUSH";
 
-pub async fn handle_dml(context: OptimizerContext, stmt: Statement) -> Result<PgResponse> {
+pub async fn handle_dml(
+    context: OptimizerContext,
+    stmt: Statement,
+    params: ParameterValues,
+) -> Result<PgResponse> {
     let stmt_type = to_statement_type(&stmt);
     let session = context.session_ctx.clone();
 
     let bound = {
-        let mut binder = Binder::new(
-            session.env().catalog_reader().read_guard(),
-            session.database().to_string(),
-        );
+        let mut binder = Binder::new_with_params(&session, params);
         binder.bind(stmt)?
     };
 
@@ -49,17 +54,7 @@ pub async fn handle_dml(context: OptimizerContext, stmt: Statement) -> Result<Pg
         (plan.to_batch_prost(), pg_descs)
     };
 
-    let execution_context: ExecutionContextRef = ExecutionContext::new(session.clone()).into();
-    let query_manager = execution_context.session().env().query_manager().clone();
-
-    let mut rows = vec![];
-    #[for_await]
-    for chunk in query_manager
-        .schedule_single(execution_context, plan)
-        .await?
-    {
-        rows.extend(to_pg_rows(chunk?));
-    }
+    let rows = execute_dml_plan(session.clone(), plan).await?;
 
     let rows_count = match stmt_type {
         // TODO(renjie): We need a better solution for this.
@@ -74,19 +69,49 @@ pub async fn handle_dml(context: OptimizerContext, stmt: Statement) -> Result<Pg
         _ => unreachable!(),
     };
 
-    // Implicitly flush the writes.
+    implicit_flush_for_write(&session, stmt_type).await?;
+
+    Ok(PgResponse::new(stmt_type, rows_count, rows, pg_descs))
+}
+
+/// Schedules the batch plan of a DML statement, and returns its output rows.
+pub(super) async fn execute_dml_plan(
+    session: Arc<SessionImpl>,
+    plan: PlanNode,
+) -> Result<Vec<Row>> {
+    let execution_context: ExecutionContextRef = ExecutionContext::new(session).into();
+    let query_manager = execution_context.session().env().query_manager().clone();
+
+    let mut rows = vec![];
+    #[for_await]
+    for chunk in query_manager
+        .schedule_single(execution_context, plan)
+        .await?
+    {
+        rows.extend(to_pg_rows(chunk?));
+    }
+    Ok(rows)
+}
+
+/// Flushes the writes if `RW_IMPLICIT_FLUSH` is on.
+pub(super) async fn implicit_flush_for_write(
+    session: &SessionImpl,
+    stmt_type: StatementType,
+) -> Result<()> {
     if let Some(flag) = session.get_config(IMPLICIT_FLUSH) {
         if flag.is_set(false) {
-            flush_for_write(&session, stmt_type).await?;
+            flush_for_write(session, stmt_type).await?;
         }
     }
-
-    Ok(PgResponse::new(stmt_type, rows_count, rows, pg_descs))
+    Ok(())
 }
 
 async fn flush_for_write(session: &SessionImpl, stmt_type: StatementType) -> Result<()> {
     match stmt_type {
-        StatementType::INSERT | StatementType::DELETE | StatementType::UPDATE => {
+        StatementType::INSERT
+        | StatementType::DELETE
+        | StatementType::UPDATE
+        | StatementType::COPY => {
             let client = session.env().meta_client();
             client.flush().await
         }
@@ -18,6 +18,7 @@ use risingwave_sqlparser::ast::{DropMode, ObjectName};
 
 use crate::binder::Binder;
 use crate::catalog::CatalogError;
+use crate::handler::privilege::check_owner;
 use crate::session::OptimizerContext;
 
 pub async fn handle_drop_database(
@@ -53,6 +54,7 @@ pub async fn handle_drop_database(
             }
         }
     };
+    check_owner(&session, &[database.owner()], "database", &database_name)?;
     let database_id = {
         // If the mode is `Restrict` or `None`, the `database` need to be empty.
         if !database.is_empty() {
@@ -18,6 +18,7 @@ use risingwave_sqlparser::ast::ObjectName;
 
 use crate::binder::Binder;
 use crate::handler::drop_table::check_source;
+use crate::handler::privilege::check_owner;
 use crate::session::OptimizerContext;
 
 pub async fn handle_drop_index(
@@ -48,6 +49,11 @@ pub async fn handle_drop_index(
                 "Use `DROP MATERIALIZED VIEW` to drop a materialized view.".to_owned(),
             )));
         }
+
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)?
+            .owner();
+        check_owner(&session, &[table.owner, schema_owner], "index", &table_name)?;
         table.id()
     };
 
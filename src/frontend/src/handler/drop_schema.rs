@@ -18,6 +18,7 @@ use risingwave_sqlparser::ast::{DropMode, ObjectName};
 
 use crate::binder::Binder;
 use crate::catalog::CatalogError;
+use crate::handler::privilege::check_owner;
 use crate::session::OptimizerContext;
 
 pub async fn handle_drop_schema(
@@ -31,10 +32,13 @@ pub async fn handle_drop_schema(
     let (database_name, schema_name) =
         Binder::resolve_schema_name(session.database(), schema_name)?;
 
-    let schema = {
+    let (schema, database_owner) = {
         let reader = catalog_reader.read_guard();
         match reader.get_schema_by_name(&database_name, &schema_name) {
-            Ok(schema) => schema.clone(),
+            Ok(schema) => (
+                schema.clone(),
+                reader.get_database_by_name(&database_name)?.owner(),
+            ),
             Err(err) => {
                 // If `if_exist` is true, not return error.
                 return if if_exist {
@@ -48,6 +52,12 @@ pub async fn handle_drop_schema(
             }
         }
     };
+    check_owner(
+        &session,
+        &[schema.owner(), database_owner],
+        "schema",
+        &schema_name,
+    )?;
     let schema_id = {
         // If the mode is `Restrict` or `None`, the `schema` need to be empty.
         if Some(DropMode::Restrict) == mode || None == mode {
@@ -0,0 +1,79 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::Result;
+use risingwave_sqlparser::ast::ObjectName;
+
+use crate::binder::Binder;
+use crate::handler::privilege::check_owner;
+use crate::session::OptimizerContext;
+
+pub async fn handle_drop_sink(
+    context: OptimizerContext,
+    sink_name: ObjectName,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, sink_name) = Binder::resolve_table_name(sink_name)?;
+
+    let sink_id = {
+        let catalog_reader = session.env().catalog_reader();
+        let reader = catalog_reader.read_guard();
+        let sink = reader.get_sink_by_name(session.database(), &schema_name, &sink_name)?;
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)?
+            .owner();
+        check_owner(&session, &[sink.owner, schema_owner], "sink", &sink_name)?;
+        sink.id
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.drop_sink(sink_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::DROP_SINK))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_sink_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 smallint);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1 from t;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create sink snk from mv with (connector = 'file', 'file.path' = '/tmp/snk');")
+            .await
+            .unwrap();
+        frontend.run_sql("drop sink snk;").await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader();
+        let sink = catalog_reader.read_guard().get_sink_by_name(
+            DEFAULT_DATABASE_NAME,
+            DEFAULT_SCHEMA_NAME,
+            "snk",
+        );
+        assert!(sink.is_err());
+    }
+}
@@ -18,6 +18,7 @@ use risingwave_pb::stream_plan::source_node::SourceType;
 use risingwave_sqlparser::ast::ObjectName;
 
 use crate::binder::Binder;
+use crate::handler::privilege::check_owner;
 use crate::session::OptimizerContext;
 
 pub async fn handle_drop_source(context: OptimizerContext, name: ObjectName) -> Result<PgResponse> {
@@ -25,10 +26,16 @@ pub async fn handle_drop_source(context: OptimizerContext, name: ObjectName) ->
     let (schema_name, source_name) = Binder::resolve_table_name(name)?;
 
     let catalog_reader = session.env().catalog_reader();
-    let source = catalog_reader
-        .read_guard()
-        .get_source_by_name(session.database(), &schema_name, &source_name)?
-        .clone();
+    let (source, schema_owner) = {
+        let reader = catalog_reader.read_guard();
+        let source = reader
+            .get_source_by_name(session.database(), &schema_name, &source_name)?
+            .clone();
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)?
+            .owner();
+        (source, schema_owner)
+    };
 
     match source.source_type {
         SourceType::Table => {
@@ -37,6 +44,12 @@ pub async fn handle_drop_source(context: OptimizerContext, name: ObjectName) ->
             )));
         }
         SourceType::Source => {
+            check_owner(
+                &session,
+                &[source.owner, schema_owner],
+                "source",
+                &source_name,
+            )?;
             let table = catalog_reader
                 .read_guard()
                 .get_table_by_name(session.database(), &schema_name, &source_name)
@@ -21,6 +21,7 @@ use risingwave_sqlparser::ast::ObjectName;
 
 use crate::binder::Binder;
 use crate::catalog::catalog_service::CatalogReader;
+use crate::handler::privilege::check_owner;
 use crate::session::{OptimizerContext, SessionImpl};
 
 pub fn check_source(
@@ -55,9 +56,16 @@ pub async fn handle_drop_table(
         let reader = catalog_reader.read_guard();
         let table = reader.get_table_by_name(session.database(), &schema_name, &table_name)?;
 
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)?
+            .owner();
+
         // If associated source is `None`, then it is a normal mview.
         match table.associated_source_id() {
-            Some(source_id) => (source_id, table.id()),
+            Some(source_id) => {
+                check_owner(&session, &[table.owner, schema_owner], "table", &table_name)?;
+                (source_id, table.id())
+            }
             None => {
                 return Err(RwError::from(ErrorCode::InvalidInputSyntax(
                     "Use `DROP MATERIALIZED VIEW` to drop a materialized view.".to_owned(),
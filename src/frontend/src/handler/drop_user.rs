@@ -0,0 +1,91 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::{InternalError, PermissionDenied};
+use risingwave_common::error::Result;
+use risingwave_sqlparser::ast::ObjectName;
+
+use crate::binder::Binder;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+pub async fn handle_drop_user(
+    context: OptimizerContext,
+    user_name: ObjectName,
+    if_exists: bool,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let user_name = Binder::resolve_user_name(user_name)?;
+
+    let user_id = {
+        let user_reader = session.env().user_info_reader();
+        let reader = user_reader.read_guard();
+        let session_user = reader
+            .get_user_by_name(session.user_name())
+            .ok_or_else(|| CatalogError::NotFound("user", session.user_name().to_string()))?;
+        if !session_user.is_supper {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        match reader.get_user_by_name(&user_name) {
+            Some(user) if user.id == session_user.id => {
+                return Err(InternalError("current user cannot be dropped".to_string()).into());
+            }
+            Some(user) => user.id,
+            None => {
+                return if if_exists {
+                    Ok(PgResponse::empty_result_with_notice(
+                        StatementType::DROP_USER,
+                        format!("NOTICE: user {} does not exist, skipping", user_name),
+                    ))
+                } else {
+                    Err(CatalogError::NotFound("user", user_name).into())
+                };
+            }
+        }
+    };
+
+    let user_info_writer = session.env().user_info_writer();
+    user_info_writer.drop_user(user_id).await?;
+    Ok(PgResponse::empty_result(StatementType::DROP_USER))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_user() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        let user_info_reader = session.env().user_info_reader();
+
+        frontend.run_sql("CREATE USER user1").await.unwrap();
+        assert!(user_info_reader
+            .read_guard()
+            .get_user_by_name("user1")
+            .is_some());
+
+        frontend.run_sql("DROP USER user1").await.unwrap();
+        assert!(user_info_reader
+            .read_guard()
+            .get_user_by_name("user1")
+            .is_none());
+
+        assert!(frontend.run_sql("DROP USER user1").await.is_err());
+        frontend.run_sql("DROP USER IF EXISTS user1").await.unwrap();
+        assert!(frontend.run_sql("DROP USER root").await.is_err());
+    }
+}
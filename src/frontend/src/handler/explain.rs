@@ -15,20 +15,29 @@
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
-use risingwave_common::error::Result;
+use risingwave_batch::executor::ExecutorStats;
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_sqlparser::ast::Statement;
 
 use super::create_mv::gen_create_mv_plan;
 use super::create_table::gen_create_table_plan;
 use crate::binder::Binder;
+use crate::optimizer::PlanRef;
 use crate::planner::Planner;
+use crate::scheduler::plan_fragmenter::{plan_node_identity, BatchPlanFragmenter};
+use crate::scheduler::{ExecutionContext, ExecutionContextRef};
 use crate::session::OptimizerContext;
 
-pub(super) fn handle_explain(
+pub(super) async fn handle_explain(
     context: OptimizerContext,
     stmt: Statement,
     _verbose: bool,
+    analyze: bool,
 ) -> Result<PgResponse> {
+    if analyze {
+        return handle_explain_analyze(context, stmt).await;
+    }
+
     let session = context.session_ctx.clone();
     // bind, plan, optimize, and serialize here
     let mut planner = Planner::new(context.into());
@@ -48,10 +57,7 @@ pub(super) fn handle_explain(
 
         stmt => {
             let bound = {
-                let mut binder = Binder::new(
-                    session.env().catalog_reader().read_guard(),
-                    session.database().to_string(),
-                );
+                let mut binder = Binder::new(&session);
                 binder.bind(stmt)?
             };
             let logical = planner.plan(bound)?;
@@ -66,7 +72,82 @@ pub(super) fn handle_explain(
         .map(|s| Row::new(vec![Some(s.into())]))
         .collect::<Vec<_>>();
 
-    Ok(PgResponse::new(
+    Ok(explain_response(rows))
+}
+
+/// Runs the query and explains its batch plan, where every plan node is annotated with the
+/// runtime statistics of its executors. The query is always executed in the distributed mode.
+async fn handle_explain_analyze(context: OptimizerContext, stmt: Statement) -> Result<PgResponse> {
+    if !matches!(stmt, Statement::Query(_)) {
+        return Err(ErrorCode::NotImplemented(
+            "EXPLAIN ANALYZE for statements other than queries".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+    let session = context.session_ctx.clone();
+
+    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
+    let (query, plan_lines) = {
+        let bound = {
+            let mut binder = Binder::new(&session);
+            binder.bind(stmt)?
+        };
+        let plan = Planner::new(context.into())
+            .plan(bound)?
+            .gen_batch_query_plan()?;
+
+        let mut plan_lines = vec![];
+        explain_with_identity(&plan, 0, &mut plan_lines);
+
+        let plan_fragmenter = BatchPlanFragmenter::new(session.env().worker_node_manager_ref());
+        (plan_fragmenter.split(plan)?, plan_lines)
+    };
+
+    let execution_context: ExecutionContextRef = ExecutionContext::new(session.clone()).into();
+    let query_manager = session.env().query_manager().clone();
+    let stats = query_manager
+        .schedule_with_stats(execution_context, query)
+        .await?;
+
+    let rows = plan_lines
+        .into_iter()
+        .map(|(line, identity)| {
+            let line = match stats.get(&identity) {
+                Some(stats) => format!("{} ({})", line, format_stats(stats)),
+                None => format!("{} (never executed)", line),
+            };
+            Row::new(vec![Some(line)])
+        })
+        .collect::<Vec<_>>();
+
+    Ok(explain_response(rows))
+}
+
+/// Explains the plan like [`PlanRef::explain`], along with the identity of the executors built from
+/// each plan node.
+fn explain_with_identity(plan: &PlanRef, level: usize, lines: &mut Vec<(String, String)>) {
+    lines.push((
+        format!("{}{}", " ".repeat(level * 2), plan),
+        plan_node_identity(plan.node_type(), plan.id()),
+    ));
+    for input in plan.inputs() {
+        explain_with_identity(&input, level + 1, lines);
+    }
+}
+
+fn format_stats(stats: &ExecutorStats) -> String {
+    format!(
+        "rows: {}, chunks: {}, time: {:.3}ms, max chunk size: {} bytes",
+        stats.rows,
+        stats.chunks,
+        stats.elapsed.as_secs_f64() * 1000.0,
+        stats.max_chunk_bytes
+    )
+}
+
+fn explain_response(rows: Vec<Row>) -> PgResponse {
+    PgResponse::new(
         StatementType::EXPLAIN,
         rows.len() as i32,
         rows,
@@ -74,5 +155,57 @@ pub(super) fn handle_explain(
             "QUERY PLAN".to_owned(),
             TypeOid::Varchar,
         )],
-    ))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::explain_with_identity;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_explain_with_identity() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+
+        let plan = frontend
+            .to_batch_plan("select v1 from t where v2 > 1")
+            .await
+            .unwrap();
+        let mut lines = vec![];
+        explain_with_identity(&plan, 0, &mut lines);
+
+        let explained = plan.explain_to_string().unwrap();
+        assert_eq!(
+            lines
+                .iter()
+                .map(|(line, _)| line.as_str())
+                .collect::<Vec<_>>(),
+            explained.lines().collect::<Vec<_>>()
+        );
+        let identities = lines
+            .iter()
+            .map(|(_, identity)| identity.as_str())
+            .collect::<HashSet<_>>();
+        assert_eq!(identities.len(), lines.len());
+        assert!(identities
+            .iter()
+            .all(|identity| identity.starts_with("Batch")));
+    }
+
+    #[tokio::test]
+    async fn test_explain_analyze_dml() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int);").await.unwrap();
+        let err = frontend
+            .run_sql("explain analyze insert into t values (1);")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("EXPLAIN ANALYZE"));
+    }
 }
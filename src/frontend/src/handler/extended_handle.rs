@@ -0,0 +1,189 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_sqlparser::ast::{ShowObject, Statement};
+
+use super::util::to_pg_field;
+use crate::binder::{Binder, ParameterValues};
+use crate::planner::Planner;
+use crate::session::{OptimizerContext, SessionImpl};
+
+/// A statement parsed by a `Parse` message of the extended query protocol, with the types of its
+/// `$n` parameters and its output columns resolved.
+#[derive(Clone)]
+pub struct PrepareStatement {
+    /// `None` for an empty query string.
+    statement: Option<Statement>,
+    param_types: Vec<DataType>,
+    row_description: Vec<PgFieldDescriptor>,
+}
+
+/// A prepared statement with the values of its `$n` parameters bound by a `Bind` message.
+#[derive(Clone)]
+pub struct Portal {
+    statement: Option<Statement>,
+    params: ParameterValues,
+    row_description: Vec<PgFieldDescriptor>,
+}
+
+impl PrepareStatement {
+    pub fn param_types(&self) -> &[DataType] {
+        &self.param_types
+    }
+
+    pub fn row_description(&self) -> &[PgFieldDescriptor] {
+        &self.row_description
+    }
+
+    /// Bind parameter values in text format to create a portal.
+    pub fn bind(self, param_values: Vec<Option<String>>) -> Portal {
+        Portal {
+            statement: self.statement,
+            params: ParameterValues {
+                types: self.param_types,
+                values: param_values,
+            },
+            row_description: self.row_description,
+        }
+    }
+}
+
+impl Portal {
+    pub fn row_description(&self) -> &[PgFieldDescriptor] {
+        &self.row_description
+    }
+}
+
+/// Resolve the parameter types and output columns of `statement`. Only queries and DML
+/// statements take parameters, whose unspecified types in `param_types` are inferred by the
+/// binder.
+pub fn handle_parse(
+    session: Arc<SessionImpl>,
+    statement: Option<Statement>,
+    param_types: Vec<Option<DataType>>,
+) -> Result<PrepareStatement> {
+    let (param_types, row_description) = match &statement {
+        Some(
+            stmt @ (Statement::Query(_)
+            | Statement::Insert { .. }
+            | Statement::Delete { .. }
+            | Statement::Update { .. }),
+        ) => {
+            let (bound, param_types) = {
+                let mut binder = Binder::new_with_param_types(&session, param_types);
+                let bound = binder.bind(stmt.clone())?;
+                (bound, binder.export_param_types())
+            };
+            let row_description = match stmt {
+                Statement::Query(_) => {
+                    let context = OptimizerContext::new(session.clone());
+                    let root = Planner::new(context.into()).plan(bound)?;
+                    root.schema().fields().iter().map(to_pg_field).collect()
+                }
+                _ => vec![],
+            };
+            (param_types, row_description)
+        }
+        Some(stmt) => (vec![], infer_row_description(stmt)),
+        None => (vec![], vec![]),
+    };
+
+    Ok(PrepareStatement {
+        statement,
+        param_types,
+        row_description,
+    })
+}
+
+/// Execute a portal. The `$n` parameters are substituted with their values when binding.
+pub async fn handle_execute(session: Arc<SessionImpl>, portal: Portal) -> Result<PgResponse> {
+    match portal.statement {
+        Some(stmt) => super::handle(session, stmt, portal.params).await,
+        None => Ok(PgResponse::empty_result(StatementType::EMPTY)),
+    }
+}
+
+/// The output columns of statements other than queries, which are known without binding.
+fn infer_row_description(stmt: &Statement) -> Vec<PgFieldDescriptor> {
+    match stmt {
+        Statement::Explain { .. } => vec![PgFieldDescriptor::new(
+            "QUERY PLAN".to_owned(),
+            TypeOid::Varchar,
+        )],
+        Statement::Describe { .. } | Statement::ShowObjects(ShowObject::Columns { .. }) => vec![
+            PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
+        ],
+        Statement::ShowObjects(_) => {
+            vec![PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar)]
+        }
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::DataType;
+    use risingwave_sqlparser::parser::Parser;
+
+    use super::*;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_prepare_and_execute() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar);")
+            .await
+            .unwrap();
+        let session = frontend.session_ref();
+
+        let parse = |sql: &str, param_types| {
+            let statement = Parser::parse_sql(sql).unwrap().pop();
+            handle_parse(session.clone(), statement, param_types).unwrap()
+        };
+
+        // Parameter types are inferred from the compared columns and the inserted columns.
+        let prepared = parse("select v2 from t where v1 = $1", vec![]);
+        assert_eq!(prepared.param_types(), &[DataType::Int32]);
+        assert_eq!(prepared.row_description().len(), 1);
+        assert_eq!(prepared.row_description()[0].get_name(), "v2");
+
+        let prepared = parse("insert into t values ($1, $2)", vec![]);
+        assert_eq!(
+            prepared.param_types(),
+            &[DataType::Int32, DataType::Varchar]
+        );
+        assert!(prepared.row_description().is_empty());
+
+        // The type specified by the client takes precedence.
+        let prepared = parse("select $1", vec![Some(DataType::Int64)]);
+        assert_eq!(prepared.param_types(), &[DataType::Int64]);
+        assert_eq!(
+            prepared.row_description()[0].get_type_oid(),
+            TypeOid::BigInt
+        );
+
+        // An empty query string.
+        let portal = parse("", vec![]).bind(vec![]);
+        let rsp = handle_execute(session.clone(), portal).await.unwrap();
+        assert!(rsp.is_empty());
+    }
+}
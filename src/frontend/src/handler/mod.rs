@@ -19,40 +19,70 @@ use pgwire::pg_response::StatementType::{ABORT, START_TRANSACTION};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_sqlparser::ast::{DropStatement, ObjectType, Statement};
 
+use crate::binder::ParameterValues;
 use crate::session::{OptimizerContext, SessionImpl};
 
+mod alter_table;
+mod alter_user;
+mod copy;
 mod create_database;
 pub mod create_index;
 pub mod create_mv;
 mod create_schema;
+pub mod create_sink;
 pub mod create_source;
 pub mod create_table;
+mod create_user;
 mod describe;
 pub mod dml;
 mod drop_database;
 mod drop_index;
 pub mod drop_mv;
 mod drop_schema;
+mod drop_sink;
 pub mod drop_source;
 pub mod drop_table;
+mod drop_user;
 mod explain;
+pub mod extended_handle;
 mod flush;
+mod privilege;
 #[allow(dead_code)]
 pub mod query;
 mod set;
 mod show;
 pub mod util;
 
-pub(super) async fn handle(session: Arc<SessionImpl>, stmt: Statement) -> Result<PgResponse> {
+/// Handle a statement. `params` holds the values of its `$n` parameters, which are only
+/// available for queries and DML statements executed with the extended query protocol.
+pub(super) async fn handle(
+    session: Arc<SessionImpl>,
+    stmt: Statement,
+    params: ParameterValues,
+) -> Result<PgResponse> {
     let context = OptimizerContext::new(session.clone());
     match stmt {
         Statement::Explain {
-            statement, verbose, ..
-        } => explain::handle_explain(context, *statement, verbose),
+            statement,
+            verbose,
+            analyze,
+            ..
+        } => explain::handle_explain(context, *statement, verbose, analyze).await,
         Statement::CreateSource {
             is_materialized,
             stmt,
         } => create_source::handle_create_source(context, is_materialized, stmt).await,
+        Statement::CreateSink { stmt } => create_sink::handle_create_sink(context, stmt).await,
+        Statement::CreateUser(stmt) => create_user::handle_create_user(context, stmt).await,
+        Statement::AlterUser(stmt) => alter_user::handle_alter_user(context, stmt).await,
+        Statement::AlterTable { name, operation } => {
+            alter_table::handle_alter_table(context, name, operation).await
+        }
+        Statement::AlterSource { name, operation } => {
+            alter_table::handle_alter_source(context, name, operation).await
+        }
+        Statement::Grant { .. } => privilege::handle_grant_privilege(context, stmt).await,
+        Statement::Revoke { .. } => privilege::handle_revoke_privilege(context, stmt).await,
         Statement::CreateTable { name, columns, .. } => {
             create_table::handle_create_table(context, name, columns).await
         }
@@ -78,6 +108,7 @@ pub(super) async fn handle(session: Arc<SessionImpl>, stmt: Statement) -> Result
             ObjectType::MaterializedView => drop_mv::handle_drop_mv(context, object_name).await,
             ObjectType::Index => drop_index::handle_drop_index(context, object_name).await,
             ObjectType::Source => drop_source::handle_drop_source(context, object_name).await,
+            ObjectType::Sink => drop_sink::handle_drop_sink(context, object_name).await,
             ObjectType::Database => {
                 drop_database::handle_drop_database(
                     context,
@@ -91,15 +122,22 @@ pub(super) async fn handle(session: Arc<SessionImpl>, stmt: Statement) -> Result
                 drop_schema::handle_drop_schema(context, object_name, if_exists, drop_mode.into())
                     .await
             }
+            ObjectType::User => drop_user::handle_drop_user(context, object_name, if_exists).await,
             _ => Err(
                 ErrorCode::InvalidInputSyntax(format!("DROP {} is unsupported", object_type))
                     .into(),
             ),
         },
-        Statement::Query(_) => query::handle_query(context, stmt).await,
+        Statement::Query(_) => query::handle_query(context, stmt, params).await,
         Statement::Insert { .. } | Statement::Delete { .. } | Statement::Update { .. } => {
-            dml::handle_dml(context, stmt).await
+            dml::handle_dml(context, stmt, params).await
         }
+        Statement::Copy {
+            source,
+            to,
+            options,
+            values,
+        } => copy::handle_copy(context, source, to, options, values).await,
         Statement::CreateView {
             materialized: true,
             or_replace: false,
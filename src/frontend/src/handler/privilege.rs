@@ -0,0 +1,466 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::{InvalidInputSyntax, NotImplemented, PermissionDenied};
+use risingwave_common::error::Result;
+use risingwave_pb::user::grant_privilege::{
+    GrantAllTables, GrantDatabase, GrantSchema, Privilege, Target,
+};
+use risingwave_pb::user::{GrantPrivilege, UserInfo};
+use risingwave_sqlparser::ast::{Action, GrantObjects, Ident, ObjectName, Privileges, Statement};
+
+use crate::binder::Binder;
+use crate::catalog::catalog_service::CatalogReadGuard;
+use crate::catalog::CatalogError;
+use crate::session::{OptimizerContext, SessionImpl};
+use crate::user::user_privilege::{
+    available_privileges, has_granted_privilege, has_privilege, relation_object,
+};
+use crate::user::UserId;
+
+/// Returns the [`UserInfo`] of the session user.
+pub(crate) fn session_user(session: &SessionImpl) -> Result<UserInfo> {
+    let user_reader = session.env().user_info_reader();
+    let reader = user_reader.read_guard();
+    reader
+        .get_user_by_name(session.user_name())
+        .cloned()
+        .ok_or_else(|| CatalogError::NotFound("user", session.user_name().to_string()).into())
+}
+
+fn object_kind(object: &Target) -> &'static str {
+    match object {
+        Target::GrantDatabase(_) => "database",
+        Target::GrantSchema(_) | Target::GrantAllTables(_) => "schema",
+        Target::GrantTable(_) => "relation",
+        Target::GrantSource(_) => "source",
+    }
+}
+
+/// Checks that the session user can perform `action` on `object` owned by `owner`, where `name`
+/// is the name of the object used in the error message.
+pub(crate) fn check_privilege(
+    session: &SessionImpl,
+    owner: UserId,
+    object: &Target,
+    action: Privilege,
+    name: &str,
+) -> Result<()> {
+    if has_privilege(&session_user(session)?, owner, object, action) {
+        Ok(())
+    } else {
+        Err(PermissionDenied(format!(
+            "permission denied for {} {}",
+            object_kind(object),
+            name
+        ))
+        .into())
+    }
+}
+
+/// Checks the `CREATE` privilege on the schema `schema_name` of the session database, which is
+/// required to create relations in the schema.
+pub(crate) fn check_schema_create_privilege(
+    session: &SessionImpl,
+    schema_name: &str,
+) -> Result<()> {
+    let (object, owner) = {
+        let catalog_reader = session.env().catalog_reader();
+        let reader = catalog_reader.read_guard();
+        let database_id = reader.get_database_by_name(session.database())?.id();
+        let schema = reader.get_schema_by_name(session.database(), schema_name)?;
+        let object = Target::GrantSchema(GrantSchema {
+            database_id,
+            schema_id: schema.id(),
+        });
+        (object, schema.owner())
+    };
+    check_privilege(session, owner, &object, Privilege::Create, schema_name)
+}
+
+/// Checks that the session user is a super user or one of the `owners`, which is required to drop
+/// an object. The owners of an object are the owner of itself and the owner of the schema or
+/// database containing it.
+pub(crate) fn check_owner(
+    session: &SessionImpl,
+    owners: &[UserId],
+    kind: &str,
+    name: &str,
+) -> Result<()> {
+    let user = session_user(session)?;
+    if user.is_supper || owners.contains(&user.id) {
+        Ok(())
+    } else {
+        Err(PermissionDenied(format!("must be owner of {} {}", kind, name)).into())
+    }
+}
+
+/// An object in `GRANT/REVOKE`, with its owner and name.
+struct GrantObject {
+    target: Target,
+    owner: UserId,
+    name: String,
+}
+
+fn resolve_objects(session: &SessionImpl, objects: GrantObjects) -> Result<Vec<GrantObject>> {
+    let catalog_reader = session.env().catalog_reader();
+    let reader = catalog_reader.read_guard();
+
+    let mut resolved = vec![];
+    match objects {
+        GrantObjects::Databases(names) => {
+            for name in names {
+                let name = Binder::resolve_database_name(name)?;
+                let database = reader.get_database_by_name(&name)?;
+                resolved.push(GrantObject {
+                    target: Target::GrantDatabase(GrantDatabase {
+                        database_id: database.id(),
+                    }),
+                    owner: database.owner(),
+                    name,
+                });
+            }
+        }
+        GrantObjects::Schemas(names) => {
+            for name in names {
+                let (database_name, name) = Binder::resolve_schema_name(session.database(), name)?;
+                let database = reader.get_database_by_name(&database_name)?;
+                let schema = reader.get_schema_by_name(&database_name, &name)?;
+                resolved.push(GrantObject {
+                    target: Target::GrantSchema(GrantSchema {
+                        database_id: database.id(),
+                        schema_id: schema.id(),
+                    }),
+                    owner: schema.owner(),
+                    name,
+                });
+            }
+        }
+        GrantObjects::AllTablesInSchema { schemas } => {
+            for name in schemas {
+                let (database_name, name) = Binder::resolve_schema_name(session.database(), name)?;
+                let database = reader.get_database_by_name(&database_name)?;
+                let schema = reader.get_schema_by_name(&database_name, &name)?;
+                resolved.push(GrantObject {
+                    target: Target::GrantAllTables(GrantAllTables {
+                        database_id: database.id(),
+                        schema_id: schema.id(),
+                    }),
+                    owner: schema.owner(),
+                    name,
+                });
+            }
+        }
+        GrantObjects::Tables(names) => {
+            for name in names {
+                resolved.push(resolve_relation(&reader, session, name, "table")?);
+            }
+        }
+        GrantObjects::Mviews(names) => {
+            for name in names {
+                resolved.push(resolve_relation(
+                    &reader,
+                    session,
+                    name,
+                    "materialized view",
+                )?);
+            }
+        }
+        GrantObjects::Sources(names) => {
+            for name in names {
+                resolved.push(resolve_relation(&reader, session, name, "source")?);
+            }
+        }
+        GrantObjects::Sequences(_) | GrantObjects::AllSequencesInSchema { .. } => {
+            return Err(NotImplemented("privileges on sequences".into(), None.into()).into());
+        }
+    }
+    Ok(resolved)
+}
+
+/// Resolves the relation `name` of `kind`, which is a table, materialized view or source. Any
+/// relation can be specified with `TABLE`, like PostgreSQL does.
+fn resolve_relation(
+    reader: &CatalogReadGuard,
+    session: &SessionImpl,
+    name: ObjectName,
+    kind: &'static str,
+) -> Result<GrantObject> {
+    let (schema_name, name) = Binder::resolve_table_name(name)?;
+    let database_id = reader.get_database_by_name(session.database())?.id();
+    let schema = reader.get_schema_by_name(session.database(), &schema_name)?;
+    let is_kind = match kind {
+        "materialized view" => schema.get_table_by_name(&name).map_or(false, |table| {
+            table.associated_source_id().is_none() && table.is_index_on.is_none()
+        }),
+        "source" => schema.get_source_by_name(&name).is_some(),
+        _ => true,
+    };
+    let (target, owner) = relation_object(database_id, schema, &name)
+        .filter(|_| is_kind)
+        .ok_or_else(|| CatalogError::NotFound(kind, name.clone()))?;
+    Ok(GrantObject {
+        target,
+        owner,
+        name,
+    })
+}
+
+fn resolve_actions(privileges: &Privileges, object: &Target) -> Result<Vec<Privilege>> {
+    let available = available_privileges(object);
+    let actions = match privileges {
+        Privileges::All { .. } => return Ok(available.to_vec()),
+        Privileges::Actions(actions) => actions,
+    };
+    actions
+        .iter()
+        .map(|action| {
+            let privilege = match action {
+                Action::Connect => Privilege::Connect,
+                Action::Create => Privilege::Create,
+                Action::Delete => Privilege::Delete,
+                Action::Insert { columns: None } => Privilege::Insert,
+                Action::Select { columns: None } => Privilege::Select,
+                Action::Update { columns: None } => Privilege::Update,
+                _ => {
+                    return Err(NotImplemented(format!("privilege {}", action), None.into()).into())
+                }
+            };
+            if available.contains(&privilege) {
+                Ok(privilege)
+            } else {
+                Err(InvalidInputSyntax(format!(
+                    "invalid privilege type {} for {}",
+                    action,
+                    object_kind(object)
+                ))
+                .into())
+            }
+        })
+        .collect()
+}
+
+fn resolve_grantees(session: &SessionImpl, grantees: &[Ident]) -> Result<Vec<UserId>> {
+    let user_reader = session.env().user_info_reader();
+    let reader = user_reader.read_guard();
+    grantees
+        .iter()
+        .map(|grantee| {
+            reader
+                .get_user_by_name(&grantee.value)
+                .map(|user| user.id)
+                .ok_or_else(|| CatalogError::NotFound("user", grantee.value.clone()).into())
+        })
+        .collect()
+}
+
+/// Resolves the privileges in `GRANT/REVOKE`, and checks that the session user is able to grant
+/// them, which requires the session user to be a super user, the owner of the objects, or to have
+/// been granted the privileges with grant option.
+fn make_prost_privileges(
+    session: &SessionImpl,
+    privileges: Privileges,
+    objects: GrantObjects,
+    granted_by: Option<Ident>,
+    with_grant_option: bool,
+) -> Result<Vec<GrantPrivilege>> {
+    if let Some(grantor) = granted_by && grantor.value != session.user_name() {
+        return Err(PermissionDenied("grantor must be current user".to_string()).into());
+    }
+
+    let user = session_user(session)?;
+    resolve_objects(session, objects)?
+        .into_iter()
+        .map(|object| {
+            let actions = resolve_actions(&privileges, &object.target)?;
+            for action in &actions {
+                if !user.is_supper
+                    && user.id != object.owner
+                    && !has_granted_privilege(&user, &object.target, *action, true)
+                {
+                    return Err(PermissionDenied(format!(
+                        "permission denied for {} {}",
+                        object_kind(&object.target),
+                        object.name
+                    ))
+                    .into());
+                }
+            }
+            Ok(GrantPrivilege {
+                target: Some(object.target),
+                privileges: actions.into_iter().map(|action| action as i32).collect(),
+                with_grant_option,
+            })
+        })
+        .collect()
+}
+
+pub async fn handle_grant_privilege(
+    context: OptimizerContext,
+    stmt: Statement,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let Statement::Grant {
+        privileges,
+        objects,
+        grantees,
+        with_grant_option,
+        granted_by,
+    } = stmt else {
+        return Err(InvalidInputSyntax("invalid grant statement".to_string()).into());
+    };
+
+    let users = resolve_grantees(&session, &grantees)?;
+    let privileges =
+        make_prost_privileges(&session, privileges, objects, granted_by, with_grant_option)?;
+
+    let user_info_writer = session.env().user_info_writer();
+    user_info_writer.grant_privilege(users, privileges).await?;
+    Ok(PgResponse::empty_result(StatementType::GRANT_PRIVILEGE))
+}
+
+pub async fn handle_revoke_privilege(
+    context: OptimizerContext,
+    stmt: Statement,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let Statement::Revoke {
+        revoke_grant_option,
+        privileges,
+        objects,
+        grantees,
+        granted_by,
+        cascade: _,
+    } = stmt else {
+        return Err(InvalidInputSyntax("invalid revoke statement".to_string()).into());
+    };
+
+    let users = resolve_grantees(&session, &grantees)?;
+    let privileges = make_prost_privileges(&session, privileges, objects, granted_by, false)?;
+
+    let user_info_writer = session.env().user_info_writer();
+    user_info_writer
+        .revoke_privilege(users, privileges, revoke_grant_option)
+        .await?;
+    Ok(PgResponse::empty_result(StatementType::REVOKE_PRIVILEGE))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::DEFAULT_DATABASE_NAME;
+    use risingwave_pb::user::grant_privilege::{GrantDatabase, Privilege, Target};
+    use risingwave_pb::user::GrantPrivilege;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_without_ownership() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("CREATE USER user1").await.unwrap();
+        frontend
+            .run_sql("CREATE TABLE t (v1 smallint)")
+            .await
+            .unwrap();
+
+        assert!(frontend
+            .run_user_sql("DROP TABLE t", "user1")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_user_sql("INSERT INTO t VALUES (1)", "user1")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_user_sql("CREATE MATERIALIZED VIEW mv AS SELECT * FROM t", "user1")
+            .await
+            .is_err());
+
+        frontend.run_sql("DROP TABLE t").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_grant_revoke_privilege() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        frontend.run_sql("CREATE USER user1").await.unwrap();
+
+        frontend
+            .run_sql("GRANT ALL ON DATABASE dev TO user1 WITH GRANT OPTION")
+            .await
+            .unwrap();
+        let database_id = {
+            let catalog_reader = session.env().catalog_reader();
+            let reader = catalog_reader.read_guard();
+            reader
+                .get_database_by_name(DEFAULT_DATABASE_NAME)
+                .unwrap()
+                .id()
+        };
+        let get_privileges = || {
+            session
+                .env()
+                .user_info_reader()
+                .read_guard()
+                .get_user_by_name("user1")
+                .unwrap()
+                .privileges
+                .clone()
+        };
+        assert_eq!(
+            get_privileges(),
+            vec![GrantPrivilege {
+                target: Some(Target::GrantDatabase(GrantDatabase { database_id })),
+                privileges: vec![Privilege::Connect as i32, Privilege::Create as i32],
+                with_grant_option: true,
+            }]
+        );
+
+        frontend
+            .run_sql("REVOKE GRANT OPTION FOR CREATE ON DATABASE dev FROM user1")
+            .await
+            .unwrap();
+        let target = Some(Target::GrantDatabase(GrantDatabase { database_id }));
+        assert_eq!(
+            get_privileges(),
+            vec![
+                GrantPrivilege {
+                    target: target.clone(),
+                    privileges: vec![Privilege::Connect as i32],
+                    with_grant_option: true,
+                },
+                GrantPrivilege {
+                    target,
+                    privileges: vec![Privilege::Create as i32],
+                    with_grant_option: false,
+                },
+            ]
+        );
+
+        frontend
+            .run_sql("REVOKE ALL ON DATABASE dev FROM user1")
+            .await
+            .unwrap();
+        assert!(get_privileges().is_empty());
+
+        assert!(frontend
+            .run_sql("GRANT SELECT ON DATABASE dev TO user1")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("GRANT ALL ON DATABASE dev TO user2")
+            .await
+            .is_err());
+    }
+}
@@ -15,12 +15,13 @@
 use futures_async_stream::for_await;
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
+use pgwire::types::Row;
 use risingwave_batch::executor::BoxedDataChunkStream;
 use risingwave_common::error::Result;
 use risingwave_sqlparser::ast::Statement;
 use tracing::info;
 
-use crate::binder::{Binder, BoundStatement};
+use crate::binder::{Binder, BoundStatement, ParameterValues};
 use crate::config::QueryMode;
 use crate::handler::util::{to_pg_field, to_pg_rows};
 use crate::planner::Planner;
@@ -30,19 +31,37 @@ use crate::session::OptimizerContext;
 
 pub static QUERY_MODE: &str = "query_mode";
 
-pub async fn handle_query(context: OptimizerContext, stmt: Statement) -> Result<PgResponse> {
+pub async fn handle_query(
+    context: OptimizerContext,
+    stmt: Statement,
+    params: ParameterValues,
+) -> Result<PgResponse> {
     let stmt_type = to_statement_type(&stmt);
     let session = context.session_ctx.clone();
 
     let bound = {
-        let mut binder = Binder::new(
-            session.env().catalog_reader().read_guard(),
-            session.database().to_string(),
-        );
+        let mut binder = Binder::new_with_params(&session, params);
         binder.bind(stmt)?
     };
 
-    let query_mode = session
+    let (rows, pg_descs) = execute_query(context, bound).await?;
+
+    let rows_count = match stmt_type {
+        StatementType::SELECT => rows.len() as i32,
+        _ => unreachable!(),
+    };
+
+    Ok(PgResponse::new(stmt_type, rows_count, rows, pg_descs))
+}
+
+/// Executes a bound query in the query mode of the session, and returns its output rows along
+/// with their descriptions.
+pub(super) async fn execute_query(
+    context: OptimizerContext,
+    bound: BoundStatement,
+) -> Result<(Vec<Row>, Vec<PgFieldDescriptor>)> {
+    let query_mode = context
+        .session_ctx
         .get_config(QUERY_MODE)
         .map(|entry| entry.get_val(QueryMode::default()))
         .unwrap_or_default();
@@ -57,13 +76,7 @@ pub async fn handle_query(context: OptimizerContext, stmt: Statement) -> Result<
     for chunk in data_stream {
         rows.extend(to_pg_rows(chunk?));
     }
-
-    let rows_count = match stmt_type {
-        StatementType::SELECT => rows.len() as i32,
-        _ => unreachable!(),
-    };
-
-    Ok(PgResponse::new(stmt_type, rows_count, rows, pg_descs))
+    Ok((rows, pg_descs))
 }
 
 fn to_statement_type(stmt: &Statement) -> StatementType {
@@ -143,8 +156,9 @@ async fn local_execute(
     };
 
     let hummock_snapshot_manager = session.env().hummock_snapshot_manager().clone();
+    let cancel_signal = session.query_cancel_signal();
 
     // TODO: Passing sql here
-    let execution = LocalQueryExecution::new(query, hummock_snapshot_manager, "");
+    let execution = LocalQueryExecution::new(query, hummock_snapshot_manager, cancel_signal, "");
     Ok((Box::pin(execution.run()), pg_descs))
 }
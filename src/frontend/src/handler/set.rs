@@ -16,6 +16,7 @@ use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::Result;
 use risingwave_sqlparser::ast::{Ident, SetVariableValue};
 
+use crate::config::{TimeZone, TIMEZONE};
 use crate::session::OptimizerContext;
 
 pub(super) fn handle_set(
@@ -24,6 +25,13 @@ pub(super) fn handle_set(
     value: Vec<SetVariableValue>,
 ) -> Result<PgResponse> {
     let string_val = to_string(&value[0]);
+    if name.value.eq_ignore_ascii_case(TIMEZONE) {
+        // The time zone is validated eagerly, as an unknown zone would otherwise only fail once a
+        // query uses it.
+        let time_zone = TimeZone::try_from(string_val.as_str())?;
+        context.session_ctx.set_config(TIMEZONE, &time_zone.0);
+        return Ok(PgResponse::empty_result(StatementType::SET_OPTION));
+    }
     // Currently store the config variable simply as String -> ConfigEntry(String).
     // In future we can add converter/parser to make the API more robust.
     context.session_ctx.set_config(&name.value, &string_val);
@@ -18,6 +18,7 @@ use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::types::Row;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::{ColumnDesc, Field};
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::{DataType, ScalarRefImpl};
 
 /// Format scalars according to postgres convention.
@@ -96,13 +97,41 @@ pub fn data_type_to_type_oid(data_type: DataType) -> TypeOid {
         DataType::Time => TypeOid::Time,
         DataType::Timestamp => TypeOid::Timestamp,
         DataType::Timestampz => TypeOid::Timestampz,
-        DataType::Decimal => TypeOid::Decimal,
+        DataType::Decimal(_) => TypeOid::Decimal,
         DataType::Interval => TypeOid::Varchar,
+        DataType::Jsonb => TypeOid::Jsonb,
+        DataType::Bytea => TypeOid::Bytea,
+        DataType::Uuid => TypeOid::Uuid,
         DataType::Struct { .. } => TypeOid::Varchar,
         DataType::List { .. } => TypeOid::Varchar,
     }
 }
 
+pub fn type_oid_to_data_type(type_oid: TypeOid) -> Result<DataType> {
+    match type_oid {
+        TypeOid::SmallInt => Ok(DataType::Int16),
+        TypeOid::Int => Ok(DataType::Int32),
+        TypeOid::BigInt => Ok(DataType::Int64),
+        TypeOid::Float4 => Ok(DataType::Float32),
+        TypeOid::Float8 => Ok(DataType::Float64),
+        TypeOid::Boolean => Ok(DataType::Boolean),
+        TypeOid::Varchar => Ok(DataType::Varchar),
+        TypeOid::Date => Ok(DataType::Date),
+        TypeOid::Time => Ok(DataType::Time),
+        TypeOid::Timestamp => Ok(DataType::Timestamp),
+        TypeOid::Timestampz => Ok(DataType::Timestampz),
+        TypeOid::Decimal => Ok(DataType::Decimal(None)),
+        TypeOid::Jsonb => Ok(DataType::Jsonb),
+        TypeOid::Bytea => Ok(DataType::Bytea),
+        TypeOid::Uuid => Ok(DataType::Uuid),
+        TypeOid::CharArray => Err(ErrorCode::NotImplemented(
+            format!("parameter of type {:?}", type_oid),
+            None.into(),
+        )
+        .into()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::array::*;
@@ -26,7 +26,7 @@ use fixedbitset::FixedBitSet;
 use itertools::Itertools as _;
 use property::Order;
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
 
 use self::heuristic::{ApplyOrder, HeuristicOptimizer};
 use self::plan_node::{BatchProject, Convention, LogicalProject, StreamMaterialize};
@@ -111,7 +111,7 @@ impl PlanRoot {
     }
 
     /// Apply logical optimization to the plan.
-    pub fn gen_optimized_logical_plan(&self) -> PlanRef {
+    pub fn gen_optimized_logical_plan(&self) -> Result<PlanRef> {
         let mut plan = self.plan.clone();
 
         // Subquery Unnesting.
@@ -120,10 +120,18 @@ impl PlanRoot {
                 // This rule should be applied first to pull up LogicalAgg.
                 UnnestAggForLOJ::create(),
                 PullUpCorrelatedPredicate::create(),
+                ApplyToJoinRule::create(),
             ];
             let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::TopDown, rules);
             heuristic_optimizer.optimize(plan)
         };
+        if has_logical_apply(&plan) {
+            return Err(ErrorCode::NotImplemented(
+                "this kind of correlated subquery can not be unnested yet".into(),
+                1343.into(),
+            )
+            .into());
+        }
 
         // Predicate Push-down
         plan = plan.predicate_pushdown(Condition::true_cond());
@@ -165,13 +173,13 @@ impl PlanRoot {
             heuristic_optimizer.optimize(plan)
         };
 
-        plan
+        Ok(plan)
     }
 
     /// Optimize and generate a batch query plan for distributed execution.
     pub fn gen_batch_query_plan(&self) -> Result<PlanRef> {
         // Logical optimization
-        let mut plan = self.gen_optimized_logical_plan();
+        let mut plan = self.gen_optimized_logical_plan()?;
 
         // Convert to physical plan node
         plan = plan.to_batch_with_order_required(&self.required_order)?;
@@ -196,7 +204,7 @@ impl PlanRoot {
     /// Optimize and generate a batch query plan for local execution.
     pub fn gen_batch_local_plan(&self) -> Result<PlanRef> {
         // Logical optimization
-        let mut plan = self.gen_optimized_logical_plan();
+        let mut plan = self.gen_optimized_logical_plan()?;
 
         // Convert to physical plan node
         plan = plan.to_batch_with_order_required(&self.required_order)?;
@@ -222,7 +230,16 @@ impl PlanRoot {
     fn gen_stream_plan(&mut self) -> Result<PlanRef> {
         let plan = match self.plan.convention() {
             Convention::Logical => {
-                let plan = self.gen_optimized_logical_plan();
+                let plan = self.gen_optimized_logical_plan()?;
+
+                // Plan the filters on row numbers with group top-n, which is only available in
+                // streaming.
+                let plan = {
+                    let rules = vec![OverAggToTopNRule::create(), ProjectEliminateRule::create()];
+                    let heuristic_optimizer = HeuristicOptimizer::new(ApplyOrder::TopDown, rules);
+                    heuristic_optimizer.optimize(plan)
+                };
+
                 let (plan, out_col_change) = plan.logical_rewrite_for_stream()?;
                 self.required_dist =
                     out_col_change.rewrite_required_distribution(&self.required_dist);
@@ -285,6 +302,12 @@ impl PlanRoot {
     }
 }
 
+/// Whether there is any [`LogicalApply`](plan_node::LogicalApply) left in the plan, i.e. some
+/// subquery is not unnested.
+fn has_logical_apply(plan: &PlanRef) -> bool {
+    plan.as_logical_apply().is_some() || plan.inputs().iter().any(has_logical_apply)
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::catalog::Field;
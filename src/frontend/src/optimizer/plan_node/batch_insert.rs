@@ -77,7 +77,12 @@ impl ToBatchProst for BatchInsert {
                 ..Default::default()
             }
             .into(),
-            column_ids: vec![], // unused
+            column_ids: self
+                .logical
+                .column_ids()
+                .iter()
+                .map(|column_id| column_id.get_id())
+                .collect(),
         })
     }
 }
@@ -0,0 +1,107 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::LateralTableFunctionNode;
+
+use super::{
+    LogicalLateralTableFunction, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst,
+    ToDistributedBatch,
+};
+use crate::optimizer::plan_node::ToLocalBatch;
+use crate::optimizer::property::{Order, RequiredDist};
+
+/// `BatchLateralTableFunction` implements [`super::LogicalLateralTableFunction`] to evaluate a
+/// table function on each input row.
+#[derive(Debug, Clone)]
+pub struct BatchLateralTableFunction {
+    pub base: PlanBase,
+    logical: LogicalLateralTableFunction,
+}
+
+impl BatchLateralTableFunction {
+    pub fn new(logical: LogicalLateralTableFunction) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let distribution = logical
+            .i2o_col_mapping()
+            .rewrite_provided_distribution(logical.input().distribution());
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            distribution,
+            Order::any().clone(),
+        );
+        BatchLateralTableFunction { base, logical }
+    }
+}
+
+impl fmt::Display for BatchLateralTableFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchLateralTableFunction")
+    }
+}
+
+impl PlanTreeNodeUnary for BatchLateralTableFunction {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { BatchLateralTableFunction }
+
+impl ToDistributedBatch for BatchLateralTableFunction {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_distributed()?;
+        Ok(self.clone_with_input(new_input).into())
+    }
+
+    fn to_distributed_with_required(
+        &self,
+        required_order: &Order,
+        required_dist: &RequiredDist,
+    ) -> Result<PlanRef> {
+        let input_required = self
+            .logical
+            .o2i_col_mapping()
+            .rewrite_required_distribution(required_dist);
+        let new_input = self
+            .input()
+            .to_distributed_with_required(&Order::any(), &input_required)?;
+        let batch_plan = self.clone_with_input(new_input);
+        let batch_plan = required_order.enforce_if_not_satisfies(batch_plan.into())?;
+        required_dist.enforce_if_not_satisfies(batch_plan, required_order)
+    }
+}
+
+impl ToBatchProst for BatchLateralTableFunction {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::LateralTableFunction(LateralTableFunctionNode {
+            table_function: Some(self.logical.to_protobuf()),
+        })
+    }
+}
+
+impl ToLocalBatch for BatchLateralTableFunction {
+    fn to_local(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_local()?;
+        Ok(self.clone_with_input(new_input).into())
+    }
+}
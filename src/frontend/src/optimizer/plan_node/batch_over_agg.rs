@@ -0,0 +1,107 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::OverAggNode;
+
+use super::{
+    LogicalOverAgg, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst, ToDistributedBatch,
+};
+use crate::optimizer::plan_node::ToLocalBatch;
+use crate::optimizer::property::{Order, RequiredDist};
+
+/// `BatchOverAgg` sorts each partition by the window order and evaluates the window functions.
+#[derive(Debug, Clone)]
+pub struct BatchOverAgg {
+    pub base: PlanBase,
+    logical: LogicalOverAgg,
+}
+
+impl BatchOverAgg {
+    pub fn new(logical: LogicalOverAgg) -> Self {
+        let ctx = logical.base.ctx.clone();
+        // The input columns are kept in place, so the distribution is still valid.
+        let dist = logical.input().distribution().clone();
+        let base = PlanBase::new_batch(ctx, logical.schema().clone(), dist, Order::any().clone());
+        BatchOverAgg { base, logical }
+    }
+
+    fn required_dist(&self) -> RequiredDist {
+        if self.logical.partition_by().is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::shard_by_key(self.input().schema().len(), self.logical.partition_by())
+        }
+    }
+}
+
+impl fmt::Display for BatchOverAgg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchOverAgg")
+    }
+}
+
+impl PlanTreeNodeUnary for BatchOverAgg {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+impl_plan_tree_node_for_unary! { BatchOverAgg }
+
+impl ToDistributedBatch for BatchOverAgg {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        let new_input = self
+            .input()
+            .to_distributed_with_required(Order::any(), &self.required_dist())?;
+        Ok(self.clone_with_input(new_input).into())
+    }
+}
+
+impl ToBatchProst for BatchOverAgg {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::OverAgg(OverAggNode {
+            partition_by: self
+                .logical
+                .partition_by()
+                .iter()
+                .map(|index| *index as u32)
+                .collect(),
+            order_by: Order::new(self.logical.order_by().to_vec())
+                .to_protobuf(self.input().schema()),
+            window_functions: self
+                .logical
+                .window_functions()
+                .iter()
+                .map(|call| call.to_protobuf())
+                .collect(),
+        })
+    }
+}
+
+impl ToLocalBatch for BatchOverAgg {
+    fn to_local(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_local()?;
+
+        let new_input = RequiredDist::single().enforce_if_not_satisfies(new_input, Order::any())?;
+
+        Ok(self.clone_with_input(new_input).into())
+    }
+}
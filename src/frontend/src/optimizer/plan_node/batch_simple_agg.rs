@@ -77,7 +77,9 @@ impl ToDistributedBatch for BatchSimpleAgg {
         // (e.g. see distribution of BatchSeqScan::new vs BatchSeqScan::to_distributed)
         let dist_input = self.input().to_distributed()?;
 
-        if dist_input.distribution().satisfies(&RequiredDist::AnyShard) {
+        if dist_input.distribution().satisfies(&RequiredDist::AnyShard)
+            && self.logical.can_two_phase_agg()
+        {
             // partial agg
             let partial_agg = self.clone_with_input(dist_input).into();
 
@@ -0,0 +1,112 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::UnionNode;
+
+use super::{LogicalUnion, PlanBase, PlanRef, PlanTreeNode, ToBatchProst, ToDistributedBatch};
+use crate::optimizer::plan_node::ToLocalBatch;
+use crate::optimizer::property::{Distribution, Order, RequiredDist};
+
+/// `BatchUnion` implements [`super::LogicalUnion`] with `all` set, which outputs the rows of its
+/// inputs one after another.
+#[derive(Debug, Clone)]
+pub struct BatchUnion {
+    pub base: PlanBase,
+    logical: LogicalUnion,
+}
+
+impl BatchUnion {
+    pub fn new(logical: LogicalUnion) -> Self {
+        assert!(logical.all());
+        let ctx = logical.base.ctx.clone();
+        let dist = if logical
+            .inputs()
+            .iter()
+            .all(|input| *input.distribution() == Distribution::Single)
+        {
+            Distribution::Single
+        } else {
+            Distribution::SomeShard
+        };
+        let base = PlanBase::new_batch(ctx, logical.schema().clone(), dist, Order::any().clone());
+        BatchUnion { base, logical }
+    }
+
+    fn clone_with_new_inputs(&self, inputs: Vec<PlanRef>) -> Self {
+        Self::new(self.logical.with_inputs(inputs))
+    }
+}
+
+impl fmt::Display for BatchUnion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchUnion")
+    }
+}
+
+impl PlanTreeNode for BatchUnion {
+    fn inputs(&self) -> smallvec::SmallVec<[PlanRef; 2]> {
+        self.logical.inputs()
+    }
+
+    fn clone_with_inputs(&self, inputs: &[PlanRef]) -> PlanRef {
+        self.clone_with_new_inputs(inputs.to_vec()).into()
+    }
+}
+
+impl ToDistributedBatch for BatchUnion {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        let inputs = self
+            .inputs()
+            .iter()
+            .map(|input| input.to_distributed())
+            .collect::<Result<Vec<_>>>()?;
+        // The inputs are kept distributed, unless some of them must be on a single node, in which
+        // case all of them are gathered.
+        let inputs = if inputs
+            .iter()
+            .any(|input| *input.distribution() == Distribution::Single)
+        {
+            inputs
+                .into_iter()
+                .map(|input| RequiredDist::single().enforce_if_not_satisfies(input, Order::any()))
+                .collect::<Result<_>>()?
+        } else {
+            inputs
+        };
+        Ok(self.clone_with_new_inputs(inputs).into())
+    }
+}
+
+impl ToBatchProst for BatchUnion {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::Union(UnionNode {})
+    }
+}
+
+impl ToLocalBatch for BatchUnion {
+    fn to_local(&self) -> Result<PlanRef> {
+        let inputs = self
+            .inputs()
+            .iter()
+            .map(|input| {
+                RequiredDist::single().enforce_if_not_satisfies(input.to_local()?, Order::any())
+            })
+            .collect::<Result<_>>()?;
+        Ok(self.clone_with_new_inputs(inputs).into())
+    }
+}
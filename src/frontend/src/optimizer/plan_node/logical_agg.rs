@@ -19,7 +19,7 @@ use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_expr::expr::AggKind;
 use risingwave_pb::expr::AggCall as ProstAggCall;
 
@@ -27,7 +27,11 @@ use super::{
     BatchHashAgg, BatchSimpleAgg, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary,
     PredicatePushdown, StreamHashAgg, StreamSimpleAgg, ToBatch, ToStream,
 };
-use crate::expr::{AggCall, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef};
+use crate::binder::{BoundQuery, BoundSetExpr};
+use crate::expr::{
+    AggCall, CorrelatedInputRef, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef,
+    Literal, Subquery, SubqueryKind,
+};
 use crate::optimizer::plan_node::{gen_filter_and_pushdown, LogicalProject};
 use crate::optimizer::property::RequiredDist;
 use crate::utils::{ColIndexMapping, Condition, Substitute};
@@ -49,6 +53,16 @@ pub struct PlanAggCall {
 
 impl fmt::Debug for PlanAggCall {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.distinct {
+            write!(f, "{}(distinct ", self.agg_kind)?;
+            for (idx, child) in self.inputs.iter().enumerate() {
+                if idx != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{:?}", child)?;
+            }
+            return write!(f, ")");
+        }
         let mut builder = f.debug_tuple(&format!("{}", self.agg_kind));
         self.inputs.iter().for_each(|child| {
             builder.field(child);
@@ -73,7 +87,17 @@ impl PlanAggCall {
             | AggKind::Max
             | AggKind::Avg
             | AggKind::StringAgg
-            | AggKind::SingleValue => self.agg_kind.clone(),
+            | AggKind::SingleValue
+            | AggKind::StddevPop
+            | AggKind::StddevSamp
+            | AggKind::VarPop
+            | AggKind::VarSamp
+            | AggKind::BoolAnd
+            | AggKind::BoolOr
+            | AggKind::BitAnd
+            | AggKind::BitOr
+            | AggKind::ArrayAgg
+            | AggKind::ApproxCountDistinct => self.agg_kind.clone(),
 
             AggKind::Count | AggKind::RowCount | AggKind::Sum => AggKind::Sum,
         };
@@ -122,6 +146,9 @@ struct ExprHandler {
     /// schema, and they are at the beginning of projects.
     expr_index: HashMap<ExprImpl, usize>,
     pub agg_calls: Vec<PlanAggCall>,
+    /// The rewritten agg calls that other agg calls are decomposed into, e.g. `sum(x)` of
+    /// `stddev_pop(x)`, by their kinds and inputs.
+    decomposed_agg_calls: HashMap<(AggKind, ExprImpl), ExprImpl>,
     pub error: Option<ErrorCode>,
 }
 
@@ -150,6 +177,7 @@ impl ExprHandler {
             group_key_len,
             expr_index,
             agg_calls: vec![],
+            decomposed_agg_calls: HashMap::new(),
             error: None,
         })
     }
@@ -161,6 +189,135 @@ impl ExprHandler {
         }
         Ok(rewritten_expr)
     }
+
+    /// Rewrites an agg call `agg_kind(input)` that another agg call is decomposed into, e.g.
+    /// `sum(x)` of `stddev_pop(x)`. The same call is only computed once for all agg calls
+    /// decomposed into it.
+    fn rewrite_decomposed_agg_call(&mut self, agg_kind: AggKind, input: ExprImpl) -> ExprImpl {
+        let key = (agg_kind, input);
+        if let Some(expr) = self.decomposed_agg_calls.get(&key) {
+            return expr.clone();
+        }
+        let (agg_kind, input) = key.clone();
+        let expr = self.rewrite_agg_call(AggCall::new(agg_kind, vec![input], false).unwrap());
+        self.decomposed_agg_calls.insert(key, expr.clone());
+        expr
+    }
+
+    /// Rewrites `var_pop`, `var_samp`, `stddev_pop` and `stddev_samp` of `x` with `sum(x)`,
+    /// `sum(x * x)` and `count(x)`:
+    /// `var_pop = (sum(x * x) - sum(x) * sum(x) / count(x)) / count(x)`. `var_samp` divides by
+    /// `count(x) - 1` instead and is null unless there are at least two rows.
+    ///
+    /// Integers and floats are computed in double precision, so that `x * x` and the sums do not
+    /// overflow for any `bigint`, and the result is double precision while PG returns numeric for
+    /// integers. The formula loses precision by cancellation when the variance is tiny compared to
+    /// the square of the mean, which PG avoids with the Youngs-Cramer algorithm that cannot be
+    /// decomposed into retractable aggregates. Numerics are computed in numeric as PG does, which
+    /// overflows when `x * x` or the square of the sum exceeds its 28 digits.
+    fn rewrite_stddev_variance(
+        &mut self,
+        agg_kind: AggKind,
+        inputs: Vec<ExprImpl>,
+        distinct: bool,
+    ) -> ExprImpl {
+        if distinct {
+            // `sum(DISTINCT x * x)` would also merge `x` and `-x`.
+            self.error = Some(ErrorCode::NotImplemented(
+                format!("{}(DISTINCT)", agg_kind),
+                None.into(),
+            ));
+            return AggCall::new(agg_kind, inputs, distinct).unwrap().into();
+        }
+        assert_eq!(inputs.len(), 1);
+        let input = inputs.into_iter().next().unwrap();
+        // Numeric for numerics, double precision for integers and floats.
+        let data_type = AggCall::infer_return_type(&agg_kind, &[input.return_type()]).unwrap();
+        let input = input.cast_implicit(data_type.clone()).unwrap();
+        let int = |v: i32| -> ExprImpl {
+            ExprImpl::literal_int(v)
+                .cast_implicit(data_type.clone())
+                .unwrap()
+        };
+        let call = |func_type: ExprType, inputs: Vec<ExprImpl>| -> ExprImpl {
+            FunctionCall::new(func_type, inputs).unwrap().into()
+        };
+
+        let squared = call(ExprType::Multiply, vec![input.clone(), input.clone()]);
+        let sum_sq = self.rewrite_decomposed_agg_call(AggKind::Sum, squared);
+        let sum = self.rewrite_decomposed_agg_call(AggKind::Sum, input.clone());
+        let count = self
+            .rewrite_decomposed_agg_call(AggKind::Count, input)
+            .cast_implicit(data_type.clone())
+            .unwrap();
+
+        let square_of_sum = call(ExprType::Multiply, vec![sum.clone(), sum]);
+        let numerator = call(
+            ExprType::Subtract,
+            vec![
+                sum_sq,
+                call(ExprType::Divide, vec![square_of_sum, count.clone()]),
+            ],
+        );
+        // Rounding errors may make the numerator slightly negative.
+        let numerator = call(
+            ExprType::Case,
+            vec![
+                call(ExprType::LessThan, vec![numerator.clone(), int(0)]),
+                int(0),
+                numerator,
+            ],
+        );
+        let variance = match agg_kind {
+            AggKind::VarPop | AggKind::StddevPop => call(ExprType::Divide, vec![numerator, count]),
+            AggKind::VarSamp | AggKind::StddevSamp => call(
+                ExprType::Case,
+                vec![
+                    call(ExprType::GreaterThan, vec![count.clone(), int(1)]),
+                    call(
+                        ExprType::Divide,
+                        vec![numerator, call(ExprType::Subtract, vec![count, int(1)])],
+                    ),
+                ],
+            ),
+            _ => unreachable!(),
+        };
+        match agg_kind {
+            // The square root is taken in double precision and cast back for numerics.
+            AggKind::StddevPop | AggKind::StddevSamp => call(
+                ExprType::Sqrt,
+                vec![variance.cast_explicit(DataType::Float64).unwrap()],
+            )
+            .cast_explicit(data_type)
+            .unwrap(),
+            _ => variance,
+        }
+    }
+
+    /// Rewrites `bool_and(x)` as `sum(x::int) = count(x)` and `bool_or(x)` as `sum(x::int) > 0`.
+    /// `DISTINCT` makes no difference to them.
+    fn rewrite_bool_agg(&mut self, agg_kind: AggKind, inputs: Vec<ExprImpl>) -> ExprImpl {
+        assert_eq!(inputs.len(), 1);
+        let input = inputs.into_iter().next().unwrap();
+        let sum = self.rewrite_decomposed_agg_call(
+            AggKind::Sum,
+            input.clone().cast_explicit(DataType::Int32).unwrap(),
+        );
+        let (func_type, right) = match agg_kind {
+            AggKind::BoolAnd => (
+                ExprType::Equal,
+                self.rewrite_decomposed_agg_call(AggKind::Count, input),
+            ),
+            AggKind::BoolOr => (
+                ExprType::GreaterThan,
+                Literal::new(Some(ScalarImpl::Int64(0)), DataType::Int64).into(),
+            ),
+            _ => unreachable!(),
+        };
+        FunctionCall::new(func_type, vec![sum, right])
+            .unwrap()
+            .into()
+    }
 }
 
 impl ExprRewriter for ExprHandler {
@@ -183,6 +340,14 @@ impl ExprRewriter for ExprHandler {
             }
         }
 
+        match agg_kind {
+            AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp => {
+                return self.rewrite_stddev_variance(agg_kind, inputs, distinct);
+            }
+            AggKind::BoolAnd | AggKind::BoolOr => return self.rewrite_bool_agg(agg_kind, inputs),
+            _ => {}
+        }
+
         let mut index = self.project.len();
         let mut input_refs = vec![];
         self.project.extend(inputs.into_iter().filter(|expr| {
@@ -235,6 +400,17 @@ impl ExprRewriter for ExprHandler {
 
             ExprImpl::from(FunctionCall::new(ExprType::Divide, vec![left, right.into()]).unwrap())
         } else {
+            // `DISTINCT` makes no difference to `min`, `max`, `bit_and`, `bit_or` and
+            // `approx_count_distinct`.
+            let distinct = distinct
+                && !matches!(
+                    agg_kind,
+                    AggKind::Min
+                        | AggKind::Max
+                        | AggKind::BitAnd
+                        | AggKind::BitOr
+                        | AggKind::ApproxCountDistinct
+                );
             self.agg_calls.push(PlanAggCall {
                 agg_kind,
                 return_type: return_type.clone(),
@@ -278,13 +454,132 @@ impl ExprRewriter for ExprHandler {
         }
     }
 
-    fn rewrite_subquery(&mut self, subquery: crate::expr::Subquery) -> ExprImpl {
+    /// The left operand of `IN`, `SOME` or `ALL` is rewritten like other exprs outside agg calls.
+    ///
+    /// A correlated subquery in the select list or HAVING is evaluated on the aggregated rows, so
+    /// the outer columns it refers to must be group columns, and are rewritten to refer to them in
+    /// the output of the aggregation.
+    fn rewrite_subquery(&mut self, mut subquery: Subquery) -> ExprImpl {
+        subquery.kind = match subquery.kind {
+            SubqueryKind::In(expr) => SubqueryKind::In(self.rewrite_expr(expr)),
+            SubqueryKind::Some(expr, op) => SubqueryKind::Some(self.rewrite_expr(expr), op),
+            SubqueryKind::All(expr, op) => SubqueryKind::All(self.rewrite_expr(expr), op),
+            kind => kind,
+        };
         if subquery.is_correlated() {
+            let mut rewriter = CorrelatedGroupColumnRewriter {
+                expr_index: &self.expr_index,
+                group_key_len: self.group_key_len,
+                depth: 1,
+                error: None,
+            };
+            rewriter.rewrite_query(&mut subquery.query);
+            if let Some(error) = rewriter.error {
+                self.error = Some(error);
+            }
+        }
+        subquery.into()
+    }
+}
+
+/// Rewrites the [`CorrelatedInputRef`]s in a subquery, which refer to the input of an aggregation,
+/// to refer to the group columns in the output of the aggregation.
+struct CorrelatedGroupColumnRewriter<'a> {
+    expr_index: &'a HashMap<ExprImpl, usize>,
+    group_key_len: usize,
+    /// Nesting level of the subquery being rewritten, relative to the aggregation.
+    depth: usize,
+    error: Option<ErrorCode>,
+}
+
+impl CorrelatedGroupColumnRewriter<'_> {
+    fn rewrite_exprs(&mut self, exprs: &mut Vec<ExprImpl>) {
+        *exprs = std::mem::take(exprs)
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+    }
+
+    fn rewrite_query(&mut self, query: &mut BoundQuery) {
+        self.rewrite_set_expr(&mut query.body);
+        self.rewrite_exprs(&mut query.extra_order_exprs);
+    }
+
+    fn rewrite_set_expr(&mut self, set_expr: &mut BoundSetExpr) {
+        match set_expr {
+            BoundSetExpr::Select(select) => {
+                self.rewrite_exprs(&mut select.select_items);
+                self.rewrite_exprs(&mut select.group_by);
+                select.where_clause = select.where_clause.take().map(|e| self.rewrite_expr(e));
+                select.having = select.having.take().map(|e| self.rewrite_expr(e));
+            }
+            BoundSetExpr::Values(values) => {
+                values
+                    .rows
+                    .iter_mut()
+                    .for_each(|row| self.rewrite_exprs(row));
+            }
+            BoundSetExpr::Query(query) => self.rewrite_query(query),
+            BoundSetExpr::SetOperation(set_operation) => {
+                self.rewrite_set_expr(&mut set_operation.left);
+                self.rewrite_set_expr(&mut set_operation.right);
+            }
+        }
+    }
+}
+
+impl ExprRewriter for CorrelatedGroupColumnRewriter<'_> {
+    fn rewrite_correlated_input_ref(&mut self, input_ref: CorrelatedInputRef) -> ExprImpl {
+        if input_ref.depth() != self.depth {
+            return input_ref.into();
+        }
+        let expr = InputRef::new(input_ref.index(), input_ref.return_type()).into();
+        match self.expr_index.get(&expr) {
+            Some(index) if *index < self.group_key_len => {
+                CorrelatedInputRef::new(*index, input_ref.return_type(), self.depth).into()
+            }
+            _ => {
+                self.error = Some(ErrorCode::InvalidInputSyntax(
+                    "subquery uses ungrouped column from outer query".into(),
+                ));
+                input_ref.into()
+            }
+        }
+    }
+
+    /// An aggregate whose arguments are all outer columns belongs to the outer query, which is
+    /// not supported yet.
+    fn rewrite_agg_call(&mut self, agg_call: AggCall) -> ExprImpl {
+        if agg_call.inputs().iter().all(|expr| !expr.has_input_ref())
+            && agg_call
+                .inputs()
+                .iter()
+                .any(|expr| expr.has_correlated_input_ref_by_depth(self.depth))
+        {
             self.error = Some(ErrorCode::NotImplemented(
-                "correlated subquery in HAVING or SELECT with agg".into(),
+                "aggregate of outer columns in subquery".into(),
                 2275.into(),
             ));
+            return agg_call.into();
         }
+        let (agg_kind, inputs, distinct) = agg_call.decompose();
+        let inputs = inputs
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        AggCall::new(agg_kind, inputs, distinct).unwrap().into()
+    }
+
+    fn rewrite_subquery(&mut self, mut subquery: Subquery) -> ExprImpl {
+        subquery.kind = match subquery.kind {
+            SubqueryKind::In(expr) => SubqueryKind::In(self.rewrite_expr(expr)),
+            SubqueryKind::Some(expr, op) => SubqueryKind::Some(self.rewrite_expr(expr), op),
+            SubqueryKind::All(expr, op) => SubqueryKind::All(self.rewrite_expr(expr), op),
+            kind => kind,
+        };
+        self.depth += 1;
+        self.rewrite_query(&mut subquery.query);
+        self.depth -= 1;
         subquery.into()
     }
 }
@@ -399,9 +694,65 @@ impl LogicalAgg {
         self.group_keys.as_ref()
     }
 
+    /// Whether the agg calls can be computed by a partial agg followed by a total agg. The
+    /// partial results of `array_agg` and `approx_count_distinct` cannot be merged.
+    pub fn can_two_phase_agg(&self) -> bool {
+        !self.agg_calls.iter().any(|agg_call| {
+            matches!(
+                agg_call.agg_kind,
+                AggKind::ArrayAgg | AggKind::ApproxCountDistinct
+            )
+        })
+    }
+
     pub fn decompose(self) -> (Vec<PlanAggCall>, Vec<usize>, PlanRef) {
         (self.agg_calls, self.group_keys, self.input)
     }
+
+    /// If all the agg calls are distinct on the same column, rewrites the aggregation into two
+    /// phases: the inner one groups by the group keys and the distinct column to remove the
+    /// duplicates, and the outer one computes the agg calls without `DISTINCT`. So that no
+    /// dedup state is needed in streaming, and the inner one can be computed in parallel even if
+    /// there are no group keys.
+    fn split_distinct_agg(&self) -> Option<LogicalAgg> {
+        let distinct_col = match self.agg_calls.first()?.inputs.as_slice() {
+            [input] => input.index(),
+            _ => return None,
+        };
+        if !self.agg_calls.iter().all(|agg_call| {
+            agg_call.distinct
+                && matches!(agg_call.inputs.as_slice(), [input] if input.index() == distinct_col)
+        }) {
+            return None;
+        }
+
+        let mut inner_group_keys = self.group_keys.clone();
+        let distinct_pos = match inner_group_keys.iter().position(|key| *key == distinct_col) {
+            Some(pos) => pos,
+            None => {
+                inner_group_keys.push(distinct_col);
+                inner_group_keys.len() - 1
+            }
+        };
+        let inner = LogicalAgg::new(vec![], inner_group_keys, self.input.clone());
+        let agg_calls = self
+            .agg_calls
+            .iter()
+            .map(|agg_call| PlanAggCall {
+                inputs: vec![InputRef::new(
+                    distinct_pos,
+                    agg_call.inputs[0].return_type(),
+                )],
+                distinct: false,
+                ..agg_call.clone()
+            })
+            .collect();
+        Some(LogicalAgg::new(
+            agg_calls,
+            (0..self.group_keys.len()).collect(),
+            inner.into(),
+        ))
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalAgg {
@@ -623,6 +974,9 @@ impl ToStream for LogicalAgg {
     }
 
     fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        if let Some(agg) = self.split_distinct_agg() {
+            return agg.logical_rewrite_for_stream();
+        }
         let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
         let (agg, out_col_change) = self.rewrite_with_input(input, input_col_change);
 
@@ -757,6 +1111,32 @@ mod tests {
             assert_eq!(group_keys, vec![0]);
         }
 
+        // Test case: select stddev_samp(v1) from test;
+        {
+            let stddev_v1 =
+                AggCall::new(AggKind::StddevSamp, vec![input_ref_1.clone().into()], false).unwrap();
+            let select_exprs = vec![stddev_v1.into()];
+
+            let (exprs, agg_calls, group_keys) = gen_internal_value(select_exprs, vec![]);
+
+            if let ExprImpl::FunctionCall(func_call) = &exprs[0] {
+                assert_eq!(func_call.get_expr_type(), ExprType::Sqrt);
+            } else {
+                panic!("Wrong expression type!");
+            }
+
+            // sum(v1 * v1), sum(v1) and count(v1) in double precision.
+            assert_eq!(agg_calls.len(), 3);
+            assert_eq!(agg_calls[0].agg_kind, AggKind::Sum);
+            assert_eq!(input_ref_to_column_indices(&agg_calls[0].inputs), vec![0]);
+            assert_eq!(agg_calls[1].agg_kind, AggKind::Sum);
+            assert_eq!(input_ref_to_column_indices(&agg_calls[1].inputs), vec![1]);
+            assert_eq!(agg_calls[2].agg_kind, AggKind::Count);
+            assert_eq!(input_ref_to_column_indices(&agg_calls[2].inputs), vec![1]);
+            assert_eq!(agg_calls[0].return_type, DataType::Float64);
+            assert!(group_keys.is_empty());
+        }
+
         // Test case: select v2, min(v1 * v3) from test group by v2;
         {
             let v1_mult_v3 = FunctionCall::new(
@@ -88,6 +88,11 @@ impl LogicalApply {
         self.join_type
     }
 
+    /// Get a reference to the logical apply's on condition.
+    pub fn on(&self) -> &Condition {
+        &self.on
+    }
+
     pub fn decompose(self) -> (PlanRef, PlanRef, Condition, JoinType) {
         (self.left, self.right, self.on, self.join_type)
     }
@@ -0,0 +1,138 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use super::{
+    ColPrunable, LogicalFilter, LogicalProject, LogicalUnion, PlanBase, PlanRef,
+    PlanTreeNodeBinary, PredicatePushdown, ToBatch, ToStream,
+};
+use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef, Literal};
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalExcept` outputs the distinct rows of its left input that don't appear in its right
+/// input.
+///
+/// It's rewritten to an aggregation which counts the rows of each input for every distinct row,
+/// when being converted to a batch or stream plan.
+#[derive(Debug, Clone)]
+pub struct LogicalExcept {
+    pub base: PlanBase,
+    left: PlanRef,
+    right: PlanRef,
+}
+
+impl LogicalExcept {
+    pub fn new(left: PlanRef, right: PlanRef) -> Self {
+        assert_eq!(left.schema().data_types(), right.schema().data_types());
+        let ctx = left.ctx();
+        let schema = left.schema().clone();
+        let base = PlanBase::new_logical(ctx, schema, vec![]);
+        LogicalExcept { base, left, right }
+    }
+
+    pub fn create(left: PlanRef, right: PlanRef) -> PlanRef {
+        Self::new(left, right).into()
+    }
+
+    /// Keeps the rows that appear in the left input but not in the right one.
+    fn to_agg(&self) -> PlanRef {
+        let col_num = self.schema().len();
+        let counts = LogicalUnion::count_by_input(vec![self.left.clone(), self.right.clone()]);
+        let count_cmp = |idx: usize, func_type: ExprType| -> ExprImpl {
+            FunctionCall::new(
+                func_type,
+                vec![
+                    InputRef::new(idx, DataType::Int64).into(),
+                    Literal::new(Some(ScalarImpl::Int64(0)), DataType::Int64).into(),
+                ],
+            )
+            .unwrap()
+            .into()
+        };
+        let predicate = Condition {
+            conjunctions: vec![
+                count_cmp(col_num, ExprType::GreaterThan),
+                count_cmp(col_num + 1, ExprType::Equal),
+            ],
+        };
+        let filter = LogicalFilter::create(counts, predicate);
+        let mapping =
+            ColIndexMapping::with_remaining_columns(&(0..col_num).collect_vec(), col_num + 2);
+        LogicalProject::with_mapping(filter, mapping).into()
+    }
+}
+
+impl PlanTreeNodeBinary for LogicalExcept {
+    fn left(&self) -> PlanRef {
+        self.left.clone()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.right.clone()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(left, right)
+    }
+}
+
+impl_plan_tree_node_for_binary! { LogicalExcept }
+
+impl fmt::Display for LogicalExcept {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LogicalExcept")
+    }
+}
+
+impl ColPrunable for LogicalExcept {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        // All the columns are needed to compare the rows.
+        let all_cols = (0..self.schema().len()).collect_vec();
+        let new = Self::new(
+            self.left.prune_col(&all_cols),
+            self.right.prune_col(&all_cols),
+        );
+        let mapping = ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
+        LogicalProject::with_mapping(new.into(), mapping).into()
+    }
+}
+
+impl PredicatePushdown for LogicalExcept {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        let left = self.left.predicate_pushdown(predicate.clone());
+        let right = self.right.predicate_pushdown(predicate);
+        Self::create(left, right)
+    }
+}
+
+impl ToBatch for LogicalExcept {
+    fn to_batch(&self) -> Result<PlanRef> {
+        self.to_agg().to_batch()
+    }
+}
+
+impl ToStream for LogicalExcept {
+    fn to_stream(&self) -> Result<PlanRef> {
+        self.to_agg().to_stream()
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        self.to_agg().logical_rewrite_for_stream()
+    }
+}
@@ -14,7 +14,7 @@
 
 use std::fmt;
 
-use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::catalog::{ColumnId, Field, Schema};
 use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
 
@@ -34,12 +34,18 @@ pub struct LogicalInsert {
     pub base: PlanBase,
     table_source_name: String, // explain-only
     source_id: TableId,        // TODO: use SourceId
+    column_ids: Vec<ColumnId>,
     input: PlanRef,
 }
 
 impl LogicalInsert {
     /// Create a [`LogicalInsert`] node. Used internally by optimizer.
-    pub fn new(input: PlanRef, table_source_name: String, source_id: TableId) -> Self {
+    pub fn new(
+        input: PlanRef,
+        table_source_name: String,
+        source_id: TableId,
+        column_ids: Vec<ColumnId>,
+    ) -> Self {
         let ctx = input.ctx();
         let schema = Schema::new(vec![Field::unnamed(DataType::Int64)]);
         let base = PlanBase::new_logical(ctx, schema, vec![]);
@@ -47,13 +53,19 @@ impl LogicalInsert {
             base,
             table_source_name,
             source_id,
+            column_ids,
             input,
         }
     }
 
     /// Create a [`LogicalInsert`] node. Used by planner.
-    pub fn create(input: PlanRef, table_source_name: String, source_id: TableId) -> Result<Self> {
-        Ok(Self::new(input, table_source_name, source_id))
+    pub fn create(
+        input: PlanRef,
+        table_source_name: String,
+        source_id: TableId,
+        column_ids: Vec<ColumnId>,
+    ) -> Result<Self> {
+        Ok(Self::new(input, table_source_name, source_id, column_ids))
     }
 
     pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
@@ -65,6 +77,11 @@ impl LogicalInsert {
     pub fn source_id(&self) -> TableId {
         self.source_id
     }
+
+    /// Get the ids of the columns inserted into, which are output by the input in order.
+    pub fn column_ids(&self) -> &[ColumnId] {
+        &self.column_ids
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalInsert {
@@ -73,7 +90,12 @@ impl PlanTreeNodeUnary for LogicalInsert {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        Self::new(input, self.table_source_name.clone(), self.source_id)
+        Self::new(
+            input,
+            self.table_source_name.clone(),
+            self.source_id,
+            self.column_ids.clone(),
+        )
     }
 }
 
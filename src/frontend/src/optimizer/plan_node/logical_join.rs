@@ -22,9 +22,9 @@ use risingwave_pb::plan_common::JoinType;
 
 use super::{
     ColPrunable, CollectInputRef, LogicalProject, PlanBase, PlanRef, PlanTreeNodeBinary,
-    PredicatePushdown, StreamHashJoin, ToBatch, ToStream,
+    PlanTreeNodeUnary, PredicatePushdown, StreamDynamicFilter, StreamHashJoin, ToBatch, ToStream,
 };
-use crate::expr::{ExprImpl, ExprType};
+use crate::expr::{Expr, ExprImpl, ExprType};
 use crate::optimizer::plan_node::batch_nested_loop_join::BatchNestedLoopJoin;
 use crate::optimizer::plan_node::{
     BatchFilter, BatchHashJoin, EqJoinPredicate, LogicalFilter, StreamFilter,
@@ -577,6 +577,93 @@ impl ToBatch for LogicalJoin {
     }
 }
 
+impl LogicalJoin {
+    /// Returns the left column, the comparator and the right column if the join can be converted
+    /// to a [`StreamDynamicFilter`], i.e. it's an inner join which compares a left column with the
+    /// right input of at most one row, e.g. `WHERE t.v > (SELECT max(v) FROM s)`.
+    fn dynamic_filter_predicate(&self) -> Option<(usize, ExprType, usize)> {
+        if self.join_type != JoinType::Inner
+            || self.on.conjunctions.len() != 1
+            || !Self::max_one_row(&self.right)
+        {
+            return None;
+        }
+        let left_col_num = self.left.schema().len();
+        let (ty, x, y) = match self.on.conjunctions[0].clone() {
+            ExprImpl::FunctionCall(function_call) if function_call.inputs().len() == 2 => {
+                match function_call.decompose_as_binary() {
+                    (ty, ExprImpl::InputRef(x), ExprImpl::InputRef(y)) => (ty, x, y),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        if x.return_type() != y.return_type() {
+            return None;
+        }
+        let (comparator, left_ref, right_ref) =
+            if x.index() < left_col_num && y.index() >= left_col_num {
+                (ty, x, y)
+            } else if y.index() < left_col_num && x.index() >= left_col_num {
+                // Swap the operands, e.g. `a < b` to `b > a`.
+                let ty = match ty {
+                    ExprType::LessThan => ExprType::GreaterThan,
+                    ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
+                    ExprType::GreaterThan => ExprType::LessThan,
+                    ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
+                    _ => return None,
+                };
+                (ty, y, x)
+            } else {
+                return None;
+            };
+        match comparator {
+            ExprType::LessThan
+            | ExprType::LessThanOrEqual
+            | ExprType::GreaterThan
+            | ExprType::GreaterThanOrEqual => Some((
+                left_ref.index(),
+                comparator,
+                right_ref.index() - left_col_num,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Converts the join to a [`StreamDynamicFilter`] if possible. Only the left columns are
+    /// output by the dynamic filter, so the caller must make sure that the right columns are not
+    /// required.
+    pub(super) fn to_stream_dynamic_filter(&self) -> Result<Option<PlanRef>> {
+        let (left_index, comparator, right_index) = match self.dynamic_filter_predicate() {
+            Some(predicate) => predicate,
+            None => return Ok(None),
+        };
+        let left = self
+            .left
+            .to_stream_with_dist_required(&RequiredDist::single())?;
+        let right = LogicalProject::with_mapping(
+            self.right.clone(),
+            ColIndexMapping::with_remaining_columns(&[right_index], self.right.schema().len()),
+        )
+        .to_stream_with_dist_required(&RequiredDist::single())?;
+        Ok(Some(
+            StreamDynamicFilter::new(left_index, comparator, left, right).into(),
+        ))
+    }
+
+    /// Whether the plan outputs at most one row, i.e. it's an aggregation without group keys under
+    /// some projections.
+    fn max_one_row(plan: &PlanRef) -> bool {
+        if let Some(agg) = plan.as_logical_agg() {
+            agg.group_keys().is_empty()
+        } else if let Some(project) = plan.as_logical_project() {
+            Self::max_one_row(&project.input())
+        } else {
+            false
+        }
+    }
+}
+
 impl ToStream for LogicalJoin {
     fn to_stream(&self) -> Result<PlanRef> {
         let predicate = EqJoinPredicate::create(
@@ -630,8 +717,13 @@ impl ToStream for LogicalJoin {
     fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
         let (left, left_col_change) = self.left.logical_rewrite_for_stream()?;
         let (right, right_col_change) = self.right.logical_rewrite_for_stream()?;
-        let (join, out_col_change) =
+        let (mut join, out_col_change) =
             self.rewrite_with_left_right(left, left_col_change, right, right_col_change);
+        // The right input of a dynamic filter has at most one row, so the left pk is enough to
+        // identify the output rows, and the right columns need not be kept by the parents.
+        if join.dynamic_filter_predicate().is_some() {
+            join.base.pk_indices = join.left.pk_indices().to_vec();
+        }
         Ok((join.into(), out_col_change))
     }
 }
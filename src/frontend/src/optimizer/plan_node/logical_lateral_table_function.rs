@@ -0,0 +1,219 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::TableFunction as ProstTableFunction;
+
+use super::{
+    gen_filter_and_pushdown, BatchLateralTableFunction, ColPrunable, LogicalProject, PlanBase,
+    PlanRef, PlanTreeNodeUnary, PredicatePushdown, StreamLateralTableFunction, ToBatch, ToStream,
+};
+use crate::binder::TableFunctionKind;
+use crate::expr::{Expr, ExprImpl, ExprRewriter};
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalLateralTableFunction` evaluates a table function, whose arguments reference the input
+/// columns, on each input row. It outputs the input row followed by each returned row and its
+/// 1-based `ordinality`, which makes the output rows unique together with the input pk.
+#[derive(Debug, Clone)]
+pub struct LogicalLateralTableFunction {
+    pub base: PlanBase,
+    input: PlanRef,
+    pub(super) kind: TableFunctionKind,
+    pub(super) args: Vec<ExprImpl>,
+}
+
+impl LogicalLateralTableFunction {
+    pub fn new(input: PlanRef, kind: TableFunctionKind, args: Vec<ExprImpl>) -> Self {
+        let ctx = input.ctx();
+        let schema: Schema = input
+            .schema()
+            .fields()
+            .iter()
+            .cloned()
+            .chain(kind.output_fields())
+            .chain(std::iter::once(Field::with_name(
+                DataType::Int64,
+                "ordinality",
+            )))
+            .collect();
+        let mut pk_indices = input.pk_indices().to_vec();
+        pk_indices.push(schema.len() - 1);
+        let base = PlanBase::new_logical(ctx, schema, pk_indices);
+        Self {
+            base,
+            input,
+            kind,
+            args,
+        }
+    }
+
+    /// Create a [`LogicalLateralTableFunction`] node. Used by planner.
+    pub fn create(input: PlanRef, kind: TableFunctionKind, args: Vec<ExprImpl>) -> PlanRef {
+        Self::new(input, kind, args).into()
+    }
+
+    pub fn ordinality_col_idx(&self) -> usize {
+        self.schema().len() - 1
+    }
+
+    pub fn o2i_col_mapping(&self) -> ColIndexMapping {
+        ColIndexMapping::identity_or_none(self.schema().len(), self.input.schema().len())
+    }
+
+    pub fn i2o_col_mapping(&self) -> ColIndexMapping {
+        ColIndexMapping::identity_or_none(self.input.schema().len(), self.schema().len())
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ {}({}) }}",
+            name,
+            self.kind.name(),
+            self.args.iter().map(|arg| format!("{:?}", arg)).join(", ")
+        )
+    }
+
+    pub fn to_protobuf(&self) -> ProstTableFunction {
+        ProstTableFunction {
+            function_type: self.kind.to_protobuf() as i32,
+            args: self.args.iter().map(|arg| arg.to_expr_proto()).collect(),
+            return_types: self
+                .kind
+                .output_fields()
+                .iter()
+                .map(|f| f.data_type().to_protobuf())
+                .collect(),
+        }
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalLateralTableFunction {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.kind, self.args.clone())
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        mut input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let args = self
+            .args
+            .clone()
+            .into_iter()
+            .map(|arg| input_col_change.rewrite_expr(arg))
+            .collect();
+        let new_node = Self::new(input, self.kind, args);
+
+        // The columns after the input ones are shifted to follow the new input columns.
+        let (mut mapping, new_input_col_num) = input_col_change.into_parts();
+        let old_input_col_num = self.input.schema().len();
+        mapping.extend(
+            (old_input_col_num..self.schema().len())
+                .map(|idx| Some(idx - old_input_col_num + new_input_col_num)),
+        );
+        (new_node, ColIndexMapping::new(mapping))
+    }
+}
+
+impl_plan_tree_node_for_unary! { LogicalLateralTableFunction }
+
+impl fmt::Display for LogicalLateralTableFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalLateralTableFunction")
+    }
+}
+
+impl ColPrunable for LogicalLateralTableFunction {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_col_num = self.input.schema().len();
+        let input_required_cols = {
+            let mut tmp = FixedBitSet::with_capacity(input_col_num);
+            tmp.extend(
+                required_cols
+                    .iter()
+                    .copied()
+                    .filter(|&idx| idx < input_col_num),
+            );
+            for arg in &self.args {
+                tmp.union_with(&arg.collect_input_refs(input_col_num));
+            }
+            tmp.ones().collect_vec()
+        };
+        let input = self.input.prune_col(&input_required_cols);
+        let input_change =
+            ColIndexMapping::with_remaining_columns(&input_required_cols, input_col_num);
+        let (new_node, out_col_change) = self.rewrite_with_input(input, input_change);
+
+        // All the function outputs are produced together, so only a projection on top is needed.
+        let output_cols = required_cols
+            .iter()
+            .map(|&idx| out_col_change.map(idx))
+            .collect_vec();
+        if output_cols.iter().copied().eq(0..new_node.schema().len()) {
+            return new_node.into();
+        }
+        let new_col_num = new_node.schema().len();
+        LogicalProject::with_mapping(
+            new_node.into(),
+            ColIndexMapping::with_remaining_columns(&output_cols, new_col_num),
+        )
+        .into()
+    }
+}
+
+impl PredicatePushdown for LogicalLateralTableFunction {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        // The conditions on the input columns only can be evaluated before the function.
+        let mut function_cols = FixedBitSet::with_capacity(self.schema().len());
+        function_cols.insert_range(self.input.schema().len()..);
+        let (function_pred, input_pred) = predicate.split_disjoint(&function_cols);
+        gen_filter_and_pushdown(self, function_pred, input_pred)
+    }
+}
+
+impl ToBatch for LogicalLateralTableFunction {
+    fn to_batch(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_batch()?;
+        let new_logical = self.clone_with_input(new_input);
+        Ok(BatchLateralTableFunction::new(new_logical).into())
+    }
+}
+
+impl ToStream for LogicalLateralTableFunction {
+    fn to_stream(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_stream()?;
+        let new_logical = self.clone_with_input(new_input);
+        Ok(StreamLateralTableFunction::new(new_logical).into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (new_node, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((new_node.into(), out_col_change))
+    }
+}
@@ -14,12 +14,13 @@
 
 use std::fmt;
 
-use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::error::Result;
 
 use super::{
-    gen_filter_and_pushdown, BatchLimit, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchLimit, ColPrunable, LogicalTopN, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, ToBatch, ToStream,
 };
+use crate::optimizer::property::Order;
 use crate::utils::{ColIndexMapping, Condition};
 
 /// `LogicalLimit` fetches up to `limit` rows from `offset`
@@ -110,11 +111,10 @@ impl ToBatch for LogicalLimit {
 }
 
 impl ToStream for LogicalLimit {
+    /// A limit in streaming is a top-n without order, which keeps the rows with the smallest
+    /// stream keys.
     fn to_stream(&self) -> Result<PlanRef> {
-        Err(RwError::from(ErrorCode::NotImplemented(
-            "there is no limit stream operator".to_string(),
-            None.into(),
-        )))
+        LogicalTopN::new(self.input(), self.limit, self.offset, Order::any().clone()).to_stream()
     }
 
     fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
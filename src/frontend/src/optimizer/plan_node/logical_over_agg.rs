@@ -0,0 +1,503 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
+use risingwave_expr::expr::AggKind;
+use risingwave_expr::window_function::{Frame, WindowFuncKind};
+use risingwave_pb::expr::WindowFunction as ProstWindowFunction;
+
+use super::{
+    gen_filter_and_pushdown, BatchOverAgg, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, StreamOverAgg, ToBatch, ToStream,
+};
+use crate::expr::{
+    AggCall, Expr, ExprImpl, ExprRewriter, ExprType, ExprVisitor, FunctionCall, InputRef,
+    InputRefDisplay, WindowFunction,
+};
+use crate::optimizer::property::{Direction, FieldOrder, RequiredDist};
+use crate::utils::{ColIndexMapping, Condition};
+
+/// A window function call in [`LogicalOverAgg`], whose arguments are columns of the input.
+#[derive(Clone)]
+pub struct PlanWindowFunction {
+    pub kind: WindowFuncKind,
+    pub return_type: DataType,
+    pub args: Vec<InputRef>,
+    pub frame: Frame,
+    /// Offset of `lag` and `lead`.
+    pub offset: usize,
+}
+
+impl fmt::Debug for PlanWindowFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut builder = f.debug_tuple(&format!("{}", self.kind));
+        self.args.iter().for_each(|child| {
+            builder.field(child);
+        });
+        builder.finish()?;
+        match self.kind {
+            WindowFuncKind::Lag | WindowFuncKind::Lead if self.offset != 1 => {
+                write!(f, " offset {}", self.offset)
+            }
+            WindowFuncKind::Aggregate(_) => write!(f, " {}", self.frame),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl PlanWindowFunction {
+    pub fn to_protobuf(&self) -> ProstWindowFunction {
+        ProstWindowFunction {
+            r#type: Some(self.kind.to_protobuf()),
+            args: self.args.iter().map(InputRef::to_agg_arg_proto).collect(),
+            return_type: Some(self.return_type.to_protobuf()),
+            frame: Some(self.frame.to_protobuf()),
+            offset: self.offset as u64,
+        }
+    }
+}
+
+/// `LogicalOverAgg` evaluates window functions sharing the same `PARTITION BY` and `ORDER BY`.
+///
+/// The output schema is the input columns followed by the results of the window functions. Unlike
+/// [`LogicalAgg`](super::LogicalAgg), it produces exactly one output row for each input row.
+#[derive(Debug, Clone)]
+pub struct LogicalOverAgg {
+    pub base: PlanBase,
+    window_functions: Vec<PlanWindowFunction>,
+    partition_by: Vec<usize>,
+    order_by: Vec<FieldOrder>,
+    input: PlanRef,
+}
+
+impl LogicalOverAgg {
+    pub fn new(
+        window_functions: Vec<PlanWindowFunction>,
+        partition_by: Vec<usize>,
+        order_by: Vec<FieldOrder>,
+        input: PlanRef,
+    ) -> Self {
+        let ctx = input.ctx();
+        let schema = Self::derive_schema(input.schema(), &window_functions);
+        let pk_indices = input.pk_indices().to_vec();
+        let base = PlanBase::new_logical(ctx, schema, pk_indices);
+        LogicalOverAgg {
+            base,
+            window_functions,
+            partition_by,
+            order_by,
+            input,
+        }
+    }
+
+    fn derive_schema(input: &Schema, window_functions: &[PlanWindowFunction]) -> Schema {
+        let fields = input
+            .fields()
+            .iter()
+            .cloned()
+            .chain(window_functions.iter().enumerate().map(|(id, call)| {
+                let name = format!("window#{}", id);
+                Field::with_name(call.return_type.clone(), name)
+            }))
+            .collect();
+        Schema { fields }
+    }
+
+    /// `create` extracts the window functions in select exprs, and constructs a plan like
+    ///
+    /// ```text
+    /// LogicalOverAgg -> ... -> LogicalOverAgg -> LogicalProject -> input
+    /// ```
+    ///
+    /// with one `LogicalOverAgg` for each distinct window (`PARTITION BY` and `ORDER BY`). The
+    /// `LogicalProject` appends the arguments and window keys that are not simple columns.
+    ///
+    /// It also returns the rewritten select exprs that reference into the window results.
+    pub fn create(input: PlanRef, select_exprs: Vec<ExprImpl>) -> Result<(PlanRef, Vec<ExprImpl>)> {
+        let mut collector = WindowFunctionCollector::default();
+        select_exprs
+            .iter()
+            .for_each(|expr| collector.visit_expr(expr));
+
+        let input_len = input.schema().len();
+        let mut project = input
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(index, field)| InputRef::new(index, field.data_type()).into())
+            .collect_vec();
+        let mut expr_index: HashMap<ExprImpl, usize> = project
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(index, expr)| (expr, index))
+            .collect();
+        let mut input_ref_of = |expr: ExprImpl| -> Result<InputRef> {
+            if expr.has_subquery() {
+                return Err(ErrorCode::NotImplemented(
+                    "subquery in window function".into(),
+                    None.into(),
+                )
+                .into());
+            }
+            let data_type = expr.return_type();
+            let index = *expr_index.entry(expr.clone()).or_insert_with(|| {
+                project.push(expr);
+                project.len() - 1
+            });
+            Ok(InputRef::new(index, data_type))
+        };
+
+        // Window functions over the same window are evaluated by the same operator.
+        let mut windows: Vec<(Vec<usize>, Vec<(usize, Direction)>, Vec<PlanWindowFunction>)> =
+            vec![];
+        // The window and the position of the (first) call of each window function.
+        let mut positions = Vec::with_capacity(collector.window_functions.len());
+        for window_function in &collector.window_functions {
+            let WindowFunction {
+                kind,
+                args,
+                return_type,
+                partition_by,
+                order_by,
+                frame,
+                offset,
+            } = window_function.clone();
+            let args: Vec<InputRef> = args.into_iter().map(&mut input_ref_of).try_collect()?;
+            let partition_by: Vec<usize> = partition_by
+                .into_iter()
+                .map(|expr| input_ref_of(expr).map(|input_ref| input_ref.index()))
+                .try_collect()?;
+            let order_by: Vec<(usize, Direction)> = order_by
+                .into_iter()
+                .map(|(expr, direct)| {
+                    input_ref_of(expr).map(|input_ref| (input_ref.index(), direct))
+                })
+                .try_collect()?;
+
+            let window_idx = match windows
+                .iter()
+                .position(|(p, o, _)| *p == partition_by && *o == order_by)
+            {
+                Some(window_idx) => window_idx,
+                None => {
+                    windows.push((partition_by, order_by, vec![]));
+                    windows.len() - 1
+                }
+            };
+            let calls = &mut windows[window_idx].2;
+            positions.push((window_idx, calls.len()));
+
+            if kind == WindowFuncKind::Aggregate(AggKind::Avg) {
+                // Rewrite avg to cast(sum as avg_return_type) / count.
+                let arg_types = args.iter().map(InputRef::return_type).collect_vec();
+                for agg_kind in [AggKind::Sum, AggKind::Count] {
+                    calls.push(PlanWindowFunction {
+                        return_type: AggCall::infer_return_type(&agg_kind, &arg_types)?,
+                        kind: WindowFuncKind::Aggregate(agg_kind),
+                        args: args.clone(),
+                        frame,
+                        offset,
+                    });
+                }
+            } else {
+                calls.push(PlanWindowFunction {
+                    kind,
+                    return_type,
+                    args,
+                    frame,
+                    offset,
+                });
+            }
+        }
+
+        let mut root = if project.len() > input_len {
+            LogicalProject::create(input, project)
+        } else {
+            input
+        };
+        let mut window_offsets = Vec::with_capacity(windows.len());
+        for (partition_by, order_by, calls) in windows {
+            window_offsets.push(root.schema().len());
+            let order_by = order_by
+                .into_iter()
+                .map(|(index, direct)| FieldOrder { index, direct })
+                .collect();
+            root = Self::new(calls, partition_by, order_by, root).into();
+        }
+
+        let mut rewriter = WindowFunctionRewriter {
+            results: HashMap::new(),
+        };
+        for (window_function, (window_idx, position)) in
+            collector.window_functions.into_iter().zip_eq(positions)
+        {
+            let index = window_offsets[window_idx] + position;
+            let result = if window_function.kind == WindowFuncKind::Aggregate(AggKind::Avg) {
+                let sum_type = root.schema().fields()[index].data_type();
+                let count_type = root.schema().fields()[index + 1].data_type();
+                let sum = ExprImpl::from(InputRef::new(index, sum_type))
+                    .cast_implicit(window_function.return_type.clone())?;
+                let count = InputRef::new(index + 1, count_type);
+                FunctionCall::new(ExprType::Divide, vec![sum, count.into()])?.into()
+            } else {
+                InputRef::new(index, window_function.return_type.clone()).into()
+            };
+            rewriter.results.insert(window_function, result);
+        }
+        let select_exprs = select_exprs
+            .into_iter()
+            .map(|expr| rewriter.rewrite_expr(expr))
+            .collect();
+
+        Ok((root, select_exprs))
+    }
+
+    pub fn window_functions(&self) -> &[PlanWindowFunction] {
+        self.window_functions.as_ref()
+    }
+
+    pub fn partition_by(&self) -> &[usize] {
+        self.partition_by.as_ref()
+    }
+
+    pub fn order_by(&self) -> &[FieldOrder] {
+        self.order_by.as_ref()
+    }
+
+    pub fn o2i_col_mapping(&self) -> ColIndexMapping {
+        ColIndexMapping::identity_or_none(self.schema().len(), self.input.schema().len())
+    }
+
+    pub fn i2o_col_mapping(&self) -> ColIndexMapping {
+        ColIndexMapping::identity_or_none(self.input.schema().len(), self.schema().len())
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        f.debug_struct(name)
+            .field("window_functions", &self.window_functions)
+            .field(
+                "partition_by",
+                &self
+                    .partition_by
+                    .iter()
+                    .copied()
+                    .map(InputRefDisplay)
+                    .collect_vec(),
+            )
+            .field("order_by", &self.order_by)
+            .finish()
+    }
+}
+
+/// Collects the distinct window functions in the order they appear.
+#[derive(Default)]
+struct WindowFunctionCollector {
+    window_functions: Vec<WindowFunction>,
+}
+
+impl ExprVisitor for WindowFunctionCollector {
+    fn visit_window_function(&mut self, window_function: &WindowFunction) {
+        if !self.window_functions.contains(window_function) {
+            self.window_functions.push(window_function.clone());
+        }
+    }
+}
+
+/// Replaces window functions with their results computed by [`LogicalOverAgg`].
+struct WindowFunctionRewriter {
+    results: HashMap<WindowFunction, ExprImpl>,
+}
+
+impl ExprRewriter for WindowFunctionRewriter {
+    fn rewrite_window_function(&mut self, window_function: WindowFunction) -> ExprImpl {
+        self.results[&window_function].clone()
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalOverAgg {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(
+            self.window_functions.clone(),
+            self.partition_by.clone(),
+            self.order_by.clone(),
+            input,
+        )
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let window_functions = self
+            .window_functions
+            .iter()
+            .cloned()
+            .map(|mut call| {
+                call.args.iter_mut().for_each(|i| {
+                    *i = InputRef::new(input_col_change.map(i.index()), i.return_type())
+                });
+                call
+            })
+            .collect();
+        let partition_by = self
+            .partition_by
+            .iter()
+            .map(|&index| input_col_change.map(index))
+            .collect();
+        let order_by = self
+            .order_by
+            .iter()
+            .map(|order| FieldOrder {
+                index: input_col_change.map(order.index),
+                direct: order.direct,
+            })
+            .collect();
+        let over_agg = Self::new(window_functions, partition_by, order_by, input.clone());
+
+        let (mut mapping, new_input_col_num) = input_col_change.into_parts();
+        assert_eq!(new_input_col_num, input.schema().len());
+        mapping.extend(
+            (new_input_col_num..over_agg.schema().len())
+                .into_iter()
+                .map(Some),
+        );
+
+        (over_agg, ColIndexMapping::new(mapping))
+    }
+}
+
+impl_plan_tree_node_for_unary! {LogicalOverAgg}
+
+impl fmt::Display for LogicalOverAgg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalOverAgg")
+    }
+}
+
+impl ColPrunable for LogicalOverAgg {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_len = self.input.schema().len();
+        let mut required_calls = required_cols
+            .iter()
+            .filter(|&&index| index >= input_len)
+            .map(|&index| index - input_len)
+            .collect_vec();
+        if required_calls.is_empty() {
+            // None of the window functions is used, so the operator can be removed.
+            return self.input.prune_col(required_cols);
+        }
+        required_calls.sort_unstable();
+        required_calls.dedup();
+
+        let input_required_cols = {
+            let mut tmp = FixedBitSet::with_capacity(input_len);
+            tmp.extend(
+                required_cols
+                    .iter()
+                    .copied()
+                    .filter(|&index| index < input_len),
+            );
+            tmp.extend(self.partition_by.iter().copied());
+            tmp.extend(self.order_by.iter().map(|order| order.index));
+            for &call_idx in &required_calls {
+                tmp.extend(
+                    self.window_functions[call_idx]
+                        .args
+                        .iter()
+                        .map(|i| i.index()),
+                );
+            }
+            tmp.ones().collect_vec()
+        };
+        let input = self.input.prune_col(&input_required_cols);
+        let input_change = ColIndexMapping::with_remaining_columns(&input_required_cols, input_len);
+
+        let over_agg = Self::new(
+            required_calls
+                .iter()
+                .map(|&call_idx| self.window_functions[call_idx].clone())
+                .collect(),
+            self.partition_by.clone(),
+            self.order_by.clone(),
+            self.input.clone(),
+        );
+        let (new_over_agg, out_col_change) = over_agg.rewrite_with_input(input, input_change);
+
+        let output_cols = required_cols
+            .iter()
+            .map(|&index| {
+                if index < input_len {
+                    out_col_change.map(index)
+                } else {
+                    let position = required_calls.binary_search(&(index - input_len)).unwrap();
+                    out_col_change.map(input_len + position)
+                }
+            })
+            .collect_vec();
+        LogicalProject::with_mapping(
+            new_over_agg.clone().into(),
+            ColIndexMapping::with_remaining_columns(&output_cols, new_over_agg.schema().len()),
+        )
+        .into()
+    }
+}
+
+impl PredicatePushdown for LogicalOverAgg {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        gen_filter_and_pushdown(self, predicate, Condition::true_cond())
+    }
+}
+
+impl ToBatch for LogicalOverAgg {
+    fn to_batch(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_batch()?;
+        let new_logical = self.clone_with_input(new_input);
+        Ok(BatchOverAgg::new(new_logical).into())
+    }
+}
+
+impl ToStream for LogicalOverAgg {
+    fn to_stream(&self) -> Result<PlanRef> {
+        // Rows of the same partition must be processed by the same actor.
+        let required_dist = if self.partition_by.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::shard_by_key(self.input().schema().len(), &self.partition_by)
+        };
+        let new_input = self.input().to_stream_with_dist_required(&required_dist)?;
+        let new_logical = self.clone_with_input(new_input);
+        Ok(StreamOverAgg::new(new_logical).into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (over_agg, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((over_agg.into(), out_col_change))
+    }
+}
@@ -21,8 +21,8 @@ use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::Result;
 
 use super::{
-    gen_filter_and_pushdown, BatchProject, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, StreamProject, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchProject, ColPrunable, PlanBase, PlanRef, PlanTreeNodeBinary,
+    PlanTreeNodeUnary, PredicatePushdown, StreamProject, ToBatch, ToStream,
 };
 use crate::expr::{assert_input_ref, Expr, ExprImpl, ExprRewriter, ExprVisitor, InputRef};
 use crate::optimizer::plan_node::CollectInputRef;
@@ -255,6 +255,29 @@ impl ToBatch for LogicalProject {
     }
 }
 
+impl LogicalProject {
+    /// Converts the input to a [`super::StreamDynamicFilter`] if it's a join comparing the left
+    /// input with a scalar subquery, and no right column is required. The left columns are kept in
+    /// place by the dynamic filter, so the expressions are still valid on it.
+    fn input_to_stream_dynamic_filter(&self) -> Result<Option<PlanRef>> {
+        let join = match self.input.as_logical_join() {
+            Some(join) => join,
+            None => return Ok(None),
+        };
+        let input_col_num = self.input.schema().len();
+        let left_col_num = join.left().schema().len();
+        let only_left_required = self.exprs.iter().all(|expr| {
+            expr.collect_input_refs(input_col_num)
+                .ones()
+                .all(|idx| idx < left_col_num)
+        });
+        if !only_left_required {
+            return Ok(None);
+        }
+        join.to_stream_dynamic_filter()
+    }
+}
+
 impl ToStream for LogicalProject {
     fn to_stream_with_dist_required(&self, required_dist: &RequiredDist) -> Result<PlanRef> {
         let input_required = if required_dist.satisfies(&RequiredDist::AnyShard) {
@@ -271,7 +294,12 @@ impl ToStream for LogicalProject {
                 _ => input_required,
             }
         };
-        let new_input = self.input().to_stream_with_dist_required(&input_required)?;
+        let new_input = match self.input_to_stream_dynamic_filter()? {
+            Some(dynamic_filter) => {
+                input_required.enforce_if_not_satisfies(dynamic_filter, Order::any())?
+            }
+            None => self.input().to_stream_with_dist_required(&input_required)?,
+        };
         let new_logical = self.clone_with_input(new_input);
         let stream_plan = StreamProject::new(new_logical);
         required_dist.enforce_if_not_satisfies(stream_plan.into(), Order::any())
@@ -0,0 +1,140 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use risingwave_common::array::Row;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{ErrorCode, Result};
+
+use super::{
+    BatchSysSeqScan, ColPrunable, LogicalFilter, PlanBase, PlanRef, PredicatePushdown, ToBatch,
+    ToStream,
+};
+use crate::session::OptimizerContextRef;
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalSysScan` returns the rows of a system table, which are generated from the catalog in
+/// the frontend.
+#[derive(Debug, Clone)]
+pub struct LogicalSysScan {
+    pub base: PlanBase,
+    table_name: String,
+    rows: Arc<[Row]>,
+}
+
+impl LogicalSysScan {
+    /// Create a [`LogicalSysScan`] node. Used internally by optimizer.
+    pub fn new(
+        table_name: String,
+        schema: Schema,
+        rows: Vec<Row>,
+        ctx: OptimizerContextRef,
+    ) -> Self {
+        let base = PlanBase::new_logical(ctx, schema, vec![]);
+        Self {
+            base,
+            table_name,
+            rows: rows.into(),
+        }
+    }
+
+    /// Create a [`LogicalSysScan`] node. Used by planner.
+    pub fn create(
+        table_name: String,
+        schema: Schema,
+        rows: Vec<Row>,
+        ctx: OptimizerContextRef,
+    ) -> PlanRef {
+        Self::new(table_name, schema, rows, ctx).into()
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        self.rows.as_ref()
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ table: {}, columns: [{}] }}",
+            name,
+            self.table_name,
+            self.schema().fields().iter().map(|f| &f.name).join(", ")
+        )
+    }
+}
+
+impl_plan_tree_node_for_leaf! { LogicalSysScan }
+
+impl fmt::Display for LogicalSysScan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalSysScan")
+    }
+}
+
+impl ColPrunable for LogicalSysScan {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| Row::new(required_cols.iter().map(|i| row.0[*i].clone()).collect()))
+            .collect();
+        let fields = required_cols
+            .iter()
+            .map(|i| self.schema().fields[*i].clone())
+            .collect();
+        Self::create(
+            self.table_name.clone(),
+            Schema { fields },
+            rows,
+            self.base.ctx.clone(),
+        )
+    }
+}
+
+impl PredicatePushdown for LogicalSysScan {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        LogicalFilter::create(self.clone().into(), predicate)
+    }
+}
+
+impl ToBatch for LogicalSysScan {
+    fn to_batch(&self) -> Result<PlanRef> {
+        Ok(BatchSysSeqScan::new(self.clone()).into())
+    }
+}
+
+impl ToStream for LogicalSysScan {
+    fn to_stream(&self) -> Result<PlanRef> {
+        Err(ErrorCode::NotImplemented(
+            "streaming on system tables is not supported".to_string(),
+            None.into(),
+        )
+        .into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        Err(ErrorCode::NotImplemented(
+            "streaming on system tables is not supported".to_string(),
+            None.into(),
+        )
+        .into())
+    }
+}
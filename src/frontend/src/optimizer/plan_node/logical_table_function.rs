@@ -0,0 +1,126 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::expr::TableFunction as ProstTableFunction;
+
+use super::{
+    BatchTableFunction, ColPrunable, LogicalFilter, LogicalProject, PlanBase, PlanRef,
+    PredicatePushdown, ToBatch, ToStream,
+};
+use crate::binder::TableFunctionKind;
+use crate::expr::{Expr, ExprImpl};
+use crate::session::OptimizerContextRef;
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalTableFunction` outputs the rows returned by a table function with constant arguments.
+#[derive(Debug, Clone)]
+pub struct LogicalTableFunction {
+    pub base: PlanBase,
+    pub(super) kind: TableFunctionKind,
+    pub(super) args: Vec<ExprImpl>,
+}
+
+impl LogicalTableFunction {
+    /// Create a [`LogicalTableFunction`] node. Used internally by optimizer.
+    pub fn new(kind: TableFunctionKind, args: Vec<ExprImpl>, ctx: OptimizerContextRef) -> Self {
+        let schema = Schema::new(kind.output_fields());
+        let base = PlanBase::new_logical(ctx, schema, vec![]);
+        Self { base, kind, args }
+    }
+
+    /// Create a [`LogicalTableFunction`] node. Used by planner.
+    pub fn create(
+        kind: TableFunctionKind,
+        args: Vec<ExprImpl>,
+        ctx: OptimizerContextRef,
+    ) -> PlanRef {
+        Self::new(kind, args, ctx).into()
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ {}({}) }}",
+            name,
+            self.kind.name(),
+            self.args.iter().map(|arg| format!("{:?}", arg)).join(", ")
+        )
+    }
+
+    pub fn to_protobuf(&self) -> ProstTableFunction {
+        ProstTableFunction {
+            function_type: self.kind.to_protobuf() as i32,
+            args: self.args.iter().map(|arg| arg.to_expr_proto()).collect(),
+            return_types: self
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.data_type().to_protobuf())
+                .collect(),
+        }
+    }
+}
+
+impl_plan_tree_node_for_leaf! { LogicalTableFunction }
+
+impl fmt::Display for LogicalTableFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalTableFunction")
+    }
+}
+
+impl ColPrunable for LogicalTableFunction {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        if required_cols.iter().copied().eq(0..self.schema().len()) {
+            return self.clone().into();
+        }
+        // All the columns are produced together, so only a projection on top is needed.
+        let mapping = ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
+        LogicalProject::with_mapping(self.clone().into(), mapping).into()
+    }
+}
+
+impl PredicatePushdown for LogicalTableFunction {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        LogicalFilter::create(self.clone().into(), predicate)
+    }
+}
+
+impl ToBatch for LogicalTableFunction {
+    fn to_batch(&self) -> Result<PlanRef> {
+        Ok(BatchTableFunction::new(self.clone()).into())
+    }
+}
+
+impl ToStream for LogicalTableFunction {
+    fn to_stream(&self) -> Result<PlanRef> {
+        Err(
+            ErrorCode::NotImplemented("LogicalTableFunction::to_stream".to_string(), None.into())
+                .into(),
+        )
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        Err(ErrorCode::NotImplemented(
+            "LogicalTableFunction::logical_rewrite_for_stream".to_string(),
+            None.into(),
+        )
+        .into())
+    }
+}
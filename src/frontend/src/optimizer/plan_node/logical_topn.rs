@@ -16,17 +16,21 @@ use std::fmt;
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
 
 use super::{
     gen_filter_and_pushdown, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary, PredicatePushdown,
     ToBatch, ToStream,
 };
-use crate::optimizer::plan_node::{BatchTopN, LogicalProject, StreamTopN};
+use crate::expr::InputRefDisplay;
+use crate::optimizer::plan_node::{BatchTopN, LogicalProject, StreamGroupTopN, StreamTopN};
 use crate::optimizer::property::{FieldOrder, Order, RequiredDist};
 use crate::utils::{ColIndexMapping, Condition};
 
 /// `LogicalTopN` sorts the input data and fetches up to `limit` rows from `offset`
+///
+/// If `group_key` is not empty, the rows are fetched from each group of the rows with the same
+/// values of the `group_key` instead.
 #[derive(Debug, Clone)]
 pub struct LogicalTopN {
     pub base: PlanBase,
@@ -34,10 +38,21 @@ pub struct LogicalTopN {
     limit: usize,
     offset: usize,
     order: Order,
+    group_key: Vec<usize>,
 }
 
 impl LogicalTopN {
     pub fn new(input: PlanRef, limit: usize, offset: usize, order: Order) -> Self {
+        Self::with_group(input, limit, offset, order, vec![])
+    }
+
+    pub fn with_group(
+        input: PlanRef,
+        limit: usize,
+        offset: usize,
+        order: Order,
+        group_key: Vec<usize>,
+    ) -> Self {
         let ctx = input.ctx();
         let schema = input.schema().clone();
         let pk_indices = input.pk_indices().to_vec();
@@ -48,6 +63,7 @@ impl LogicalTopN {
             limit,
             offset,
             order,
+            group_key,
         }
     }
 
@@ -73,6 +89,30 @@ impl LogicalTopN {
     pub fn topn_order(&self) -> &Order {
         &self.order
     }
+
+    pub fn group_key(&self) -> &[usize] {
+        &self.group_key
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ order: {}, limit: {}, offset: {}",
+            name, &self.order, &self.limit, &self.offset,
+        )?;
+        if !self.group_key.is_empty() {
+            write!(
+                f,
+                ", group_key: {:?}",
+                self.group_key
+                    .iter()
+                    .copied()
+                    .map(InputRefDisplay)
+                    .collect_vec()
+            )?;
+        }
+        write!(f, " }}")
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalTopN {
@@ -81,7 +121,13 @@ impl PlanTreeNodeUnary for LogicalTopN {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        Self::new(input, self.limit, self.offset, self.order.clone())
+        Self::with_group(
+            input,
+            self.limit,
+            self.offset,
+            self.order.clone(),
+            self.group_key.clone(),
+        )
     }
 
     #[must_use]
@@ -91,13 +137,17 @@ impl PlanTreeNodeUnary for LogicalTopN {
         input_col_change: ColIndexMapping,
     ) -> (Self, ColIndexMapping) {
         (
-            Self::new(
+            Self::with_group(
                 input,
                 self.limit,
                 self.offset,
                 input_col_change
                     .rewrite_required_order(&self.order)
                     .unwrap(),
+                self.group_key
+                    .iter()
+                    .map(|&idx| input_col_change.map(idx))
+                    .collect(),
             ),
             input_col_change,
         )
@@ -106,11 +156,7 @@ impl PlanTreeNodeUnary for LogicalTopN {
 impl_plan_tree_node_for_unary! {LogicalTopN}
 impl fmt::Display for LogicalTopN {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "LogicalTopN {{ order: {}, limit: {}, offset: {} }}",
-            &self.order, &self.limit, &self.offset,
-        )
+        self.fmt_with_name(f, "LogicalTopN")
     }
 }
 
@@ -123,6 +169,7 @@ impl ColPrunable for LogicalTopN {
                 .field_order
                 .iter()
                 .for_each(|fo| order_required_cols.insert(fo.index));
+            order_required_cols.extend(self.group_key.iter().copied());
             order_required_cols
         };
 
@@ -146,8 +193,10 @@ impl ColPrunable for LogicalTopN {
                 })
                 .collect(),
         };
+        let new_group_key = self.group_key.iter().map(|&idx| mapping.map(idx)).collect();
         let new_input = self.input.prune_col(&input_required_cols);
-        let top_n = Self::new(new_input, self.limit, self.offset, new_order).into();
+        let top_n =
+            Self::with_group(new_input, self.limit, self.offset, new_order, new_group_key).into();
 
         if input_required_cols == required_cols {
             top_n
@@ -174,6 +223,11 @@ impl ToBatch for LogicalTopN {
     }
 
     fn to_batch_with_order_required(&self, required_order: &Order) -> Result<PlanRef> {
+        if !self.group_key.is_empty() {
+            return Err(
+                ErrorCode::NotImplemented("group top-n in batch".into(), None.into()).into(),
+            );
+        }
         let new_input = self.input().to_batch()?;
         let new_logical = self.clone_with_input(new_input);
         let ret = BatchTopN::new(new_logical).into();
@@ -188,6 +242,16 @@ impl ToBatch for LogicalTopN {
 
 impl ToStream for LogicalTopN {
     fn to_stream(&self) -> Result<PlanRef> {
+        if !self.group_key.is_empty() {
+            // Rows of the same group must be processed by the same actor.
+            let input = self
+                .input()
+                .to_stream_with_dist_required(&RequiredDist::shard_by_key(
+                    self.input().schema().len(),
+                    &self.group_key,
+                ))?;
+            return Ok(StreamGroupTopN::new(self.clone_with_input(input)).into());
+        }
         // Unlike `BatchTopN`, `StreamTopN` cannot guarantee the output order
         let input = self
             .input()
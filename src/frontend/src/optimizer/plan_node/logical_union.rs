@@ -0,0 +1,260 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_expr::expr::AggKind;
+
+use super::{
+    BatchUnion, ColPrunable, LogicalAgg, LogicalProject, PlanAggCall, PlanBase, PlanRef,
+    PlanTreeNode, PredicatePushdown, StreamUnion, ToBatch, ToStream,
+};
+use crate::expr::{ExprImpl, InputRef, Literal};
+use crate::optimizer::property::{Distribution, RequiredDist};
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalUnion` outputs the rows of all its inputs, which have the same schema. Duplicated rows
+/// are removed unless `all` is set.
+#[derive(Debug, Clone)]
+pub struct LogicalUnion {
+    pub base: PlanBase,
+    all: bool,
+    inputs: Vec<PlanRef>,
+}
+
+impl LogicalUnion {
+    pub fn new(all: bool, inputs: Vec<PlanRef>) -> Self {
+        Self::new_with_pk(all, inputs, vec![])
+    }
+
+    fn new_with_pk(all: bool, inputs: Vec<PlanRef>, pk_indices: Vec<usize>) -> Self {
+        let ctx = inputs[0].ctx();
+        let schema = inputs[0].schema().clone();
+        for input in &inputs {
+            assert_eq!(input.schema().data_types(), schema.data_types());
+        }
+        let base = PlanBase::new_logical(ctx, schema, pk_indices);
+        LogicalUnion { base, all, inputs }
+    }
+
+    pub fn create(all: bool, inputs: Vec<PlanRef>) -> PlanRef {
+        Self::new(all, inputs).into()
+    }
+
+    pub fn all(&self) -> bool {
+        self.all
+    }
+
+    /// Creates a union of the same kind and stream key with new inputs.
+    pub(super) fn with_inputs(&self, inputs: Vec<PlanRef>) -> Self {
+        Self::new_with_pk(self.all, inputs, self.base.pk_indices.clone())
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(f, "{} {{ all: {} }}", name, self.all)
+    }
+
+    /// Rewrites `UNION` to a `UNION ALL` with all of its columns grouped by an aggregation.
+    fn to_distinct_agg(&self) -> PlanRef {
+        assert!(!self.all);
+        let union_all = Self::create(true, self.inputs.clone());
+        let group_keys = (0..self.schema().len()).collect();
+        LogicalAgg::new(vec![], group_keys, union_all).into()
+    }
+
+    /// Counts how many times each distinct row appears in each input, by a `UNION ALL` of the
+    /// inputs with a marker column for each of them, which is `1` for the rows from the input and
+    /// `NULL` otherwise. The output has the distinct rows followed by a count for each input.
+    ///
+    /// This is used to rewrite `INTERSECT` and `EXCEPT`. Note that `NULL`s are regarded as equal
+    /// by the grouping, as the set operations require.
+    pub(super) fn count_by_input(inputs: Vec<PlanRef>) -> PlanRef {
+        let input_num = inputs.len();
+        let col_num = inputs[0].schema().len();
+        let inputs = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let exprs = input
+                    .schema()
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, field)| InputRef::new(idx, field.data_type()).into())
+                    .chain((0..input_num).map(|j| {
+                        let marker = (i == j).then(|| ScalarImpl::Int32(1));
+                        Literal::new(marker, DataType::Int32).into()
+                    }))
+                    .collect();
+                LogicalProject::create(input, exprs)
+            })
+            .collect();
+        let union_all = Self::create(true, inputs);
+        let agg_calls = (0..input_num)
+            .map(|i| PlanAggCall {
+                agg_kind: AggKind::Count,
+                return_type: DataType::Int64,
+                inputs: vec![InputRef::new(col_num + i, DataType::Int32)],
+                distinct: false,
+            })
+            .collect();
+        LogicalAgg::new(agg_calls, (0..col_num).collect(), union_all).into()
+    }
+}
+
+impl PlanTreeNode for LogicalUnion {
+    fn inputs(&self) -> smallvec::SmallVec<[PlanRef; 2]> {
+        self.inputs.iter().cloned().collect()
+    }
+
+    fn clone_with_inputs(&self, inputs: &[PlanRef]) -> PlanRef {
+        self.with_inputs(inputs.to_vec()).into()
+    }
+}
+
+impl fmt::Display for LogicalUnion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalUnion")
+    }
+}
+
+impl ColPrunable for LogicalUnion {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        if self.all {
+            let inputs = self
+                .inputs
+                .iter()
+                .map(|input| input.prune_col(required_cols))
+                .collect();
+            Self::create(true, inputs)
+        } else {
+            // All the columns are needed to remove the duplicated rows.
+            let inputs = self
+                .inputs
+                .iter()
+                .map(|input| input.prune_col(&(0..input.schema().len()).collect_vec()))
+                .collect();
+            let mapping =
+                ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
+            LogicalProject::with_mapping(Self::create(false, inputs), mapping).into()
+        }
+    }
+}
+
+impl PredicatePushdown for LogicalUnion {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| input.predicate_pushdown(predicate.clone()))
+            .collect();
+        Self::create(self.all, inputs)
+    }
+}
+
+impl ToBatch for LogicalUnion {
+    fn to_batch(&self) -> Result<PlanRef> {
+        if !self.all {
+            return self.to_distinct_agg().to_batch();
+        }
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| input.to_batch())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(BatchUnion::new(self.with_inputs(inputs)).into())
+    }
+}
+
+impl ToStream for LogicalUnion {
+    fn to_stream(&self) -> Result<PlanRef> {
+        assert!(self.all);
+        // All the inputs must be distributed in the same way, so that the output is hash sharded
+        // by the stream key.
+        let required_dist =
+            RequiredDist::PhysicalDist(Distribution::HashShard(self.pk_indices().to_vec()));
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| input.to_stream_with_dist_required(&required_dist))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(StreamUnion::new(self.with_inputs(inputs)).into())
+    }
+
+    /// The rows from different inputs may have the same pk, so the stream key of the union is
+    /// composed of the pks of all inputs and the index of the input. Each input is projected with
+    /// its own pk columns, `NULL`s for the pk columns of the other inputs, and its index.
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        if !self.all {
+            return self.to_distinct_agg().logical_rewrite_for_stream();
+        }
+        let col_num = self.schema().len();
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| input.logical_rewrite_for_stream())
+            .collect::<Result<Vec<_>>>()?;
+        let pk_types = inputs
+            .iter()
+            .map(|(input, _)| {
+                input
+                    .pk_indices()
+                    .iter()
+                    .map(|&idx| input.schema().fields()[idx].data_type())
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let new_inputs = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, (input, col_change))| {
+                let exprs = self
+                    .schema()
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, field)| {
+                        InputRef::new(col_change.map(idx), field.data_type()).into()
+                    })
+                    .chain(pk_types.iter().enumerate().flat_map(|(j, types)| {
+                        types
+                            .iter()
+                            .enumerate()
+                            .map(move |(k, data_type)| -> ExprImpl {
+                                if i == j {
+                                    InputRef::new(input.pk_indices()[k], data_type.clone()).into()
+                                } else {
+                                    Literal::new(None, data_type.clone()).into()
+                                }
+                            })
+                    }))
+                    .chain(std::iter::once(
+                        Literal::new(Some(ScalarImpl::Int32(i as i32)), DataType::Int32).into(),
+                    ))
+                    .collect();
+                LogicalProject::create(input.clone(), exprs)
+            })
+            .collect_vec();
+
+        let new_col_num = new_inputs[0].schema().len();
+        let pk_indices = (col_num..new_col_num).collect();
+        let union = Self::new_with_pk(true, new_inputs, pk_indices);
+        let out_col_change = ColIndexMapping::identity_or_none(col_num, new_col_num);
+        Ok((union.into(), out_col_change))
+    }
+}
@@ -213,44 +213,62 @@ mod batch_hash_agg;
 mod batch_hash_join;
 mod batch_hop_window;
 mod batch_insert;
+mod batch_lateral_table_function;
 mod batch_limit;
 mod batch_nested_loop_join;
+mod batch_over_agg;
 mod batch_project;
 mod batch_seq_scan;
 mod batch_simple_agg;
 mod batch_sort;
+mod batch_sys_seq_scan;
+mod batch_table_function;
 mod batch_topn;
+mod batch_union;
 mod batch_update;
 mod batch_values;
 mod logical_agg;
 mod logical_apply;
 mod logical_delete;
+mod logical_except;
 mod logical_filter;
 mod logical_generate_series;
 mod logical_hop_window;
 mod logical_insert;
+mod logical_intersect;
 mod logical_join;
+mod logical_lateral_table_function;
 mod logical_limit;
 mod logical_multi_join;
+mod logical_over_agg;
 mod logical_project;
 mod logical_scan;
 mod logical_source;
+mod logical_sys_scan;
+mod logical_table_function;
 mod logical_topn;
+mod logical_union;
 mod logical_update;
 mod logical_values;
 mod stream_delta_join;
+mod stream_dynamic_filter;
 mod stream_exchange;
 mod stream_filter;
+mod stream_group_topn;
 mod stream_hash_agg;
 mod stream_hash_join;
 mod stream_hop_window;
 mod stream_index_scan;
+mod stream_lateral_table_function;
 mod stream_materialize;
+mod stream_over_agg;
 mod stream_project;
 mod stream_simple_agg;
+mod stream_sink;
 mod stream_source;
 mod stream_table_scan;
 mod stream_topn;
+mod stream_union;
 
 pub use batch_delete::BatchDelete;
 pub use batch_exchange::BatchExchange;
@@ -260,44 +278,62 @@ pub use batch_hash_agg::BatchHashAgg;
 pub use batch_hash_join::BatchHashJoin;
 pub use batch_hop_window::BatchHopWindow;
 pub use batch_insert::BatchInsert;
+pub use batch_lateral_table_function::BatchLateralTableFunction;
 pub use batch_limit::BatchLimit;
 pub use batch_nested_loop_join::BatchNestedLoopJoin;
+pub use batch_over_agg::BatchOverAgg;
 pub use batch_project::BatchProject;
 pub use batch_seq_scan::BatchSeqScan;
 pub use batch_simple_agg::BatchSimpleAgg;
 pub use batch_sort::BatchSort;
+pub use batch_sys_seq_scan::BatchSysSeqScan;
+pub use batch_table_function::BatchTableFunction;
 pub use batch_topn::BatchTopN;
+pub use batch_union::BatchUnion;
 pub use batch_update::BatchUpdate;
 pub use batch_values::BatchValues;
 pub use logical_agg::{LogicalAgg, PlanAggCall};
 pub use logical_apply::LogicalApply;
 pub use logical_delete::LogicalDelete;
+pub use logical_except::LogicalExcept;
 pub use logical_filter::LogicalFilter;
 pub use logical_generate_series::LogicalGenerateSeries;
 pub use logical_hop_window::LogicalHopWindow;
 pub use logical_insert::LogicalInsert;
+pub use logical_intersect::LogicalIntersect;
 pub use logical_join::LogicalJoin;
+pub use logical_lateral_table_function::LogicalLateralTableFunction;
 pub use logical_limit::LogicalLimit;
 pub use logical_multi_join::LogicalMultiJoin;
+pub use logical_over_agg::{LogicalOverAgg, PlanWindowFunction};
 pub use logical_project::LogicalProject;
 pub use logical_scan::LogicalScan;
 pub use logical_source::LogicalSource;
+pub use logical_sys_scan::LogicalSysScan;
+pub use logical_table_function::LogicalTableFunction;
 pub use logical_topn::LogicalTopN;
+pub use logical_union::LogicalUnion;
 pub use logical_update::LogicalUpdate;
 pub use logical_values::LogicalValues;
 pub use stream_delta_join::StreamDeltaJoin;
+pub use stream_dynamic_filter::StreamDynamicFilter;
 pub use stream_exchange::StreamExchange;
 pub use stream_filter::StreamFilter;
+pub use stream_group_topn::StreamGroupTopN;
 pub use stream_hash_agg::StreamHashAgg;
 pub use stream_hash_join::StreamHashJoin;
 pub use stream_hop_window::StreamHopWindow;
 pub use stream_index_scan::StreamIndexScan;
+pub use stream_lateral_table_function::StreamLateralTableFunction;
 pub use stream_materialize::StreamMaterialize;
+pub use stream_over_agg::StreamOverAgg;
 pub use stream_project::StreamProject;
 pub use stream_simple_agg::StreamSimpleAgg;
+pub use stream_sink::StreamSink;
 pub use stream_source::StreamSource;
 pub use stream_table_scan::StreamTableScan;
 pub use stream_topn::StreamTopN;
+pub use stream_union::StreamUnion;
 
 use crate::session::OptimizerContextRef;
 
@@ -334,6 +370,13 @@ macro_rules! for_all_plan_nodes {
             , { Logical, HopWindow }
             , { Logical, GenerateSeries }
             , { Logical, MultiJoin }
+            , { Logical, OverAgg }
+            , { Logical, Union }
+            , { Logical, Intersect }
+            , { Logical, Except }
+            , { Logical, SysScan }
+            , { Logical, TableFunction }
+            , { Logical, LateralTableFunction }
             // , { Logical, Sort } we don't need a LogicalSort, just require the Order
             , { Batch, SimpleAgg }
             , { Batch, HashAgg }
@@ -352,6 +395,11 @@ macro_rules! for_all_plan_nodes {
             , { Batch, TopN }
             , { Batch, HopWindow }
             , { Batch, GenerateSeries }
+            , { Batch, OverAgg }
+            , { Batch, Union }
+            , { Batch, SysSeqScan }
+            , { Batch, TableFunction }
+            , { Batch, LateralTableFunction }
             , { Stream, Project }
             , { Stream, Filter }
             , { Stream, TableScan }
@@ -362,9 +410,15 @@ macro_rules! for_all_plan_nodes {
             , { Stream, SimpleAgg }
             , { Stream, Materialize }
             , { Stream, TopN }
+            , { Stream, GroupTopN }
             , { Stream, HopWindow }
             , { Stream, DeltaJoin }
+            , { Stream, DynamicFilter }
             , { Stream, IndexScan }
+            , { Stream, Sink }
+            , { Stream, OverAgg }
+            , { Stream, Union }
+            , { Stream, LateralTableFunction }
         }
     };
 }
@@ -391,6 +445,13 @@ macro_rules! for_logical_plan_nodes {
             , { Logical, HopWindow }
             , { Logical, GenerateSeries }
             , { Logical, MultiJoin }
+            , { Logical, OverAgg }
+            , { Logical, Union }
+            , { Logical, Intersect }
+            , { Logical, Except }
+            , { Logical, SysScan }
+            , { Logical, TableFunction }
+            , { Logical, LateralTableFunction }
             // , { Logical, Sort} not sure if we will support Order by clause in subquery/view/MV
             // if we dont support thatk, we don't need LogicalSort, just require the Order at the top of query
         }
@@ -420,6 +481,11 @@ macro_rules! for_batch_plan_nodes {
             , { Batch, Update }
             , { Batch, HopWindow }
             , { Batch, GenerateSeries }
+            , { Batch, OverAgg }
+            , { Batch, Union }
+            , { Batch, SysSeqScan }
+            , { Batch, TableFunction }
+            , { Batch, LateralTableFunction }
         }
     };
 }
@@ -440,9 +506,15 @@ macro_rules! for_stream_plan_nodes {
             , { Stream, SimpleAgg }
             , { Stream, Materialize }
             , { Stream, TopN }
+            , { Stream, GroupTopN }
             , { Stream, HopWindow }
             , { Stream, DeltaJoin }
+            , { Stream, DynamicFilter }
             , { Stream, IndexScan }
+            , { Stream, Sink }
+            , { Stream, OverAgg }
+            , { Stream, Union }
+            , { Stream, LateralTableFunction }
         }
     };
 }
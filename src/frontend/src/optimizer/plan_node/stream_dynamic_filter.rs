@@ -0,0 +1,121 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::types::DataType;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::DynamicFilterNode;
+
+use super::{PlanBase, PlanRef, PlanTreeNodeBinary, ToStreamProst};
+use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef};
+use crate::optimizer::property::Distribution;
+
+/// `StreamDynamicFilter` filters the left input by comparing one of its columns with the single
+/// value of the right input, which may change over time, e.g., `WHERE t.v > (SELECT max(v) FROM
+/// s)`. Only the left columns are output.
+///
+/// All the left rows are kept in the state table, which is scanned by ranges of the key when the
+/// right value changes, so both inputs are gathered to a single actor.
+#[derive(Debug, Clone)]
+pub struct StreamDynamicFilter {
+    pub base: PlanBase,
+    left_index: usize,
+    comparator: ExprType,
+    left: PlanRef,
+    right: PlanRef,
+}
+
+impl StreamDynamicFilter {
+    /// The `comparator` compares the column `left_index` of the left input with the single column
+    /// of the right input, in this order.
+    pub fn new(left_index: usize, comparator: ExprType, left: PlanRef, right: PlanRef) -> Self {
+        assert_eq!(right.schema().len(), 1);
+        assert!(matches!(
+            comparator,
+            ExprType::LessThan
+                | ExprType::LessThanOrEqual
+                | ExprType::GreaterThan
+                | ExprType::GreaterThanOrEqual
+        ));
+        let ctx = left.ctx();
+        // The left rows are retracted and emitted again when the right value changes.
+        let base = PlanBase::new_stream(
+            ctx,
+            left.schema().clone(),
+            left.pk_indices().to_vec(),
+            Distribution::Single,
+            false,
+        );
+        StreamDynamicFilter {
+            base,
+            left_index,
+            comparator,
+            left,
+            right,
+        }
+    }
+
+    /// The predicate with the right column placed after the left columns.
+    fn predicate(&self) -> ExprImpl {
+        let left_fields = self.left.schema().fields();
+        let left_ref = InputRef::new(self.left_index, left_fields[self.left_index].data_type());
+        let right_ref = InputRef::new(
+            left_fields.len(),
+            self.right.schema().fields()[0].data_type(),
+        );
+        FunctionCall::new_unchecked(
+            self.comparator,
+            vec![left_ref.into(), right_ref.into()],
+            DataType::Boolean,
+        )
+        .into()
+    }
+}
+
+impl fmt::Display for StreamDynamicFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "StreamDynamicFilter {{ predicate: {:?} }}",
+            self.predicate()
+        )
+    }
+}
+
+impl PlanTreeNodeBinary for StreamDynamicFilter {
+    fn left(&self) -> PlanRef {
+        self.left.clone()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.right.clone()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(self.left_index, self.comparator, left, right)
+    }
+}
+
+impl_plan_tree_node_for_binary! { StreamDynamicFilter }
+
+impl ToStreamProst for StreamDynamicFilter {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::DynamicFilter(DynamicFilterNode {
+            left_key: self.left_index as u32,
+            comparator: self.comparator as i32,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::expr::InputRefExpr;
+use risingwave_pb::plan_common::ColumnOrder;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+
+use super::{LogicalTopN, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+
+/// `StreamGroupTopN` implements [`super::LogicalTopN`] with a group key, to find the top N elements
+/// of each group.
+#[derive(Debug, Clone)]
+pub struct StreamGroupTopN {
+    pub base: PlanBase,
+    logical: LogicalTopN,
+}
+
+impl StreamGroupTopN {
+    pub fn new(logical: LogicalTopN) -> Self {
+        assert!(!logical.group_key().is_empty());
+        let ctx = logical.base.ctx.clone();
+        // The input is sharded by the group key, and the rows are kept in place.
+        let dist = logical.input().distribution().clone();
+
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            logical.input().pk_indices().to_vec(),
+            dist,
+            false,
+        );
+        StreamGroupTopN { base, logical }
+    }
+}
+
+impl fmt::Display for StreamGroupTopN {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamGroupTopN")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamGroupTopN {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamGroupTopN }
+
+impl ToStreamProst for StreamGroupTopN {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+        let column_orders = self
+            .logical
+            .topn_order()
+            .field_order
+            .iter()
+            .map(|f| ColumnOrder {
+                order_type: f.direct.to_protobuf() as i32,
+                input_ref: Some(InputRefExpr {
+                    column_idx: f.index as i32,
+                }),
+                return_type: Some(self.input().schema()[f.index].data_type().to_protobuf()),
+            })
+            .collect();
+        ProstStreamNode::GroupTopN(GroupTopNNode {
+            column_orders,
+            limit: self.logical.limit() as u64,
+            offset: self.logical.offset() as u64,
+            group_keys: self
+                .logical
+                .group_key()
+                .iter()
+                .map(|&idx| idx as u32)
+                .collect(),
+        })
+    }
+}
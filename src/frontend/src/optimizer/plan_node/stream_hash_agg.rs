@@ -100,6 +100,7 @@ impl ToStreamProst for StreamHashAgg {
                 .map(PlanAggCall::to_protobuf)
                 .collect_vec(),
             table_ids: vec![],
+            distinct_dedup_table_ids: Default::default(),
             append_only: self.append_only(),
         })
     }
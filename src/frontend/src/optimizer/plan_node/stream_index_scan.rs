@@ -103,6 +103,7 @@ impl StreamIndexScan {
                     name: column_name.clone(),
                     field_descs: vec![],
                     type_name: "".to_string(),
+                    default_value: col.to_protobuf().default_value,
                 })
                 .collect(),
             distribution_keys: self
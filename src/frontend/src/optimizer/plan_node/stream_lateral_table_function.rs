@@ -0,0 +1,70 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::LateralTableFunctionNode;
+
+use super::{LogicalLateralTableFunction, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+
+/// [`StreamLateralTableFunction`] evaluates a table function on each input row.
+#[derive(Debug, Clone)]
+pub struct StreamLateralTableFunction {
+    pub base: PlanBase,
+    logical: LogicalLateralTableFunction,
+}
+
+impl StreamLateralTableFunction {
+    pub fn new(logical: LogicalLateralTableFunction) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let pk_indices = logical.base.pk_indices.to_vec();
+        let input = logical.input();
+
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            pk_indices,
+            input.distribution().clone(),
+            input.append_only(),
+        );
+        Self { base, logical }
+    }
+}
+
+impl fmt::Display for StreamLateralTableFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamLateralTableFunction")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamLateralTableFunction {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamLateralTableFunction }
+
+impl ToStreamProst for StreamLateralTableFunction {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::LateralTableFunction(LateralTableFunctionNode {
+            table_function: Some(self.logical.to_protobuf()),
+        })
+    }
+}
@@ -166,6 +166,8 @@ impl StreamMaterialize {
             pks: pk_indices.clone(),
             is_index_on,
             distribution_keys: base.dist.dist_column_indices().to_vec(),
+            owner: base.ctx.inner().session_ctx.user_id(),
+            version: 0,
         };
 
         Ok(Self { base, input, table })
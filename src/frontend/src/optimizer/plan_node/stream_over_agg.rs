@@ -0,0 +1,85 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+
+use super::{LogicalOverAgg, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::optimizer::property::Order;
+
+/// `StreamOverAgg` keeps the rows of each partition in a state table, and emits retractions for
+/// the window results of earlier rows when they are changed by later rows.
+///
+/// The executor evaluates a changed partition as a whole in memory, so the filters on
+/// `row_number()` are planned with [`super::StreamGroupTopN`] instead whenever possible.
+#[derive(Debug, Clone)]
+pub struct StreamOverAgg {
+    pub base: PlanBase,
+    logical: LogicalOverAgg,
+}
+
+impl StreamOverAgg {
+    pub fn new(logical: LogicalOverAgg) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let pk_indices = logical.base.pk_indices.to_vec();
+        // The input columns are kept in place, so the distribution is still valid.
+        let dist = logical.input().distribution().clone();
+        // Over agg executor retracts its previous outputs, so the stream is never append-only.
+        let base = PlanBase::new_stream(ctx, logical.schema().clone(), pk_indices, dist, false);
+        StreamOverAgg { base, logical }
+    }
+}
+
+impl fmt::Display for StreamOverAgg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamOverAgg")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamOverAgg {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+impl_plan_tree_node_for_unary! { StreamOverAgg }
+
+impl ToStreamProst for StreamOverAgg {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+
+        ProstStreamNode::OverAgg(OverAggNode {
+            partition_by: self
+                .logical
+                .partition_by()
+                .iter()
+                .map(|idx| *idx as u32)
+                .collect_vec(),
+            order_by: Order::new(self.logical.order_by().to_vec())
+                .to_protobuf(self.input().schema()),
+            window_functions: self
+                .logical
+                .window_functions()
+                .iter()
+                .map(|call| call.to_protobuf())
+                .collect_vec(),
+            table_id: 0,
+        })
+    }
+}
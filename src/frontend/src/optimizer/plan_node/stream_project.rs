@@ -18,7 +18,7 @@ use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 use risingwave_pb::stream_plan::ProjectNode;
 
 use super::{LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
-use crate::expr::Expr;
+use crate::expr::{Expr, ExprImpl, ExprType};
 
 /// `StreamProject` implements [`super::LogicalProject`] to evaluate specified expressions on input
 /// rows.
@@ -53,6 +53,22 @@ impl StreamProject {
         );
         StreamProject { base, logical }
     }
+
+    /// Returns the input column on which the output expression is monotonically non-decreasing,
+    /// so that a watermark on the input column can be derived to the output column.
+    fn watermark_input(expr: &ExprImpl) -> Option<usize> {
+        match expr {
+            ExprImpl::InputRef(input_ref) => Some(input_ref.index()),
+            ExprImpl::FunctionCall(call) => match (call.get_expr_type(), call.inputs()) {
+                (
+                    ExprType::Add | ExprType::Subtract | ExprType::TumbleStart,
+                    [ExprImpl::InputRef(input_ref), ExprImpl::Literal(_)],
+                ) => Some(input_ref.index()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl PlanTreeNodeUnary for StreamProject {
@@ -68,6 +84,15 @@ impl_plan_tree_node_for_unary! {StreamProject}
 
 impl ToStreamProst for StreamProject {
     fn to_stream_prost_body(&self) -> ProstStreamNode {
+        let (watermark_input_key, watermark_output_key) = self
+            .logical
+            .exprs()
+            .iter()
+            .enumerate()
+            .filter_map(|(output_idx, expr)| {
+                Self::watermark_input(expr).map(|input_idx| (input_idx as u32, output_idx as u32))
+            })
+            .unzip();
         ProstStreamNode::Project(ProjectNode {
             select_list: self
                 .logical
@@ -75,6 +100,8 @@ impl ToStreamProst for StreamProject {
                 .iter()
                 .map(Expr::to_expr_proto)
                 .collect(),
+            watermark_input_key,
+            watermark_output_key,
         })
     }
 }
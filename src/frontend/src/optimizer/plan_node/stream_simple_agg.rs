@@ -86,6 +86,7 @@ impl ToStreamProst for StreamSimpleAgg {
                 .map(|idx| *idx as u32)
                 .collect_vec(),
             table_ids: vec![],
+            distinct_dedup_table_ids: Default::default(),
             append_only: self.append_only(),
         })
     }
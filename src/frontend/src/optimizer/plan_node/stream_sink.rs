@@ -0,0 +1,90 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::SinkNode;
+
+use super::{PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::catalog::TableId;
+
+/// [`StreamSink`] writes the changes of its input into an external sink.
+#[derive(Debug, Clone)]
+pub struct StreamSink {
+    pub base: PlanBase,
+    input: PlanRef,
+    associated_table_id: TableId,
+    properties: HashMap<String, String>,
+}
+
+impl StreamSink {
+    pub fn new(
+        input: PlanRef,
+        associated_table_id: TableId,
+        properties: HashMap<String, String>,
+    ) -> Self {
+        // Sink executor passes through its input.
+        let base = PlanBase::new_stream(
+            input.ctx(),
+            input.schema().clone(),
+            input.pk_indices().to_vec(),
+            input.distribution().clone(),
+            input.append_only(),
+        );
+        Self {
+            base,
+            input,
+            associated_table_id,
+            properties,
+        }
+    }
+
+    pub fn connector(&self) -> &str {
+        self.properties
+            .get("connector")
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for StreamSink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StreamSink {{ connector: {} }}", self.connector())
+    }
+}
+
+impl PlanTreeNodeUnary for StreamSink {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.associated_table_id, self.properties.clone())
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamSink }
+
+impl ToStreamProst for StreamSink {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::Sink(SinkNode {
+            // The sink id will be generated on meta catalog service.
+            table_ref_id: None,
+            associated_table_id: self.associated_table_id.table_id(),
+            properties: self.properties.clone(),
+        })
+    }
+}
@@ -80,6 +80,7 @@ impl ToStreamProst for StreamSource {
                 .collect(),
             source_type: self.logical.source_catalog.source_type as i32,
             stream_source_state: None,
+            watermark_descs: self.logical.source_catalog.watermark_descs.clone(),
         })
     }
 }
@@ -108,6 +108,7 @@ impl StreamTableScan {
                     name: column_name.clone(),
                     field_descs: vec![],
                     type_name: "".to_string(),
+                    default_value: col.to_protobuf().default_value,
                 })
                 .collect(),
             distribution_keys: self
@@ -0,0 +1,238 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::types::DataType;
+use risingwave_pb::plan_common::JoinType;
+
+use super::{BoxedRule, Rule};
+use crate::expr::{
+    CorrelatedInputRef, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef,
+};
+use crate::optimizer::plan_node::{LogicalJoin, LogicalProject, PlanTreeNodeBinary};
+use crate::optimizer::PlanRef;
+use crate::utils::{Condition, Substitute};
+
+/// This rule is for pattern: Apply->(Project|Filter)*->X, where X is not correlated.
+///
+/// The projects and filters are merged into the expressions and predicates over X, which are then
+/// evaluated on the Join of Apply's left child and X, with correlated variables referring to the
+/// left child.
+///
+/// For `LeftSemi` and `LeftAnti`, both of them are substituted into the `on` clause of the Join.
+/// For `LeftOuter`, the predicates go into the `on` clause, while the expressions are put in a
+/// Project on top of the Join. As an expression with correlated variables is evaluated after the
+/// Join, it must be `NULL` for the rows of the left child that match nothing, so a constant column
+/// is appended to X to tell such rows.
+///
+/// It handles what [`UnnestAggForLOJ`](super::UnnestAggForLOJ) and
+/// [`PullUpCorrelatedPredicate`](super::PullUpCorrelatedPredicate) leave behind, e.g. correlated
+/// variables in the select list of the subquery.
+pub struct ApplyToJoinRule {}
+impl Rule for ApplyToJoinRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let apply = plan.as_logical_apply()?;
+        let (left, right, on, join_type) = apply.clone().decompose();
+        let left_len = left.schema().len();
+
+        // Merge the projects and filters on the right into `exprs` and `predicates` over `input`.
+        let mut exprs: Vec<ExprImpl> = right
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+            .collect();
+        let mut predicates: Vec<ExprImpl> = vec![];
+        let mut input = right;
+        loop {
+            if let Some(project) = input.as_logical_project() {
+                let mut subst = Substitute {
+                    mapping: project.exprs().clone(),
+                };
+                exprs = exprs.into_iter().map(|e| subst.rewrite_expr(e)).collect();
+                predicates = predicates
+                    .into_iter()
+                    .map(|e| subst.rewrite_expr(e))
+                    .collect();
+                input = project.input();
+            } else if let Some(filter) = input.as_logical_filter() {
+                predicates.extend(filter.predicate().conjunctions.iter().cloned());
+                input = filter.input();
+            } else {
+                break;
+            }
+        }
+        // Only the correlated variables referring to the left child can be lifted.
+        if is_correlated(&input)
+            || exprs
+                .iter()
+                .chain(predicates.iter())
+                .any(|e| e.has_correlated_input_ref_by_depth(2))
+        {
+            return None;
+        }
+
+        let mut lift = LiftCorrelatedInputRef { offset: left_len };
+        let predicates = Condition {
+            conjunctions: predicates
+                .into_iter()
+                .map(|e| lift.rewrite_expr(e))
+                .collect(),
+        };
+        let left_exprs = left
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+            .collect_vec();
+
+        let (right, right_exprs) = match join_type {
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                let exprs = exprs
+                    .into_iter()
+                    .map(|e| lift.rewrite_expr(e))
+                    .collect_vec();
+                (input, exprs)
+            }
+            JoinType::LeftOuter => {
+                // The uncorrelated expressions are evaluated below the Join, and the correlated
+                // ones above it, guarded by the constant column.
+                let input_len = input.schema().len();
+                let mut input_exprs = input
+                    .schema()
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+                    .collect_vec();
+                let is_matched: Option<ExprImpl> = exprs
+                    .iter()
+                    .any(|e| e.as_input_ref().is_none() && e.has_correlated_input_ref())
+                    .then(|| {
+                        let marker = InputRef::new(left_len + input_len, DataType::Int32);
+                        input_exprs.push(ExprImpl::literal_int(1));
+                        FunctionCall::new_unchecked(
+                            ExprType::IsNotNull,
+                            vec![marker.into()],
+                            DataType::Boolean,
+                        )
+                        .into()
+                    });
+                let right_exprs = exprs
+                    .into_iter()
+                    .map(|expr| match expr {
+                        ExprImpl::InputRef(input_ref) => {
+                            InputRef::new(input_ref.index() + left_len, input_ref.return_type())
+                                .into()
+                        }
+                        expr if !expr.has_correlated_input_ref() => {
+                            let input_ref =
+                                InputRef::new(left_len + input_exprs.len(), expr.return_type());
+                            input_exprs.push(expr);
+                            input_ref.into()
+                        }
+                        expr => {
+                            let return_type = expr.return_type();
+                            FunctionCall::new_unchecked(
+                                ExprType::Case,
+                                vec![is_matched.clone().unwrap(), lift.rewrite_expr(expr)],
+                                return_type,
+                            )
+                            .into()
+                        }
+                    })
+                    .collect_vec();
+                let right = if input_exprs.len() == input_len {
+                    input
+                } else {
+                    LogicalProject::create(input, input_exprs)
+                };
+                (right, right_exprs)
+            }
+            _ => unreachable!(),
+        };
+
+        let mut subst = Substitute {
+            mapping: left_exprs
+                .iter()
+                .cloned()
+                .chain(right_exprs.iter().cloned())
+                .collect(),
+        };
+        let on = on.rewrite_expr(&mut subst).and(predicates);
+        let join = LogicalJoin::new(left, right, join_type, on);
+        match join_type {
+            JoinType::LeftOuter => Some(LogicalProject::create(
+                join.into(),
+                left_exprs.into_iter().chain(right_exprs).collect(),
+            )),
+            _ => Some(join.into()),
+        }
+    }
+}
+
+impl ApplyToJoinRule {
+    pub fn create() -> BoxedRule {
+        Box::new(ApplyToJoinRule {})
+    }
+}
+
+/// Whether `plan` refers to the columns of an outer query, i.e. has a [`CorrelatedInputRef`] which
+/// reaches out of it.
+pub(super) fn is_correlated(plan: &PlanRef) -> bool {
+    has_correlated_input_ref(plan, 1)
+}
+
+fn has_correlated_input_ref(plan: &PlanRef, depth: usize) -> bool {
+    let reaches_out = |expr: &ExprImpl| expr.has_correlated_input_ref_by_depth(depth);
+    if let Some(apply) = plan.as_logical_apply() {
+        // The right child of an Apply is nested one level deeper.
+        return apply.on().conjunctions.iter().any(reaches_out)
+            || has_correlated_input_ref(&apply.left(), depth)
+            || has_correlated_input_ref(&apply.right(), depth + 1);
+    }
+    let has = if let Some(project) = plan.as_logical_project() {
+        project.exprs().iter().any(reaches_out)
+    } else if let Some(filter) = plan.as_logical_filter() {
+        filter.predicate().conjunctions.iter().any(reaches_out)
+    } else if let Some(join) = plan.as_logical_join() {
+        join.on().conjunctions.iter().any(reaches_out)
+    } else if let Some(values) = plan.as_logical_values() {
+        values.rows().iter().flatten().any(reaches_out)
+    } else {
+        false
+    };
+    has || plan
+        .inputs()
+        .iter()
+        .any(|input| has_correlated_input_ref(input, depth))
+}
+
+/// Rewrites the correlated variables referring to Apply's left child to `InputRef`s, and shifts the
+/// `InputRef`s of its right child by `offset`, the number of columns of the left child.
+pub(super) struct LiftCorrelatedInputRef {
+    pub offset: usize,
+}
+
+impl ExprRewriter for LiftCorrelatedInputRef {
+    fn rewrite_correlated_input_ref(&mut self, input_ref: CorrelatedInputRef) -> ExprImpl {
+        InputRef::new(input_ref.index(), input_ref.return_type()).into()
+    }
+
+    fn rewrite_input_ref(&mut self, input_ref: InputRef) -> ExprImpl {
+        InputRef::new(input_ref.index() + self.offset, input_ref.return_type()).into()
+    }
+}
@@ -35,6 +35,8 @@ mod unnest_agg_for_loj;
 pub use unnest_agg_for_loj::*;
 mod pull_up_correlated_predicate;
 pub use pull_up_correlated_predicate::*;
+mod apply_to_join;
+pub use apply_to_join::*;
 mod index_delta_join;
 pub use index_delta_join::*;
 mod multijoin_filter;
@@ -43,3 +45,5 @@ mod multijoin_join;
 pub use multijoin_join::*;
 mod reorder_multijoin;
 pub use reorder_multijoin::*;
+mod over_agg_to_topn;
+pub use over_agg_to_topn::*;
@@ -0,0 +1,155 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::ScalarImpl;
+use risingwave_expr::window_function::WindowFuncKind;
+
+use super::super::plan_node::*;
+use super::{BoxedRule, Rule};
+use crate::expr::{ExprImpl, ExprType};
+use crate::optimizer::property::Order;
+use crate::utils::Condition;
+
+/// Transforms the filter on the `row_number()` of each partition into a [`LogicalTopN`] grouped by
+/// the partition keys, e.g.
+///
+/// ```sql
+/// select x, y from (
+///     select *, row_number() over (partition by x order by y) as rank from t
+/// ) where rank <= 3;
+/// ```
+///
+/// The rule only applies when the row number itself is not required by the [`LogicalProject`]
+/// above, as the top-n doesn't output it.
+pub struct OverAggToTopNRule {}
+impl Rule for OverAggToTopNRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let project = plan.as_logical_project()?;
+        let filter = project.input();
+        let filter = filter.as_logical_filter()?;
+        let over_agg = filter.input();
+        let over_agg = over_agg.as_logical_over_agg()?;
+        if over_agg.window_functions().len() != 1
+            || over_agg.window_functions()[0].kind != WindowFuncKind::RowNumber
+        {
+            return None;
+        }
+        let col_num = over_agg.schema().len();
+        // The row number is the only column after the input columns.
+        let rank_idx = over_agg.input().schema().len();
+        if project
+            .exprs()
+            .iter()
+            .any(|expr| expr.collect_input_refs(col_num).contains(rank_idx))
+        {
+            return None;
+        }
+
+        // The rows to keep are those with row numbers in `[lower, upper]`.
+        let mut lower = 1;
+        let mut upper = None;
+        let mut others = vec![];
+        for conjunct in &filter.predicate().conjunctions {
+            if !conjunct.collect_input_refs(col_num).contains(rank_idx) {
+                others.push(conjunct.clone());
+                continue;
+            }
+            let (comparator, bound) = rank_bound(conjunct, rank_idx)?;
+            let (new_lower, new_upper) = match comparator {
+                ExprType::LessThan => (None, Some(bound.saturating_sub(1))),
+                ExprType::LessThanOrEqual => (None, Some(bound)),
+                ExprType::Equal => (Some(bound), Some(bound)),
+                ExprType::GreaterThan => (Some(bound.saturating_add(1)), None),
+                ExprType::GreaterThanOrEqual => (Some(bound), None),
+                _ => unreachable!(),
+            };
+            if let Some(new_lower) = new_lower {
+                lower = lower.max(new_lower);
+            }
+            if let Some(new_upper) = new_upper {
+                upper = Some(upper.map_or(new_upper, |upper: i64| upper.min(new_upper)));
+            }
+        }
+        // An empty result is left as it is, as a top-n should never have a limit of 0.
+        let upper = upper?;
+        if upper < lower {
+            return None;
+        }
+
+        let top_n = LogicalTopN::with_group(
+            over_agg.input(),
+            (upper - lower + 1) as usize,
+            (lower - 1) as usize,
+            Order {
+                field_order: over_agg.order_by().to_vec(),
+            },
+            over_agg.partition_by().to_vec(),
+        );
+        let filter = LogicalFilter::create(
+            top_n.into(),
+            Condition {
+                conjunctions: others,
+            },
+        );
+        Some(LogicalProject::create(filter, project.exprs().clone()))
+    }
+}
+
+/// Matches the comparison of the row number with a constant, i.e. `rank <op> const` or
+/// `const <op> rank`, and returns the comparison in the former form and the constant.
+fn rank_bound(expr: &ExprImpl, rank_idx: usize) -> Option<(ExprType, i64)> {
+    let func = match expr {
+        ExprImpl::FunctionCall(func) => func,
+        _ => return None,
+    };
+    let comparator = func.get_expr_type();
+    let (comparator, bound) = match func.inputs() {
+        [ExprImpl::InputRef(rank), bound] if rank.index() == rank_idx => (comparator, bound),
+        [bound, ExprImpl::InputRef(rank)] if rank.index() == rank_idx => {
+            // Swap the operands, e.g. `3 >= rank` to `rank <= 3`.
+            let comparator = match comparator {
+                ExprType::LessThan => ExprType::GreaterThan,
+                ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
+                ExprType::GreaterThan => ExprType::LessThan,
+                ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
+                other => other,
+            };
+            (comparator, bound)
+        }
+        _ => return None,
+    };
+    match comparator {
+        ExprType::LessThan
+        | ExprType::LessThanOrEqual
+        | ExprType::Equal
+        | ExprType::GreaterThan
+        | ExprType::GreaterThanOrEqual => {}
+        _ => return None,
+    }
+    if !bound.is_const() {
+        return None;
+    }
+    match bound.eval_row_const().ok()? {
+        Some(ScalarImpl::Int16(bound)) => Some((comparator, bound as i64)),
+        Some(ScalarImpl::Int32(bound)) => Some((comparator, bound as i64)),
+        Some(ScalarImpl::Int64(bound)) => Some((comparator, bound)),
+        _ => None,
+    }
+}
+
+impl OverAggToTopNRule {
+    pub fn create() -> BoxedRule {
+        Box::new(OverAggToTopNRule {})
+    }
+}
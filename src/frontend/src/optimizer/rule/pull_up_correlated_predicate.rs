@@ -15,6 +15,7 @@
 use itertools::{Either, Itertools};
 
 use super::super::plan_node::*;
+use super::apply_to_join::is_correlated;
 use super::{BoxedRule, Rule};
 use crate::expr::{CorrelatedInputRef, Expr, ExprImpl, ExprRewriter, InputRef};
 use crate::optimizer::PlanRef;
@@ -36,6 +37,20 @@ impl Rule for PullUpCorrelatedPredicate {
         let input = project.input();
         let filter = input.as_logical_filter()?;
 
+        // Leave the other patterns of correlated variables to `ApplyToJoinRule`.
+        if proj_exprs
+            .iter()
+            .any(|expr| expr.has_correlated_input_ref())
+            || filter
+                .predicate()
+                .conjunctions
+                .iter()
+                .any(|expr| expr.has_correlated_input_ref_by_depth(2))
+            || is_correlated(&filter.input())
+        {
+            return None;
+        }
+
         let mut rewriter = Rewriter {
             input_refs: vec![],
             index: proj_exprs.len() + apply_left.schema().fields().len(),
@@ -16,13 +16,13 @@ use risingwave_common::types::DataType;
 use risingwave_expr::expr::AggKind;
 use risingwave_pb::plan_common::JoinType;
 
+use super::apply_to_join::LiftCorrelatedInputRef;
 use super::{BoxedRule, Rule};
 use crate::expr::{ExprImpl, ExprRewriter, InputRef};
 use crate::optimizer::plan_node::{
     LogicalAgg, LogicalProject, PlanTreeNodeBinary, PlanTreeNodeUnary,
 };
 use crate::optimizer::PlanRef;
-use crate::utils::ColIndexMapping;
 
 /// This rule is for pattern: Apply->Project(p1)->Agg->Project(p2), and it will be converted into
 /// Project(p1')->Agg->Apply->Project(p2').
@@ -49,6 +49,15 @@ impl Rule for UnnestAggForLOJ {
         let right = apply.right();
         let project = right.as_logical_project()?;
 
+        // Correlated variables in `project` can only refer to Apply's left child.
+        if project
+            .exprs()
+            .iter()
+            .any(|expr| expr.has_correlated_input_ref_by_depth(2))
+        {
+            return None;
+        }
+
         let input = project.input();
         let agg = input.as_logical_agg()?;
 
@@ -102,13 +111,18 @@ impl Rule for UnnestAggForLOJ {
             .map(|(i, field)| InputRef::new(i, field.data_type()).into())
             .collect();
 
-        let mut shift_input_ref =
-            ColIndexMapping::with_shift_offset(agg.agg_calls().len(), apply_left_len as isize);
-        // Extend with the project columns in the right.
-        exprs.extend(project.exprs().clone().into_iter().map(|expr| {
-            // We currently assume that there is no correlated variable in LogicalProject.
-            shift_input_ref.rewrite_expr(expr)
-        }));
+        // Extend with the project columns in the right, where correlated variables now refer to
+        // the group keys.
+        let mut lift = LiftCorrelatedInputRef {
+            offset: apply_left_len,
+        };
+        exprs.extend(
+            project
+                .exprs()
+                .clone()
+                .into_iter()
+                .map(|expr| lift.rewrite_expr(expr)),
+        );
 
         let project = LogicalProject::new(agg.into(), exprs);
 
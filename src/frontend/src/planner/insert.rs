@@ -27,11 +27,17 @@ impl Planner {
         if !insert.cast_exprs.is_empty() {
             input = LogicalProject::create(input, insert.cast_exprs);
         }
-        // `columns` not used by backend yet.
+        let column_ids = insert
+            .table_source
+            .columns
+            .iter()
+            .map(|column| column.column_id)
+            .collect();
         let plan: PlanRef = LogicalInsert::create(
             input,
             insert.table_source.name,
             insert.table_source.source_id,
+            column_ids,
         )?
         .into();
         let order = Order::any().clone();
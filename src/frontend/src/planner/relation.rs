@@ -18,17 +18,20 @@ use itertools::Itertools;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::ScalarImpl;
+use risingwave_pb::plan_common::JoinType;
 
 use crate::binder::{
-    BoundBaseTable, BoundGenerateSeriesFunction, BoundJoin, BoundSource, BoundWindowTableFunction,
-    Relation, WindowTableFunctionKind,
+    BoundBaseTable, BoundGenerateSeriesFunction, BoundJoin, BoundSource, BoundSystemTable,
+    BoundTableFunction, BoundWindowTableFunction, Relation, WindowTableFunctionKind,
 };
 use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef};
 use crate::optimizer::plan_node::{
-    LogicalGenerateSeries, LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan,
-    LogicalSource, PlanRef,
+    LogicalFilter, LogicalGenerateSeries, LogicalHopWindow, LogicalJoin,
+    LogicalLateralTableFunction, LogicalProject, LogicalScan, LogicalSource, LogicalSysScan,
+    LogicalTableFunction, PlanRef,
 };
 use crate::planner::Planner;
+use crate::utils::{ColIndexMapping, Condition};
 
 impl Planner {
     pub(super) fn plan_relation(&mut self, relation: Relation) -> Result<PlanRef> {
@@ -40,6 +43,8 @@ impl Planner {
             Relation::WindowTableFunction(tf) => self.plan_window_table_function(*tf),
             Relation::Source(s) => self.plan_source(*s),
             Relation::GenerateSeriesFunction(gs) => self.plan_generate_series_function(*gs),
+            Relation::SystemTable(st) => self.plan_system_table(*st),
+            Relation::TableFunction(tf) => self.plan_table_function(*tf),
         }
     }
 
@@ -60,14 +65,65 @@ impl Planner {
         Ok(LogicalSource::new(Rc::new(source.catalog), self.ctx()).into())
     }
 
+    pub(super) fn plan_system_table(&mut self, system_table: BoundSystemTable) -> Result<PlanRef> {
+        Ok(LogicalSysScan::create(
+            system_table.name,
+            Schema::new(system_table.fields),
+            system_table.rows,
+            self.ctx(),
+        ))
+    }
+
     pub(super) fn plan_join(&mut self, join: BoundJoin) -> Result<PlanRef> {
         let left = self.plan_relation(join.left)?;
-        let right = self.plan_relation(join.right)?;
+        let right = match join.right {
+            Relation::TableFunction(tf) if tf.lateral_input_col_num.is_some() => {
+                return self.plan_lateral_table_function(left, *tf, join.join_type, join.cond);
+            }
+            right => self.plan_relation(right)?,
+        };
         let join_type = join.join_type;
         let on_clause = join.cond;
         Ok(LogicalJoin::create(left, right, join_type, on_clause))
     }
 
+    /// Plans a table function referencing the columns of the preceding `FROM` items as a
+    /// [`LogicalLateralTableFunction`] on them, which are planned as `input`.
+    fn plan_lateral_table_function(
+        &mut self,
+        input: PlanRef,
+        table_function: BoundTableFunction,
+        join_type: JoinType,
+        on_clause: ExprImpl,
+    ) -> Result<PlanRef> {
+        if join_type != JoinType::Inner
+            || table_function.lateral_input_col_num != Some(input.schema().len())
+        {
+            return Err(ErrorCode::NotImplemented(
+                format!(
+                    "function {} not inner joined right after the FROM items it references",
+                    table_function.kind.name()
+                ),
+                None.into(),
+            )
+            .into());
+        }
+        let lateral =
+            LogicalLateralTableFunction::new(input, table_function.kind, table_function.args);
+        let ordinality_col_idx = lateral.ordinality_col_idx();
+        let col_num = lateral.schema().len();
+        let plan = LogicalFilter::create(lateral.into(), Condition::with_expr(on_clause));
+        // The ordinality is not visible to the query.
+        Ok(LogicalProject::with_mapping(
+            plan,
+            ColIndexMapping::with_remaining_columns(
+                &(0..ordinality_col_idx).collect_vec(),
+                col_num,
+            ),
+        )
+        .into())
+    }
+
     pub(super) fn plan_window_table_function(
         &mut self,
         table_function: BoundWindowTableFunction,
@@ -111,6 +167,17 @@ impl Planner {
         ))
     }
 
+    pub(super) fn plan_table_function(
+        &mut self,
+        table_function: BoundTableFunction,
+    ) -> Result<PlanRef> {
+        Ok(LogicalTableFunction::create(
+            table_function.kind,
+            table_function.args,
+            self.ctx(),
+        ))
+    }
+
     fn plan_tumble_window(
         &mut self,
         input: Relation,
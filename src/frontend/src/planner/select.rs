@@ -16,15 +16,18 @@ use itertools::Itertools;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
+use risingwave_expr::expr::AggKind;
 use risingwave_pb::plan_common::JoinType;
 
 use crate::binder::BoundSelect;
 use crate::expr::{
-    Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Subquery, SubqueryKind,
+    CorrelatedInputRef, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Literal,
+    Subquery, SubqueryKind,
 };
 pub use crate::optimizer::plan_node::LogicalFilter;
 use crate::optimizer::plan_node::{
-    LogicalAgg, LogicalApply, LogicalJoin, LogicalProject, LogicalValues, PlanAggCall, PlanRef,
+    LogicalAgg, LogicalApply, LogicalJoin, LogicalOverAgg, LogicalProject, LogicalValues,
+    PlanAggCall, PlanRef,
 };
 use crate::planner::Planner;
 use crate::utils::Condition;
@@ -72,6 +75,11 @@ impl Planner {
             root = self.plan_where(root, having)?;
         }
 
+        // Window functions are evaluated after grouping, so they can take aggregates as input.
+        if select_items.iter().any(|e| e.has_window_function()) {
+            (root, select_items) = LogicalOverAgg::create(root, select_items)?;
+        }
+
         if select_items.iter().any(|e| e.has_subquery()) {
             (root, select_items) = self.substitute_subqueries(root, select_items)?;
         }
@@ -106,8 +114,98 @@ impl Planner {
         Ok(LogicalProject::create(count_star.into(), vec![ge.into()]))
     }
 
-    /// For `(NOT) EXISTS subquery` or `(NOT) IN subquery`, we can plan it as
-    /// `LeftSemi/LeftAnti` [`LogicalApply`] (correlated) or [`LogicalJoin`].
+    /// Helper to create a quantified comparison `left_expr op SOME|ALL (subquery)` on the given
+    /// planned subquery `input`, which is `ALL` if `all` is set.
+    ///
+    /// The comparison is evaluated on each row of `input`, where `left_expr` becomes correlated.
+    /// The result is decided by whether there is a row on which it is `true` (for `SOME`) or
+    /// `false` (for `ALL`), and is `NULL` if otherwise any of them is `NULL`. For `SOME`, it is
+    /// represented by
+    ///
+    /// ```text
+    /// Project([CASE WHEN $0 > 0 THEN true WHEN $1 > 0 THEN NULL ELSE false END])
+    ///   Agg([count($0), count($1)])
+    ///     Project([CASE WHEN cmp THEN 1 END, CASE WHEN cmp IS NULL THEN 1 END])
+    ///       input
+    /// ```
+    fn create_quantified_comparison(
+        &self,
+        input: PlanRef,
+        left_expr: ExprImpl,
+        op: ExprType,
+        all: bool,
+    ) -> Result<PlanRef> {
+        /// Rewrites the columns of the outer query to be correlated in the subquery.
+        struct CorrelateInputRefs {}
+
+        impl ExprRewriter for CorrelateInputRefs {
+            fn rewrite_input_ref(&mut self, input_ref: InputRef) -> ExprImpl {
+                CorrelatedInputRef::new(input_ref.index(), input_ref.return_type(), 1).into()
+            }
+
+            fn rewrite_correlated_input_ref(&mut self, input_ref: CorrelatedInputRef) -> ExprImpl {
+                CorrelatedInputRef::new(
+                    input_ref.index(),
+                    input_ref.return_type(),
+                    input_ref.depth() + 1,
+                )
+                .into()
+            }
+        }
+
+        let left_expr = CorrelateInputRefs {}.rewrite_expr(left_expr);
+        let right_expr = InputRef::new(0, input.schema().fields()[0].data_type());
+        let cmp: ExprImpl = FunctionCall::new(op, vec![left_expr, right_expr.into()])?.into();
+        let decisive = if all {
+            FunctionCall::new(ExprType::IsFalse, vec![cmp.clone()])?.into()
+        } else {
+            cmp.clone()
+        };
+        let unknown = FunctionCall::new(ExprType::IsNull, vec![cmp])?.into();
+        let exprs = [decisive, unknown]
+            .into_iter()
+            .map(|cond| {
+                FunctionCall::new(ExprType::Case, vec![cond, ExprImpl::literal_int(1)])
+                    .map(Into::into)
+            })
+            .collect::<Result<_>>()?;
+        let project = LogicalProject::create(input, exprs);
+
+        let agg_calls = (0..2)
+            .map(|i| PlanAggCall {
+                agg_kind: AggKind::Count,
+                return_type: DataType::Int64,
+                inputs: vec![InputRef::new(i, DataType::Int32)],
+                distinct: false,
+            })
+            .collect();
+        let counts = LogicalAgg::new(agg_calls, vec![], project);
+
+        let positive = |i| -> Result<ExprImpl> {
+            Ok(FunctionCall::new(
+                ExprType::GreaterThan,
+                vec![
+                    InputRef::new(i, DataType::Int64).into(),
+                    ExprImpl::literal_int(0),
+                ],
+            )?
+            .into())
+        };
+        let result = FunctionCall::new(
+            ExprType::Case,
+            vec![
+                positive(0)?,
+                ExprImpl::literal_bool(!all),
+                positive(1)?,
+                Literal::new(None, DataType::Boolean).into(),
+                ExprImpl::literal_bool(all),
+            ],
+        )?;
+        Ok(LogicalProject::create(counts.into(), vec![result.into()]))
+    }
+
+    /// For `(NOT) EXISTS subquery`, `(NOT) IN subquery` or `(NOT) expr op SOME|ALL subquery`, we
+    /// can plan it as `LeftSemi/LeftAnti` [`LogicalApply`] (correlated) or [`LogicalJoin`].
     ///
     /// For other subqueries, we plan it as `LeftOuter` [`LogicalApply`] (correlated) or
     /// [`LogicalJoin`] using [`Self::substitute_subqueries`].
@@ -118,10 +216,11 @@ impl Planner {
         let (subquery_conjunctions, not_subquery_conjunctions, others) =
             Condition::with_expr(where_clause)
                 .group_by::<_, 3>(|expr| match expr {
-                    ExprImpl::Subquery(_) => 0,
+                    ExprImpl::Subquery(subquery) if subquery.kind != SubqueryKind::Scalar => 0,
                     ExprImpl::FunctionCall(func_call)
                         if func_call.get_expr_type() == ExprType::Not
-                            && matches!(func_call.inputs()[0], ExprImpl::Subquery(_)) =>
+                            && matches!(&func_call.inputs()[0], ExprImpl::Subquery(subquery)
+                                if subquery.kind != SubqueryKind::Scalar) =>
                     {
                         1
                     }
@@ -131,12 +230,12 @@ impl Planner {
                 .next_tuple()
                 .unwrap();
 
-        // EXISTS and IN in WHERE.
+        // EXISTS, IN, SOME and ALL in WHERE.
         for expr in subquery_conjunctions {
             self.handle_exists_and_in(expr, false, &mut input)?;
         }
 
-        // NOT EXISTS and NOT IN in WHERE.
+        // NOT EXISTS, NOT IN, NOT SOME and NOT ALL in WHERE.
         for expr in not_subquery_conjunctions {
             let not = expr.into_function_call().unwrap();
             let (_, expr) = not.decompose_as_unary();
@@ -156,38 +255,62 @@ impl Planner {
         }
     }
 
-    /// Handle (NOT) EXISTS and (NOT) IN in WHERE clause.
+    /// Handle (NOT) EXISTS, (NOT) IN, (NOT) SOME and (NOT) ALL in WHERE clause.
     ///
     /// We will use a = b to replace a in (select b from ....) for (NOT) IN thus avoiding adding a
-    /// `LogicalFilter` on `LogicalApply`.
+    /// `LogicalFilter` on `LogicalApply`. Similarly, `a op SOME (select b ...)` holds if the
+    /// semi join on `a op b` matches, and `a op ALL (select b ...)` holds if the anti join on
+    /// `(a op b) IS NOT TRUE` matches nothing.
     fn handle_exists_and_in(
         &mut self,
         expr: ExprImpl,
         negated: bool,
         input: &mut PlanRef,
     ) -> Result<()> {
-        let join_type = if negated {
-            JoinType::LeftAnti
-        } else {
-            JoinType::LeftSemi
-        };
         let subquery = expr.into_subquery().unwrap();
         let is_correlated = subquery.is_correlated();
         let output_column_type = subquery.query.data_types()[0].clone();
         let right_plan = self.plan_query(subquery.query)?.as_subplan();
-        let on = match subquery.kind {
-            SubqueryKind::Existential => ExprImpl::literal_bool(true),
-            SubqueryKind::In(left_expr) => {
-                let right_expr = InputRef::new(input.schema().fields().len(), output_column_type);
-                FunctionCall::new(ExprType::Equal, vec![left_expr, right_expr.into()])?.into()
+        let right_expr = InputRef::new(input.schema().fields().len(), output_column_type);
+        let (on, anti) = match subquery.kind {
+            SubqueryKind::Existential => (ExprImpl::literal_bool(true), negated),
+            SubqueryKind::In(left_expr) => (
+                FunctionCall::new(ExprType::Equal, vec![left_expr, right_expr.into()])?.into(),
+                negated,
+            ),
+            SubqueryKind::Some(left_expr, op) => {
+                let cmp = FunctionCall::new(op, vec![left_expr, right_expr.into()])?.into();
+                if negated {
+                    // `NOT (a op SOME ...)` holds if `a op b` is false for all rows.
+                    (
+                        FunctionCall::new(ExprType::IsNotFalse, vec![cmp])?.into(),
+                        true,
+                    )
+                } else {
+                    (cmp, false)
+                }
             }
-            kind => {
-                return Err(ErrorCode::NotImplemented(
-                    format!("Not supported subquery kind: {:?}", kind),
-                    1343.into(),
-                )
-                .into())
+            SubqueryKind::All(left_expr, op) => {
+                let cmp = FunctionCall::new(op, vec![left_expr, right_expr.into()])?.into();
+                if negated {
+                    // `NOT (a op ALL ...)` holds if `a op b` is false for some row.
+                    (
+                        FunctionCall::new(ExprType::IsFalse, vec![cmp])?.into(),
+                        false,
+                    )
+                } else {
+                    (
+                        FunctionCall::new(ExprType::IsNotTrue, vec![cmp])?.into(),
+                        true,
+                    )
+                }
             }
+            SubqueryKind::Scalar => unreachable!(),
+        };
+        let join_type = if anti {
+            JoinType::LeftAnti
+        } else {
+            JoinType::LeftSemi
         };
         *input =
             Self::create_apply_or_join(is_correlated, input.clone(), right_plan, on, join_type);
@@ -231,7 +354,7 @@ impl Planner {
             .collect();
 
         for subquery in rewriter.subqueries {
-            let is_correlated = subquery.is_correlated();
+            let mut is_correlated = subquery.is_correlated();
             let mut right = self.plan_query(subquery.query)?.as_subplan();
 
             match subquery.kind {
@@ -239,12 +362,22 @@ impl Planner {
                 SubqueryKind::Existential => {
                     right = self.create_exists(right)?;
                 }
-                _ => {
-                    return Err(ErrorCode::NotImplemented(
-                        format!("{:?}", subquery.kind),
-                        1343.into(),
-                    )
-                    .into())
+                SubqueryKind::In(left_expr) => {
+                    is_correlated |= Self::refers_to_input(&left_expr);
+                    right = self.create_quantified_comparison(
+                        right,
+                        left_expr,
+                        ExprType::Equal,
+                        false,
+                    )?;
+                }
+                SubqueryKind::Some(left_expr, op) => {
+                    is_correlated |= Self::refers_to_input(&left_expr);
+                    right = self.create_quantified_comparison(right, left_expr, op, false)?;
+                }
+                SubqueryKind::All(left_expr, op) => {
+                    is_correlated |= Self::refers_to_input(&left_expr);
+                    right = self.create_quantified_comparison(right, left_expr, op, true)?;
                 }
             }
 
@@ -259,6 +392,12 @@ impl Planner {
         Ok((root, exprs))
     }
 
+    /// Whether the left operand of a quantified comparison refers to the columns of the outer
+    /// query, which makes the planned comparison correlated.
+    fn refers_to_input(left_expr: &ExprImpl) -> bool {
+        left_expr.has_input_ref() || left_expr.has_correlated_input_ref()
+    }
+
     fn create_apply_or_join(
         is_correlated: bool,
         left: PlanRef,
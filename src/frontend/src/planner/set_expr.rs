@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools;
 use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_sqlparser::ast::SetOperator;
 
-use crate::binder::BoundSetExpr;
-use crate::expr::ExprImpl;
-use crate::optimizer::plan_node::PlanRef;
+use crate::binder::{BoundSetExpr, BoundSetOperation};
+use crate::expr::{ExprImpl, InputRef};
+use crate::optimizer::plan_node::{
+    LogicalExcept, LogicalIntersect, LogicalProject, LogicalUnion, PlanRef, PlanTreeNode,
+};
 use crate::planner::Planner;
 
 impl Planner {
@@ -28,6 +33,85 @@ impl Planner {
         match set_expr {
             BoundSetExpr::Select(s) => self.plan_select(*s, extra_order_exprs),
             BoundSetExpr::Values(v) => self.plan_values(*v),
+            BoundSetExpr::Query(q) => {
+                let plan = self.plan_query(*q)?.as_subplan();
+                Ok(Self::append_extra_order_exprs(plan, extra_order_exprs))
+            }
+            BoundSetExpr::SetOperation(s) => {
+                let plan = self.plan_set_operation(*s)?;
+                Ok(Self::append_extra_order_exprs(plan, extra_order_exprs))
+            }
         }
     }
+
+    fn plan_set_operation(&mut self, set_operation: BoundSetOperation) -> Result<PlanRef> {
+        let data_types = set_operation.schema().data_types();
+        let BoundSetOperation {
+            op,
+            all,
+            left,
+            right,
+            ..
+        } = set_operation;
+        let left = self.plan_set_operation_input(left, &data_types)?;
+        let right = self.plan_set_operation_input(right, &data_types)?;
+        let plan = match op {
+            SetOperator::Union => {
+                // Flatten the chained unions of the same kind, e.g. `a UNION b UNION c`.
+                let inputs = [left, right]
+                    .into_iter()
+                    .flat_map(|input| match input.as_logical_union() {
+                        Some(union) if union.all() == all => union.inputs().into_vec(),
+                        _ => vec![input],
+                    })
+                    .collect();
+                LogicalUnion::create(all, inputs)
+            }
+            SetOperator::Intersect => LogicalIntersect::create(left, right),
+            SetOperator::Except => LogicalExcept::create(left, right),
+        };
+        Ok(plan)
+    }
+
+    /// Plans an input of a set operation, and casts its columns to the types of the output.
+    fn plan_set_operation_input(
+        &mut self,
+        set_expr: BoundSetExpr,
+        data_types: &[DataType],
+    ) -> Result<PlanRef> {
+        let plan = self.plan_set_expr(set_expr, vec![])?;
+        if plan.schema().data_types() == data_types {
+            return Ok(plan);
+        }
+        let exprs = plan
+            .schema()
+            .fields()
+            .iter()
+            .zip_eq(data_types)
+            .enumerate()
+            .map(|(idx, (field, data_type))| {
+                ExprImpl::from(InputRef::new(idx, field.data_type()))
+                    .cast_implicit(data_type.clone())
+            })
+            .collect::<Result<_>>()?;
+        Ok(LogicalProject::create(plan, exprs))
+    }
+
+    /// Appends the expressions only used by the order by clause to the output of `plan`. They
+    /// can only be constants here, as the columns of a set operation can't be referenced by
+    /// expressions.
+    fn append_extra_order_exprs(plan: PlanRef, extra_order_exprs: Vec<ExprImpl>) -> PlanRef {
+        if extra_order_exprs.is_empty() {
+            return plan;
+        }
+        let exprs = plan
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| InputRef::new(idx, field.data_type()).into())
+            .chain(extra_order_exprs)
+            .collect();
+        LogicalProject::create(plan, exprs)
+    }
 }
@@ -16,6 +16,7 @@ use std::collections::{HashMap, HashSet};
 use std::mem::swap;
 use std::sync::Arc;
 
+use risingwave_batch::executor::ExecutorStats;
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_pb::batch_plan::{TaskId as TaskIdProst, TaskOutputId as TaskOutputIdProst};
@@ -61,7 +62,7 @@ enum QueryState {
     /// Running
     Running {
         _msg_sender: Sender<QueryMessage>,
-        _task_handle: JoinHandle<Result<()>>,
+        task_handle: JoinHandle<Result<()>>,
     },
 
     /// Failed
@@ -71,10 +72,11 @@ enum QueryState {
     Completed,
 }
 
+#[derive(Clone)]
 pub struct QueryExecution {
     query: Arc<Query>,
     state: Arc<RwLock<QueryState>>,
-    _stage_executions: Arc<HashMap<StageId, Arc<StageExecution>>>,
+    stage_executions: Arc<HashMap<StageId, Arc<StageExecution>>>,
 }
 
 struct QueryRunner {
@@ -101,6 +103,7 @@ impl QueryExecution {
         worker_node_manager: WorkerNodeManagerRef,
         hummock_snapshot_manager: HummockSnapshotManagerRef,
         compute_client_pool: ComputeClientPoolRef,
+        collect_stats: bool,
     ) -> Self {
         let query = Arc::new(query);
         let (sender, receiver) = channel(100);
@@ -124,6 +127,7 @@ impl QueryExecution {
                     sender.clone(),
                     children_stages,
                     compute_client_pool.clone(),
+                    collect_stats,
                 ));
                 stage_executions.insert(stage_id, stage_exec);
             }
@@ -153,7 +157,7 @@ impl QueryExecution {
         Self {
             query,
             state: Arc::new(RwLock::new(state)),
-            _stage_executions: stage_executions,
+            stage_executions,
         }
     }
 
@@ -188,7 +192,7 @@ impl QueryExecution {
 
                 *state = QueryState::Running {
                     _msg_sender: msg_sender,
-                    _task_handle: task_handle,
+                    task_handle,
                 };
 
                 Ok(root_stage)
@@ -201,10 +205,43 @@ impl QueryExecution {
         }
     }
 
-    /// Cancel execution of this query.
-    #[allow(unused)]
-    pub async fn abort(&mut self) -> Result<()> {
-        todo!()
+    /// Returns the runtime statistics of the executors in all tasks, merged by the identity of the
+    /// plan nodes. The query must be created with `collect_stats`.
+    pub async fn collect_executor_stats(&self) -> Result<HashMap<String, ExecutorStats>> {
+        let mut merged: HashMap<String, ExecutorStats> = HashMap::new();
+        for stage_execution in self.stage_executions.values() {
+            for stats in stage_execution.collect_executor_stats().await? {
+                merged
+                    .entry(stats.identity.clone())
+                    .or_default()
+                    .merge(&(&stats).into());
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Cancel execution of this query by aborting the scheduled tasks of all stages.
+    pub async fn abort(&self) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            if let QueryState::Running { task_handle, .. } = &*state {
+                // Stop scheduling more stages.
+                task_handle.abort();
+            }
+            *state = Failed;
+        }
+
+        let mut result = Ok(());
+        for (stage_id, stage_execution) in self.stage_executions.iter() {
+            if let Err(e) = stage_execution.stop().await {
+                warn!(
+                    "Failed to stop query stage {:?}-{:?}: {}",
+                    self.query.query_id, stage_id, e
+                );
+                result = Err(e);
+            }
+        }
+        result
     }
 }
 
@@ -405,6 +442,7 @@ mod tests {
                 MockFrontendMetaClient {},
             ))),
             compute_client_pool,
+            false,
         );
 
         assert!(query_execution.start().await.is_err());
@@ -433,6 +471,7 @@ mod tests {
                         column_id: 0.into(),
                         name: "a".to_string(),
                         type_name: String::new(),
+                        default_value: None,
                         field_descs: vec![],
                     },
                     ColumnDesc {
@@ -440,6 +479,7 @@ mod tests {
                         column_id: 1.into(),
                         name: "b".to_string(),
                         type_name: String::new(),
+                        default_value: None,
                         field_descs: vec![],
                     },
                 ],
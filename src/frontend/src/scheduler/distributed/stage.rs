@@ -26,13 +26,13 @@ use risingwave_pb::batch_plan::{
     TaskId as TaskIdProst, TaskOutputId,
 };
 use risingwave_pb::common::HostAddress;
+use risingwave_pb::task_service::ExecutorStats as ExecutorStatsProst;
 use risingwave_rpc_client::{ComputeClient, ComputeClientPoolRef};
 use tokio::spawn;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tracing::{error, info};
-use uuid::Uuid;
 use StageEvent::Failed;
 
 use crate::optimizer::plan_node::PlanNodeType;
@@ -97,6 +97,8 @@ pub struct StageExecution {
     /// We use `Vec` here since children's size is usually small.
     children: Vec<Arc<StageExecution>>,
     compute_client_pool: ComputeClientPoolRef,
+    /// Whether the tasks collect the runtime statistics of their executors.
+    collect_stats: bool,
 }
 
 struct StageRunner {
@@ -110,6 +112,7 @@ struct StageRunner {
     msg_sender: Sender<QueryMessage>,
     children: Vec<Arc<StageExecution>>,
     compute_client_pool: ComputeClientPoolRef,
+    collect_stats: bool,
 }
 
 impl TaskStatusHolder {
@@ -137,6 +140,7 @@ impl StageExecution {
         msg_sender: Sender<QueryMessage>,
         children: Vec<Arc<StageExecution>>,
         compute_client_pool: ComputeClientPoolRef,
+        collect_stats: bool,
     ) -> Self {
         let tasks = (0..stage.parallelism)
             .into_iter()
@@ -151,6 +155,7 @@ impl StageExecution {
             msg_sender,
             children,
             compute_client_pool,
+            collect_stats,
         }
     }
 
@@ -170,6 +175,7 @@ impl StageExecution {
                     children: self.children.clone(),
                     state: self.state.clone(),
                     compute_client_pool: self.compute_client_pool.clone(),
+                    collect_stats: self.collect_stats,
                 };
                 let handle = spawn(async move {
                     if let Err(e) = runner.run().await {
@@ -195,8 +201,56 @@ impl StageExecution {
         }
     }
 
+    /// Aborts the tasks of this stage that have been scheduled to compute nodes.
     pub async fn stop(&self) -> Result<()> {
-        todo!()
+        let mut futures = vec![];
+        for (task_id, status_holder) in self.tasks.iter() {
+            if let Some(location) = status_holder.get_status().location.clone() {
+                let task_id = TaskIdProst {
+                    query_id: self.stage.query_id.id.clone(),
+                    stage_id: self.stage.id,
+                    task_id: *task_id,
+                };
+                futures.push(async move {
+                    self.compute_client_pool
+                        .get_client_for_addr((&location).into())
+                        .await?
+                        .abort_task(task_id)
+                        .await
+                });
+            }
+        }
+        let mut buffered = stream::iter(futures).buffer_unordered(TASK_SCHEDULING_PARALLELISM);
+        let mut result = Ok(());
+        while let Some(res) = buffered.next().await {
+            // Keep aborting the other tasks even if one fails.
+            if let Err(e) = res {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    /// Returns the runtime statistics of the executors in all tasks of this stage, which must be
+    /// created with `collect_stats`.
+    pub async fn collect_executor_stats(&self) -> Result<Vec<ExecutorStatsProst>> {
+        let mut executor_stats = vec![];
+        for (task_id, status_holder) in self.tasks.iter() {
+            let location = status_holder.get_status().task_host_unchecked();
+            let task_id = TaskIdProst {
+                query_id: self.stage.query_id.id.clone(),
+                stage_id: self.stage.id,
+                task_id: *task_id,
+            };
+            let task_info = self
+                .compute_client_pool
+                .get_client_for_addr((&location).into())
+                .await?
+                .get_task_info(task_id)
+                .await?;
+            executor_stats.extend(task_info.executor_stats);
+        }
+        Ok(executor_stats)
     }
 
     pub async fn is_scheduled(&self) -> bool {
@@ -321,7 +375,7 @@ impl StageRunner {
 
         let t_id = task_id.task_id;
         compute_client
-            .create_task2(task_id, plan_fragment, self.epoch)
+            .create_task2(task_id, plan_fragment, self.epoch, self.collect_stats)
             .await?;
 
         self.tasks[&t_id].inner.store(Arc::new(TaskStatus {
@@ -360,31 +414,25 @@ impl StageRunner {
                     .unwrap();
 
                 match &execution_plan_node.node {
-                    NodeBody::Exchange(_exchange_node) => {
-                        PlanNodeProst {
-                            children: vec![],
-                            // TODO: Generate meaningful identify
-                            identity: Uuid::new_v4().to_string(),
-                            node_body: Some(NodeBody::Exchange(ExchangeNode {
+                    NodeBody::Exchange(_exchange_node) => PlanNodeProst {
+                        children: vec![],
+                        identity: execution_plan_node.identity(),
+                        node_body: Some(NodeBody::Exchange(ExchangeNode {
+                            sources: exchange_sources,
+                            input_schema: execution_plan_node.schema.clone(),
+                        })),
+                    },
+                    NodeBody::MergeSortExchange(sort_merge_exchange_node) => PlanNodeProst {
+                        children: vec![],
+                        identity: execution_plan_node.identity(),
+                        node_body: Some(NodeBody::MergeSortExchange(MergeSortExchangeNode {
+                            exchange: Some(ExchangeNode {
                                 sources: exchange_sources,
                                 input_schema: execution_plan_node.schema.clone(),
-                            })),
-                        }
-                    }
-                    NodeBody::MergeSortExchange(sort_merge_exchange_node) => {
-                        PlanNodeProst {
-                            children: vec![],
-                            // TODO: Generate meaningful identify
-                            identity: Uuid::new_v4().to_string(),
-                            node_body: Some(NodeBody::MergeSortExchange(MergeSortExchangeNode {
-                                exchange: Some(ExchangeNode {
-                                    sources: exchange_sources,
-                                    input_schema: execution_plan_node.schema.clone(),
-                                }),
-                                column_orders: sort_merge_exchange_node.column_orders.clone(),
-                            })),
-                        }
-                    }
+                            }),
+                            column_orders: sort_merge_exchange_node.column_orders.clone(),
+                        })),
+                    },
                     _ => unreachable!(),
                 }
             }
@@ -397,8 +445,7 @@ impl StageRunner {
 
                 PlanNodeProst {
                     children,
-                    // TODO: Generate meaningful identify
-                    identity: Uuid::new_v4().to_string(),
+                    identity: execution_plan_node.identity(),
                     node_body: Some(execution_plan_node.node.clone()),
                 }
             }
@@ -14,6 +14,7 @@
 
 //! Local execution for batch query.
 
+use futures::StreamExt;
 use futures_async_stream::try_stream;
 use risingwave_batch::executor::ExecutorBuilder;
 use risingwave_batch::task::TaskId;
@@ -26,24 +27,28 @@ use uuid::Uuid;
 use crate::optimizer::plan_node::PlanNodeType;
 use crate::scheduler::plan_fragmenter::{ExecutionPlanNode, Query};
 use crate::scheduler::task_context::FrontendBatchTaskContext;
-use crate::scheduler::{DataChunkStream, HummockSnapshotManagerRef};
+use crate::scheduler::{DataChunkStream, HummockSnapshotManagerRef, QueryCancelSignal};
 
 pub struct LocalQueryExecution {
     sql: String,
     query: Query,
     hummock_snapshot_manager: HummockSnapshotManagerRef,
+    /// The execution stops with an error once it resolves.
+    cancel_signal: QueryCancelSignal,
 }
 
 impl LocalQueryExecution {
     pub fn new<S: Into<String>>(
         query: Query,
         hummock_snapshot_manager: HummockSnapshotManagerRef,
+        cancel_signal: QueryCancelSignal,
         sql: S,
     ) -> Self {
         Self {
             sql: sql.into(),
             query,
             hummock_snapshot_manager,
+            cancel_signal,
         }
     }
 
@@ -71,9 +76,18 @@ impl LocalQueryExecution {
         let executor = ExecutorBuilder::new(&plan_node, &task_id, context, epoch);
         let executor = executor.build().await?;
 
-        #[for_await]
-        for chunk in executor.execute() {
-            yield chunk?;
+        // The executors run in this stream, so they are stopped as soon as it fails.
+        let mut data_chunk_stream = executor.execute();
+        let mut cancel_signal = self.cancel_signal;
+        loop {
+            let chunk = tokio::select! {
+                err = &mut cancel_signal => Err(err),
+                chunk = data_chunk_stream.next() => Ok(chunk),
+            };
+            match chunk? {
+                Some(chunk) => yield chunk?,
+                None => break,
+            }
         }
     }
 
@@ -14,6 +14,9 @@
 
 use std::sync::Arc;
 
+use futures::future::BoxFuture;
+use risingwave_common::error::RwError;
+
 use crate::session::SessionImpl;
 
 mod distributed;
@@ -27,6 +30,10 @@ pub mod worker_node_manager;
 pub use local::*;
 mod task_context;
 
+/// Resolves with the error to report when a running query should be cancelled. See
+/// [`SessionImpl::query_cancel_signal`].
+pub type QueryCancelSignal = BoxFuture<'static, RwError>;
+
 /// Context for mpp query execution.
 pub struct ExecutionContext {
     session: Arc<SessionImpl>,
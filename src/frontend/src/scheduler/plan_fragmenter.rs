@@ -74,6 +74,17 @@ impl ExecutionPlanNode {
     pub fn node_type(&self) -> PlanNodeType {
         self.plan_node_type
     }
+
+    /// The identity of the executors built from this node. See [`plan_node_identity`].
+    pub fn identity(&self) -> String {
+        plan_node_identity(self.plan_node_type, self.plan_node_id)
+    }
+}
+
+/// Returns the identity of the executors built from a plan node, which is unique in a query, so
+/// that the runtime statistics of the executors can be mapped back to the plan node.
+pub fn plan_node_identity(plan_node_type: PlanNodeType, plan_node_id: PlanNodeId) -> String {
+    format!("{:?}-{}", plan_node_type, plan_node_id.0)
 }
 
 /// `BatchPlanFragmenter` splits a query plan into fragments.
@@ -424,6 +435,7 @@ mod tests {
                         column_id: 0.into(),
                         name: "a".to_string(),
                         type_name: String::new(),
+                        default_value: None,
                         field_descs: vec![],
                     },
                     ColumnDesc {
@@ -431,6 +443,7 @@ mod tests {
                         column_id: 1.into(),
                         name: "b".to_string(),
                         type_name: String::new(),
+                        default_value: None,
                         field_descs: vec![],
                     },
                 ],
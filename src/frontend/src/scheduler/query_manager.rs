@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
 use futures::Stream;
-use futures_async_stream::try_stream;
-use log::debug;
+use futures_async_stream::{for_await, try_stream};
+use log::{debug, warn};
+use risingwave_batch::executor::ExecutorStats;
 use risingwave_common::array::DataChunk;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::batch_plan::{PlanNode as BatchPlanProst, TaskId, TaskOutputId};
@@ -27,7 +29,7 @@ use risingwave_rpc_client::{
 };
 use uuid::Uuid;
 
-use super::HummockSnapshotManagerRef;
+use super::{HummockSnapshotManagerRef, QueryCancelSignal};
 use crate::scheduler::distributed::QueryExecution;
 use crate::scheduler::plan_fragmenter::{Query, QueryId};
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
@@ -71,9 +73,10 @@ impl QueryManager {
     /// This is kept for dml only.
     pub async fn schedule_single(
         &self,
-        _context: ExecutionContextRef,
+        context: ExecutionContextRef,
         plan: BatchPlanProst,
     ) -> Result<impl Stream<Item = Result<DataChunk>>> {
+        let cancel_signal = context.session().query_cancel_signal();
         let worker_node_addr = self.worker_node_manager.next_random()?.host.unwrap();
         let compute_client = self
             .compute_client_pool
@@ -118,14 +121,42 @@ impl QueryManager {
             self.compute_client_pool.clone(),
         );
 
-        Ok(query_result_fetcher.run())
+        Ok(query_result_fetcher.run(cancel_signal, None))
     }
 
     pub async fn schedule(
         &self,
-        _context: ExecutionContextRef,
+        context: ExecutionContextRef,
         query: Query,
     ) -> Result<impl DataChunkStream> {
+        let cancel_signal = context.session().query_cancel_signal();
+        let (query_execution, query_result_fetcher) = self.start_query(query, false).await?;
+        Ok(query_result_fetcher.run(cancel_signal, Some(query_execution)))
+    }
+
+    /// Runs the query to the end and discards its output, and then returns the runtime statistics
+    /// of its executors, merged by the identity of the plan nodes. This is used by
+    /// `EXPLAIN ANALYZE`.
+    pub async fn schedule_with_stats(
+        &self,
+        context: ExecutionContextRef,
+        query: Query,
+    ) -> Result<HashMap<String, ExecutorStats>> {
+        let cancel_signal = context.session().query_cancel_signal();
+        let (query_execution, query_result_fetcher) = self.start_query(query, true).await?;
+
+        #[for_await]
+        for chunk in query_result_fetcher.run(cancel_signal, Some(query_execution.clone())) {
+            chunk?;
+        }
+        query_execution.collect_executor_stats().await
+    }
+
+    async fn start_query(
+        &self,
+        query: Query,
+        collect_stats: bool,
+    ) -> Result<(QueryExecution, QueryResultFetcher)> {
         let query_id = query.query_id().clone();
         // Cheat compiler to resolve type
         let epoch = self
@@ -139,19 +170,18 @@ impl QueryManager {
             self.worker_node_manager.clone(),
             self.hummock_snapshot_manager.clone(),
             self.compute_client_pool.clone(),
+            collect_stats,
         );
 
-        let query_result_fetcher = match query_execution.start().await {
-            Ok(query_result_fetcher) => query_result_fetcher,
+        match query_execution.start().await {
+            Ok(query_result_fetcher) => Ok((query_execution, query_result_fetcher)),
             Err(e) => {
                 self.hummock_snapshot_manager
                     .unpin_snapshot(epoch, &query_id)
                     .await?;
-                return Err(e);
+                Err(e)
             }
-        };
-
-        Ok(query_result_fetcher.run())
+        }
     }
 }
 
@@ -172,8 +202,15 @@ impl QueryResultFetcher {
         }
     }
 
+    /// Fetches the output of the root task. Once `cancel_signal` resolves, the tasks of the query
+    /// are aborted and the stream fails. `query_execution` is `None` for a query scheduled to a
+    /// single node, which has only the root task.
     #[try_stream(ok = DataChunk, error = RwError)]
-    async fn run(self) {
+    async fn run(
+        self,
+        mut cancel_signal: QueryCancelSignal,
+        query_execution: Option<QueryExecution>,
+    ) {
         debug!(
             "Starting to run query result fetcher, task output id: {:?}, task_host: {:?}",
             self.task_output_id, self.task_host
@@ -183,13 +220,44 @@ impl QueryResultFetcher {
             .get_client_for_addr((&self.task_host).into())
             .await?;
 
-        let mut source = compute_client.get_data(self.task_output_id).await?;
-        while let Some(chunk) = source.take_data().await? {
-            yield chunk;
+        let mut source = compute_client.get_data(self.task_output_id.clone()).await?;
+        loop {
+            let chunk = tokio::select! {
+                err = &mut cancel_signal => {
+                    self.abort(&compute_client, query_execution.as_ref()).await;
+                    Err(err)
+                }
+                chunk = source.take_data() => chunk,
+            };
+            match chunk? {
+                Some(chunk) => yield chunk,
+                None => break,
+            }
         }
 
         let epoch = self.epoch;
     }
+
+    /// Aborts the running tasks of the query on compute nodes.
+    async fn abort(
+        &self,
+        compute_client: &ComputeClient,
+        query_execution: Option<&QueryExecution>,
+    ) {
+        let res = match query_execution {
+            Some(query_execution) => query_execution.abort().await,
+            None => {
+                let task_id = self.task_output_id.task_id.clone().unwrap();
+                compute_client.abort_task(task_id).await
+            }
+        };
+        if let Err(e) = res {
+            warn!(
+                "Failed to abort query of task output {:?}: {}",
+                self.task_output_id, e
+            );
+        }
+    }
 }
 
 impl Debug for QueryResultFetcher {
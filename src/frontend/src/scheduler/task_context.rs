@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use risingwave_batch::executor::BatchMetrics;
 use risingwave_batch::task::{BatchTaskContext, TaskId, TaskOutput, TaskOutputId};
+use risingwave_common::config::BatchConfig;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::util::addr::HostAddr;
 use risingwave_source::SourceManagerRef;
@@ -44,4 +45,8 @@ impl BatchTaskContext for FrontendBatchTaskContext {
     fn stats(&self) -> Arc<BatchMetrics> {
         todo!()
     }
+
+    fn config(&self) -> Arc<BatchConfig> {
+        Arc::new(BatchConfig::default())
+    }
 }
@@ -14,34 +14,53 @@
 
 use std::collections::HashMap;
 use std::fmt::Formatter;
+use std::io::{Error, ErrorKind};
 use std::marker::Sync;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use parking_lot::RwLock;
+use futures::future::{self, FutureExt};
+use parking_lot::{Mutex, RwLock};
+use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::PgResponse;
-use pgwire::pg_server::{BoxedError, Session, SessionManager};
+use pgwire::pg_server::{BoxedError, Session, SessionId, SessionManager, UserAuthenticator};
+use rand::RngCore;
+#[cfg(test)]
+use risingwave_common::catalog::{
+    DEFAULT_DATABASE_NAME, DEFAULT_SUPER_USER, DEFAULT_SUPER_USER_ID,
+};
 use risingwave_common::config::FrontendConfig;
-use risingwave_common::error::{Result, RwError};
+use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::util::addr::HostAddr;
 use risingwave_pb::common::WorkerType;
+use risingwave_pb::user::auth_info::EncryptionType;
 use risingwave_rpc_client::{ComputeClientPool, MetaClient};
 use risingwave_sqlparser::parser::Parser;
 use tokio::sync::oneshot::Sender;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
+use crate::binder::ParameterValues;
 use crate::catalog::catalog_service::{CatalogReader, CatalogWriter, CatalogWriterImpl};
 use crate::catalog::root_catalog::Catalog;
+use crate::config::{StatementTimeout, STATEMENT_TIMEOUT};
 use crate::handler::dml::IMPLICIT_FLUSH;
+use crate::handler::extended_handle::{handle_execute, handle_parse, Portal, PrepareStatement};
 use crate::handler::handle;
+use crate::handler::util::{data_type_to_type_oid, type_oid_to_data_type};
 use crate::meta_client::{FrontendMetaClient, FrontendMetaClientImpl};
 use crate::observer::observer_manager::ObserverManager;
 use crate::optimizer::plan_node::PlanNodeId;
 use crate::scheduler::worker_node_manager::{WorkerNodeManager, WorkerNodeManagerRef};
-use crate::scheduler::{HummockSnapshotManager, HummockSnapshotManagerRef, QueryManager};
+use crate::scheduler::{
+    HummockSnapshotManager, HummockSnapshotManagerRef, QueryCancelSignal, QueryManager,
+};
+use crate::user::user_authentication::md5_hash_with_salt;
+use crate::user::user_manager::UserInfoManager;
+use crate::user::user_service::{UserInfoReader, UserInfoWriter, UserInfoWriterImpl};
+use crate::user::UserId;
 use crate::FrontendOpts;
 
 pub struct OptimizerContext {
@@ -124,6 +143,8 @@ pub struct FrontendEnv {
     meta_client: Arc<dyn FrontendMetaClient>,
     catalog_writer: Arc<dyn CatalogWriter>,
     catalog_reader: CatalogReader,
+    user_info_writer: Arc<dyn UserInfoWriter>,
+    user_info_reader: UserInfoReader,
     worker_node_manager: WorkerNodeManagerRef,
     query_manager: QueryManager,
     hummock_snapshot_manager: HummockSnapshotManagerRef,
@@ -138,11 +159,14 @@ impl FrontendEnv {
     }
 
     pub fn mock() -> Self {
-        use crate::test_utils::{MockCatalogWriter, MockFrontendMetaClient};
+        use crate::test_utils::{MockCatalogWriter, MockFrontendMetaClient, MockUserInfoWriter};
 
         let catalog = Arc::new(RwLock::new(Catalog::default()));
         let catalog_writer = Arc::new(MockCatalogWriter::new(catalog.clone()));
         let catalog_reader = CatalogReader::new(catalog);
+        let user_info_manager = Arc::new(RwLock::new(UserInfoManager::default()));
+        let user_info_writer = Arc::new(MockUserInfoWriter::new(user_info_manager.clone()));
+        let user_info_reader = UserInfoReader::new(user_info_manager);
         let worker_node_manager = Arc::new(WorkerNodeManager::mock(vec![]));
         let meta_client = Arc::new(MockFrontendMetaClient {});
         let hummock_snapshot_manager = Arc::new(HummockSnapshotManager::new(meta_client.clone()));
@@ -156,6 +180,8 @@ impl FrontendEnv {
             meta_client,
             catalog_writer,
             catalog_reader,
+            user_info_writer,
+            user_info_reader,
             worker_node_manager,
             query_manager,
             hummock_snapshot_manager,
@@ -193,6 +219,14 @@ impl FrontendEnv {
         ));
         let catalog_reader = CatalogReader::new(catalog.clone());
 
+        let (user_info_updated_tx, user_info_updated_rx) = watch::channel(0);
+        let user_info_manager = Arc::new(RwLock::new(UserInfoManager::default()));
+        let user_info_reader = UserInfoReader::new(user_info_manager.clone());
+        let user_info_writer = Arc::new(UserInfoWriterImpl::new(
+            meta_client.clone(),
+            user_info_updated_rx,
+        ));
+
         let worker_node_manager = Arc::new(WorkerNodeManager::new(meta_client.clone()).await?);
 
         let frontend_meta_client = Arc::new(FrontendMetaClientImpl(meta_client.clone()));
@@ -212,6 +246,8 @@ impl FrontendEnv {
             catalog,
             catalog_updated_tx,
             hummock_snapshot_manager.clone(),
+            user_info_manager,
+            user_info_updated_tx,
         )
         .await;
         let observer_join_handle = observer_manager.start().await?;
@@ -222,6 +258,8 @@ impl FrontendEnv {
             Self {
                 catalog_reader,
                 catalog_writer,
+                user_info_reader,
+                user_info_writer,
                 worker_node_manager,
                 meta_client: frontend_meta_client,
                 query_manager,
@@ -243,6 +281,16 @@ impl FrontendEnv {
         &self.catalog_reader
     }
 
+    /// Get a reference to the frontend env's user info writer.
+    pub fn user_info_writer(&self) -> &dyn UserInfoWriter {
+        &*self.user_info_writer
+    }
+
+    /// Get a reference to the frontend env's user info reader.
+    pub fn user_info_reader(&self) -> &UserInfoReader {
+        &self.user_info_reader
+    }
+
     pub fn worker_node_manager(&self) -> &WorkerNodeManager {
         &*self.worker_node_manager
     }
@@ -271,8 +319,15 @@ impl FrontendEnv {
 pub struct SessionImpl {
     env: FrontendEnv,
     database: String,
+    user_name: String,
+    user_id: UserId,
+    user_authenticator: UserAuthenticator,
+    /// Identifies the session in cancel requests.
+    id: SessionId,
     /// Stores the value of configurations.
     config_map: RwLock<HashMap<String, ConfigEntry>>,
+    /// Notifies the running queries subscribed by [`SessionImpl::query_cancel_signal`] to cancel.
+    cancel_tx: watch::Sender<()>,
 }
 
 #[derive(Clone)]
@@ -299,11 +354,23 @@ impl ConfigEntry {
 }
 
 impl SessionImpl {
-    pub fn new(env: FrontendEnv, database: String) -> Self {
+    pub fn new(
+        env: FrontendEnv,
+        database: String,
+        user_name: String,
+        user_id: UserId,
+        user_authenticator: UserAuthenticator,
+        id: SessionId,
+    ) -> Self {
         Self {
             env,
             database,
+            user_name,
+            user_id,
+            user_authenticator,
+            id,
             config_map: Self::init_config_map(),
+            cancel_tx: watch::channel(()).0,
         }
     }
 
@@ -311,8 +378,13 @@ impl SessionImpl {
     pub fn mock() -> Self {
         Self {
             env: FrontendEnv::mock(),
-            database: "dev".to_string(),
+            database: DEFAULT_DATABASE_NAME.to_string(),
+            user_name: DEFAULT_SUPER_USER.to_string(),
+            user_id: DEFAULT_SUPER_USER_ID,
+            user_authenticator: UserAuthenticator::None,
+            id: (0, 0),
             config_map: Self::init_config_map(),
+            cancel_tx: watch::channel(()).0,
         }
     }
 
@@ -324,6 +396,14 @@ impl SessionImpl {
         &self.database
     }
 
+    pub fn user_name(&self) -> &str {
+        &self.user_name
+    }
+
+    pub fn user_id(&self) -> UserId {
+        self.user_id
+    }
+
     /// Set configuration values in this session.
     /// For example, `set_config("RW_IMPLICIT_FLUSH", true)` will implicit flush for every inserts.
     pub fn set_config(&self, key: &str, val: &str) {
@@ -338,6 +418,41 @@ impl SessionImpl {
         reader.get(key).cloned()
     }
 
+    /// Returns a signal for the query starting now, which resolves with the error to report once
+    /// the query is cancelled by [`SessionImpl::cancel_running_queries`] or runs longer than
+    /// `statement_timeout`.
+    pub fn query_cancel_signal(&self) -> QueryCancelSignal {
+        let mut cancel_rx = self.cancel_tx.subscribe();
+        let timeout = self
+            .get_config(STATEMENT_TIMEOUT)
+            .map(|entry| entry.get_val(StatementTimeout::default()))
+            .unwrap_or_default()
+            .0;
+        async move {
+            let timeout = async move {
+                match timeout {
+                    Some(timeout) => tokio::time::sleep(timeout).await,
+                    None => future::pending().await,
+                }
+            };
+            tokio::select! {
+                _ = cancel_rx.changed() => {
+                    RwError::from(ErrorCode::QueryCancelled("user request".to_string()))
+                }
+                _ = timeout => {
+                    RwError::from(ErrorCode::QueryCancelled("statement timeout".to_string()))
+                }
+            }
+        }
+        .boxed()
+    }
+
+    /// Cancels the queries running in this session.
+    pub fn cancel_running_queries(&self) {
+        // It fails only if no query is running, which has nothing to cancel.
+        let _ = self.cancel_tx.send(());
+    }
+
     fn init_config_map() -> RwLock<HashMap<String, ConfigEntry>> {
         let mut map = HashMap::new();
         // FIXME: May need better init way + default config.
@@ -354,13 +469,97 @@ pub struct SessionManagerImpl {
     observer_join_handle: JoinHandle<()>,
     heartbeat_join_handle: JoinHandle<()>,
     _heartbeat_shutdown_sender: Sender<()>,
+    /// The connected sessions, used to route cancel requests.
+    sessions_map: Mutex<HashMap<SessionId, Arc<SessionImpl>>>,
+    /// The process id of the next session.
+    next_process_id: AtomicI32,
 }
 
 impl SessionManager for SessionManagerImpl {
     type Session = SessionImpl;
 
-    fn connect(&self, database: &str) -> std::result::Result<Arc<Self::Session>, BoxedError> {
-        Ok(SessionImpl::new(self.env.clone(), database.to_string()).into())
+    fn connect(
+        &self,
+        database: &str,
+        user_name: &str,
+    ) -> std::result::Result<Arc<Self::Session>, BoxedError> {
+        let catalog_reader = self.env.catalog_reader();
+        let reader = catalog_reader.read_guard();
+        if reader.get_database_by_name(database).is_err() {
+            return Err(Box::new(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Not found database name: {}", database),
+            )));
+        }
+        let user_reader = self.env.user_info_reader();
+        let reader = user_reader.read_guard();
+        let user = reader.get_user_by_name(user_name).ok_or_else(|| {
+            Box::new(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Role {} does not exist", user_name),
+            ))
+        })?;
+        // TODO: check the `CONNECT` privilege on the database. Postgres grants it to `PUBLIC` by
+        // default, and since there are no roles or `PUBLIC` yet, every user that can login is
+        // allowed to connect to every database.
+        if !user.can_login {
+            return Err(Box::new(Error::new(
+                ErrorKind::InvalidInput,
+                format!("User {} is not allowed to login", user_name),
+            )));
+        }
+
+        let user_authenticator = match &user.auth_info {
+            None => UserAuthenticator::None,
+            Some(auth_info) => match auth_info.encryption_type() {
+                EncryptionType::Plaintext => {
+                    UserAuthenticator::ClearText(auth_info.encrypted_value.clone())
+                }
+                EncryptionType::Md5 => {
+                    let mut salt = [0; 4];
+                    rand::thread_rng().fill_bytes(&mut salt);
+                    UserAuthenticator::Md5WithSalt {
+                        encrypted_password: md5_hash_with_salt(&auth_info.encrypted_value, &salt),
+                        salt,
+                    }
+                }
+                _ => {
+                    return Err(Box::new(Error::new(
+                        ErrorKind::Unsupported,
+                        format!("Unsupported auth type: {:?}", auth_info.encryption_type()),
+                    )));
+                }
+            },
+        };
+
+        let session_id = (
+            self.next_process_id.fetch_add(1, Ordering::Relaxed),
+            rand::thread_rng().next_u32() as i32,
+        );
+        let session: Arc<SessionImpl> = SessionImpl::new(
+            self.env.clone(),
+            database.to_string(),
+            user_name.to_string(),
+            user.id,
+            user_authenticator,
+            session_id,
+        )
+        .into();
+        self.sessions_map.lock().insert(session_id, session.clone());
+        Ok(session)
+    }
+
+    fn cancel_queries_in_session(&self, session_id: SessionId) {
+        // Clone the session out, so that the map is not locked while cancelling.
+        let session = self.sessions_map.lock().get(&session_id).cloned();
+        match session {
+            Some(session) => session.cancel_running_queries(),
+            None => tracing::info!("Session {:?} to cancel does not exist", session_id),
+        }
+    }
+
+    fn end_session(&self, session: &Self::Session) {
+        self.sessions_map.lock().remove(&session.id());
     }
 }
 
@@ -373,6 +572,8 @@ impl SessionManagerImpl {
             observer_join_handle: join_handle,
             heartbeat_join_handle,
             _heartbeat_shutdown_sender: heartbeat_shutdown_sender,
+            sessions_map: Mutex::new(HashMap::new()),
+            next_process_id: AtomicI32::new(1),
         })
     }
 
@@ -385,6 +586,17 @@ impl SessionManagerImpl {
 
 #[async_trait::async_trait]
 impl Session for SessionImpl {
+    type Portal = Portal;
+    type PreparedStatement = PrepareStatement;
+
+    fn user_authenticator(&self) -> &UserAuthenticator {
+        &self.user_authenticator
+    }
+
+    fn id(&self) -> SessionId {
+        self.id
+    }
+
     async fn run_statement(
         self: Arc<Self>,
         sql: &str,
@@ -405,23 +617,108 @@ impl Session for SessionImpl {
             ));
         }
         let stmt = stmts.swap_remove(0);
-        let rsp = handle(self, stmt).await.map_err(|e| {
-            tracing::error!("failed to handle sql:\n{}:\n{}", sql, e);
+        let rsp = handle(self, stmt, ParameterValues::default())
+            .await
+            .map_err(|e| {
+                tracing::error!("failed to handle sql:\n{}:\n{}", sql, e);
+                e
+            })?;
+        Ok(rsp)
+    }
+
+    fn parse(
+        self: Arc<Self>,
+        sql: &str,
+        param_types: Vec<Option<TypeOid>>,
+    ) -> std::result::Result<PrepareStatement, BoxedError> {
+        let mut stmts = Parser::parse_sql(sql).map_err(|e| {
+            tracing::error!("failed to parse sql:\n{}:\n{}", sql, e);
+            e
+        })?;
+        if stmts.len() > 1 {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(
+                "cannot insert multiple commands into a prepared statement".to_string(),
+            ))
+            .into());
+        }
+        let param_types = param_types
+            .into_iter()
+            .map(|t| t.map(type_oid_to_data_type).transpose())
+            .collect::<Result<Vec<_>>>()?;
+        let prepared = handle_parse(self, stmts.pop(), param_types).map_err(|e| {
+            tracing::error!("failed to prepare sql:\n{}:\n{}", sql, e);
+            e
+        })?;
+        Ok(prepared)
+    }
+
+    fn bind(
+        self: Arc<Self>,
+        prepared_statement: PrepareStatement,
+        params: Vec<Option<String>>,
+    ) -> std::result::Result<Portal, BoxedError> {
+        Ok(prepared_statement.bind(params))
+    }
+
+    async fn execute(
+        self: Arc<Self>,
+        portal: Portal,
+    ) -> std::result::Result<PgResponse, BoxedError> {
+        let rsp = handle_execute(self, portal).await.map_err(|e| {
+            tracing::error!("failed to execute portal: {}", e);
             e
         })?;
         Ok(rsp)
     }
+
+    fn describe_statement(
+        &self,
+        prepared_statement: &PrepareStatement,
+    ) -> (Vec<TypeOid>, Vec<PgFieldDescriptor>) {
+        (
+            prepared_statement
+                .param_types()
+                .iter()
+                .cloned()
+                .map(data_type_to_type_oid)
+                .collect(),
+            prepared_statement.row_description().to_vec(),
+        )
+    }
+
+    fn describe_portal(&self, portal: &Portal) -> Vec<PgFieldDescriptor> {
+        portal.row_description().to_vec()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use assert_impl::assert_impl;
 
-    use crate::session::OptimizerContextRef;
+    use crate::config::STATEMENT_TIMEOUT;
+    use crate::session::{OptimizerContextRef, SessionImpl};
 
     #[test]
     fn check_query_context_ref() {
         assert_impl!(Send: OptimizerContextRef);
         assert_impl!(!Sync: OptimizerContextRef);
     }
+
+    #[tokio::test]
+    async fn test_query_cancel_signal() {
+        let session = SessionImpl::mock();
+
+        let signal = session.query_cancel_signal();
+        session.cancel_running_queries();
+        assert_eq!(
+            signal.await.to_string(),
+            "canceling statement due to user request"
+        );
+
+        session.set_config(STATEMENT_TIMEOUT, "'10ms'");
+        assert_eq!(
+            session.query_cancel_signal().await.to_string(),
+            "canceling statement due to statement timeout"
+        );
+    }
 }
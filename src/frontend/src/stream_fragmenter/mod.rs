@@ -98,7 +98,9 @@ impl StreamFragmenter {
                 // For stateful operators, set `exchange_flag = true`. If it's already true, force
                 // add an exchange.
                 NodeBody::HashAgg(_)
+                | NodeBody::OverAgg(_)
                 | NodeBody::HashJoin(_)
+                | NodeBody::DynamicFilter(_)
                 | NodeBody::DeltaIndexJoin(_)
                 | NodeBody::Chain(_) => {
                     // We didn't make `fields` available on Java frontend yet, so we check if schema
@@ -185,7 +187,9 @@ impl StreamFragmenter {
         match stream_node.get_node_body()? {
             NodeBody::Source(_) => current_fragment.fragment_type = FragmentType::Source,
 
-            NodeBody::Materialize(_) => current_fragment.fragment_type = FragmentType::Sink,
+            NodeBody::Materialize(_) | NodeBody::Sink(_) => {
+                current_fragment.fragment_type = FragmentType::Sink
+            }
 
             // TODO: Force singleton for TopN as a workaround. We should implement two phase TopN.
             NodeBody::TopN(_) => current_fragment.is_singleton = true,
@@ -231,11 +235,19 @@ impl StreamFragmenter {
             }
         }
 
-        // Rewrite hash agg. One agg call -> one table id.
+        // Rewrite hash agg. One agg call -> one table id, and one more table id for the dedup
+        // state of each distinct agg call.
         if let NodeBody::HashAgg(hash_agg_node) = stream_node.node_body.as_mut().unwrap() {
             for _ in &hash_agg_node.agg_calls {
                 hash_agg_node.table_ids.push(state.gen_table_id());
             }
+            for (idx, agg_call) in hash_agg_node.agg_calls.iter().enumerate() {
+                if agg_call.distinct {
+                    hash_agg_node
+                        .distinct_dedup_table_ids
+                        .insert(idx as u32, state.gen_table_id());
+                }
+            }
         }
 
         match stream_node.node_body.as_mut().unwrap() {
@@ -243,6 +255,18 @@ impl StreamFragmenter {
                 for _ in &node.agg_calls {
                     node.table_ids.push(state.gen_table_id());
                 }
+                for (idx, agg_call) in node.agg_calls.iter().enumerate() {
+                    if agg_call.distinct {
+                        node.distinct_dedup_table_ids
+                            .insert(idx as u32, state.gen_table_id());
+                    }
+                }
+            }
+            // One table for the rows of all partitions.
+            NodeBody::OverAgg(node) => node.table_id = state.gen_table_id(),
+            NodeBody::DynamicFilter(node) => {
+                node.left_table_id = state.gen_table_id();
+                node.right_table_id = state.gen_table_id();
             }
             _ => {}
         }
@@ -19,14 +19,23 @@ use std::sync::Arc;
 
 use parking_lot::RwLock;
 use pgwire::pg_response::PgResponse;
-use pgwire::pg_server::{BoxedError, Session, SessionManager};
-use risingwave_common::catalog::{TableId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+use pgwire::pg_server::{BoxedError, Session, SessionId, SessionManager, UserAuthenticator};
+use risingwave_common::catalog::{
+    TableId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, DEFAULT_SUPER_USER,
+    DEFAULT_SUPER_USER_FOR_PG, DEFAULT_SUPER_USER_FOR_PG_ID, DEFAULT_SUPER_USER_ID,
+    NON_RESERVED_USER_ID,
+};
 use risingwave_common::error::Result;
+use risingwave_pb::catalog::source::Info;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
+    Database as ProstDatabase, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
+    Table as ProstTable,
 };
+use risingwave_pb::ddl_service::alter_source_request::Change;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
+use risingwave_pb::user::update_user_request::UpdateField;
+use risingwave_pb::user::{GrantPrivilege, UserInfo};
 use risingwave_sqlparser::ast::Statement;
 use risingwave_sqlparser::parser::Parser;
 use tempfile::{Builder, NamedTempFile};
@@ -39,6 +48,9 @@ use crate::meta_client::FrontendMetaClient;
 use crate::optimizer::PlanRef;
 use crate::planner::Planner;
 use crate::session::{FrontendEnv, OptimizerContext, SessionImpl};
+use crate::user::user_manager::UserInfoManager;
+use crate::user::user_service::UserInfoWriter;
+use crate::user::UserId;
 use crate::FrontendOpts;
 
 /// An embedded frontend without starting meta and without starting frontend as a tcp server.
@@ -50,9 +62,18 @@ pub struct LocalFrontend {
 impl SessionManager for LocalFrontend {
     type Session = SessionImpl;
 
-    fn connect(&self, _database: &str) -> std::result::Result<Arc<Self::Session>, BoxedError> {
+    fn connect(
+        &self,
+        _database: &str,
+        _user_name: &str,
+    ) -> std::result::Result<Arc<Self::Session>, BoxedError> {
         Ok(self.session_ref())
     }
+
+    /// The sessions are not tracked, since a new one is created for every statement.
+    fn cancel_queries_in_session(&self, _session_id: SessionId) {}
+
+    fn end_session(&self, _session: &Self::Session) {}
 }
 
 impl LocalFrontend {
@@ -69,6 +90,18 @@ impl LocalFrontend {
         self.session_ref().run_statement(sql.as_str()).await
     }
 
+    /// Run the sql in a session of the user `user_name`, which must exist.
+    pub async fn run_user_sql(
+        &self,
+        sql: impl Into<String>,
+        user_name: &str,
+    ) -> std::result::Result<PgResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let sql = sql.into();
+        self.session_user_ref(user_name)
+            .run_statement(sql.as_str())
+            .await
+    }
+
     pub async fn query_formatted_result(&self, sql: impl Into<String>) -> Vec<String> {
         self.run_sql(sql)
             .await
@@ -86,10 +119,7 @@ impl LocalFrontend {
             let session = self.session_ref();
 
             let bound = {
-                let mut binder = Binder::new(
-                    session.env().catalog_reader().read_guard(),
-                    session.database().to_string(),
-                );
+                let mut binder = Binder::new(&session);
                 binder.bind(Statement::Query(query.clone()))?
             };
             Planner::new(OptimizerContext::new(session).into())
@@ -101,10 +131,34 @@ impl LocalFrontend {
         }
     }
 
+    pub fn session_user_ref(&self, user_name: &str) -> Arc<SessionImpl> {
+        let user_id = self
+            .env
+            .user_info_reader()
+            .read_guard()
+            .get_user_by_name(user_name)
+            .unwrap()
+            .id;
+        Arc::new(SessionImpl::new(
+            self.env.clone(),
+            DEFAULT_DATABASE_NAME.to_string(),
+            user_name.to_string(),
+            user_id,
+            UserAuthenticator::None,
+            // The id is unused since the sessions are never cancelled.
+            (0, 0),
+        ))
+    }
+
     pub fn session_ref(&self) -> Arc<SessionImpl> {
         Arc::new(SessionImpl::new(
             self.env.clone(),
             DEFAULT_DATABASE_NAME.to_string(),
+            DEFAULT_SUPER_USER.to_string(),
+            DEFAULT_SUPER_USER_ID,
+            UserAuthenticator::None,
+            // The id is unused since the sessions are never cancelled.
+            (0, 0),
         ))
     }
 }
@@ -114,24 +168,32 @@ pub struct MockCatalogWriter {
     id: AtomicU32,
     table_id_to_schema_id: RwLock<HashMap<u32, SchemaId>>,
     schema_id_to_database_id: RwLock<HashMap<u32, DatabaseId>>,
+    sources: RwLock<HashMap<u32, ProstSource>>,
 }
 
 #[async_trait::async_trait]
 impl CatalogWriter for MockCatalogWriter {
-    async fn create_database(&self, db_name: &str) -> Result<()> {
+    async fn create_database(&self, db_name: &str, owner: UserId) -> Result<()> {
         self.catalog.write().create_database(ProstDatabase {
             name: db_name.to_string(),
             id: self.gen_id(),
+            owner,
         });
         Ok(())
     }
 
-    async fn create_schema(&self, db_id: DatabaseId, schema_name: &str) -> Result<()> {
+    async fn create_schema(
+        &self,
+        db_id: DatabaseId,
+        schema_name: &str,
+        owner: UserId,
+    ) -> Result<()> {
         let id = self.gen_id();
         self.catalog.write().create_schema(ProstSchema {
             id,
             name: schema_name.to_string(),
             database_id: db_id,
+            owner,
         });
         self.add_schema_id(id, db_id);
         Ok(())
@@ -165,6 +227,13 @@ impl CatalogWriter for MockCatalogWriter {
         self.create_source_inner(source).map(|_| ())
     }
 
+    async fn create_sink(&self, mut sink: ProstSink, _graph: StreamFragmentGraph) -> Result<()> {
+        sink.id = self.gen_id();
+        self.catalog.write().create_sink(&sink);
+        self.add_table_or_source_id(sink.id, sink.schema_id, sink.database_id);
+        Ok(())
+    }
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()> {
         let (database_id, schema_id) = self.drop_table_or_source_id(source_id);
         self.drop_table_or_source_id(table_id.table_id);
@@ -174,6 +243,7 @@ impl CatalogWriter for MockCatalogWriter {
         self.catalog
             .write()
             .drop_source(database_id, schema_id, source_id);
+        self.sources.write().remove(&source_id);
         Ok(())
     }
 
@@ -182,6 +252,53 @@ impl CatalogWriter for MockCatalogWriter {
         self.catalog
             .write()
             .drop_source(database_id, schema_id, source_id);
+        self.sources.write().remove(&source_id);
+        Ok(())
+    }
+
+    async fn alter_source(&self, source_id: u32, _version: u64, change: Change) -> Result<()> {
+        let mut source = self.sources.read().get(&source_id).unwrap().clone();
+        let mut table = self.get_associated_table(&source);
+        let columns = match source.info.as_mut().unwrap() {
+            Info::StreamSource(info) => &mut info.columns,
+            Info::TableSource(info) => &mut info.columns,
+        };
+
+        match change {
+            Change::AddColumn(mut column) => {
+                column.column_desc.as_mut().unwrap().column_id = columns.len() as i32;
+                columns.push(column.clone());
+                if let Some(table) = &mut table {
+                    table.columns.push(column);
+                }
+            }
+            Change::DropColumnId(column_id) => {
+                for column in columns
+                    .iter_mut()
+                    .chain(table.iter_mut().flat_map(|table| table.columns.iter_mut()))
+                    .filter(|column| column.column_desc.as_ref().unwrap().column_id == column_id)
+                {
+                    column.column_desc.as_mut().unwrap().name = format!("_dropped_{}", column_id);
+                    column.is_hidden = true;
+                }
+            }
+        }
+
+        source.version += 1;
+        if let Some(mut table) = table {
+            table.version += 1;
+            self.catalog.write().update_table(&table);
+        }
+        self.catalog.write().update_source(source.clone());
+        self.sources.write().insert(source_id, source);
+        Ok(())
+    }
+
+    async fn drop_sink(&self, sink_id: u32) -> Result<()> {
+        let (database_id, schema_id) = self.drop_table_or_source_id(sink_id);
+        self.catalog
+            .write()
+            .drop_sink(database_id, schema_id, sink_id);
         Ok(())
     }
 
@@ -210,11 +327,13 @@ impl MockCatalogWriter {
         catalog.write().create_database(ProstDatabase {
             name: DEFAULT_DATABASE_NAME.to_string(),
             id: 0,
+            owner: DEFAULT_SUPER_USER_ID,
         });
         catalog.write().create_schema(ProstSchema {
             id: 0,
             name: DEFAULT_SCHEMA_NAME.to_string(),
             database_id: 0,
+            owner: DEFAULT_SUPER_USER_ID,
         });
         let mut map: HashMap<u32, DatabaseId> = HashMap::new();
         map.insert(0_u32, 0_u32);
@@ -223,6 +342,7 @@ impl MockCatalogWriter {
             id: AtomicU32::new(0),
             table_id_to_schema_id: Default::default(),
             schema_id_to_database_id: RwLock::new(map),
+            sources: Default::default(),
         }
     }
 
@@ -263,9 +383,24 @@ impl MockCatalogWriter {
         source.id = self.gen_id();
         self.catalog.write().create_source(source.clone());
         self.add_table_or_source_id(source.id, source.schema_id, source.database_id);
+        self.sources.write().insert(source.id, source.clone());
         Ok(source.id)
     }
 
+    fn get_associated_table(&self, source: &ProstSource) -> Option<ProstTable> {
+        let catalog = self.catalog.read();
+        let database = catalog
+            .iter_databases()
+            .find(|database| database.id() == source.database_id)?;
+        let schema = database
+            .iter_schemas()
+            .find(|schema| schema.id() == source.schema_id)?;
+        schema
+            .get_table_by_name(&source.name)
+            .filter(|table| table.associated_source_id() == Some(TableId::new(source.id)))
+            .map(|table| table.to_prost(source.schema_id, source.database_id))
+    }
+
     fn get_database_id_by_schema(&self, schema_id: u32) -> DatabaseId {
         *self
             .schema_id_to_database_id
@@ -275,6 +410,159 @@ impl MockCatalogWriter {
     }
 }
 
+pub struct MockUserInfoWriter {
+    id: AtomicU32,
+    user_info: Arc<RwLock<UserInfoManager>>,
+}
+
+#[async_trait::async_trait]
+impl UserInfoWriter for MockUserInfoWriter {
+    async fn create_user(&self, mut user: UserInfo) -> Result<()> {
+        user.id = self.gen_id();
+        self.user_info.write().create_user(user);
+        Ok(())
+    }
+
+    async fn drop_user(&self, id: UserId) -> Result<()> {
+        self.user_info.write().drop_user(id);
+        Ok(())
+    }
+
+    async fn update_user(
+        &self,
+        update_user: UserInfo,
+        update_fields: Vec<UpdateField>,
+    ) -> Result<()> {
+        let mut user_info = self.user_info.write();
+        let mut user = user_info
+            .get_user_name_by_id(update_user.id)
+            .and_then(|name| user_info.get_user_by_name(&name).cloned())
+            .unwrap();
+        for field in update_fields {
+            match field {
+                UpdateField::Unknown => {}
+                UpdateField::Super => user.is_supper = update_user.is_supper,
+                UpdateField::Login => user.can_login = update_user.can_login,
+                UpdateField::CreateDb => user.can_create_db = update_user.can_create_db,
+                UpdateField::AuthInfo => user.auth_info = update_user.auth_info.clone(),
+                UpdateField::Rename => user.name = update_user.name.clone(),
+            }
+        }
+        user_info.update_user(user);
+        Ok(())
+    }
+
+    async fn grant_privilege(
+        &self,
+        users: Vec<UserId>,
+        privileges: Vec<GrantPrivilege>,
+    ) -> Result<()> {
+        self.update_privileges(users, |user_privileges| {
+            for privilege in &privileges {
+                match user_privileges.iter_mut().find(|p| {
+                    p.target == privilege.target
+                        && p.with_grant_option == privilege.with_grant_option
+                }) {
+                    Some(p) => {
+                        for action in &privilege.privileges {
+                            if !p.privileges.contains(action) {
+                                p.privileges.push(*action);
+                            }
+                        }
+                    }
+                    None => user_privileges.push(privilege.clone()),
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn revoke_privilege(
+        &self,
+        users: Vec<UserId>,
+        privileges: Vec<GrantPrivilege>,
+        revoke_grant_option: bool,
+    ) -> Result<()> {
+        self.update_privileges(users, |user_privileges| {
+            for privilege in &privileges {
+                let mut revoked = vec![];
+                for p in user_privileges.iter_mut().filter(|p| {
+                    p.target == privilege.target && (!revoke_grant_option || p.with_grant_option)
+                }) {
+                    p.privileges.retain(|action| {
+                        if privilege.privileges.contains(action) {
+                            revoked.push(*action);
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                }
+                // Revoking the grant option keeps the revoked actions, only without grant option.
+                if revoke_grant_option && !revoked.is_empty() {
+                    match user_privileges
+                        .iter_mut()
+                        .find(|p| p.target == privilege.target && !p.with_grant_option)
+                    {
+                        Some(p) => {
+                            for action in revoked {
+                                if !p.privileges.contains(&action) {
+                                    p.privileges.push(action);
+                                }
+                            }
+                        }
+                        None => user_privileges.push(GrantPrivilege {
+                            privileges: revoked,
+                            with_grant_option: false,
+                            ..privilege.clone()
+                        }),
+                    }
+                }
+            }
+            user_privileges.retain(|p| !p.privileges.is_empty());
+        });
+        Ok(())
+    }
+}
+
+impl MockUserInfoWriter {
+    pub fn new(user_info: Arc<RwLock<UserInfoManager>>) -> Self {
+        for (id, name) in [
+            (DEFAULT_SUPER_USER_ID, DEFAULT_SUPER_USER),
+            (DEFAULT_SUPER_USER_FOR_PG_ID, DEFAULT_SUPER_USER_FOR_PG),
+        ] {
+            user_info.write().create_user(UserInfo {
+                id,
+                name: name.to_string(),
+                is_supper: true,
+                can_create_db: true,
+                can_login: true,
+                ..Default::default()
+            });
+        }
+        Self {
+            user_info,
+            id: AtomicU32::new(NON_RESERVED_USER_ID as _),
+        }
+    }
+
+    fn gen_id(&self) -> u32 {
+        self.id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    fn update_privileges(&self, users: Vec<UserId>, f: impl Fn(&mut Vec<GrantPrivilege>)) {
+        let mut user_info = self.user_info.write();
+        for id in users {
+            let mut user = user_info
+                .get_user_name_by_id(id)
+                .and_then(|name| user_info.get_user_by_name(&name).cloned())
+                .unwrap();
+            f(&mut user.privileges);
+            user_info.update_user(user);
+        }
+    }
+}
+
 pub struct MockFrontendMetaClient {}
 
 #[async_trait::async_trait]
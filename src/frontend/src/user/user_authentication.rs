@@ -0,0 +1,86 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::user::auth_info::EncryptionType;
+use risingwave_pb::user::AuthInfo;
+
+/// The prefix of a password encrypted with MD5, like PostgreSQL does.
+const MD5_ENCRYPTED_PREFIX: &str = "md5";
+/// The length of the hex string of a MD5 digest.
+const MD5_HEX_LENGTH: usize = 32;
+
+/// Build `AuthInfo` for `password` of user `name`, which is stored as
+/// `md5(concat(password, name))`, the same as PostgreSQL. A password given in this encrypted
+/// form, with the `md5` prefix, is stored as is. Returns `None` for an empty password.
+pub fn encrypted_password(name: &str, password: &str) -> Option<AuthInfo> {
+    if password.is_empty() {
+        return None;
+    }
+    let encrypted_value = match password.strip_prefix(MD5_ENCRYPTED_PREFIX) {
+        Some(hash) if is_md5_hex(hash) => hash.as_bytes().to_vec(),
+        _ => md5_hex(format!("{}{}", password, name).as_bytes()).into_bytes(),
+    };
+    Some(AuthInfo {
+        encryption_type: EncryptionType::Md5 as i32,
+        encrypted_value,
+    })
+}
+
+/// Returns the response expected from a client to the `AuthenticationMD5Password` request with
+/// `salt`, which is `concat('md5', md5(concat(md5(concat(password, name)), salt)))`.
+pub fn md5_hash_with_salt(encrypted_value: &[u8], salt: &[u8; 4]) -> Vec<u8> {
+    let mut bytes = encrypted_value.to_vec();
+    bytes.extend_from_slice(salt);
+    format!("{}{}", MD5_ENCRYPTED_PREFIX, md5_hex(&bytes)).into_bytes()
+}
+
+fn md5_hex(bytes: &[u8]) -> String {
+    format!("{:x}", md5::compute(bytes))
+}
+
+fn is_md5_hex(s: &str) -> bool {
+    s.len() == MD5_HEX_LENGTH && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_password() {
+        let user_name = "user";
+        let password = "password";
+        // Computed by `SELECT md5('passworduser')` in PostgreSQL.
+        let expected = "4d45974e13472b5a0be3533de4666414";
+
+        let auth_info = encrypted_password(user_name, password).unwrap();
+        assert_eq!(
+            auth_info.get_encryption_type().unwrap(),
+            EncryptionType::Md5
+        );
+        assert_eq!(auth_info.encrypted_value, expected.as_bytes());
+
+        // An encrypted password is kept as is.
+        let encrypted = format!("md5{}", expected);
+        let auth_info = encrypted_password("another", &encrypted).unwrap();
+        assert_eq!(auth_info.encrypted_value, expected.as_bytes());
+
+        assert!(encrypted_password(user_name, "").is_none());
+
+        assert_eq!(
+            md5_hash_with_salt(expected.as_bytes(), &[1, 2, 3, 4]),
+            b"md5a3576f1ae039b8996bc4fc2720f9c71a"
+        );
+    }
+}
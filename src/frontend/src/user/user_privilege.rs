@@ -0,0 +1,162 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::user::grant_privilege::{GrantSource, GrantTable, Privilege, Target};
+use risingwave_pb::user::UserInfo;
+
+use crate::catalog::schema_catalog::SchemaCatalog;
+use crate::catalog::DatabaseId;
+use crate::user::UserId;
+
+/// Returns the privileges that can be granted on `object`, which are the ones granted by
+/// `ALL PRIVILEGES`.
+pub fn available_privileges(object: &Target) -> &'static [Privilege] {
+    match object {
+        Target::GrantDatabase(_) => &[Privilege::Connect, Privilege::Create],
+        Target::GrantSchema(_) => &[Privilege::Create],
+        Target::GrantTable(_) | Target::GrantAllTables(_) => &[
+            Privilege::Select,
+            Privilege::Insert,
+            Privilege::Update,
+            Privilege::Delete,
+        ],
+        Target::GrantSource(_) => &[Privilege::Select],
+    }
+}
+
+/// Returns whether `user` can perform `action` on `object` owned by `owner`. A super user and the
+/// owner of the object have all privileges on it.
+pub fn has_privilege(user: &UserInfo, owner: UserId, object: &Target, action: Privilege) -> bool {
+    user.is_supper || user.id == owner || has_granted_privilege(user, object, action, false)
+}
+
+/// Returns whether `action` on `object` has been granted to `user`, with grant option if
+/// `with_grant_option` is set.
+pub fn has_granted_privilege(
+    user: &UserInfo,
+    object: &Target,
+    action: Privilege,
+    with_grant_option: bool,
+) -> bool {
+    user.privileges.iter().any(|privilege| {
+        (privilege.with_grant_option || !with_grant_option)
+            && privilege
+                .target
+                .as_ref()
+                .map_or(false, |target| target_contains(target, object))
+            && privilege
+                .privileges
+                .iter()
+                .any(|a| *a == action as i32 || *a == Privilege::All as i32)
+    })
+}
+
+/// Whether the privileges granted on `granted` also apply to `object`.
+fn target_contains(granted: &Target, object: &Target) -> bool {
+    match (granted, object) {
+        (Target::GrantAllTables(all), Target::GrantTable(table)) => {
+            all.database_id == table.database_id && all.schema_id == table.schema_id
+        }
+        (Target::GrantAllTables(all), Target::GrantSource(source)) => {
+            all.database_id == source.database_id && all.schema_id == source.schema_id
+        }
+        _ => granted == object,
+    }
+}
+
+/// Returns the object to check privileges on for the relation `name` in `schema`, and its owner.
+/// Tables, materialized views and materialized sources are identified by the id of the table
+/// catalog, while the other sources are identified by the id of the source catalog.
+pub fn relation_object(
+    database_id: DatabaseId,
+    schema: &SchemaCatalog,
+    name: &str,
+) -> Option<(Target, UserId)> {
+    if let Some(table) = schema.get_table_by_name(name) {
+        let object = Target::GrantTable(GrantTable {
+            database_id,
+            schema_id: schema.id(),
+            table_id: table.id().table_id(),
+        });
+        Some((object, table.owner))
+    } else {
+        schema.get_source_by_name(name).map(|source| {
+            let object = Target::GrantSource(GrantSource {
+                database_id,
+                schema_id: schema.id(),
+                source_id: source.id,
+            });
+            (object, source.owner)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::user::grant_privilege::GrantAllTables;
+    use risingwave_pb::user::GrantPrivilege;
+
+    use super::*;
+
+    fn make_table(table_id: u32) -> Target {
+        Target::GrantTable(GrantTable {
+            database_id: 1,
+            schema_id: 1,
+            table_id,
+        })
+    }
+
+    #[test]
+    fn test_has_privilege() {
+        let mut user = UserInfo {
+            id: 10,
+            privileges: vec![
+                GrantPrivilege {
+                    target: Some(make_table(1)),
+                    privileges: vec![Privilege::Select as i32],
+                    with_grant_option: true,
+                },
+                GrantPrivilege {
+                    target: Some(Target::GrantAllTables(GrantAllTables {
+                        database_id: 1,
+                        schema_id: 1,
+                    })),
+                    privileges: vec![Privilege::Insert as i32],
+                    with_grant_option: false,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert!(has_privilege(&user, 0, &make_table(1), Privilege::Select));
+        assert!(!has_privilege(&user, 0, &make_table(2), Privilege::Select));
+        assert!(has_privilege(&user, 0, &make_table(2), Privilege::Insert));
+        assert!(has_privilege(&user, 10, &make_table(2), Privilege::Delete));
+        assert!(has_granted_privilege(
+            &user,
+            &make_table(1),
+            Privilege::Select,
+            true
+        ));
+        assert!(!has_granted_privilege(
+            &user,
+            &make_table(2),
+            Privilege::Insert,
+            true
+        ));
+
+        user.is_supper = true;
+        assert!(has_privilege(&user, 0, &make_table(2), Privilege::Delete));
+    }
+}
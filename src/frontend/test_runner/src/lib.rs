@@ -308,10 +308,7 @@ impl TestCase {
         let mut ret = TestCaseResult::default();
 
         let bound = {
-            let mut binder = Binder::new(
-                session.env().catalog_reader().read_guard(),
-                session.database().to_string(),
-            );
+            let mut binder = Binder::new(&session);
             match binder.bind(stmt.clone()) {
                 Ok(bound) => bound,
                 Err(err) => {
@@ -336,10 +333,17 @@ impl TestCase {
             }
         };
 
-        // Only generate optimized_logical_plan if it is specified in test case
-        if self.optimized_logical_plan.is_some() {
-            ret.optimized_logical_plan =
-                Some(explain_plan(&logical_plan.gen_optimized_logical_plan()));
+        // Only generate optimized_logical_plan if it or optimizer_error is specified in test case
+        if self.optimized_logical_plan.is_some() || self.optimizer_error.is_some() {
+            match logical_plan.gen_optimized_logical_plan() {
+                Ok(optimized_logical_plan) => {
+                    ret.optimized_logical_plan = Some(explain_plan(&optimized_logical_plan));
+                }
+                Err(err) => {
+                    ret.optimizer_error = Some(err.to_string());
+                    return Ok(ret);
+                }
+            }
         }
 
         if self.batch_plan.is_some() || self.batch_plan_proto.is_some() {
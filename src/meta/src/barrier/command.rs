@@ -18,15 +18,18 @@ use futures::future::try_join_all;
 use risingwave_common::catalog::TableId;
 use risingwave_common::error::{Result, RwError, ToRwResult};
 use risingwave_common::util::epoch::Epoch;
-use risingwave_pb::common::ActorInfo;
+use risingwave_pb::common::{ActorInfo, ParallelUnitMapping};
 use risingwave_pb::data::barrier::Mutation;
-use risingwave_pb::data::{AddMutation, DispatcherMutation, NothingMutation, StopMutation};
+use risingwave_pb::data::{
+    AddMutation, DispatcherMutation, NothingMutation, RescheduleMutation, StopMutation,
+};
 use risingwave_pb::stream_service::DropActorsRequest;
 use risingwave_rpc_client::StreamClientPoolRef;
 use uuid::Uuid;
 
 use super::info::BarrierActorInfo;
-use crate::model::{ActorId, DispatcherId, TableFragments};
+use crate::cluster::WorkerId;
+use crate::model::{ActorId, DispatcherId, FragmentId, TableFragments};
 use crate::storage::MetaStore;
 use crate::stream::FragmentManagerRef;
 
@@ -61,6 +64,24 @@ pub enum Command {
         table_sink_map: HashMap<TableId, Vec<ActorId>>,
         dispatches: HashMap<(ActorId, DispatcherId), Vec<ActorInfo>>,
     },
+
+    /// `Reschedule` command generates a `Reschedule` barrier to add or remove actors of a
+    /// fragment, and update the dispatchers and merges around it.
+    ///
+    /// Barriers from the new actors, which are marked as `Inactive` at first, WILL be collected,
+    /// since the new actors are built and started by this barrier. Barriers from the removed
+    /// actors will also be collected.
+    /// After the barrier is collected, it notifies the compute nodes to drop the removed actors,
+    /// and then updates the table fragments info in meta store.
+    Reschedule {
+        table_id: TableId,
+        fragment_id: FragmentId,
+        /// The removed actors grouped by node id.
+        removed_actors: HashMap<WorkerId, Vec<ActorId>>,
+        /// The new vnode mapping of the fragment.
+        vnode_mapping: ParallelUnitMapping,
+        mutation: RescheduleMutation,
+    },
 }
 
 impl Command {
@@ -68,11 +89,14 @@ impl Command {
         Self::Plain(Mutation::Nothing(NothingMutation {}))
     }
 
+    /// Returns the table whose `Inactive` actors should also be collected.
     pub fn creating_table_id(&self) -> Option<TableId> {
         match self {
             Command::CreateMaterializedView {
                 table_fragments, ..
             } => Some(table_fragments.table_id()),
+            // The new actors of the rescheduled fragment are the only inactive ones of the table.
+            Command::Reschedule { table_id, .. } => Some(*table_id),
             _ => None,
         }
     }
@@ -142,6 +166,8 @@ where
                     .collect();
                 Mutation::Add(AddMutation { mutations })
             }
+
+            Command::Reschedule { mutation, .. } => Mutation::Reschedule(mutation.clone()),
         };
 
         Ok(mutation)
@@ -219,6 +245,43 @@ where
                     )
                     .await?;
             }
+
+            Command::Reschedule {
+                table_id,
+                fragment_id,
+                removed_actors,
+                vnode_mapping,
+                mutation,
+            } => {
+                // Tell compute nodes to drop the removed actors, which have been stopped by the
+                // barrier.
+                let futures = removed_actors.iter().map(|(node_id, actors)| {
+                    let node = self.info.node_map.get(node_id).unwrap();
+                    let request_id = Uuid::new_v4().to_string();
+
+                    async move {
+                        let mut client = self.client_pool.get(node).await?;
+                        let request = DropActorsRequest {
+                            request_id,
+                            actor_ids: actors.to_owned(),
+                        };
+                        client.drop_actors(request).await.to_rw_result()?;
+
+                        Ok::<_, RwError>(())
+                    }
+                });
+
+                try_join_all(futures).await?;
+
+                self.fragment_manager
+                    .finish_reschedule_fragment(
+                        table_id,
+                        *fragment_id,
+                        vnode_mapping.clone(),
+                        mutation,
+                    )
+                    .await?;
+            }
         }
 
         Ok(())
@@ -18,12 +18,14 @@ use std::option::Option::Some;
 use std::sync::Arc;
 
 use anyhow::anyhow;
-use risingwave_common::catalog::{CatalogVersion, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+use risingwave_common::catalog::{
+    CatalogVersion, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, DEFAULT_SUPER_USER_ID,
+};
 use risingwave_common::ensure;
 use risingwave_common::error::ErrorCode::{CatalogError, InternalError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
-use risingwave_pb::catalog::{Database, Schema, Source, Table};
+use risingwave_pb::catalog::{Database, Schema, Sink, Source, Table};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use tokio::sync::{Mutex, MutexGuard};
 
@@ -36,9 +38,16 @@ pub type DatabaseId = u32;
 pub type SchemaId = u32;
 pub type TableId = u32;
 pub type SourceId = u32;
+pub type SinkId = u32;
 pub type RelationId = u32;
 
-pub type Catalog = (Vec<Database>, Vec<Schema>, Vec<Table>, Vec<Source>);
+pub type Catalog = (
+    Vec<Database>,
+    Vec<Schema>,
+    Vec<Table>,
+    Vec<Source>,
+    Vec<Sink>,
+);
 
 pub struct CatalogManager<S: MetaStore> {
     env: MetaSrvEnv<S>,
@@ -62,8 +71,10 @@ where
 
     // Create default database and schema.
     async fn init(&self) -> Result<()> {
+        self.migrate_owner().await?;
         let mut database = Database {
             name: DEFAULT_DATABASE_NAME.to_string(),
+            owner: DEFAULT_SUPER_USER_ID,
             ..Default::default()
         };
         if !self.core.lock().await.has_database(&database) {
@@ -84,6 +95,7 @@ where
         let mut schema = Schema {
             name: DEFAULT_SCHEMA_NAME.to_string(),
             database_id: databases[0].id,
+            owner: DEFAULT_SUPER_USER_ID,
             ..Default::default()
         };
         if !self.core.lock().await.has_schema(&schema) {
@@ -97,6 +109,26 @@ where
         Ok(())
     }
 
+    /// Catalogs created before ownership was recorded have owner 0, which no user has. They are
+    /// assigned to the default super user.
+    async fn migrate_owner(&self) -> Result<()> {
+        let meta_store = self.env.meta_store();
+        macro_rules! migrate {
+            ($($catalog:ident),*) => {
+                $(
+                    for mut catalog in $catalog::list(meta_store).await? {
+                        if catalog.owner == 0 {
+                            catalog.owner = DEFAULT_SUPER_USER_ID;
+                            catalog.insert(meta_store).await?;
+                        }
+                    }
+                )*
+            };
+        }
+        migrate!(Database, Schema, Table, Source, Sink);
+        Ok(())
+    }
+
     /// Used in `NotificationService::subscribe`.
     /// Need to pay attention to the order of acquiring locks to prevent deadlock problems.
     pub async fn get_catalog_core_guard(&self) -> MutexGuard<'_, CatalogManagerCore<S>> {
@@ -406,6 +438,168 @@ where
         }
     }
 
+    /// Starts altering the columns of a source, and returns the source together with its
+    /// associated table if it's a table source. The alter is rejected if the `version` is not the
+    /// current version of the source, which means the change is made on a stale catalog.
+    pub async fn start_alter_source_procedure(
+        &self,
+        source_id: SourceId,
+        version: u64,
+    ) -> Result<(Source, Option<Table>)> {
+        let mut core = self.core.lock().await;
+        let source = Source::select(self.env.meta_store(), &source_id)
+            .await?
+            .ok_or_else(|| {
+                RwError::from(InternalError(format!(
+                    "source doesn't exist: id={}",
+                    source_id
+                )))
+            })?;
+        if source.version != version || core.has_in_progress_alter(source_id) {
+            return Err(CatalogError(
+                anyhow!(
+                    "Fail to alter source `{}` because it has been altered concurrently.",
+                    source.name
+                )
+                .into(),
+            )
+            .into());
+        }
+
+        let table = Table::list(self.env.meta_store())
+            .await?
+            .into_iter()
+            .find(|table| {
+                table.optional_associated_source_id
+                    == Some(OptionalAssociatedSourceId::AssociatedSourceId(source_id))
+            });
+        core.mark_altering(source_id);
+
+        Ok((source, table))
+    }
+
+    /// Finishes altering a source by storing the altered source and table with their versions
+    /// bumped.
+    pub async fn finish_alter_source_procedure(
+        &self,
+        mut source: Source,
+        mut table: Option<Table>,
+    ) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        if !core.has_in_progress_alter(source.id) {
+            return Err(RwError::from(InternalError(
+                "source not in altering procedure".to_string(),
+            )));
+        }
+        core.unmark_altering(source.id);
+
+        source.version += 1;
+        let mut transaction = Transaction::default();
+        source.upsert_in_transaction(&mut transaction)?;
+        if let Some(table) = &mut table {
+            table.version += 1;
+            table.upsert_in_transaction(&mut transaction)?;
+        }
+        core.env.meta_store().txn(transaction).await?;
+
+        if let Some(table) = table {
+            self.env
+                .notification_manager()
+                .notify_frontend(Operation::Update, Info::Table(table))
+                .await;
+        }
+        let version = self
+            .env
+            .notification_manager()
+            .notify_frontend(Operation::Update, Info::Source(source))
+            .await;
+        Ok(version)
+    }
+
+    pub async fn cancel_alter_source_procedure(&self, source_id: SourceId) -> Result<()> {
+        let mut core = self.core.lock().await;
+        if core.has_in_progress_alter(source_id) {
+            core.unmark_altering(source_id);
+            Ok(())
+        } else {
+            Err(RwError::from(InternalError(
+                "source not in altering procedure".to_string(),
+            )))
+        }
+    }
+
+    pub async fn start_create_sink_procedure(&self, sink: &Sink) -> Result<()> {
+        let mut core = self.core.lock().await;
+        let key = (sink.database_id, sink.schema_id, sink.name.clone());
+        if !core.has_sink(sink) && !core.has_in_progress_creation(&key) {
+            core.mark_creating(&key);
+            core.increase_ref_count(sink.associated_table_id);
+            Ok(())
+        } else {
+            Err(RwError::from(InternalError(
+                "sink already exists or in creating procedure".to_string(),
+            )))
+        }
+    }
+
+    pub async fn finish_create_sink_procedure(&self, sink: &Sink) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        let key = (sink.database_id, sink.schema_id, sink.name.clone());
+        if !core.has_sink(sink) && core.has_in_progress_creation(&key) {
+            core.unmark_creating(&key);
+            sink.insert(self.env.meta_store()).await?;
+            core.add_sink(sink);
+
+            let version = self
+                .env
+                .notification_manager()
+                .notify_frontend(Operation::Add, Info::Sink(sink.to_owned()))
+                .await;
+
+            Ok(version)
+        } else {
+            Err(RwError::from(InternalError(
+                "sink already exist or not in creating procedure".to_string(),
+            )))
+        }
+    }
+
+    pub async fn cancel_create_sink_procedure(&self, sink: &Sink) -> Result<()> {
+        let mut core = self.core.lock().await;
+        let key = (sink.database_id, sink.schema_id, sink.name.clone());
+        if !core.has_sink(sink) && core.has_in_progress_creation(&key) {
+            core.unmark_creating(&key);
+            core.decrease_ref_count(sink.associated_table_id);
+            Ok(())
+        } else {
+            Err(RwError::from(InternalError(
+                "sink already exist or not in creating procedure".to_string(),
+            )))
+        }
+    }
+
+    pub async fn drop_sink(&self, sink_id: SinkId) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        let sink = Sink::select(self.env.meta_store(), &sink_id).await?;
+        if let Some(sink) = sink {
+            Sink::delete(self.env.meta_store(), &sink_id).await?;
+            core.drop_sink(&sink);
+            core.decrease_ref_count(sink.associated_table_id);
+
+            let version = self
+                .env
+                .notification_manager()
+                .notify_frontend(Operation::Delete, Info::Sink(sink))
+                .await;
+
+            Ok(version)
+        } else {
+            Err(RwError::from(InternalError(
+                "sink doesn't exist".to_string(),
+            )))
+        }
+    }
+
     pub async fn start_create_materialized_source_procedure(
         &self,
         source: &Source,
@@ -576,6 +770,7 @@ type DatabaseKey = String;
 type SchemaKey = (DatabaseId, String);
 type TableKey = (DatabaseId, SchemaId, String);
 type SourceKey = (DatabaseId, SchemaId, String);
+type SinkKey = (DatabaseId, SchemaId, String);
 type RelationKey = (DatabaseId, SchemaId, String);
 
 /// [`CatalogManagerCore`] caches meta catalog information and maintains dependent relationship
@@ -590,11 +785,15 @@ pub struct CatalogManagerCore<S: MetaStore> {
     sources: HashSet<SourceKey>,
     /// Cached table key information.
     tables: HashSet<TableKey>,
+    /// Cached sink key information.
+    sinks: HashSet<SinkKey>,
     /// Relation refer count mapping.
     relation_ref_count: HashMap<RelationId, usize>,
 
     // In-progress creation tracker
     in_progress_creation_tracker: HashSet<RelationKey>,
+    // In-progress alter tracker
+    in_progress_alter_tracker: HashSet<SourceId>,
 }
 
 impl<S> CatalogManagerCore<S>
@@ -606,6 +805,7 @@ where
         let schemas = Schema::list(env.meta_store()).await?;
         let sources = Source::list(env.meta_store()).await?;
         let tables = Table::list(env.meta_store()).await?;
+        let sinks = Sink::list(env.meta_store()).await?;
 
         let mut relation_ref_count = HashMap::new();
 
@@ -626,8 +826,15 @@ where
             }
             (table.database_id, table.schema_id, table.name)
         }));
+        let sinks = HashSet::from_iter(sinks.into_iter().map(|sink| {
+            *relation_ref_count
+                .entry(sink.associated_table_id)
+                .or_insert(0) += 1;
+            (sink.database_id, sink.schema_id, sink.name)
+        }));
 
         let in_progress_creation_tracker = HashSet::new();
+        let in_progress_alter_tracker = HashSet::new();
 
         Ok(Self {
             env,
@@ -635,8 +842,10 @@ where
             schemas,
             sources,
             tables,
+            sinks,
             relation_ref_count,
             in_progress_creation_tracker,
+            in_progress_alter_tracker,
         })
     }
 
@@ -646,6 +855,7 @@ where
             Schema::list(self.env.meta_store()).await?,
             Table::list(self.env.meta_store()).await?,
             Source::list(self.env.meta_store()).await?,
+            Sink::list(self.env.meta_store()).await?,
         ))
     }
 
@@ -710,6 +920,21 @@ where
             .remove(&(source.database_id, source.schema_id, source.name.clone()))
     }
 
+    fn has_sink(&self, sink: &Sink) -> bool {
+        self.sinks
+            .contains(&(sink.database_id, sink.schema_id, sink.name.clone()))
+    }
+
+    fn add_sink(&mut self, sink: &Sink) {
+        self.sinks
+            .insert((sink.database_id, sink.schema_id, sink.name.clone()));
+    }
+
+    fn drop_sink(&mut self, sink: &Sink) -> bool {
+        self.sinks
+            .remove(&(sink.database_id, sink.schema_id, sink.name.clone()))
+    }
+
     pub async fn get_source(&self, id: SourceId) -> Result<Option<Source>> {
         Source::select(self.env.meta_store(), &id).await
     }
@@ -746,4 +971,16 @@ where
     fn unmark_creating(&mut self, relation: &RelationKey) {
         self.in_progress_creation_tracker.remove(&relation.clone());
     }
+
+    fn has_in_progress_alter(&self, source_id: SourceId) -> bool {
+        self.in_progress_alter_tracker.contains(&source_id)
+    }
+
+    fn mark_altering(&mut self, source_id: SourceId) {
+        self.in_progress_alter_tracker.insert(source_id);
+    }
+
+    fn unmark_altering(&mut self, source_id: SourceId) {
+        self.in_progress_alter_tracker.remove(&source_id);
+    }
 }
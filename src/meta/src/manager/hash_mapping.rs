@@ -63,6 +63,23 @@ impl HashMappingManager {
         core.set_fragment_hash_mapping(fragment_id, hash_mapping);
     }
 
+    /// Compute a load-balanced vnode mapping of the fragment after the `added_parallel_units` are
+    /// added and the `removed_parallel_units` are removed, moving as few vnodes as possible. The
+    /// new mapping is not applied until [`Self::set_fragment_hash_mapping`] is called.
+    pub fn rebalance_fragment_hash_mapping(
+        &self,
+        fragment_id: FragmentId,
+        added_parallel_units: &[ParallelUnit],
+        removed_parallel_units: &[ParallelUnitId],
+    ) -> Option<Vec<ParallelUnitId>> {
+        let core = self.core.lock();
+        core.rebalance_fragment_hash_mapping(
+            fragment_id,
+            added_parallel_units,
+            removed_parallel_units,
+        )
+    }
+
     pub fn set_fragment_state_table(&self, fragment_id: FragmentId, state_table_id: TableId) {
         let mut core = self.core.lock();
         core.state_table_fragment_mapping
@@ -110,6 +127,58 @@ struct HashMappingInfo {
     load_balancer: BTreeMap<usize, Vec<ParallelUnitId>>,
 }
 
+impl HashMappingInfo {
+    fn add_to_balancer(&mut self, parallel_unit_id: ParallelUnitId) {
+        let vnode_count = self.owner_mapping[&parallel_unit_id].len();
+        self.load_balancer
+            .entry(vnode_count)
+            .or_default()
+            .push(parallel_unit_id);
+    }
+
+    fn remove_from_balancer(&mut self, parallel_unit_id: ParallelUnitId) {
+        let vnode_count = self.owner_mapping[&parallel_unit_id].len();
+        let parallel_units = self.load_balancer.get_mut(&vnode_count).unwrap();
+        parallel_units.retain(|id| *id != parallel_unit_id);
+        if parallel_units.is_empty() {
+            self.load_balancer.remove(&vnode_count);
+        }
+    }
+
+    fn least_loaded(&self) -> (usize, ParallelUnitId) {
+        let (vnode_count, parallel_units) = self.load_balancer.iter().next().unwrap();
+        (*vnode_count, parallel_units[0])
+    }
+
+    fn most_loaded(&self) -> (usize, ParallelUnitId) {
+        let (vnode_count, parallel_units) = self.load_balancer.iter().next_back().unwrap();
+        (*vnode_count, parallel_units[0])
+    }
+
+    /// Assign the `vnode` to the parallel unit, keeping the load balancer updated.
+    fn push_vnode(&mut self, parallel_unit_id: ParallelUnitId, vnode: VirtualNode) {
+        self.remove_from_balancer(parallel_unit_id);
+        self.owner_mapping
+            .get_mut(&parallel_unit_id)
+            .unwrap()
+            .push(vnode);
+        self.add_to_balancer(parallel_unit_id);
+    }
+
+    /// Take a vnode from the parallel unit, keeping the load balancer updated.
+    fn pop_vnode(&mut self, parallel_unit_id: ParallelUnitId) -> VirtualNode {
+        self.remove_from_balancer(parallel_unit_id);
+        let vnode = self
+            .owner_mapping
+            .get_mut(&parallel_unit_id)
+            .unwrap()
+            .pop()
+            .unwrap();
+        self.add_to_balancer(parallel_unit_id);
+        vnode
+    }
+}
+
 struct HashMappingManagerCore {
     /// Mapping from fragment to hash mapping information. One fragment will have exactly one vnode
     /// mapping, which describes the data distribution of the fragment.
@@ -175,6 +244,53 @@ impl HashMappingManagerCore {
         vnode_mapping
     }
 
+    fn rebalance_fragment_hash_mapping(
+        &self,
+        fragment_id: FragmentId,
+        added_parallel_units: &[ParallelUnit],
+        removed_parallel_units: &[ParallelUnitId],
+    ) -> Option<Vec<ParallelUnitId>> {
+        let mut info = self.hash_mapping_infos.get(&fragment_id)?.clone();
+
+        let mut orphan_vnodes = vec![];
+        for parallel_unit_id in removed_parallel_units {
+            if info.owner_mapping.contains_key(parallel_unit_id) {
+                info.remove_from_balancer(*parallel_unit_id);
+                orphan_vnodes.extend(info.owner_mapping.remove(parallel_unit_id).unwrap());
+            }
+        }
+        for parallel_unit in added_parallel_units {
+            info.owner_mapping.insert(parallel_unit.id, vec![]);
+            info.add_to_balancer(parallel_unit.id);
+        }
+        if info.owner_mapping.is_empty() {
+            return None;
+        }
+
+        // Vnodes of the removed parallel units go to the least loaded ones first, and then vnodes
+        // are moved one by one from the most loaded parallel unit to the least loaded one.
+        for vnode in orphan_vnodes {
+            let (_, target) = info.least_loaded();
+            info.push_vnode(target, vnode);
+        }
+        loop {
+            let (min_count, target) = info.least_loaded();
+            let (max_count, source) = info.most_loaded();
+            if max_count - min_count <= 1 {
+                break;
+            }
+            let vnode = info.pop_vnode(source);
+            info.push_vnode(target, vnode);
+        }
+
+        for (parallel_unit_id, vnodes) in &info.owner_mapping {
+            for vnode in vnodes {
+                info.vnode_mapping[*vnode as usize] = *parallel_unit_id;
+            }
+        }
+        Some(info.vnode_mapping)
+    }
+
     fn set_fragment_hash_mapping(
         &mut self,
         fragment_id: FragmentId,
@@ -287,6 +403,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rebalance_hash_mapping() {
+        let parallel_units = (1..=4)
+            .map(|id| ParallelUnit {
+                id,
+                r#type: ParallelUnitType::Hash as i32,
+                worker_node_id: 1,
+            })
+            .collect_vec();
+        let hash_mapping_manager = HashMappingManager::new();
+        let fragment_id = 1u32;
+        let old_vnode_mapping =
+            hash_mapping_manager.build_fragment_hash_mapping(fragment_id, &parallel_units[..3]);
+
+        // Replace parallel unit 3 with 4.
+        let vnode_mapping = hash_mapping_manager
+            .rebalance_fragment_hash_mapping(fragment_id, &parallel_units[3..], &[3])
+            .unwrap();
+        assert_eq!(
+            vnode_mapping.iter().filter(|id| **id == 4).count(),
+            old_vnode_mapping.iter().filter(|id| **id == 3).count()
+        );
+        assert!(vnode_mapping.iter().all(|id| *id != 3));
+        // The vnodes of the remaining parallel units are not moved.
+        for (old, new) in old_vnode_mapping.iter().zip_eq(&vnode_mapping) {
+            if *old != 3 {
+                assert_eq!(old, new);
+            }
+        }
+        // The new mapping is not applied until it's set.
+        assert_eq!(
+            hash_mapping_manager
+                .get_fragment_hash_mapping(&fragment_id)
+                .unwrap(),
+            old_vnode_mapping
+        );
+
+        // Scale out to 4 parallel units, and then scale in to 2.
+        let vnode_mapping = hash_mapping_manager
+            .rebalance_fragment_hash_mapping(fragment_id, &parallel_units[3..], &[])
+            .unwrap();
+        let counts = (1..=4)
+            .map(|id| vnode_mapping.iter().filter(|x| **x == id).count())
+            .collect_vec();
+        assert_eq!(counts.iter().sum::<usize>(), VIRTUAL_NODE_COUNT);
+        assert!(counts.iter().max().unwrap() - counts.iter().min().unwrap() <= 1);
+
+        let vnode_mapping = hash_mapping_manager
+            .rebalance_fragment_hash_mapping(fragment_id, &[], &[2, 3])
+            .unwrap();
+        assert_eq!(
+            vnode_mapping.iter().filter(|id| **id == 1).count(),
+            VIRTUAL_NODE_COUNT
+        );
+    }
+
     #[test]
     fn test_restore_hash_mapping() {
         let fragment_id = 3u32;
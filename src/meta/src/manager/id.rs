@@ -15,6 +15,7 @@
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::Arc;
 
+use risingwave_common::catalog::NON_RESERVED_USER_ID;
 use risingwave_common::error::Result;
 use tokio::sync::RwLock;
 
@@ -132,6 +133,7 @@ pub mod IdCategory {
     pub const HummockSSTableId: IdCategoryType = 8;
     pub const ParallelUnit: IdCategoryType = 9;
     pub const Source: IdCategoryType = 10;
+    pub const User: IdCategoryType = 11;
 }
 
 pub type IdGeneratorManagerRef<S> = Arc<IdGeneratorManager<S>>;
@@ -150,6 +152,7 @@ pub struct IdGeneratorManager<S> {
     hummock_snapshot: Arc<StoredIdGenerator<S>>,
     hummock_ss_table_id: Arc<StoredIdGenerator<S>>,
     parallel_unit: Arc<StoredIdGenerator<S>>,
+    user: Arc<StoredIdGenerator<S>>,
 }
 
 impl<S> IdGeneratorManager<S>
@@ -180,6 +183,10 @@ where
             parallel_unit: Arc::new(
                 StoredIdGenerator::new(meta_store.clone(), "parallel_unit", None).await,
             ),
+            user: Arc::new(
+                StoredIdGenerator::new(meta_store.clone(), "user", Some(NON_RESERVED_USER_ID))
+                    .await,
+            ),
         }
     }
 
@@ -196,6 +203,7 @@ where
             IdCategory::Worker => &self.worker,
             IdCategory::HummockSSTableId => &self.hummock_ss_table_id,
             IdCategory::ParallelUnit => &self.parallel_unit,
+            IdCategory::User => &self.user,
             _ => unreachable!(),
         }
     }
@@ -0,0 +1,485 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use risingwave_common::catalog::{
+    CatalogVersion, DEFAULT_SUPER_USER, DEFAULT_SUPER_USER_FOR_PG, DEFAULT_SUPER_USER_FOR_PG_ID,
+    DEFAULT_SUPER_USER_ID,
+};
+use risingwave_common::error::ErrorCode::{CatalogError, InternalError};
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::meta::subscribe_response::{Info, Operation};
+use risingwave_pb::user::update_user_request::UpdateField;
+use risingwave_pb::user::{GrantPrivilege, UserInfo};
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::manager::MetaSrvEnv;
+use crate::model::{MetadataModel, Transactional};
+use crate::storage::{MetaStore, Transaction};
+
+pub type UserId = u32;
+
+/// [`UserManager`] manages the user info, including authentication info and privileges, and
+/// persists it in the meta store.
+pub struct UserManager<S: MetaStore> {
+    env: MetaSrvEnv<S>,
+    core: Mutex<HashMap<UserId, UserInfo>>,
+}
+
+pub type UserManagerRef<S> = Arc<UserManager<S>>;
+
+impl<S: MetaStore> UserManager<S> {
+    pub async fn new(env: MetaSrvEnv<S>) -> Result<Self> {
+        let users = UserInfo::list(env.meta_store()).await?;
+        let user_manager = Self {
+            env,
+            core: Mutex::new(users.into_iter().map(|user| (user.id, user)).collect()),
+        };
+        user_manager.init().await?;
+        Ok(user_manager)
+    }
+
+    // Create default super users.
+    async fn init(&self) -> Result<()> {
+        for (id, name) in [
+            (DEFAULT_SUPER_USER_ID, DEFAULT_SUPER_USER),
+            (DEFAULT_SUPER_USER_FOR_PG_ID, DEFAULT_SUPER_USER_FOR_PG),
+        ] {
+            if self.get_user_by_name(name).await.is_none() {
+                let user = UserInfo {
+                    id,
+                    name: name.to_string(),
+                    is_supper: true,
+                    can_create_db: true,
+                    can_login: true,
+                    ..Default::default()
+                };
+                self.create_user(&user).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Used in `NotificationService::subscribe`.
+    /// Need to pay attention to the order of acquiring locks to prevent deadlock problems.
+    pub async fn get_user_core_guard(&self) -> MutexGuard<'_, HashMap<UserId, UserInfo>> {
+        self.core.lock().await
+    }
+
+    pub async fn list_users(&self) -> Vec<UserInfo> {
+        self.core.lock().await.values().cloned().collect()
+    }
+
+    pub async fn get_user_by_name(&self, name: &str) -> Option<UserInfo> {
+        self.core
+            .lock()
+            .await
+            .values()
+            .find(|user| user.name == name)
+            .cloned()
+    }
+
+    pub async fn create_user(&self, user: &UserInfo) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        if core.values().any(|u| u.name == user.name) {
+            return Err(RwError::from(CatalogError(
+                anyhow!("user {} already exists", user.name).into(),
+            )));
+        }
+        user.insert(self.env.meta_store()).await?;
+        core.insert(user.id, user.clone());
+
+        Ok(self
+            .env
+            .notification_manager()
+            .notify_frontend(Operation::Add, Info::User(user.to_owned()))
+            .await)
+    }
+
+    /// Updates the fields in `update_fields` of the user with the same id as `update_user`.
+    pub async fn update_user(
+        &self,
+        update_user: &UserInfo,
+        update_fields: &[UpdateField],
+    ) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        let mut user = core
+            .get(&update_user.id)
+            .cloned()
+            .ok_or_else(|| InternalError(format!("user {} not found", update_user.id)))?;
+
+        for field in update_fields {
+            match field {
+                UpdateField::Unknown => {}
+                UpdateField::Super => user.is_supper = update_user.is_supper,
+                UpdateField::Login => user.can_login = update_user.can_login,
+                UpdateField::CreateDb => user.can_create_db = update_user.can_create_db,
+                UpdateField::AuthInfo => user.auth_info = update_user.auth_info.clone(),
+                UpdateField::Rename => {
+                    if core
+                        .values()
+                        .any(|u| u.name == update_user.name && u.id != user.id)
+                    {
+                        return Err(RwError::from(CatalogError(
+                            anyhow!("user {} already exists", update_user.name).into(),
+                        )));
+                    }
+                    user.name = update_user.name.clone();
+                }
+            }
+        }
+
+        user.insert(self.env.meta_store()).await?;
+        core.insert(user.id, user.clone());
+
+        Ok(self
+            .env
+            .notification_manager()
+            .notify_frontend(Operation::Update, Info::User(user))
+            .await)
+    }
+
+    pub async fn drop_user(&self, id: UserId) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        let user = core
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| InternalError(format!("user {} not found", id)))?;
+        if id == DEFAULT_SUPER_USER_ID || id == DEFAULT_SUPER_USER_FOR_PG_ID {
+            return Err(RwError::from(InternalError(format!(
+                "cannot drop default super user {}",
+                user.name
+            ))));
+        }
+
+        UserInfo::delete(self.env.meta_store(), &id).await?;
+        core.remove(&id);
+
+        Ok(self
+            .env
+            .notification_manager()
+            .notify_frontend(Operation::Delete, Info::User(user))
+            .await)
+    }
+
+    /// Grants `new_privileges` to all users in `user_ids`.
+    pub async fn grant_privilege(
+        &self,
+        user_ids: &[UserId],
+        new_privileges: &[GrantPrivilege],
+    ) -> Result<CatalogVersion> {
+        self.update_privileges(user_ids, |privileges| {
+            for new_privilege in new_privileges {
+                merge_granted_privilege(privileges, new_privilege);
+            }
+        })
+        .await
+    }
+
+    /// Revokes `revoke_privileges` from all users in `user_ids`. Only the grant option of the
+    /// privileges is revoked if `revoke_grant_option` is set.
+    pub async fn revoke_privilege(
+        &self,
+        user_ids: &[UserId],
+        revoke_privileges: &[GrantPrivilege],
+        revoke_grant_option: bool,
+    ) -> Result<CatalogVersion> {
+        self.update_privileges(user_ids, |privileges| {
+            for revoke_privilege in revoke_privileges {
+                remove_revoked_privilege(privileges, revoke_privilege, revoke_grant_option);
+            }
+        })
+        .await
+    }
+
+    async fn update_privileges(
+        &self,
+        user_ids: &[UserId],
+        f: impl Fn(&mut Vec<GrantPrivilege>),
+    ) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        let mut users = Vec::with_capacity(user_ids.len());
+        let mut transaction = Transaction::default();
+        for user_id in user_ids {
+            let mut user = core
+                .get(user_id)
+                .cloned()
+                .ok_or_else(|| InternalError(format!("user {} not found", user_id)))?;
+            f(&mut user.privileges);
+            user.upsert_in_transaction(&mut transaction)?;
+            users.push(user);
+        }
+        self.env.meta_store().txn(transaction).await?;
+
+        let mut version = 0;
+        for user in users {
+            core.insert(user.id, user.clone());
+            version = self
+                .env
+                .notification_manager()
+                .notify_frontend(Operation::Update, Info::User(user))
+                .await;
+        }
+        Ok(version)
+    }
+}
+
+/// Merges `new_privilege` into `privileges`. The actions granted with grant option and the ones
+/// without it are kept in two separate items for the same target.
+fn merge_granted_privilege(privileges: &mut Vec<GrantPrivilege>, new_privilege: &GrantPrivilege) {
+    let with_grant_option = new_privilege.with_grant_option;
+    let mut actions = new_privilege.privileges.clone();
+    if let Some(other) = privileges
+        .iter_mut()
+        .find(|p| p.target == new_privilege.target && p.with_grant_option != with_grant_option)
+    {
+        if with_grant_option {
+            // The actions are upgraded to be granted with grant option.
+            other.privileges.retain(|action| !actions.contains(action));
+        } else {
+            // The actions already granted with grant option are kept as they are.
+            actions.retain(|action| !other.privileges.contains(action));
+        }
+    }
+
+    match privileges
+        .iter_mut()
+        .find(|p| p.target == new_privilege.target && p.with_grant_option == with_grant_option)
+    {
+        Some(privilege) => {
+            for action in actions {
+                if !privilege.privileges.contains(&action) {
+                    privilege.privileges.push(action);
+                }
+            }
+        }
+        None => privileges.push(GrantPrivilege {
+            privileges: actions,
+            ..new_privilege.clone()
+        }),
+    }
+    privileges.retain(|p| !p.privileges.is_empty());
+}
+
+/// Removes the actions in `revoke_privilege` from `privileges`. If `revoke_grant_option` is set,
+/// the actions are kept but without grant option.
+fn remove_revoked_privilege(
+    privileges: &mut Vec<GrantPrivilege>,
+    revoke_privilege: &GrantPrivilege,
+    revoke_grant_option: bool,
+) {
+    let mut revoked = vec![];
+    for privilege in privileges
+        .iter_mut()
+        .filter(|p| p.target == revoke_privilege.target)
+    {
+        if revoke_grant_option && !privilege.with_grant_option {
+            continue;
+        }
+        privilege.privileges.retain(|action| {
+            if revoke_privilege.privileges.contains(action) {
+                revoked.push(*action);
+                false
+            } else {
+                true
+            }
+        });
+    }
+    if revoke_grant_option && !revoked.is_empty() {
+        merge_granted_privilege(
+            privileges,
+            &GrantPrivilege {
+                privileges: revoked,
+                with_grant_option: false,
+                ..revoke_privilege.clone()
+            },
+        );
+    }
+    privileges.retain(|p| !p.privileges.is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::user::grant_privilege::{GrantTable, Privilege, Target};
+
+    use super::*;
+
+    fn make_test_user(id: UserId, name: &str) -> UserInfo {
+        UserInfo {
+            id,
+            name: name.to_string(),
+            can_login: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_user_manager() -> Result<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let user_manager = UserManager::new(env.clone()).await?;
+        assert!(user_manager
+            .get_user_by_name(DEFAULT_SUPER_USER)
+            .await
+            .is_some());
+
+        let user = make_test_user(100, "user1");
+        user_manager.create_user(&user).await?;
+        assert!(user_manager
+            .create_user(&make_test_user(101, "user1"))
+            .await
+            .is_err());
+
+        let mut update_user = user.clone();
+        update_user.name = "user2".to_string();
+        update_user.is_supper = true;
+        user_manager
+            .update_user(&update_user, &[UpdateField::Rename])
+            .await?;
+        let updated = user_manager.get_user_by_name("user2").await.unwrap();
+        assert!(!updated.is_supper);
+        assert_eq!(updated.id, 100);
+
+        // Users are restored from meta store.
+        let user_manager = UserManager::new(env).await?;
+        assert!(user_manager.get_user_by_name("user2").await.is_some());
+        user_manager.drop_user(100).await?;
+        assert!(user_manager.get_user_by_name("user2").await.is_none());
+        let root = user_manager
+            .get_user_by_name(DEFAULT_SUPER_USER)
+            .await
+            .unwrap();
+        assert!(user_manager.drop_user(root.id).await.is_err());
+        Ok(())
+    }
+
+    fn make_table_privilege(
+        table_id: u32,
+        actions: &[Privilege],
+        with_grant_option: bool,
+    ) -> GrantPrivilege {
+        GrantPrivilege {
+            target: Some(Target::GrantTable(GrantTable {
+                database_id: 1,
+                schema_id: 1,
+                table_id,
+            })),
+            privileges: actions.iter().map(|action| *action as i32).collect(),
+            with_grant_option,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grant_revoke_privilege() -> Result<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let user_manager = UserManager::new(env.clone()).await?;
+        user_manager
+            .create_user(&make_test_user(100, "user1"))
+            .await?;
+        user_manager
+            .create_user(&make_test_user(101, "user2"))
+            .await?;
+
+        user_manager
+            .grant_privilege(
+                &[100, 101],
+                &[make_table_privilege(
+                    1,
+                    &[Privilege::Select, Privilege::Insert],
+                    false,
+                )],
+            )
+            .await?;
+        user_manager
+            .grant_privilege(
+                &[100],
+                &[make_table_privilege(1, &[Privilege::Select], true)],
+            )
+            .await?;
+        let user = user_manager.get_user_by_name("user1").await.unwrap();
+        assert_eq!(
+            user.privileges,
+            vec![
+                make_table_privilege(1, &[Privilege::Insert], false),
+                make_table_privilege(1, &[Privilege::Select], true),
+            ]
+        );
+
+        // Granting the action without grant option again does not downgrade it.
+        user_manager
+            .grant_privilege(
+                &[100],
+                &[make_table_privilege(1, &[Privilege::Select], false)],
+            )
+            .await?;
+        assert_eq!(
+            user_manager
+                .get_user_by_name("user1")
+                .await
+                .unwrap()
+                .privileges,
+            user.privileges
+        );
+
+        user_manager
+            .revoke_privilege(
+                &[100],
+                &[make_table_privilege(1, &[Privilege::Select], false)],
+                true,
+            )
+            .await?;
+        let user = user_manager.get_user_by_name("user1").await.unwrap();
+        assert_eq!(
+            user.privileges,
+            vec![make_table_privilege(
+                1,
+                &[Privilege::Insert, Privilege::Select],
+                false
+            )]
+        );
+
+        user_manager
+            .revoke_privilege(
+                &[100, 101],
+                &[make_table_privilege(
+                    1,
+                    &[Privilege::Select, Privilege::Insert],
+                    false,
+                )],
+                false,
+            )
+            .await?;
+        for name in ["user1", "user2"] {
+            let user = user_manager.get_user_by_name(name).await.unwrap();
+            assert!(user.privileges.is_empty());
+        }
+
+        // Privileges are restored from meta store.
+        user_manager
+            .grant_privilege(
+                &[101],
+                &[make_table_privilege(2, &[Privilege::Delete], false)],
+            )
+            .await?;
+        let user_manager = UserManager::new(env).await?;
+        let user = user_manager.get_user_by_name("user2").await.unwrap();
+        assert_eq!(
+            user.privileges,
+            vec![make_table_privilege(2, &[Privilege::Delete], false)]
+        );
+        Ok(())
+    }
+}
@@ -19,6 +19,7 @@ use risingwave_common::catalog::TableId;
 use risingwave_common::error::Result;
 use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus, Fragment};
 use risingwave_pb::meta::TableFragments as ProstTableFragments;
+use risingwave_pb::plan_common::Field;
 use risingwave_pb::stream_plan::source_node::SourceType;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
 use risingwave_pb::stream_plan::{FragmentType, StreamActor, StreamNode};
@@ -94,6 +95,37 @@ impl TableFragments {
         self.actor_status = actor_status;
     }
 
+    /// Returns the status of actors.
+    pub fn actor_status(&self) -> &BTreeMap<ActorId, ActorStatus> {
+        &self.actor_status
+    }
+
+    /// Add new actors to the fragment, with their status.
+    pub fn add_actors(
+        &mut self,
+        fragment_id: FragmentId,
+        actors: Vec<StreamActor>,
+        actor_status: BTreeMap<ActorId, ActorStatus>,
+    ) {
+        self.fragments
+            .get_mut(&fragment_id)
+            .unwrap()
+            .actors
+            .extend(actors);
+        self.actor_status.extend(actor_status);
+    }
+
+    /// Remove the actors and their status from the fragment.
+    pub fn remove_actors(&mut self, fragment_id: FragmentId, actor_ids: &HashSet<ActorId>) {
+        self.fragments
+            .get_mut(&fragment_id)
+            .unwrap()
+            .actors
+            .retain(|actor| !actor_ids.contains(&actor.actor_id));
+        self.actor_status
+            .retain(|actor_id, _| !actor_ids.contains(actor_id));
+    }
+
     /// Returns the table id.
     pub fn table_id(&self) -> TableId {
         self.table_id
@@ -207,6 +239,72 @@ impl TableFragments {
         table_ids
     }
 
+    /// Resolves the columns of the relation read by the `Chain` nodes, or the `Source` nodes if the
+    /// relation is a source.
+    fn resolve_referenced_columns(
+        stream_node: &StreamNode,
+        relation_id: &TableId,
+        column_ids: &mut HashSet<i32>,
+    ) {
+        match stream_node.node_body.as_ref() {
+            Some(NodeBody::Chain(chain)) if TableId::from(&chain.table_ref_id) == *relation_id => {
+                column_ids.extend(chain.column_ids.iter().copied());
+            }
+            Some(NodeBody::Source(source))
+                if source.source_type == SourceType::Source as i32
+                    && TableId::from(&source.table_ref_id) == *relation_id =>
+            {
+                column_ids.extend(source.column_ids.iter().copied());
+            }
+            _ => {}
+        }
+
+        for child in &stream_node.input {
+            Self::resolve_referenced_columns(child, relation_id, column_ids);
+        }
+    }
+
+    /// Returns the ids of the columns of the relation that are read by these fragments.
+    pub fn referenced_column_ids(&self, relation_id: &TableId) -> HashSet<i32> {
+        let mut column_ids = HashSet::new();
+        self.fragments.values().for_each(|fragment| {
+            let actor = &fragment.actors[0];
+            Self::resolve_referenced_columns(
+                actor.nodes.as_ref().unwrap(),
+                relation_id,
+                &mut column_ids,
+            );
+        });
+
+        column_ids
+    }
+
+    /// Appends the columns added by `ALTER TABLE ADD COLUMN` to the fragments of a table. The
+    /// nodes of a table pass all the columns from the source to the materialize, so the columns
+    /// are appended to each of them.
+    pub fn add_table_columns(&mut self, fields: &[Field], column_ids: &[i32]) {
+        fn add_columns(stream_node: &mut StreamNode, fields: &[Field], column_ids: &[i32]) {
+            stream_node.fields.extend_from_slice(fields);
+            match stream_node.node_body.as_mut() {
+                Some(NodeBody::Source(source)) => source.column_ids.extend_from_slice(column_ids),
+                Some(NodeBody::Materialize(materialize)) => {
+                    materialize.column_ids.extend_from_slice(column_ids)
+                }
+                Some(NodeBody::Merge(merge)) => merge.fields.extend_from_slice(fields),
+                _ => {}
+            }
+            for input in &mut stream_node.input {
+                add_columns(input, fields, column_ids);
+            }
+        }
+
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                add_columns(actor.nodes.as_mut().unwrap(), fields, column_ids);
+            }
+        }
+    }
+
     /// Returns states of actors group by node id.
     pub fn node_actor_states(&self) -> BTreeMap<WorkerId, Vec<(ActorId, ActorState)>> {
         let mut map = BTreeMap::default();
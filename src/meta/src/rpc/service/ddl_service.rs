@@ -14,20 +14,24 @@
 
 use std::collections::HashSet;
 
+use anyhow::anyhow;
 use risingwave_common::catalog::CatalogVersion;
-use risingwave_common::error::{tonic_err, Result as RwResult};
+use risingwave_common::error::ErrorCode::{CatalogError, InternalError};
+use risingwave_common::error::{tonic_err, Result as RwResult, RwError};
+use risingwave_pb::catalog::source::Info as SourceInfo;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::*;
 use risingwave_pb::common::ParallelUnitType;
+use risingwave_pb::ddl_service::alter_source_request::Change;
 use risingwave_pb::ddl_service::ddl_service_server::DdlService;
 use risingwave_pb::ddl_service::*;
-use risingwave_pb::plan_common::TableRefId;
+use risingwave_pb::plan_common::{ColumnCatalog, TableRefId};
 use risingwave_pb::stream_plan::stream_node::NodeBody;
 use risingwave_pb::stream_plan::{StreamFragmentGraph, StreamNode};
 use tonic::{Request, Response, Status};
 
 use crate::cluster::ClusterManagerRef;
-use crate::manager::{CatalogManagerRef, IdCategory, MetaSrvEnv, SourceId, TableId};
+use crate::manager::{CatalogManagerRef, IdCategory, MetaSrvEnv, SinkId, SourceId, TableId};
 use crate::model::TableFragments;
 use crate::storage::MetaStore;
 use crate::stream::{
@@ -224,6 +228,26 @@ where
         }))
     }
 
+    async fn alter_source(
+        &self,
+        request: Request<AlterSourceRequest>,
+    ) -> Result<Response<AlterSourceResponse>, Status> {
+        let req = request.into_inner();
+        let change = req
+            .change
+            .ok_or_else(|| tonic_err(InternalError("change is not set".to_string())))?;
+
+        let version = self
+            .alter_source_inner(req.source_id, req.version, change)
+            .await
+            .map_err(tonic_err)?;
+
+        Ok(Response::new(AlterSourceResponse {
+            status: None,
+            version,
+        }))
+    }
+
     async fn create_materialized_view(
         &self,
         request: Request<CreateMaterializedViewRequest>,
@@ -336,6 +360,102 @@ where
         }))
     }
 
+    async fn create_sink(
+        &self,
+        request: Request<CreateSinkRequest>,
+    ) -> Result<Response<CreateSinkResponse>, Status> {
+        let req = request.into_inner();
+        let mut sink = req.get_sink().map_err(tonic_err)?.clone();
+        let fragment_graph = req.get_fragment_graph().map_err(tonic_err)?.clone();
+
+        // 0. Generate an id from sink. The sink shares the id space of tables since its stream
+        // job is managed as table fragments.
+        let id = self
+            .env
+            .id_gen_manager()
+            .generate::<{ IdCategory::Table }>()
+            .await
+            .map_err(tonic_err)? as u32;
+        sink.id = id;
+
+        // 1. Mark current sink as "creating" and add reference count to the associated mview.
+        self.catalog_manager
+            .start_create_sink_procedure(&sink)
+            .await
+            .map_err(tonic_err)?;
+
+        // 2. Create sink in stream manager. The id in stream node will be filled.
+        if let Err(e) = self
+            .create_mview_on_compute_node(fragment_graph, id, None)
+            .await
+        {
+            self.catalog_manager
+                .cancel_create_sink_procedure(&sink)
+                .await
+                .map_err(tonic_err)?;
+            return Err(e.to_grpc_status());
+        }
+
+        // 3. Finally, update the catalog.
+        let version = self
+            .catalog_manager
+            .finish_create_sink_procedure(&sink)
+            .await
+            .map_err(tonic_err)?;
+
+        Ok(Response::new(CreateSinkResponse {
+            status: None,
+            sink_id: id,
+            version,
+        }))
+    }
+
+    async fn drop_sink(
+        &self,
+        request: Request<DropSinkRequest>,
+    ) -> Result<Response<DropSinkResponse>, Status> {
+        use risingwave_common::catalog::TableId;
+
+        let sink_id: SinkId = request.into_inner().sink_id;
+        // 1. Drop sink in catalog.
+        let version = self
+            .catalog_manager
+            .drop_sink(sink_id)
+            .await
+            .map_err(tonic_err)?;
+
+        // 2. Drop sink in stream manager.
+        self.stream_manager
+            .drop_materialized_view(&TableId::new(sink_id))
+            .await
+            .map_err(tonic_err)?;
+
+        Ok(Response::new(DropSinkResponse {
+            status: None,
+            version,
+        }))
+    }
+
+    async fn get_catalog(
+        &self,
+        _request: Request<GetCatalogRequest>,
+    ) -> Result<Response<GetCatalogResponse>, Status> {
+        let (databases, schemas, tables, sources, sinks) = self
+            .catalog_manager
+            .get_catalog()
+            .await
+            .map_err(tonic_err)?;
+
+        Ok(Response::new(GetCatalogResponse {
+            status: None,
+            databases,
+            schemas,
+            tables,
+            sources,
+            sinks,
+        }))
+    }
+
     async fn create_materialized_source(
         &self,
         request: Request<CreateMaterializedSourceRequest>,
@@ -392,13 +512,20 @@ where
 
         use crate::stream::CreateMaterializedViewContext;
 
-        // Fill in the correct mview id for stream node.
+        // Fill in the correct mview id for stream node. A sink is created the same way as a
+        // materialized view, with the sink id in place of the mview id.
         fn fill_mview_id(stream_node: &mut StreamNode, mview_id: TableId) -> usize {
             let mut mview_count = 0;
-            if let NodeBody::Materialize(materialize_node) = stream_node.node_body.as_mut().unwrap()
-            {
-                materialize_node.table_ref_id = TableRefId::from(&mview_id).into();
-                mview_count += 1;
+            match stream_node.node_body.as_mut().unwrap() {
+                NodeBody::Materialize(materialize_node) => {
+                    materialize_node.table_ref_id = TableRefId::from(&mview_id).into();
+                    mview_count += 1;
+                }
+                NodeBody::Sink(sink_node) => {
+                    sink_node.table_ref_id = TableRefId::from(&mview_id).into();
+                    mview_count += 1;
+                }
+                _ => {}
             }
             for input in &mut stream_node.input {
                 mview_count += fill_mview_id(input, mview_id);
@@ -414,7 +541,7 @@ where
 
         assert_eq!(
             mview_count, 1,
-            "require exactly 1 materialize node when creating materialized view"
+            "require exactly 1 materialize or sink node when creating materialized view or sink"
         );
 
         // Resolve fragments.
@@ -529,6 +656,111 @@ where
         Ok((source_id, mview_id, version))
     }
 
+    async fn alter_source_inner(
+        &self,
+        source_id: SourceId,
+        version: u64,
+        change: Change,
+    ) -> RwResult<CatalogVersion> {
+        // 1. Mark the source as altering, the version will be checked.
+        let (mut source, mut table) = self
+            .catalog_manager
+            .start_alter_source_procedure(source_id, version)
+            .await?;
+
+        // 2. Alter the columns of the source and its associated table, and apply the change on
+        // compute nodes.
+        if let Err(e) = self
+            .alter_source_columns(&mut source, table.as_mut(), change)
+            .await
+        {
+            self.catalog_manager
+                .cancel_alter_source_procedure(source_id)
+                .await?;
+            return Err(e);
+        }
+
+        // 3. Finally, update the catalog.
+        self.catalog_manager
+            .finish_alter_source_procedure(source, table)
+            .await
+    }
+
+    /// Adds or drops a column of the source and its associated table. Columns are never removed
+    /// physically: the id of an added column is its position, and a dropped column is kept as a
+    /// hidden column with a reserved name, so that the rows written before are still readable.
+    async fn alter_source_columns(
+        &self,
+        source: &mut Source,
+        table: Option<&mut Table>,
+        change: Change,
+    ) -> RwResult<()> {
+        use risingwave_common::catalog::TableId;
+
+        let original_source = source.clone();
+        let (columns, pk_column_ids) = match source.info.as_mut().unwrap() {
+            SourceInfo::StreamSource(info) => (&mut info.columns, info.pk_column_ids.clone()),
+            SourceInfo::TableSource(info) => (&mut info.columns, vec![]),
+        };
+
+        let mut added_table_column = None;
+        match change {
+            Change::AddColumn(mut column) => {
+                column.column_desc.as_mut().unwrap().column_id = columns.len() as i32;
+                columns.push(column.clone());
+
+                if let Some(table) = table {
+                    table.columns.push(column.clone());
+                    added_table_column = Some((TableId::new(table.id), column));
+                }
+            }
+            Change::DropColumnId(column_id) => {
+                let column = find_visible_column(columns, column_id)?;
+                let name = column.column_desc.as_ref().unwrap().name.clone();
+
+                // The relation is read through the table if the source is associated to one.
+                let relation_id = table.as_ref().map_or(source.id, |table| table.id);
+                let referenced_column_ids = self
+                    .fragment_manager
+                    .get_referenced_column_ids(&TableId::new(relation_id))
+                    .await;
+                if pk_column_ids.contains(&column_id) || referenced_column_ids.contains(&column_id)
+                {
+                    return Err(CatalogError(
+                        anyhow!(
+                            "Fail to drop column `{}` because other objects depend on it.",
+                            name
+                        )
+                        .into(),
+                    )
+                    .into());
+                }
+
+                mark_column_dropped(column);
+                if let Some(table) = table {
+                    mark_column_dropped(find_visible_column(&mut table.columns, column_id)?);
+                }
+            }
+        }
+
+        // The sources on compute nodes are altered before the materialize executors, so that
+        // the chunks with the added column are never written by an executor without it. If the
+        // executors fail to add the column, the sources are restored.
+        self.source_manager.alter_source(source).await?;
+        if let Some((table_id, column)) = added_table_column {
+            if let Err(e) = self
+                .stream_manager
+                .add_table_columns(&table_id, &[column])
+                .await
+            {
+                self.source_manager.alter_source(&original_source).await?;
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
     async fn drop_materialized_source_inner(
         &self,
         source_id: SourceId,
@@ -552,3 +784,248 @@ where
         Ok(version)
     }
 }
+
+fn find_visible_column(
+    columns: &mut [ColumnCatalog],
+    column_id: i32,
+) -> RwResult<&mut ColumnCatalog> {
+    columns
+        .iter_mut()
+        .find(|column| {
+            !column.is_hidden && column.column_desc.as_ref().unwrap().column_id == column_id
+        })
+        .ok_or_else(|| {
+            RwError::from(InternalError(format!(
+                "column doesn't exist: id={}",
+                column_id
+            )))
+        })
+}
+
+/// Hides a dropped column and renames it, so that its name can be reused by a new column.
+fn mark_column_dropped(column: &mut ColumnCatalog) {
+    let column_desc = column.column_desc.as_mut().unwrap();
+    column_desc.name = format!("_dropped_{}", column_desc.column_id);
+    column.is_hidden = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use risingwave_common::types::DataType;
+    use risingwave_pb::meta::table_fragments::Fragment;
+    use risingwave_pb::plan_common::ColumnDesc;
+    use risingwave_pb::stream_plan::source_node::SourceType;
+    use risingwave_pb::stream_plan::{SourceNode, StreamActor};
+
+    use super::*;
+    use crate::barrier::GlobalBarrierManager;
+    use crate::cluster::ClusterManager;
+    use crate::hummock::HummockManager;
+    use crate::manager::CatalogManager;
+    use crate::rpc::metrics::MetaMetrics;
+    use crate::storage::MemStore;
+    use crate::stream::{FragmentManager, GlobalStreamManager, SourceManager};
+
+    /// Builds a [`DdlServiceImpl`] on a cluster without compute nodes.
+    async fn create_ddl_service() -> RwResult<DdlServiceImpl<MemStore>> {
+        let env = MetaSrvEnv::for_test().await;
+        let cluster_manager =
+            Arc::new(ClusterManager::new(env.clone(), Duration::from_secs(3600)).await?);
+        let catalog_manager = Arc::new(CatalogManager::new(env.clone()).await?);
+        let fragment_manager = Arc::new(FragmentManager::new(env.clone()).await?);
+        let meta_metrics = Arc::new(MetaMetrics::new());
+        let hummock_manager = Arc::new(
+            HummockManager::new(env.clone(), cluster_manager.clone(), meta_metrics.clone()).await?,
+        );
+        let barrier_manager = Arc::new(GlobalBarrierManager::new(
+            env.clone(),
+            cluster_manager.clone(),
+            catalog_manager.clone(),
+            fragment_manager.clone(),
+            hummock_manager,
+            meta_metrics,
+        ));
+        let source_manager = Arc::new(
+            SourceManager::new(
+                env.clone(),
+                cluster_manager.clone(),
+                barrier_manager.clone(),
+                catalog_manager.clone(),
+            )
+            .await?,
+        );
+        let stream_manager = Arc::new(
+            GlobalStreamManager::new(
+                env.clone(),
+                fragment_manager.clone(),
+                barrier_manager,
+                cluster_manager.clone(),
+                source_manager.clone(),
+            )
+            .await?,
+        );
+
+        Ok(DdlServiceImpl::new(
+            env,
+            catalog_manager,
+            stream_manager,
+            source_manager,
+            cluster_manager,
+            fragment_manager,
+        ))
+    }
+
+    fn make_column(column_id: i32, name: &str) -> ColumnCatalog {
+        ColumnCatalog {
+            column_desc: Some(ColumnDesc {
+                column_type: Some(DataType::Int32.to_protobuf()),
+                column_id,
+                name: name.to_string(),
+                ..Default::default()
+            }),
+            is_hidden: false,
+        }
+    }
+
+    /// Creates a stream source with the columns `v0` and `v1`.
+    async fn create_source(ddl_service: &DdlServiceImpl<MemStore>, source_id: u32) -> RwResult<()> {
+        let source = Source {
+            id: source_id,
+            name: "s".to_string(),
+            info: Some(SourceInfo::StreamSource(StreamSourceInfo {
+                columns: vec![make_column(0, "v0"), make_column(1, "v1")],
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let catalog_manager = &ddl_service.catalog_manager;
+        catalog_manager
+            .start_create_source_procedure(&source)
+            .await?;
+        catalog_manager
+            .finish_create_source_procedure(&source)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_source(ddl_service: &DdlServiceImpl<MemStore>, source_id: u32) -> Source {
+        ddl_service
+            .catalog_manager
+            .list_sources()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|source| source.id == source_id)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_alter_source_version() -> RwResult<()> {
+        let ddl_service = create_ddl_service().await?;
+        create_source(&ddl_service, 1).await?;
+
+        ddl_service
+            .alter_source_inner(1, 0, Change::AddColumn(make_column(0, "v2")))
+            .await?;
+        let source = get_source(&ddl_service, 1).await;
+        assert_eq!(source.version, 1);
+        match source.info.unwrap() {
+            SourceInfo::StreamSource(info) => {
+                assert_eq!(info.columns.len(), 3);
+                assert_eq!(info.columns[2].column_desc.as_ref().unwrap().column_id, 2);
+            }
+            SourceInfo::TableSource(_) => unreachable!(),
+        }
+
+        // The change based on a stale version is rejected.
+        assert!(ddl_service
+            .alter_source_inner(1, 0, Change::DropColumnId(2))
+            .await
+            .is_err());
+
+        // The change is rejected while the source is being altered, and the version is kept.
+        ddl_service
+            .catalog_manager
+            .start_alter_source_procedure(1, 1)
+            .await?;
+        assert!(ddl_service
+            .alter_source_inner(1, 1, Change::DropColumnId(2))
+            .await
+            .is_err());
+        ddl_service
+            .catalog_manager
+            .cancel_alter_source_procedure(1)
+            .await?;
+        assert_eq!(get_source(&ddl_service, 1).await.version, 1);
+
+        ddl_service
+            .alter_source_inner(1, 1, Change::DropColumnId(2))
+            .await?;
+        assert_eq!(get_source(&ddl_service, 1).await.version, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drop_referenced_column() -> RwResult<()> {
+        use risingwave_common::catalog::TableId;
+
+        let ddl_service = create_ddl_service().await?;
+        create_source(&ddl_service, 1).await?;
+
+        // A materialized view reads the column `v1` of the source.
+        let source_node = StreamNode {
+            node_body: Some(NodeBody::Source(SourceNode {
+                table_ref_id: Some(TableRefId {
+                    schema_ref_id: None,
+                    table_id: 1,
+                }),
+                column_ids: vec![1],
+                source_type: SourceType::Source as i32,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let fragment = Fragment {
+            fragment_id: 1,
+            actors: vec![StreamActor {
+                actor_id: 1,
+                fragment_id: 1,
+                nodes: Some(source_node),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        ddl_service
+            .fragment_manager
+            .start_create_table_fragments(TableFragments::new(
+                TableId::new(2),
+                BTreeMap::from([(1, fragment)]),
+            ))
+            .await?;
+
+        // The referenced column can't be dropped, and the source is left unchanged.
+        assert!(ddl_service
+            .alter_source_inner(1, 0, Change::DropColumnId(1))
+            .await
+            .is_err());
+        let source = get_source(&ddl_service, 1).await;
+        assert_eq!(source.version, 0);
+        match source.info.unwrap() {
+            SourceInfo::StreamSource(info) => assert!(info.columns.iter().all(|c| !c.is_hidden)),
+            SourceInfo::TableSource(_) => unreachable!(),
+        }
+
+        // The column not referenced can be dropped.
+        ddl_service
+            .alter_source_inner(1, 0, Change::DropColumnId(0))
+            .await?;
+        assert_eq!(get_source(&ddl_service, 1).await.version, 1);
+
+        Ok(())
+    }
+}
@@ -23,13 +23,14 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use tonic::{Request, Response, Status};
 
 use crate::cluster::{ClusterManagerRef, WorkerKey};
-use crate::manager::{CatalogManagerRef, MetaSrvEnv, Notification};
+use crate::manager::{CatalogManagerRef, MetaSrvEnv, Notification, UserManagerRef};
 use crate::storage::MetaStore;
 pub struct NotificationServiceImpl<S: MetaStore> {
     env: MetaSrvEnv<S>,
 
     catalog_manager: CatalogManagerRef<S>,
     cluster_manager: ClusterManagerRef<S>,
+    user_manager: UserManagerRef<S>,
 }
 
 impl<S> NotificationServiceImpl<S>
@@ -40,11 +41,13 @@ where
         env: MetaSrvEnv<S>,
         catalog_manager: CatalogManagerRef<S>,
         cluster_manager: ClusterManagerRef<S>,
+        user_manager: UserManagerRef<S>,
     ) -> Self {
         Self {
             env,
             catalog_manager,
             cluster_manager,
+            user_manager,
         }
     }
 }
@@ -76,7 +79,7 @@ where
             }
             WorkerType::Frontend => {
                 let catalog_guard = self.catalog_manager.get_catalog_core_guard().await;
-                let (database, schema, table, source) = catalog_guard
+                let (database, schema, table, source, sink) = catalog_guard
                     .get_catalog()
                     .await
                     .map_err(|e| e.to_grpc_status())?;
@@ -84,6 +87,9 @@ where
                 let cluster_guard = self.cluster_manager.get_cluster_core_guard().await;
                 let nodes = cluster_guard.list_worker_node(WorkerType::ComputeNode, Some(Running));
 
+                let user_guard = self.user_manager.get_user_core_guard().await;
+                let users = user_guard.values().cloned().collect();
+
                 // Send the snapshot on subscription. After that we will send only updates.
                 let meta_snapshot = MetaSnapshot {
                     nodes,
@@ -92,6 +98,8 @@ where
                     source,
                     table,
                     view: Default::default(),
+                    sink,
+                    users,
                 };
                 tx.send(Ok(SubscribeResponse {
                     status: None,
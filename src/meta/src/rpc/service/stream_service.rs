@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_common::catalog::TableId;
 use risingwave_pb::meta::stream_manager_service_server::StreamManagerService;
 use risingwave_pb::meta::*;
 use tonic::{Request, Response, Status};
 
+use crate::model::MetadataModel;
 use crate::storage::MetaStore;
-use crate::stream::GlobalStreamManagerRef;
+use crate::stream::{FragmentManagerRef, GlobalStreamManagerRef};
 
 pub type TonicResponse<T> = Result<Response<T>, Status>;
 
@@ -27,15 +29,20 @@ where
     S: MetaStore,
 {
     global_stream_manager: GlobalStreamManagerRef<S>,
+    fragment_manager: FragmentManagerRef<S>,
 }
 
 impl<S> StreamServiceImpl<S>
 where
     S: MetaStore,
 {
-    pub fn new(global_stream_manager: GlobalStreamManagerRef<S>) -> Self {
+    pub fn new(
+        global_stream_manager: GlobalStreamManagerRef<S>,
+        fragment_manager: FragmentManagerRef<S>,
+    ) -> Self {
         StreamServiceImpl {
             global_stream_manager,
+            fragment_manager,
         }
     }
 }
@@ -55,4 +62,56 @@ where
             .map_err(|e| e.to_grpc_status())?;
         Ok(Response::new(FlushResponse { status: None }))
     }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn reschedule(
+        &self,
+        request: Request<RescheduleRequest>,
+    ) -> TonicResponse<RescheduleResponse> {
+        let req = request.into_inner();
+
+        self.global_stream_manager
+            .reschedule_fragment(
+                req.fragment_id,
+                &req.added_parallel_units,
+                &req.removed_parallel_units,
+            )
+            .await
+            .map_err(|e| e.to_grpc_status())?;
+        Ok(Response::new(RescheduleResponse { status: None }))
+    }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn list_table_fragments(
+        &self,
+        request: Request<ListTableFragmentsRequest>,
+    ) -> TonicResponse<ListTableFragmentsResponse> {
+        let req = request.into_inner();
+
+        let table_fragments = if req.table_ids.is_empty() {
+            self.fragment_manager
+                .list_table_fragments()
+                .await
+                .map_err(|e| e.to_grpc_status())?
+        } else {
+            let mut table_fragments = Vec::with_capacity(req.table_ids.len());
+            for table_id in req.table_ids {
+                table_fragments.push(
+                    self.fragment_manager
+                        .select_table_fragments_by_table_id(&TableId::new(table_id))
+                        .await
+                        .map_err(|e| e.to_grpc_status())?,
+                );
+            }
+            table_fragments
+        };
+
+        Ok(Response::new(ListTableFragmentsResponse {
+            status: None,
+            table_fragments: table_fragments
+                .iter()
+                .map(MetadataModel::to_protobuf)
+                .collect(),
+        }))
+    }
 }
@@ -22,13 +22,18 @@ use risingwave_common::error::{Result, RwError};
 use risingwave_common::hash::VIRTUAL_NODE_COUNT;
 use risingwave_common::try_match_expand;
 use risingwave_common::util::compress::decompress_data;
-use risingwave_pb::meta::table_fragments::ActorState;
-use risingwave_pb::stream_plan::{FragmentType, StreamActor};
+use risingwave_pb::common::ParallelUnitMapping;
+use risingwave_pb::data::reschedule_mutation::MergeUpdate;
+use risingwave_pb::data::RescheduleMutation;
+use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus};
+use risingwave_pb::plan_common::Field;
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::{ActorMapping, FragmentType, StreamActor, StreamNode};
 use tokio::sync::RwLock;
 
 use crate::cluster::{ParallelUnitId, WorkerId};
 use crate::manager::{HashMappingManagerRef, MetaSrvEnv};
-use crate::model::{ActorId, MetadataModel, TableFragments, Transactional};
+use crate::model::{ActorId, FragmentId, MetadataModel, TableFragments, Transactional};
 use crate::storage::{MetaStore, Transaction};
 use crate::stream::set_table_vnode_mappings;
 
@@ -90,6 +95,20 @@ where
         Ok(map.values().cloned().collect())
     }
 
+    pub async fn select_table_fragments_by_table_id(
+        &self,
+        table_id: &TableId,
+    ) -> Result<TableFragments> {
+        let map = &self.core.read().await.table_fragments;
+        match map.get(table_id) {
+            Some(table_fragment) => Ok(table_fragment.clone()),
+            None => Err(RwError::from(InternalError(format!(
+                "table_fragment not exist: id={}",
+                table_id
+            )))),
+        }
+    }
+
     pub async fn update_table_fragments(&self, table_fragment: TableFragments) -> Result<()> {
         let map = &mut self.core.write().await.table_fragments;
 
@@ -107,6 +126,41 @@ where
         }
     }
 
+    /// Returns the ids of the columns of the relation that are read by any materialized view.
+    pub async fn get_referenced_column_ids(&self, relation_id: &TableId) -> HashSet<i32> {
+        let map = &self.core.read().await.table_fragments;
+
+        map.values()
+            .flat_map(|table_fragments| table_fragments.referenced_column_ids(relation_id))
+            .collect()
+    }
+
+    /// Appends the columns added by `ALTER TABLE ADD COLUMN` to the fragments of the table, so
+    /// that the actors rebuilt on recovery have the columns.
+    pub async fn add_table_columns(
+        &self,
+        table_id: &TableId,
+        fields: &[Field],
+        column_ids: &[i32],
+    ) -> Result<()> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        match map.get_mut(table_id) {
+            Some(table_fragments) => {
+                let mut new_table_fragments = table_fragments.clone();
+                new_table_fragments.add_table_columns(fields, column_ids);
+                new_table_fragments.insert(&*self.meta_store).await?;
+                *table_fragments = new_table_fragments;
+
+                Ok(())
+            }
+            None => Err(RwError::from(InternalError(format!(
+                "table_fragment not exist: id={}",
+                table_id
+            )))),
+        }
+    }
+
     /// Start create a new `TableFragments` and insert it into meta store, currently the actors'
     /// state is `ActorState::Inactive`.
     pub async fn start_create_table_fragments(&self, table_fragment: TableFragments) -> Result<()> {
@@ -249,6 +303,151 @@ where
         }
     }
 
+    /// Returns the table fragments containing the given fragment.
+    pub async fn get_table_fragments_of_fragment(
+        &self,
+        fragment_id: FragmentId,
+    ) -> Result<TableFragments> {
+        let map = &self.core.read().await.table_fragments;
+
+        map.values()
+            .find(|table_fragments| table_fragments.fragments.contains_key(&fragment_id))
+            .cloned()
+            .ok_or_else(|| {
+                RwError::from(InternalError(format!(
+                    "fragment not exist: id={}",
+                    fragment_id
+                )))
+            })
+    }
+
+    /// Start rescheduling a fragment by adding the new actors to it, currently the new actors'
+    /// state is `ActorState::Inactive`.
+    pub async fn start_reschedule_fragment(
+        &self,
+        table_id: &TableId,
+        fragment_id: FragmentId,
+        new_actors: Vec<StreamActor>,
+        new_actor_status: BTreeMap<ActorId, ActorStatus>,
+    ) -> Result<()> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        let mut table_fragments = Self::get_table_fragments(map, table_id)?.clone();
+        table_fragments.add_actors(fragment_id, new_actors, new_actor_status);
+        table_fragments.insert(&*self.meta_store).await?;
+        map.insert(*table_id, table_fragments);
+
+        Ok(())
+    }
+
+    /// Cancel rescheduling a fragment by removing the new actors from it.
+    pub async fn cancel_reschedule_fragment(
+        &self,
+        table_id: &TableId,
+        fragment_id: FragmentId,
+        new_actor_ids: &HashSet<ActorId>,
+    ) -> Result<()> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        let mut table_fragments = Self::get_table_fragments(map, table_id)?.clone();
+        table_fragments.remove_actors(fragment_id, new_actor_ids);
+        table_fragments.insert(&*self.meta_store).await?;
+        map.insert(*table_id, table_fragments);
+
+        Ok(())
+    }
+
+    /// Finish rescheduling a fragment. The stopped actors are removed, the new actors are marked as
+    /// `ActorState::Running`, and the dispatchers and merges around the fragment are updated as
+    /// the `mutation` describes.
+    pub async fn finish_reschedule_fragment(
+        &self,
+        table_id: &TableId,
+        fragment_id: FragmentId,
+        vnode_mapping: ParallelUnitMapping,
+        mutation: &RescheduleMutation,
+    ) -> Result<()> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        let mut table_fragments = Self::get_table_fragments(map, table_id)?.clone();
+        let stopped_actors = mutation.stopped_actors.iter().copied().collect();
+        table_fragments.remove_actors(fragment_id, &stopped_actors);
+        table_fragments.update_actors_state(ActorState::Running);
+
+        let dispatcher_updates = mutation
+            .dispatchers
+            .iter()
+            .map(|update| ((update.actor_id, update.dispatcher_id), update))
+            .collect::<HashMap<_, _>>();
+        let merge_updates = mutation
+            .merges
+            .iter()
+            .map(|update| (update.actor_id, update))
+            .collect::<HashMap<_, _>>();
+        let prev_fragment_actors = mutation
+            .prev_fragment_actors
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>();
+
+        for fragment in table_fragments.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                for dispatcher in &mut actor.dispatcher {
+                    if let Some(update) =
+                        dispatcher_updates.get(&(actor.actor_id, dispatcher.dispatcher_id))
+                    {
+                        dispatcher
+                            .downstream_actor_id
+                            .retain(|id| !update.removed_downstream_actor_ids.contains(id));
+                        dispatcher.downstream_actor_id.extend(
+                            update
+                                .added_downstream_actors
+                                .iter()
+                                .map(|info| info.actor_id),
+                        );
+                        if !update.hash_mapping_data.is_empty() {
+                            dispatcher.hash_mapping = Some(ActorMapping {
+                                original_indices: update.hash_mapping_original_indices.clone(),
+                                data: update.hash_mapping_data.clone(),
+                            });
+                        }
+                    }
+                }
+
+                if let Some(update) = merge_updates.get(&actor.actor_id) {
+                    apply_merge_update(&mut actor.upstream_actor_id, update);
+                    update_merge_nodes(
+                        actor.nodes.as_mut().unwrap(),
+                        &prev_fragment_actors,
+                        update,
+                    );
+                }
+            }
+        }
+        table_fragments
+            .fragments
+            .get_mut(&fragment_id)
+            .unwrap()
+            .vnode_mapping = Some(vnode_mapping);
+
+        table_fragments.insert(&*self.meta_store).await?;
+        map.insert(*table_id, table_fragments);
+
+        Ok(())
+    }
+
+    fn get_table_fragments<'a>(
+        map: &'a HashMap<TableId, TableFragments>,
+        table_id: &TableId,
+    ) -> Result<&'a TableFragments> {
+        map.get(table_id).ok_or_else(|| {
+            RwError::from(InternalError(format!(
+                "table_fragment not exist: id={}",
+                table_id
+            )))
+        })
+    }
+
     /// Used in [`crate::barrier::GlobalBarrierManager`]
     pub async fn load_all_actors(&self, with_creating_table: Option<TableId>) -> ActorInfos {
         let mut actor_maps = HashMap::new();
@@ -448,3 +647,35 @@ where
         Ok(())
     }
 }
+
+fn apply_merge_update(upstream_actor_ids: &mut Vec<ActorId>, update: &MergeUpdate) {
+    upstream_actor_ids.retain(|id| !update.removed_upstream_actor_ids.contains(id));
+    upstream_actor_ids.extend(
+        update
+            .added_upstream_actors
+            .iter()
+            .map(|info| info.actor_id),
+    );
+}
+
+/// Update the merge nodes receiving from the rescheduled fragment, whose actors before rescheduling
+/// are `prev_fragment_actors`.
+fn update_merge_nodes(
+    stream_node: &mut StreamNode,
+    prev_fragment_actors: &HashSet<ActorId>,
+    update: &MergeUpdate,
+) {
+    if let Some(NodeBody::Merge(merge)) = stream_node.node_body.as_mut() {
+        if merge
+            .upstream_actor_id
+            .iter()
+            .any(|id| prev_fragment_actors.contains(id))
+        {
+            apply_merge_update(&mut merge.upstream_actor_id, update);
+        }
+    }
+
+    for child in &mut stream_node.input {
+        update_merge_nodes(child, prev_fragment_actors, update);
+    }
+}
@@ -52,7 +52,10 @@ pub fn set_table_vnode_mappings(
         }
         NodeBody::HashAgg(node) => {
             let table_ids = node.get_table_ids();
-            for table_id in table_ids {
+            for table_id in table_ids
+                .iter()
+                .chain(node.distinct_dedup_table_ids.values())
+            {
                 hash_mapping_manager.set_fragment_state_table(fragment_id, *table_id);
             }
         }
@@ -60,6 +63,9 @@ pub fn set_table_vnode_mappings(
             hash_mapping_manager.set_fragment_state_table(fragment_id, node.left_table_id);
             hash_mapping_manager.set_fragment_state_table(fragment_id, node.right_table_id);
         }
+        NodeBody::OverAgg(node) => {
+            hash_mapping_manager.set_fragment_state_table(fragment_id, node.table_id);
+        }
         _ => {}
     }
     let input_nodes = stream_node.get_input();
@@ -25,6 +25,7 @@ use risingwave_pb::catalog::Source;
 use risingwave_pb::common::worker_node::State::Running;
 use risingwave_pb::common::WorkerType;
 use risingwave_pb::stream_service::{
+    AlterSourceRequest as ComputeNodeAlterSourceRequest,
     CreateSourceRequest as ComputeNodeCreateSourceRequest,
     DropSourceRequest as ComputeNodeDropSourceRequest,
 };
@@ -183,6 +184,24 @@ where
         Ok(())
     }
 
+    /// Broadcasts the altered source to all compute nodes, which replace the columns of the source
+    /// with the new ones.
+    pub async fn alter_source(&self, source: &Source) -> Result<()> {
+        let futures = self
+            .all_stream_clients()
+            .await?
+            .into_iter()
+            .map(|mut client| {
+                let request = ComputeNodeAlterSourceRequest {
+                    source: Some(source.clone()),
+                };
+                async move { client.alter_source(request).await.to_rw_result() }
+            });
+        let _responses: Vec<_> = try_join_all(futures).await?;
+
+        Ok(())
+    }
+
     pub async fn run(&self) -> Result<()> {
         // todo: in the future, split change will be pushed as a long running service
         Ok(())
@@ -560,12 +560,24 @@ impl StreamGraphBuilder {
                     node.table_id += table_id_offset;
                 }
 
+                if let NodeBody::OverAgg(node) = new_stream_node.node_body.as_mut().unwrap() {
+                    node.table_id += table_id_offset;
+                }
+
+                if let NodeBody::DynamicFilter(node) = new_stream_node.node_body.as_mut().unwrap() {
+                    node.left_table_id += table_id_offset;
+                    node.right_table_id += table_id_offset;
+                }
+
                 if let NodeBody::HashAgg(node) = new_stream_node.node_body.as_mut().unwrap() {
                     assert_eq!(node.table_ids.len(), node.agg_calls.len());
                     // In-place update the table id. Convert from local to global.
                     for table_id in &mut node.table_ids {
                         *table_id += table_id_offset;
                     }
+                    for table_id in node.distinct_dedup_table_ids.values_mut() {
+                        *table_id += table_id_offset;
+                    }
                 }
 
                 match new_stream_node.node_body.as_mut().unwrap() {
@@ -575,6 +587,9 @@ impl StreamGraphBuilder {
                         for table_id in &mut node.table_ids {
                             *table_id += table_id_offset;
                         }
+                        for table_id in node.distinct_dedup_table_ids.values_mut() {
+                            *table_id += table_id_offset;
+                        }
                     }
                     _ => {}
                 }
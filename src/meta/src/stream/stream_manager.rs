@@ -19,14 +19,23 @@ use std::time::Instant;
 use itertools::Itertools;
 use log::{debug, info};
 use risingwave_common::catalog::TableId;
-use risingwave_common::error::{internal_error, Result, ToRwResult};
+use risingwave_common::error::{internal_error, ErrorCode, Result, RwError, ToRwResult};
 use risingwave_common::hash::VIRTUAL_NODE_COUNT;
-use risingwave_common::util::compress::compress_data;
+use risingwave_common::util::compress::{compress_data, decompress_data};
 use risingwave_pb::catalog::Source;
-use risingwave_pb::common::{ActorInfo, ParallelUnitMapping, WorkerType};
-use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus};
+use risingwave_pb::common::{
+    ActorInfo, ParallelUnit, ParallelUnitMapping, ParallelUnitType, WorkerNode, WorkerType,
+};
+use risingwave_pb::data::barrier::Mutation;
+use risingwave_pb::data::reschedule_mutation::{DispatcherUpdate, MergeUpdate};
+use risingwave_pb::data::{AddColumnsMutation, RescheduleMutation};
+use risingwave_pb::meta::table_fragments::fragment::FragmentDistributionType;
+use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus, Fragment};
+use risingwave_pb::plan_common::{ColumnCatalog, Field};
 use risingwave_pb::stream_plan::stream_node::NodeBody;
-use risingwave_pb::stream_plan::{ActorMapping, DispatcherType, StreamNode, StreamSourceState};
+use risingwave_pb::stream_plan::{
+    ActorMapping, DispatcherType, StreamActor, StreamNode, StreamSourceState,
+};
 use risingwave_pb::stream_service::{
     BroadcastActorInfoTableRequest, BuildActorsRequest, HangingChannel, UpdateActorsRequest,
 };
@@ -36,8 +45,8 @@ use uuid::Uuid;
 use super::ScheduledLocations;
 use crate::barrier::{BarrierManagerRef, Command};
 use crate::cluster::{ClusterManagerRef, ParallelUnitId, WorkerId};
-use crate::manager::{HashMappingManagerRef, MetaSrvEnv};
-use crate::model::{ActorId, DispatcherId, TableFragments};
+use crate::manager::{HashMappingManagerRef, IdCategory, IdGeneratorManagerRef, MetaSrvEnv};
+use crate::model::{ActorId, DispatcherId, FragmentId, TableFragments};
 use crate::storage::MetaStore;
 use crate::stream::{FragmentManagerRef, Scheduler, SourceManagerRef};
 
@@ -82,6 +91,9 @@ pub struct GlobalStreamManager<S: MetaStore> {
 
     /// Client Pool to stream service on compute nodes
     client_pool: StreamClientPoolRef,
+
+    /// Generates ids of the actors created by rescheduling.
+    id_gen_manager: IdGeneratorManagerRef<S>,
 }
 
 impl<S> GlobalStreamManager<S>
@@ -103,6 +115,7 @@ where
             source_manager,
             hash_mapping_manager: env.hash_mapping_manager_ref(),
             client_pool: env.stream_client_pool_ref(),
+            id_gen_manager: env.id_gen_manager_ref(),
         })
     }
 
@@ -657,6 +670,52 @@ where
         Ok(())
     }
 
+    /// Adds columns to the materialized view of a table. The fragments of the table are updated
+    /// first, then an `AddColumns` barrier makes the running materialize executors of the table
+    /// write the new columns.
+    pub async fn add_table_columns(
+        &self,
+        table_id: &TableId,
+        columns: &[ColumnCatalog],
+    ) -> Result<()> {
+        let fields = columns
+            .iter()
+            .map(|column| {
+                let column_desc = column.column_desc.as_ref().unwrap();
+                Field {
+                    data_type: column_desc.column_type.clone(),
+                    name: column_desc.name.clone(),
+                }
+            })
+            .collect_vec();
+        let column_ids = columns
+            .iter()
+            .map(|column| column.column_desc.as_ref().unwrap().column_id)
+            .collect_vec();
+
+        self.fragment_manager
+            .add_table_columns(table_id, &fields, &column_ids)
+            .await?;
+
+        let mutation = AddColumnsMutation {
+            table_id: table_id.table_id,
+            column_ids,
+            column_types: fields
+                .into_iter()
+                .map(|field| field.data_type.unwrap())
+                .collect(),
+            default_values: columns
+                .iter()
+                .map(|column| column.column_desc.as_ref().unwrap().default_value.clone())
+                .collect(),
+        };
+        self.barrier_manager
+            .run_command(Command::Plain(Mutation::AddColumns(mutation)))
+            .await?;
+
+        Ok(())
+    }
+
     /// Flush means waiting for the next barrier to collect.
     pub async fn flush(&self) -> Result<()> {
         let start = Instant::now();
@@ -672,7 +731,531 @@ where
         Ok(())
     }
 
-    // fn
+    /// Reschedule the actors of a fragment online, it works as follows:
+    /// 1. check whether the fragment can be rescheduled, and rebalance its vnode mapping over the
+    /// new parallel units.
+    /// 2. create actors on the added parallel units.
+    /// 3. notify related nodes to update and build the new actors, and create the channels between
+    /// them and their upstream and downstream actors.
+    /// 4. run a `Reschedule` barrier command, which starts the new actors, stops the actors on the
+    /// removed parallel units, and updates the dispatchers and merges around the fragment.
+    ///
+    /// States of a fragment are partitioned by vnode and shared by its actors, so the states of
+    /// the moved vnodes are taken over by their new owners without being copied.
+    pub async fn reschedule_fragment(
+        &self,
+        fragment_id: FragmentId,
+        added_parallel_units: &[ParallelUnitId],
+        removed_parallel_units: &[ParallelUnitId],
+    ) -> Result<()> {
+        let table_fragments = self
+            .fragment_manager
+            .get_table_fragments_of_fragment(fragment_id)
+            .await?;
+        let table_id = table_fragments.table_id();
+        let fragment = table_fragments.fragments[&fragment_id].clone();
+        let actor_status = table_fragments.actor_status();
+
+        let nodes: HashMap<WorkerId, WorkerNode> = self
+            .cluster_manager
+            .list_worker_node(
+                WorkerType::ComputeNode,
+                Some(risingwave_pb::common::worker_node::State::Running),
+            )
+            .await
+            .into_iter()
+            .map(|node| (node.id, node))
+            .collect();
+        let actor_parallel_unit = |actor_id: &ActorId| -> Result<ParallelUnit> {
+            Ok(actor_status
+                .get(actor_id)
+                .ok_or_else(|| internal_error(format!("actor {} has no status", actor_id)))?
+                .get_parallel_unit()?
+                .clone())
+        };
+        let actor_info = |actor_id: ActorId, parallel_unit: &ParallelUnit| -> Result<ActorInfo> {
+            let node = nodes.get(&parallel_unit.worker_node_id).ok_or_else(|| {
+                internal_error(format!(
+                    "worker node {} is not running",
+                    parallel_unit.worker_node_id
+                ))
+            })?;
+            Ok(ActorInfo {
+                actor_id,
+                host: node.host.clone(),
+            })
+        };
+
+        let mut parallel_unit_actors = HashMap::new();
+        for actor in &fragment.actors {
+            parallel_unit_actors.insert(actor_parallel_unit(&actor.actor_id)?.id, actor.actor_id);
+        }
+        let hash_parallel_units = self
+            .cluster_manager
+            .list_parallel_units(Some(ParallelUnitType::Hash))
+            .await;
+        let added_parallel_units = added_parallel_units
+            .iter()
+            .map(|id| {
+                hash_parallel_units
+                    .iter()
+                    .find(|parallel_unit| {
+                        parallel_unit.id == *id && nodes.contains_key(&parallel_unit.worker_node_id)
+                    })
+                    .cloned()
+                    .ok_or_else(|| {
+                        invalid_reschedule(format!(
+                            "parallel unit {} is not a hash parallel unit on a running node",
+                            id
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        check_reschedulable(
+            &table_fragments,
+            &fragment,
+            &parallel_unit_actors,
+            &added_parallel_units,
+            removed_parallel_units,
+        )?;
+
+        // Rebalance the vnode mapping of the fragment.
+        let vnode_mapping = self
+            .hash_mapping_manager
+            .rebalance_fragment_hash_mapping(
+                fragment_id,
+                &added_parallel_units,
+                removed_parallel_units,
+            )
+            .ok_or_else(|| {
+                internal_error(format!("fragment {} has no vnode mapping", fragment_id))
+            })?;
+        let prev_mapping = fragment.get_vnode_mapping()?;
+        let prev_vnode_mapping =
+            decompress_data(&prev_mapping.original_indices, &prev_mapping.data);
+
+        // Create the new actors by cloning the existing ones.
+        let start_actor_id = self
+            .id_gen_manager
+            .generate_interval::<{ IdCategory::Actor }>(added_parallel_units.len() as i32)
+            .await? as ActorId;
+        let mut new_actors = Vec::with_capacity(added_parallel_units.len());
+        let mut new_actor_status = BTreeMap::new();
+        let mut new_actor_infos = Vec::with_capacity(added_parallel_units.len());
+        for (actor_id, parallel_unit) in (start_actor_id..).zip_eq(&added_parallel_units) {
+            new_actors.push(StreamActor {
+                actor_id,
+                ..fragment.actors[0].clone()
+            });
+            new_actor_status.insert(
+                actor_id,
+                ActorStatus {
+                    parallel_unit: Some(parallel_unit.clone()),
+                    state: ActorState::Inactive as i32,
+                },
+            );
+            new_actor_infos.push(actor_info(actor_id, parallel_unit)?);
+            parallel_unit_actors.insert(parallel_unit.id, actor_id);
+        }
+        let new_actor_ids: HashSet<ActorId> = new_actors.iter().map(|a| a.actor_id).collect();
+
+        let prev_fragment_actors: HashSet<ActorId> =
+            fragment.actors.iter().map(|a| a.actor_id).collect();
+        let removed_actors: HashMap<ActorId, ParallelUnit> = removed_parallel_units
+            .iter()
+            .map(|id| {
+                let actor_id = parallel_unit_actors[id];
+                Ok((actor_id, actor_parallel_unit(&actor_id)?))
+            })
+            .collect::<Result<_>>()?;
+        let removed_actor_ids: Vec<ActorId> = removed_actors.keys().copied().collect();
+
+        // The actors whose vnodes are changed, including the new ones.
+        let mut vnode_changed_actors: HashSet<ActorId> = new_actor_ids.clone();
+        for (prev, curr) in prev_vnode_mapping.iter().zip_eq(&vnode_mapping) {
+            if prev != curr {
+                vnode_changed_actors.insert(parallel_unit_actors[curr]);
+                if let Some(actor_id) = parallel_unit_actors.get(prev) {
+                    if !removed_actors.contains_key(actor_id) {
+                        vnode_changed_actors.insert(*actor_id);
+                    }
+                }
+            }
+        }
+
+        // The hash mapping of the upstream dispatchers, from vnode to the new actors.
+        let (hash_mapping_original_indices, hash_mapping_data) = compress_data(
+            &vnode_mapping
+                .iter()
+                .map(|parallel_unit_id| parallel_unit_actors[parallel_unit_id])
+                .collect_vec(),
+        );
+
+        // Updates of the dispatchers of the upstream actors, and the merges of the downstream
+        // actors.
+        let mut upstream_actors = HashMap::new();
+        let mut downstream_actors = HashMap::new();
+        let mut dispatchers = vec![];
+        let mut merges = vec![];
+        for other_fragment in table_fragments.fragments.values() {
+            if other_fragment.fragment_id == fragment_id {
+                continue;
+            }
+            for actor in &other_fragment.actors {
+                for dispatcher in &actor.dispatcher {
+                    if dispatcher
+                        .downstream_actor_id
+                        .iter()
+                        .any(|id| prev_fragment_actors.contains(id))
+                    {
+                        let is_hash = dispatcher.get_type()? == DispatcherType::Hash;
+                        dispatchers.push(DispatcherUpdate {
+                            actor_id: actor.actor_id,
+                            dispatcher_id: dispatcher.dispatcher_id,
+                            hash_mapping_original_indices: if is_hash {
+                                hash_mapping_original_indices.clone()
+                            } else {
+                                vec![]
+                            },
+                            hash_mapping_data: if is_hash {
+                                hash_mapping_data.clone()
+                            } else {
+                                vec![]
+                            },
+                            added_downstream_actors: new_actor_infos.clone(),
+                            removed_downstream_actor_ids: removed_actor_ids.clone(),
+                        });
+                        upstream_actors
+                            .insert(actor.actor_id, actor_parallel_unit(&actor.actor_id)?);
+                    }
+                }
+                if actor
+                    .upstream_actor_id
+                    .iter()
+                    .any(|id| prev_fragment_actors.contains(id))
+                {
+                    merges.push(MergeUpdate {
+                        actor_id: actor.actor_id,
+                        added_upstream_actors: new_actor_infos.clone(),
+                        removed_upstream_actor_ids: removed_actor_ids.clone(),
+                    });
+                    downstream_actors.insert(actor.actor_id, actor_parallel_unit(&actor.actor_id)?);
+                }
+            }
+        }
+        let mutation = RescheduleMutation {
+            dispatchers,
+            merges,
+            prev_fragment_actors: prev_fragment_actors.iter().copied().collect(),
+            vnode_changed_actors: vnode_changed_actors.into_iter().collect(),
+            stopped_actors: removed_actor_ids.clone(),
+        };
+
+        // Broadcast the infos of the new actors and the actors around them, and create the channels
+        // to and from the new actors on the nodes of the remote upstream and downstream actors.
+        let mut actor_infos_to_broadcast = new_actor_infos.clone();
+        for (actor_id, parallel_unit) in upstream_actors.iter().chain(downstream_actors.iter()) {
+            actor_infos_to_broadcast.push(actor_info(*actor_id, parallel_unit)?);
+        }
+        let mut node_hanging_channels: HashMap<WorkerId, Vec<HangingChannel>> = HashMap::new();
+        for (new_actor_info, parallel_unit) in new_actor_infos.iter().zip_eq(&added_parallel_units)
+        {
+            for (up_id, up_parallel_unit) in &upstream_actors {
+                if up_parallel_unit.worker_node_id != parallel_unit.worker_node_id {
+                    node_hanging_channels
+                        .entry(up_parallel_unit.worker_node_id)
+                        .or_default()
+                        .push(HangingChannel {
+                            upstream: Some(ActorInfo {
+                                actor_id: *up_id,
+                                host: None,
+                            }),
+                            downstream: Some(new_actor_info.clone()),
+                        });
+                }
+            }
+            for (down_id, down_parallel_unit) in &downstream_actors {
+                if down_parallel_unit.worker_node_id != parallel_unit.worker_node_id {
+                    node_hanging_channels
+                        .entry(down_parallel_unit.worker_node_id)
+                        .or_default()
+                        .push(HangingChannel {
+                            upstream: Some(new_actor_info.clone()),
+                            downstream: Some(ActorInfo {
+                                actor_id: *down_id,
+                                host: None,
+                            }),
+                        });
+                }
+            }
+        }
+
+        let mut node_new_actors: HashMap<WorkerId, Vec<StreamActor>> = HashMap::new();
+        for (actor, parallel_unit) in new_actors.iter().zip_eq(&added_parallel_units) {
+            node_new_actors
+                .entry(parallel_unit.worker_node_id)
+                .or_default()
+                .push(actor.clone());
+        }
+        let involved_nodes: HashSet<WorkerId> = node_new_actors
+            .keys()
+            .chain(node_hanging_channels.keys())
+            .copied()
+            .collect();
+        for node_id in involved_nodes {
+            let node = nodes.get(&node_id).unwrap();
+            let client = self.client_pool.get(node).await?;
+
+            client
+                .to_owned()
+                .broadcast_actor_info_table(BroadcastActorInfoTableRequest {
+                    info: actor_infos_to_broadcast.clone(),
+                })
+                .await
+                .to_rw_result_with(|| format!("failed to connect to {}", node_id))?;
+
+            let request_id = Uuid::new_v4().to_string();
+            let actors = node_new_actors.get(&node_id).cloned().unwrap_or_default();
+            let actor_ids = actors.iter().map(|a| a.actor_id).collect_vec();
+            tracing::debug!(request_id = request_id.as_str(), actors = ?actor_ids, "update actors");
+            client
+                .to_owned()
+                .update_actors(UpdateActorsRequest {
+                    request_id,
+                    actors,
+                    hanging_channels: node_hanging_channels.remove(&node_id).unwrap_or_default(),
+                })
+                .await
+                .to_rw_result_with(|| format!("failed to connect to {}", node_id))?;
+        }
+
+        for (node_id, actors) in &node_new_actors {
+            let node = nodes.get(node_id).unwrap();
+            let client = self.client_pool.get(node).await?;
+
+            let request_id = Uuid::new_v4().to_string();
+            let actors = actors.iter().map(|actor| actor.actor_id).collect_vec();
+            tracing::debug!(request_id = request_id.as_str(), actors = ?actors, "build actors");
+            client
+                .to_owned()
+                .build_actors(BuildActorsRequest {
+                    request_id,
+                    actor_id: actors,
+                })
+                .await
+                .to_rw_result_with(|| format!("failed to connect to {}", node_id))?;
+        }
+
+        // Add the new actors to meta store with state `Inactive`.
+        self.fragment_manager
+            .start_reschedule_fragment(&table_id, fragment_id, new_actors, new_actor_status)
+            .await?;
+        let mut removed_node_actors: HashMap<WorkerId, Vec<ActorId>> = HashMap::new();
+        for (actor_id, parallel_unit) in &removed_actors {
+            removed_node_actors
+                .entry(parallel_unit.worker_node_id)
+                .or_default()
+                .push(*actor_id);
+        }
+        let (original_indices, data) = compress_data(&vnode_mapping);
+        if let Err(err) = self
+            .barrier_manager
+            .run_command(Command::Reschedule {
+                table_id,
+                fragment_id,
+                removed_actors: removed_node_actors,
+                vnode_mapping: ParallelUnitMapping {
+                    table_id: prev_mapping.table_id,
+                    original_indices,
+                    data,
+                },
+                mutation,
+            })
+            .await
+        {
+            self.fragment_manager
+                .cancel_reschedule_fragment(&table_id, fragment_id, &new_actor_ids)
+                .await?;
+            return Err(err);
+        }
+
+        self.hash_mapping_manager
+            .set_fragment_hash_mapping(fragment_id, vnode_mapping);
+
+        Ok(())
+    }
+}
+
+fn invalid_reschedule(msg: impl Into<String>) -> RwError {
+    ErrorCode::InvalidParameterValue(msg.into()).into()
+}
+
+/// Returns the name of the first node in the tree that prevents the fragment from rescheduling.
+///
+/// Sources and top-n keep their states in the keyspaces of the executors, which can't be handed
+/// over to other actors. Chains and lookups rely on the placement of actors in other fragments.
+fn find_unreschedulable_node(stream_node: &StreamNode) -> Option<&'static str> {
+    let name = match stream_node.node_body.as_ref() {
+        Some(NodeBody::Source(_)) => Some("Source"),
+        Some(NodeBody::Chain(_)) => Some("Chain"),
+        Some(NodeBody::BatchPlan(_)) => Some("BatchPlan"),
+        Some(NodeBody::TopN(_)) => Some("TopN"),
+        Some(NodeBody::AppendOnlyTopN(_)) => Some("AppendOnlyTopN"),
+        Some(NodeBody::GroupTopN(_)) => Some("GroupTopN"),
+        Some(NodeBody::Lookup(_)) => Some("Lookup"),
+        Some(NodeBody::LookupUnion(_)) => Some("LookupUnion"),
+        Some(NodeBody::Arrange(_)) => Some("Arrange"),
+        Some(NodeBody::DeltaIndexJoin(_)) => Some("DeltaIndexJoin"),
+        _ => None,
+    };
+    name.or_else(|| stream_node.input.iter().find_map(find_unreschedulable_node))
+}
+
+/// Check whether the fragment can be rescheduled by adding the `added_parallel_units` and removing
+/// the `removed_parallel_units`.
+fn check_reschedulable(
+    table_fragments: &TableFragments,
+    fragment: &Fragment,
+    parallel_unit_actors: &HashMap<ParallelUnitId, ActorId>,
+    added_parallel_units: &[ParallelUnit],
+    removed_parallel_units: &[ParallelUnitId],
+) -> Result<()> {
+    if fragment.distribution_type != FragmentDistributionType::Hash as i32 {
+        return Err(invalid_reschedule(format!(
+            "fragment {} is not hash distributed",
+            fragment.fragment_id
+        )));
+    }
+    for actor in &fragment.actors {
+        if actor.same_worker_node_as_upstream {
+            return Err(invalid_reschedule(format!(
+                "actor {} must stay on the same node as its upstream",
+                actor.actor_id
+            )));
+        }
+        if let Some(name) = find_unreschedulable_node(actor.get_nodes()?) {
+            return Err(invalid_reschedule(format!(
+                "fragment {} with {} node can't be rescheduled",
+                fragment.fragment_id, name
+            )));
+        }
+    }
+
+    // Each vnode must be owned by an actor, so that its state can be handed over.
+    let vnode_mapping = fragment.get_vnode_mapping()?;
+    if vnode_mapping
+        .data
+        .iter()
+        .any(|id| !parallel_unit_actors.contains_key(id))
+    {
+        return Err(invalid_reschedule(format!(
+            "vnode mapping of fragment {} doesn't match its actors",
+            fragment.fragment_id
+        )));
+    }
+
+    let removed: HashSet<ParallelUnitId> = removed_parallel_units.iter().copied().collect();
+    let added: HashSet<ParallelUnitId> = added_parallel_units.iter().map(|p| p.id).collect();
+    if removed.len() != removed_parallel_units.len() || added.len() != added_parallel_units.len() {
+        return Err(invalid_reschedule("duplicated parallel units"));
+    }
+    if let Some(id) = removed
+        .iter()
+        .find(|id| !parallel_unit_actors.contains_key(id))
+    {
+        return Err(invalid_reschedule(format!(
+            "parallel unit {} is not used by fragment {}",
+            id, fragment.fragment_id
+        )));
+    }
+    if let Some(id) = added
+        .iter()
+        .find(|id| parallel_unit_actors.contains_key(id))
+    {
+        return Err(invalid_reschedule(format!(
+            "parallel unit {} is already used by fragment {}",
+            id, fragment.fragment_id
+        )));
+    }
+    // Merges with a single upstream are built as receivers, which can't change their upstreams.
+    let new_actor_count = fragment.actors.len() + added.len() - removed.len();
+    if fragment.actors.len() < 2 || new_actor_count < 2 {
+        return Err(invalid_reschedule(format!(
+            "fragment {} must have at least 2 actors before and after rescheduling",
+            fragment.fragment_id
+        )));
+    }
+
+    let fragment_actors: HashSet<ActorId> = fragment.actors.iter().map(|a| a.actor_id).collect();
+    let table_actors: HashSet<ActorId> = table_fragments.actor_ids().into_iter().collect();
+    for dispatcher in fragment.actors.iter().flat_map(|actor| &actor.dispatcher) {
+        if dispatcher.get_type()? == DispatcherType::NoShuffle {
+            return Err(invalid_reschedule(format!(
+                "fragment {} with no-shuffle dispatcher can't be rescheduled",
+                fragment.fragment_id
+            )));
+        }
+        if dispatcher
+            .downstream_actor_id
+            .iter()
+            .any(|id| !table_actors.contains(id))
+        {
+            return Err(invalid_reschedule(format!(
+                "fragment {} with dependent materialized views can't be rescheduled",
+                fragment.fragment_id
+            )));
+        }
+    }
+    for other_fragment in table_fragments.fragments.values() {
+        if other_fragment.fragment_id == fragment.fragment_id {
+            continue;
+        }
+        for actor in &other_fragment.actors {
+            for dispatcher in &actor.dispatcher {
+                if dispatcher
+                    .downstream_actor_id
+                    .iter()
+                    .any(|id| fragment_actors.contains(id))
+                    && !matches!(
+                        dispatcher.get_type()?,
+                        DispatcherType::Hash | DispatcherType::Broadcast
+                    )
+                {
+                    return Err(invalid_reschedule(format!(
+                        "fragment {} must be dispatched by hash or broadcast",
+                        fragment.fragment_id
+                    )));
+                }
+            }
+            let merge_count = count_merges_from(actor.get_nodes()?, &fragment_actors);
+            if merge_count > 1 {
+                return Err(invalid_reschedule(format!(
+                    "actor {} has {} merges from fragment {}",
+                    actor.actor_id, merge_count, fragment.fragment_id
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the number of merge nodes receiving from the `actors`.
+fn count_merges_from(stream_node: &StreamNode, actors: &HashSet<ActorId>) -> usize {
+    let count = match stream_node.node_body.as_ref() {
+        Some(NodeBody::Merge(merge))
+            if merge.upstream_actor_id.iter().any(|id| actors.contains(id)) =>
+        {
+            1
+        }
+        _ => 0,
+    };
+    count
+        + stream_node
+            .input
+            .iter()
+            .map(|input| count_merges_from(input, actors))
+            .sum::<usize>()
 }
 
 #[cfg(test)]
@@ -715,6 +1298,7 @@ mod tests {
         actor_streams: Mutex<HashMap<ActorId, StreamActor>>,
         actor_ids: Mutex<HashSet<ActorId>>,
         actor_infos: Mutex<HashMap<ActorId, HostAddress>>,
+        dropped_actor_ids: Mutex<HashSet<ActorId>>,
     }
 
     struct FakeStreamService {
@@ -772,9 +1356,13 @@ mod tests {
 
         async fn drop_actors(
             &self,
-            _request: Request<DropActorsRequest>,
+            request: Request<DropActorsRequest>,
         ) -> std::result::Result<Response<DropActorsResponse>, Status> {
-            panic!("not implemented")
+            let req = request.into_inner();
+            let mut guard = self.inner.dropped_actor_ids.lock().unwrap();
+            guard.extend(req.get_actor_ids());
+
+            Ok(Response::new(DropActorsResponse::default()))
         }
 
         async fn inject_barrier(
@@ -798,6 +1386,13 @@ mod tests {
             unimplemented!()
         }
 
+        async fn alter_source(
+            &self,
+            _request: Request<AlterSourceRequest>,
+        ) -> std::result::Result<Response<AlterSourceResponse>, Status> {
+            unimplemented!()
+        }
+
         async fn force_stop_actors(
             &self,
             _request: Request<ForceStopActorsRequest>,
@@ -816,33 +1411,46 @@ mod tests {
     struct MockServices {
         global_stream_manager: GlobalStreamManager<MemStore>,
         fragment_manager: FragmentManagerRef<MemStore>,
+        cluster_manager: ClusterManagerRef<MemStore>,
+        hash_mapping_manager: HashMappingManagerRef,
         state: Arc<FakeFragmentState>,
         join_handles: Vec<JoinHandle<()>>,
         shutdown_txs: Vec<Sender<()>>,
     }
 
+    /// Starts a fake compute node serving on `host:port`.
+    fn start_fake_compute_node(
+        host: &str,
+        port: u16,
+    ) -> (Arc<FakeFragmentState>, JoinHandle<()>, Sender<()>) {
+        let addr = SocketAddr::new(host.parse().unwrap(), port);
+        let state = Arc::new(FakeFragmentState {
+            actor_streams: Mutex::new(HashMap::new()),
+            actor_ids: Mutex::new(HashSet::new()),
+            actor_infos: Mutex::new(HashMap::new()),
+            dropped_actor_ids: Mutex::new(HashSet::new()),
+        });
+        let fake_service = FakeStreamService {
+            inner: state.clone(),
+        };
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let stream_srv = StreamServiceServer::new(fake_service);
+        let join_handle = tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(stream_srv)
+                .serve_with_shutdown(addr, async move { shutdown_rx.await.unwrap() })
+                .await
+                .unwrap();
+        });
+        sleep(Duration::from_secs(1));
+
+        (state, join_handle, shutdown_tx)
+    }
+
     impl MockServices {
         async fn start(host: &str, port: u16) -> Result<Self> {
-            let addr = SocketAddr::new(host.parse().unwrap(), port);
-            let state = Arc::new(FakeFragmentState {
-                actor_streams: Mutex::new(HashMap::new()),
-                actor_ids: Mutex::new(HashSet::new()),
-                actor_infos: Mutex::new(HashMap::new()),
-            });
-            let fake_service = FakeStreamService {
-                inner: state.clone(),
-            };
-
-            let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
-            let stream_srv = StreamServiceServer::new(fake_service);
-            let join_handle = tokio::spawn(async move {
-                tonic::transport::Server::builder()
-                    .add_service(stream_srv)
-                    .serve_with_shutdown(addr, async move { shutdown_rx.await.unwrap() })
-                    .await
-                    .unwrap();
-            });
-            sleep(Duration::from_secs(1));
+            let (state, join_handle, shutdown_tx) = start_fake_compute_node(host, port);
 
             let env = MetaSrvEnv::for_test().await;
             let cluster_manager =
@@ -896,12 +1504,37 @@ mod tests {
             Ok(Self {
                 global_stream_manager: stream_manager,
                 fragment_manager,
+                cluster_manager,
+                hash_mapping_manager: env.hash_mapping_manager_ref(),
                 state,
                 join_handles: vec![join_handle_2, join_handle],
                 shutdown_txs: vec![shutdown_tx_2, shutdown_tx],
             })
         }
 
+        /// Starts another fake compute node and adds it to the cluster.
+        async fn add_compute_node(
+            &mut self,
+            host: &str,
+            port: u16,
+        ) -> Result<(WorkerNode, Arc<FakeFragmentState>)> {
+            let (state, join_handle, shutdown_tx) = start_fake_compute_node(host, port);
+            self.join_handles.push(join_handle);
+            self.shutdown_txs.push(shutdown_tx);
+
+            let host = HostAddress {
+                host: host.to_string(),
+                port: port as i32,
+            };
+            let (worker_node, _) = self
+                .cluster_manager
+                .add_worker_node(host.clone(), WorkerType::ComputeNode)
+                .await?;
+            self.cluster_manager.activate_worker_node(host).await?;
+
+            Ok((worker_node, state))
+        }
+
         async fn stop(self) {
             for shutdown_tx in self.shutdown_txs {
                 shutdown_tx.send(()).unwrap();
@@ -1010,4 +1643,138 @@ mod tests {
         services.stop().await;
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reschedule_fragment() -> Result<()> {
+        let mut services = MockServices::start("127.0.0.1", 12334).await?;
+
+        let table_ref_id = TableRefId {
+            schema_ref_id: None,
+            table_id: 0,
+        };
+        let table_id = TableId::from(&Some(table_ref_id.clone()));
+
+        // Each compute node has 3 hash parallel units, so the 3 actors are placed on the first one.
+        let actors = (0..3)
+            .map(|i| StreamActor {
+                actor_id: i,
+                nodes: Some(risingwave_pb::stream_plan::StreamNode {
+                    node_body: Some(
+                        risingwave_pb::stream_plan::stream_node::NodeBody::Materialize(
+                            risingwave_pb::stream_plan::MaterializeNode {
+                                table_ref_id: Some(table_ref_id.clone()),
+                                ..Default::default()
+                            },
+                        ),
+                    ),
+                    operator_id: 1,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let mut fragments = BTreeMap::default();
+        fragments.insert(
+            0,
+            Fragment {
+                fragment_id: 0,
+                fragment_type: FragmentType::Sink as i32,
+                distribution_type: FragmentDistributionType::Hash as i32,
+                actors: actors.clone(),
+                vnode_mapping: None,
+            },
+        );
+        let table_fragments = TableFragments::new(table_id, fragments);
+
+        services
+            .global_stream_manager
+            .create_materialized_view(table_fragments, CreateMaterializedViewContext::default())
+            .await?;
+
+        let (worker_node, new_state) = services.add_compute_node("127.0.0.1", 12335).await?;
+        let added_parallel_unit = services
+            .cluster_manager
+            .list_parallel_units(Some(ParallelUnitType::Hash))
+            .await
+            .into_iter()
+            .find(|parallel_unit| parallel_unit.worker_node_id == worker_node.id)
+            .unwrap();
+        let table_fragments = services
+            .fragment_manager
+            .get_table_fragments_of_fragment(0)
+            .await?;
+        let removed_parallel_unit = table_fragments.actor_status()[&2]
+            .get_parallel_unit()
+            .unwrap()
+            .clone();
+        let prev_vnode_mapping = services
+            .hash_mapping_manager
+            .get_fragment_hash_mapping(&0)
+            .unwrap();
+
+        // Move the actor 2 to the new compute node.
+        services
+            .global_stream_manager
+            .reschedule_fragment(0, &[added_parallel_unit.id], &[removed_parallel_unit.id])
+            .await?;
+
+        let new_actor_ids = new_state
+            .actor_streams
+            .lock()
+            .unwrap()
+            .keys()
+            .copied()
+            .collect_vec();
+        assert_eq!(new_actor_ids.len(), 1);
+        let new_actor_id = new_actor_ids[0];
+        assert!(!(0..3).contains(&new_actor_id));
+        assert!(new_state.actor_ids.lock().unwrap().contains(&new_actor_id));
+        assert_eq!(
+            services
+                .state
+                .dropped_actor_ids
+                .lock()
+                .unwrap()
+                .iter()
+                .copied()
+                .collect_vec(),
+            vec![2]
+        );
+
+        let actor_ids = services
+            .fragment_manager
+            .get_table_actor_ids(&table_id)
+            .await?;
+        assert_eq!(actor_ids, vec![0, 1, new_actor_id]);
+        let table_fragments = services
+            .fragment_manager
+            .get_table_fragments_of_fragment(0)
+            .await?;
+        assert_eq!(
+            table_fragments.actor_status()[&new_actor_id].get_state(),
+            Ok(ActorState::Running)
+        );
+
+        // The vnodes of the removed parallel unit are all moved to the added one.
+        let vnode_mapping = services
+            .hash_mapping_manager
+            .get_fragment_hash_mapping(&0)
+            .unwrap();
+        for (prev, curr) in prev_vnode_mapping.iter().zip_eq(&vnode_mapping) {
+            if *prev == removed_parallel_unit.id {
+                assert_eq!(*curr, added_parallel_unit.id);
+            } else {
+                assert_eq!(prev, curr);
+            }
+        }
+        let mapping = table_fragments.fragments[&0].get_vnode_mapping().unwrap();
+        assert_eq!(
+            decompress_data(&mapping.original_indices, &mapping.data),
+            vnode_mapping
+        );
+
+        services.stop().await;
+        Ok(())
+    }
 }
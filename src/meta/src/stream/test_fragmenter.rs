@@ -113,6 +113,7 @@ fn make_stream_node() -> StreamNode {
             column_ids: vec![1, 2, 0],
             source_type: SourceType::Table as i32,
             stream_source_state: None,
+            watermark_descs: vec![],
         })),
         pk_indices: vec![2],
         ..Default::default()
@@ -168,6 +169,7 @@ fn make_stream_node() -> StreamNode {
             distribution_keys: Default::default(),
             table_ids: vec![],
             append_only: false,
+            distinct_dedup_table_ids: Default::default(),
         })),
         input: vec![filter_node],
         fields: vec![], // TODO: fill this later
@@ -200,6 +202,7 @@ fn make_stream_node() -> StreamNode {
             distribution_keys: Default::default(),
             table_ids: vec![],
             append_only: false,
+            distinct_dedup_table_ids: Default::default(),
         })),
         fields: vec![], // TODO: fill this later
         input: vec![exchange_node_1],
@@ -227,6 +230,7 @@ fn make_stream_node() -> StreamNode {
                 make_inputref(0),
                 make_inputref(1),
             ],
+            ..Default::default()
         })),
         fields: vec![], // TODO: fill this later
         input: vec![simple_agg_node_1],
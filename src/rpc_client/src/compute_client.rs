@@ -26,8 +26,8 @@ use risingwave_pb::batch_plan::{ExchangeInfo, PlanFragment, PlanNode, TaskId, Ta
 use risingwave_pb::task_service::exchange_service_client::ExchangeServiceClient;
 use risingwave_pb::task_service::task_service_client::TaskServiceClient;
 use risingwave_pb::task_service::{
-    CreateTaskRequest, CreateTaskResponse, GetDataRequest, GetDataResponse, GetStreamRequest,
-    GetStreamResponse,
+    AbortTaskRequest, CreateTaskRequest, CreateTaskResponse, GetDataRequest, GetDataResponse,
+    GetStreamRequest, GetStreamResponse, GetTaskInfoRequest, TaskInfo,
 };
 use tonic::transport::{Channel, Endpoint};
 use tonic::Streaming;
@@ -113,22 +113,27 @@ impl ComputeClient {
                 task_id: Some(task_id),
                 plan: Some(plan),
                 epoch,
+                collect_stats: false,
             })
             .await?;
         Ok(())
     }
 
+    /// Creates a task running `plan`. If `collect_stats` is set, the runtime statistics of its
+    /// executors can be fetched by [`ComputeClient::get_task_info`].
     pub async fn create_task2(
         &self,
         task_id: TaskId,
         plan: PlanFragment,
         epoch: u64,
+        collect_stats: bool,
     ) -> Result<()> {
         let _ = self
             .create_task_inner(CreateTaskRequest {
                 task_id: Some(task_id),
                 plan: Some(plan),
                 epoch,
+                collect_stats,
             })
             .await?;
         Ok(())
@@ -143,6 +148,31 @@ impl ComputeClient {
             .to_rw_result()?
             .into_inner())
     }
+
+    pub async fn get_task_info(&self, task_id: TaskId) -> Result<TaskInfo> {
+        let resp = self
+            .task_client
+            .to_owned()
+            .get_task_info(GetTaskInfoRequest {
+                task_id: Some(task_id),
+            })
+            .await
+            .to_rw_result()?
+            .into_inner();
+        Ok(resp.task_info.unwrap_or_default())
+    }
+
+    pub async fn abort_task(&self, task_id: TaskId) -> Result<()> {
+        let _ = self
+            .task_client
+            .to_owned()
+            .abort_task(AbortTaskRequest {
+                task_id: Some(task_id),
+            })
+            .await
+            .to_rw_result()?;
+        Ok(())
+    }
 }
 
 /// Each ExchangeSource maps to one task, it takes the execution result from task chunk by chunk.
@@ -23,17 +23,21 @@ use risingwave_common::try_match_expand;
 use risingwave_common::util::addr::HostAddr;
 use risingwave_hummock_sdk::{HummockEpoch, HummockSSTableId, HummockVersionId};
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
+    Database as ProstDatabase, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
+    Table as ProstTable,
 };
 use risingwave_pb::common::{WorkerNode, WorkerType};
+use risingwave_pb::ddl_service::alter_source_request::Change;
 use risingwave_pb::ddl_service::ddl_service_client::DdlServiceClient;
 use risingwave_pb::ddl_service::{
-    CreateDatabaseRequest, CreateDatabaseResponse, CreateMaterializedSourceRequest,
-    CreateMaterializedSourceResponse, CreateMaterializedViewRequest,
-    CreateMaterializedViewResponse, CreateSchemaRequest, CreateSchemaResponse, CreateSourceRequest,
+    AlterSourceRequest, AlterSourceResponse, CreateDatabaseRequest, CreateDatabaseResponse,
+    CreateMaterializedSourceRequest, CreateMaterializedSourceResponse,
+    CreateMaterializedViewRequest, CreateMaterializedViewResponse, CreateSchemaRequest,
+    CreateSchemaResponse, CreateSinkRequest, CreateSinkResponse, CreateSourceRequest,
     CreateSourceResponse, DropDatabaseRequest, DropDatabaseResponse, DropMaterializedSourceRequest,
     DropMaterializedSourceResponse, DropMaterializedViewRequest, DropMaterializedViewResponse,
-    DropSchemaRequest, DropSchemaResponse, DropSourceRequest, DropSourceResponse,
+    DropSchemaRequest, DropSchemaResponse, DropSinkRequest, DropSinkResponse, DropSourceRequest,
+    DropSourceResponse, GetCatalogRequest, GetCatalogResponse,
 };
 use risingwave_pb::hummock::hummock_manager_service_client::HummockManagerServiceClient;
 use risingwave_pb::hummock::{
@@ -52,9 +56,17 @@ use risingwave_pb::meta::{
     ActivateWorkerNodeRequest, ActivateWorkerNodeResponse, AddWorkerNodeRequest,
     AddWorkerNodeResponse, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse, FlushRequest,
     FlushResponse, HeartbeatRequest, HeartbeatResponse, ListAllNodesRequest, ListAllNodesResponse,
-    SubscribeRequest, SubscribeResponse,
+    ListTableFragmentsRequest, ListTableFragmentsResponse, RescheduleRequest, RescheduleResponse,
+    SubscribeRequest, SubscribeResponse, TableFragments,
 };
 use risingwave_pb::stream_plan::StreamFragmentGraph;
+use risingwave_pb::user::update_user_request::UpdateField;
+use risingwave_pb::user::user_service_client::UserServiceClient;
+use risingwave_pb::user::{
+    CreateUserRequest, CreateUserResponse, DropUserRequest, DropUserResponse, GrantPrivilege,
+    GrantPrivilegeRequest, GrantPrivilegeResponse, RevokePrivilegeRequest, RevokePrivilegeResponse,
+    UpdateUserRequest, UpdateUserResponse, UserInfo,
+};
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::oneshot::Sender;
 use tokio::task::JoinHandle;
@@ -213,12 +225,47 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    pub async fn create_sink(
+        &self,
+        sink: ProstSink,
+        graph: StreamFragmentGraph,
+    ) -> Result<(u32, CatalogVersion)> {
+        let request = CreateSinkRequest {
+            sink: Some(sink),
+            fragment_graph: Some(graph),
+        };
+
+        let resp = self.inner.create_sink(request).await?;
+        Ok((resp.sink_id, resp.version))
+    }
+
+    pub async fn drop_sink(&self, sink_id: u32) -> Result<CatalogVersion> {
+        let request = DropSinkRequest { sink_id };
+        let resp = self.inner.drop_sink(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn drop_source(&self, source_id: u32) -> Result<CatalogVersion> {
         let request = DropSourceRequest { source_id };
         let resp = self.inner.drop_source(request).await?;
         Ok(resp.version)
     }
 
+    pub async fn alter_source(
+        &self,
+        source_id: u32,
+        version: u64,
+        change: Change,
+    ) -> Result<CatalogVersion> {
+        let request = AlterSourceRequest {
+            source_id,
+            version,
+            change: Some(change),
+        };
+        let resp = self.inner.alter_source(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn drop_database(&self, database_id: u32) -> Result<CatalogVersion> {
         let request = DropDatabaseRequest { database_id };
         let resp = self.inner.drop_database(request).await?;
@@ -231,6 +278,59 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    pub async fn create_user(&self, user: UserInfo) -> Result<CatalogVersion> {
+        let request = CreateUserRequest { user: Some(user) };
+        let resp = self.inner.create_user(request).await?;
+        Ok(resp.version)
+    }
+
+    pub async fn update_user(
+        &self,
+        user: UserInfo,
+        update_fields: Vec<UpdateField>,
+    ) -> Result<CatalogVersion> {
+        let request = UpdateUserRequest {
+            user: Some(user),
+            update_fields: update_fields.into_iter().map(|f| f as i32).collect(),
+        };
+        let resp = self.inner.update_user(request).await?;
+        Ok(resp.version)
+    }
+
+    pub async fn drop_user(&self, user_id: u32) -> Result<CatalogVersion> {
+        let request = DropUserRequest { user_id };
+        let resp = self.inner.drop_user(request).await?;
+        Ok(resp.version)
+    }
+
+    pub async fn grant_privilege(
+        &self,
+        user_ids: Vec<u32>,
+        privileges: Vec<GrantPrivilege>,
+    ) -> Result<CatalogVersion> {
+        let request = GrantPrivilegeRequest {
+            user_ids,
+            privileges,
+        };
+        let resp = self.inner.grant_privilege(request).await?;
+        Ok(resp.version)
+    }
+
+    pub async fn revoke_privilege(
+        &self,
+        user_ids: Vec<u32>,
+        privileges: Vec<GrantPrivilege>,
+        revoke_grant_option: bool,
+    ) -> Result<CatalogVersion> {
+        let request = RevokePrivilegeRequest {
+            user_ids,
+            privileges,
+            revoke_grant_option,
+        };
+        let resp = self.inner.revoke_privilege(request).await?;
+        Ok(resp.version)
+    }
+
     /// Unregister the current node to the cluster.
     pub async fn unregister(&self, addr: HostAddr) -> Result<()> {
         let request = DeleteWorkerNodeRequest {
@@ -306,6 +406,37 @@ impl MetaClient {
         self.inner.flush(request).await?;
         Ok(())
     }
+
+    /// Reschedule the actors of the fragment by adding and removing parallel units.
+    pub async fn reschedule(
+        &self,
+        fragment_id: u32,
+        added_parallel_units: Vec<u32>,
+        removed_parallel_units: Vec<u32>,
+    ) -> Result<()> {
+        let request = RescheduleRequest {
+            fragment_id,
+            added_parallel_units,
+            removed_parallel_units,
+        };
+        self.inner.reschedule(request).await?;
+        Ok(())
+    }
+
+    /// List the fragments of the given tables, or of all tables if `table_ids` is empty.
+    pub async fn list_table_fragments(&self, table_ids: &[u32]) -> Result<Vec<TableFragments>> {
+        let request = ListTableFragmentsRequest {
+            table_ids: table_ids.to_vec(),
+        };
+        let resp = self.inner.list_table_fragments(request).await?;
+        Ok(resp.table_fragments)
+    }
+
+    /// Get a snapshot of all catalog objects.
+    pub async fn get_catalog(&self) -> Result<GetCatalogResponse> {
+        let request = GetCatalogRequest {};
+        self.inner.get_catalog(request).await
+    }
 }
 
 #[async_trait]
@@ -393,6 +524,7 @@ pub struct GrpcMetaClient {
     pub hummock_client: HummockManagerServiceClient<Channel>,
     pub notification_client: NotificationServiceClient<Channel>,
     pub stream_client: StreamManagerServiceClient<Channel>,
+    pub user_client: UserServiceClient<Channel>,
 }
 
 impl GrpcMetaClient {
@@ -409,7 +541,8 @@ impl GrpcMetaClient {
         let ddl_client = DdlServiceClient::new(channel.clone());
         let hummock_client = HummockManagerServiceClient::new(channel.clone());
         let notification_client = NotificationServiceClient::new(channel.clone());
-        let stream_client = StreamManagerServiceClient::new(channel);
+        let stream_client = StreamManagerServiceClient::new(channel.clone());
+        let user_client = UserServiceClient::new(channel);
         Ok(Self {
             cluster_client,
             heartbeat_client,
@@ -417,6 +550,7 @@ impl GrpcMetaClient {
             hummock_client,
             notification_client,
             stream_client,
+            user_client,
         })
     }
 }
@@ -449,16 +583,22 @@ macro_rules! for_all_meta_rpc {
             ,{ cluster_client, list_all_nodes, ListAllNodesRequest, ListAllNodesResponse }
             ,{ heartbeat_client, heartbeat, HeartbeatRequest, HeartbeatResponse }
             ,{ stream_client, flush, FlushRequest, FlushResponse }
+            ,{ stream_client, reschedule, RescheduleRequest, RescheduleResponse }
+            ,{ stream_client, list_table_fragments, ListTableFragmentsRequest, ListTableFragmentsResponse }
             ,{ ddl_client, create_materialized_source, CreateMaterializedSourceRequest, CreateMaterializedSourceResponse }
             ,{ ddl_client, create_materialized_view, CreateMaterializedViewRequest, CreateMaterializedViewResponse }
             ,{ ddl_client, create_source, CreateSourceRequest, CreateSourceResponse }
+            ,{ ddl_client, create_sink, CreateSinkRequest, CreateSinkResponse }
             ,{ ddl_client, create_schema, CreateSchemaRequest, CreateSchemaResponse }
             ,{ ddl_client, create_database, CreateDatabaseRequest, CreateDatabaseResponse }
             ,{ ddl_client, drop_materialized_source, DropMaterializedSourceRequest, DropMaterializedSourceResponse }
             ,{ ddl_client, drop_materialized_view, DropMaterializedViewRequest, DropMaterializedViewResponse }
             ,{ ddl_client, drop_source, DropSourceRequest, DropSourceResponse }
+            ,{ ddl_client, alter_source, AlterSourceRequest, AlterSourceResponse }
+            ,{ ddl_client, drop_sink, DropSinkRequest, DropSinkResponse }
             ,{ ddl_client, drop_database, DropDatabaseRequest, DropDatabaseResponse }
             ,{ ddl_client, drop_schema, DropSchemaRequest, DropSchemaResponse }
+            ,{ ddl_client, get_catalog, GetCatalogRequest, GetCatalogResponse }
             ,{ hummock_client, pin_version, PinVersionRequest, PinVersionResponse }
             ,{ hummock_client, unpin_version, UnpinVersionRequest, UnpinVersionResponse }
             ,{ hummock_client, pin_snapshot, PinSnapshotRequest, PinSnapshotResponse }
@@ -467,6 +607,11 @@ macro_rules! for_all_meta_rpc {
             ,{ hummock_client, get_new_table_id, GetNewTableIdRequest, GetNewTableIdResponse }
             ,{ hummock_client, subscribe_compact_tasks, SubscribeCompactTasksRequest, Streaming<SubscribeCompactTasksResponse> }
             ,{ hummock_client, report_vacuum_task, ReportVacuumTaskRequest, ReportVacuumTaskResponse }
+            ,{ user_client, create_user, CreateUserRequest, CreateUserResponse }
+            ,{ user_client, update_user, UpdateUserRequest, UpdateUserResponse }
+            ,{ user_client, drop_user, DropUserRequest, DropUserResponse }
+            ,{ user_client, grant_privilege, GrantPrivilegeRequest, GrantPrivilegeResponse }
+            ,{ user_client, revoke_privilege, RevokePrivilegeRequest, RevokePrivilegeResponse }
         }
     };
 }
@@ -40,6 +40,13 @@ pub trait SourceManager: Debug + Sync + Send {
     async fn create_source(&self, table_id: &TableId, info: StreamSourceInfo) -> Result<()>;
     fn create_table_source(&self, table_id: &TableId, columns: Vec<ColumnDesc>) -> Result<()>;
 
+    /// Updates the columns of a created source. The running streaming tasks reading the source
+    /// are not affected.
+    async fn alter_source(&self, source_id: &TableId, info: StreamSourceInfo) -> Result<()>;
+    /// Updates the columns of a created table source, which can only append new columns to the
+    /// existing ones.
+    fn alter_table_source(&self, table_id: &TableId, columns: Vec<ColumnDesc>) -> Result<()>;
+
     fn get_source(&self, source_id: &TableId) -> Result<SourceDesc>;
     fn drop_source(&self, source_id: &TableId) -> Result<()>;
 
@@ -108,68 +115,7 @@ pub struct MemSourceManager {
 #[async_trait]
 impl SourceManager for MemSourceManager {
     async fn create_source(&self, source_id: &TableId, info: StreamSourceInfo) -> Result<()> {
-        let format = match info.get_row_format()? {
-            RowFormatType::Json => SourceFormat::Json,
-            RowFormatType::Protobuf => SourceFormat::Protobuf,
-            RowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
-            RowFormatType::Avro => SourceFormat::Avro,
-        };
-
-        if format == SourceFormat::Protobuf && info.row_schema_location.is_empty() {
-            return Err(RwError::from(ProtocolError(
-                "protobuf file location not provided".to_string(),
-            )));
-        }
-        let source_parser_rs =
-            SourceParserImpl::create(&format, &info.properties, info.row_schema_location.as_str())
-                .await;
-        let parser = if let Ok(source_parser) = source_parser_rs {
-            source_parser
-        } else {
-            return Err(source_parser_rs.err().unwrap());
-        };
-
-        let columns = info
-            .columns
-            .iter()
-            .enumerate()
-            .map(|(idx, c)| {
-                let c = c.column_desc.as_ref().unwrap().clone();
-                SourceColumnDesc {
-                    name: c.name.clone(),
-                    data_type: DataType::from(&c.column_type.unwrap()),
-                    column_id: ColumnId::from(c.column_id),
-                    skip_parse: idx as i32 == info.row_id_index,
-                }
-            })
-            .collect::<Vec<SourceColumnDesc>>();
-
-        assert!(
-            info.row_id_index >= 0,
-            "expected row_id_index >= 0, got {}",
-            info.row_id_index
-        );
-        let row_id_index = info.row_id_index as usize;
-
-        let config = ConnectorProperties::extract(info.properties)
-            .map_err(|e| RwError::from(ConnectorError(e.to_string())))?;
-
-        let source = SourceImpl::Connector(ConnectorSource {
-            config,
-            columns: columns.clone(),
-            parser,
-        });
-
-        let desc = SourceDesc {
-            source: Arc::new(source),
-            format,
-            columns,
-            row_id_index,
-            row_id_generator: Arc::new(Mutex::new(RowIdGenerator::with_epoch(
-                self.worker_id,
-                *UNIX_SINGULARITY_DATE_EPOCH,
-            ))),
-        };
+        let desc = self.new_source_desc(info).await?;
 
         let mut tables = self.get_sources()?;
         ensure!(
@@ -210,6 +156,56 @@ impl SourceManager for MemSourceManager {
         Ok(())
     }
 
+    async fn alter_source(&self, source_id: &TableId, info: StreamSourceInfo) -> Result<()> {
+        let mut desc = self.new_source_desc(info).await?;
+
+        let mut sources = self.get_sources()?;
+        let old_desc = sources.get_mut(source_id).ok_or_else(|| {
+            RwError::from(InternalError(format!(
+                "Source does not exist: {:?}",
+                source_id
+            )))
+        })?;
+        // Keep generating the row ids from where the source is.
+        desc.row_id_generator = old_desc.row_id_generator.clone();
+        *old_desc = desc;
+        Ok(())
+    }
+
+    fn alter_table_source(&self, table_id: &TableId, columns: Vec<ColumnDesc>) -> Result<()> {
+        let mut sources = self.get_sources()?;
+        let desc = sources.get_mut(table_id).ok_or_else(|| {
+            RwError::from(InternalError(format!(
+                "Source does not exist: {:?}",
+                table_id
+            )))
+        })?;
+        let table_source = desc.source.as_table_v2().ok_or_else(|| {
+            RwError::from(InternalError(format!(
+                "Source is not a table source: {:?}",
+                table_id
+            )))
+        })?;
+
+        // The columns are appended by `ALTER TABLE ADD COLUMN`, and truncated if meta fails to add
+        // them to the materialized table.
+        ensure!(
+            columns
+                .iter()
+                .zip(&desc.columns)
+                .all(|(new, old)| new.column_id == old.column_id),
+            "Columns of table source can only be appended or truncated: {:?}",
+            table_id
+        );
+        if columns.len() >= desc.columns.len() {
+            table_source.add_columns(columns[desc.columns.len()..].to_vec());
+        } else {
+            table_source.truncate_columns(columns.len());
+        }
+        desc.columns = columns.iter().map(SourceColumnDesc::from).collect();
+        Ok(())
+    }
+
     fn get_source(&self, table_id: &TableId) -> Result<SourceDesc> {
         let sources = self.get_sources()?;
         sources.get(table_id).cloned().ok_or_else(|| {
@@ -246,6 +242,74 @@ impl MemSourceManager {
     fn get_sources(&self) -> Result<MutexGuard<HashMap<TableId, SourceDesc>>> {
         Ok(self.sources.lock())
     }
+
+    /// Creates the description of a source with the info, which has its own row id generator.
+    async fn new_source_desc(&self, info: StreamSourceInfo) -> Result<SourceDesc> {
+        let format = match info.get_row_format()? {
+            RowFormatType::Json => SourceFormat::Json,
+            RowFormatType::Protobuf => SourceFormat::Protobuf,
+            RowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
+            RowFormatType::Avro => SourceFormat::Avro,
+        };
+
+        if format == SourceFormat::Protobuf && info.row_schema_location.is_empty() {
+            return Err(RwError::from(ProtocolError(
+                "protobuf file location not provided".to_string(),
+            )));
+        }
+        let source_parser_rs =
+            SourceParserImpl::create(&format, &info.properties, info.row_schema_location.as_str())
+                .await;
+        let parser = if let Ok(source_parser) = source_parser_rs {
+            source_parser
+        } else {
+            return Err(source_parser_rs.err().unwrap());
+        };
+
+        let columns = info
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(idx, c)| {
+                let c = c.column_desc.as_ref().unwrap().clone();
+                SourceColumnDesc {
+                    name: c.name.clone(),
+                    data_type: DataType::from(&c.column_type.unwrap()),
+                    column_id: ColumnId::from(c.column_id),
+                    skip_parse: idx as i32 == info.row_id_index,
+                }
+            })
+            .collect::<Vec<SourceColumnDesc>>();
+
+        assert!(
+            info.row_id_index >= 0,
+            "expected row_id_index >= 0, got {}",
+            info.row_id_index
+        );
+        let row_id_index = info.row_id_index as usize;
+
+        let config = ConnectorProperties::extract(info.properties)
+            .map_err(|e| RwError::from(ConnectorError(e.to_string())))?;
+
+        let source = SourceImpl::Connector(ConnectorSource {
+            config,
+            columns: columns.clone(),
+            parser,
+        });
+
+        let desc = SourceDesc {
+            source: Arc::new(source),
+            format,
+            columns,
+            row_id_index,
+            row_id_generator: Arc::new(Mutex::new(RowIdGenerator::with_epoch(
+                self.worker_id,
+                *UNIX_SINGULARITY_DATE_EPOCH,
+            ))),
+        };
+
+        Ok(desc)
+    }
 }
 
 #[cfg(test)]
@@ -282,6 +346,7 @@ mod tests {
             row_id_index: 0,
             pk_column_ids: vec![0],
             columns,
+            watermark_descs: vec![],
         };
         let source_id = TableId::default();
 
@@ -299,8 +364,8 @@ mod tests {
 
         let schema = Schema {
             fields: vec![
-                Field::unnamed(DataType::Decimal),
-                Field::unnamed(DataType::Decimal),
+                Field::unnamed(DataType::Decimal(None)),
+                Field::unnamed(DataType::Decimal(None)),
             ],
         };
 
@@ -314,6 +379,7 @@ mod tests {
                 name: f.name.clone(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             })
             .collect();
 
@@ -335,4 +401,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_alter_table_source() -> Result<()> {
+        let table_id = TableId::default();
+        let columns = vec![
+            ColumnDesc::unnamed(ColumnId::from(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::from(1), DataType::Int32),
+        ];
+
+        let mem_source_manager = MemSourceManager::default();
+        mem_source_manager.create_table_source(&table_id, columns[..1].to_vec())?;
+
+        // Append a column.
+        mem_source_manager.alter_table_source(&table_id, columns.clone())?;
+        let source_desc = mem_source_manager.get_source(&table_id)?;
+        assert_eq!(source_desc.columns.len(), 2);
+        assert_eq!(source_desc.columns[1].column_id, ColumnId::from(1));
+
+        // Truncate the appended column.
+        mem_source_manager.alter_table_source(&table_id, columns[..1].to_vec())?;
+        let source_desc = mem_source_manager.get_source(&table_id)?;
+        assert_eq!(source_desc.columns.len(), 1);
+
+        // The existing columns can't be replaced.
+        assert!(mem_source_manager
+            .alter_table_source(&table_id, columns[1..].to_vec())
+            .is_err());
+
+        Ok(())
+    }
 }
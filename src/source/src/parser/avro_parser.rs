@@ -117,6 +117,8 @@ macro_rules! from_avro_primitive {
 ///  - float: f32
 ///  - double: f64
 ///  - string: String
+///  - bytes: Bytea
+///  - uuid (or a string holding a uuid): Uuid
 ///  - Date (the number of days from the unix epoch, 1970-1-1 UTC)
 ///  - Timestamp (the number of milliseconds from the unix epoch,  1970-1-1 00:00:00.000 UTC)
 pub(crate) fn from_avro_value(column: &SourceColumnDesc, field_value: Value) -> Result<ScalarImpl> {
@@ -140,7 +142,7 @@ pub(crate) fn from_avro_value(column: &SourceColumnDesc, field_value: Value) ->
                 d.into()
             )))
         }
-        DataType::Decimal => {
+        DataType::Decimal(_) => {
             from_avro_primitive!(field_value, Double, |d: f64| {
                 let decimal = Decimal::from_f64(d);
                 match decimal {
@@ -154,6 +156,18 @@ pub(crate) fn from_avro_value(column: &SourceColumnDesc, field_value: Value) ->
         DataType::Varchar => {
             from_avro_primitive!(field_value, String, |s: String| Ok(ScalarImpl::Utf8(s)))
         }
+        DataType::Bytea => {
+            from_avro_primitive!(field_value, Bytes, |b: Vec<u8>| Ok(ScalarImpl::Bytea(
+                b.into()
+            )))
+        }
+        DataType::Uuid => match field_value {
+            Value::Uuid(v) => Ok(ScalarImpl::Uuid(v.to_string().parse()?)),
+            Value::String(s) => Ok(ScalarImpl::Uuid(s.parse()?)),
+            _ => Err(RwError::from(InternalError(
+                "avro parse error.type incompatible".to_string(),
+            ))),
+        },
         DataType::Date => {
             from_avro_datetime!(
                 field_value,
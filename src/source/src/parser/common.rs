@@ -66,7 +66,7 @@ pub(crate) fn json_parse_value(
                 |v: f64| ScalarImpl::Float64(v.into())
             )
         }
-        DataType::Decimal => match value.and_then(|v| v.as_f64()) {
+        DataType::Decimal(_) => match value.and_then(|v| v.as_f64()) {
             Some(v) => match Decimal::from_f64(v) {
                 Some(v) => Ok(ScalarImpl::Decimal(v)),
                 None => Err(RwError::from(InternalError(
@@ -94,6 +94,18 @@ pub(crate) fn json_parse_value(
                 Err(e) => Err(e),
             },
         },
+        DataType::Jsonb => match value {
+            Some(v) => Ok(ScalarImpl::Jsonb(v.clone().into())),
+            None => Err(RwError::from(InternalError("json parse error".to_string()))),
+        },
+        DataType::Bytea => match value.and_then(|v| v.as_str()) {
+            Some(v) => Ok(ScalarImpl::Bytea(v.parse()?)),
+            None => Err(RwError::from(InternalError("json parse error".to_string()))),
+        },
+        DataType::Uuid => match value.and_then(|v| v.as_str()) {
+            Some(v) => Ok(ScalarImpl::Uuid(v.parse()?)),
+            None => Err(RwError::from(InternalError("json parse error".to_string()))),
+        },
         _ => Err(ErrorCode::NotImplemented(
             "unsupported type for json_parse_value".to_string(),
             None.into(),
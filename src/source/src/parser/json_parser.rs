@@ -15,15 +15,28 @@
 use risingwave_common::array::Op;
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
-use risingwave_common::types::Datum;
+use risingwave_common::types::{Datum, ScalarImpl};
 use serde_json::Value;
 
 use crate::parser::common::json_parse_value;
 use crate::{Event, SourceColumnDesc, SourceParser};
 
+/// The option naming the column which keeps the whole payload of a JSON source.
+pub const JSON_PAYLOAD_COLUMN_KEY: &str = "json.payload.column";
+
 /// Parser for JSON format
-#[derive(Debug)]
-pub struct JSONParser;
+#[derive(Debug, Default)]
+pub struct JSONParser {
+    /// If set, the whole payload is kept as a `jsonb` value in the column with this name, which is
+    /// useful for schemaless topics.
+    payload_column: Option<String>,
+}
+
+impl JSONParser {
+    pub fn new(payload_column: Option<String>) -> Self {
+        Self { payload_column }
+    }
+}
 
 impl SourceParser for JSONParser {
     fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event> {
@@ -37,6 +50,8 @@ impl SourceParser for JSONParser {
                 .map(|column| {
                     if column.skip_parse {
                         None
+                    } else if self.payload_column.as_ref() == Some(&column.name) {
+                        Some(ScalarImpl::Jsonb(value.clone().into()))
                     } else {
                         json_parse_value(column, value.get(&column.name)).ok()
                     }
@@ -56,7 +71,7 @@ mod tests {
 
     #[test]
     fn test_json_parser() {
-        let parser = JSONParser {};
+        let parser = JSONParser::default();
         let payload = r#"{"i32":1,"bool":true,"i16":1,"i64":12345678,"f32":1.23,"f64":1.2345,"varchar":"varchar","date":"2021-01-01","timestamp":"2021-01-01 16:06:12.269"}"#.as_bytes();
         let descs = vec![
             SourceColumnDesc {
@@ -147,4 +162,44 @@ mod tests {
         let result = parser.parse(payload, &descs);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_json_parser_jsonb() {
+        let parser = JSONParser::new(Some("payload".to_string()));
+        let payload = r#"{"id":1,"tags":["a","b"]}"#.as_bytes();
+        let descs = vec![
+            SourceColumnDesc {
+                name: "id".to_string(),
+                data_type: DataType::Int32,
+                column_id: ColumnId::from(0),
+                skip_parse: false,
+            },
+            SourceColumnDesc {
+                name: "tags".to_string(),
+                data_type: DataType::Jsonb,
+                column_id: ColumnId::from(1),
+                skip_parse: false,
+            },
+            SourceColumnDesc {
+                name: "payload".to_string(),
+                data_type: DataType::Jsonb,
+                column_id: ColumnId::from(2),
+                skip_parse: false,
+            },
+        ];
+
+        let event = parser.parse(payload, &descs).unwrap();
+        let row = event.rows.first().unwrap();
+        assert_eq!(row[0], Some(ScalarImpl::Int32(1)));
+        assert_eq!(
+            row[1],
+            Some(ScalarImpl::Jsonb(r#"["a", "b"]"#.parse().unwrap()))
+        );
+        assert_eq!(
+            row[2],
+            Some(ScalarImpl::Jsonb(
+                r#"{"id": 1, "tags": ["a", "b"]}"#.parse().unwrap()
+            ))
+        );
+    }
 }
@@ -73,8 +73,16 @@ impl SourceParserImpl {
         schema_location: &str,
     ) -> Result<Arc<Self>> {
         const PROTOBUF_MESSAGE_KEY: &str = "proto.message";
+        if *format != SourceFormat::Json && properties.contains_key(JSON_PAYLOAD_COLUMN_KEY) {
+            return Err(RwError::from(ProtocolError(format!(
+                "'{}' is only supported by the JSON format",
+                JSON_PAYLOAD_COLUMN_KEY
+            ))));
+        }
         let parser = match format {
-            SourceFormat::Json => SourceParserImpl::Json(JSONParser {}),
+            SourceFormat::Json => SourceParserImpl::Json(JSONParser::new(
+                properties.get(JSON_PAYLOAD_COLUMN_KEY).cloned(),
+            )),
             SourceFormat::Protobuf => {
                 let message_name = properties.get(PROTOBUF_MESSAGE_KEY).ok_or_else(|| {
                     RwError::from(ProtocolError(format!(
@@ -19,7 +19,9 @@ use protobuf::RepeatedField;
 use risingwave_common::array::Op;
 use risingwave_common::error::ErrorCode::{self, InternalError, ItemNotFound, ProtocolError};
 use risingwave_common::error::{Result, RwError};
-use risingwave_common::types::{DataType, Datum, Decimal, OrderedF32, OrderedF64, ScalarImpl};
+use risingwave_common::types::{
+    ByteaVal, DataType, Datum, Decimal, OrderedF32, OrderedF64, ScalarImpl,
+};
 use risingwave_expr::vector_op::cast::{str_to_date, str_to_timestamp};
 use risingwave_pb::plan_common::ColumnDesc;
 use serde::de::Deserialize;
@@ -153,6 +155,7 @@ impl ProtobufParser {
                 column_type: Some(data_type.to_protobuf()),
                 field_descs: column_vec,
                 type_name: m.name().to_string(),
+                default_value: vec![],
             })
         } else {
             *index += 1;
@@ -197,6 +200,7 @@ fn protobuf_type_mapping(f: &FieldDescriptor, descriptors: &Descriptors) -> Resu
         FieldType::Int32 | FieldType::SFixed32 | FieldType::SInt32 => DataType::Int32,
         FieldType::Bool => DataType::Boolean,
         FieldType::String => DataType::Varchar,
+        FieldType::Bytes => DataType::Bytea,
         FieldType::Message(m) => {
             let vec = m
                 .fields()
@@ -251,12 +255,15 @@ impl SourceParser for ProtobufParser {
                 DataType::Float64 => {
                     protobuf_match_type!(value, ScalarImpl::Float64, { I8, I16, I32, U8, U16, U32, F32, F64}, OrderedF64)
                 }
-                DataType::Decimal => {
+                DataType::Decimal(_) => {
                     protobuf_match_type!(value, ScalarImpl::Decimal, { I8, I16, I32, I64, U8, U16, U32, U64}, Decimal)
                 }
                 DataType::Varchar => {
                     protobuf_match_type!(value, ScalarImpl::Utf8, { String }, String)
                 }
+                DataType::Bytea => {
+                    protobuf_match_type!(value, ScalarImpl::Bytea, { Bytes }, ByteaVal)
+                }
                 DataType::Date => {
                     value.and_then(|v| match v {
                         Value::String(b) => str_to_date(&b).ok(),
@@ -16,6 +16,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::RwLock;
 
 use async_trait::async_trait;
+use itertools::Itertools;
 use rand::prelude::SliceRandom;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::{ColumnDesc, ColumnId};
@@ -43,8 +44,8 @@ struct TableSourceV2Core {
 pub struct TableSourceV2 {
     core: RwLock<TableSourceV2Core>,
 
-    /// All columns in this table.
-    column_descs: Vec<ColumnDesc>,
+    /// All columns in this table, which may be appended by `ALTER TABLE ADD COLUMN`.
+    column_descs: RwLock<Vec<ColumnDesc>>,
 
     /// Current allocated row id.
     next_row_id: AtomicUsize,
@@ -58,11 +59,21 @@ impl TableSourceV2 {
 
         Self {
             core: RwLock::new(core),
-            column_descs,
+            column_descs: RwLock::new(column_descs),
             next_row_id: 0.into(),
         }
     }
 
+    /// Appends the columns added by `ALTER TABLE`. The chunks written afterwards must contain them.
+    pub fn add_columns(&self, columns: Vec<ColumnDesc>) {
+        self.column_descs.write().unwrap().extend(columns);
+    }
+
+    /// Removes the columns appended since the table has `len` columns, when they fail to be added.
+    pub fn truncate_columns(&self, len: usize) {
+        self.column_descs.write().unwrap().truncate(len);
+    }
+
     /// Generate a global-unique row id with given `worker_id`.
     pub fn next_row_id(&self, worker_id: u32) -> i64 {
         let local_row_id = self.next_row_id.fetch_add(1, Ordering::SeqCst) as u32;
@@ -117,8 +128,9 @@ pub struct TableV2StreamReader {
     /// The receiver of the changes channel.
     rx: mpsc::UnboundedReceiver<(StreamChunk, oneshot::Sender<usize>)>,
 
-    /// Mappings from the source column to the column to be read.
-    column_indices: Vec<usize>,
+    /// Mappings from the source column to the column to be read. `None` if all the columns are
+    /// read, in which case the columns added later are also read.
+    column_indices: Option<Vec<usize>>,
 }
 
 #[async_trait]
@@ -133,14 +145,14 @@ impl StreamSourceReader for TableV2StreamReader {
         // Caveats: this function is an arm of `tokio::select`. We should ensure there's no `await`
         // after here.
 
-        let (ops, columns, bitmap) = chunk.into_inner();
-
-        let selected_columns = self
-            .column_indices
-            .iter()
-            .map(|i| columns[*i].clone())
-            .collect();
-        let chunk = StreamChunk::new(ops, selected_columns, bitmap);
+        let chunk = match &self.column_indices {
+            Some(column_indices) => {
+                let (ops, columns, bitmap) = chunk.into_inner();
+                let selected_columns = column_indices.iter().map(|i| columns[*i].clone()).collect();
+                StreamChunk::new(ops, selected_columns, bitmap)
+            }
+            None => chunk,
+        };
 
         // Notify about that we've taken the chunk.
         notifier.send(chunk.cardinality()).ok();
@@ -155,15 +167,21 @@ impl StreamSourceReader for TableV2StreamReader {
 impl TableSourceV2 {
     /// Create a new stream reader.
     pub async fn stream_reader(&self, column_ids: Vec<ColumnId>) -> Result<TableV2StreamReader> {
-        let column_indices = column_ids
-            .into_iter()
-            .map(|id| {
-                self.column_descs
-                    .iter()
-                    .position(|c| c.column_id == id)
-                    .expect("column id not exists")
-            })
-            .collect();
+        let column_indices = {
+            let column_descs = self.column_descs.read().unwrap();
+            let column_indices = column_ids
+                .into_iter()
+                .map(|id| {
+                    column_descs
+                        .iter()
+                        .position(|c| c.column_id == id)
+                        .expect("column id not exists")
+                })
+                .collect_vec();
+            let read_all = column_indices.len() == column_descs.len()
+                && column_indices.iter().enumerate().all(|(i, idx)| i == *idx);
+            (!read_all).then(|| column_indices)
+        };
 
         let mut core = self.core.write().unwrap();
         let (tx, rx) = mpsc::unbounded_channel();
@@ -233,4 +251,38 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_table_source_v2_add_columns() -> Result<()> {
+        let source = new_source();
+        let mut reader = source.stream_reader(vec![ColumnId::from(0)]).await?;
+
+        source.add_columns(vec![ColumnDesc::unnamed(
+            ColumnId::from(1),
+            DataType::Int64,
+        )]);
+        let chunk = StreamChunk::new(
+            vec![Op::Insert],
+            vec![
+                column_nonnull!(I64Array, [1]),
+                column_nonnull!(I64Array, [2]),
+            ],
+            None,
+        );
+        let _notifier = source.write_chunk(chunk)?;
+
+        // The reader of all the columns also reads the added one.
+        let chunk = reader.next().await?.chunk;
+        assert_eq!(chunk.columns().len(), 2);
+        assert_eq!(
+            chunk.columns()[1]
+                .array_ref()
+                .as_int64()
+                .iter()
+                .collect_vec(),
+            vec![Some(2)]
+        );
+
+        Ok(())
+    }
 }
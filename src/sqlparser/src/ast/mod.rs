@@ -173,6 +173,8 @@ impl ParseTo for ObjectName {
 pub enum Expr {
     /// Identifier e.g. table name or column name
     Identifier(Ident),
+    /// A positional parameter, e.g. `$1`, in a prepared statement
+    Parameter { index: u64 },
     /// Multi-part identifier, e.g. `table_alias.column` or `schema.table.col`
     CompoundIdentifier(Vec<Ident>),
     /// Struct-field identifier, expr is a table or a column struct, ident is field.
@@ -206,6 +208,18 @@ pub enum Expr {
         subquery: Box<Query>,
         negated: bool,
     },
+    /// `<expr> <op> ANY | SOME (SELECT ...)`
+    AnySubquery {
+        expr: Box<Expr>,
+        op: BinaryOperator,
+        subquery: Box<Query>,
+    },
+    /// `<expr> <op> ALL (SELECT ...)`
+    AllSubquery {
+        expr: Box<Expr>,
+        op: BinaryOperator,
+        subquery: Box<Query>,
+    },
     /// `<expr> [ NOT ] BETWEEN <low> AND <high>`
     Between {
         expr: Box<Expr>,
@@ -213,6 +227,13 @@ pub enum Expr {
         low: Box<Expr>,
         high: Box<Expr>,
     },
+    /// `<expr> [ NOT ] SIMILAR TO <pattern> [ ESCAPE <escape_char> ]`
+    SimilarTo {
+        expr: Box<Expr>,
+        negated: bool,
+        pattern: Box<Expr>,
+        escape_char: Option<Box<Expr>>,
+    },
     /// Binary operation e.g. `1 + 1` or `foo > bar`
     BinaryOp {
         left: Box<Expr>,
@@ -237,6 +258,11 @@ pub enum Expr {
         field: DateTimeField,
         expr: Box<Expr>,
     },
+    /// <expr> AT TIME ZONE <expr>
+    AtTimeZone {
+        timestamp: Box<Expr>,
+        time_zone: Box<Expr>,
+    },
     /// SUBSTRING(<expr> [FROM <expr>] [FOR <expr>])
     Substring {
         expr: Box<Expr>,
@@ -302,6 +328,7 @@ impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Expr::Identifier(s) => write!(f, "{}", s),
+            Expr::Parameter { index } => write!(f, "${}", index),
             Expr::CompoundIdentifier(s) => write!(f, "{}", display_separated(s, ".")),
             Expr::FieldIdentifier(ast, s) => write!(f, "{}.{}", ast, display_separated(s, ".")),
             Expr::IsNull(ast) => write!(f, "{} IS NULL", ast),
@@ -332,6 +359,12 @@ impl fmt::Display for Expr {
                 if *negated { "NOT " } else { "" },
                 subquery
             ),
+            Expr::AnySubquery { expr, op, subquery } => {
+                write!(f, "{} {} ANY ({})", expr, op, subquery)
+            }
+            Expr::AllSubquery { expr, op, subquery } => {
+                write!(f, "{} {} ALL ({})", expr, op, subquery)
+            }
             Expr::Between {
                 expr,
                 negated,
@@ -345,6 +378,24 @@ impl fmt::Display for Expr {
                 low,
                 high
             ),
+            Expr::SimilarTo {
+                expr,
+                negated,
+                pattern,
+                escape_char,
+            } => {
+                write!(
+                    f,
+                    "{} {}SIMILAR TO {}",
+                    expr,
+                    if *negated { "NOT " } else { "" },
+                    pattern
+                )?;
+                if let Some(escape_char) = escape_char {
+                    write!(f, " ESCAPE {}", escape_char)?;
+                }
+                Ok(())
+            }
             Expr::BinaryOp { left, op, right } => write!(f, "{} {} {}", left, op, right),
             Expr::UnaryOp { op, expr } => {
                 if op == &UnaryOperator::PGPostfixFactorial {
@@ -356,6 +407,10 @@ impl fmt::Display for Expr {
             Expr::Cast { expr, data_type } => write!(f, "CAST({} AS {})", expr, data_type),
             Expr::TryCast { expr, data_type } => write!(f, "TRY_CAST({} AS {})", expr, data_type),
             Expr::Extract { field, expr } => write!(f, "EXTRACT({} FROM {})", field, expr),
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => write!(f, "{} AT TIME ZONE {}", timestamp, time_zone),
             Expr::Collate { expr, collation } => write!(f, "{} COLLATE {}", expr, collation),
             Expr::Nested(ast) => write!(f, "({})", ast),
             Expr::Value(v) => write!(f, "{}", v),
@@ -688,12 +743,15 @@ pub enum Statement {
         /// A SQL query that specifies what to insert
         source: Box<Query>,
     },
+    /// COPY
     Copy {
-        /// TABLE
-        table_name: ObjectName,
-        /// COLUMNS
-        columns: Vec<Ident>,
-        /// VALUES a vector of values to be copied
+        /// The table or query to copy
+        source: CopySource,
+        /// `true` for `TO STDOUT`, `false` for `FROM STDIN`
+        to: bool,
+        /// The options in parentheses
+        options: Vec<CopyOption>,
+        /// VALUES a vector of values to be copied, inlined after `FROM STDIN;`
         values: Vec<Option<String>>,
     },
     /// UPDATE
@@ -751,12 +809,25 @@ pub enum Statement {
         is_materialized: bool,
         stmt: CreateSourceStatement,
     },
+    /// CREATE SINK
+    CreateSink { stmt: CreateSinkStatement },
+    /// CREATE USER
+    CreateUser(CreateUserStatement),
+    /// ALTER USER
+    AlterUser(AlterUserStatement),
     /// ALTER TABLE
     AlterTable {
         /// Table name
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// ALTER SOURCE
+    AlterSource {
+        /// Source name
+        name: ObjectName,
+        /// Only `AddColumn` and `DropColumn` are allowed
+        operation: AlterTableOperation,
+    },
     /// DESCRIBE TABLE OR SOURCE
     Describe {
         /// Table or Source name
@@ -822,8 +893,9 @@ pub enum Statement {
         with_grant_option: bool,
         granted_by: Option<Ident>,
     },
-    /// REVOKE privileges ON objects FROM grantees
+    /// REVOKE [GRANT OPTION FOR] privileges ON objects FROM grantees
     Revoke {
+        revoke_grant_option: bool,
         privileges: Privileges,
         objects: GrantObjects,
         grantees: Vec<Ident>,
@@ -921,17 +993,22 @@ impl fmt::Display for Statement {
             }
 
             Statement::Copy {
-                table_name,
-                columns,
+                source,
+                to,
+                options,
                 values,
             } => {
-                write!(f, "COPY {}", table_name)?;
-                if !columns.is_empty() {
-                    write!(f, " ({})", display_comma_separated(columns))?;
+                write!(f, "COPY {}", source)?;
+                if *to {
+                    write!(f, " TO STDOUT")?;
+                } else {
+                    write!(f, " FROM STDIN")?;
+                }
+                if !options.is_empty() {
+                    write!(f, " ({})", display_comma_separated(options))?;
                 }
-                write!(f, " FROM stdin; ")?;
                 if !values.is_empty() {
-                    writeln!(f)?;
+                    writeln!(f, ";")?;
                     let mut delim = "";
                     for v in values {
                         write!(f, "{}", delim)?;
@@ -942,8 +1019,9 @@ impl fmt::Display for Statement {
                             write!(f, "\\N")?;
                         }
                     }
+                    write!(f, "\n\\.")?;
                 }
-                write!(f, "\n\\.")
+                Ok(())
             }
             Statement::Update {
                 table,
@@ -1087,9 +1165,15 @@ impl fmt::Display for Statement {
                     ""
                 }
             ),
+            Statement::CreateSink { stmt } => write!(f, "CREATE SINK {}", stmt),
+            Statement::CreateUser(stmt) => write!(f, "CREATE USER {}", stmt),
+            Statement::AlterUser(stmt) => write!(f, "ALTER USER {}", stmt),
             Statement::AlterTable { name, operation } => {
                 write!(f, "ALTER TABLE {} {}", name, operation)
             }
+            Statement::AlterSource { name, operation } => {
+                write!(f, "ALTER SOURCE {} {}", name, operation)
+            }
             Statement::Drop(stmt) => write!(f, "DROP {}", stmt),
             Statement::SetVariable {
                 local,
@@ -1177,13 +1261,18 @@ impl fmt::Display for Statement {
                 Ok(())
             }
             Statement::Revoke {
+                revoke_grant_option,
                 privileges,
                 objects,
                 grantees,
                 granted_by,
                 cascade,
             } => {
-                write!(f, "REVOKE {} ", privileges)?;
+                write!(f, "REVOKE ")?;
+                if *revoke_grant_option {
+                    write!(f, "GRANT OPTION FOR ")?;
+                }
+                write!(f, "{} ", privileges)?;
                 write!(f, "ON {} ", objects)?;
                 write!(f, "FROM {}", display_comma_separated(grantees))?;
                 if let Some(grantor) = granted_by {
@@ -1354,6 +1443,12 @@ pub enum GrantObjects {
     Sequences(Vec<ObjectName>),
     /// Grant privileges on specific tables
     Tables(Vec<ObjectName>),
+    /// Grant privileges on specific databases
+    Databases(Vec<ObjectName>),
+    /// Grant privileges on specific materialized views
+    Mviews(Vec<ObjectName>),
+    /// Grant privileges on specific sources
+    Sources(Vec<ObjectName>),
 }
 
 impl fmt::Display for GrantObjects {
@@ -1368,6 +1463,15 @@ impl fmt::Display for GrantObjects {
             GrantObjects::Tables(tables) => {
                 write!(f, "{}", display_comma_separated(tables))
             }
+            GrantObjects::Databases(databases) => {
+                write!(f, "DATABASE {}", display_comma_separated(databases))
+            }
+            GrantObjects::Mviews(mviews) => {
+                write!(f, "MATERIALIZED VIEW {}", display_comma_separated(mviews))
+            }
+            GrantObjects::Sources(sources) => {
+                write!(f, "SOURCE {}", display_comma_separated(sources))
+            }
             GrantObjects::AllSequencesInSchema { schemas } => {
                 write!(
                     f,
@@ -1479,7 +1583,9 @@ pub enum ObjectType {
     Schema,
     Source,
     MaterializedSource,
+    Sink,
     Database,
+    User,
 }
 
 impl fmt::Display for ObjectType {
@@ -1492,7 +1598,9 @@ impl fmt::Display for ObjectType {
             ObjectType::Schema => "SCHEMA",
             ObjectType::Source => "SOURCE",
             ObjectType::MaterializedSource => "MATERIALIZED SOURCE",
+            ObjectType::Sink => "SINK",
             ObjectType::Database => "DATABASE",
+            ObjectType::User => "USER",
         })
     }
 }
@@ -1509,15 +1617,19 @@ impl ParseTo for ObjectType {
             ObjectType::MaterializedSource
         } else if parser.parse_keyword(Keyword::SOURCE) {
             ObjectType::Source
+        } else if parser.parse_keyword(Keyword::SINK) {
+            ObjectType::Sink
         } else if parser.parse_keyword(Keyword::INDEX) {
             ObjectType::Index
         } else if parser.parse_keyword(Keyword::SCHEMA) {
             ObjectType::Schema
         } else if parser.parse_keyword(Keyword::DATABASE) {
             ObjectType::Database
+        } else if parser.parse_keyword(Keyword::USER) {
+            ObjectType::User
         } else {
             return parser.expected(
-                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, or SCHEMA after DROP",
+                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, SINK, SCHEMA, DATABASE or USER after DROP",
                 parser.peek_token(),
             );
         };
@@ -1612,6 +1724,62 @@ impl fmt::Display for ShowStatementFilter {
     }
 }
 
+/// The table or query copied by `COPY`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CopySource {
+    Table {
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+    },
+    Query(Box<Query>),
+}
+
+impl fmt::Display for CopySource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CopySource::Table {
+                table_name,
+                columns,
+            } => {
+                write!(f, "{}", table_name)?;
+                if !columns.is_empty() {
+                    write!(f, " ({})", display_comma_separated(columns))?;
+                }
+                Ok(())
+            }
+            CopySource::Query(query) => write!(f, "({})", query),
+        }
+    }
+}
+
+/// An option of `COPY`, e.g. `FORMAT csv`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CopyOption {
+    Format(Ident),
+    Delimiter(char),
+    Null(String),
+    Header(bool),
+}
+
+impl fmt::Display for CopyOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use CopyOption::*;
+        match self {
+            Format(format) => write!(f, "FORMAT {}", format),
+            Delimiter(delimiter) => write!(
+                f,
+                "DELIMITER '{}'",
+                value::escape_single_quote_string(&delimiter.to_string())
+            ),
+            Null(null) => write!(f, "NULL '{}'", value::escape_single_quote_string(null)),
+            Header(true) => write!(f, "HEADER"),
+            Header(false) => write!(f, "HEADER false"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SetVariableValue {
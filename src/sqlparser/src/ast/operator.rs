@@ -86,6 +86,12 @@ pub enum BinaryOperator {
     PGRegexIMatch,
     PGRegexNotMatch,
     PGRegexNotIMatch,
+    PGJsonAccess,
+    PGJsonAccessText,
+    PGJsonPathAccess,
+    PGJsonPathAccessText,
+    PGJsonContains,
+    PGJsonExists,
 }
 
 impl fmt::Display for BinaryOperator {
@@ -121,6 +127,12 @@ impl fmt::Display for BinaryOperator {
             BinaryOperator::PGRegexIMatch => "~*",
             BinaryOperator::PGRegexNotMatch => "!~",
             BinaryOperator::PGRegexNotIMatch => "!~*",
+            BinaryOperator::PGJsonAccess => "->",
+            BinaryOperator::PGJsonAccessText => "->>",
+            BinaryOperator::PGJsonPathAccess => "#>",
+            BinaryOperator::PGJsonPathAccessText => "#>>",
+            BinaryOperator::PGJsonContains => "@>",
+            BinaryOperator::PGJsonExists => "?",
         })
     }
 }
@@ -20,9 +20,13 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use super::ObjectType;
-use crate::ast::{display_comma_separated, ColumnDef, ObjectName, SqlOption, TableConstraint};
+use crate::ast::{
+    display_comma_separated, display_separated, ColumnDef, Expr, Ident, ObjectName, SqlOption,
+    TableConstraint,
+};
 use crate::keywords::Keyword;
 use crate::parser::{Parser, ParserError};
+use crate::tokenizer::Token;
 
 /// Consumes token from the parser into an AST node.
 pub trait ParseTo: Sized {
@@ -73,11 +77,26 @@ pub struct CreateSourceStatement {
     pub if_not_exists: bool,
     pub columns: Vec<ColumnDef>,
     pub constraints: Vec<TableConstraint>,
+    pub source_watermarks: Vec<SourceWatermark>,
     pub source_name: ObjectName,
     pub with_properties: WithProperties,
     pub source_schema: SourceSchema,
 }
 
+/// `WATERMARK FOR column AS expr` in the column list of `CREATE SOURCE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SourceWatermark {
+    pub column: Ident,
+    pub expr: Expr,
+}
+
+impl fmt::Display for SourceWatermark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WATERMARK FOR {} AS {}", self.column, self.expr)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SourceSchema {
@@ -154,7 +173,7 @@ impl ParseTo for CreateSourceStatement {
         impl_parse_to!(source_name: ObjectName, p);
 
         // parse columns
-        let (columns, constraints) = p.parse_columns()?;
+        let (columns, constraints, source_watermarks) = p.parse_columns_with_watermark()?;
 
         impl_parse_to!(with_properties: WithProperties, p);
         impl_parse_to!([Keyword::ROW, Keyword::FORMAT], p);
@@ -163,6 +182,7 @@ impl ParseTo for CreateSourceStatement {
             if_not_exists,
             columns,
             constraints,
+            source_watermarks,
             source_name,
             with_properties,
             source_schema,
@@ -175,6 +195,18 @@ impl fmt::Display for CreateSourceStatement {
         let mut v: Vec<String> = vec![];
         impl_fmt_display!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], v, self);
         impl_fmt_display!(source_name, v, self);
+        if !self.columns.is_empty()
+            || !self.constraints.is_empty()
+            || !self.source_watermarks.is_empty()
+        {
+            let elems = self
+                .columns
+                .iter()
+                .map(ToString::to_string)
+                .chain(self.constraints.iter().map(ToString::to_string))
+                .chain(self.source_watermarks.iter().map(ToString::to_string));
+            v.push(format!("({})", elems.format(", ")));
+        }
         impl_fmt_display!(with_properties, v, self);
         impl_fmt_display!([Keyword::ROW, Keyword::FORMAT], v);
         impl_fmt_display!(source_schema, v, self);
@@ -182,6 +214,251 @@ impl fmt::Display for CreateSourceStatement {
     }
 }
 
+// sql_grammar!(CreateSinkStatement {
+//     if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS],
+//     sink_name: Ident,
+//     [Keyword::FROM],
+//     materialized_view: Ident,
+//     with_properties: WithProperties,
+// });
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateSinkStatement {
+    pub if_not_exists: bool,
+    pub sink_name: ObjectName,
+    pub materialized_view: ObjectName,
+    pub with_properties: WithProperties,
+}
+
+impl ParseTo for CreateSinkStatement {
+    fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
+        impl_parse_to!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], p);
+        impl_parse_to!(sink_name: ObjectName, p);
+        impl_parse_to!([Keyword::FROM], p);
+        impl_parse_to!(materialized_view: ObjectName, p);
+        impl_parse_to!(with_properties: WithProperties, p);
+        Ok(Self {
+            if_not_exists,
+            sink_name,
+            materialized_view,
+            with_properties,
+        })
+    }
+}
+
+impl fmt::Display for CreateSinkStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut v: Vec<String> = vec![];
+        impl_fmt_display!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], v, self);
+        impl_fmt_display!(sink_name, v, self);
+        impl_fmt_display!([Keyword::FROM], v);
+        impl_fmt_display!(materialized_view, v, self);
+        impl_fmt_display!(with_properties, v, self);
+        v.iter().join(" ").fmt(f)
+    }
+}
+
+// sql_grammar!(CreateUserStatement {
+//     user_name: ObjectName,
+//     with_options: UserOptions,
+// });
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateUserStatement {
+    pub user_name: ObjectName,
+    pub with_options: UserOptions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UserOption {
+    SuperUser,
+    NoSuperUser,
+    CreateDB,
+    NoCreateDB,
+    Login,
+    NoLogin,
+    EncryptedPassword(AstString),
+    /// `None` for `PASSWORD NULL`.
+    Password(Option<AstString>),
+}
+
+impl fmt::Display for UserOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserOption::SuperUser => write!(f, "SUPERUSER"),
+            UserOption::NoSuperUser => write!(f, "NOSUPERUSER"),
+            UserOption::CreateDB => write!(f, "CREATEDB"),
+            UserOption::NoCreateDB => write!(f, "NOCREATEDB"),
+            UserOption::Login => write!(f, "LOGIN"),
+            UserOption::NoLogin => write!(f, "NOLOGIN"),
+            UserOption::EncryptedPassword(p) => write!(f, "ENCRYPTED PASSWORD {}", p),
+            UserOption::Password(None) => write!(f, "PASSWORD NULL"),
+            UserOption::Password(Some(p)) => write!(f, "PASSWORD {}", p),
+        }
+    }
+}
+
+/// The options of `CREATE USER` and `ALTER USER`, where the `WITH` keyword is optional.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UserOptions(pub Vec<UserOption>);
+
+const USER_OPTIONS_EXPECTED: &str =
+    "SUPERUSER | NOSUPERUSER | CREATEDB | NOCREATEDB | LOGIN | NOLOGIN | [ENCRYPTED] PASSWORD";
+
+#[derive(Default)]
+struct UserOptionsBuilder {
+    super_user: Option<UserOption>,
+    create_db: Option<UserOption>,
+    login: Option<UserOption>,
+    password: Option<UserOption>,
+}
+
+impl UserOptionsBuilder {
+    fn build(self) -> UserOptions {
+        let options = [self.super_user, self.create_db, self.login, self.password];
+        UserOptions(options.into_iter().flatten().collect())
+    }
+}
+
+impl ParseTo for UserOptions {
+    fn parse_to(parser: &mut Parser) -> Result<Self, ParserError> {
+        let mut builder = UserOptionsBuilder::default();
+        let add_option = |item: &mut Option<UserOption>, user_option| {
+            let old_value = item.replace(user_option);
+            if old_value.is_some() {
+                Err(ParserError::ParserError(
+                    "conflicting or redundant options".to_string(),
+                ))
+            } else {
+                Ok(())
+            }
+        };
+        let _ = parser.parse_keyword(Keyword::WITH);
+        loop {
+            let token = parser.peek_token();
+            if token == Token::EOF || token == Token::SemiColon {
+                break;
+            }
+
+            if let Token::Word(ref w) = token {
+                parser.next_token();
+                let (item_mut_ref, user_option) = match w.keyword {
+                    Keyword::SUPERUSER => (&mut builder.super_user, UserOption::SuperUser),
+                    Keyword::NOSUPERUSER => (&mut builder.super_user, UserOption::NoSuperUser),
+                    Keyword::CREATEDB => (&mut builder.create_db, UserOption::CreateDB),
+                    Keyword::NOCREATEDB => (&mut builder.create_db, UserOption::NoCreateDB),
+                    Keyword::LOGIN => (&mut builder.login, UserOption::Login),
+                    Keyword::NOLOGIN => (&mut builder.login, UserOption::NoLogin),
+                    Keyword::PASSWORD => {
+                        if parser.parse_keyword(Keyword::NULL) {
+                            (&mut builder.password, UserOption::Password(None))
+                        } else {
+                            (
+                                &mut builder.password,
+                                UserOption::Password(Some(AstString::parse_to(parser)?)),
+                            )
+                        }
+                    }
+                    Keyword::ENCRYPTED => {
+                        parser.expect_keyword(Keyword::PASSWORD)?;
+                        (
+                            &mut builder.password,
+                            UserOption::EncryptedPassword(AstString::parse_to(parser)?),
+                        )
+                    }
+                    _ => return parser.expected(USER_OPTIONS_EXPECTED, token.clone()),
+                };
+                add_option(item_mut_ref, user_option)?;
+            } else {
+                return parser.expected(USER_OPTIONS_EXPECTED, token);
+            }
+        }
+        Ok(builder.build())
+    }
+}
+
+impl fmt::Display for UserOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.0.is_empty() {
+            write!(f, "WITH {}", display_separated(self.0.as_slice(), " "))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl ParseTo for CreateUserStatement {
+    fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
+        impl_parse_to!(user_name: ObjectName, p);
+        impl_parse_to!(with_options: UserOptions, p);
+
+        Ok(CreateUserStatement {
+            user_name,
+            with_options,
+        })
+    }
+}
+
+impl fmt::Display for CreateUserStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut v: Vec<String> = vec![];
+        impl_fmt_display!(user_name, v, self);
+        impl_fmt_display!(with_options, v, self);
+        v.iter().join(" ").fmt(f)
+    }
+}
+
+// sql_grammar!(AlterUserStatement {
+//     user_name: ObjectName,
+//     mode: AlterUserMode,
+// });
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AlterUserStatement {
+    pub user_name: ObjectName,
+    pub mode: AlterUserMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterUserMode {
+    Options(UserOptions),
+    Rename(ObjectName),
+}
+
+impl ParseTo for AlterUserStatement {
+    fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
+        impl_parse_to!(user_name: ObjectName, p);
+        let mode = if p.parse_keywords(&[Keyword::RENAME, Keyword::TO]) {
+            impl_parse_to!(new_name: ObjectName, p);
+            AlterUserMode::Rename(new_name)
+        } else {
+            impl_parse_to!(with_options: UserOptions, p);
+            AlterUserMode::Options(with_options)
+        };
+
+        Ok(AlterUserStatement { user_name, mode })
+    }
+}
+
+impl fmt::Display for AlterUserStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut v: Vec<String> = vec![];
+        impl_fmt_display!(user_name, v, self);
+        match &self.mode {
+            AlterUserMode::Options(options) => {
+                v.push(options.to_string());
+            }
+            AlterUserMode::Rename(new_name) => {
+                v.push(format!("RENAME TO {}", new_name));
+            }
+        }
+        v.iter().join(" ").fmt(f)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AstVec<T>(pub Vec<T>);
@@ -141,6 +141,7 @@ define_keywords!(
     COVAR_POP,
     COVAR_SAMP,
     CREATE,
+    CREATEDB,
     CROSS,
     CSV,
     CUBE,
@@ -170,6 +171,7 @@ define_keywords!(
     DECLARE,
     DEFAULT,
     DELETE,
+    DELIMITER,
     DENSE_RANK,
     DEREF,
     DESC,
@@ -184,6 +186,7 @@ define_keywords!(
     EACH,
     ELEMENT,
     ELSE,
+    ENCRYPTED,
     END,
     END_EXEC = "END-EXEC",
     END_FRAME,
@@ -251,6 +254,7 @@ define_keywords!(
     ISOLATION,
     JOIN,
     JSON,
+    JSONB,
     KEY,
     LANGUAGE,
     LARGE,
@@ -266,6 +270,7 @@ define_keywords!(
     LOCALTIME,
     LOCALTIMESTAMP,
     LOCATION,
+    LOGIN,
     LOWER,
     MATCH,
     MATERIALIZED,
@@ -288,9 +293,12 @@ define_keywords!(
     NEW,
     NEXT,
     NO,
+    NOCREATEDB,
+    NOLOGIN,
     NONE,
     NORMALIZE,
     NOSCAN,
+    NOSUPERUSER,
     NOT,
     NTH_VALUE,
     NTILE,
@@ -321,6 +329,7 @@ define_keywords!(
     PARTITION,
     PARTITIONED,
     PARTITIONS,
+    PASSWORD,
     PERCENT,
     PERCENTILE_CONT,
     PERCENTILE_DISC,
@@ -396,6 +405,7 @@ define_keywords!(
     SETS,
     SHOW,
     SIMILAR,
+    SINK,
     SMALLINT,
     SNAPSHOT,
     SOME,
@@ -415,6 +425,7 @@ define_keywords!(
     STDDEV_POP,
     STDDEV_SAMP,
     STDIN,
+    STDOUT,
     STORED,
     STRING,
     STRUCT,
@@ -423,6 +434,7 @@ define_keywords!(
     SUBSTRING_REGEX,
     SUCCEEDS,
     SUM,
+    SUPERUSER,
     SYMMETRIC,
     SYNC,
     SYSTEM,
@@ -484,6 +496,7 @@ define_keywords!(
     VIEW,
     VIEWS,
     VIRTUAL,
+    WATERMARK,
     WHEN,
     WHENEVER,
     WHERE,
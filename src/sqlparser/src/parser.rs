@@ -488,6 +488,11 @@ impl Parser {
                 }
             }
 
+            Token::Parameter(s) => match s.parse() {
+                Ok(index) => Ok(Expr::Parameter { index }),
+                Err(e) => parser_err!(format!("Could not parse '${}' as u64: {}", s, e)),
+            },
+
             Token::LBrace => {
                 self.prev_token();
                 Ok(Expr::Array(self.parse_token_wrapped_exprs(
@@ -970,6 +975,12 @@ impl Parser {
             Token::TildeAsterisk => Some(BinaryOperator::PGRegexIMatch),
             Token::ExclamationMarkTilde => Some(BinaryOperator::PGRegexNotMatch),
             Token::ExclamationMarkTildeAsterisk => Some(BinaryOperator::PGRegexNotIMatch),
+            Token::Arrow => Some(BinaryOperator::PGJsonAccess),
+            Token::LongArrow => Some(BinaryOperator::PGJsonAccessText),
+            Token::HashArrow => Some(BinaryOperator::PGJsonPathAccess),
+            Token::HashLongArrow => Some(BinaryOperator::PGJsonPathAccessText),
+            Token::AtArrow => Some(BinaryOperator::PGJsonContains),
+            Token::QuestionMark => Some(BinaryOperator::PGJsonExists),
             Token::Word(w) => match w.keyword {
                 Keyword::AND => Some(BinaryOperator::And),
                 Keyword::OR => Some(BinaryOperator::Or),
@@ -991,11 +1002,25 @@ impl Parser {
         };
 
         if let Some(op) = regular_binary_operator {
-            Ok(Expr::BinaryOp {
-                left: Box::new(expr),
-                op,
-                right: Box::new(self.parse_subexpr(precedence)?),
-            })
+            let quantifier = match op {
+                BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq => {
+                    self.parse_one_of_keywords(&[Keyword::ANY, Keyword::SOME, Keyword::ALL])
+                }
+                _ => None,
+            };
+            match quantifier {
+                Some(quantifier) => self.parse_quantified_subquery(expr, op, quantifier),
+                None => Ok(Expr::BinaryOp {
+                    left: Box::new(expr),
+                    op,
+                    right: Box::new(self.parse_subexpr(precedence)?),
+                }),
+            }
         } else if let Token::Word(w) = &tok {
             match w.keyword {
                 Keyword::IS => {
@@ -1025,15 +1050,24 @@ impl Parser {
                         )
                     }
                 }
-                Keyword::NOT | Keyword::IN | Keyword::BETWEEN => {
+                Keyword::AT => {
+                    self.expect_keywords(&[Keyword::TIME, Keyword::ZONE])?;
+                    Ok(Expr::AtTimeZone {
+                        timestamp: Box::new(expr),
+                        time_zone: Box::new(self.parse_subexpr(precedence)?),
+                    })
+                }
+                Keyword::NOT | Keyword::IN | Keyword::BETWEEN | Keyword::SIMILAR => {
                     self.prev_token();
                     let negated = self.parse_keyword(Keyword::NOT);
                     if self.parse_keyword(Keyword::IN) {
                         self.parse_in(expr, negated)
                     } else if self.parse_keyword(Keyword::BETWEEN) {
                         self.parse_between(expr, negated)
+                    } else if self.parse_keyword(Keyword::SIMILAR) {
+                        self.parse_similar_to(expr, negated)
                     } else {
-                        self.expected("IN or BETWEEN after NOT", self.peek_token())
+                        self.expected("IN, BETWEEN or SIMILAR TO after NOT", self.peek_token())
                     }
                 }
                 // Can only happen if `get_next_precedence` got out of sync with this function
@@ -1070,6 +1104,23 @@ impl Parser {
         })
     }
 
+    /// Parses the parenthesized subquery following `<op> ANY | SOME | ALL`.
+    pub fn parse_quantified_subquery(
+        &mut self,
+        expr: Expr,
+        op: BinaryOperator,
+        quantifier: Keyword,
+    ) -> Result<Expr, ParserError> {
+        self.expect_token(&Token::LParen)?;
+        let subquery = Box::new(self.parse_query()?);
+        self.expect_token(&Token::RParen)?;
+        let expr = Box::new(expr);
+        Ok(match quantifier {
+            Keyword::ALL => Expr::AllSubquery { expr, op, subquery },
+            _ => Expr::AnySubquery { expr, op, subquery },
+        })
+    }
+
     /// Parses the parens following the `[ NOT ] IN` operator
     pub fn parse_in(&mut self, expr: Expr, negated: bool) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
@@ -1106,6 +1157,24 @@ impl Parser {
         })
     }
 
+    /// Parses `TO <pattern> [ ESCAPE <escape_char> ]`, assuming the `SIMILAR` keyword was already
+    /// consumed
+    pub fn parse_similar_to(&mut self, expr: Expr, negated: bool) -> Result<Expr, ParserError> {
+        self.expect_keyword(Keyword::TO)?;
+        let pattern = self.parse_subexpr(Self::BETWEEN_PREC)?;
+        let escape_char = if self.parse_keyword(Keyword::ESCAPE) {
+            Some(Box::new(self.parse_subexpr(Self::BETWEEN_PREC)?))
+        } else {
+            None
+        };
+        Ok(Expr::SimilarTo {
+            expr: Box::new(expr),
+            negated,
+            pattern: Box::new(pattern),
+            escape_char,
+        })
+    }
+
     /// Parse a postgresql casting style which is in the form of `expr::datatype`
     pub fn parse_pg_cast(&mut self, expr: Expr) -> Result<Expr, ParserError> {
         Ok(Expr::Cast {
@@ -1132,13 +1201,25 @@ impl Parser {
                 Token::Word(w) if w.keyword == Keyword::BETWEEN => Ok(Self::BETWEEN_PREC),
                 Token::Word(w) if w.keyword == Keyword::LIKE => Ok(Self::BETWEEN_PREC),
                 Token::Word(w) if w.keyword == Keyword::ILIKE => Ok(Self::BETWEEN_PREC),
+                Token::Word(w) if w.keyword == Keyword::SIMILAR => Ok(Self::BETWEEN_PREC),
                 _ => Ok(0),
             },
             Token::Word(w) if w.keyword == Keyword::IS => Ok(17),
+            Token::Word(w) if w.keyword == Keyword::AT => {
+                match (self.peek_nth_token(1), self.peek_nth_token(2)) {
+                    (Token::Word(w1), Token::Word(w2))
+                        if w1.keyword == Keyword::TIME && w2.keyword == Keyword::ZONE =>
+                    {
+                        Ok(41)
+                    }
+                    _ => Ok(0),
+                }
+            }
             Token::Word(w) if w.keyword == Keyword::IN => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::BETWEEN => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::LIKE => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::ILIKE => Ok(Self::BETWEEN_PREC),
+            Token::Word(w) if w.keyword == Keyword::SIMILAR => Ok(Self::BETWEEN_PREC),
             Token::Eq
             | Token::Lt
             | Token::LtEq
@@ -1153,6 +1234,12 @@ impl Parser {
             | Token::Spaceship => Ok(20),
             Token::Pipe => Ok(21),
             Token::Caret | Token::Sharp | Token::ShiftRight | Token::ShiftLeft => Ok(22),
+            Token::Arrow
+            | Token::LongArrow
+            | Token::HashArrow
+            | Token::HashLongArrow
+            | Token::AtArrow
+            | Token::QuestionMark => Ok(22),
             Token::Ampersand => Ok(23),
             Token::Plus | Token::Minus => Ok(Self::PLUS_MINUS_PREC),
             Token::Mul | Token::Div | Token::Mod | Token::StringConcat => Ok(40),
@@ -1378,6 +1465,8 @@ impl Parser {
             self.parse_create_source(false, or_replace)
         } else if self.parse_keywords(&[Keyword::MATERIALIZED, Keyword::SOURCE]) {
             self.parse_create_source(true, or_replace)
+        } else if self.parse_keyword(Keyword::SINK) {
+            self.parse_create_sink(or_replace)
         } else if or_replace {
             self.expected(
                 "[EXTERNAL] TABLE or [MATERIALIZED] VIEW after CREATE OR REPLACE",
@@ -1391,6 +1480,8 @@ impl Parser {
             self.parse_create_schema()
         } else if self.parse_keyword(Keyword::DATABASE) {
             self.parse_create_database()
+        } else if self.parse_keyword(Keyword::USER) {
+            self.parse_create_user()
         } else {
             self.expected("an object type after CREATE", self.peek_token())
         }
@@ -1458,6 +1549,29 @@ impl Parser {
         })
     }
 
+    // CREATE [OR REPLACE]?
+    // SINK
+    // [IF NOT EXISTS]?
+    // <sink_name: Ident>
+    // FROM
+    // <materialized_view: Ident>
+    // [WITH (properties)]?
+    pub fn parse_create_sink(&mut self, _or_replace: bool) -> Result<Statement, ParserError> {
+        Ok(Statement::CreateSink {
+            stmt: CreateSinkStatement::parse_to(self)?,
+        })
+    }
+
+    // CREATE USER name [ [ WITH ] option [ ... ] ]
+    // where option can be:
+    //       SUPERUSER | NOSUPERUSER
+    //     | CREATEDB | NOCREATEDB
+    //     | LOGIN | NOLOGIN
+    //     | [ ENCRYPTED ] PASSWORD 'password' | PASSWORD NULL
+    fn parse_create_user(&mut self) -> Result<Statement, ParserError> {
+        Ok(Statement::CreateUser(CreateUserStatement::parse_to(self)?))
+    }
+
     fn parse_with_properties(&mut self) -> Result<Vec<SqlOption>, ParserError> {
         Ok(self.parse_options(Keyword::WITH)?.to_vec())
     }
@@ -1523,15 +1637,37 @@ impl Parser {
     }
 
     pub fn parse_columns(&mut self) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>), ParserError> {
+        let (columns, constraints, _) = self.parse_columns_inner(false)?;
+        Ok((columns, constraints))
+    }
+
+    /// Parses the column list of `CREATE SOURCE`, which may also contain
+    /// `WATERMARK FOR column AS expr`.
+    pub fn parse_columns_with_watermark(
+        &mut self,
+    ) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>, Vec<SourceWatermark>), ParserError> {
+        self.parse_columns_inner(true)
+    }
+
+    fn parse_columns_inner(
+        &mut self,
+        allow_watermark: bool,
+    ) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>, Vec<SourceWatermark>), ParserError> {
         let mut columns = vec![];
         let mut constraints = vec![];
+        let mut watermarks = vec![];
         if !self.consume_token(&Token::LParen) || self.consume_token(&Token::RParen) {
-            return Ok((columns, constraints));
+            return Ok((columns, constraints, watermarks));
         }
 
         loop {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
+            } else if allow_watermark && self.parse_keywords(&[Keyword::WATERMARK, Keyword::FOR]) {
+                let column = self.parse_identifier()?;
+                self.expect_keyword(Keyword::AS)?;
+                let expr = self.parse_expr()?;
+                watermarks.push(SourceWatermark { column, expr });
             } else if let Token::Word(_) = self.peek_token() {
                 columns.push(self.parse_column_def()?);
             } else {
@@ -1546,7 +1682,7 @@ impl Parser {
             }
         }
 
-        Ok((columns, constraints))
+        Ok((columns, constraints, watermarks))
     }
 
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
@@ -1733,8 +1869,48 @@ impl Parser {
     }
 
     pub fn parse_alter(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword(Keyword::TABLE)?;
-        self.parse_alter_table()
+        if self.parse_keyword(Keyword::TABLE) {
+            self.parse_alter_table()
+        } else if self.parse_keyword(Keyword::SOURCE) {
+            self.parse_alter_source()
+        } else if self.parse_keyword(Keyword::USER) {
+            self.parse_alter_user()
+        } else {
+            self.expected("TABLE, SOURCE or USER after ALTER", self.peek_token())
+        }
+    }
+
+    // ALTER SOURCE name ADD [ COLUMN ] column_def
+    // ALTER SOURCE name DROP [ COLUMN ] [ IF EXISTS ] column_name [ CASCADE ]
+    pub fn parse_alter_source(&mut self) -> Result<Statement, ParserError> {
+        let source_name = self.parse_object_name()?;
+        let operation = if self.parse_keyword(Keyword::ADD) {
+            let _ = self.parse_keyword(Keyword::COLUMN);
+            let column_def = self.parse_column_def()?;
+            AlterTableOperation::AddColumn { column_def }
+        } else if self.parse_keyword(Keyword::DROP) {
+            let _ = self.parse_keyword(Keyword::COLUMN);
+            let if_exists = self.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+            let column_name = self.parse_identifier()?;
+            let cascade = self.parse_keyword(Keyword::CASCADE);
+            AlterTableOperation::DropColumn {
+                column_name,
+                if_exists,
+                cascade,
+            }
+        } else {
+            return self.expected("ADD or DROP after ALTER SOURCE", self.peek_token());
+        };
+        Ok(Statement::AlterSource {
+            name: source_name,
+            operation,
+        })
+    }
+
+    // ALTER USER name [ [ WITH ] option [ ... ] ]
+    // ALTER USER name RENAME TO new_name
+    pub fn parse_alter_user(&mut self) -> Result<Statement, ParserError> {
+        Ok(Statement::AlterUser(AlterUserStatement::parse_to(self)?))
     }
 
     pub fn parse_alter_table(&mut self) -> Result<Statement, ParserError> {
@@ -1821,18 +1997,79 @@ impl Parser {
 
     /// Parse a copy statement
     pub fn parse_copy(&mut self) -> Result<Statement, ParserError> {
-        let table_name = self.parse_object_name()?;
-        let columns = self.parse_parenthesized_column_list(Optional)?;
-        self.expect_keywords(&[Keyword::FROM, Keyword::STDIN])?;
-        self.expect_token(&Token::SemiColon)?;
-        let values = self.parse_tsv();
+        let source = if self.consume_token(&Token::LParen) {
+            let query = self.parse_query()?;
+            self.expect_token(&Token::RParen)?;
+            CopySource::Query(Box::new(query))
+        } else {
+            let table_name = self.parse_object_name()?;
+            let columns = self.parse_parenthesized_column_list(Optional)?;
+            CopySource::Table {
+                table_name,
+                columns,
+            }
+        };
+        let to = match self.parse_one_of_keywords(&[Keyword::FROM, Keyword::TO]) {
+            Some(Keyword::FROM) => false,
+            Some(Keyword::TO) => true,
+            _ => return self.expected("FROM or TO", self.peek_token()),
+        };
+        if to {
+            self.expect_keyword(Keyword::STDOUT)?;
+        } else if let CopySource::Query(_) = source {
+            return parser_err!("COPY FROM does not support a query as the target");
+        } else {
+            self.expect_keyword(Keyword::STDIN)?;
+        }
+        let options = if self.parse_keyword(Keyword::WITH) || self.peek_token() == Token::LParen {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Parser::parse_copy_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        // The data may be inlined after the statement, as in the output of `pg_dump`.
+        let values = if !to && self.consume_token(&Token::SemiColon) {
+            self.parse_tsv()
+        } else {
+            vec![]
+        };
         Ok(Statement::Copy {
-            table_name,
-            columns,
+            source,
+            to,
+            options,
             values,
         })
     }
 
+    fn parse_copy_option(&mut self) -> Result<CopyOption, ParserError> {
+        match self.parse_one_of_keywords(&[
+            Keyword::FORMAT,
+            Keyword::DELIMITER,
+            Keyword::NULL,
+            Keyword::HEADER,
+        ]) {
+            Some(Keyword::FORMAT) => Ok(CopyOption::Format(self.parse_identifier()?)),
+            Some(Keyword::DELIMITER) => {
+                let delimiter = self.parse_literal_string()?;
+                let mut chars = delimiter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(CopyOption::Delimiter(c)),
+                    _ => parser_err!("COPY delimiter must be a single character"),
+                }
+            }
+            Some(Keyword::NULL) => Ok(CopyOption::Null(self.parse_literal_string()?)),
+            Some(Keyword::HEADER) => {
+                match self.parse_one_of_keywords(&[Keyword::TRUE, Keyword::FALSE]) {
+                    Some(Keyword::FALSE) => Ok(CopyOption::Header(false)),
+                    _ => Ok(CopyOption::Header(true)),
+                }
+            }
+            _ => self.expected("COPY option", self.peek_token()),
+        }
+    }
+
     /// Parse a tab separated values in
     /// COPY payload
     fn parse_tsv(&mut self) -> Vec<Option<String>> {
@@ -2042,6 +2279,7 @@ impl Parser {
                 }
                 Keyword::STRUCT => Ok(DataType::Struct(self.parse_struct_data_type()?)),
                 Keyword::BYTEA => Ok(DataType::Bytea),
+                Keyword::JSONB => Ok(DataType::Jsonb),
                 Keyword::NUMERIC | Keyword::DECIMAL | Keyword::DEC => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
                     Ok(DataType::Decimal(precision, scale))
@@ -2500,19 +2738,30 @@ impl Parser {
         })
     }
 
+    fn parse_set_variable_value(&mut self) -> Result<SetVariableValue, ParserError> {
+        let token = self.peek_token();
+        match (self.parse_value(), token) {
+            (Ok(value), _) => Ok(SetVariableValue::Literal(value)),
+            (Err(_), Token::Word(ident)) => Ok(SetVariableValue::Ident(ident.to_ident())),
+            (Err(_), unexpected) => self.expected("variable value", unexpected),
+        }
+    }
+
     pub fn parse_set(&mut self) -> Result<Statement, ParserError> {
         let modifier = self.parse_one_of_keywords(&[Keyword::SESSION, Keyword::LOCAL]);
         let variable = self.parse_identifier()?;
+        if variable.value.eq_ignore_ascii_case("TIME") && self.parse_keyword(Keyword::ZONE) {
+            // `SET TIME ZONE x` is an alias of `SET timezone = x`.
+            return Ok(Statement::SetVariable {
+                local: modifier == Some(Keyword::LOCAL),
+                variable: Ident::new("timezone"),
+                value: vec![self.parse_set_variable_value()?],
+            });
+        }
         if self.consume_token(&Token::Eq) || self.parse_keyword(Keyword::TO) {
             let mut values = vec![];
             loop {
-                let token = self.peek_token();
-                let value = match (self.parse_value(), token) {
-                    (Ok(value), _) => SetVariableValue::Literal(value),
-                    (Err(_), Token::Word(ident)) => SetVariableValue::Ident(ident.to_ident()),
-                    (Err(_), unexpected) => self.expected("variable value", unexpected)?,
-                };
-                values.push(value);
+                values.push(self.parse_set_variable_value()?);
                 if self.consume_token(&Token::Comma) {
                     continue;
                 }
@@ -2811,10 +3060,14 @@ impl Parser {
                 self.parse_comma_separated(Parser::parse_grant_permission)?
                     .into_iter()
                     .map(|(kw, columns)| match kw {
+                        Keyword::CONNECT => Action::Connect,
+                        Keyword::CREATE => Action::Create,
                         Keyword::DELETE => Action::Delete,
+                        Keyword::EXECUTE => Action::Execute,
                         Keyword::INSERT => Action::Insert { columns },
                         Keyword::REFERENCES => Action::References { columns },
                         Keyword::SELECT => Action::Select { columns },
+                        Keyword::TEMPORARY => Action::Temporary,
                         Keyword::TRIGGER => Action::Trigger,
                         Keyword::TRUNCATE => Action::Truncate,
                         Keyword::UPDATE => Action::Update { columns },
@@ -2845,13 +3098,22 @@ impl Parser {
             GrantObjects::AllSequencesInSchema {
                 schemas: self.parse_comma_separated(Parser::parse_object_name)?,
             }
+        } else if self.parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW]) {
+            GrantObjects::Mviews(self.parse_comma_separated(Parser::parse_object_name)?)
         } else {
-            let object_type =
-                self.parse_one_of_keywords(&[Keyword::SEQUENCE, Keyword::SCHEMA, Keyword::TABLE]);
+            let object_type = self.parse_one_of_keywords(&[
+                Keyword::DATABASE,
+                Keyword::SEQUENCE,
+                Keyword::SCHEMA,
+                Keyword::SOURCE,
+                Keyword::TABLE,
+            ]);
             let objects = self.parse_comma_separated(Parser::parse_object_name);
             match object_type {
+                Some(Keyword::DATABASE) => GrantObjects::Databases(objects?),
                 Some(Keyword::SCHEMA) => GrantObjects::Schemas(objects?),
                 Some(Keyword::SEQUENCE) => GrantObjects::Sequences(objects?),
+                Some(Keyword::SOURCE) => GrantObjects::Sources(objects?),
                 Some(Keyword::TABLE) | None => GrantObjects::Tables(objects?),
                 _ => unreachable!(),
             }
@@ -2894,6 +3156,8 @@ impl Parser {
 
     /// Parse a REVOKE statement
     pub fn parse_revoke(&mut self) -> Result<Statement, ParserError> {
+        let revoke_grant_option =
+            self.parse_keywords(&[Keyword::GRANT, Keyword::OPTION, Keyword::FOR]);
         let (privileges, objects) = self.parse_grant_revoke_privileges_objects()?;
 
         self.expect_keyword(Keyword::FROM)?;
@@ -2910,6 +3174,7 @@ impl Parser {
         }
 
         Ok(Statement::Revoke {
+            revoke_grant_option,
             privileges,
             objects,
             grantees,
@@ -138,6 +138,20 @@ pub enum Token {
     PGSquareRoot,
     /// `||/` , a cube root math operator in PostgreSQL
     PGCubeRoot,
+    /// `->`, access a jsonb field or element in PostgreSQL
+    Arrow,
+    /// `->>`, access a jsonb field or element as text in PostgreSQL
+    LongArrow,
+    /// `#>`, access a jsonb value at a path in PostgreSQL
+    HashArrow,
+    /// `#>>`, access a jsonb value at a path as text in PostgreSQL
+    HashLongArrow,
+    /// `@>`, the jsonb containment operator in PostgreSQL
+    AtArrow,
+    /// `?`, the jsonb key existence operator in PostgreSQL
+    QuestionMark,
+    /// `$1`, a positional parameter in a prepared statement
+    Parameter(String),
 }
 
 impl fmt::Display for Token {
@@ -193,6 +207,13 @@ impl fmt::Display for Token {
             Token::ShiftRight => f.write_str(">>"),
             Token::PGSquareRoot => f.write_str("|/"),
             Token::PGCubeRoot => f.write_str("||/"),
+            Token::Arrow => f.write_str("->"),
+            Token::LongArrow => f.write_str("->>"),
+            Token::HashArrow => f.write_str("#>"),
+            Token::HashLongArrow => f.write_str("#>>"),
+            Token::AtArrow => f.write_str("@>"),
+            Token::QuestionMark => f.write_str("?"),
+            Token::Parameter(ref s) => write!(f, "${}", s),
         }
     }
 }
@@ -475,6 +496,13 @@ impl<'a> Tokenizer<'a> {
                                 comment,
                             })))
                         }
+                        Some('>') => {
+                            chars.next(); // consume the '>'
+                            match chars.peek() {
+                                Some('>') => self.consume_and_return(chars, Token::LongArrow),
+                                _ => Ok(Some(Token::Arrow)),
+                            }
+                        }
                         // a regular '-' operator
                         _ => Ok(Some(Token::Minus)),
                     }
@@ -576,8 +604,36 @@ impl<'a> Tokenizer<'a> {
                         _ => Ok(Some(Token::Tilde)),
                     }
                 }
-                '#' => self.consume_and_return(chars, Token::Sharp),
-                '@' => self.consume_and_return(chars, Token::AtSign),
+                '#' => {
+                    chars.next(); // consume
+                    match chars.peek() {
+                        Some('>') => {
+                            chars.next();
+                            match chars.peek() {
+                                Some('>') => self.consume_and_return(chars, Token::HashLongArrow),
+                                _ => Ok(Some(Token::HashArrow)),
+                            }
+                        }
+                        _ => Ok(Some(Token::Sharp)),
+                    }
+                }
+                '@' => {
+                    chars.next(); // consume
+                    match chars.peek() {
+                        Some('>') => self.consume_and_return(chars, Token::AtArrow),
+                        _ => Ok(Some(Token::AtSign)),
+                    }
+                }
+                '?' => self.consume_and_return(chars, Token::QuestionMark),
+                '$' => {
+                    chars.next(); // consume
+                    let s = peeking_take_while(chars, |ch| ch.is_ascii_digit());
+                    if s.is_empty() {
+                        Ok(Some(Token::Char('$')))
+                    } else {
+                        Ok(Some(Token::Parameter(s)))
+                    }
+                }
                 other => self.consume_and_return(chars, Token::Char(other)),
             },
             None => Ok(None),
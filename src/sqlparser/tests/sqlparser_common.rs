@@ -715,6 +715,45 @@ fn parse_like() {
     chk(true);
 }
 
+#[test]
+fn parse_similar_to() {
+    fn chk(negated: bool) {
+        let sql = &format!(
+            "SELECT * FROM customers WHERE name {}SIMILAR TO '%(a|b)'",
+            if negated { "NOT " } else { "" }
+        );
+        let select = verified_only_select(sql);
+        assert_eq!(
+            Expr::SimilarTo {
+                expr: Box::new(Expr::Identifier(Ident::new("name"))),
+                negated,
+                pattern: Box::new(Expr::Value(Value::SingleQuotedString("%(a|b)".to_string()))),
+                escape_char: None,
+            },
+            select.selection.unwrap()
+        );
+
+        let sql = &format!(
+            "SELECT * FROM customers WHERE name {}SIMILAR TO '%#_a' ESCAPE '#' IS NULL",
+            if negated { "NOT " } else { "" }
+        );
+        let select = verified_only_select(sql);
+        assert_eq!(
+            Expr::IsNull(Box::new(Expr::SimilarTo {
+                expr: Box::new(Expr::Identifier(Ident::new("name"))),
+                negated,
+                pattern: Box::new(Expr::Value(Value::SingleQuotedString("%#_a".to_string()))),
+                escape_char: Some(Box::new(Expr::Value(Value::SingleQuotedString(
+                    "#".to_string()
+                )))),
+            })),
+            select.selection.unwrap()
+        );
+    }
+    chk(false);
+    chk(true);
+}
+
 #[test]
 fn parse_ilike() {
     fn chk(negated: bool) {
@@ -798,6 +837,36 @@ fn parse_in_subquery() {
     );
 }
 
+#[test]
+fn parse_quantified_subquery() {
+    let sql = "SELECT * FROM customers WHERE segment > ANY (SELECT segm FROM bar)";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        Expr::AnySubquery {
+            expr: Box::new(Expr::Identifier(Ident::new("segment"))),
+            op: BinaryOperator::Gt,
+            subquery: Box::new(verified_query("SELECT segm FROM bar")),
+        },
+        select.selection.unwrap()
+    );
+
+    let sql = "SELECT * FROM customers WHERE segment <> ALL (SELECT segm FROM bar)";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        Expr::AllSubquery {
+            expr: Box::new(Expr::Identifier(Ident::new("segment"))),
+            op: BinaryOperator::NotEq,
+            subquery: Box::new(verified_query("SELECT segm FROM bar")),
+        },
+        select.selection.unwrap()
+    );
+
+    one_statement_parses_to(
+        "SELECT * FROM customers WHERE segment = SOME (SELECT segm FROM bar)",
+        "SELECT * FROM customers WHERE segment = ANY (SELECT segm FROM bar)",
+    );
+}
+
 #[test]
 fn parse_string_agg() {
     let sql = "SELECT a || b";
@@ -1182,6 +1251,33 @@ fn parse_try_cast() {
     );
 }
 
+#[test]
+fn parse_at_time_zone() {
+    let sql = "SELECT ts AT TIME ZONE 'UTC'";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        &Expr::AtTimeZone {
+            timestamp: Box::new(Expr::Identifier(Ident::new("ts"))),
+            time_zone: Box::new(Expr::Value(Value::SingleQuotedString("UTC".into()))),
+        },
+        expr_from_projection(only(&select.projection)),
+    );
+
+    // `AT TIME ZONE` binds tighter than arithmetic operators.
+    let select = verified_only_select("SELECT ts + i AT TIME ZONE z");
+    assert_eq!(
+        &Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("ts"))),
+            op: BinaryOperator::Plus,
+            right: Box::new(Expr::AtTimeZone {
+                timestamp: Box::new(Expr::Identifier(Ident::new("i"))),
+                time_zone: Box::new(Expr::Identifier(Ident::new("z"))),
+            }),
+        },
+        expr_from_projection(only(&select.projection)),
+    );
+}
+
 #[test]
 fn parse_extract() {
     let sql = "SELECT EXTRACT(YEAR FROM d)";
@@ -3528,12 +3624,14 @@ fn test_revoke() {
     let sql = "REVOKE ALL PRIVILEGES ON users, auth FROM analyst CASCADE";
     match verified_stmt(sql) {
         Statement::Revoke {
+            revoke_grant_option,
             privileges,
             objects: GrantObjects::Tables(tables),
             grantees,
             cascade,
             granted_by,
         } => {
+            assert!(!revoke_grant_option);
             assert_eq!(
                 Privileges::All {
                     with_privileges_keyword: true
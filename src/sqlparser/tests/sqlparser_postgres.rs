@@ -383,6 +383,63 @@ PHP	₱ USD $
     // assert_eq!(sql, ast.to_string());
 }
 
+#[test]
+fn parse_copy_from_stdin() {
+    match verified_stmt("COPY t (a, b) FROM STDIN (FORMAT csv, DELIMITER ';', NULL 'null', HEADER)")
+    {
+        Statement::Copy {
+            source:
+                CopySource::Table {
+                    table_name,
+                    columns,
+                },
+            to: false,
+            options,
+            values,
+        } => {
+            assert_eq!("t", table_name.to_string());
+            assert_eq!(vec![Ident::new("a"), Ident::new("b")], columns);
+            assert_eq!(
+                vec![
+                    CopyOption::Format(Ident::new("csv")),
+                    CopyOption::Delimiter(';'),
+                    CopyOption::Null("null".to_string()),
+                    CopyOption::Header(true),
+                ],
+                options
+            );
+            assert!(values.is_empty());
+        }
+        _ => unreachable!(),
+    }
+
+    one_statement_parses_to(
+        "COPY t FROM STDIN WITH (FORMAT text, HEADER true)",
+        "COPY t FROM STDIN (FORMAT text, HEADER)",
+    );
+    verified_stmt("COPY t FROM STDIN (HEADER false)");
+}
+
+#[test]
+fn parse_copy_to_stdout() {
+    verified_stmt("COPY t TO STDOUT");
+    match verified_stmt("COPY (SELECT a FROM t WHERE a > 1) TO STDOUT (FORMAT csv)") {
+        Statement::Copy {
+            source: CopySource::Query(query),
+            to: true,
+            options,
+            ..
+        } => {
+            assert_eq!("SELECT a FROM t WHERE a > 1", query.to_string());
+            assert_eq!(vec![CopyOption::Format(Ident::new("csv"))], options);
+        }
+        _ => unreachable!(),
+    }
+
+    assert!(parse_sql_statements("COPY (SELECT 1) FROM STDIN").is_err());
+    assert!(parse_sql_statements("COPY t FROM STDIN (DELIMITER ',,')").is_err());
+}
+
 #[test]
 fn parse_set() {
     let stmt = verified_stmt("SET a = b");
@@ -439,6 +496,11 @@ fn parse_set() {
 
     one_statement_parses_to("SET a TO b", "SET a = b");
     one_statement_parses_to("SET SESSION a = b", "SET a = b");
+    one_statement_parses_to(
+        "SET TIME ZONE 'Asia/Shanghai'",
+        "SET timezone = 'Asia/Shanghai'",
+    );
+    one_statement_parses_to("SET LOCAL TIME ZONE LOCAL", "SET LOCAL timezone = LOCAL");
 
     assert_eq!(
         parse_sql_statements("SET"),
@@ -629,6 +691,33 @@ fn parse_pg_bitwise_binary_ops() {
     }
 }
 
+#[test]
+fn parse_pg_json_binary_ops() {
+    let json_ops = &[
+        ("->", BinaryOperator::PGJsonAccess),
+        ("->>", BinaryOperator::PGJsonAccessText),
+        ("#>", BinaryOperator::PGJsonPathAccess),
+        ("#>>", BinaryOperator::PGJsonPathAccessText),
+        ("@>", BinaryOperator::PGJsonContains),
+        ("?", BinaryOperator::PGJsonExists),
+    ];
+
+    for (str_op, op) in json_ops {
+        let select = verified_only_select(&format!("SELECT a {} b", &str_op));
+        assert_eq!(
+            SelectItem::UnnamedExpr(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: op.clone(),
+                right: Box::new(Expr::Identifier(Ident::new("b"))),
+            }),
+            select.projection[0]
+        );
+    }
+
+    one_statement_parses_to("SELECT a->'b'->>0", "SELECT a -> 'b' ->> 0");
+    verified_stmt("SELECT CAST('{}' AS JSONB)");
+}
+
 #[test]
 fn parse_pg_unary_ops() {
     let pg_unary_ops = &[
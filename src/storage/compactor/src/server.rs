@@ -85,6 +85,7 @@ pub async fn compactor_serve(
         storage_config.data_directory.to_string(),
         storage_config.block_cache_capacity_mb * (1 << 20),
         storage_config.meta_cache_capacity_mb * (1 << 20),
+        None,
     ));
     monitor_cache(sstable_store.clone(), &registry).unwrap();
 
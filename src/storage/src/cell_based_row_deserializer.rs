@@ -14,12 +14,12 @@
 
 use std::collections::HashMap;
 
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
 use risingwave_common::array::Row;
 use risingwave_common::catalog::{ColumnDesc, ColumnId};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::Datum;
-use risingwave_common::util::ordered::deserialize_column_id;
+use risingwave_common::util::ordered::{deserialize_column_id, SENTINEL_CELL_ID};
 use risingwave_common::util::value_encoding::deserialize_cell;
 
 #[derive(Clone)]
@@ -29,6 +29,10 @@ pub struct CellBasedRowDeserializer {
 
     data: Vec<Datum>,
 
+    /// The next column id of the table when the current row was written, read from the sentinel
+    /// cell. `None` if the row was written before the sentinel cell recorded it.
+    next_column_id: Option<i32>,
+
     /// `CellBasedRowDeserializer` does not deserialize pk itself. We need to take the key in as
     /// we have to know the cell id of each datum. So `pk_bytes` serves as an additional check
     /// which should also be done on the caller side.
@@ -46,6 +50,7 @@ impl CellBasedRowDeserializer {
         Self {
             columns,
             data: vec![None; num_cells],
+            next_column_id: None,
             pk_bytes: None,
         }
     }
@@ -72,7 +77,11 @@ impl CellBasedRowDeserializer {
             self.pk_bytes = Some(cur_pk_bytes.to_vec());
         }
 
-        if let Some((column_desc, index)) = self.columns.get(&cell_id) {
+        if cell_id == SENTINEL_CELL_ID {
+            if cell.len() == 4 {
+                self.next_column_id = Some(cell.clone().get_i32_le());
+            }
+        } else if let Some((column_desc, index)) = self.columns.get(&cell_id) {
             if let Some(datum) = deserialize_cell(&mut cell.clone(), &column_desc.data_type)? {
                 let old = self.data.get_mut(*index).unwrap().replace(datum);
                 assert!(old.is_none());
@@ -87,7 +96,17 @@ impl CellBasedRowDeserializer {
     /// Take the remaining data out of the deserializer.
     pub fn take(&mut self) -> Option<(Vec<u8>, Row)> {
         let cur_pk_bytes = self.pk_bytes.take();
+        let next_column_id = self.next_column_id.take();
         cur_pk_bytes.map(|bytes| {
+            // Columns added after the row was written have no cell, and read as their defaults.
+            for (column_desc, index) in self.columns.values() {
+                let datum = &mut self.data[*index];
+                if datum.is_none()
+                    && next_column_id.map_or(true, |id| column_desc.column_id.get_id() >= id)
+                {
+                    *datum = column_desc.default_value.clone();
+                }
+            }
             let ret = self.data.iter_mut().map(Option::take).collect::<Vec<_>>();
             (bytes, Row(ret))
         })
@@ -99,6 +118,7 @@ impl CellBasedRowDeserializer {
         self.data.iter_mut().for_each(|datum| {
             datum.take();
         });
+        self.next_column_id.take();
         self.pk_bytes.take();
     }
 }
@@ -113,6 +133,7 @@ mod tests {
     use risingwave_common::util::ordered::serialize_pk_and_row_state;
 
     use crate::cell_based_row_deserializer::CellBasedRowDeserializer;
+    use crate::cell_based_row_serializer::CellBasedRowSerializer;
 
     #[test]
     fn test_cell_based_deserializer() {
@@ -170,4 +191,104 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_cell_based_deserializer_with_added_column() {
+        let column_ids = vec![ColumnId::from(0), ColumnId::from(1)];
+        let pk1 = vec![0u8, 0u8, 0u8, 0u8];
+        let pk2 = vec![0u8, 0u8, 0u8, 1u8];
+        let row1 = Row(vec![
+            Some(ScalarImpl::Int64(1)),
+            Some(ScalarImpl::Utf8("abc".to_string())),
+        ]);
+        let row2 = Row(vec![Some(ScalarImpl::Int64(2)), None]);
+        // The rows are written before the column 2 is added.
+        let bytes1 = serialize_pk_and_row_state(&pk1, &Some(row1), &column_ids).unwrap();
+        let bytes2 = serialize_pk_and_row_state(&pk2, &Some(row2), &column_ids).unwrap();
+        let bytes = [bytes1, bytes2].concat();
+
+        let table_column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::from(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::from(1), DataType::Varchar),
+            ColumnDesc::unnamed(ColumnId::from(2), DataType::Float64),
+        ];
+        let mut result = vec![];
+        let mut deserializer = CellBasedRowDeserializer::new(table_column_descs);
+        for (key_bytes, value_bytes) in bytes {
+            let pk_and_row = deserializer
+                .deserialize(&Bytes::from(key_bytes), &Bytes::from(value_bytes.unwrap()))
+                .unwrap();
+            if let Some(pk_and_row) = pk_and_row {
+                result.push(pk_and_row.1);
+            }
+        }
+        result.push(deserializer.take().unwrap().1);
+
+        // The added column is read as `NULL`.
+        assert_eq!(
+            result,
+            vec![
+                Row(vec![
+                    Some(ScalarImpl::Int64(1)),
+                    Some(ScalarImpl::Utf8("abc".to_string())),
+                    None,
+                ]),
+                Row(vec![Some(ScalarImpl::Int64(2)), None, None]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cell_based_deserializer_with_added_column_default() {
+        let mut serializer = CellBasedRowSerializer::new();
+        let pk1 = vec![0u8, 0u8, 0u8, 0u8];
+        let pk2 = vec![0u8, 0u8, 0u8, 1u8];
+        // The row 1 is written before the column 2 is added, and the row 2 sets it to `NULL`.
+        let bytes1 = serializer
+            .serialize(
+                &pk1,
+                Row(vec![Some(ScalarImpl::Int64(1))]),
+                &[ColumnId::from(0)],
+            )
+            .unwrap();
+        let bytes2 = serializer
+            .serialize(
+                &pk2,
+                Row(vec![Some(ScalarImpl::Int64(2)), None]),
+                &[ColumnId::from(0), ColumnId::from(1)],
+            )
+            .unwrap();
+        let bytes = [bytes1, bytes2].concat();
+
+        let table_column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::from(0), DataType::Int64),
+            ColumnDesc {
+                default_value: Some(ScalarImpl::Float64(1.5f64.into())),
+                ..ColumnDesc::unnamed(ColumnId::from(1), DataType::Float64)
+            },
+        ];
+        let mut result = vec![];
+        let mut deserializer = CellBasedRowDeserializer::new(table_column_descs);
+        for (key_bytes, value_bytes) in bytes {
+            let pk_and_row = deserializer
+                .deserialize(&Bytes::from(key_bytes), &Bytes::from(value_bytes))
+                .unwrap();
+            if let Some(pk_and_row) = pk_and_row {
+                result.push(pk_and_row.1);
+            }
+        }
+        result.push(deserializer.take().unwrap().1);
+
+        // The added column is read as its default only in the row written before it.
+        assert_eq!(
+            result,
+            vec![
+                Row(vec![
+                    Some(ScalarImpl::Int64(1)),
+                    Some(ScalarImpl::Float64(1.5f64.into())),
+                ]),
+                Row(vec![Some(ScalarImpl::Int64(2)), None]),
+            ]
+        );
+    }
 }
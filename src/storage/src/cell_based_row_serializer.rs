@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use bytes::BufMut;
 use itertools::Itertools;
 use risingwave_common::array::Row;
 use risingwave_common::catalog::ColumnId;
@@ -41,11 +42,9 @@ impl CellBasedRowSerializer {
         row: Row,
         column_ids: &[ColumnId],
     ) -> Result<Vec<(KeyBytes, ValueBytes)>> {
-        let res = serialize_pk_and_row(pk, &row, column_ids)?
-            .into_iter()
-            .flatten()
-            .collect_vec();
-        Ok(res)
+        let mut res = serialize_pk_and_row(pk, &row, column_ids)?;
+        fill_sentinel_cell(&mut res, column_ids);
+        Ok(res.into_iter().flatten().collect_vec())
     }
 
     /// Serialize key and value. Each column id will occupy a position in Vec. For `column_ids` that
@@ -57,7 +56,8 @@ impl CellBasedRowSerializer {
         row: Row,
         column_ids: &[ColumnId],
     ) -> Result<Vec<Option<(KeyBytes, ValueBytes)>>> {
-        let res = serialize_pk_and_row(pk, &row, column_ids)?;
+        let mut res = serialize_pk_and_row(pk, &row, column_ids)?;
+        fill_sentinel_cell(&mut res, column_ids);
         Ok(res)
     }
 
@@ -80,3 +80,17 @@ impl CellBasedRowSerializer {
         Ok(results)
     }
 }
+
+/// Writes the next column id of the table into the sentinel cell, which is the last cell of a row.
+/// Columns with a larger id are added by `ALTER TABLE ADD COLUMN` after the row is written, and
+/// the deserializer fills their default values.
+fn fill_sentinel_cell(cells: &mut [Option<(KeyBytes, ValueBytes)>], column_ids: &[ColumnId]) {
+    let next_column_id = column_ids
+        .iter()
+        .map(|id| id.get_id() + 1)
+        .max()
+        .unwrap_or(0);
+    if let Some(Some((_, value))) = cells.last_mut() {
+        value.put_i32_le(next_column_id);
+    }
+}
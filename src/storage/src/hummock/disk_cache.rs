@@ -0,0 +1,366 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use parking_lot::Mutex;
+use risingwave_hummock_sdk::HummockSSTableId;
+
+use super::{xxhash64_checksum, xxhash64_verify, HummockError, HummockResult};
+
+/// Every cache file ends with the checksum of its content.
+const CHECKSUM_LEN: usize = 8;
+
+/// Suffix of the files being written, which are discarded if left over by a crash.
+const TEMP_FILE_SUFFIX: &str = ".tmp";
+
+/// Key of an entry in [`DiskCache`], which is stored in a file named after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiskCacheKey {
+    Meta(HummockSSTableId),
+    Block(HummockSSTableId, u64),
+}
+
+impl DiskCacheKey {
+    pub fn sst_id(&self) -> HummockSSTableId {
+        match self {
+            Self::Meta(sst_id) | Self::Block(sst_id, _) => *sst_id,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        match self {
+            Self::Meta(sst_id) => format!("{}.meta", sst_id),
+            Self::Block(sst_id, block_idx) => format!("{}-{}.block", sst_id, block_idx),
+        }
+    }
+
+    fn from_file_name(name: &str) -> Option<Self> {
+        if let Some(sst_id) = name.strip_suffix(".meta") {
+            return Some(Self::Meta(sst_id.parse().ok()?));
+        }
+        let (sst_id, block_idx) = name.strip_suffix(".block")?.split_once('-')?;
+        Some(Self::Block(sst_id.parse().ok()?, block_idx.parse().ok()?))
+    }
+}
+
+struct DiskCacheEntry {
+    size: usize,
+    /// Position of the entry in the LRU list.
+    seq: u64,
+}
+
+#[derive(Default)]
+struct DiskCacheCore {
+    entries: HashMap<DiskCacheKey, DiskCacheEntry>,
+    /// Keys ordered from the least recently used one.
+    lru: BTreeMap<u64, DiskCacheKey>,
+    next_seq: u64,
+    usage: usize,
+}
+
+impl DiskCacheCore {
+    /// Marks the entry as the most recently used one, and returns whether it exists.
+    fn touch(&mut self, key: &DiskCacheKey) -> bool {
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.lru.remove(&entry.seq);
+                entry.seq = self.next_seq;
+                self.lru.insert(self.next_seq, *key);
+                self.next_seq += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, key: DiskCacheKey, size: usize) {
+        self.remove(&key);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.entries.insert(key, DiskCacheEntry { size, seq });
+        self.lru.insert(seq, key);
+        self.usage += size;
+    }
+
+    fn remove(&mut self, key: &DiskCacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.lru.remove(&entry.seq);
+            self.usage -= entry.size;
+        }
+    }
+
+    /// Evicts the least recently used entries until the usage fits in `capacity`, and returns the
+    /// evicted keys, whose files should be removed.
+    fn evict(&mut self, capacity: usize) -> Vec<DiskCacheKey> {
+        let mut evicted = vec![];
+        while self.usage > capacity {
+            let (_, key) = self.lru.pop_first().unwrap();
+            let entry = self.entries.remove(&key).unwrap();
+            self.usage -= entry.size;
+            evicted.push(key);
+        }
+        evicted
+    }
+}
+
+struct DiskCacheInner {
+    dir: PathBuf,
+    capacity: usize,
+    core: Mutex<DiskCacheCore>,
+    next_temp_file_id: AtomicU64,
+}
+
+/// `DiskCache` is a persistent cache of SST blocks and metas on the local disk, which sits between
+/// the in-memory caches of [`super::SstableStore`] and the remote object store.
+///
+/// Each entry is stored in its own file, written to a temporary file first and then renamed, and
+/// verified by a checksum on read. So the index can always be rebuilt from the directory after a
+/// restart or a crash.
+#[derive(Clone)]
+pub struct DiskCache {
+    inner: Arc<DiskCacheInner>,
+}
+
+impl DiskCache {
+    /// Opens the cache in `dir` and recovers the entries left by previous runs. The recovered
+    /// entries are ordered by the modification time of their files to approximate the recency.
+    pub async fn open(dir: impl Into<PathBuf>, capacity: usize) -> HummockResult<Self> {
+        let dir = dir.into();
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(HummockError::disk_cache_error)?;
+
+        let mut files = vec![];
+        let mut read_dir = tokio::fs::read_dir(&dir)
+            .await
+            .map_err(HummockError::disk_cache_error)?;
+        while let Some(dir_entry) = read_dir
+            .next_entry()
+            .await
+            .map_err(HummockError::disk_cache_error)?
+        {
+            let metadata = dir_entry
+                .metadata()
+                .await
+                .map_err(HummockError::disk_cache_error)?;
+            if !metadata.is_file() {
+                tracing::warn!("skip {:?} in disk cache, not a file", dir_entry.path());
+                continue;
+            }
+            let file_name = dir_entry.file_name();
+            let file_name = file_name.to_str();
+            match file_name.and_then(DiskCacheKey::from_file_name) {
+                Some(key) => files.push((
+                    metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    key,
+                    metadata.len() as usize,
+                )),
+                // Temporary files of interrupted writes.
+                None if file_name.map_or(false, |name| name.ends_with(TEMP_FILE_SUFFIX)) => {
+                    tokio::fs::remove_file(dir_entry.path())
+                        .await
+                        .map_err(HummockError::disk_cache_error)?;
+                }
+                None => {
+                    tracing::warn!("skip {:?} in disk cache, unknown file", dir_entry.path());
+                }
+            }
+        }
+        files.sort_by_key(|(modified, _, _)| *modified);
+
+        let mut core = DiskCacheCore::default();
+        for (_, key, size) in files {
+            core.insert(key, size);
+        }
+        let evicted = core.evict(capacity);
+
+        let cache = Self {
+            inner: Arc::new(DiskCacheInner {
+                dir,
+                capacity,
+                core: Mutex::new(core),
+                next_temp_file_id: AtomicU64::new(0),
+            }),
+        };
+        cache.remove_files(evicted).await;
+        Ok(cache)
+    }
+
+    pub async fn get(&self, key: DiskCacheKey) -> Option<Bytes> {
+        if !self.inner.core.lock().touch(&key) {
+            return None;
+        }
+        let path = self.inner.dir.join(key.file_name());
+        let data = match tokio::fs::read(&path).await {
+            Ok(data) => Self::decode(Bytes::from(data)),
+            Err(e) => Err(HummockError::disk_cache_error(e)),
+        };
+        match data {
+            Ok(data) => Some(data),
+            Err(e) => {
+                tracing::warn!("failed to read {:?} from disk cache: {}", key, e);
+                self.inner.core.lock().remove(&key);
+                self.remove_files(vec![key]).await;
+                None
+            }
+        }
+    }
+
+    pub async fn insert(&self, key: DiskCacheKey, data: &[u8]) -> HummockResult<()> {
+        let size = data.len() + CHECKSUM_LEN;
+        if size > self.inner.capacity {
+            return Ok(());
+        }
+        let mut buf = BytesMut::with_capacity(size);
+        buf.put_slice(data);
+        buf.put_u64_le(xxhash64_checksum(data));
+
+        let temp_file_id = self.inner.next_temp_file_id.fetch_add(1, Ordering::Relaxed);
+        let temp_path = self.inner.dir.join(format!(
+            "{}.{}{}",
+            key.file_name(),
+            temp_file_id,
+            TEMP_FILE_SUFFIX
+        ));
+        let path = self.inner.dir.join(key.file_name());
+        let write_result = match tokio::fs::write(&temp_path, &buf).await {
+            Ok(()) => tokio::fs::rename(&temp_path, &path).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = write_result {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(HummockError::disk_cache_error(e));
+        }
+
+        let evicted = {
+            let mut core = self.inner.core.lock();
+            core.insert(key, size);
+            core.evict(self.inner.capacity)
+        };
+        self.remove_files(evicted).await;
+        Ok(())
+    }
+
+    /// Total size of the cached files in bytes.
+    pub fn size(&self) -> usize {
+        self.inner.core.lock().usage
+    }
+
+    fn decode(mut data: Bytes) -> HummockResult<Bytes> {
+        if data.len() < CHECKSUM_LEN {
+            return Err(HummockError::disk_cache_error("truncated file"));
+        }
+        let mut checksum = data.split_off(data.len() - CHECKSUM_LEN);
+        xxhash64_verify(&data, checksum.get_u64_le())?;
+        Ok(data)
+    }
+
+    async fn remove_files(&self, keys: Vec<DiskCacheKey>) {
+        for key in keys {
+            if let Err(e) = tokio::fs::remove_file(self.inner.dir.join(key.file_name())).await
+                && e.kind() != ErrorKind::NotFound
+            {
+                tracing::warn!("failed to remove {:?} from disk cache: {}", key, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path(), 2 * (8 + CHECKSUM_LEN))
+            .await
+            .unwrap();
+
+        cache
+            .insert(DiskCacheKey::Meta(1), b"meta0001")
+            .await
+            .unwrap();
+        cache
+            .insert(DiskCacheKey::Block(1, 0), b"block000")
+            .await
+            .unwrap();
+        assert_eq!(cache.size(), 2 * (8 + CHECKSUM_LEN));
+        assert_eq!(
+            cache.get(DiskCacheKey::Meta(1)).await.unwrap(),
+            Bytes::from_static(b"meta0001")
+        );
+
+        // The least recently used block is evicted.
+        cache
+            .insert(DiskCacheKey::Block(1, 1), b"block001")
+            .await
+            .unwrap();
+        assert!(cache.get(DiskCacheKey::Block(1, 0)).await.is_none());
+        assert!(!dir.path().join("1-0.block").exists());
+        assert_eq!(
+            cache.get(DiskCacheKey::Block(1, 1)).await.unwrap(),
+            Bytes::from_static(b"block001")
+        );
+
+        // Entries larger than the capacity are not cached.
+        cache
+            .insert(DiskCacheKey::Block(2, 0), &[0; 64])
+            .await
+            .unwrap();
+        assert!(cache.get(DiskCacheKey::Block(2, 0)).await.is_none());
+        assert!(cache.get(DiskCacheKey::Meta(1)).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_recovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path(), 1024).await.unwrap();
+        cache
+            .insert(DiskCacheKey::Meta(1), b"meta0001")
+            .await
+            .unwrap();
+        cache
+            .insert(DiskCacheKey::Block(1, 0), b"block000")
+            .await
+            .unwrap();
+        drop(cache);
+
+        // Simulate a write interrupted by a crash and a corrupted file.
+        std::fs::write(dir.path().join("1-1.block.0.tmp"), b"block001").unwrap();
+        std::fs::write(dir.path().join("1-0.block"), b"corrupted block").unwrap();
+        // Files not created by the cache are left alone.
+        std::fs::write(dir.path().join("unknown"), b"unknown").unwrap();
+        std::fs::create_dir(dir.path().join("1-2.block.0.tmp")).unwrap();
+
+        let cache = DiskCache::open(dir.path(), 1024).await.unwrap();
+        assert!(!dir.path().join("1-1.block.0.tmp").exists());
+        assert!(dir.path().join("unknown").exists());
+        assert!(dir.path().join("1-2.block.0.tmp").is_dir());
+        assert_eq!(
+            cache.get(DiskCacheKey::Meta(1)).await.unwrap(),
+            Bytes::from_static(b"meta0001")
+        );
+        assert!(cache.get(DiskCacheKey::Block(1, 0)).await.is_none());
+        assert!(!dir.path().join("1-0.block").exists());
+        assert_eq!(cache.size(), 8 + CHECKSUM_LEN);
+    }
+}
@@ -49,6 +49,8 @@ enum HummockErrorInner {
     ExpiredEpoch { safe_epoch: u64, epoch: u64 },
     #[error("CompactionExecutor error {0}.")]
     CompactionExecutor(String),
+    #[error("DiskCache error {0}.")]
+    DiskCacheError(String),
     #[error("Other error {0}.")]
     Other(String),
 }
@@ -114,6 +116,10 @@ impl HummockError {
         HummockErrorInner::CompactionExecutor(error.to_string()).into()
     }
 
+    pub fn disk_cache_error(error: impl ToString) -> HummockError {
+        HummockErrorInner::DiskCacheError(error.to_string()).into()
+    }
+
     pub fn other(error: impl ToString) -> HummockError {
         HummockErrorInner::Other(error.to_string()).into()
     }
@@ -56,7 +56,7 @@ pub fn mock_sstable_store() -> SstableStoreRef {
 
 pub fn mock_sstable_store_with_object_store(store: ObjectStoreRef) -> SstableStoreRef {
     let path = "test".to_string();
-    Arc::new(SstableStore::new(store, path, 64 << 20, 64 << 20))
+    Arc::new(SstableStore::new(store, path, 64 << 20, 64 << 20, None))
 }
 
 /// Generates keys like `key_test_00002` with epoch 233.
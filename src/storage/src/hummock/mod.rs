@@ -24,6 +24,8 @@ use risingwave_rpc_client::HummockMetaClient;
 
 mod block_cache;
 pub use block_cache::*;
+mod disk_cache;
+pub use disk_cache::*;
 mod sstable;
 pub use sstable::*;
 mod cache;
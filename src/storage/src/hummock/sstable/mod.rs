@@ -36,10 +36,9 @@ use risingwave_pb::hummock::{KeyRange, SstableInfo};
 pub mod group_builder;
 mod utils;
 
-pub use utils::CompressionAlgorithm;
 use utils::{get_length_prefixed_slice, put_length_prefixed_slice};
+pub use utils::{xxhash64_checksum, xxhash64_verify, CompressionAlgorithm};
 
-use self::utils::{xxhash64_checksum, xxhash64_verify};
 use super::{HummockError, HummockResult};
 
 const DEFAULT_META_BUFFER_CAPACITY: usize = 4096;
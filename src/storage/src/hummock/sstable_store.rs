@@ -23,7 +23,7 @@ use futures::channel::oneshot::{channel, Sender};
 use futures::future::try_join_all;
 use risingwave_hummock_sdk::{is_remote_sst_id, HummockSSTableId};
 
-use super::{Block, BlockCache, Sstable, SstableMeta};
+use super::{Block, BlockCache, DiskCache, DiskCacheKey, Sstable, SstableMeta};
 use crate::hummock::{BlockHolder, CachableEntry, HummockError, HummockResult, LruCache};
 use crate::monitor::StoreLocalStatistic;
 use crate::object::{get_local_path, BlockLocation, ObjectStoreRef};
@@ -50,6 +50,8 @@ pub struct SstableStore {
     store: ObjectStoreRef,
     block_cache: BlockCache,
     meta_cache: Arc<LruCache<HummockSSTableId, Box<Sstable>>>,
+    /// Secondary cache of remote SSTs on the local disk.
+    disk_cache: Option<DiskCache>,
     prefetch_request: Arc<Mutex<HashMap<u64, Vec<Sender<()>>>>>,
 }
 
@@ -59,6 +61,7 @@ impl SstableStore {
         path: String,
         block_cache_capacity: usize,
         meta_cache_capacity: usize,
+        disk_cache: Option<DiskCache>,
     ) -> Self {
         let mut shard_bits = MAX_META_CACHE_SHARD_BITS;
         while (meta_cache_capacity >> shard_bits) < MIN_BUFFER_SIZE_PER_SHARD && shard_bits > 0 {
@@ -70,6 +73,7 @@ impl SstableStore {
             store,
             block_cache: BlockCache::new(block_cache_capacity),
             meta_cache,
+            disk_cache,
             prefetch_request: Arc::new(Default::default()),
         }
     }
@@ -130,7 +134,7 @@ impl SstableStore {
                 let _ = rc.await;
                 continue;
             }
-            let ret = self.get_data(sst, block_index as usize).await;
+            let ret = self.get_data(sst, block_index as usize, stats).await;
             let mut prefetch_request = self.prefetch_request.lock().unwrap();
             let pending_requests = prefetch_request.remove(&sst.id).unwrap();
             for p in pending_requests {
@@ -177,7 +181,12 @@ impl SstableStore {
         Ok(())
     }
 
-    pub async fn get_data(&self, sst: &Sstable, block_index: usize) -> HummockResult<BlockHolder> {
+    pub async fn get_data(
+        &self,
+        sst: &Sstable,
+        block_index: usize,
+        stats: &mut StoreLocalStatistic,
+    ) -> HummockResult<BlockHolder> {
         let block_meta = sst
             .meta
             .block_metas
@@ -194,6 +203,16 @@ impl SstableStore {
             }
         }
 
+        if let Some(block_data) = self
+            .read_disk_cache(DiskCacheKey::Block(sst.id, block_index as u64), stats)
+            .await
+        {
+            let block = Block::decode(block_data)?;
+            return Ok(self
+                .block_cache
+                .insert(sst.id, block_index as u64, Box::new(block)));
+        }
+
         let block_loc = BlockLocation {
             offset: block_meta.offset as usize,
             size: read_size as usize,
@@ -204,6 +223,11 @@ impl SstableStore {
             .read(&data_path, Some(block_loc))
             .await
             .map_err(HummockError::object_io_error)?;
+        self.fill_disk_cache(
+            DiskCacheKey::Block(sst.id, block_index as u64),
+            &block_data[..block_meta.len as usize],
+        )
+        .await;
         let block = Block::decode(block_data.slice(..block_meta.len as usize))?;
         let ret = self
             .block_cache
@@ -238,6 +262,12 @@ impl SstableStore {
                 .block_metas
                 .get(block_index as usize)
                 .ok_or_else(HummockError::invalid_block)?;
+            let disk_cache_key = DiskCacheKey::Block(sst.id, block_index);
+            if !matches!(policy, CachePolicy::Disable)
+                && let Some(block_data) = self.read_disk_cache(disk_cache_key, stats).await
+            {
+                return Ok(Box::new(Block::decode(block_data)?));
+            }
             let block_loc = BlockLocation {
                 offset: block_meta.offset as usize,
                 size: block_meta.len as usize,
@@ -248,6 +278,9 @@ impl SstableStore {
                 .read(&data_path, Some(block_loc))
                 .await
                 .map_err(HummockError::object_io_error)?;
+            if let CachePolicy::Fill = policy {
+                self.fill_disk_cache(disk_cache_key, &block_data).await;
+            }
             let block = Block::decode(block_data)?;
             Ok(Box::new(block))
         };
@@ -284,12 +317,9 @@ impl SstableStore {
             let f = self
                 .meta_cache
                 .lookup_with_request_dedup(sst_id, sst_id, move || async move {
-                    let path = self.get_sst_meta_path(id);
                     let buf = self
-                        .store
-                        .read(&path, None)
-                        .await
-                        .map_err(HummockError::object_io_error)?;
+                        .read_meta_data(id, &mut StoreLocalStatistic::default())
+                        .await?;
                     let size = buf.len();
                     let meta = SstableMeta::decode(&mut &buf[..])?;
                     let sst = Box::new(Sstable { id, meta });
@@ -312,12 +342,7 @@ impl SstableStore {
             .meta_cache
             .lookup_with_request_dedup(sst_id, sst_id, || async {
                 stats.cache_meta_block_miss += 1;
-                let path = self.get_sst_meta_path(sst_id);
-                let buf = self
-                    .store
-                    .read(&path, None)
-                    .await
-                    .map_err(HummockError::object_io_error)?;
+                let buf = self.read_meta_data(sst_id, stats).await?;
                 let size = buf.len();
                 let meta = SstableMeta::decode(&mut &buf[..])?;
                 let sst = Box::new(Sstable { id: sst_id, meta });
@@ -327,6 +352,65 @@ impl SstableStore {
         Ok(entry)
     }
 
+    /// Reads the encoded meta of the SST from the disk cache, or from the object store otherwise.
+    async fn read_meta_data(
+        &self,
+        sst_id: HummockSSTableId,
+        stats: &mut StoreLocalStatistic,
+    ) -> HummockResult<Bytes> {
+        let disk_cache_key = DiskCacheKey::Meta(sst_id);
+        if let Some(buf) = self.read_disk_cache(disk_cache_key, stats).await {
+            return Ok(buf);
+        }
+        let path = self.get_sst_meta_path(sst_id);
+        let buf = self
+            .store
+            .read(&path, None)
+            .await
+            .map_err(HummockError::object_io_error)?;
+        self.fill_disk_cache(disk_cache_key, &buf).await;
+        Ok(buf)
+    }
+
+    /// Reads the entry from the disk cache, which only caches remote SSTs.
+    async fn read_disk_cache(
+        &self,
+        key: DiskCacheKey,
+        stats: &mut StoreLocalStatistic,
+    ) -> Option<Bytes> {
+        let disk_cache = self
+            .disk_cache
+            .as_ref()
+            .filter(|_| is_remote_sst_id(key.sst_id()))?;
+        let data = disk_cache.get(key).await;
+        let (total, miss) = match key {
+            DiskCacheKey::Meta(_) => (
+                &mut stats.disk_cache_meta_block_total,
+                &mut stats.disk_cache_meta_block_miss,
+            ),
+            DiskCacheKey::Block(..) => (
+                &mut stats.disk_cache_data_block_total,
+                &mut stats.disk_cache_data_block_miss,
+            ),
+        };
+        *total += 1;
+        if data.is_none() {
+            *miss += 1;
+        }
+        data
+    }
+
+    /// Fills the disk cache with the entry read from the object store. Failures are only logged
+    /// since the disk cache is best-effort.
+    async fn fill_disk_cache(&self, key: DiskCacheKey, data: &[u8]) {
+        if let Some(disk_cache) = &self.disk_cache
+            && is_remote_sst_id(key.sst_id())
+            && let Err(e) = disk_cache.insert(key, data).await
+        {
+            tracing::warn!("failed to fill {:?} into disk cache: {}", key, e);
+        }
+    }
+
     pub fn get_sst_meta_path(&self, sst_id: HummockSSTableId) -> String {
         let mut ret = format!("{}/{}.meta", self.path, sst_id);
         if !is_remote_sst_id(sst_id) {
@@ -355,6 +439,10 @@ impl SstableStore {
         self.block_cache.clone()
     }
 
+    pub fn get_disk_cache(&self) -> Option<DiskCache> {
+        self.disk_cache.clone()
+    }
+
     #[cfg(test)]
     pub fn clear_block_cache(&self) {
         self.block_cache.clear();
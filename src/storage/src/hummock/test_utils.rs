@@ -50,6 +50,8 @@ pub fn default_config_for_test() -> StorageConfig {
         disable_remote_compactor: false,
         enable_local_spill: false,
         local_object_store: "memory".to_string(),
+        disk_cache_capacity_mb: 0,
+        disk_cache_dir: "".to_string(),
     }
 }
 
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::future::Future;
+use std::ops::Bound;
 
 use bytes::{BufMut, Bytes, BytesMut};
 use risingwave_common::catalog::TableId;
@@ -165,6 +166,53 @@ impl<S: StateStore> Keyspace<S> {
         Ok(strip_prefix_iterator)
     }
 
+    /// Gets an iterator over the keys of this keyspace starting with `key_prefix`. Note that only
+    /// the prefix of this keyspace will be stripped, so the returned keys still start with
+    /// `key_prefix`. The returned iterator will iterate data from a snapshot corresponding to the
+    /// given `epoch`
+    pub async fn iter_with_key_prefix(
+        &self,
+        key_prefix: &[u8],
+        epoch: u64,
+    ) -> StorageResult<StripPrefixIterator<S::Iter>> {
+        let start_key = self.prefixed_key(key_prefix);
+        let end_key = next_key(start_key.as_slice());
+        let range = start_key..end_key;
+        let iter = self.store.iter(range, epoch).await?;
+        let strip_prefix_iterator = StripPrefixIterator {
+            iter,
+            prefix_len: self.prefix.len(),
+        };
+        Ok(strip_prefix_iterator)
+    }
+
+    /// Gets an iterator over the keys of this keyspace in `key_range`, whose bounds are relative to
+    /// the prefix of this keyspace. Note that only the prefix of this keyspace will be stripped.
+    /// The returned iterator will iterate data from a snapshot corresponding to the given
+    /// `epoch`
+    pub async fn iter_with_key_range(
+        &self,
+        key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
+        epoch: u64,
+    ) -> StorageResult<StripPrefixIterator<S::Iter>> {
+        let start_key = match key_range.0 {
+            Bound::Included(key) => Bound::Included(self.prefixed_key(key)),
+            Bound::Excluded(key) => Bound::Excluded(self.prefixed_key(key)),
+            Bound::Unbounded => Bound::Included(self.prefix.to_owned()),
+        };
+        let end_key = match key_range.1 {
+            Bound::Included(key) => Bound::Included(self.prefixed_key(key)),
+            Bound::Excluded(key) => Bound::Excluded(self.prefixed_key(key)),
+            Bound::Unbounded => Bound::Excluded(next_key(self.prefix.as_slice())),
+        };
+        let iter = self.store.iter((start_key, end_key), epoch).await?;
+        let strip_prefix_iterator = StripPrefixIterator {
+            iter,
+            prefix_len: self.prefix.len(),
+        };
+        Ok(strip_prefix_iterator)
+    }
+
     /// Gets the underlying state store.
     pub fn state_store(&self) -> S {
         self.store.clone()
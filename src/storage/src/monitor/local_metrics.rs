@@ -20,6 +20,10 @@ pub struct StoreLocalStatistic {
     pub cache_data_block_total: u64,
     pub cache_meta_block_miss: u64,
     pub cache_meta_block_total: u64,
+    pub disk_cache_data_block_miss: u64,
+    pub disk_cache_data_block_total: u64,
+    pub disk_cache_meta_block_miss: u64,
+    pub disk_cache_meta_block_total: u64,
 
     // include multiple versions of one key.
     pub scan_key_count: u64,
@@ -37,6 +41,11 @@ impl StoreLocalStatistic {
         self.cache_data_block_miss += other.cache_data_block_miss;
         self.cache_data_block_total += other.cache_data_block_total;
 
+        self.disk_cache_data_block_miss += other.disk_cache_data_block_miss;
+        self.disk_cache_data_block_total += other.disk_cache_data_block_total;
+        self.disk_cache_meta_block_miss += other.disk_cache_meta_block_miss;
+        self.disk_cache_meta_block_total += other.disk_cache_meta_block_total;
+
         self.scan_key_count += other.scan_key_count;
         self.processed_key_count += other.processed_key_count;
         self.bloom_filter_true_negative_count += other.bloom_filter_true_negative_count;
@@ -73,6 +82,20 @@ impl StoreLocalStatistic {
                 .inc_by(self.cache_meta_block_miss);
         }
 
+        for (label, count) in [
+            ("disk_data_total", self.disk_cache_data_block_total),
+            ("disk_data_miss", self.disk_cache_data_block_miss),
+            ("disk_meta_total", self.disk_cache_meta_block_total),
+            ("disk_meta_miss", self.disk_cache_meta_block_miss),
+        ] {
+            if count > 0 {
+                metrics
+                    .sst_store_block_request_counts
+                    .with_label_values(&[label])
+                    .inc_by(count);
+            }
+        }
+
         if self.bloom_filter_true_negative_count > 0 {
             metrics
                 .bloom_filter_true_negative_counts
@@ -24,7 +24,7 @@ use risingwave_hummock_sdk::HummockSSTableId;
 
 use super::{monitor_process, Print};
 use crate::hummock::sstable_store::SstableStoreRef;
-use crate::hummock::{BlockCache, LruCache, Sstable};
+use crate::hummock::{BlockCache, DiskCache, LruCache, Sstable};
 
 /// Define all metrics.
 #[macro_export]
@@ -335,9 +335,11 @@ impl StateStoreMetrics {
 struct StateStoreCollector {
     block_cache: BlockCache,
     meta_cache: Arc<LruCache<HummockSSTableId, Box<Sstable>>>,
+    disk_cache: Option<DiskCache>,
     descs: Vec<Desc>,
     block_cache_size: IntGauge,
     meta_cache_size: IntGauge,
+    disk_cache_size: IntGauge,
 }
 
 impl StateStoreCollector {
@@ -358,12 +360,21 @@ impl StateStoreCollector {
         .unwrap();
         descs.extend(meta_cache_size.desc().into_iter().cloned());
 
+        let disk_cache_size = IntGauge::with_opts(Opts::new(
+            "state_store_disk_cache_size",
+            "the size of disk cache for remote sstable blocks and metas",
+        ))
+        .unwrap();
+        descs.extend(disk_cache_size.desc().into_iter().cloned());
+
         Self {
             block_cache: sstable_store.get_block_cache(),
             meta_cache: sstable_store.get_meta_cache(),
+            disk_cache: sstable_store.get_disk_cache(),
             descs,
             block_cache_size,
             meta_cache_size,
+            disk_cache_size,
         }
     }
 }
@@ -377,11 +388,17 @@ impl Collector for StateStoreCollector {
         self.block_cache_size.set(self.block_cache.size() as i64);
         self.meta_cache_size
             .set(self.meta_cache.get_memory_usage() as i64);
+        self.disk_cache_size.set(
+            self.disk_cache
+                .as_ref()
+                .map_or(0, |disk_cache| disk_cache.size()) as i64,
+        );
 
         // collect MetricFamilies.
-        let mut mfs = Vec::with_capacity(2);
+        let mut mfs = Vec::with_capacity(3);
         mfs.extend(self.block_cache_size.collect());
         mfs.extend(self.meta_cache_size.collect());
+        mfs.extend(self.disk_cache_size.collect());
         mfs
     }
 }
@@ -20,7 +20,7 @@ use risingwave_common::config::StorageConfig;
 use risingwave_rpc_client::HummockMetaClient;
 
 use crate::error::StorageResult;
-use crate::hummock::{HummockStorage, SstableStore};
+use crate::hummock::{DiskCache, HummockStorage, SstableStore};
 use crate::memory::MemoryStateStore;
 use crate::monitor::{MonitoredStateStore as Monitored, ObjectStoreMetrics, StateStoreMetrics};
 use crate::object::{parse_object_store, HybridObjectStore, ObjectStoreImpl};
@@ -106,6 +106,17 @@ impl StateStoreImpl {
                     remote_object_store
                 };
 
+                let disk_cache = if config.disk_cache_capacity_mb > 0 {
+                    Some(
+                        DiskCache::open(
+                            config.disk_cache_dir.as_str(),
+                            config.disk_cache_capacity_mb * (1 << 20),
+                        )
+                        .await?,
+                    )
+                } else {
+                    None
+                };
                 let sstable_store = Arc::new(SstableStore::new(
                     Arc::new(ObjectStoreImpl::new(
                         object_store,
@@ -114,6 +125,7 @@ impl StateStoreImpl {
                     config.data_directory.to_string(),
                     config.block_cache_capacity_mb * (1 << 20),
                     config.meta_cache_capacity_mb * (1 << 20),
+                    disk_cache,
                 ));
                 let inner = HummockStorage::new(
                     config.clone(),
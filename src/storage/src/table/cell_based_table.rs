@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::BTreeMap;
+use std::ops::Bound;
 use std::sync::Arc;
 
 use bytes::Bytes;
@@ -447,6 +448,38 @@ impl<S: StateStore> CellBasedTableStreamingIter<S> {
         Ok(iter)
     }
 
+    /// Creates an iterator over the rows whose serialized pk starts with `pk_prefix`.
+    pub async fn new_with_pk_prefix(
+        keyspace: &Keyspace<S>,
+        table_descs: Vec<ColumnDesc>,
+        pk_prefix: &[u8],
+        epoch: u64,
+    ) -> StorageResult<Self> {
+        let cell_based_row_deserializer = CellBasedRowDeserializer::new(table_descs);
+        let iter = keyspace.iter_with_key_prefix(pk_prefix, epoch).await?;
+        let iter = Self {
+            iter,
+            cell_based_row_deserializer,
+        };
+        Ok(iter)
+    }
+
+    /// Creates an iterator over the rows whose serialized pk is in `pk_range`.
+    pub async fn new_with_pk_range(
+        keyspace: &Keyspace<S>,
+        table_descs: Vec<ColumnDesc>,
+        pk_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
+        epoch: u64,
+    ) -> StorageResult<Self> {
+        let cell_based_row_deserializer = CellBasedRowDeserializer::new(table_descs);
+        let iter = keyspace.iter_with_key_range(pk_range, epoch).await?;
+        let iter = Self {
+            iter,
+            cell_based_row_deserializer,
+        };
+        Ok(iter)
+    }
+
     /// return a row with its pk.
     pub async fn next(&mut self) -> StorageResult<Option<(Vec<u8>, Row)>> {
         loop {
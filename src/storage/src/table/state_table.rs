@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use std::cmp::Ordering;
-use std::collections::btree_map;
 use std::iter::Peekable;
+use std::ops::Bound;
 use std::sync::Arc;
 
 use risingwave_common::array::Row;
@@ -21,6 +21,7 @@ use risingwave_common::catalog::ColumnDesc;
 use risingwave_common::error::RwError;
 use risingwave_common::util::ordered::{serialize_pk, OrderedRowSerializer};
 use risingwave_common::util::sort_util::OrderType;
+use risingwave_hummock_sdk::key::next_key;
 
 use super::cell_based_table::{CellBasedTable, CellBasedTableStreamingIter};
 use super::mem_table::{MemTable, RowOp};
@@ -114,16 +115,138 @@ impl<S: StateStore> StateTable<S> {
     }
 
     pub async fn iter(&self, epoch: u64) -> StorageResult<StateTableRowIter<'_, S>> {
-        let mem_table_iter = self.mem_table.buffer.iter().peekable();
+        let mem_table_iter: MemTableIter<'_> = Box::new(self.mem_table.buffer.iter());
+        let cell_based_streaming_iter =
+            CellBasedTableStreamingIter::new(&self.keyspace, self.column_descs.clone(), epoch)
+                .await?;
         StateTableRowIter::new(
+            cell_based_streaming_iter,
+            mem_table_iter.peekable(),
+            &self.order_types,
+        )
+        .await
+    }
+
+    /// Iterates the rows whose pk starts with `pk_prefix`, in the order of pk.
+    pub async fn iter_with_pk_prefix(
+        &self,
+        pk_prefix: &Row,
+        epoch: u64,
+    ) -> StorageResult<StateTableRowIter<'_, S>> {
+        let prefix_len = pk_prefix.size();
+        assert!(prefix_len <= self.order_types.len());
+        let prefix_serializer = OrderedRowSerializer::new(self.order_types[..prefix_len].to_vec());
+        let serialized_pk_prefix = serialize_pk(pk_prefix, &prefix_serializer).map_err(err)?;
+
+        let pk_prefix = pk_prefix.clone();
+        let mem_table_iter: MemTableIter<'_> = Box::new(
+            self.mem_table
+                .buffer
+                .iter()
+                .filter(move |(pk, _)| pk.0[..prefix_len] == pk_prefix.0[..]),
+        );
+        let cell_based_streaming_iter = CellBasedTableStreamingIter::new_with_pk_prefix(
             &self.keyspace,
             self.column_descs.clone(),
-            mem_table_iter,
+            &serialized_pk_prefix,
+            epoch,
+        )
+        .await?;
+        StateTableRowIter::new(
+            cell_based_streaming_iter,
+            mem_table_iter.peekable(),
             &self.order_types,
+        )
+        .await
+    }
+
+    /// Iterates the rows whose pk is in `pk_range`, in the order of pk. The bounds of the range can
+    /// be prefixes of the pk, and a prefix bound covers all the pks starting with it, e.g., the
+    /// rows with pk `(1, 2)` are in the range `(Excluded((0)), Included((1)))`.
+    pub async fn iter_with_pk_range(
+        &self,
+        pk_range: (Bound<Row>, Bound<Row>),
+        epoch: u64,
+    ) -> StorageResult<StateTableRowIter<'_, S>> {
+        let serialize_bound = |bound: &Bound<Row>| -> Bound<(usize, Vec<u8>)> {
+            match bound {
+                Bound::Included(pk) => Bound::Included(self.serialize_pk_prefix(pk)),
+                Bound::Excluded(pk) => Bound::Excluded(self.serialize_pk_prefix(pk)),
+                Bound::Unbounded => Bound::Unbounded,
+            }
+        };
+        let start = serialize_bound(&pk_range.0);
+        let end = serialize_bound(&pk_range.1);
+
+        // A prefix bound covers all the keys starting with it.
+        let start_key = match &start {
+            Bound::Included((_, key)) => Bound::Included(key.clone()),
+            Bound::Excluded((_, key)) => Bound::Included(next_key(key)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end_key = match &end {
+            Bound::Included((_, key)) => {
+                let next = next_key(key);
+                if next.is_empty() {
+                    Bound::Unbounded
+                } else {
+                    Bound::Excluded(next)
+                }
+            }
+            Bound::Excluded((_, key)) => Bound::Excluded(key.clone()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let order_types = self.order_types.clone();
+        let in_range = move |pk: &Row| {
+            let serialize_prefix = |len: usize| {
+                let mut key = vec![];
+                OrderedRowSerializer::new(order_types[..len].to_vec())
+                    .serialize(&Row(pk.0[..len].to_vec()), &mut key);
+                key
+            };
+            let after_start = match &start {
+                Bound::Included((len, key)) => serialize_prefix(*len) >= *key,
+                Bound::Excluded((len, key)) => serialize_prefix(*len) > *key,
+                Bound::Unbounded => true,
+            };
+            let before_end = match &end {
+                Bound::Included((len, key)) => serialize_prefix(*len) <= *key,
+                Bound::Excluded((len, key)) => serialize_prefix(*len) < *key,
+                Bound::Unbounded => true,
+            };
+            after_start && before_end
+        };
+        let mem_table_iter: MemTableIter<'_> = Box::new(
+            self.mem_table
+                .buffer
+                .iter()
+                .filter(move |(pk, _)| in_range(pk)),
+        );
+        let cell_based_streaming_iter = CellBasedTableStreamingIter::new_with_pk_range(
+            &self.keyspace,
+            self.column_descs.clone(),
+            (start_key, end_key),
             epoch,
         )
+        .await?;
+        StateTableRowIter::new(
+            cell_based_streaming_iter,
+            mem_table_iter.peekable(),
+            &self.order_types,
+        )
         .await
     }
+
+    /// Serializes a prefix of pk, and returns it with its length.
+    fn serialize_pk_prefix(&self, pk_prefix: &Row) -> (usize, Vec<u8>) {
+        let prefix_len = pk_prefix.size();
+        assert!(prefix_len <= self.order_types.len());
+        let mut key = vec![];
+        OrderedRowSerializer::new(self.order_types[..prefix_len].to_vec())
+            .serialize(pk_prefix, &mut key);
+        (prefix_len, key)
+    }
 }
 
 /// `StateTableRowIter` is able to read the just written data (uncommited data).
@@ -137,7 +260,7 @@ pub struct StateTableRowIter<'a, S: StateStore> {
     cell_based_item: Option<(Vec<u8>, Row)>,
     pk_serializer: OrderedRowSerializer,
 }
-type MemTableIter<'a> = btree_map::Iter<'a, Row, RowOp>;
+type MemTableIter<'a> = Box<dyn Iterator<Item = (&'a Row, &'a RowOp)> + Send + 'a>;
 
 enum NextOutcome {
     MemTable,
@@ -147,14 +270,10 @@ enum NextOutcome {
 }
 impl<'a, S: StateStore> StateTableRowIter<'a, S> {
     async fn new(
-        keyspace: &Keyspace<S>,
-        table_descs: Vec<ColumnDesc>,
+        mut cell_based_streaming_iter: CellBasedTableStreamingIter<S>,
         mem_table_iter: Peekable<MemTableIter<'a>>,
         order_types_vec: &[OrderType],
-        epoch: u64,
     ) -> StorageResult<StateTableRowIter<'a, S>> {
-        let mut cell_based_streaming_iter =
-            CellBasedTableStreamingIter::new(keyspace, table_descs, epoch).await?;
         let pk_serializer = OrderedRowSerializer::new(order_types_vec.to_vec());
         let cell_based_item = cell_based_streaming_iter.next().await.map_err(err)?;
         let state_table_iter = Self {
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Bound;
+
 use risingwave_common::array::Row;
 use risingwave_common::catalog::{ColumnDesc, ColumnId};
 use risingwave_common::types::DataType;
@@ -800,6 +802,102 @@ async fn test_multi_state_table_iter() {
     state_2.commit(epoch).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_state_table_iter_with_pk_prefix() {
+    let state_store = MemoryStateStore::new();
+    let keyspace = Keyspace::executor_root(state_store.clone(), 0x42);
+    let column_descs = vec![
+        ColumnDesc::unnamed(ColumnId::from(0), DataType::Int32),
+        ColumnDesc::unnamed(ColumnId::from(1), DataType::Int32),
+        ColumnDesc::unnamed(ColumnId::from(2), DataType::Int32),
+    ];
+    let order_types = vec![OrderType::Ascending, OrderType::Ascending];
+    let mut state = StateTable::new(keyspace, column_descs, order_types, None);
+    let row = |a: i32, b: i32| Row(vec![Some(a.into()), Some(b.into()), Some((a * b).into())]);
+    let pk = |a: i32, b: i32| Row(vec![Some(a.into()), Some(b.into())]);
+
+    let mut epoch: u64 = 0;
+    state.insert(pk(1, 11), row(1, 11)).unwrap();
+    state.insert(pk(1, 12), row(1, 12)).unwrap();
+    state.insert(pk(2, 21), row(2, 21)).unwrap();
+    state.commit(epoch).await.unwrap();
+
+    // Mix committed rows with rows in the mem table.
+    epoch += 1;
+    state.delete(pk(1, 11), row(1, 11)).unwrap();
+    state.insert(pk(1, 13), row(1, 13)).unwrap();
+    state.insert(pk(3, 31), row(3, 31)).unwrap();
+
+    let mut iter = state
+        .iter_with_pk_prefix(&Row(vec![Some(1_i32.into())]), epoch)
+        .await
+        .unwrap();
+    assert_eq!(iter.next().await.unwrap(), Some(row(1, 12)));
+    assert_eq!(iter.next().await.unwrap(), Some(row(1, 13)));
+    assert_eq!(iter.next().await.unwrap(), None);
+
+    let mut iter = state
+        .iter_with_pk_prefix(&Row(vec![Some(3_i32.into())]), epoch)
+        .await
+        .unwrap();
+    assert_eq!(iter.next().await.unwrap(), Some(row(3, 31)));
+    assert_eq!(iter.next().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_state_table_iter_with_pk_range() {
+    let state_store = MemoryStateStore::new();
+    let keyspace = Keyspace::executor_root(state_store.clone(), 0x42);
+    let column_descs = vec![
+        ColumnDesc::unnamed(ColumnId::from(0), DataType::Int32),
+        ColumnDesc::unnamed(ColumnId::from(1), DataType::Int32),
+    ];
+    let order_types = vec![OrderType::Ascending, OrderType::Ascending];
+    let mut state = StateTable::new(keyspace, column_descs, order_types, None);
+    let row = |a: Option<i32>, b: i32| Row(vec![a.map(Into::into), Some(b.into())]);
+    let key = |a: i32| Row(vec![Some(a.into())]);
+
+    let mut epoch: u64 = 0;
+    state.insert(row(None, 1), row(None, 1)).unwrap();
+    state.insert(row(Some(1), 2), row(Some(1), 2)).unwrap();
+    state.insert(row(Some(2), 3), row(Some(2), 3)).unwrap();
+    state.insert(row(Some(3), 4), row(Some(3), 4)).unwrap();
+    state.commit(epoch).await.unwrap();
+
+    // Mix committed rows with rows in the mem table.
+    epoch += 1;
+    state.delete(row(Some(2), 3), row(Some(2), 3)).unwrap();
+    state.insert(row(Some(2), 5), row(Some(2), 5)).unwrap();
+    state.insert(row(Some(4), 6), row(Some(4), 6)).unwrap();
+
+    let mut iter = state
+        .iter_with_pk_range((Bound::Excluded(key(1)), Bound::Included(key(3))), epoch)
+        .await
+        .unwrap();
+    assert_eq!(iter.next().await.unwrap(), Some(row(Some(2), 5)));
+    assert_eq!(iter.next().await.unwrap(), Some(row(Some(3), 4)));
+    assert_eq!(iter.next().await.unwrap(), None);
+
+    // `NULL`s are the smallest, and can be skipped by an excluded `NULL` bound.
+    let mut iter = state
+        .iter_with_pk_range(
+            (Bound::Excluded(Row(vec![None])), Bound::Excluded(key(2))),
+            epoch,
+        )
+        .await
+        .unwrap();
+    assert_eq!(iter.next().await.unwrap(), Some(row(Some(1), 2)));
+    assert_eq!(iter.next().await.unwrap(), None);
+
+    let mut iter = state
+        .iter_with_pk_range((Bound::Included(key(3)), Bound::Unbounded), epoch)
+        .await
+        .unwrap();
+    assert_eq!(iter.next().await.unwrap(), Some(row(Some(3), 4)));
+    assert_eq!(iter.next().await.unwrap(), Some(row(Some(4), 6)));
+    assert_eq!(iter.next().await.unwrap(), None);
+}
+
 #[tokio::test]
 async fn test_cell_based_get_row_by_scan() {
     let state_store = MemoryStateStore::new();
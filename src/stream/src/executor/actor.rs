@@ -81,6 +81,7 @@ impl OperatorInfoStatus {
                         .push_back((epoch, Instant::now()))
                 }
             }
+            Message::Watermark(_) => {}
         }
     }
 }
@@ -64,4 +64,9 @@ pub struct AggCall {
     /// Whether the stream is append-only.
     /// Specific `StreamingAggStateImpl` may optimize its implementation based on this knowledge.
     pub append_only: bool,
+
+    /// Whether the input of the aggregation function is deduplicated, e.g. `COUNT(DISTINCT x)`.
+    /// The deduplication is done by [`crate::executor::aggregation::DistinctDeduplicater`] before
+    /// the input is applied to the state.
+    pub distinct: bool,
 }
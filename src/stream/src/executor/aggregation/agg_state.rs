@@ -20,6 +20,7 @@ use risingwave_common::error::Result;
 use risingwave_common::types::Datum;
 use risingwave_storage::StateStore;
 
+use super::DistinctDeduplicater;
 use crate::executor::managed_state::aggregation::ManagedStateImpl;
 
 /// States for [`crate::executor::LocalSimpleAggExecutor`],
@@ -28,6 +29,10 @@ pub struct AggState<S: StateStore> {
     /// Current managed states for all [`crate::executor::aggregation::AggCall`]s.
     pub managed_states: Vec<ManagedStateImpl<S>>,
 
+    /// Dedup states of the distinct [`crate::executor::aggregation::AggCall`]s, one-to-one map
+    /// with `managed_states`.
+    pub distinct_dedups: Vec<Option<DistinctDeduplicater<S>>>,
+
     /// Previous outputs of managed states. Initializing with `None`.
     pub prev_states: Option<Vec<Datum>>,
 }
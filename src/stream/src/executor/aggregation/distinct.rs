@@ -0,0 +1,153 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use madsim::collections::HashMap;
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::{ArrayImpl, Op, Row};
+use risingwave_common::buffer::{Bitmap, BitmapBuilder};
+use risingwave_common::catalog::TableId;
+use risingwave_common::error::Result;
+use risingwave_common::hash::HashCode;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_common::util::value_encoding::{deserialize_cell, serialize_cell};
+use risingwave_storage::storage_value::{StorageValue, ValueMeta};
+use risingwave_storage::write_batch::WriteBatch;
+use risingwave_storage::{Keyspace, StateStore};
+
+/// The count of a distinct value, and whether it has been changed since the last flush.
+struct DedupCount {
+    count: i64,
+    dirty: bool,
+}
+
+/// Deduplicates the input of a distinct agg call in a group. It counts how many times each value
+/// appears in the state store, so that a value is only applied to the agg state when its first
+/// copy is inserted, and retracted when its last copy is deleted.
+///
+/// The state is stored as `table_id/[group_key]/[value] -> count`.
+pub struct DistinctDeduplicater<S: StateStore> {
+    /// The keyspace of the dedup table, prefixed by the group key.
+    keyspace: Keyspace<S>,
+
+    /// The value meta of all the entries, which contains the vnode of the group.
+    value_meta: ValueMeta,
+
+    /// Counts of the values touched in the current epoch, keyed by the memcomparable-encoded
+    /// value. Cleared on flush.
+    cache: HashMap<Vec<u8>, DedupCount>,
+}
+
+impl<S: StateStore> DistinctDeduplicater<S> {
+    pub fn new(keyspace: Keyspace<S>, key_hash_code: Option<HashCode>) -> Self {
+        let value_meta = key_hash_code
+            .map(|hash_code| ValueMeta::with_vnode(hash_code.to_vnode()))
+            .unwrap_or_default();
+        Self {
+            keyspace,
+            value_meta,
+            cache: HashMap::new(),
+        }
+    }
+
+    async fn count_mut(&mut self, key: Vec<u8>, epoch: u64) -> Result<&mut DedupCount> {
+        if !self.cache.contains_key(&key) {
+            let count = match self.keyspace.get(&key, epoch).await? {
+                Some(mut raw_value) => deserialize_cell(&mut raw_value, &DataType::Int64)?
+                    .map(|count| *count.as_int64())
+                    .unwrap_or(0),
+                None => 0,
+            };
+            self.cache.insert(
+                key.clone(),
+                DedupCount {
+                    count,
+                    dirty: false,
+                },
+            );
+        }
+        Ok(self.cache.get_mut(&key).unwrap())
+    }
+
+    /// Applies a batch of the distinct column to the dedup state, and returns the visibility with
+    /// which the batch should be applied to the agg state. An insertion is visible only if the
+    /// value did not exist before, and a deletion is visible only if it removes the last copy of
+    /// the value. `NULL`s are always visible as they are ignored by the agg calls.
+    pub async fn dedup(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        column: &ArrayImpl,
+        epoch: u64,
+    ) -> Result<Bitmap> {
+        let mut builder = BitmapBuilder::with_capacity(ops.len());
+        for (row_idx, op) in ops.iter().enumerate() {
+            if let Some(visibility) = visibility && !visibility.is_set(row_idx)? {
+                builder.append(false);
+                continue;
+            }
+            let datum = column.datum_at(row_idx);
+            if datum.is_none() {
+                builder.append(true);
+                continue;
+            }
+            let key = Row(vec![datum]).serialize()?;
+            let entry = self.count_mut(key, epoch).await?;
+            entry.dirty = true;
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    entry.count += 1;
+                    builder.append(entry.count == 1);
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    entry.count -= 1;
+                    builder.append(entry.count == 0);
+                }
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// Flush the changed counts to a write batch, and clear the cache.
+    pub fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
+        let mut local = write_batch.prefixify(&self.keyspace);
+        for (key, entry) in self.cache.drain() {
+            if !entry.dirty {
+                continue;
+            }
+            if entry.count > 0 {
+                let value = serialize_cell(&Some(ScalarImpl::Int64(entry.count)))?;
+                local.put(key, StorageValue::new_put(self.value_meta, value));
+            } else {
+                local.delete_with_value_meta(key, self.value_meta);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the keyspaces of the dedup states from the table ids of the distinct agg calls, which
+/// are keyed by the index of the agg call.
+pub fn build_distinct_dedup_keyspaces<S: StateStore>(
+    store: &S,
+    agg_call_num: usize,
+    distinct_dedup_table_ids: &std::collections::HashMap<u32, u32>,
+) -> Vec<Option<Keyspace<S>>> {
+    (0..agg_call_num)
+        .map(|idx| {
+            distinct_dedup_table_ids
+                .get(&(idx as u32))
+                .map(|table_id| Keyspace::table_root(store.clone(), &TableId::new(*table_id)))
+        })
+        .collect()
+}
@@ -16,6 +16,7 @@ use std::any::Any;
 
 pub use agg_call::*;
 pub use agg_state::*;
+pub use distinct::*;
 use dyn_clone::{self, DynClone};
 pub use foldable::*;
 use itertools::Itertools;
@@ -44,6 +45,7 @@ use crate::executor::{Executor, PkDataTypes};
 
 mod agg_call;
 mod agg_state;
+mod distinct;
 mod foldable;
 mod row_count;
 mod single_value;
@@ -331,11 +333,14 @@ pub fn generate_agg_schema(
 }
 
 /// Generate initial [`AggState`] from `agg_calls`. For [`crate::executor::HashAggExecutor`], the
-/// group key should be provided.
+/// group key should be provided. `distinct_dedup_keyspaces` has a keyspace for each distinct agg
+/// call.
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_managed_agg_state<S: StateStore>(
     key: Option<&Row>,
     agg_calls: &[AggCall],
     keyspace: &[Keyspace<S>],
+    distinct_dedup_keyspaces: &[Option<Keyspace<S>>],
     pk_data_types: PkDataTypes,
     epoch: u64,
     key_hash_code: Option<HashCode>,
@@ -343,6 +348,21 @@ pub async fn generate_managed_agg_state<S: StateStore>(
 ) -> StreamExecutorResult<AggState<S>> {
     let mut managed_states = vec![];
 
+    // The prefix of the dedup state is also `table_id/[group_key]`.
+    let distinct_dedups = distinct_dedup_keyspaces
+        .iter()
+        .map(|keyspace| {
+            keyspace.as_ref().map(|keyspace| {
+                let keyspace = if let Some(key) = key {
+                    keyspace.append(key.serialize().unwrap())
+                } else {
+                    keyspace.clone()
+                };
+                DistinctDeduplicater::new(keyspace, key_hash_code.clone())
+            })
+        })
+        .collect();
+
     // Currently the loop here only works if `ROW_COUNT_COLUMN` is 0.
     const_assert_eq!(ROW_COUNT_COLUMN, 0);
     let mut row_count = None;
@@ -385,6 +405,7 @@ pub async fn generate_managed_agg_state<S: StateStore>(
 
     Ok(AggState {
         managed_states,
+        distinct_dedups,
         prev_states: None,
     })
 }
@@ -402,6 +423,8 @@ pub fn get_key_len(agg_call: &AggCall) -> usize {
                 1
             }
         }
+        // Table states keyed by the input pk, or by the value if distinct.
+        AggKind::BitAnd | AggKind::BitOr | AggKind::ArrayAgg | AggKind::ApproxCountDistinct => 1,
         // These agg call do not have keys besides group key.
         AggKind::Sum | AggKind::Count | AggKind::SingleValue | AggKind::RowCount => 0,
         _ => unimplemented!("{:?} do not implemented!", agg_call.kind),
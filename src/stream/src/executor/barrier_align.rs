@@ -17,13 +17,15 @@ use futures::StreamExt;
 use futures_async_stream::try_stream;
 
 use super::error::StreamExecutorError;
-use super::{Barrier, BoxedMessageStream, Message, StreamChunk};
+use super::{Barrier, BoxedMessageStream, Message, StreamChunk, Watermark};
 
 #[derive(Debug, PartialEq)]
 pub enum AlignedMessage {
     Left(StreamChunk),
     Right(StreamChunk),
     Barrier(Barrier),
+    WatermarkLeft(Watermark),
+    WatermarkRight(Watermark),
 }
 
 #[try_stream(ok = AlignedMessage, error = StreamExecutorError)]
@@ -47,6 +49,9 @@ pub async fn barrier_align(mut left: BoxedMessageStream, mut right: BoxedMessage
                 while let Some(msg) = right.next().await {
                     match msg? {
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
+                        }
                         Message::Barrier(_) => {
                             panic!("right barrier received while left stream end")
                         }
@@ -59,6 +64,9 @@ pub async fn barrier_align(mut left: BoxedMessageStream, mut right: BoxedMessage
                 while let Some(msg) = left.next().await {
                     match msg? {
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
+                        }
                         Message::Barrier(_) => {
                             panic!("left barrier received while right stream end")
                         }
@@ -68,10 +76,14 @@ pub async fn barrier_align(mut left: BoxedMessageStream, mut right: BoxedMessage
             }
             Either::Left((Some(msg), _)) => match msg? {
                 Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkLeft(watermark),
                 Message::Barrier(_) => loop {
                     // received left barrier, waiting for right barrier
                     match right.next().await.unwrap()? {
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
+                        }
                         Message::Barrier(barrier) => {
                             yield AlignedMessage::Barrier(barrier);
                             break;
@@ -81,10 +93,14 @@ pub async fn barrier_align(mut left: BoxedMessageStream, mut right: BoxedMessage
             },
             Either::Right((Some(msg), _)) => match msg? {
                 Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkRight(watermark),
                 Message::Barrier(_) => loop {
                     // received right barrier, waiting for left barrier
                     match left.next().await.unwrap()? {
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
+                        }
                         Message::Barrier(barrier) => {
                             yield AlignedMessage::Barrier(barrier);
                             break;
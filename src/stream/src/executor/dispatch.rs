@@ -27,9 +27,10 @@ use risingwave_common::error::{internal_error, Result};
 use risingwave_common::hash::VIRTUAL_NODE_COUNT;
 use risingwave_common::util::addr::{is_local_address, HostAddr};
 use risingwave_common::util::hash_util::CRC32FastBuilder;
+use risingwave_pb::common::ActorInfo;
 use tracing::event;
 
-use crate::executor::{Barrier, BoxedExecutor, Message, Mutation, StreamConsumer};
+use crate::executor::{Barrier, BoxedExecutor, Message, Mutation, StreamConsumer, Watermark};
 use crate::task::{ActorId, DispatcherId, SharedContext};
 
 /// `Output` provides an interface for `Dispatcher` to send data into downstream actors.
@@ -179,6 +180,11 @@ impl DispatchExecutorInner {
                 }
                 self.post_mutate_outputs(&mutation).await?;
             }
+            Message::Watermark(watermark) => {
+                for dispatcher in &mut self.dispatchers {
+                    dispatcher.dispatch_watermark(watermark.clone()).await?;
+                }
+            }
         };
         Ok(())
     }
@@ -224,17 +230,25 @@ impl DispatchExecutorInner {
                     if let Some(downstream_actor_infos) =
                         adds.get(&(self.actor_id, dispatcher.get_dispatcher_id()))
                     {
-                        let mut outputs_to_add = Vec::with_capacity(downstream_actor_infos.len());
-                        for downstream_actor_info in downstream_actor_infos {
-                            let down_id = downstream_actor_info.get_actor_id();
-                            let downstream_addr = downstream_actor_info.get_host()?.into();
-                            outputs_to_add.push(new_output(
-                                &self.context,
-                                downstream_addr,
-                                self.actor_id,
-                                down_id,
-                            )?);
-                        }
+                        let outputs_to_add =
+                            new_outputs(&self.context, self.actor_id, downstream_actor_infos)?;
+                        dispatcher.add_outputs(outputs_to_add);
+                    }
+                }
+            }
+
+            // New actors of the rescheduled fragment should receive this barrier first.
+            Mutation::Reschedule(reschedule) => {
+                for dispatcher in &mut self.dispatchers {
+                    if let Some(update) = reschedule
+                        .dispatchers
+                        .get(&(self.actor_id, dispatcher.get_dispatcher_id()))
+                    {
+                        let outputs_to_add = new_outputs(
+                            &self.context,
+                            self.actor_id,
+                            &update.added_downstream_actors,
+                        )?;
                         dispatcher.add_outputs(outputs_to_add);
                     }
                 }
@@ -246,21 +260,57 @@ impl DispatchExecutorInner {
         Ok(())
     }
 
-    /// For `Stop`, update the outputs after we dispatch the barrier.
+    /// For `Stop` and `Reschedule`, update the outputs after we dispatch the barrier.
     async fn post_mutate_outputs(&mut self, mutation: &Option<Arc<Mutation>>) -> Result<()> {
-        if let Some(Mutation::Stop(stops)) = mutation.as_deref() {
-            // Remove outputs only if this actor itself is not to be stopped.
-            if !stops.contains(&self.actor_id) {
-                for dispatcher in &mut self.dispatchers {
-                    dispatcher.remove_outputs(stops);
+        match mutation.as_deref() {
+            Some(Mutation::Stop(stops)) => {
+                // Remove outputs only if this actor itself is not to be stopped.
+                if !stops.contains(&self.actor_id) {
+                    for dispatcher in &mut self.dispatchers {
+                        dispatcher.remove_outputs(stops);
+                    }
                 }
             }
+
+            Some(Mutation::Reschedule(reschedule)) => {
+                if !reschedule.stopped_actors.contains(&self.actor_id) {
+                    for dispatcher in &mut self.dispatchers {
+                        if let Some(update) = reschedule
+                            .dispatchers
+                            .get(&(self.actor_id, dispatcher.get_dispatcher_id()))
+                        {
+                            let removed = &update.removed_downstream_actor_ids;
+                            let actor_id = self.actor_id;
+                            self.context.retain(|&(up_id, down_id)| {
+                                up_id != actor_id || !removed.contains(&down_id)
+                            });
+                            dispatcher.remove_outputs(removed);
+                            if let Some(hash_mapping) = &update.hash_mapping {
+                                dispatcher.set_hash_mapping(hash_mapping.clone());
+                            }
+                        }
+                    }
+                }
+            }
+
+            _ => {}
         }
 
         Ok(())
     }
 }
 
+fn new_outputs(
+    context: &SharedContext,
+    actor_id: ActorId,
+    downstream_actor_infos: &[ActorInfo],
+) -> Result<Vec<BoxedOutput>> {
+    downstream_actor_infos
+        .iter()
+        .map(|info| new_output(context, info.get_host()?.into(), actor_id, info.actor_id))
+        .collect()
+}
+
 impl DispatchExecutor {
     pub fn new(
         input: BoxedExecutor,
@@ -323,6 +373,12 @@ macro_rules! impl_dispatcher {
                 }
             }
 
+            pub async fn dispatch_watermark(&mut self, watermark: Watermark) -> Result<()> {
+                match self {
+                    $( Self::$variant_name(inner) => inner.dispatch_watermark(watermark).await, )*
+                }
+            }
+
             pub fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
                 match self {
                     $( Self::$variant_name(inner) => inner.set_outputs(outputs), )*
@@ -364,10 +420,21 @@ macro_rules! for_all_dispatcher_variants {
 
 for_all_dispatcher_variants! { impl_dispatcher }
 
+impl DispatcherImpl {
+    /// Sets the vnode mapping of a hash dispatcher after the downstream fragment is rescheduled.
+    pub fn set_hash_mapping(&mut self, hash_mapping: Vec<ActorId>) {
+        match self {
+            Self::Hash(inner) => inner.hash_mapping = hash_mapping,
+            _ => unreachable!("only hash dispatchers have vnode mappings"),
+        }
+    }
+}
+
 macro_rules! define_dispatcher_associated_types {
     () => {
         type DataFuture<'a> = impl DispatchFuture<'a>;
         type BarrierFuture<'a> = impl DispatchFuture<'a>;
+        type WatermarkFuture<'a> = impl DispatchFuture<'a>;
     };
 }
 
@@ -376,9 +443,12 @@ pub trait DispatchFuture<'a> = Future<Output = Result<()>> + Send;
 pub trait Dispatcher: Debug + 'static {
     type DataFuture<'a>: DispatchFuture<'a>;
     type BarrierFuture<'a>: DispatchFuture<'a>;
+    type WatermarkFuture<'a>: DispatchFuture<'a>;
 
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_>;
     fn dispatch_barrier(&mut self, barrier: Barrier) -> Self::BarrierFuture<'_>;
+    /// Watermarks are broadcast to all outputs, as barriers.
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_>;
 
     fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>);
     fn add_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>);
@@ -433,6 +503,15 @@ impl Dispatcher for RoundRobinDataDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            for output in &mut self.outputs {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
         self.outputs = outputs.into_iter().collect();
         self.cur = self.cur.min(self.outputs.len() - 1);
@@ -494,11 +573,21 @@ impl Dispatcher for HashDataDispatcher {
     define_dispatcher_associated_types!();
 
     fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
-        self.outputs = outputs.into_iter().collect()
+        self.outputs = outputs.into_iter().collect();
+        self.fragment_ids = self
+            .outputs
+            .iter()
+            .map(|output| output.actor_id())
+            .collect();
     }
 
     fn add_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
         self.outputs.extend(outputs.into_iter());
+        self.fragment_ids = self
+            .outputs
+            .iter()
+            .map(|output| output.actor_id())
+            .collect();
     }
 
     fn dispatch_barrier(&mut self, barrier: Barrier) -> Self::BarrierFuture<'_> {
@@ -511,6 +600,15 @@ impl Dispatcher for HashDataDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            for output in &mut self.outputs {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_> {
         async move {
             // A chunk can be shuffled into multiple output chunks that to be sent to downstreams.
@@ -625,6 +723,7 @@ impl Dispatcher for HashDataDispatcher {
         self.outputs
             .drain_filter(|output| actor_ids.contains(&output.actor_id()))
             .count();
+        self.fragment_ids.retain(|id| !actor_ids.contains(id));
     }
 
     fn get_dispatcher_id(&self) -> DispatcherId {
@@ -687,6 +786,15 @@ impl Dispatcher for BroadcastDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            for output in self.outputs.values_mut() {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
         self.outputs = Self::into_pairs(outputs).collect()
     }
@@ -747,6 +855,13 @@ impl Dispatcher for SimpleDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            self.output.send(Message::Watermark(watermark)).await?;
+            Ok(())
+        }
+    }
+
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_> {
         async move {
             self.output.send(Message::Chunk(chunk)).await?;
@@ -783,7 +898,7 @@ mod tests {
 
     use super::*;
     use crate::executor::receiver::ReceiverExecutor;
-    use crate::executor::ActorContext;
+    use crate::executor::{ActorContext, DispatcherUpdate, Reschedule};
     use crate::task::{LOCAL_OUTPUT_CHANNEL_SIZE, LOCAL_TEST_ADDR};
 
     #[derive(Debug)]
@@ -1003,6 +1118,105 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_reschedule_hash_dispatcher() {
+        let schema = Schema { fields: vec![] };
+        let (mut tx, rx) = channel(16);
+        let input = Box::new(ReceiverExecutor::new(
+            schema,
+            vec![],
+            rx,
+            ActorContext::create(),
+            0,
+        ));
+        let actor_id = 233;
+        let dispatcher_id = 666;
+        let ctx = Arc::new(SharedContext::for_test());
+        add_local_channels(ctx.clone(), vec![(233, 1), (233, 2), (233, 3)]);
+        let outputs = [1, 2]
+            .into_iter()
+            .map(|down_id| {
+                Box::new(LocalOutput::new(
+                    down_id,
+                    ctx.take_sender(&(actor_id, down_id)).unwrap(),
+                )) as BoxedOutput
+            })
+            .collect_vec();
+        let mut rxs = [1, 2, 3]
+            .into_iter()
+            .map(|down_id| ctx.take_receiver(&(actor_id, down_id)).unwrap())
+            .collect_vec();
+
+        let half = VIRTUAL_NODE_COUNT / 2;
+        let mut hash_mapping = vec![1; half];
+        hash_mapping.resize(VIRTUAL_NODE_COUNT, 2);
+        let executor = Box::new(DispatchExecutor::new(
+            input,
+            vec![DispatcherImpl::Hash(HashDataDispatcher::new(
+                vec![1, 2],
+                outputs,
+                vec![0],
+                hash_mapping,
+                dispatcher_id,
+            ))],
+            actor_id,
+            ctx.clone(),
+        ))
+        .execute();
+        pin_mut!(executor);
+
+        // Replace actor 2 with actor 3.
+        let mut new_hash_mapping = vec![1; half];
+        new_hash_mapping.resize(VIRTUAL_NODE_COUNT, 3);
+        let mut reschedule = Reschedule::default();
+        reschedule.dispatchers.insert(
+            (actor_id, dispatcher_id),
+            DispatcherUpdate {
+                hash_mapping: Some(new_hash_mapping),
+                added_downstream_actors: vec![helper_make_local_actor(3)],
+                removed_downstream_actor_ids: [2].into_iter().collect(),
+            },
+        );
+        reschedule.stopped_actors.insert(2);
+        tx.send(Message::Barrier(
+            Barrier::new_test_barrier(1).with_mutation(Mutation::Reschedule(reschedule)),
+        ))
+        .await
+        .unwrap();
+        executor.next().await.unwrap().unwrap();
+
+        // All downstream actors receive the barrier, and the removed one is disconnected after it.
+        for rx in &mut rxs {
+            assert!(rx.next().await.unwrap().as_barrier().is_some());
+        }
+        assert!(rxs[1].next().await.is_none());
+        assert_eq!(ctx.get_channel_pair_number(), 2);
+
+        tx.send(Message::Chunk(StreamChunk::from_pretty(
+            "  I
+            +  1
+            +  2
+            +  3
+            +  4",
+        )))
+        .await
+        .unwrap();
+        executor.next().await.unwrap().unwrap();
+        tx.send(Message::Barrier(Barrier::new_test_barrier(2)))
+            .await
+            .unwrap();
+        executor.next().await.unwrap().unwrap();
+
+        // The rows are dispatched to actor 1 and the new actor 3 only.
+        let mut cardinality = 0;
+        for rx in [&mut rxs[0], &mut rxs[2]] {
+            while let Message::Chunk(chunk) = rx.next().await.unwrap() {
+                cardinality += chunk.cardinality();
+            }
+        }
+        assert_eq!(cardinality, 4);
+    }
+
     #[tokio::test]
     async fn test_hash_dispatcher() {
         let num_outputs = 5; // actor id ranges from 1 to 5
@@ -0,0 +1,489 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Bound;
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema};
+use risingwave_common::types::{Datum, ScalarImpl, ToOwnedDatum};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_storage::table::state_table::StateTable;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::barrier_align::*;
+use super::error::StreamExecutorError;
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndices, PkIndicesRef,
+    StreamExecutorResult,
+};
+
+/// The comparison between the key of the left input and the right value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+/// [`DynamicFilterExecutor`] filters the left input by comparing its key with the single value of
+/// the right input, e.g., `WHERE t.v > (SELECT max(v) FROM s)`.
+///
+/// The rows of the left input are filtered by the right value as they arrive, while the changes of
+/// the right value take effect at the barrier. All left rows are kept in the left table with the
+/// key as the first column of its pk, so that when the right value changes, only the rows whose
+/// keys are between the old and the new values are scanned, and inserted or deleted as their
+/// results change. The right value is kept in the right table.
+pub struct DynamicFilterExecutor<S: StateStore> {
+    input_l: Option<BoxedExecutor>,
+    input_r: Option<BoxedExecutor>,
+
+    info: ExecutorInfo,
+
+    /// Index of the key column in the left input.
+    key_l: usize,
+
+    comparator: Comparator,
+
+    /// Indices of the pk columns of the left table: the key and the left pk.
+    state_pk_indices: Vec<usize>,
+
+    left_table: StateTable<S>,
+
+    /// The right value, with an empty pk.
+    right_table: StateTable<S>,
+}
+
+impl<S: StateStore> Executor for DynamicFilterExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+impl<S: StateStore> DynamicFilterExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input_l: BoxedExecutor,
+        input_r: BoxedExecutor,
+        key_l: usize,
+        comparator: Comparator,
+        keyspace_l: Keyspace<S>,
+        keyspace_r: Keyspace<S>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+    ) -> Self {
+        let schema = input_l.schema().clone();
+        let state_pk_indices = std::iter::once(key_l)
+            .chain(input_l.pk_indices().iter().copied())
+            .collect_vec();
+        let column_descs = schema
+            .data_types()
+            .into_iter()
+            .enumerate()
+            .map(|(id, data_type)| ColumnDesc::unnamed(ColumnId::from(id as i32), data_type))
+            .collect();
+        let left_table = StateTable::new(
+            keyspace_l,
+            column_descs,
+            vec![OrderType::Ascending; state_pk_indices.len()],
+            None,
+        );
+        let right_table = StateTable::new(
+            keyspace_r,
+            vec![ColumnDesc::unnamed(
+                ColumnId::from(0),
+                input_r.schema().fields[0].data_type(),
+            )],
+            vec![],
+            None,
+        );
+
+        Self {
+            input_l: Some(input_l),
+            input_r: Some(input_r),
+            info: ExecutorInfo {
+                schema,
+                pk_indices,
+                identity: format!("DynamicFilterExecutor {:X}", executor_id),
+            },
+            key_l,
+            comparator,
+            state_pk_indices,
+            left_table,
+            right_table,
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let input_l = self.input_l.take().unwrap();
+        let input_r = self.input_r.take().unwrap();
+        let aligned_stream = barrier_align(input_l.execute(), input_r.execute());
+
+        // The right value that the emitted rows are filtered by, and the latest right value which
+        // takes effect at the next barrier. No right value is the same as `NULL`, which filters out
+        // all rows.
+        let mut committed_value: Datum = None;
+        let mut staging_value: Datum = None;
+        let mut epoch = None;
+
+        #[for_await]
+        for msg in aligned_stream {
+            match msg? {
+                AlignedMessage::Left(chunk) => {
+                    if let Some(chunk) = self.apply_left_chunk(chunk, &committed_value)? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                AlignedMessage::Right(chunk) => {
+                    let (data_chunk, ops) = chunk.into_parts();
+                    for (i, op) in ops.into_iter().enumerate() {
+                        let (row, visible) = data_chunk
+                            .row_at(i)
+                            .map_err(StreamExecutorError::eval_error)?;
+                        if !visible {
+                            continue;
+                        }
+                        staging_value = match op {
+                            Op::Insert | Op::UpdateInsert => row.value_at(0).to_owned_datum(),
+                            Op::Delete | Op::UpdateDelete => None,
+                        };
+                    }
+                }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    yield Message::Watermark(watermark);
+                }
+                // The watermarks of the right value say nothing about the left rows.
+                AlignedMessage::WatermarkRight(_) => {}
+                AlignedMessage::Barrier(barrier) => {
+                    match epoch {
+                        // Recover the right value from the right table.
+                        None => {
+                            committed_value = self
+                                .right_table
+                                .get_row(&Row(vec![]), barrier.epoch.curr)
+                                .await
+                                .map_err(StreamExecutorError::storage)?
+                                .and_then(|row| row.0.into_iter().next().unwrap());
+                            staging_value = committed_value.clone();
+                        }
+                        Some(epoch) => {
+                            if staging_value != committed_value {
+                                if let Some(chunk) = self
+                                    .apply_right_value(&committed_value, &staging_value, epoch)
+                                    .await?
+                                {
+                                    yield Message::Chunk(chunk);
+                                }
+                                self.write_right_value(&committed_value, &staging_value)?;
+                                committed_value = staging_value.clone();
+                            }
+                            self.left_table
+                                .commit(epoch)
+                                .await
+                                .map_err(StreamExecutorError::storage)?;
+                            self.right_table
+                                .commit(epoch)
+                                .await
+                                .map_err(StreamExecutorError::storage)?;
+                        }
+                    }
+                    epoch = Some(barrier.epoch.curr);
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+
+    /// Writes the left rows to the left table, and returns the ones satisfying the comparison with
+    /// `value`.
+    fn apply_left_chunk(
+        &mut self,
+        chunk: StreamChunk,
+        value: &Datum,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        let (data_chunk, ops) = chunk.into_parts();
+        let mut rows = vec![];
+        for (i, op) in ops.into_iter().enumerate() {
+            let (row, visible) = data_chunk
+                .row_at(i)
+                .map_err(StreamExecutorError::eval_error)?;
+            if !visible {
+                continue;
+            }
+            let row = row.to_owned_row();
+            let state_pk = row.by_indices(&self.state_pk_indices);
+            match op {
+                Op::Insert | Op::UpdateInsert => self
+                    .left_table
+                    .insert(state_pk, row.clone())
+                    .map_err(StreamExecutorError::storage)?,
+                Op::Delete | Op::UpdateDelete => self
+                    .left_table
+                    .delete(state_pk, row.clone())
+                    .map_err(StreamExecutorError::storage)?,
+            }
+            if self.satisfies(&row.0[self.key_l], value) {
+                rows.push((op, row));
+            }
+        }
+
+        // An update whose other half is filtered out becomes an insertion or a deletion.
+        for i in 0..rows.len() {
+            match rows[i].0 {
+                Op::UpdateDelete if !matches!(rows.get(i + 1), Some((Op::UpdateInsert, _))) => {
+                    rows[i].0 = Op::Delete;
+                }
+                Op::UpdateInsert if i == 0 || rows[i - 1].0 != Op::UpdateDelete => {
+                    rows[i].0 = Op::Insert;
+                }
+                _ => {}
+            }
+        }
+        self.build_chunk(rows)
+    }
+
+    /// Emits the changes of the left rows whose results are changed from `old_value` to
+    /// `new_value`.
+    async fn apply_right_value(
+        &self,
+        old_value: &Datum,
+        new_value: &Datum,
+        epoch: u64,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        let range = match (old_value, new_value) {
+            (None, None) => return Ok(None),
+            (Some(value), None) | (None, Some(value)) => self.satisfying_range(value),
+            (Some(old_value), Some(new_value)) => {
+                let (lower, upper) = if old_value <= new_value {
+                    (old_value, new_value)
+                } else {
+                    (new_value, old_value)
+                };
+                (
+                    Bound::Included(Row(vec![Some(lower.clone())])),
+                    Bound::Included(Row(vec![Some(upper.clone())])),
+                )
+            }
+        };
+
+        let mut rows = vec![];
+        let mut iter = self
+            .left_table
+            .iter_with_pk_range(range, epoch)
+            .await
+            .map_err(StreamExecutorError::storage)?;
+        while let Some(row) = iter.next().await.map_err(StreamExecutorError::storage)? {
+            let key = &row.0[self.key_l];
+            match (
+                self.satisfies(key, old_value),
+                self.satisfies(key, new_value),
+            ) {
+                (true, false) => rows.push((Op::Delete, row)),
+                (false, true) => rows.push((Op::Insert, row)),
+                _ => {}
+            }
+        }
+        self.build_chunk(rows)
+    }
+
+    /// Replaces the right value in the right table.
+    fn write_right_value(
+        &mut self,
+        old_value: &Datum,
+        new_value: &Datum,
+    ) -> StreamExecutorResult<()> {
+        if old_value.is_some() {
+            self.right_table
+                .delete(Row(vec![]), Row(vec![old_value.clone()]))
+                .map_err(StreamExecutorError::storage)?;
+        }
+        if new_value.is_some() {
+            self.right_table
+                .insert(Row(vec![]), Row(vec![new_value.clone()]))
+                .map_err(StreamExecutorError::storage)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the range of the keys satisfying the comparison with `value`.
+    fn satisfying_range(&self, value: &ScalarImpl) -> (Bound<Row>, Bound<Row>) {
+        let value = Row(vec![Some(value.clone())]);
+        // `NULL`s are the smallest keys in the left table, and never satisfy the comparison.
+        let non_null = Bound::Excluded(Row(vec![None]));
+        match self.comparator {
+            Comparator::LessThan => (non_null, Bound::Excluded(value)),
+            Comparator::LessThanOrEqual => (non_null, Bound::Included(value)),
+            Comparator::GreaterThan => (Bound::Excluded(value), Bound::Unbounded),
+            Comparator::GreaterThanOrEqual => (Bound::Included(value), Bound::Unbounded),
+        }
+    }
+
+    fn satisfies(&self, key: &Datum, value: &Datum) -> bool {
+        match (key, value) {
+            (Some(key), Some(value)) => match self.comparator {
+                Comparator::LessThan => key < value,
+                Comparator::LessThanOrEqual => key <= value,
+                Comparator::GreaterThan => key > value,
+                Comparator::GreaterThanOrEqual => key >= value,
+            },
+            _ => false,
+        }
+    }
+
+    fn build_chunk(&self, rows: Vec<(Op, Row)>) -> StreamExecutorResult<Option<StreamChunk>> {
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let chunk = StreamChunk::from_rows(&rows, &self.info.schema.data_types())
+            .map_err(StreamExecutorError::eval_error)?;
+        Ok(Some(chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::{Comparator, DynamicFilterExecutor};
+    use crate::executor::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor::{Executor, Message};
+
+    #[tokio::test]
+    async fn test_dynamic_filter() {
+        // (v, id) with pk (id)
+        let schema_l = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let schema_r = Schema {
+            fields: vec![Field::unnamed(DataType::Int64)],
+        };
+        let (mut tx_l, source_l) = MockSource::channel(schema_l, vec![1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema_r, vec![]);
+        // v > (SELECT max(v) FROM s)
+        let executor = DynamicFilterExecutor::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            0,
+            Comparator::GreaterThan,
+            create_in_memory_keyspace(),
+            create_in_memory_keyspace(),
+            vec![1],
+            1,
+        );
+        let mut executor = Box::new(executor).execute();
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        // No right value, so no rows are emitted until the barrier.
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 1
+            + 3 2
+            + 5 3",
+        ));
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            " I
+            + 2",
+        ));
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert_eq!(
+            executor
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I
+                + 3 2
+                + 5 3"
+            )
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        // An update whose old row is filtered out becomes an insertion.
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+            +  4 4
+            U- 1 1
+            U+ 6 1",
+        ));
+        assert_eq!(
+            executor
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I
+                + 4 4
+                + 6 1"
+            )
+        );
+
+        // The rows between the old and the new right values are deleted at the barrier.
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            "  I
+            U- 2
+            U+ 4",
+        ));
+        tx_l.push_barrier(3, false);
+        tx_r.push_barrier(3, false);
+        assert_eq!(
+            executor
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I
+                - 3 2
+                - 4 4"
+            )
+        );
+        assert_matches!(executor.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+}
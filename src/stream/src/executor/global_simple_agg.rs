@@ -59,6 +59,9 @@ pub struct SimpleAggExecutor<S: StateStore> {
     /// The executor operates on this keyspace.
     keyspace: Vec<Keyspace<S>>,
 
+    /// The keyspaces of the dedup states, one for each distinct [`AggCall`].
+    distinct_dedup_keyspaces: Vec<Option<Keyspace<S>>>,
+
     /// Aggregation states of the current operator.
     /// This is an `Option` and the initial state is built when `Executor::next` is called, since
     /// we may not want `Self::new` to be an `async` function.
@@ -99,12 +102,14 @@ impl<S: StateStore> SimpleAggExecutor<S> {
         input: Box<dyn Executor>,
         agg_calls: Vec<AggCall>,
         keyspace: Vec<Keyspace<S>>,
+        distinct_dedup_keyspaces: Vec<Option<Keyspace<S>>>,
         pk_indices: PkIndices,
         executor_id: u64,
         key_indices: Vec<usize>,
     ) -> Result<Self> {
         let input_info = input.info();
         let schema = generate_agg_schema(input.as_ref(), &agg_calls, None);
+        assert_eq!(distinct_dedup_keyspaces.len(), agg_calls.len());
 
         // Create state tables for each agg call.
         let mut state_tables = Vec::with_capacity(agg_calls.len());
@@ -132,6 +137,7 @@ impl<S: StateStore> SimpleAggExecutor<S> {
             input_pk_indices: input_info.pk_indices,
             input_schema: input_info.schema,
             keyspace,
+            distinct_dedup_keyspaces,
             states: None,
             agg_calls,
             key_indices,
@@ -146,6 +152,7 @@ impl<S: StateStore> SimpleAggExecutor<S> {
         input_schema: &Schema,
         states: &mut Option<AggState<S>>,
         keyspace: &[Keyspace<S>],
+        distinct_dedup_keyspaces: &[Option<Keyspace<S>>],
         chunk: StreamChunk,
         epoch: u64,
         state_tables: &[StateTable<S>],
@@ -176,6 +183,7 @@ impl<S: StateStore> SimpleAggExecutor<S> {
                 None,
                 agg_calls,
                 keyspace,
+                distinct_dedup_keyspaces,
                 input_pk_data_types,
                 epoch,
                 None,
@@ -192,10 +200,30 @@ impl<S: StateStore> SimpleAggExecutor<S> {
             .await
             .map_err(StreamExecutorError::agg_state_error)?;
 
-        // 3. Apply batch to each of the state (per agg_call)
-        for (agg_state, data) in states.managed_states.iter_mut().zip_eq(all_agg_data.iter()) {
+        // 3. Apply batch to each of the state (per agg_call). The input of distinct agg calls is
+        // deduplicated first.
+        for ((agg_state, dedup), data) in states
+            .managed_states
+            .iter_mut()
+            .zip_eq(states.distinct_dedups.iter_mut())
+            .zip_eq(all_agg_data.iter())
+        {
+            let dedup_visibility = match dedup {
+                Some(dedup) => Some(
+                    dedup
+                        .dedup(&ops, visibility.as_ref(), data[0], epoch)
+                        .await
+                        .map_err(StreamExecutorError::agg_state_error)?,
+                ),
+                None => None,
+            };
             agg_state
-                .apply_batch(&ops, visibility.as_ref(), data, epoch)
+                .apply_batch(
+                    &ops,
+                    dedup_visibility.as_ref().or(visibility.as_ref()),
+                    data,
+                    epoch,
+                )
                 .await
                 .map_err(StreamExecutorError::agg_state_error)?;
         }
@@ -232,6 +260,11 @@ impl<S: StateStore> SimpleAggExecutor<S> {
                 .await
                 .map_err(StreamExecutorError::agg_state_error)?;
         }
+        for dedup in states.distinct_dedups.iter_mut().flatten() {
+            dedup
+                .flush(&mut write_batch)
+                .map_err(StreamExecutorError::agg_state_error)?;
+        }
         write_batch
             .ingest(epoch)
             .await
@@ -275,6 +308,7 @@ impl<S: StateStore> SimpleAggExecutor<S> {
             input_pk_indices,
             input_schema,
             keyspace,
+            distinct_dedup_keyspaces,
             mut states,
             agg_calls,
             key_indices: _,
@@ -297,6 +331,7 @@ impl<S: StateStore> SimpleAggExecutor<S> {
                         &input_schema,
                         &mut states,
                         &keyspace,
+                        &distinct_dedup_keyspaces,
                         chunk,
                         epoch,
                         &state_tables,
@@ -320,6 +355,8 @@ impl<S: StateStore> SimpleAggExecutor<S> {
                     yield Message::Barrier(barrier);
                     epoch = next_epoch;
                 }
+                // Simple agg has no group key to clean by watermarks.
+                Message::Watermark(_) => {}
             }
         }
     }
@@ -379,30 +416,42 @@ mod tests {
                 args: AggArgs::None,
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 0),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Min,
                 args: AggArgs::Unary(DataType::Int64, 0),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
         ];
 
         let simple_agg = Box::new(
-            SimpleAggExecutor::new(Box::new(source), agg_calls, keyspace, vec![], 1, vec![])
-                .unwrap(),
+            SimpleAggExecutor::new(
+                Box::new(source),
+                agg_calls,
+                keyspace,
+                vec![None; 4],
+                vec![],
+                1,
+                vec![],
+            )
+            .unwrap(),
         );
         let mut simple_agg = simple_agg.execute();
 
@@ -432,4 +481,219 @@ mod tests {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_collect_simple_aggregation_in_memory() {
+        test_collect_simple_aggregation(create_in_memory_keyspace_agg(3)).await
+    }
+
+    async fn test_collect_simple_aggregation(keyspace: Vec<Keyspace<impl StateStore>>) {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                // primary key column
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, vec![1]); // pk
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            "  I    I
+            + 12 1001
+            + 10 1002
+            + 12 1003",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            "  I    I
+            - 10 1002
+            + 14 1004",
+        ));
+        tx.push_barrier(3, false);
+
+        let append_only = false;
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::RowCount,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::BitAnd,
+                args: AggArgs::Unary(DataType::Int64, 0),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::ApproxCountDistinct,
+                args: AggArgs::Unary(DataType::Int64, 0),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+        ];
+
+        let simple_agg = Box::new(
+            SimpleAggExecutor::new(
+                Box::new(source),
+                agg_calls,
+                keyspace,
+                vec![None; 3],
+                vec![],
+                1,
+                vec![],
+            )
+            .unwrap(),
+        );
+        let mut simple_agg = simple_agg.execute();
+
+        // Consume the init barrier
+        simple_agg.next().await.unwrap().unwrap();
+        let msg = simple_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            *msg.as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I
+                + 3 8 2"
+            )
+        );
+        assert_matches!(
+            simple_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // Retracting `10` brings the bits it cleared back.
+        let msg = simple_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            *msg.as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                "  I  I I
+                U- 3  8 2
+                U+ 3 12 2"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_distinct_simple_aggregation_in_memory() {
+        test_distinct_simple_aggregation(create_in_memory_keyspace_agg(5)).await
+    }
+
+    async fn test_distinct_simple_aggregation(mut keyspace: Vec<Keyspace<impl StateStore>>) {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                // primary key column
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, vec![1]); // pk
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I    I
+            + 1 1001
+            + 1 1002
+            + 2 1003",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I    I
+            - 1 1001
+            + 3 1004
+            - 2 1003",
+        ));
+        tx.push_barrier(3, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I    I
+            - 1 1002",
+        ));
+        tx.push_barrier(4, false);
+
+        let append_only = false;
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::RowCount,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::Unary(DataType::Int64, 0),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: true,
+            },
+            AggCall {
+                kind: AggKind::Sum,
+                args: AggArgs::Unary(DataType::Int64, 0),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: true,
+            },
+        ];
+        let distinct_dedup_keyspaces = keyspace.split_off(3);
+        let distinct_dedup_keyspaces = std::iter::once(None)
+            .chain(distinct_dedup_keyspaces.into_iter().map(Some))
+            .collect();
+
+        let simple_agg = Box::new(
+            SimpleAggExecutor::new(
+                Box::new(source),
+                agg_calls,
+                keyspace,
+                distinct_dedup_keyspaces,
+                vec![],
+                1,
+                vec![],
+            )
+            .unwrap(),
+        );
+        let mut simple_agg = simple_agg.execute();
+
+        // Consume the init barrier
+        simple_agg.next().await.unwrap().unwrap();
+        let msg = simple_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            *msg.as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I
+                + 3 2 3"
+            )
+        );
+        assert_matches!(
+            simple_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The value `1` still has a copy, so only `2` is retracted.
+        let msg = simple_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            *msg.as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                "  I I I
+                U- 3 2 3
+                U+ 3 2 4"
+            )
+        );
+        assert_matches!(
+            simple_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The last copy of `1` is deleted.
+        let msg = simple_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            *msg.as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                "  I I I
+                U- 3 2 4
+                U+ 2 1 3"
+            )
+        );
+    }
 }
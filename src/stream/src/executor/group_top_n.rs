@@ -0,0 +1,359 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use risingwave_common::array::{Row, StreamChunk};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema};
+use risingwave_common::collection::evictable::EvictableHashMap;
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_common::util::ordered::{OrderedRow, OrderedRowDeserializer};
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_storage::cell_based_row_deserializer::CellBasedRowDeserializer;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::error::{StreamExecutorError, StreamExecutorResult};
+use super::managed_state::top_n::ManagedTopNRangeState;
+use super::top_n::generate_internal_key;
+use super::top_n_executor::{generate_output, TopNExecutorBase, TopNExecutorWrapper};
+use super::{BoxedMessageStream, Executor, ExecutorInfo, PkIndices, PkIndicesRef};
+
+/// Number of groups kept in the cache after each barrier.
+const GROUP_CACHE_SIZE: usize = 1 << 10;
+
+/// `GroupTopNExecutor` works like [`super::TopNExecutor`], but returns the top-n rows of each
+/// group, i.e. the rows with the same values of the group key, instead of the top-n rows overall.
+pub type GroupTopNExecutor<S> = TopNExecutorWrapper<InnerGroupTopNExecutor<S>>;
+
+impl<S: StateStore> GroupTopNExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input: Box<dyn Executor>,
+        order_pairs: Vec<OrderPair>,
+        offset_and_limit: (usize, Option<usize>),
+        pk_indices: PkIndices,
+        keyspace: Keyspace<S>,
+        cache_size: Option<usize>,
+        executor_id: u64,
+        group_by: Vec<usize>,
+    ) -> Result<Self> {
+        let info = input.info();
+        let schema = input.schema().clone();
+
+        Ok(TopNExecutorWrapper {
+            input,
+            inner: InnerGroupTopNExecutor::new(
+                info,
+                schema,
+                order_pairs,
+                offset_and_limit,
+                pk_indices,
+                keyspace,
+                cache_size,
+                executor_id,
+                group_by,
+            )?,
+        })
+    }
+}
+
+pub struct InnerGroupTopNExecutor<S: StateStore> {
+    info: ExecutorInfo,
+
+    /// Schema of the executor.
+    schema: Schema,
+
+    /// `OFFSET` and `LIMIT` of each group.
+    offset_and_limit: (usize, Option<usize>),
+
+    /// The primary key indices of the `GroupTopNExecutor`
+    pk_indices: PkIndices,
+
+    /// The internal key indices of the `GroupTopNExecutor`
+    internal_key_indices: PkIndices,
+
+    /// The order of internal keys of the `GroupTopNExecutor`
+    internal_key_order_types: Vec<OrderType>,
+
+    /// Indices of the columns of the group key.
+    group_by: Vec<usize>,
+
+    /// The states of each group are kept under the keyspace prefixed by the group key.
+    keyspace: Keyspace<S>,
+    cache_size: Option<usize>,
+    row_data_types: Vec<DataType>,
+    ordered_row_deserializer: OrderedRowDeserializer,
+    cell_based_row_deserializer: CellBasedRowDeserializer,
+
+    /// The cached states of the groups. `group key -> rows of the group`.
+    groups: EvictableHashMap<Row, ManagedTopNRangeState<S>>,
+}
+
+impl<S: StateStore> InnerGroupTopNExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input_info: ExecutorInfo,
+        schema: Schema,
+        order_pairs: Vec<OrderPair>,
+        offset_and_limit: (usize, Option<usize>),
+        pk_indices: PkIndices,
+        keyspace: Keyspace<S>,
+        cache_size: Option<usize>,
+        executor_id: u64,
+        group_by: Vec<usize>,
+    ) -> Result<Self> {
+        let (internal_key_indices, internal_key_data_types, internal_key_order_types) =
+            generate_internal_key(&order_pairs, &pk_indices, &schema);
+
+        let ordered_row_deserializer =
+            OrderedRowDeserializer::new(internal_key_data_types, internal_key_order_types.clone());
+
+        let row_data_types = schema
+            .fields
+            .iter()
+            .map(|field| field.data_type.clone())
+            .collect::<Vec<_>>();
+        let table_column_descs = row_data_types
+            .iter()
+            .enumerate()
+            .map(|(id, data_type)| {
+                ColumnDesc::unnamed(ColumnId::from(id as i32), data_type.clone())
+            })
+            .collect::<Vec<_>>();
+        let cell_based_row_deserializer = CellBasedRowDeserializer::new(table_column_descs);
+
+        Ok(Self {
+            info: ExecutorInfo {
+                schema: input_info.schema,
+                pk_indices: input_info.pk_indices,
+                identity: format!("GroupTopNExecutor {:X}", executor_id),
+            },
+            schema,
+            offset_and_limit,
+            pk_indices,
+            internal_key_indices,
+            internal_key_order_types,
+            group_by,
+            keyspace,
+            cache_size,
+            row_data_types,
+            ordered_row_deserializer,
+            cell_based_row_deserializer,
+            groups: EvictableHashMap::new(GROUP_CACHE_SIZE),
+        })
+    }
+
+    /// Creates the state of a group, filled in from storage.
+    async fn new_group_state(
+        &self,
+        group_key: &Row,
+        epoch: u64,
+    ) -> StreamExecutorResult<ManagedTopNRangeState<S>> {
+        let keyspace = self.keyspace.append(
+            group_key
+                .serialize()
+                .map_err(StreamExecutorError::top_n_state_error)?,
+        );
+        // The total counts are recovered when the cache is filled in.
+        let mut state = ManagedTopNRangeState::new(
+            self.offset_and_limit,
+            self.cache_size,
+            (0, 0, 0),
+            keyspace,
+            self.row_data_types.clone(),
+            self.ordered_row_deserializer.clone(),
+            self.cell_based_row_deserializer.clone(),
+        );
+        state
+            .fill_in_cache(epoch)
+            .await
+            .map_err(StreamExecutorError::top_n_state_error)?;
+        Ok(state)
+    }
+}
+
+impl<S: StateStore> Executor for InnerGroupTopNExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        panic!("Should execute by wrapper");
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> TopNExecutorBase for InnerGroupTopNExecutor<S> {
+    async fn apply_chunk(
+        &mut self,
+        chunk: StreamChunk,
+        epoch: u64,
+    ) -> StreamExecutorResult<StreamChunk> {
+        let mut new_ops = vec![];
+        let mut new_rows = vec![];
+
+        for (op, row_ref) in chunk.rows() {
+            let group_key = row_ref.row_by_indices(&self.group_by);
+            if !self.groups.contains(&group_key) {
+                let state = self.new_group_state(&group_key, epoch).await?;
+                self.groups.put(group_key.clone(), state);
+            }
+            let state = self.groups.get_mut(&group_key).unwrap();
+
+            let pk_row = row_ref.row_by_indices(&self.internal_key_indices);
+            let ordered_pk_row = OrderedRow::new(pk_row, &self.internal_key_order_types);
+            let row = row_ref.to_owned_row();
+            state
+                .apply(op, ordered_pk_row, row, epoch, &mut new_ops, &mut new_rows)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
+        }
+        generate_output(new_rows, new_ops, &self.schema)
+    }
+
+    async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        for state in self.groups.values_mut() {
+            if state.is_dirty() {
+                state
+                    .flush(epoch)
+                    .await
+                    .map_err(StreamExecutorError::top_n_state_error)?;
+            }
+        }
+        self.groups.evict_to_target_cap();
+        Ok(())
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::Field;
+
+    use super::*;
+    use crate::executor::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor::{Barrier, Message};
+
+    fn create_source() -> Box<MockSource> {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let chunk1 = StreamChunk::from_pretty(
+            " I I I
+            + 1 3 0
+            + 1 1 1
+            + 2 5 2
+            + 1 2 3
+            + 2 4 4",
+        );
+        let chunk2 = StreamChunk::from_pretty(
+            " I I I
+            - 1 1 1
+            + 2 6 5",
+        );
+        Box::new(MockSource::with_messages(
+            schema,
+            vec![2],
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(chunk1),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(chunk2),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_group_top_n_executor() {
+        let source = create_source();
+        let keyspace = create_in_memory_keyspace();
+        let top_n_executor = Box::new(
+            GroupTopNExecutor::new(
+                source as Box<dyn Executor>,
+                vec![OrderPair::new(1, OrderType::Ascending)],
+                (0, Some(2)),
+                vec![2],
+                keyspace,
+                Some(2),
+                1,
+                vec![0],
+            )
+            .unwrap(),
+        );
+        let mut top_n_executor = top_n_executor.execute();
+
+        // consume the init barrier
+        top_n_executor.next().await.unwrap().unwrap();
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            *res.as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I
+                + 1 3 0
+                + 1 1 1
+                + 2 5 2
+                - 1 3 0
+                + 1 2 3
+                + 2 4 4"
+            )
+        );
+        // group 1: (1, 2) -> (3), group 2: (4, 5) -> ()
+        assert_matches!(
+            top_n_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            *res.as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I
+                - 1 1 1
+                + 1 3 0"
+            )
+        );
+        // group 1: (2, 3) -> (), group 2: (4, 5) -> (6)
+        assert_matches!(
+            top_n_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+    }
+}
@@ -33,15 +33,17 @@ use risingwave_storage::table::state_table::StateTable;
 use risingwave_storage::{Keyspace, StateStore};
 
 use super::{
-    expect_first_barrier, pk_input_arrays, Executor, PkDataTypes, PkIndicesRef,
-    StreamExecutorResult,
+    delete_below_watermark, expect_first_barrier, pk_input_arrays, Executor, PkDataTypes,
+    PkIndicesRef, StreamExecutorResult, Watermark,
 };
 use crate::executor::aggregation::{
     agg_input_arrays, generate_agg_schema, generate_managed_agg_state, get_key_len, AggCall,
     AggState,
 };
 use crate::executor::error::StreamExecutorError;
+use crate::executor::managed_state::aggregation::is_value_state;
 use crate::executor::{BoxedMessageStream, Message, PkIndices, PROCESSING_WINDOW_SIZE};
+use crate::task::ActorId;
 
 /// [`HashAggExecutor`] could process large amounts of data using a state backend. It works as
 /// follows:
@@ -53,6 +55,9 @@ use crate::executor::{BoxedMessageStream, Message, PkIndices, PROCESSING_WINDOW_
 /// * Upon a barrier is received, the executor will call `.flush` on the storage backend, so that
 ///   all modifications will be flushed to the storage backend. Meanwhile, the executor will go
 ///   through `modified_keys`, and produce a stream chunk based on the state changes.
+/// * If a watermark on the first group key is received, the states of the groups below it will
+///   never change. They are cleaned from both the cache and the storage backend on the next
+///   barrier, and the watermark is forwarded on the corresponding output column.
 pub struct HashAggExecutor<K: HashKey, S: StateStore> {
     input: Box<dyn Executor>,
 
@@ -71,6 +76,9 @@ struct HashAggExecutorExtra<S: StateStore> {
     /// See [`Executor::identity`].
     identity: String,
 
+    /// Belonged actor id.
+    actor_id: ActorId,
+
     /// Pk indices from input
     input_pk_indices: Vec<usize>,
 
@@ -80,6 +88,9 @@ struct HashAggExecutorExtra<S: StateStore> {
     /// The executor operates on this keyspace.
     keyspace: Vec<Keyspace<S>>,
 
+    /// The keyspaces of the dedup states, one for each distinct [`AggCall`].
+    distinct_dedup_keyspaces: Vec<Option<Keyspace<S>>>,
+
     /// A [`HashAggExecutor`] may have multiple [`AggCall`]s.
     agg_calls: Vec<AggCall>,
 
@@ -113,12 +124,15 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         input: Box<dyn Executor>,
         agg_calls: Vec<AggCall>,
         keyspace: Vec<Keyspace<S>>,
+        distinct_dedup_keyspaces: Vec<Option<Keyspace<S>>>,
         pk_indices: PkIndices,
+        actor_id: ActorId,
         executor_id: u64,
         key_indices: Vec<usize>,
     ) -> Result<Self> {
         let input_info = input.info();
         let schema = generate_agg_schema(input.as_ref(), &agg_calls, Some(&key_indices));
+        assert_eq!(distinct_dedup_keyspaces.len(), agg_calls.len());
 
         let mut state_tables = Vec::with_capacity(agg_calls.len());
         for (agg_call, ks) in agg_calls.iter().zip_eq(&keyspace) {
@@ -141,9 +155,11 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 schema,
                 pk_indices,
                 identity: format!("HashAggExecutor-{:X}", executor_id),
+                actor_id,
                 input_pk_indices: input_info.pk_indices,
                 input_schema: input_info.schema,
                 keyspace,
+                distinct_dedup_keyspaces,
                 agg_calls,
                 key_indices,
                 state_tables,
@@ -210,6 +226,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             ref input_pk_indices,
             ref input_schema,
             ref keyspace,
+            ref distinct_dedup_keyspaces,
             ref schema,
             ref state_tables,
             ..
@@ -278,6 +295,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                                 ),
                                 agg_calls,
                                 keyspace,
+                                distinct_dedup_keyspaces,
                                 input_pk_data_types.clone(),
                                 epoch,
                                 Some(hash_code),
@@ -294,13 +312,31 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                     .await
                     .map_err(StreamExecutorError::agg_state_error)?;
 
-                // 3. Apply batch to each of the state (per agg_call)
-                for (agg_state, data) in
-                    states.managed_states.iter_mut().zip_eq(all_agg_data.iter())
+                // 3. Apply batch to each of the state (per agg_call). The input of distinct agg
+                // calls is deduplicated first.
+                for ((agg_state, dedup), data) in states
+                    .managed_states
+                    .iter_mut()
+                    .zip_eq(states.distinct_dedups.iter_mut())
+                    .zip_eq(all_agg_data.iter())
                 {
                     let data = data.iter().map(|d| &**d).collect_vec();
+                    let dedup_vis_map = match dedup {
+                        Some(dedup) => Some(
+                            dedup
+                                .dedup(&ops, Some(&vis_map), data[0], epoch)
+                                .await
+                                .map_err(StreamExecutorError::agg_state_error)?,
+                        ),
+                        None => None,
+                    };
                     agg_state
-                        .apply_batch(&ops, Some(&vis_map), &data, epoch)
+                        .apply_batch(
+                            &ops,
+                            Some(dedup_vis_map.as_ref().unwrap_or(&vis_map)),
+                            &data,
+                            epoch,
+                        )
                         .await
                         .map_err(StreamExecutorError::agg_state_error)?;
                 }
@@ -342,9 +378,8 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             for states in state_map.values_mut() {
                 if states.as_ref().unwrap().is_dirty() {
                     dirty_cnt += 1;
+                    let states = states.as_mut().unwrap();
                     for (state, state_table) in states
-                        .as_mut()
-                        .unwrap()
                         .managed_states
                         .iter_mut()
                         .zip_eq(state_tables.iter_mut())
@@ -354,6 +389,11 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                             .await
                             .map_err(StreamExecutorError::agg_state_error)?;
                     }
+                    for dedup in states.distinct_dedups.iter_mut().flatten() {
+                        dedup
+                            .flush(&mut write_batch)
+                            .map_err(StreamExecutorError::agg_state_error)?;
+                    }
                 }
             }
 
@@ -425,6 +465,59 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         }
     }
 
+    /// Clean the states of the groups whose first group key is below the `watermark`.
+    async fn clean_state(
+        &HashAggExecutorExtra::<S> {
+            ref key_indices,
+            ref agg_calls,
+            ref keyspace,
+            ref distinct_dedup_keyspaces,
+            ref schema,
+            ..
+        }: &HashAggExecutorExtra<S>,
+        state_map: &mut EvictableHashMap<K, Option<Box<AggState<S>>>>,
+        watermark: &Watermark,
+        epoch: u64,
+    ) -> StreamExecutorResult<()> {
+        let key_data_types = &schema.data_types()[..key_indices.len()];
+        let mut expired_keys = vec![];
+        for (key, _) in state_map.iter() {
+            let key_row = key
+                .clone()
+                .deserialize(key_data_types.iter())
+                .map_err(StreamExecutorError::eval_error)?;
+            if let Some(val) = &key_row[0] && val < &watermark.val {
+                expired_keys.push(key.clone());
+            }
+        }
+        for key in expired_keys {
+            state_map.pop(&key);
+        }
+
+        let mut write_batch = keyspace[0].state_store().start_write_batch();
+        for (agg_call, keyspace) in agg_calls.iter().zip_eq(keyspace) {
+            delete_below_watermark(
+                keyspace,
+                &mut write_batch,
+                watermark,
+                is_value_state(agg_call),
+                epoch,
+            )
+            .await?;
+        }
+        // The dedup states are prefixed by the ascending-encoded group key.
+        for keyspace in distinct_dedup_keyspaces.iter().flatten() {
+            delete_below_watermark(keyspace, &mut write_batch, watermark, false, epoch).await?;
+        }
+        if !write_batch.is_empty() {
+            write_batch
+                .ingest(epoch)
+                .await
+                .map_err(StreamExecutorError::agg_state_error)?;
+        }
+        Ok(())
+    }
+
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(self) {
         let HashAggExecutor {
@@ -437,7 +530,20 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         let mut input = input.execute();
         let barrier = expect_first_barrier(&mut input).await?;
         let mut epoch = barrier.epoch.curr;
+        let vnode_changed = barrier.is_vnode_changed(extra.actor_id);
+        let prev_epoch = barrier.epoch.prev;
         yield Message::Barrier(barrier);
+        // A new actor created by rescheduling reads the states written by the previous owners.
+        if vnode_changed {
+            extra.keyspace[0]
+                .state_store()
+                .wait_epoch(prev_epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+        }
+
+        // The latest watermark on the first group key received in this epoch.
+        let mut watermark: Option<Watermark> = None;
 
         #[for_await]
         for msg in input {
@@ -446,6 +552,12 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 Message::Chunk(chunk) => {
                     Self::apply_chunk(&extra, &mut state_map, chunk, epoch).await?;
                 }
+                Message::Watermark(w) => {
+                    // Watermarks on other columns can not be used to clean the state.
+                    if extra.key_indices.first() == Some(&w.col_idx) {
+                        watermark = Some(w);
+                    }
+                }
                 Message::Barrier(barrier) => {
                     let next_epoch = barrier.epoch.curr;
                     assert_eq!(epoch, barrier.epoch.prev);
@@ -455,7 +567,27 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                         yield Message::Chunk(chunk?);
                     }
 
+                    if let Some(watermark) = watermark.take() {
+                        Self::clean_state(&extra, &mut state_map, &watermark, epoch).await?;
+                        // The first group key is the first output column.
+                        yield Message::Watermark(watermark.with_idx(0));
+                    }
+
+                    // The vnodes of this actor are changed by the reschedule, so the cached states
+                    // may be owned by other actors now.
+                    let vnode_changed = barrier.is_vnode_changed(extra.actor_id);
+                    if vnode_changed {
+                        state_map.clear();
+                    }
+
                     yield Message::Barrier(barrier);
+                    if vnode_changed {
+                        extra.keyspace[0]
+                            .state_store()
+                            .wait_epoch(epoch)
+                            .await
+                            .map_err(StreamExecutorError::storage)?;
+                    }
                     epoch = next_epoch;
                 }
             }
@@ -476,13 +608,13 @@ mod tests {
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::error::Result;
     use risingwave_common::hash::{calc_hash_key_kind, HashKey, HashKeyDispatcher};
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_expr::expr::*;
     use risingwave_storage::{Keyspace, StateStore};
 
     use crate::executor::aggregation::{AggArgs, AggCall};
     use crate::executor::test_utils::*;
-    use crate::executor::{Executor, HashAggExecutor, Message, PkIndices};
+    use crate::executor::{Executor, HashAggExecutor, Message, PkIndices, Watermark};
 
     struct HashAggExecutorDispatcher<S: StateStore>(PhantomData<S>);
 
@@ -491,6 +623,7 @@ mod tests {
         agg_calls: Vec<AggCall>,
         key_indices: Vec<usize>,
         keyspace: Vec<Keyspace<S>>,
+        distinct_dedup_keyspaces: Vec<Option<Keyspace<S>>>,
         pk_indices: PkIndices,
         executor_id: u64,
     }
@@ -504,18 +637,41 @@ mod tests {
                 args.input,
                 args.agg_calls,
                 args.keyspace,
+                args.distinct_dedup_keyspaces,
                 args.pk_indices,
+                0,
                 args.executor_id,
                 args.key_indices,
             )?))
         }
     }
 
-    fn new_boxed_hash_agg_executor(
+    fn new_boxed_hash_agg_executor<S: StateStore>(
+        input: Box<dyn Executor>,
+        agg_calls: Vec<AggCall>,
+        key_indices: Vec<usize>,
+        keyspace: Vec<Keyspace<S>>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+    ) -> Box<dyn Executor> {
+        let distinct_dedup_keyspaces = vec![None; agg_calls.len()];
+        new_boxed_distinct_hash_agg_executor(
+            input,
+            agg_calls,
+            key_indices,
+            keyspace,
+            distinct_dedup_keyspaces,
+            pk_indices,
+            executor_id,
+        )
+    }
+
+    fn new_boxed_distinct_hash_agg_executor<S: StateStore>(
         input: Box<dyn Executor>,
         agg_calls: Vec<AggCall>,
         key_indices: Vec<usize>,
-        keyspace: Vec<Keyspace<impl StateStore>>,
+        keyspace: Vec<Keyspace<S>>,
+        distinct_dedup_keyspaces: Vec<Option<Keyspace<S>>>,
         pk_indices: PkIndices,
         executor_id: u64,
     ) -> Box<dyn Executor> {
@@ -528,6 +684,7 @@ mod tests {
             agg_calls,
             key_indices,
             keyspace,
+            distinct_dedup_keyspaces,
             pk_indices,
             executor_id,
         };
@@ -587,18 +744,21 @@ mod tests {
                 args: AggArgs::None,
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Count,
                 args: AggArgs::Unary(DataType::Int64, 0),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Count,
                 args: AggArgs::None,
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
         ];
 
@@ -674,12 +834,14 @@ mod tests {
                 args: AggArgs::None,
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             // This is local hash aggregation, so we add another sum state
             AggCall {
@@ -687,6 +849,7 @@ mod tests {
                 args: AggArgs::Unary(DataType::Int64, 2),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
         ];
 
@@ -769,12 +932,14 @@ mod tests {
                 args: AggArgs::None,
                 return_type: DataType::Int64,
                 append_only: false,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Min,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
                 append_only: false,
+                distinct: false,
             },
         ];
 
@@ -856,12 +1021,14 @@ mod tests {
                 args: AggArgs::None,
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Min,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
         ];
 
@@ -903,6 +1070,327 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_collect_hash_aggregation_in_memory() {
+        test_collect_hash_aggregation(create_in_memory_keyspace_agg(3)).await
+    }
+
+    async fn test_collect_hash_aggregation(keyspace: Vec<Keyspace<impl StateStore>>) {
+        let schema = Schema {
+            fields: vec![
+                // group key column
+                Field::unnamed(DataType::Int64),
+                // data column
+                Field::unnamed(DataType::Int64),
+                // primary key column
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, vec![2]); // pk
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I    I
+            + 1 1 1001
+            + 1 2 1002
+            + 1 2 1003
+            + 2 4 1004",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I    I
+            - 1 1 1001
+            - 1 2 1002
+            + 2 8 1005",
+        ));
+        tx.push_barrier(3, false);
+
+        let keys = vec![0];
+        let append_only = false;
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::RowCount,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::BitOr,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::ApproxCountDistinct,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+        ];
+
+        let hash_agg =
+            new_boxed_hash_agg_executor(Box::new(source), agg_calls, keys, keyspace, vec![], 1);
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 1 3 3 2
+                + 2 1 4 1"
+            )
+            .sorted_rows(),
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The outputs are recomputed from the values left in each group.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I  I I
+                U- 1 3  3 2
+                U+ 1 1  2 1
+                U- 2 1  4 1
+                U+ 2 2 12 2"
+            )
+            .sorted_rows(),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_distinct_hash_aggregation_in_memory() {
+        test_distinct_hash_aggregation(create_in_memory_keyspace_agg(5)).await
+    }
+
+    async fn test_distinct_hash_aggregation(mut keyspace: Vec<Keyspace<impl StateStore>>) {
+        let schema = Schema {
+            fields: vec![
+                // group key column
+                Field::unnamed(DataType::Int64),
+                // distinct column
+                Field::unnamed(DataType::Int64),
+                // primary key column
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, vec![2]); // pk
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            + 1 10 1001
+            + 1 10 1002
+            + 1 20 1003
+            + 2 10 1004",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            - 1 10 1001
+            - 1 20 1003
+            + 2 10 1005",
+        ));
+        tx.push_barrier(3, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            + 1 20 1006",
+        ));
+        tx.push_barrier(4, false);
+
+        let keys = vec![0];
+        let append_only = false;
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::RowCount,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: true,
+            },
+            AggCall {
+                kind: AggKind::Sum,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: true,
+            },
+        ];
+        let distinct_dedup_keyspaces = keyspace.split_off(3);
+        let distinct_dedup_keyspaces = std::iter::once(None)
+            .chain(distinct_dedup_keyspaces.into_iter().map(Some))
+            .collect();
+
+        let hash_agg = new_boxed_distinct_hash_agg_executor(
+            Box::new(source),
+            agg_calls,
+            keys,
+            keyspace,
+            distinct_dedup_keyspaces,
+            vec![],
+            1,
+        );
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I I  I
+                + 1 3 2 30
+                + 2 1 1 10"
+            )
+            .sorted_rows(),
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // `10` still has a copy in group 1, while the count of `20` drops to zero. The values are
+        // deduplicated in each group separately.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I I  I
+                U- 1 3 2 30
+                U+ 1 1 1 10
+                U- 2 1 1 10
+                U+ 2 2 1 10"
+            )
+            .sorted_rows(),
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // `20` is counted again after its count dropped to zero.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I I  I
+                U- 1 1 1 10
+                U+ 1 2 2 30"
+            )
+            .sorted_rows(),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_aggregation_watermark_in_memory() {
+        test_hash_aggregation_watermark(create_in_memory_keyspace_agg(2)).await
+    }
+
+    async fn test_hash_aggregation_watermark(keyspace: Vec<Keyspace<impl StateStore>>) {
+        let schema = Schema {
+            fields: vec![
+                // group key column
+                Field::unnamed(DataType::Int64),
+                // data column to get minimum
+                Field::unnamed(DataType::Int64),
+                // primary key column
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, vec![2]); // pk
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I  I
+            + 1 10 1000
+            + 2 20 1001
+            + 3 30 1002",
+        ));
+        tx.push_watermark(0, DataType::Int64, ScalarImpl::Int64(3));
+        tx.push_barrier(2, false);
+        // The group 1 is below the watermark, which never happens in practice. It's only used to
+        // check that the state of the group has been cleaned.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I  I
+            + 1 15 1003
+            + 3 5  1004",
+        ));
+        tx.push_barrier(3, false);
+
+        let keys = vec![0];
+        let append_only = false;
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::RowCount,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::Min,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                append_only,
+                distinct: false,
+            },
+        ];
+
+        let hash_agg =
+            new_boxed_hash_agg_executor(Box::new(source), agg_calls, keys, keyspace, vec![], 1);
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I I
+                + 1 1 10
+                + 2 1 20
+                + 3 1 30"
+            )
+            .sorted_rows(),
+        );
+        // The watermark is forwarded on the group key after the chunk.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_watermark().unwrap(),
+            Watermark::new(0, DataType::Int64, ScalarImpl::Int64(3))
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The group 1 starts from scratch, while the group 3 is kept.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I I
+                +  1 1 15
+                U- 3 1 30
+                U+ 3 2 5"
+            )
+            .sorted_rows(),
+        );
+    }
+
     trait SortedRows {
         fn sorted_rows(self) -> Vec<(Op, Row)>;
     }
@@ -20,16 +20,19 @@ use risingwave_common::array::{Array, ArrayRef, DataChunk, Op, Row, RowRef, Stre
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{internal_error, Result, RwError};
 use risingwave_common::hash::HashKey;
-use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
 use risingwave_expr::expr::RowExpression;
 use risingwave_storage::{Keyspace, StateStore};
 
 use super::barrier_align::*;
 use super::error::StreamExecutorError;
 use super::managed_state::join::*;
-use super::{BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef};
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef, Watermark,
+};
 use crate::common::StreamChunkBuilder;
 use crate::executor::PROCESSING_WINDOW_SIZE;
+use crate::task::ActorId;
 
 pub const JOIN_CACHE_SIZE: usize = 1 << 16;
 
@@ -125,6 +128,8 @@ struct JoinSide<K: HashKey, S: StateStore> {
     start_pos: usize,
     /// The join side operates on this keyspace.
     keyspace: Keyspace<S>,
+    /// The latest watermark on the first join key of this side.
+    watermark: Option<ScalarImpl>,
 }
 
 impl<K: HashKey, S: StateStore> std::fmt::Debug for JoinSide<K, S> {
@@ -157,6 +162,10 @@ impl<K: HashKey, S: StateStore> JoinSide<K, S> {
 
 /// `HashJoinExecutor` takes two input streams and runs equal hash join on them.
 /// The output columns are the concatenation of left and right columns.
+///
+/// Watermarks on the first join key of both sides are aligned by taking the minimum. Rows below
+/// the aligned watermark can no longer be matched or retracted, so their states are cleaned on the
+/// next barrier, and the watermark is forwarded on the output columns of the join key.
 pub struct HashJoinExecutor<K: HashKey, S: StateStore, const T: JoinTypePrimitive> {
     /// Left input executor.
     input_l: Option<BoxedExecutor>,
@@ -176,6 +185,8 @@ pub struct HashJoinExecutor<K: HashKey, S: StateStore, const T: JoinTypePrimitiv
     cond: Option<RowExpression>,
     /// Identity string
     identity: String,
+    /// Belonged actor id
+    actor_id: ActorId,
     /// Epoch
     epoch: u64,
 
@@ -189,6 +200,12 @@ pub struct HashJoinExecutor<K: HashKey, S: StateStore, const T: JoinTypePrimitiv
 
     /// Whether the logic can be optimized for append-only stream
     append_only_optimize: bool,
+
+    /// The last aligned watermark on the join key.
+    aligned_watermark: Option<ScalarImpl>,
+
+    /// The aligned watermark to clean the states with on the next barrier.
+    watermark_to_clean: Option<Watermark>,
 }
 
 impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> std::fmt::Debug
@@ -357,6 +374,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         params_l: JoinParams,
         params_r: JoinParams,
         pk_indices: PkIndices,
+        actor_id: ActorId,
         executor_id: u64,
         cond: Option<RowExpression>,
         op_info: String,
@@ -436,6 +454,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                 pk_indices: pk_indices_l,
                 start_pos: 0,
                 keyspace: ks_l,
+                watermark: None,
             },
             side_r: JoinSide {
                 ht: JoinHashMap::new(
@@ -450,14 +469,18 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                 pk_indices: pk_indices_r,
                 start_pos: side_l_column_n,
                 keyspace: ks_r,
+                watermark: None,
             },
             pk_indices,
             cond,
             identity: format!("HashJoinExecutor {:X}", executor_id),
+            actor_id,
             op_info,
             key_indices,
             epoch: 0,
             append_only_optimize,
+            aligned_watermark: None,
+            watermark_to_clean: None,
         }
     }
 
@@ -497,20 +520,94 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                         yield chunk.map_err(StreamExecutorError::hash_join_error)?;
                     }
                 }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    for watermark in self.handle_watermark(SideType::Left, watermark) {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+                AlignedMessage::WatermarkRight(watermark) => {
+                    for watermark in self.handle_watermark(SideType::Right, watermark) {
+                        yield Message::Watermark(watermark);
+                    }
+                }
                 AlignedMessage::Barrier(barrier) => {
                     self.flush_data()
                         .await
                         .map_err(StreamExecutorError::hash_join_error)?;
+                    if let Some(watermark) = self.watermark_to_clean.take() {
+                        self.side_l.ht.clean_below_watermark(&watermark).await?;
+                        self.side_r.ht.clean_below_watermark(&watermark).await?;
+                    }
+                    // The cached rows of the vnodes moved to other actors by the reschedule must
+                    // not be matched any more.
+                    let vnode_changed = barrier.is_vnode_changed(self.actor_id);
+                    if vnode_changed {
+                        self.side_l.ht.clear();
+                        self.side_r.ht.clear();
+                    }
+                    let prev_epoch = barrier.epoch.prev;
                     let epoch = barrier.epoch.curr;
                     self.side_l.ht.update_epoch(epoch);
                     self.side_r.ht.update_epoch(epoch);
                     self.epoch = epoch;
                     yield Message::Barrier(barrier);
+                    // The rows of the vnodes taken over are written by other actors in the
+                    // previous epoch, which must be committed before they are read.
+                    if vnode_changed {
+                        self.side_l
+                            .keyspace
+                            .state_store()
+                            .wait_epoch(prev_epoch)
+                            .await
+                            .map_err(StreamExecutorError::storage)?;
+                    }
                 }
             }
         }
     }
 
+    /// Handle a watermark from one side. Returns the watermarks to emit on the output columns of
+    /// the join key if the aligned watermark advances.
+    fn handle_watermark(
+        &mut self,
+        side: SideTypePrimitive,
+        watermark: Watermark,
+    ) -> Vec<Watermark> {
+        let side_update = if side == SideType::Left {
+            &mut self.side_l
+        } else {
+            &mut self.side_r
+        };
+        // Only the watermark on the first join key can be used to clean the state.
+        if side_update.key_indices.first() != Some(&watermark.col_idx) {
+            return vec![];
+        }
+        side_update.watermark = Some(watermark.val);
+
+        let (Some(val_l), Some(val_r)) = (&self.side_l.watermark, &self.side_r.watermark) else {
+            return vec![];
+        };
+        let aligned = val_l.min(val_r).clone();
+        if matches!(&self.aligned_watermark, Some(prev) if prev >= &aligned) {
+            return vec![];
+        }
+        self.aligned_watermark = Some(aligned.clone());
+        let aligned = Watermark::new(0, watermark.data_type, aligned);
+        self.watermark_to_clean = Some(aligned.clone());
+
+        let key_l = self.side_l.key_indices[0];
+        let key_r = self.side_r.key_indices[0];
+        let output_indices = match T {
+            JoinType::LeftSemi | JoinType::LeftAnti => vec![key_l],
+            JoinType::RightSemi | JoinType::RightAnti => vec![key_r],
+            _ => vec![key_l, self.side_r.start_pos + key_r],
+        };
+        output_indices
+            .into_iter()
+            .map(|idx| aligned.clone().with_idx(idx))
+            .collect()
+    }
+
     async fn flush_data(&mut self) -> Result<()> {
         let epoch = self.epoch;
         for side in [&mut self.side_l, &mut self.side_r] {
@@ -753,7 +850,7 @@ mod tests {
 
     use super::{HashJoinExecutor, JoinParams, JoinType, *};
     use crate::executor::test_utils::{MessageSender, MockSource};
-    use crate::executor::{Barrier, Epoch, Message};
+    use crate::executor::{Barrier, Epoch, Message, Watermark};
 
     fn create_in_memory_keyspace() -> (Keyspace<MemoryStateStore>, Keyspace<MemoryStateStore>) {
         let mem_state = MemoryStateStore::new();
@@ -798,6 +895,7 @@ mod tests {
             params_l,
             params_r,
             vec![1],
+            0,
             1,
             cond,
             "HashJoinExecutor".to_string(),
@@ -833,6 +931,7 @@ mod tests {
             params_l,
             params_r,
             vec![1],
+            0,
             1,
             cond,
             "HashJoinExecutor".to_string(),
@@ -919,6 +1018,62 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join_watermark() {
+        let chunk_l1 = StreamChunk::from_pretty(
+            "  I I
+             + 1 4
+             + 3 6",
+        );
+        // The row with key 1 is below the watermark, which never happens in practice. It's only
+        // used to check that the state of the key has been cleaned.
+        let chunk_r1 = StreamChunk::from_pretty(
+            "  I I
+             + 1 7
+             + 3 8",
+        );
+        let (mut tx_l, mut tx_r, mut hash_join) = create_executor::<{ JoinType::Inner }>(false);
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty("I I I I")
+        );
+
+        // The aligned watermark is the minimum of both sides, forwarded on both join keys.
+        tx_l.push_watermark(0, DataType::Int64, ScalarImpl::Int64(3));
+        tx_r.push_watermark(0, DataType::Int64, ScalarImpl::Int64(2));
+        for col_idx in [0, 2] {
+            let msg = hash_join.next().await.unwrap().unwrap();
+            assert_eq!(
+                msg.into_watermark().unwrap(),
+                Watermark::new(col_idx, DataType::Int64, ScalarImpl::Int64(2))
+            );
+        }
+
+        // The states below the watermark are cleaned on the barrier.
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        tx_r.push_chunk(chunk_r1);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 3 6 3 8"
+            )
+        );
+    }
+
     #[tokio::test]
     async fn test_streaming_hash_left_semi_join() {
         let chunk_l1 = StreamChunk::from_pretty(
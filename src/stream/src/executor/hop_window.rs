@@ -93,6 +93,8 @@ impl HopWindowExecutor {
             })?
             .get();
 
+        // The output columns are the input columns followed by `window_start` and `window_end`.
+        let window_start_col_idx = input.schema().len();
         let schema = self.info.schema;
         let time_col_data_type = schema.fields()[time_col_idx].data_type();
         let time_col_ref = InputRefExpression::new(time_col_data_type, self.time_col_idx).boxed();
@@ -170,11 +172,33 @@ impl HopWindowExecutor {
 
         #[for_await]
         for msg in input.execute() {
-            let msg = msg?;
-            let Message::Chunk(chunk) = msg else {
-                // TODO: syn has not supported `let_else`, we desugar here manually.
-                yield std::task::Poll::Ready(msg);
-                continue;
+            let chunk = match msg? {
+                Message::Chunk(chunk) => chunk,
+                Message::Watermark(watermark) => {
+                    if watermark.col_idx == time_col_idx {
+                        // No later rows can belong to windows before the first window of the
+                        // watermark.
+                        if let Some(window_start) = watermark
+                            .clone()
+                            .transform_with_expr(&hop_start, window_start_col_idx)?
+                        {
+                            let window_end = window_start.clone().with_idx(0).transform_with_expr(
+                                &window_end_exprs[0],
+                                window_start_col_idx + 1,
+                            )?;
+                            yield Message::Watermark(window_start);
+                            if let Some(window_end) = window_end {
+                                yield Message::Watermark(window_end);
+                            }
+                        }
+                    }
+                    yield Message::Watermark(watermark);
+                    continue;
+                }
+                msg => {
+                    yield msg;
+                    continue;
+                }
             };
             // TODO: compact may be not necessary here.
             let chunk = chunk.compact().map_err(StreamExecutorError::executor_v1)?;
@@ -210,10 +234,10 @@ mod tests {
     use futures::StreamExt;
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::{DataType, IntervalUnit};
+    use risingwave_common::types::{DataType, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl};
 
     use crate::executor::test_utils::MockSource;
-    use crate::executor::{Executor, ExecutorInfo, StreamChunk};
+    use crate::executor::{Executor, ExecutorInfo, Message, StreamChunk, Watermark};
 
     #[tokio::test]
     async fn test_execute() {
@@ -293,4 +317,56 @@ mod tests {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_watermark() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::with_name(DataType::Timestamp, "created_at"),
+        ]);
+        let ts = |minutes: i64| {
+            ScalarImpl::NaiveDateTime(
+                NaiveDateTimeWrapper::from_protobuf(minutes * 60 * 1_000_000).unwrap(),
+            )
+        };
+        let input = MockSource::with_messages(
+            schema.clone(),
+            vec![0],
+            vec![Message::Watermark(Watermark::new(
+                1,
+                DataType::Timestamp,
+                ts(22),
+            ))],
+        )
+        .boxed();
+
+        let executor = super::HopWindowExecutor::new(
+            input,
+            ExecutorInfo {
+                schema,
+                pk_indices: vec![0],
+                identity: "test".to_string(),
+            },
+            1,
+            IntervalUnit::from_minutes(15),
+            IntervalUnit::from_minutes(30),
+        )
+        .boxed();
+        let mut stream = executor.execute();
+
+        let mut watermarks = vec![];
+        for _ in 0..3 {
+            let msg = stream.next().await.unwrap().unwrap();
+            watermarks.push(msg.into_watermark().unwrap());
+        }
+        // The first window of 00:22 is [00:00, 00:30).
+        assert_eq!(
+            watermarks,
+            vec![
+                Watermark::new(2, DataType::Timestamp, ts(0)),
+                Watermark::new(3, DataType::Timestamp, ts(30)),
+                Watermark::new(1, DataType::Timestamp, ts(22)),
+            ]
+        );
+    }
 }
@@ -56,12 +56,14 @@ async fn test_merger_sum_aggr() {
                     args: AggArgs::None,
                     return_type: DataType::Int64,
                     append_only,
+                    distinct: false,
                 },
                 AggCall {
                     kind: AggKind::Sum,
                     args: AggArgs::Unary(DataType::Int64, 0),
                     return_type: DataType::Int64,
                     append_only,
+                    distinct: false,
                 },
             ],
             vec![],
@@ -133,7 +135,15 @@ async fn test_merger_sum_aggr() {
     handles.push(tokio::spawn(actor.run()));
 
     // use a merge operator to collect data from dispatchers before sending them to aggregator
-    let merger = MergeExecutor::new(schema, vec![], 0, outputs, ActorContext::create(), 0);
+    let merger = MergeExecutor::new(
+        schema,
+        vec![],
+        0,
+        (0..).zip(outputs).collect(),
+        SharedContext::for_test().into(),
+        ActorContext::create(),
+        0,
+    );
 
     // for global aggregator, we need to sum data and sum row count
     let append_only = false;
@@ -145,15 +155,18 @@ async fn test_merger_sum_aggr() {
                 args: AggArgs::Unary(DataType::Int64, 0),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
                 append_only,
+                distinct: false,
             },
         ],
         create_in_memory_keyspace_agg(2),
+        vec![None, None],
         vec![],
         2,
         vec![],
@@ -168,6 +181,7 @@ async fn test_merger_sum_aggr() {
             Box::new(InputRefExpression::new(DataType::Int64, 1)),
         ],
         3,
+        vec![],
     );
 
     let items = Arc::new(Mutex::new(vec![]));
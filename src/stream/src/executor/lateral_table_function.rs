@@ -0,0 +1,232 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Debug, Formatter};
+
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_expr::table_function::{eval_lateral, lateral_return_types, BoxedTableFunction};
+
+use super::{
+    Executor, ExecutorInfo, PkIndices, PkIndicesRef, SimpleExecutor, SimpleExecutorWrapper,
+    StreamExecutorResult,
+};
+use crate::executor::error::StreamExecutorError;
+
+pub type LateralTableFunctionExecutor = SimpleExecutorWrapper<SimpleLateralTableFunctionExecutor>;
+
+impl LateralTableFunctionExecutor {
+    pub fn new(
+        input: Box<dyn Executor>,
+        pk_indices: PkIndices,
+        table_function: BoxedTableFunction,
+        executor_id: u64,
+    ) -> Self {
+        let schema = Schema::new(
+            lateral_return_types(table_function.as_ref(), &input.schema().data_types())
+                .into_iter()
+                .map(Field::unnamed)
+                .collect(),
+        );
+        let info = ExecutorInfo {
+            schema,
+            pk_indices,
+            identity: format!("LateralTableFunctionExecutor {:X}", executor_id),
+        };
+        SimpleExecutorWrapper {
+            input,
+            inner: SimpleLateralTableFunctionExecutor {
+                info,
+                table_function,
+            },
+        }
+    }
+}
+
+/// `LateralTableFunctionExecutor` outputs each input row followed by each row returned by a table
+/// function on it, and the 1-based ordinality of the returned row, which makes the output rows
+/// unique together with the input pk. The input columns keep their indices, so watermarks are
+/// forwarded as is.
+pub struct SimpleLateralTableFunctionExecutor {
+    info: ExecutorInfo,
+
+    /// The table function, which must always return the same rows for the same input row, so that
+    /// a deleted row retracts exactly the rows output on its insertion.
+    table_function: BoxedTableFunction,
+}
+
+impl Debug for SimpleLateralTableFunctionExecutor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LateralTableFunctionExecutor")
+            .field("table_function", &self.table_function)
+            .finish()
+    }
+}
+
+impl SimpleExecutor for SimpleLateralTableFunctionExecutor {
+    fn map_filter_chunk(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        let mut rows = vec![];
+        for (op, row) in chunk.rows() {
+            // The old and the new value of an update may expand to different numbers of rows, so
+            // they are output as a plain delete and insert.
+            let op = match op {
+                Op::Insert | Op::UpdateInsert => Op::Insert,
+                Op::Delete | Op::UpdateDelete => Op::Delete,
+            };
+            let output = eval_lateral(self.table_function.as_ref(), &row.to_owned_row())
+                .map_err(StreamExecutorError::eval_error)?;
+            rows.extend(output.into_iter().map(|row| (op, row)));
+        }
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let chunk = StreamChunk::from_rows(&rows, &self.info.schema.data_types())
+            .map_err(StreamExecutorError::eval_error)?;
+        Ok(Some(chunk))
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::{ListValue, Row};
+    use risingwave_common::types::{DataType, ScalarImpl};
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
+    use risingwave_expr::table_function::RegexpMatches;
+
+    use super::super::test_utils::MockSource;
+    use super::super::*;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lateral_table_function() {
+        let chunk1 = StreamChunk::from_pretty(
+            " I T
+            + 1 a1b2
+            + 2 x
+            + 3 c3",
+        );
+        let chunk2 = StreamChunk::from_pretty(
+            "  I T
+            U- 1 a1b2
+            U+ 1 d4
+            -  3 c3",
+        );
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Varchar),
+        ]);
+        let source = MockSource::with_chunks(schema, vec![0], vec![chunk1, chunk2]);
+
+        // regexp_matches($1, '[a-z](\d)', 'g')
+        let literal = |s: &str| {
+            Box::new(LiteralExpression::new(
+                DataType::Varchar,
+                Some(ScalarImpl::Utf8(s.to_string())),
+            ))
+        };
+        let list_type = DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        };
+        let table_function = RegexpMatches::with_const_pattern(
+            vec![
+                Box::new(InputRefExpression::new(DataType::Varchar, 1)),
+                literal(r"[a-z](\d)"),
+                literal("g"),
+            ],
+            vec![list_type.clone()],
+        )
+        .unwrap();
+        let executor = Box::new(LateralTableFunctionExecutor::new(
+            Box::new(source),
+            vec![0, 3],
+            Box::new(table_function),
+            1,
+        ));
+        assert_eq!(
+            executor.schema().data_types(),
+            vec![
+                DataType::Int64,
+                DataType::Varchar,
+                list_type.clone(),
+                DataType::Int64
+            ]
+        );
+        let mut executor = executor.execute();
+
+        let row = |id: i64, text: &str, group: &str, ordinality: i64| {
+            Row::new(vec![
+                Some(id.into()),
+                Some(text.to_string().into()),
+                Some(ScalarImpl::List(ListValue::new(vec![Some(
+                    group.to_string().into(),
+                )]))),
+                Some(ordinality.into()),
+            ])
+        };
+        let data_types = vec![
+            DataType::Int64,
+            DataType::Varchar,
+            list_type,
+            DataType::Int64,
+        ];
+
+        let msg = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            *msg.as_chunk().unwrap(),
+            StreamChunk::from_rows(
+                &[
+                    (Op::Insert, row(1, "a1b2", "1", 1)),
+                    (Op::Insert, row(1, "a1b2", "2", 2)),
+                    (Op::Insert, row(3, "c3", "3", 1)),
+                ],
+                &data_types
+            )
+            .unwrap()
+        );
+
+        let msg = executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            *msg.as_chunk().unwrap(),
+            StreamChunk::from_rows(
+                &[
+                    (Op::Delete, row(1, "a1b2", "1", 1)),
+                    (Op::Delete, row(1, "a1b2", "2", 2)),
+                    (Op::Insert, row(1, "d4", "4", 1)),
+                    (Op::Delete, row(3, "c3", "3", 1)),
+                ],
+                &data_types
+            )
+            .unwrap()
+        );
+
+        assert!(executor.next().await.unwrap().unwrap().is_stop());
+    }
+}
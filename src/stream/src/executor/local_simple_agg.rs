@@ -138,6 +138,8 @@ impl LocalSimpleAggExecutor {
 
                     yield m;
                 }
+                // The output has no column to carry the watermark.
+                Message::Watermark(_) => {}
             }
         }
     }
@@ -194,6 +196,7 @@ mod tests {
             args: AggArgs::None,
             return_type: DataType::Int64,
             append_only: false,
+            distinct: false,
         }];
 
         let simple_agg = Box::new(LocalSimpleAggExecutor::new(
@@ -248,18 +251,21 @@ mod tests {
                 args: AggArgs::None,
                 return_type: DataType::Int64,
                 append_only: false,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 0),
                 return_type: DataType::Int64,
                 append_only: false,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
                 append_only: false,
+                distinct: false,
             },
         ];
 
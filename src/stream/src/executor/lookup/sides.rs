@@ -107,6 +107,7 @@ pub async fn poll_until_barrier(stream: impl MessageStream, expected_barrier: Ba
     for item in stream {
         match item? {
             c @ Message::Chunk(_) => yield c,
+            Message::Watermark(_) => {}
             Message::Barrier(b) => {
                 if b.epoch != expected_barrier.epoch {
                     return Err(StreamExecutorError::align_barrier(expected_barrier, b));
@@ -124,7 +125,8 @@ fn prefer_right(_: &mut ()) -> PollNext {
 }
 
 /// A biased barrier aligner which prefers message from the right side. Barrier message will be
-/// available for both left and right side, instead of being combined.
+/// available for both left and right side, instead of being combined. Watermarks are dropped, as
+/// lookup join does not clean its state by watermarks.
 #[try_stream(ok = BarrierAlignedMessage, error = StreamExecutorError)]
 pub async fn align_barrier(left: impl MessageStream, right: impl MessageStream) {
     let mut left = Box::pin(left);
@@ -158,6 +160,8 @@ pub async fn align_barrier(left: impl MessageStream, right: impl MessageStream)
                     yield Either::Right(Message::Barrier(b.clone()));
                     break 'inner (SideStatus::RightBarrier, b);
                 }
+                Some(Either::Left(Ok(Message::Watermark(_))))
+                | Some(Either::Right(Ok(Message::Watermark(_)))) => {}
                 Some(Either::Left(Err(e))) | Some(Either::Right(Err(e))) => return Err(e),
                 None => {
                     break 'outer;
@@ -222,6 +226,9 @@ pub async fn stream_lookup_arrange_prev_epoch(
                     yield ArrangeMessage::Barrier(barrier);
                     stream_side_end = true;
                 }
+                Either::Left(Message::Watermark(_)) | Either::Right(Message::Watermark(_)) => {
+                    unreachable!()
+                }
                 Either::Right(Message::Barrier(barrier)) => {
                     if stream_side_end {
                         yield ArrangeMessage::ArrangeReady(
@@ -248,7 +255,7 @@ pub async fn stream_lookup_arrange_prev_epoch(
                     yield ArrangeMessage::Barrier(b);
                     break;
                 }
-                Either::Right(_) => unreachable!(),
+                Either::Left(Message::Watermark(_)) | Either::Right(_) => unreachable!(),
             }
         }
 
@@ -302,6 +309,9 @@ pub async fn stream_lookup_arrange_this_epoch(
                 Either::Left(Message::Barrier(barrier)) => {
                     break 'inner Status::StreamReady(barrier);
                 }
+                Either::Left(Message::Watermark(_)) | Either::Right(Message::Watermark(_)) => {
+                    unreachable!()
+                }
                 Either::Right(Message::Barrier(barrier)) => {
                     yield ArrangeMessage::ArrangeReady(std::mem::take(&mut arrange_buf), barrier);
                     for msg in std::mem::take(&mut stream_buf) {
@@ -325,7 +335,7 @@ pub async fn stream_lookup_arrange_this_epoch(
                         yield ArrangeMessage::Barrier(b);
                         break;
                     }
-                    Either::Right(_) => unreachable!(),
+                    Either::Left(Message::Watermark(_)) | Either::Right(_) => unreachable!(),
                 }
             },
             // Stream is done in this epoch, but arrangement is not ready -- we wait for the
@@ -336,7 +346,7 @@ pub async fn stream_lookup_arrange_this_epoch(
                     .await
                     .expect("unexpected close of barrier aligner")?
                 {
-                    Either::Left(_) => unreachable!(),
+                    Either::Left(_) | Either::Right(Message::Watermark(_)) => unreachable!(),
                     Either::Right(Message::Chunk(chunk)) => {
                         arrange_buf.push(chunk);
                     }
@@ -40,6 +40,7 @@ fn arrangement_col_descs() -> Vec<ColumnDesc> {
             name: "rowid_column".to_string(),
             field_descs: vec![],
             type_name: "".to_string(),
+            default_value: None,
         },
         ColumnDesc {
             data_type: DataType::Int64,
@@ -47,6 +48,7 @@ fn arrangement_col_descs() -> Vec<ColumnDesc> {
             name: "join_column".to_string(),
             field_descs: vec![],
             type_name: "".to_string(),
+            default_value: None,
         },
     ]
 }
@@ -127,6 +129,7 @@ async fn create_arrangement(
 
     Box::new(MaterializeExecutor::new(
         Box::new(source),
+        table_id,
         keyspace,
         arrangement_col_arrange_rules(),
         column_ids,
@@ -154,6 +157,7 @@ async fn create_source() -> Box<dyn Executor + Send> {
             name: "join_column".to_string(),
             field_descs: vec![],
             type_name: "".to_string(),
+            default_value: None,
         },
         ColumnDesc {
             data_type: DataType::Int64,
@@ -161,6 +165,7 @@ async fn create_source() -> Box<dyn Executor + Send> {
             name: "rowid_column".to_string(),
             field_descs: vec![],
             type_name: "".to_string(),
+            default_value: None,
         },
     ];
 
@@ -241,7 +246,7 @@ async fn test_lookup_this_epoch() {
     next_msg(&mut msgs, &mut lookup_executor).await;
 
     for (k, v) in store.scan::<_, Vec<u8>>(.., None, u64::MAX).await.unwrap() {
-        // Do not deserialize datum for SENTINEL_CELL_ID cuz the value length is 0.
+        // Do not deserialize datum for SENTINEL_CELL_ID cuz it is not a column.
         if deserialize_column_id(&k[k.len() - 4..]).unwrap() != SENTINEL_CELL_ID {
             println!(
                 "{:?} => {:?}",
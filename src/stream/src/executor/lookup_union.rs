@@ -113,6 +113,8 @@ impl LookupUnionExecutor {
                     end = false;
                     match msg {
                         msg @ Message::Chunk(_) => yield msg,
+                        // The inputs are not aligned on watermarks.
+                        Message::Watermark(_) => {}
                         Message::Barrier(barrier) => {
                             if let Some(this_barrier) = &this_barrier {
                                 if this_barrier != &barrier {
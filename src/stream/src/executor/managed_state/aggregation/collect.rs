@@ -0,0 +1,353 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::{BitAnd, BitOr};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use itertools::Itertools;
+use madsim::collections::BTreeMap;
+use risingwave_common::array::stream_chunk::{Op, Ops};
+use risingwave_common::array::{ArrayImpl, ListValue};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::Result;
+use risingwave_common::hash::{HashCode, VirtualNode};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::ordered::OrderedArraysSerializer;
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_common::util::value_encoding::{deserialize_cell, serialize_cell};
+use risingwave_expr::expr::AggKind;
+use risingwave_expr::vector_op::agg::HyperLogLog;
+use risingwave_storage::storage_value::{StorageValue, ValueMeta};
+use risingwave_storage::write_batch::WriteBatch;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::super::flush_status::BtreeMapFlushStatus as FlushStatus;
+use super::ManagedTableState;
+use crate::executor::aggregation::AggCall;
+use crate::executor::PkDataTypes;
+
+/// A table state keeping every input value of the group, for aggregations that can neither be
+/// retracted from a single value nor decomposed into other aggregations, i.e. `bit_and`, `bit_or`,
+/// `array_agg` and `approx_count_distinct`.
+///
+/// Values are stored as `input_pk -> value`, or as `value -> value` for distinct agg calls, whose
+/// input has been deduplicated. All values of the group are loaded into memory on first access,
+/// and the output is recomputed from them whenever it is requested after a change.
+///
+/// Both the state and each recomputation are linear in the number of rows of the group, even for
+/// `approx_count_distinct`, whose `HyperLogLog` sketch cannot retract a value and is rebuilt from
+/// all values, and for `bit_and` and `bit_or`. Keeping only the sketch or the result would need
+/// the input to be append-only, which the state does not take advantage of yet.
+pub struct ManagedCollectState<S: StateStore> {
+    kind: AggKind,
+
+    /// Data type of the input values.
+    data_type: DataType,
+
+    /// All values of the group. Only valid when `cache_synced` is set.
+    cache: BTreeMap<Bytes, Datum>,
+
+    /// Whether `cache` contains all values in the state store.
+    cache_synced: bool,
+
+    /// Changes not yet flushed to the state store.
+    flush_buffer: BTreeMap<Bytes, FlushStatus<Datum>>,
+
+    /// The output computed from `cache`, cleared on every change.
+    result: Option<Datum>,
+
+    /// The keyspace to operate on.
+    keyspace: Keyspace<S>,
+
+    /// Serializes the key columns of the input, i.e. the value for distinct agg calls and the
+    /// input primary key otherwise.
+    key_serializer: OrderedArraysSerializer,
+
+    /// Number of primary key columns following the value in the input.
+    pk_length: usize,
+
+    /// Computed via consistent hash on the group key, to be set in value meta.
+    vnode: VirtualNode,
+}
+
+impl<S: StateStore> ManagedCollectState<S> {
+    pub fn new(
+        agg_call: &AggCall,
+        keyspace: Keyspace<S>,
+        row_count: usize,
+        pk_data_types: PkDataTypes,
+        group_key_hash_code: HashCode,
+    ) -> Self {
+        let pk_length = pk_data_types.len();
+        let key_indices = if agg_call.distinct {
+            vec![0]
+        } else {
+            (1..=pk_length).collect_vec()
+        };
+        let order_pairs = key_indices
+            .into_iter()
+            .map(|idx| OrderPair::new(idx, OrderType::Ascending))
+            .collect();
+        Self {
+            kind: agg_call.kind.clone(),
+            data_type: agg_call.args.arg_types()[0].clone(),
+            cache: BTreeMap::new(),
+            // A new group has nothing in the state store.
+            cache_synced: row_count == 0,
+            flush_buffer: BTreeMap::new(),
+            result: None,
+            keyspace,
+            key_serializer: OrderedArraysSerializer::new(order_pairs),
+            pk_length,
+            vnode: group_key_hash_code.to_vnode(),
+        }
+    }
+
+    async fn sync_cache(&mut self, epoch: u64) -> Result<()> {
+        if self.cache_synced {
+            return Ok(());
+        }
+        // Nothing has been applied before the first sync, so the store is up to date.
+        assert!(self.flush_buffer.is_empty());
+        let all_data = self.keyspace.scan(None, epoch).await?;
+        for (raw_key, mut raw_value) in all_data {
+            let value = deserialize_cell(&mut raw_value, &self.data_type)?;
+            self.cache.insert(raw_key, value);
+        }
+        self.cache_synced = true;
+        Ok(())
+    }
+
+    fn compute_output(&self) -> Datum {
+        let values = self.cache.values();
+        match self.kind {
+            AggKind::ArrayAgg => {
+                if self.cache.is_empty() {
+                    None
+                } else {
+                    Some(ListValue::new(values.cloned().collect()).into())
+                }
+            }
+            AggKind::ApproxCountDistinct => {
+                let mut sketch = HyperLogLog::new();
+                values.flatten().for_each(|value| sketch.add(value));
+                Some(sketch.count().into())
+            }
+            AggKind::BitAnd | AggKind::BitOr => {
+                let is_and = self.kind == AggKind::BitAnd;
+                values
+                    .flatten()
+                    .cloned()
+                    .reduce(|l, r| bit_and_or(is_and, l, r))
+            }
+            _ => unreachable!("unsupported collect state: {}", self.kind),
+        }
+    }
+}
+
+fn bit_and_or(is_and: bool, l: ScalarImpl, r: ScalarImpl) -> ScalarImpl {
+    fn apply<T: BitAnd<Output = T> + BitOr<Output = T>>(is_and: bool, l: T, r: T) -> T {
+        if is_and {
+            l & r
+        } else {
+            l | r
+        }
+    }
+    match (l, r) {
+        (ScalarImpl::Int16(l), ScalarImpl::Int16(r)) => ScalarImpl::Int16(apply(is_and, l, r)),
+        (ScalarImpl::Int32(l), ScalarImpl::Int32(r)) => ScalarImpl::Int32(apply(is_and, l, r)),
+        (ScalarImpl::Int64(l), ScalarImpl::Int64(r)) => ScalarImpl::Int64(apply(is_and, l, r)),
+        (l, r) => unreachable!("bit_and/bit_or of {:?} and {:?}", l, r),
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> ManagedTableState<S> for ManagedCollectState<S> {
+    async fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+        epoch: u64,
+    ) -> Result<()> {
+        debug_assert!(super::verify_batch(ops, visibility, data));
+        // The value is followed by the primary key columns.
+        assert_eq!(
+            data.len(),
+            1 + self.pk_length,
+            "mismatched data input with pk_length"
+        );
+        self.sync_cache(epoch).await?;
+
+        let mut row_keys = vec![];
+        self.key_serializer.serialize(data, &mut row_keys);
+
+        for (row_idx, (op, key)) in ops.iter().zip_eq(row_keys.into_iter()).enumerate() {
+            let visible = visibility
+                .map(|x| x.is_set(row_idx).unwrap())
+                .unwrap_or(true);
+            if !visible {
+                continue;
+            }
+
+            let key = Bytes::from(key);
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    let value = data[0].datum_at(row_idx);
+                    self.cache.insert(key.clone(), value.clone());
+                    FlushStatus::do_insert(self.flush_buffer.entry(key), value);
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    self.cache.remove(&key);
+                    FlushStatus::do_delete(self.flush_buffer.entry(key));
+                }
+            }
+            self.result = None;
+        }
+        Ok(())
+    }
+
+    async fn get_output(&mut self, epoch: u64) -> Result<Datum> {
+        if self.result.is_none() {
+            self.sync_cache(epoch).await?;
+            self.result = Some(self.compute_output());
+        }
+        Ok(self.result.clone().unwrap())
+    }
+
+    fn is_dirty(&self) -> bool {
+        !self.flush_buffer.is_empty()
+    }
+
+    fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
+        if !self.is_dirty() {
+            return Ok(());
+        }
+
+        let mut local = write_batch.prefixify(&self.keyspace);
+        let value_meta = ValueMeta::with_vnode(self.vnode);
+
+        for (key, value) in std::mem::take(&mut self.flush_buffer) {
+            match value.into_option() {
+                Some(value) => {
+                    local.put(
+                        key,
+                        StorageValue::new_put(value_meta, serialize_cell(&value)?),
+                    );
+                }
+                None => {
+                    local.delete_with_value_meta(key, value_meta);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{I32Array, I64Array, Op};
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::executor::aggregation::AggArgs;
+    use crate::executor::test_utils::create_in_memory_keyspace;
+
+    fn create_agg_call(kind: AggKind, return_type: DataType) -> AggCall {
+        AggCall {
+            kind,
+            args: AggArgs::Unary(DataType::Int32, 0),
+            return_type,
+            append_only: false,
+            distinct: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_managed_collect_state() {
+        let keyspace = create_in_memory_keyspace();
+        let store = keyspace.state_store();
+        let list_type = DataType::List {
+            datatype: Box::new(DataType::Int32),
+        };
+        let agg_call = create_agg_call(AggKind::ArrayAgg, list_type);
+        let mut state = ManagedCollectState::new(
+            &agg_call,
+            keyspace.clone(),
+            0,
+            vec![DataType::Int64],
+            HashCode(0),
+        );
+        let epoch = 0;
+
+        state
+            .apply_batch(
+                &[Op::Insert, Op::Insert, Op::Insert],
+                None,
+                &[
+                    &I32Array::from_slice(&[Some(3), None, Some(1)])
+                        .unwrap()
+                        .into(),
+                    &I64Array::from_slice(&[Some(0), Some(1), Some(2)])
+                        .unwrap()
+                        .into(),
+                ],
+                epoch,
+            )
+            .await
+            .unwrap();
+        assert!(state.is_dirty());
+        let int = |v| Some(ScalarImpl::Int32(v));
+        assert_eq!(
+            state.get_output(epoch).await.unwrap(),
+            Some(ListValue::new(vec![int(3), None, int(1)]).into())
+        );
+
+        let mut write_batch = store.start_write_batch();
+        state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+        assert!(!state.is_dirty());
+
+        // Restore the values from the state store and retract one of them.
+        let agg_call = create_agg_call(AggKind::BitOr, DataType::Int32);
+        let mut state = ManagedCollectState::new(
+            &agg_call,
+            keyspace.clone(),
+            3,
+            vec![DataType::Int64],
+            HashCode(0),
+        );
+        assert_eq!(
+            state.get_output(epoch).await.unwrap(),
+            Some(ScalarImpl::Int32(3))
+        );
+        state
+            .apply_batch(
+                &[Op::Delete],
+                None,
+                &[
+                    &I32Array::from_slice(&[Some(3)]).unwrap().into(),
+                    &I64Array::from_slice(&[Some(0)]).unwrap().into(),
+                ],
+                epoch,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            state.get_output(epoch).await.unwrap(),
+            Some(ScalarImpl::Int32(1))
+        );
+    }
+}
@@ -474,7 +474,7 @@ pub async fn create_streaming_extreme_state<S: StateStore>(
         { Int16, I16Array },
         { Float64, F64Array },
         { Float32, F32Array },
-        { Decimal, DecimalArray },
+        { Decimal(_), DecimalArray },
         { Varchar, Utf8Array },
         { Interval, IntervalArray },
     )
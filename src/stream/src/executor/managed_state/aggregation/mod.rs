@@ -14,6 +14,7 @@
 
 //! Aggregators with state store support
 
+use collect::ManagedCollectState;
 pub use extreme::*;
 use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::{ArrayImpl, Row};
@@ -30,6 +31,7 @@ pub use value::*;
 use crate::executor::aggregation::AggCall;
 use crate::executor::PkDataTypes;
 
+mod collect;
 mod extreme;
 mod extreme_serializer;
 mod string_agg;
@@ -50,6 +52,19 @@ pub fn verify_batch(
     all_lengths.iter().min() == all_lengths.iter().max()
 }
 
+/// Whether the state of `agg_call` is a [`ManagedValueState`] persisted in the state table, whose
+/// primary key is encoded in descending order. Otherwise, the state is a table state prefixed by
+/// the ascending-encoded group key.
+pub fn is_value_state(agg_call: &AggCall) -> bool {
+    match agg_call.kind {
+        AggKind::Max | AggKind::Min => agg_call.append_only,
+        AggKind::BitAnd | AggKind::BitOr | AggKind::ArrayAgg | AggKind::ApproxCountDistinct => {
+            false
+        }
+        _ => true,
+    }
+}
+
 /// All managed state for aggregation. The managed state will manage the cache and integrate
 /// the state with the underlying state store. Managed states can only be evicted from outer cache
 /// when they are not dirty.
@@ -142,6 +157,29 @@ impl<S: StateStore> ManagedStateImpl<S> {
                     ))
                 }
             }
+            AggKind::BitAnd | AggKind::BitOr | AggKind::ArrayAgg | AggKind::ApproxCountDistinct => {
+                assert!(
+                    row_count.is_some(),
+                    "should set row_count for value states other than AggKind::RowCount"
+                );
+                Ok(Self::Table(Box::new(ManagedCollectState::new(
+                    &agg_call,
+                    keyspace,
+                    row_count.unwrap(),
+                    pk_data_types,
+                    key_hash_code.unwrap_or_default(),
+                ))))
+            }
+            AggKind::StddevPop
+            | AggKind::StddevSamp
+            | AggKind::VarPop
+            | AggKind::VarSamp
+            | AggKind::BoolAnd
+            | AggKind::BoolOr => Err(ErrorCode::InternalError(format!(
+                "{} should have been rewritten by the optimizer",
+                agg_call.kind
+            ))
+            .into()),
             AggKind::StringAgg => {
                 // TODO, It seems with `order by`, `StringAgg` needs more stuff from `AggCall`
                 Err(ErrorCode::NotImplemented(
@@ -139,6 +139,7 @@ mod tests {
             args: AggArgs::Unary(DataType::Int64, 0),
             return_type: DataType::Int64,
             append_only: false,
+            distinct: false,
         }
     }
 
@@ -202,6 +203,7 @@ mod tests {
             args: AggArgs::Unary(DataType::Int64, 0),
             return_type: DataType::Int64,
             append_only: true,
+            distinct: false,
         }
     }
 
@@ -26,6 +26,9 @@ use risingwave_common::hash::{HashKey, PrecomputedBuildHasher};
 use risingwave_common::types::{DataType, Datum};
 use risingwave_storage::{Keyspace, StateStore};
 
+use crate::executor::error::{StreamExecutorError, StreamExecutorResult};
+use crate::executor::{delete_below_watermark, Watermark};
+
 /// This is a row with a match degree
 #[derive(Clone, Debug)]
 pub struct JoinRow {
@@ -293,6 +296,45 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
             Ok(self.inner.get_mut(key).unwrap())
         }
     }
+
+    /// Remove the states whose first join key is below the `watermark` from both the cache and the
+    /// state store. Should only be called after the states are flushed.
+    pub async fn clean_below_watermark(
+        &mut self,
+        watermark: &Watermark,
+    ) -> StreamExecutorResult<()> {
+        let mut expired_keys = vec![];
+        for (key, _) in self.inner.iter() {
+            let key_row = key
+                .clone()
+                .deserialize(self.join_key_data_types.iter())
+                .map_err(StreamExecutorError::eval_error)?;
+            if let Some(val) = &key_row[0] && val < &watermark.val {
+                expired_keys.push(key.clone());
+            }
+        }
+        for key in expired_keys {
+            self.inner.pop(&key);
+        }
+
+        // The join key is encoded in ascending order, see `get_state_keyspace`.
+        let mut write_batch = self.keyspace.state_store().start_write_batch();
+        delete_below_watermark(
+            &self.keyspace,
+            &mut write_batch,
+            watermark,
+            false,
+            self.current_epoch,
+        )
+        .await?;
+        if !write_batch.is_empty() {
+            write_batch
+                .ingest(self.current_epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+        }
+        Ok(())
+    }
 }
 
 impl<K: HashKey, S: StateStore> Deref for JoinHashMap<K, S> {
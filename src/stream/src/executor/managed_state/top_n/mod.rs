@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod top_n_bottom_n_state;
+mod top_n_range_state;
 mod top_n_state;
 
 use bytes::Bytes;
@@ -22,6 +23,7 @@ use risingwave_common::util::ordered::{OrderedRow, OrderedRowDeserializer};
 use risingwave_storage::cell_based_row_deserializer::CellBasedRowDeserializer;
 use risingwave_storage::StateStoreIter;
 pub use top_n_bottom_n_state::ManagedTopNBottomNState;
+pub use top_n_range_state::ManagedTopNRangeState;
 pub use top_n_state::ManagedTopNState;
 
 pub mod variants {
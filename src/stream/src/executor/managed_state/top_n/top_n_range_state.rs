@@ -0,0 +1,281 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{Op, Row};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_common::util::ordered::{OrderedRow, OrderedRowDeserializer};
+use risingwave_storage::cell_based_row_deserializer::CellBasedRowDeserializer;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::variants::{TOP_N_MAX, TOP_N_MIN};
+use super::{ManagedTopNBottomNState, ManagedTopNState};
+
+/// All the rows seen by a top-n operator, which are split by their order into three ranges:
+/// `[0, offset)`, `[offset, offset+limit)` and `[offset+limit, +inf)`. We are only interested in
+/// the middle range, but still need to record the rows in the other two ranges, as they may be
+/// moved into the middle one when rows are inserted or deleted.
+pub struct ManagedTopNRangeState<S: StateStore> {
+    /// `LIMIT XXX`. `None` means no limit.
+    limit: Option<usize>,
+    /// `OFFSET XXX`. `0` means no offset.
+    offset: usize,
+
+    managed_lowest_state: ManagedTopNState<S, TOP_N_MAX>,
+    managed_middle_state: ManagedTopNBottomNState<S>,
+    managed_highest_state: ManagedTopNState<S, TOP_N_MIN>,
+}
+
+impl<S: StateStore> ManagedTopNRangeState<S> {
+    pub fn new(
+        offset_and_limit: (usize, Option<usize>),
+        cache_size: Option<usize>,
+        total_count: (usize, usize, usize),
+        keyspace: Keyspace<S>,
+        row_data_types: Vec<DataType>,
+        ordered_row_deserializer: OrderedRowDeserializer,
+        cell_based_row_deserializer: CellBasedRowDeserializer,
+    ) -> Self {
+        let lower_sub_keyspace = keyspace.append_u8(b'l');
+        let middle_sub_keyspace = keyspace.append_u8(b'm');
+        let higher_sub_keyspace = keyspace.append_u8(b'h');
+        let managed_lowest_state = ManagedTopNState::<S, TOP_N_MAX>::new(
+            cache_size,
+            total_count.0,
+            lower_sub_keyspace,
+            row_data_types.clone(),
+            ordered_row_deserializer.clone(),
+            cell_based_row_deserializer.clone(),
+        );
+        let managed_middle_state = ManagedTopNBottomNState::new(
+            cache_size,
+            total_count.1,
+            middle_sub_keyspace,
+            row_data_types.clone(),
+            ordered_row_deserializer.clone(),
+            cell_based_row_deserializer.clone(),
+        );
+        let managed_highest_state = ManagedTopNState::<S, TOP_N_MIN>::new(
+            cache_size,
+            total_count.2,
+            higher_sub_keyspace,
+            row_data_types,
+            ordered_row_deserializer,
+            cell_based_row_deserializer,
+        );
+        Self {
+            offset: offset_and_limit.0,
+            limit: offset_and_limit.1,
+            managed_lowest_state,
+            managed_middle_state,
+            managed_highest_state,
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.managed_lowest_state.is_dirty()
+            || self.managed_middle_state.is_dirty()
+            || self.managed_highest_state.is_dirty()
+    }
+
+    /// Fills in the caches of all the ranges from storage. This should be called before applying
+    /// any change, when the state is not dirty.
+    pub async fn fill_in_cache(&mut self, epoch: u64) -> Result<()> {
+        self.managed_lowest_state.fill_in_cache(epoch).await?;
+        self.managed_middle_state.fill_in_cache(epoch).await?;
+        self.managed_highest_state.fill_in_cache(epoch).await
+    }
+
+    pub async fn flush(&mut self, epoch: u64) -> Result<()> {
+        self.managed_highest_state.flush(epoch).await?;
+        self.managed_middle_state.flush(epoch).await?;
+        self.managed_lowest_state.flush(epoch).await
+    }
+
+    /// Applies a change of a row, whose sort key is `ordered_pk_row`, and appends the changes of
+    /// the middle range to `new_ops` and `new_rows`.
+    pub async fn apply(
+        &mut self,
+        op: Op,
+        ordered_pk_row: OrderedRow,
+        row: Row,
+        epoch: u64,
+        new_ops: &mut Vec<Op>,
+        new_rows: &mut Vec<Row>,
+    ) -> Result<()> {
+        let num_limit = self.limit.unwrap_or(usize::MAX);
+        match op {
+            Op::Insert | Op::UpdateInsert => {
+                if self.managed_lowest_state.total_count() < self.offset {
+                    // `elem` is in the range of `[0, offset)`,
+                    // we ignored it for now as it is not in the result set.
+                    self.managed_lowest_state
+                        .insert(ordered_pk_row, row, epoch)
+                        .await?;
+                    return Ok(());
+                }
+
+                // We remark that when offset is 0, every input row has nothing to do with
+                // `managed_lower_state`.
+                let element_to_compare_with_middle = if self.offset > 0
+                    && &ordered_pk_row < self.managed_lowest_state.top_element().unwrap().0
+                {
+                    // If the new element is smaller than the largest element in [0, offset),
+                    // the largest element need to move to [offset, offset+limit).
+                    let res = self
+                        .managed_lowest_state
+                        .pop_top_element(epoch)
+                        .await?
+                        .unwrap();
+                    self.managed_lowest_state
+                        .insert(ordered_pk_row, row, epoch)
+                        .await?;
+                    res
+                } else {
+                    (ordered_pk_row, row)
+                };
+
+                if self.managed_middle_state.total_count() < num_limit {
+                    // `elem` is in the range of `[offset, offset+limit)`,
+                    self.managed_middle_state
+                        .insert(
+                            element_to_compare_with_middle.0,
+                            element_to_compare_with_middle.1.clone(),
+                        )
+                        .await;
+                    new_ops.push(Op::Insert);
+                    new_rows.push(element_to_compare_with_middle.1);
+                    return Ok(());
+                }
+
+                let element_to_compare_with_highest = if &element_to_compare_with_middle.0
+                    < self.managed_middle_state.top_element().unwrap().0
+                {
+                    let res = self
+                        .managed_middle_state
+                        .pop_top_element(epoch)
+                        .await?
+                        .unwrap();
+                    new_ops.push(Op::Delete);
+                    new_rows.push(res.1.clone());
+                    new_ops.push(Op::Insert);
+                    new_rows.push(element_to_compare_with_middle.1.clone());
+                    self.managed_middle_state
+                        .insert(
+                            element_to_compare_with_middle.0,
+                            element_to_compare_with_middle.1,
+                        )
+                        .await;
+                    res
+                } else {
+                    element_to_compare_with_middle
+                };
+
+                // `elem` is in the range of `[offset+limit, +inf)`.
+                self.managed_highest_state
+                    .insert(
+                        element_to_compare_with_highest.0,
+                        element_to_compare_with_highest.1,
+                        epoch,
+                    )
+                    .await?;
+            }
+            Op::Delete | Op::UpdateDelete => {
+                // The extra care we need to take for deletion is that when we delete an element
+                // from a managed state, we may need to move an element from
+                // a higher range to the current range. And this process may
+                // be recursive. Since this is a delete operator, the key
+                // must already exist in one of the three managed states. We
+                // first check whether the element is in the highest state.
+                if self.managed_middle_state.total_count() == num_limit
+                    && ordered_pk_row > *self.managed_middle_state.top_element().unwrap().0
+                {
+                    // The current element in in the range of `[offset+limit, +inf)`
+                    self.managed_highest_state
+                        .delete(&ordered_pk_row, epoch)
+                        .await?;
+                } else if self.managed_lowest_state.total_count() == self.offset
+                    && (self.offset == 0
+                        || ordered_pk_row > *self.managed_lowest_state.top_element().unwrap().0)
+                {
+                    // The current element in in the range of `[offset, offset+limit)`
+                    self.managed_middle_state
+                        .delete(&ordered_pk_row, epoch)
+                        .await?;
+                    new_ops.push(Op::Delete);
+                    new_rows.push(row.clone());
+                    // We need to bring one, if any, from highest to lowest.
+                    if self.managed_highest_state.total_count() > 0 {
+                        let smallest_element_from_highest_state = self
+                            .managed_highest_state
+                            .pop_top_element(epoch)
+                            .await?
+                            .unwrap();
+                        new_ops.push(Op::Insert);
+                        new_rows.push(smallest_element_from_highest_state.1.clone());
+                        self.managed_middle_state
+                            .insert(
+                                smallest_element_from_highest_state.0,
+                                smallest_element_from_highest_state.1,
+                            )
+                            .await;
+                    }
+                } else {
+                    // The current element in in the range of `[0, offset)`
+                    self.managed_lowest_state
+                        .delete(&ordered_pk_row, epoch)
+                        .await?;
+                    // We need to bring one, if any, from middle to lowest.
+                    if self.managed_middle_state.total_count() > 0 {
+                        let smallest_element_from_middle_state = self
+                            .managed_middle_state
+                            .pop_bottom_element(epoch)
+                            .await?
+                            .unwrap();
+                        new_ops.push(Op::Delete);
+                        new_rows.push(smallest_element_from_middle_state.1.clone());
+                        self.managed_lowest_state
+                            .insert(
+                                smallest_element_from_middle_state.0,
+                                smallest_element_from_middle_state.1,
+                                epoch,
+                            )
+                            .await?;
+                    }
+                    // We check whether we need to/can bring one from highest to middle.
+                    // We remark that if `self.limit` is Some, it cannot be 0 as this should be
+                    // optimized away in the frontend.
+                    if self.managed_middle_state.total_count() == (num_limit - 1)
+                        && self.managed_highest_state.total_count() > 0
+                    {
+                        let smallest_element_from_highest_state = self
+                            .managed_highest_state
+                            .pop_top_element(epoch)
+                            .await?
+                            .unwrap();
+                        new_ops.push(Op::Insert);
+                        new_rows.push(smallest_element_from_highest_state.1.clone());
+                        self.managed_middle_state
+                            .insert(
+                                smallest_element_from_highest_state.0,
+                                smallest_element_from_highest_state.1,
+                            )
+                            .await;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
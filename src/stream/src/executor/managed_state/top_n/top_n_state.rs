@@ -301,6 +301,8 @@ impl<S: StateStore, const TOP_N_TYPE: usize> ManagedTopNState<S, TOP_N_TYPE> {
     /// We don't need to care about whether `self.top_n` is empty or not as the key is unique.
     /// An element with duplicated key scanned from the storage would just override the element with
     /// the same key in the cache, and their value must be the same.
+    ///
+    /// All the elements in the storage are scanned, so that `total_count` is recovered as well.
     pub async fn fill_in_cache(&mut self, epoch: u64) -> Result<()> {
         debug_assert!(!self.is_dirty());
         let iter = self.keyspace.iter(epoch).await?;
@@ -309,15 +311,18 @@ impl<S: StateStore, const TOP_N_TYPE: usize> ManagedTopNState<S, TOP_N_TYPE> {
             &mut self.ordered_row_deserializer,
             &mut self.cell_based_row_deserializer,
         );
+        let mut total_count = 0;
         while let Some((pk, row)) = pk_and_row_iter.next().await? {
+            total_count += 1;
+            if let Some(top_n_count) = self.top_n_count && top_n_count <= self.top_n.len() {
+                continue;
+            }
             let prev_row = self.top_n.insert(pk, row.clone());
             if let Some(prev_row) = prev_row {
                 debug_assert_eq!(prev_row, row);
             }
-            if let Some(top_n_count) = self.top_n_count && top_n_count == self.top_n.len() {
-                break;
-            }
         }
+        self.total_count = total_count;
         Ok(())
     }
 
@@ -13,21 +13,24 @@
 // limitations under the License.
 
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use async_trait::async_trait;
 use futures::channel::mpsc::{Receiver, Sender};
 use futures::{SinkExt, Stream, StreamExt};
-use futures_async_stream::for_await;
+use futures_async_stream::{for_await, try_stream};
+use madsim::collections::HashSet;
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::Result;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::util::addr::{is_local_address, HostAddr};
 use risingwave_pb::task_service::GetStreamResponse;
 use risingwave_rpc_client::ComputeClient;
 use tonic::Streaming;
 
 use super::error::StreamExecutorError;
 use super::*;
-use crate::task::UpDownActorIds;
+use crate::task::{ActorId, SharedContext, UpDownActorIds};
 
 /// Receive data from `gRPC` and forwards to `MergerExecutor`/`ReceiverExecutor`
 pub struct RemoteInput {
@@ -76,11 +79,46 @@ impl RemoteInput {
     }
 }
 
+/// Create the input channel from the upstream actor `up_id` to `actor_id`. If the upstream actor
+/// is on another node, a `RemoteInput` is spawned to forward the messages to the channel.
+pub fn new_input(
+    context: &SharedContext,
+    upstream_addr: HostAddr,
+    up_id: ActorId,
+    actor_id: ActorId,
+) -> Result<Receiver<Message>> {
+    if !is_local_address(&upstream_addr, &context.addr) {
+        // Get the sender for `RemoteInput` to forward received messages to receivers in
+        // `ReceiverExecutor` or `MergerExecutor`.
+        let sender = context.take_sender(&(up_id, actor_id))?;
+        let pool = context.compute_client_pool.clone();
+
+        tokio::spawn(async move {
+            let init_client = async move {
+                let remote_input = RemoteInput::create(
+                    pool.get_client_for_addr(upstream_addr).await?,
+                    (up_id, actor_id),
+                    sender,
+                )
+                .await?;
+                Ok::<_, RwError>(remote_input)
+            };
+            match init_client.await {
+                Ok(remote_input) => remote_input.run().await,
+                Err(e) => {
+                    error!("Spawn remote input fails:{}", e);
+                }
+            }
+        });
+    }
+    context.take_receiver(&(up_id, actor_id))
+}
+
 /// `MergeExecutor` merges data from multiple channels. Dataflow from one channel
 /// will be stopped on barrier.
 pub struct MergeExecutor {
-    /// Upstream channels.
-    upstreams: Vec<Receiver<Message>>,
+    /// Upstream channels, with the ids of the upstream actors.
+    upstreams: Vec<(ActorId, Receiver<Message>)>,
 
     /// Belonged actor id.
     actor_id: u32,
@@ -89,6 +127,10 @@ pub struct MergeExecutor {
 
     /// Actor operator context
     status: OperatorInfoStatus,
+
+    /// Shared context of the stream manager, used to create the inputs of new upstreams when the
+    /// upstream fragment is rescheduled.
+    context: Arc<SharedContext>,
 }
 
 impl MergeExecutor {
@@ -96,7 +138,8 @@ impl MergeExecutor {
         schema: Schema,
         pk_indices: PkIndices,
         actor_id: u32,
-        inputs: Vec<Receiver<Message>>,
+        inputs: Vec<(ActorId, Receiver<Message>)>,
+        context: Arc<SharedContext>,
         actor_context: ActorContextRef,
         receiver_id: u64,
     ) -> Self {
@@ -109,6 +152,62 @@ impl MergeExecutor {
                 identity: "MergeExecutor".to_string(),
             },
             status: OperatorInfoStatus::new(actor_context, receiver_id),
+            context,
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(self: Box<Self>) {
+        let actor_id = self.actor_id;
+        let context = self.context;
+        let mut select_all = SelectReceivers::new(actor_id, self.status, self.upstreams);
+
+        while let Some(msg) = select_all.next().await {
+            let msg = msg?;
+            let barrier = match &msg {
+                Message::Barrier(barrier) => Some(barrier.clone()),
+                _ => None,
+            };
+            yield msg;
+
+            // Update the upstreams after the barrier of the reschedule, if the upstream fragment
+            // of this merge is the rescheduled one.
+            if let Some(barrier) = barrier
+                && let Some(Mutation::Reschedule(reschedule)) = barrier.mutation.as_deref()
+                && let Some(update) = reschedule.merges.get(&actor_id)
+                && select_all
+                    .upstream_actor_ids()
+                    .any(|id| reschedule.prev_fragment_actors.contains(&id))
+            {
+                let mut added = Vec::with_capacity(update.added_upstream_actors.len());
+                for info in &update.added_upstream_actors {
+                    let upstream_addr = info
+                        .get_host()
+                        .map_err(StreamExecutorError::input_error)?
+                        .into();
+                    let mut input = new_input(&context, upstream_addr, info.actor_id, actor_id)
+                        .map_err(StreamExecutorError::input_error)?;
+                    // The new upstream actor is started by the same barrier, which has been
+                    // collected from the others.
+                    match input.next().await {
+                        Some(Message::Barrier(new_barrier)) if new_barrier.epoch == barrier.epoch => {}
+                        Some(Message::Barrier(new_barrier)) => {
+                            return Err(StreamExecutorError::align_barrier(
+                                barrier.clone(),
+                                new_barrier,
+                            ));
+                        }
+                        _ => {
+                            return Err(StreamExecutorError::channel_closed(format!(
+                                "expect the first barrier from new upstream {}",
+                                info.actor_id
+                            )));
+                        }
+                    }
+                    added.push((info.actor_id, input));
+                }
+                select_all.update_upstreams(added, &update.removed_upstream_actor_ids);
+            }
         }
     }
 }
@@ -116,12 +215,7 @@ impl MergeExecutor {
 #[async_trait]
 impl Executor for MergeExecutor {
     fn execute(self: Box<Self>) -> BoxedMessageStream {
-        let upstreams = self.upstreams;
-        // Futures of all active upstreams.
-        let status = self.status;
-        let select_all = SelectReceivers::new(self.actor_id, status, upstreams);
-        // Channels that're blocked by the barrier to align.
-        select_all.boxed()
+        self.execute_inner().boxed()
     }
 
     fn schema(&self) -> &Schema {
@@ -138,18 +232,26 @@ impl Executor for MergeExecutor {
 }
 
 pub struct SelectReceivers {
-    blocks: Vec<Receiver<Message>>,
-    upstreams: Vec<Receiver<Message>>,
+    /// Upstream channels that're blocked by the barrier to align, with their actor ids.
+    blocks: Vec<(ActorId, Receiver<Message>)>,
+    /// Active upstream channels, with their actor ids.
+    upstreams: Vec<(ActorId, Receiver<Message>)>,
     barrier: Option<Barrier>,
     last_base: usize,
     status: OperatorInfoStatus,
     actor_id: u32,
+    buffered_watermarks: BufferedWatermarks,
 }
 
 impl SelectReceivers {
-    fn new(actor_id: u32, status: OperatorInfoStatus, upstreams: Vec<Receiver<Message>>) -> Self {
+    fn new(
+        actor_id: u32,
+        status: OperatorInfoStatus,
+        upstreams: Vec<(ActorId, Receiver<Message>)>,
+    ) -> Self {
         Self {
             blocks: Vec::with_capacity(upstreams.len()),
+            buffered_watermarks: BufferedWatermarks::new(upstreams.len()),
             upstreams,
             last_base: 0,
             actor_id,
@@ -157,6 +259,27 @@ impl SelectReceivers {
             barrier: None,
         }
     }
+
+    fn upstream_actor_ids(&self) -> impl Iterator<Item = ActorId> + '_ {
+        self.upstreams.iter().map(|(id, _)| *id)
+    }
+
+    /// Add and remove upstreams. Must be called right after a barrier is emitted, when no upstream
+    /// is blocked.
+    fn update_upstreams(
+        &mut self,
+        added: Vec<(ActorId, Receiver<Message>)>,
+        removed: &HashSet<ActorId>,
+    ) {
+        assert!(self.blocks.is_empty() && self.barrier.is_none());
+        self.buffered_watermarks.update_upstreams(
+            added.len(),
+            &removed.iter().map(|id| *id as usize).collect::<Vec<_>>(),
+        );
+        self.upstreams.retain(|(id, _)| !removed.contains(id));
+        self.upstreams.extend(added);
+        self.last_base = 0;
+    }
 }
 
 impl Unpin for SelectReceivers {}
@@ -168,7 +291,9 @@ impl Stream for SelectReceivers {
         let mut poll_count = 0;
         while poll_count < self.upstreams.len() {
             let idx = (poll_count + self.last_base) % self.upstreams.len();
-            match self.upstreams[idx].poll_next_unpin(cx) {
+            let (upstream_id, upstream) = &mut self.upstreams[idx];
+            let upstream_id = *upstream_id;
+            match upstream.poll_next_unpin(cx) {
                 Poll::Pending => {
                     poll_count += 1;
                     continue;
@@ -201,6 +326,15 @@ impl Stream for SelectReceivers {
                             self.last_base = (idx + 1) % self.upstreams.len();
                             return Poll::Ready(Some(Ok(message)));
                         }
+                        Message::Watermark(watermark) => {
+                            if let Some(watermark) = self
+                                .buffered_watermarks
+                                .handle_watermark(upstream_id as usize, watermark)
+                            {
+                                self.last_base = (idx + 1) % self.upstreams.len();
+                                return Poll::Ready(Some(Ok(Message::Watermark(watermark))));
+                            }
+                        }
                     }
                 }
             }
@@ -268,8 +402,15 @@ mod tests {
             txs.push(tx);
             rxs.push(rx);
         }
-        let merger =
-            MergeExecutor::new(Schema::default(), vec![], 0, rxs, ActorContext::create(), 0);
+        let merger = MergeExecutor::new(
+            Schema::default(),
+            vec![],
+            0,
+            (0..CHANNEL_NUMBER as ActorId).zip_eq(rxs).collect(),
+            SharedContext::for_test().into(),
+            ActorContext::create(),
+            0,
+        );
         let mut handles = Vec::with_capacity(CHANNEL_NUMBER);
 
         let epochs = (10..1000u64).step_by(10).collect_vec();
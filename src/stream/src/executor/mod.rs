@@ -19,19 +19,26 @@ use enum_as_inner::EnumAsInner;
 use error::StreamExecutorResult;
 use futures::stream::BoxStream;
 use futures::{Stream, StreamExt};
+use itertools::Itertools;
 use madsim::collections::{HashMap, HashSet};
 use risingwave_common::array::column::Column;
 use risingwave_common::array::{ArrayImpl, ArrayRef, DataChunk, StreamChunk};
 use risingwave_common::buffer::Bitmap;
-use risingwave_common::catalog::Schema;
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema, TableId};
 use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
+use risingwave_common::util::compress::{compress_data, decompress_data};
+use risingwave_common::util::value_encoding::{deserialize_cell, serialize_cell};
 use risingwave_pb::common::ActorInfo;
 use risingwave_pb::data::barrier::Mutation as ProstMutation;
+use risingwave_pb::data::reschedule_mutation::{
+    DispatcherUpdate as ProstDispatcherUpdate, MergeUpdate as ProstMergeUpdate,
+};
 use risingwave_pb::data::stream_message::StreamMessage;
 use risingwave_pb::data::{
-    AddMutation, Barrier as ProstBarrier, DispatcherMutation, Epoch as ProstEpoch, NothingMutation,
-    StopMutation, StreamMessage as ProstStreamMessage, UpdateMutation,
+    AddColumnsMutation, AddMutation, Barrier as ProstBarrier, DispatcherMutation,
+    Epoch as ProstEpoch, NothingMutation, RescheduleMutation, StopMutation,
+    StreamMessage as ProstStreamMessage, UpdateMutation,
 };
 use smallvec::SmallVec;
 use tracing::trace_span;
@@ -45,12 +52,15 @@ mod batch_query;
 mod chain;
 mod debug;
 pub mod dispatch;
+mod dynamic_filter;
 mod error;
 mod filter;
 mod global_simple_agg;
+mod group_top_n;
 mod hash_agg;
 pub mod hash_join;
 mod hop_window;
+mod lateral_table_function;
 mod local_simple_agg;
 mod lookup;
 mod lookup_union;
@@ -58,15 +68,18 @@ mod managed_state;
 pub mod merge;
 pub mod monitor;
 mod mview;
+mod over_agg;
 mod project;
 mod rearranged_chain;
 pub mod receiver;
 mod simple;
+mod sink;
 mod source;
 mod top_n;
 mod top_n_appendonly;
 mod top_n_executor;
 mod union;
+mod watermark;
 
 #[cfg(test)]
 mod integration_tests;
@@ -78,23 +91,29 @@ pub use batch_query::BatchQueryExecutor;
 pub use chain::ChainExecutor;
 pub use debug::DebugExecutor;
 pub use dispatch::DispatchExecutor;
+pub use dynamic_filter::{Comparator, DynamicFilterExecutor};
 pub use filter::FilterExecutor;
 pub use global_simple_agg::SimpleAggExecutor;
+pub use group_top_n::GroupTopNExecutor;
 pub use hash_agg::HashAggExecutor;
 pub use hash_join::*;
 pub use hop_window::HopWindowExecutor;
+pub use lateral_table_function::LateralTableFunctionExecutor;
 pub use local_simple_agg::LocalSimpleAggExecutor;
 pub use lookup::*;
 pub use lookup_union::LookupUnionExecutor;
 pub use merge::MergeExecutor;
 pub use mview::*;
+pub use over_agg::OverAggExecutor;
 pub use project::ProjectExecutor;
 pub use rearranged_chain::RearrangedChainExecutor;
 use simple::{SimpleExecutor, SimpleExecutorWrapper};
+pub use sink::SinkExecutor;
 pub use source::*;
 pub use top_n::TopNExecutor;
 pub use top_n_appendonly::AppendOnlyTopNExecutor;
 pub use union::UnionExecutor;
+pub use watermark::*;
 
 pub type BoxedExecutor = Box<dyn Executor>;
 pub type BoxedMessageStream = BoxStream<'static, StreamExecutorResult<Message>>;
@@ -165,6 +184,40 @@ pub enum Mutation {
     Stop(HashSet<ActorId>),
     UpdateOutputs(HashMap<(ActorId, DispatcherId), Vec<ActorInfo>>),
     AddOutput(HashMap<(ActorId, DispatcherId), Vec<ActorInfo>>),
+    Reschedule(Reschedule),
+    /// Appends the columns to the materialized table by `ALTER TABLE ADD COLUMN`.
+    AddColumns(TableId, Vec<ColumnDesc>),
+}
+
+/// Changes of the actors around a rescheduled fragment.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Reschedule {
+    /// Updates of the dispatchers sending to the rescheduled fragment.
+    pub dispatchers: HashMap<(ActorId, DispatcherId), DispatcherUpdate>,
+    /// Updates of the merges receiving from the rescheduled fragment.
+    pub merges: HashMap<ActorId, MergeUpdate>,
+    /// All actors of the rescheduled fragment before rescheduling. A merge only applies the update
+    /// if it receives from these actors, since an actor may have merges from other fragments.
+    pub prev_fragment_actors: HashSet<ActorId>,
+    /// Actors of the rescheduled fragment whose vnodes are changed, including the new ones. They
+    /// may take over vnodes whose states were written by other actors in the previous epoch.
+    pub vnode_changed_actors: HashSet<ActorId>,
+    /// Removed actors, which stop after this barrier.
+    pub stopped_actors: HashSet<ActorId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatcherUpdate {
+    /// The new vnode mapping of a hash dispatcher.
+    pub hash_mapping: Option<Vec<ActorId>>,
+    pub added_downstream_actors: Vec<ActorInfo>,
+    pub removed_downstream_actor_ids: HashSet<ActorId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeUpdate {
+    pub added_upstream_actors: Vec<ActorInfo>,
+    pub removed_upstream_actor_ids: HashSet<ActorId>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -249,7 +302,21 @@ impl Barrier {
     }
 
     pub fn is_to_stop_actor(&self, actor_id: ActorId) -> bool {
-        matches!(self.mutation.as_deref(), Some(Mutation::Stop(actors)) if actors.contains(&actor_id))
+        match self.mutation.as_deref() {
+            Some(Mutation::Stop(actors)) => actors.contains(&actor_id),
+            Some(Mutation::Reschedule(reschedule)) => reschedule.stopped_actors.contains(&actor_id),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the vnodes owned by the actor are changed by rescheduling, in which case the
+    /// actor must drop its cached states, and wait for the previous epoch to be committed before
+    /// reading the states written by the previous owners of the vnodes.
+    pub fn is_vnode_changed(&self, actor_id: ActorId) -> bool {
+        matches!(
+            self.mutation.as_deref(),
+            Some(Mutation::Reschedule(reschedule)) if reschedule.vnode_changed_actors.contains(&actor_id)
+        )
     }
 
     pub fn is_to_add_output(&self, actor_id: ActorId) -> bool {
@@ -316,6 +383,20 @@ impl Barrier {
                         })
                         .collect(),
                 })),
+                Some(Mutation::Reschedule(reschedule)) => {
+                    Some(ProstMutation::Reschedule(reschedule.to_protobuf()))
+                }
+                Some(Mutation::AddColumns(table_id, columns)) => {
+                    Some(ProstMutation::AddColumns(AddColumnsMutation {
+                        table_id: table_id.table_id(),
+                        column_ids: columns.iter().map(|c| c.column_id.get_id()).collect(),
+                        column_types: columns.iter().map(|c| c.data_type.to_protobuf()).collect(),
+                        default_values: columns
+                            .iter()
+                            .map(|c| serialize_cell(&c.default_value).unwrap())
+                            .collect(),
+                    }))
+                }
             },
             span: vec![],
         }
@@ -356,6 +437,28 @@ impl Barrier {
                 )
                 .into(),
             ),
+            ProstMutation::Reschedule(reschedule) => {
+                Some(Mutation::Reschedule(Reschedule::from_protobuf(reschedule)).into())
+            }
+            ProstMutation::AddColumns(add_columns) => Some(
+                Mutation::AddColumns(
+                    TableId::new(add_columns.table_id),
+                    add_columns
+                        .column_ids
+                        .iter()
+                        .zip_eq(&add_columns.column_types)
+                        .zip_eq(&add_columns.default_values)
+                        .map(|((id, data_type), default_value)| {
+                            let data_type = DataType::from(data_type);
+                            Ok(ColumnDesc {
+                                default_value: deserialize_cell(&default_value[..], &data_type)?,
+                                ..ColumnDesc::unnamed(ColumnId::new(*id), data_type)
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                )
+                .into(),
+            ),
         };
         let epoch = prost.get_epoch().unwrap();
         Ok(Barrier {
@@ -370,10 +473,107 @@ impl Barrier {
     }
 }
 
+impl Reschedule {
+    fn to_protobuf(&self) -> RescheduleMutation {
+        RescheduleMutation {
+            dispatchers: self
+                .dispatchers
+                .iter()
+                .map(|(&(actor_id, dispatcher_id), update)| {
+                    let (original_indices, data) = match &update.hash_mapping {
+                        Some(hash_mapping) => compress_data(hash_mapping),
+                        None => (vec![], vec![]),
+                    };
+                    ProstDispatcherUpdate {
+                        actor_id,
+                        dispatcher_id,
+                        hash_mapping_original_indices: original_indices,
+                        hash_mapping_data: data,
+                        added_downstream_actors: update.added_downstream_actors.clone(),
+                        removed_downstream_actor_ids: update
+                            .removed_downstream_actor_ids
+                            .iter()
+                            .copied()
+                            .collect(),
+                    }
+                })
+                .collect(),
+            merges: self
+                .merges
+                .iter()
+                .map(|(&actor_id, update)| ProstMergeUpdate {
+                    actor_id,
+                    added_upstream_actors: update.added_upstream_actors.clone(),
+                    removed_upstream_actor_ids: update
+                        .removed_upstream_actor_ids
+                        .iter()
+                        .copied()
+                        .collect(),
+                })
+                .collect(),
+            prev_fragment_actors: self.prev_fragment_actors.iter().copied().collect(),
+            vnode_changed_actors: self.vnode_changed_actors.iter().copied().collect(),
+            stopped_actors: self.stopped_actors.iter().copied().collect(),
+        }
+    }
+
+    fn from_protobuf(prost: &RescheduleMutation) -> Self {
+        Self {
+            dispatchers: prost
+                .dispatchers
+                .iter()
+                .map(|update| {
+                    let hash_mapping = if update.hash_mapping_data.is_empty() {
+                        None
+                    } else {
+                        Some(decompress_data(
+                            &update.hash_mapping_original_indices,
+                            &update.hash_mapping_data,
+                        ))
+                    };
+                    (
+                        (update.actor_id, update.dispatcher_id),
+                        DispatcherUpdate {
+                            hash_mapping,
+                            added_downstream_actors: update.added_downstream_actors.clone(),
+                            removed_downstream_actor_ids: update
+                                .removed_downstream_actor_ids
+                                .iter()
+                                .copied()
+                                .collect(),
+                        },
+                    )
+                })
+                .collect(),
+            merges: prost
+                .merges
+                .iter()
+                .map(|update| {
+                    (
+                        update.actor_id,
+                        MergeUpdate {
+                            added_upstream_actors: update.added_upstream_actors.clone(),
+                            removed_upstream_actor_ids: update
+                                .removed_upstream_actor_ids
+                                .iter()
+                                .copied()
+                                .collect(),
+                        },
+                    )
+                })
+                .collect(),
+            prev_fragment_actors: prost.prev_fragment_actors.iter().copied().collect(),
+            vnode_changed_actors: prost.vnode_changed_actors.iter().copied().collect(),
+            stopped_actors: prost.stopped_actors.iter().copied().collect(),
+        }
+    }
+}
+
 #[derive(Debug, EnumAsInner, PartialEq)]
 pub enum Message {
     Chunk(StreamChunk),
     Barrier(Barrier),
+    Watermark(Watermark),
 }
 
 impl<'a> TryFrom<&'a Message> for &'a Barrier {
@@ -381,7 +581,7 @@ impl<'a> TryFrom<&'a Message> for &'a Barrier {
 
     fn try_from(m: &'a Message) -> std::result::Result<Self, Self::Error> {
         match m {
-            Message::Chunk(_) => Err(()),
+            Message::Chunk(_) | Message::Watermark(_) => Err(()),
             Message::Barrier(b) => Ok(b),
         }
     }
@@ -410,6 +610,7 @@ impl Message {
                 StreamMessage::StreamChunk(prost_stream_chunk)
             }
             Self::Barrier(barrier) => StreamMessage::Barrier(barrier.clone().to_protobuf()),
+            Self::Watermark(watermark) => StreamMessage::Watermark(watermark.to_protobuf()?),
         };
         let prost_stream_msg = ProstStreamMessage {
             stream_message: Some(prost),
@@ -425,6 +626,9 @@ impl Message {
             StreamMessage::Barrier(ref barrier) => {
                 Message::Barrier(Barrier::from_protobuf(barrier)?)
             }
+            StreamMessage::Watermark(ref watermark) => {
+                Message::Watermark(Watermark::from_protobuf(watermark)?)
+            }
         };
         Ok(res)
     }
@@ -17,20 +17,31 @@ use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::array::Op::*;
 use risingwave_common::array::Row;
-use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema};
-use risingwave_common::util::sort_util::OrderPair;
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema, TableId};
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
 use risingwave_storage::table::state_table::StateTable;
 use risingwave_storage::{Keyspace, StateStore};
 
 use crate::executor::error::StreamExecutorError;
 use crate::executor::{
-    BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndicesRef,
+    BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, Mutation, PkIndicesRef,
 };
 
 /// `MaterializeExecutor` materializes changes in stream into a materialized view on storage.
 pub struct MaterializeExecutor<S: StateStore> {
     input: BoxedExecutor,
 
+    table_id: TableId,
+
+    keyspace: Keyspace<S>,
+
+    /// Columns of the materialized table, which may be appended by `ALTER TABLE ADD COLUMN`.
+    column_descs: Vec<ColumnDesc>,
+
+    arrange_order_types: Vec<OrderType>,
+
+    pk_dist_indices: Vec<usize>,
+
     state_table: StateTable<S>,
 
     /// Columns of arrange keys (including pk, group keys, join keys, etc.)
@@ -42,6 +53,7 @@ pub struct MaterializeExecutor<S: StateStore> {
 impl<S: StateStore> MaterializeExecutor<S> {
     pub fn new(
         input: BoxedExecutor,
+        table_id: TableId,
         keyspace: Keyspace<S>,
         keys: Vec<OrderPair>,
         column_ids: Vec<ColumnId>,
@@ -49,7 +61,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
         distribution_keys: Vec<usize>,
     ) -> Self {
         let arrange_columns: Vec<usize> = keys.iter().map(|k| k.column_idx).collect();
-        let arrange_order_types = keys.iter().map(|k| k.order_type).collect();
+        let arrange_order_types: Vec<_> = keys.iter().map(|k| k.order_type).collect();
         let schema = input.schema().clone();
         let column_descs = column_ids
             .into_iter()
@@ -60,6 +72,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
                 name: field.name,
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             })
             .collect_vec();
         let pk_dist_indices = distribution_keys
@@ -74,6 +87,11 @@ impl<S: StateStore> MaterializeExecutor<S> {
             .collect_vec();
         Self {
             input,
+            table_id,
+            keyspace: keyspace.clone(),
+            column_descs: column_descs.clone(),
+            arrange_order_types: arrange_order_types.clone(),
+            pk_dist_indices: pk_dist_indices.clone(),
             state_table: StateTable::new(
                 keyspace,
                 column_descs,
@@ -115,12 +133,21 @@ impl<S: StateStore> MaterializeExecutor<S> {
                             .map(|col_idx| chunk.column_at(*col_idx).array_ref().datum_at(idx))
                             .collect_vec());
 
-                        // assemble row
-                        let row = Row(chunk
+                        // assemble row, where the columns added after the chunk is written
+                        // are filled with their defaults, and the columns not yet added to the
+                        // table are dropped, whose rows read them as the defaults
+                        let mut row = Row(chunk
                             .columns()
                             .iter()
                             .map(|x| x.array_ref().datum_at(idx))
                             .collect_vec());
+                        row.0.truncate(self.column_descs.len());
+                        let num_columns = row.0.len();
+                        row.0.extend(
+                            self.column_descs[num_columns..]
+                                .iter()
+                                .map(|c| c.default_value.clone()),
+                        );
 
                         match op {
                             Insert | UpdateInsert => {
@@ -140,13 +167,33 @@ impl<S: StateStore> MaterializeExecutor<S> {
                         .commit_with_value_meta(b.epoch.prev)
                         .await
                         .map_err(StreamExecutorError::executor_v1)?;
+                    if let Some(Mutation::AddColumns(table_id, columns)) = b.mutation.as_deref() {
+                        if *table_id == self.table_id {
+                            self.add_columns(columns);
+                        }
+                    }
                     Message::Barrier(b)
                 }
+                Message::Watermark(watermark) => Message::Watermark(watermark),
             }
         }
     }
 }
 
+impl<S: StateStore> MaterializeExecutor<S> {
+    /// Appends the columns to the table. The state table is rebuilt with the new columns, which is
+    /// fine since it has just been committed.
+    fn add_columns(&mut self, columns: &[ColumnDesc]) {
+        self.column_descs.extend(columns.iter().cloned());
+        self.state_table = StateTable::new(
+            self.keyspace.clone(),
+            self.column_descs.clone(),
+            self.arrange_order_types.clone(),
+            Some(self.pk_dist_indices.clone()),
+        );
+    }
+}
+
 impl<S: StateStore> Executor for MaterializeExecutor<S> {
     fn execute(self: Box<Self>) -> BoxedMessageStream {
         self.execute_inner().boxed()
@@ -236,6 +283,7 @@ mod tests {
         let table = CellBasedTable::new_for_test(keyspace.clone(), column_descs, order_types);
         let mut materialize_executor = Box::new(MaterializeExecutor::new(
             Box::new(source),
+            table_id,
             keyspace,
             vec![OrderPair::new(0, OrderType::Ascending)],
             column_ids,
@@ -270,4 +318,128 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_materialize_executor_add_columns() {
+        let memory_state_store = MemoryStateStore::new();
+        let table_id = TableId::new(1);
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+        ]);
+        let column_ids = vec![0.into(), 1.into()];
+
+        let chunk1 = StreamChunk::from_pretty(
+            " i i
+            + 1 4",
+        );
+        // The column 2 is added by the barrier before this chunk.
+        let chunk2 = StreamChunk::from_pretty(
+            " i i I
+            + 2 5 6
+            + 3 6 .",
+        );
+        // The chunk written before the column is added reaches the executor after the barrier.
+        let chunk3 = StreamChunk::from_pretty(
+            " i i
+            + 4 7",
+        );
+        let added_column = ColumnDesc {
+            default_value: Some(10_i64.into()),
+            ..ColumnDesc::unnamed(2.into(), DataType::Int64)
+        };
+
+        let source = MockSource::with_messages(
+            schema.clone(),
+            PkIndices::new(),
+            vec![
+                Message::Chunk(chunk1),
+                Message::Barrier(
+                    Barrier::new_test_barrier(1)
+                        .with_mutation(Mutation::AddColumns(table_id, vec![added_column.clone()])),
+                ),
+                Message::Chunk(chunk2),
+                Message::Chunk(chunk3),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+            ],
+        );
+
+        let keyspace = Keyspace::table_root(memory_state_store.clone(), &table_id);
+        let column_descs = vec![
+            ColumnDesc::unnamed(column_ids[0], DataType::Int32),
+            ColumnDesc::unnamed(column_ids[1], DataType::Int32),
+            added_column,
+        ];
+        let table = CellBasedTable::new_for_test(
+            keyspace.clone(),
+            column_descs,
+            vec![OrderType::Ascending],
+        );
+        let mut materialize_executor = Box::new(MaterializeExecutor::new(
+            Box::new(source),
+            table_id,
+            keyspace,
+            vec![OrderPair::new(0, OrderType::Ascending)],
+            column_ids,
+            1,
+            vec![0],
+        ))
+        .execute();
+
+        materialize_executor.next().await.transpose().unwrap();
+        materialize_executor.next().await.transpose().unwrap();
+        materialize_executor.next().await.transpose().unwrap();
+        materialize_executor.next().await.transpose().unwrap();
+        match materialize_executor.next().await.transpose().unwrap() {
+            Some(Message::Barrier(_)) => {
+                // The row written before the column is added reads its default value.
+                let row = table
+                    .get_row(&Row(vec![Some(1_i32.into())]), u64::MAX)
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    row,
+                    Some(Row(vec![
+                        Some(1_i32.into()),
+                        Some(4_i32.into()),
+                        Some(10_i64.into())
+                    ]))
+                );
+                let row = table
+                    .get_row(&Row(vec![Some(2_i32.into())]), u64::MAX)
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    row,
+                    Some(Row(vec![
+                        Some(2_i32.into()),
+                        Some(5_i32.into()),
+                        Some(6_i64.into())
+                    ]))
+                );
+                // The `NULL` written after the column is added is kept.
+                let row = table
+                    .get_row(&Row(vec![Some(3_i32.into())]), u64::MAX)
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    row,
+                    Some(Row(vec![Some(3_i32.into()), Some(6_i32.into()), None]))
+                );
+                let row = table
+                    .get_row(&Row(vec![Some(4_i32.into())]), u64::MAX)
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    row,
+                    Some(Row(vec![
+                        Some(4_i32.into()),
+                        Some(7_i32.into()),
+                        Some(10_i64.into())
+                    ]))
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
 }
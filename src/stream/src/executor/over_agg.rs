@@ -0,0 +1,479 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use itertools::{EitherOrBoth, Itertools};
+use madsim::collections::HashMap;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema};
+use risingwave_common::collection::evictable::EvictableHashMap;
+use risingwave_common::types::DataType;
+use risingwave_common::util::ordered::{serialize_pk, OrderedRowSerializer};
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_expr::window_function::{evaluate_partition, WindowFuncCall};
+use risingwave_storage::table::state_table::StateTable;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message,
+    PkIndices, PkIndicesRef, StreamExecutorResult,
+};
+use crate::task::ActorId;
+
+/// Number of partitions kept in the cache after each barrier.
+const PARTITION_CACHE_SIZE: usize = 1 << 10;
+
+/// Rows of a partition, keyed by their serialized pk in the state table, so that they are sorted
+/// by the `ORDER BY` of the window.
+type Partition = BTreeMap<Vec<u8>, Row>;
+
+/// [`OverAggExecutor`] evaluates window functions over the partitions of its input, and outputs
+/// the input rows with the results appended.
+///
+/// All rows of the input are kept in the state table, with the partition key, the order key and
+/// the input pk as its pk, so that a partition can be loaded with a prefix scan. When a chunk
+/// changes a partition, the window functions are evaluated over the whole partition before and
+/// after the change, and the rows whose results changed are retracted and emitted again.
+///
+/// Note that a changed partition is always loaded and evaluated as a whole, so every partition must
+/// fit in memory and each change costs time linear to the size of its partition. Queries with few
+/// large partitions, e.g. without `PARTITION BY`, are better planned with a top-n when possible.
+pub struct OverAggExecutor<S: StateStore> {
+    input: BoxedExecutor,
+
+    extra: OverAggExecutorExtra<S>,
+}
+
+struct OverAggExecutorExtra<S: StateStore> {
+    info: ExecutorInfo,
+
+    /// Belonged actor id.
+    actor_id: ActorId,
+
+    calls: Vec<WindowFuncCall>,
+
+    /// Indices of the partition key columns.
+    partition_by: Vec<usize>,
+
+    /// Indices of the order key columns.
+    order_key_indices: Vec<usize>,
+
+    /// Data types of the input columns.
+    input_types: Vec<DataType>,
+
+    /// Indices of the pk columns of the state table: partition key, order key and input pk.
+    state_pk_indices: Vec<usize>,
+
+    state_pk_serializer: OrderedRowSerializer,
+
+    state_table: StateTable<S>,
+
+    /// The state store of the state table, used to wait for epochs to be committed.
+    store: S,
+}
+
+impl<S: StateStore> Executor for OverAggExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.extra.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.extra.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.extra.info.identity
+    }
+}
+
+impl<S: StateStore> OverAggExecutor<S> {
+    pub fn new(
+        input: BoxedExecutor,
+        calls: Vec<WindowFuncCall>,
+        partition_by: Vec<usize>,
+        order_by: Vec<OrderPair>,
+        keyspace: Keyspace<S>,
+        pk_indices: PkIndices,
+        actor_id: ActorId,
+        executor_id: u64,
+    ) -> Self {
+        let input_info = input.info();
+        let input_types = input_info.schema.data_types();
+
+        let mut fields = input_info.schema.fields.clone();
+        fields.extend(
+            calls
+                .iter()
+                .map(|call| Field::unnamed(call.return_type.clone())),
+        );
+
+        let state_pk_indices = partition_by
+            .iter()
+            .copied()
+            .chain(order_by.iter().map(|order| order.column_idx))
+            .chain(input_info.pk_indices.iter().copied())
+            .collect_vec();
+        let state_order_types = partition_by
+            .iter()
+            .map(|_| OrderType::Ascending)
+            .chain(order_by.iter().map(|order| order.order_type))
+            .chain(input_info.pk_indices.iter().map(|_| OrderType::Ascending))
+            .collect_vec();
+        let column_descs = input_types
+            .iter()
+            .enumerate()
+            .map(|(id, data_type)| {
+                ColumnDesc::unnamed(ColumnId::from(id as i32), data_type.clone())
+            })
+            .collect();
+        let store = keyspace.state_store();
+        let state_table = StateTable::new(
+            keyspace,
+            column_descs,
+            state_order_types.clone(),
+            // The partition key is the prefix of the pk of the state table.
+            Some((0..partition_by.len()).collect()),
+        );
+
+        Self {
+            input,
+            extra: OverAggExecutorExtra {
+                info: ExecutorInfo {
+                    schema: Schema { fields },
+                    pk_indices,
+                    identity: format!("OverAggExecutor {:X}", executor_id),
+                },
+                actor_id,
+                calls,
+                partition_by,
+                order_key_indices: order_by.iter().map(|order| order.column_idx).collect(),
+                input_types,
+                state_pk_indices,
+                state_pk_serializer: OrderedRowSerializer::new(state_order_types),
+                state_table,
+                store,
+            },
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(self) {
+        let OverAggExecutor { input, mut extra } = self;
+
+        // The cached partitions. `partition key -> rows`.
+        let mut partitions = EvictableHashMap::new(PARTITION_CACHE_SIZE);
+
+        let mut input = input.execute();
+        let barrier = expect_first_barrier(&mut input).await?;
+        let mut epoch = barrier.epoch.curr;
+        let vnode_changed = barrier.is_vnode_changed(extra.actor_id);
+        let prev_epoch = barrier.epoch.prev;
+        yield Message::Barrier(barrier);
+        // A new actor created by rescheduling reads the partitions written by the previous owners.
+        if vnode_changed {
+            extra
+                .store
+                .wait_epoch(prev_epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+        }
+
+        #[for_await]
+        for msg in input {
+            let msg = msg?;
+            match msg {
+                Message::Chunk(chunk) => {
+                    if let Some(chunk) = extra.apply_chunk(&mut partitions, chunk, epoch).await? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    assert_eq!(epoch, barrier.epoch.prev);
+                    extra
+                        .state_table
+                        .commit(epoch)
+                        .await
+                        .map_err(StreamExecutorError::storage)?;
+                    partitions.evict_to_target_cap();
+                    // The partitions cached may be owned by other actors after the reschedule.
+                    let vnode_changed = barrier.is_vnode_changed(extra.actor_id);
+                    if vnode_changed {
+                        partitions.clear();
+                    }
+
+                    let prev_epoch = epoch;
+                    epoch = barrier.epoch.curr;
+                    yield Message::Barrier(barrier);
+                    if vnode_changed {
+                        extra
+                            .store
+                            .wait_epoch(prev_epoch)
+                            .await
+                            .map_err(StreamExecutorError::storage)?;
+                    }
+                }
+                // Window functions may still change the output of old rows.
+                Message::Watermark(_) => {}
+            }
+        }
+    }
+}
+
+impl<S: StateStore> OverAggExecutorExtra<S> {
+    async fn apply_chunk(
+        &mut self,
+        partitions: &mut EvictableHashMap<Row, Partition>,
+        chunk: StreamChunk,
+        epoch: u64,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        // Group the changes by partition, in the order that the partitions first appear.
+        let (data_chunk, ops) = chunk.into_parts();
+        let mut changes: Vec<(Row, Vec<(Op, Row)>)> = vec![];
+        let mut partition_positions = HashMap::new();
+        for (i, op) in ops.into_iter().enumerate() {
+            let (row, visible) = data_chunk
+                .row_at(i)
+                .map_err(StreamExecutorError::eval_error)?;
+            if !visible {
+                continue;
+            }
+            let partition_key = row.row_by_indices(&self.partition_by);
+            let position = *partition_positions
+                .entry(partition_key.clone())
+                .or_insert_with(|| {
+                    changes.push((partition_key, vec![]));
+                    changes.len() - 1
+                });
+            changes[position].1.push((op, row.to_owned_row()));
+        }
+
+        // Deletions are emitted before insertions, as a row may move to another position or
+        // partition with the same input pk.
+        let mut deletes = vec![];
+        let mut updates = vec![];
+        let mut inserts = vec![];
+        for (partition_key, partition_changes) in changes {
+            if !partitions.contains(&partition_key) {
+                let partition = self.load_partition(&partition_key, epoch).await?;
+                partitions.put(partition_key.clone(), partition);
+            }
+            let partition = partitions.get_mut(&partition_key).unwrap();
+
+            let old_outputs = self.evaluate(partition)?;
+            for (op, row) in partition_changes {
+                let state_pk = row.by_indices(&self.state_pk_indices);
+                let key = serialize_pk(&state_pk, &self.state_pk_serializer)
+                    .map_err(StreamExecutorError::eval_error)?;
+                match op {
+                    Op::Insert | Op::UpdateInsert => {
+                        partition.insert(key, row.clone());
+                        self.state_table
+                            .insert(state_pk, row)
+                            .map_err(StreamExecutorError::storage)?;
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        partition.remove(&key);
+                        self.state_table
+                            .delete(state_pk, row)
+                            .map_err(StreamExecutorError::storage)?;
+                    }
+                }
+            }
+            let new_outputs = self.evaluate(partition)?;
+
+            for output in old_outputs
+                .into_iter()
+                .merge_join_by(new_outputs, |(old_key, _), (new_key, _)| {
+                    old_key.cmp(new_key)
+                })
+            {
+                match output {
+                    EitherOrBoth::Left((_, old_row)) => deletes.push((Op::Delete, old_row)),
+                    EitherOrBoth::Right((_, new_row)) => inserts.push((Op::Insert, new_row)),
+                    EitherOrBoth::Both((_, old_row), (_, new_row)) => {
+                        if old_row != new_row {
+                            updates.push((Op::UpdateDelete, old_row));
+                            updates.push((Op::UpdateInsert, new_row));
+                        }
+                    }
+                }
+            }
+        }
+
+        let rows = deletes
+            .into_iter()
+            .chain(updates)
+            .chain(inserts)
+            .collect_vec();
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let chunk = StreamChunk::from_rows(&rows, &self.info.schema.data_types())
+            .map_err(StreamExecutorError::eval_error)?;
+        Ok(Some(chunk))
+    }
+
+    /// Loads the rows of a partition from the state table.
+    async fn load_partition(
+        &self,
+        partition_key: &Row,
+        epoch: u64,
+    ) -> StreamExecutorResult<Partition> {
+        let mut partition = Partition::new();
+        let mut iter = self
+            .state_table
+            .iter_with_pk_prefix(partition_key, epoch)
+            .await
+            .map_err(StreamExecutorError::storage)?;
+        while let Some(row) = iter.next().await.map_err(StreamExecutorError::storage)? {
+            let state_pk = row.by_indices(&self.state_pk_indices);
+            let key = serialize_pk(&state_pk, &self.state_pk_serializer)
+                .map_err(StreamExecutorError::eval_error)?;
+            partition.insert(key, row);
+        }
+        Ok(partition)
+    }
+
+    /// Evaluates the window functions over a partition, and returns the output rows keyed by the
+    /// same keys as the partition.
+    fn evaluate(&self, partition: &Partition) -> StreamExecutorResult<Vec<(Vec<u8>, Row)>> {
+        let rows = partition.values().cloned().collect_vec();
+        let results = evaluate_partition(
+            &self.calls,
+            &rows,
+            &self.input_types,
+            &self.order_key_indices,
+        )
+        .map_err(StreamExecutorError::eval_error)?;
+        Ok(partition
+            .keys()
+            .cloned()
+            .zip_eq(rows.into_iter().zip_eq(results))
+            .map(|(key, (row, result))| (key, Row(row.0.into_iter().chain(result.0).collect())))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::sort_util::{OrderPair, OrderType};
+    use risingwave_expr::window_function::{Frame, WindowFuncCall, WindowFuncKind};
+
+    use super::OverAggExecutor;
+    use crate::executor::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor::{Barrier, Executor, Message};
+
+    #[tokio::test]
+    async fn test_over_agg_executor() {
+        // (user_id, ts, value) with pk (ts)
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let source = MockSource::with_messages(
+            schema,
+            vec![1],
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I I  I
+                    + 1 1 10
+                    + 1 3 30
+                    + 2 2 20",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I I  I
+                    + 1 4 15
+                    - 2 2 20
+                    U- 1 1 10
+                    U+ 1 1 11",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        );
+        let calls = vec![
+            WindowFuncCall {
+                kind: WindowFuncKind::RowNumber,
+                args: vec![],
+                return_type: DataType::Int64,
+                frame: Frame::default(),
+                offset: 1,
+            },
+            WindowFuncCall {
+                kind: WindowFuncKind::Lag,
+                args: vec![(2, DataType::Int64)],
+                return_type: DataType::Int64,
+                frame: Frame::default(),
+                offset: 1,
+            },
+        ];
+        // row_number() OVER (PARTITION BY user_id ORDER BY ts DESC), lag(value) OVER (...)
+        let over_agg = OverAggExecutor::new(
+            Box::new(source),
+            calls,
+            vec![0],
+            vec![OrderPair::new(1, OrderType::Descending)],
+            create_in_memory_keyspace(),
+            vec![1],
+            0,
+            1,
+        );
+        let mut over_agg = Box::new(over_agg).execute();
+
+        assert_matches!(over_agg.next().await.unwrap().unwrap(), Message::Barrier(_));
+        assert_eq!(
+            *over_agg.next().await.unwrap().unwrap().as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I I  I
+                + 1 3 30 1  .
+                + 1 1 10 2 30
+                + 2 2 20 1  ."
+            )
+        );
+
+        assert_matches!(over_agg.next().await.unwrap().unwrap(), Message::Barrier(_));
+        // The earlier rows of user 1 are retracted, as a newer row is inserted before them.
+        assert_eq!(
+            *over_agg.next().await.unwrap().unwrap().as_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                "  I I  I I  I
+                -  2 2 20 1  .
+                U- 1 3 30 1  .
+                U+ 1 3 30 2 15
+                U- 1 1 10 2 30
+                U+ 1 1 11 3 30
+                +  1 4 15 1  ."
+            )
+        );
+        assert_matches!(over_agg.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+}
@@ -22,7 +22,7 @@ use risingwave_expr::expr::BoxedExpression;
 
 use super::{
     Executor, ExecutorInfo, PkIndices, PkIndicesRef, SimpleExecutor, SimpleExecutorWrapper,
-    StreamExecutorResult,
+    StreamExecutorResult, Watermark,
 };
 use crate::executor::error::StreamExecutorError;
 
@@ -34,6 +34,7 @@ impl ProjectExecutor {
         pk_indices: PkIndices,
         exprs: Vec<BoxedExpression>,
         execuotr_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
     ) -> Self {
         let info = ExecutorInfo {
             schema: input.schema().to_owned(),
@@ -42,7 +43,7 @@ impl ProjectExecutor {
         };
         SimpleExecutorWrapper {
             input,
-            inner: SimpleProjectExecutor::new(info, exprs, execuotr_id),
+            inner: SimpleProjectExecutor::new(info, exprs, execuotr_id, watermark_derivations),
         }
     }
 }
@@ -55,10 +56,19 @@ pub struct SimpleProjectExecutor {
 
     /// Expressions of the current projection.
     exprs: Vec<BoxedExpression>,
+
+    /// Pairs of `(input column, output column)`. The watermark on the input column derives a
+    /// watermark on the output column through the output expression.
+    watermark_derivations: Vec<(usize, usize)>,
 }
 
 impl SimpleProjectExecutor {
-    pub fn new(input_info: ExecutorInfo, exprs: Vec<BoxedExpression>, executor_id: u64) -> Self {
+    pub fn new(
+        input_info: ExecutorInfo,
+        exprs: Vec<BoxedExpression>,
+        executor_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
+    ) -> Self {
         let schema = Schema {
             fields: exprs
                 .iter()
@@ -72,6 +82,7 @@ impl SimpleProjectExecutor {
                 identity: format!("ProjectExecutor {:X}", executor_id),
             },
             exprs,
+            watermark_derivations,
         }
     }
 }
@@ -115,6 +126,21 @@ impl SimpleExecutor for SimpleProjectExecutor {
         Ok(Some(new_chunk))
     }
 
+    fn handle_watermark(&mut self, watermark: Watermark) -> StreamExecutorResult<Vec<Watermark>> {
+        let mut watermarks = vec![];
+        for &(input_idx, output_idx) in &self.watermark_derivations {
+            if input_idx == watermark.col_idx {
+                if let Some(derived) = watermark
+                    .clone()
+                    .transform_with_expr(&self.exprs[output_idx], output_idx)?
+                {
+                    watermarks.push(derived);
+                }
+            }
+        }
+        Ok(watermarks)
+    }
+
     fn schema(&self) -> &Schema {
         &self.info.schema
     }
@@ -134,9 +160,9 @@ mod tests {
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::array::StreamChunk;
     use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
-    use risingwave_expr::expr::InputRefExpression;
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
     use risingwave_pb::expr::expr_node::Type;
 
     use super::super::test_utils::MockSource;
@@ -178,6 +204,7 @@ mod tests {
             vec![],
             vec![test_expr],
             1,
+            vec![],
         ));
         let mut project = project.execute();
 
@@ -204,4 +231,55 @@ mod tests {
 
         assert!(project.next().await.unwrap().unwrap().is_stop());
     }
+
+    #[tokio::test]
+    async fn test_watermark_projection() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let source = MockSource::with_messages(
+            schema,
+            PkIndices::new(),
+            vec![
+                Message::Watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(10))),
+                Message::Watermark(Watermark::new(1, DataType::Int64, ScalarImpl::Int64(5))),
+            ],
+        );
+
+        // $1, $0 + 1
+        let test_expr = new_binary_expr(
+            Type::Add,
+            DataType::Int64,
+            Box::new(InputRefExpression::new(DataType::Int64, 0)),
+            Box::new(LiteralExpression::new(
+                DataType::Int64,
+                Some(ScalarImpl::Int64(1)),
+            )),
+        );
+        let project = Box::new(ProjectExecutor::new(
+            Box::new(source),
+            vec![],
+            vec![
+                Box::new(InputRefExpression::new(DataType::Int64, 1)),
+                test_expr,
+            ],
+            1,
+            vec![(1, 0), (0, 1)],
+        ));
+        let mut project = project.execute();
+
+        let msg = project.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_watermark().unwrap(),
+            Watermark::new(1, DataType::Int64, ScalarImpl::Int64(11))
+        );
+        let msg = project.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_watermark().unwrap(),
+            Watermark::new(0, DataType::Int64, ScalarImpl::Int64(5))
+        );
+    }
 }
@@ -0,0 +1,176 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::internal_error;
+use risingwave_connector::sink::{Sink, SinkConfig, SinkImpl};
+
+use super::error::StreamExecutorError;
+use super::{BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndicesRef};
+
+/// `SinkExecutor` writes the changes of its input into an external sink. Chunks are forwarded to
+/// the sink as they arrive, and the sink commits the buffered records on each barrier. Messages are
+/// passed through unchanged.
+pub struct SinkExecutor {
+    input: BoxedExecutor,
+    config: SinkConfig,
+    info: ExecutorInfo,
+}
+
+impl SinkExecutor {
+    pub fn new(input: BoxedExecutor, config: SinkConfig, executor_id: u64) -> Self {
+        let info = ExecutorInfo {
+            schema: input.schema().clone(),
+            pk_indices: input.pk_indices().to_vec(),
+            identity: format!("SinkExecutor {:X}", executor_id),
+        };
+        Self {
+            input,
+            config,
+            info,
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(self) {
+        // The sink may connect to external systems, so it is created here instead of in the
+        // synchronous executor builder.
+        let mut sink = SinkImpl::new(
+            self.config,
+            self.info.schema.clone(),
+            self.info.pk_indices.clone(),
+        )
+        .await
+        .map_err(sink_error)?;
+        let mut in_epoch = false;
+
+        let input = self.input.execute();
+        #[for_await]
+        for msg in input {
+            let msg = msg?;
+            match &msg {
+                Message::Chunk(chunk) => {
+                    sink.write_batch(chunk.clone()).await.map_err(sink_error)?;
+                }
+                Message::Barrier(barrier) => {
+                    // Nothing is written before the first barrier, so there is nothing to commit.
+                    if in_epoch {
+                        sink.commit().await.map_err(sink_error)?;
+                    }
+                    sink.begin_epoch(barrier.epoch.curr)
+                        .await
+                        .map_err(sink_error)?;
+                    in_epoch = true;
+                }
+                Message::Watermark(_) => {}
+            }
+            yield msg;
+        }
+    }
+}
+
+fn sink_error(error: anyhow::Error) -> StreamExecutorError {
+    StreamExecutorError::sink_error(internal_error(error.to_string()))
+}
+
+impl Executor for SinkExecutor {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.info.identity.as_str()
+    }
+}
+
+impl std::fmt::Debug for SinkExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SinkExecutor")
+            .field("input info", &self.info())
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::DataType;
+    use risingwave_connector::sink::{FileSinkConfig, SinkFormat};
+
+    use super::*;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::Barrier;
+
+    #[tokio::test]
+    async fn test_sink_commit_on_barrier() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sink.json");
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "v"),
+        ]);
+        let source = MockSource::with_messages(
+            schema,
+            vec![0],
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    " I I
+                    + 1 2
+                    + 2 3",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    "  I I
+                    U- 1 2
+                    U+ 1 4",
+                )),
+            ],
+        )
+        .stop_on_finish(false);
+        let config = SinkConfig::File(FileSinkConfig {
+            path: path.to_str().unwrap().to_string(),
+            format: SinkFormat::Upsert,
+        });
+
+        let outputs: Vec<_> = Box::new(SinkExecutor::new(Box::new(source), config, 1))
+            .execute()
+            .try_collect()
+            .await
+            .unwrap();
+
+        // All messages are passed through.
+        assert_eq!(outputs.len(), 4);
+        // The last chunk is not followed by a barrier, so it is never committed.
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"key\":{\"k\":1},\"value\":{\"k\":1,\"v\":2}}\n\
+             {\"key\":{\"k\":2},\"value\":{\"k\":2,\"v\":3}}\n"
+        );
+    }
+}
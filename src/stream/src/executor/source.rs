@@ -19,16 +19,20 @@ use either::Either;
 use futures::stream::{select_with_strategy, PollNext};
 use futures::{Stream, StreamExt};
 use futures_async_stream::try_stream;
+use itertools::Itertools;
 use paste::paste;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::{ArrayBuilder, ArrayImpl, I64ArrayBuilder, StreamChunk};
+use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::{ColumnId, Schema, TableId};
 use risingwave_common::error::{internal_error, Result, RwError, ToRwResult};
+use risingwave_common::types::ScalarImpl;
 use risingwave_connector::state::SourceStateHandler;
 use risingwave_connector::{
     ConnectorState, SplitImpl, KAFKA_CONNECTOR, KINESIS_CONNECTOR, NEXMARK_CONNECTOR,
     PULSAR_CONNECTOR,
 };
+use risingwave_expr::expr::BoxedExpression;
 use risingwave_source::*;
 use risingwave_storage::{Keyspace, StateStore};
 use tokio::sync::mpsc::UnboundedReceiver;
@@ -69,6 +73,22 @@ pub struct SourceExecutor<S: StateStore> {
 
     /// Expected barrier latency
     expected_barrier_latency_ms: u64,
+
+    /// Generators of the watermarks defined on the source.
+    watermark_generators: Vec<WatermarkGenerator>,
+}
+
+/// Generates the watermark on a column with the `WATERMARK FOR` expression of the source.
+struct WatermarkGenerator {
+    /// Index of the column in the output of the executor.
+    col_idx: usize,
+    /// Index of the column in the source, which the expression refers to.
+    source_col_idx: usize,
+    expr: BoxedExpression,
+    /// The max value of the column seen so far.
+    max: Option<ScalarImpl>,
+    /// The last emitted watermark. Rows below it are late and will be dropped.
+    current: Option<ScalarImpl>,
 }
 
 impl<S: StateStore> SourceExecutor<S> {
@@ -87,7 +107,23 @@ impl<S: StateStore> SourceExecutor<S> {
         streaming_metrics: Arc<StreamingMetrics>,
         stream_source_splits: Vec<SplitImpl>,
         expected_barrier_latency_ms: u64,
+        watermark_descs: Vec<(usize, BoxedExpression)>,
     ) -> Result<Self> {
+        let watermark_generators = watermark_descs
+            .into_iter()
+            .filter_map(|(source_col_idx, expr)| {
+                let column_id = source_desc.columns[source_col_idx].column_id;
+                // The watermark is not needed if the column is pruned.
+                let col_idx = column_ids.iter().position(|id| *id == column_id)?;
+                Some(WatermarkGenerator {
+                    col_idx,
+                    source_col_idx,
+                    expr,
+                    max: None,
+                    current: None,
+                })
+            })
+            .collect();
         Ok(Self {
             source_id,
             source_desc,
@@ -102,6 +138,7 @@ impl<S: StateStore> SourceExecutor<S> {
             split_state_store: SourceStateHandler::new(keyspace),
             state_cache: HashMap::new(),
             expected_barrier_latency_ms,
+            watermark_generators,
         })
     }
 
@@ -132,6 +169,68 @@ impl<S: StateStore> SourceExecutor<S> {
         }
         chunk
     }
+
+    /// Drop the late rows below the current watermarks, and advance the watermarks with the max
+    /// values of the chunk. Returns the filtered chunk and the watermarks to emit after it.
+    fn apply_watermarks(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<(StreamChunk, Vec<Watermark>)> {
+        if self.watermark_generators.is_empty() {
+            return Ok((chunk, vec![]));
+        }
+
+        let (ops, columns, visibility) = chunk.into_inner();
+        let mut vis = match &visibility {
+            Some(bitmap) => bitmap.iter().collect_vec(),
+            None => vec![true; ops.len()],
+        };
+        let mut watermarks = vec![];
+        for generator in &mut self.watermark_generators {
+            let array = columns[generator.col_idx].array_ref();
+            for (i, visible) in vis.iter_mut().enumerate() {
+                if !*visible {
+                    continue;
+                }
+                let Some(val) = array.datum_at(i) else {
+                    continue;
+                };
+                if generator
+                    .current
+                    .as_ref()
+                    .map_or(false, |current| &val < current)
+                {
+                    *visible = false;
+                } else if generator.max.as_ref().map_or(true, |max| &val > max) {
+                    generator.max = Some(val);
+                }
+            }
+
+            let Some(max) = generator.max.clone() else {
+                continue;
+            };
+            let data_type = self.schema.fields[generator.col_idx].data_type();
+            let watermark = Watermark::new(generator.source_col_idx, data_type, max)
+                .transform_with_expr(&generator.expr, generator.col_idx)?;
+            if let Some(watermark) = watermark {
+                if generator
+                    .current
+                    .as_ref()
+                    .map_or(true, |current| &watermark.val > current)
+                {
+                    generator.current = Some(watermark.val.clone());
+                    watermarks.push(watermark);
+                }
+            }
+        }
+
+        let visibility = if vis.iter().all(|v| *v) {
+            None
+        } else {
+            Some(Bitmap::try_from(vis).map_err(StreamExecutorError::source_error)?)
+        };
+        Ok((StreamChunk::new(ops, columns, visibility), watermarks))
+    }
 }
 
 struct SourceReader {
@@ -347,11 +446,15 @@ impl<S: StateStore> SourceExecutor<S> {
                         chunk = self.refill_row_id_column(chunk);
                     }
 
+                    let (chunk, watermarks) = self.apply_watermarks(chunk)?;
                     self.metrics
                         .source_output_row_count
                         .with_label_values(&[self.source_identify.as_str()])
                         .inc_by(chunk.cardinality() as u64);
                     yield Message::Chunk(chunk);
+                    for watermark in watermarks {
+                        yield Message::Watermark(watermark);
+                    }
                 }
             }
         }
@@ -417,6 +520,7 @@ mod tests {
                 name: String::new(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             },
             ColumnDesc {
                 column_id: ColumnId::from(1),
@@ -424,6 +528,7 @@ mod tests {
                 name: String::new(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             },
             ColumnDesc {
                 column_id: ColumnId::from(2),
@@ -431,6 +536,7 @@ mod tests {
                 name: String::new(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             },
         ];
         let source_manager = MemSourceManager::default();
@@ -479,6 +585,7 @@ mod tests {
             Arc::new(StreamingMetrics::new(prometheus::Registry::new())),
             vec![],
             u64::MAX,
+            vec![],
         )
         .unwrap();
         let mut executor = Box::new(executor).execute();
@@ -545,6 +652,7 @@ mod tests {
                 name: String::new(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             },
             ColumnDesc {
                 column_id: ColumnId::from(1),
@@ -552,6 +660,7 @@ mod tests {
                 name: String::new(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             },
             ColumnDesc {
                 column_id: ColumnId::from(2),
@@ -559,6 +668,7 @@ mod tests {
                 name: String::new(),
                 field_descs: vec![],
                 type_name: "".to_string(),
+                default_value: None,
             },
         ];
         let source_manager = MemSourceManager::default();
@@ -601,6 +711,7 @@ mod tests {
             Arc::new(StreamingMetrics::unused()),
             vec![],
             u64::MAX,
+            vec![],
         )
         .unwrap();
         let mut executor = Box::new(executor).execute();
@@ -14,7 +14,7 @@
 
 use async_trait::async_trait;
 use madsim::collections::HashSet;
-use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema};
 use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
@@ -24,8 +24,7 @@ use risingwave_storage::cell_based_row_deserializer::CellBasedRowDeserializer;
 use risingwave_storage::{Keyspace, StateStore};
 
 use super::error::{StreamExecutorError, StreamExecutorResult};
-use super::managed_state::top_n::variants::{TOP_N_MAX, TOP_N_MIN};
-use super::managed_state::top_n::{ManagedTopNBottomNState, ManagedTopNState};
+use super::managed_state::top_n::ManagedTopNRangeState;
 use super::top_n_executor::{generate_output, TopNExecutorBase, TopNExecutorWrapper};
 use super::{BoxedMessageStream, Executor, ExecutorInfo, PkIndices, PkIndicesRef};
 
@@ -73,11 +72,6 @@ pub struct InnerTopNExecutor<S: StateStore> {
     /// Schema of the executor.
     schema: Schema,
 
-    /// `LIMIT XXX`. `None` means no limit.
-    limit: Option<usize>,
-    /// `OFFSET XXX`. `0` means no offset.
-    offset: usize,
-
     /// The primary key indices of the `TopNExecutor`
     pk_indices: PkIndices,
 
@@ -87,11 +81,9 @@ pub struct InnerTopNExecutor<S: StateStore> {
     /// The order of internal keys of the `TopNExecutor`
     internal_key_order_types: Vec<OrderType>,
 
-    /// We are interested in which element is in the range of [offset, offset+limit). But we
-    /// still need to record elements in the other two ranges.
-    managed_lowest_state: ManagedTopNState<S, TOP_N_MAX>,
-    managed_middle_state: ManagedTopNBottomNState<S>,
-    managed_highest_state: ManagedTopNState<S, TOP_N_MIN>,
+    /// The rows in the ranges of `[0, offset)`, `[offset, offset+limit)` and
+    /// `[offset+limit, +inf)`.
+    managed_state: ManagedTopNRangeState<S>,
 
     /// Marks whether this is first-time execution. If yes, we need to fill in the cache from
     /// storage.
@@ -165,29 +157,11 @@ impl<S: StateStore> InnerTopNExecutor<S> {
             })
             .collect::<Vec<_>>();
         let cell_based_row_deserializer = CellBasedRowDeserializer::new(table_column_descs);
-        let lower_sub_keyspace = keyspace.append_u8(b'l');
-        let middle_sub_keyspace = keyspace.append_u8(b'm');
-        let higher_sub_keyspace = keyspace.append_u8(b'h');
-        let managed_lowest_state = ManagedTopNState::<S, TOP_N_MAX>::new(
-            cache_size,
-            total_count.0,
-            lower_sub_keyspace,
-            row_data_types.clone(),
-            ordered_row_deserializer.clone(),
-            cell_based_row_deserializer.clone(),
-        );
-        let managed_middle_state = ManagedTopNBottomNState::new(
-            cache_size,
-            total_count.1,
-            middle_sub_keyspace,
-            row_data_types.clone(),
-            ordered_row_deserializer.clone(),
-            cell_based_row_deserializer.clone(),
-        );
-        let managed_highest_state = ManagedTopNState::<S, TOP_N_MIN>::new(
+        let managed_state = ManagedTopNRangeState::new(
+            offset_and_limit,
             cache_size,
-            total_count.2,
-            higher_sub_keyspace,
+            total_count,
+            keyspace,
             row_data_types,
             ordered_row_deserializer,
             cell_based_row_deserializer,
@@ -199,11 +173,7 @@ impl<S: StateStore> InnerTopNExecutor<S> {
                 identity: format!("TopNExecutor {:X}", executor_id),
             },
             schema,
-            offset: offset_and_limit.0,
-            limit: offset_and_limit.1,
-            managed_lowest_state,
-            managed_middle_state,
-            managed_highest_state,
+            managed_state,
             pk_indices,
             internal_key_indices,
             internal_key_order_types,
@@ -213,15 +183,7 @@ impl<S: StateStore> InnerTopNExecutor<S> {
     }
 
     async fn flush_inner(&mut self, epoch: u64) -> StreamExecutorResult<()> {
-        self.managed_highest_state
-            .flush(epoch)
-            .await
-            .map_err(StreamExecutorError::top_n_state_error)?;
-        self.managed_middle_state
-            .flush(epoch)
-            .await
-            .map_err(StreamExecutorError::top_n_state_error)?;
-        self.managed_lowest_state
+        self.managed_state
             .flush(epoch)
             .await
             .map_err(StreamExecutorError::top_n_state_error)
@@ -254,22 +216,13 @@ impl<S: StateStore> TopNExecutorBase for InnerTopNExecutor<S> {
         epoch: u64,
     ) -> StreamExecutorResult<StreamChunk> {
         if self.first_execution {
-            self.managed_lowest_state
-                .fill_in_cache(epoch)
-                .await
-                .map_err(StreamExecutorError::top_n_state_error)?;
-            self.managed_middle_state
-                .fill_in_cache(epoch)
-                .await
-                .map_err(StreamExecutorError::top_n_state_error)?;
-            self.managed_highest_state
+            self.managed_state
                 .fill_in_cache(epoch)
                 .await
                 .map_err(StreamExecutorError::top_n_state_error)?;
             self.first_execution = false;
         }
 
-        let num_limit = self.limit.unwrap_or(usize::MAX);
         let mut new_ops = vec![];
         let mut new_rows = vec![];
 
@@ -278,179 +231,10 @@ impl<S: StateStore> TopNExecutorBase for InnerTopNExecutor<S> {
             let ordered_pk_row = OrderedRow::new(pk_row, &self.internal_key_order_types);
             let row = row_ref.to_owned_row();
 
-            match op {
-                Op::Insert | Op::UpdateInsert => {
-                    if self.managed_lowest_state.total_count() < self.offset {
-                        // `elem` is in the range of `[0, offset)`,
-                        // we ignored it for now as it is not in the result set.
-                        self.managed_lowest_state
-                            .insert(ordered_pk_row, row, epoch)
-                            .await
-                            .map_err(StreamExecutorError::top_n_state_error)?;
-                        continue;
-                    }
-
-                    // We remark that when offset is 0, every input row has nothing to do with
-                    // `managed_lower_state`.
-                    let element_to_compare_with_middle = if self.offset > 0
-                        && &ordered_pk_row < self.managed_lowest_state.top_element().unwrap().0
-                    {
-                        // If the new element is smaller than the largest element in [0, offset),
-                        // the largest element need to move to [offset, offset+limit).
-                        let res = self
-                            .managed_lowest_state
-                            .pop_top_element(epoch)
-                            .await
-                            .map_err(StreamExecutorError::top_n_state_error)?
-                            .unwrap();
-                        self.managed_lowest_state
-                            .insert(ordered_pk_row, row, epoch)
-                            .await
-                            .map_err(StreamExecutorError::top_n_state_error)?;
-                        res
-                    } else {
-                        (ordered_pk_row, row)
-                    };
-
-                    if self.managed_middle_state.total_count() < num_limit {
-                        // `elem` is in the range of `[offset, offset+limit)`,
-                        self.managed_middle_state
-                            .insert(
-                                element_to_compare_with_middle.0,
-                                element_to_compare_with_middle.1.clone(),
-                            )
-                            .await;
-                        new_ops.push(Op::Insert);
-                        new_rows.push(element_to_compare_with_middle.1);
-                        continue;
-                    }
-
-                    let element_to_compare_with_highest = if &element_to_compare_with_middle.0
-                        < self.managed_middle_state.top_element().unwrap().0
-                    {
-                        let res = self
-                            .managed_middle_state
-                            .pop_top_element(epoch)
-                            .await
-                            .map_err(StreamExecutorError::top_n_state_error)?
-                            .unwrap();
-                        new_ops.push(Op::Delete);
-                        new_rows.push(res.1.clone());
-                        new_ops.push(Op::Insert);
-                        new_rows.push(element_to_compare_with_middle.1.clone());
-                        self.managed_middle_state
-                            .insert(
-                                element_to_compare_with_middle.0,
-                                element_to_compare_with_middle.1,
-                            )
-                            .await;
-                        res
-                    } else {
-                        element_to_compare_with_middle
-                    };
-
-                    // `elem` is in the range of `[offset+limit, +inf)`.
-                    self.managed_highest_state
-                        .insert(
-                            element_to_compare_with_highest.0,
-                            element_to_compare_with_highest.1,
-                            epoch,
-                        )
-                        .await
-                        .map_err(StreamExecutorError::top_n_state_error)?;
-                }
-                Op::Delete | Op::UpdateDelete => {
-                    // The extra care we need to take for deletion is that when we delete an element
-                    // from a managed state, we may need to move an element from
-                    // a higher range to the current range. And this process may
-                    // be recursive. Since this is a delete operator, the key
-                    // must already exist in one of the three managed states. We
-                    // first check whether the element is in the highest state.
-                    if self.managed_middle_state.total_count() == num_limit
-                        && ordered_pk_row > *self.managed_middle_state.top_element().unwrap().0
-                    {
-                        // The current element in in the range of `[offset+limit, +inf)`
-                        self.managed_highest_state
-                            .delete(&ordered_pk_row, epoch)
-                            .await
-                            .map_err(StreamExecutorError::top_n_state_error)?;
-                    } else if self.managed_lowest_state.total_count() == self.offset
-                        && (self.offset == 0
-                            || ordered_pk_row > *self.managed_lowest_state.top_element().unwrap().0)
-                    {
-                        // The current element in in the range of `[offset, offset+limit)`
-                        self.managed_middle_state
-                            .delete(&ordered_pk_row, epoch)
-                            .await
-                            .map_err(StreamExecutorError::top_n_state_error)?;
-                        new_ops.push(Op::Delete);
-                        new_rows.push(row.clone());
-                        // We need to bring one, if any, from highest to lowest.
-                        if self.managed_highest_state.total_count() > 0 {
-                            let smallest_element_from_highest_state = self
-                                .managed_highest_state
-                                .pop_top_element(epoch)
-                                .await
-                                .map_err(StreamExecutorError::top_n_state_error)?
-                                .unwrap();
-                            new_ops.push(Op::Insert);
-                            new_rows.push(smallest_element_from_highest_state.1.clone());
-                            self.managed_middle_state
-                                .insert(
-                                    smallest_element_from_highest_state.0,
-                                    smallest_element_from_highest_state.1,
-                                )
-                                .await;
-                        }
-                    } else {
-                        // The current element in in the range of `[0, offset)`
-                        self.managed_lowest_state
-                            .delete(&ordered_pk_row, epoch)
-                            .await
-                            .map_err(StreamExecutorError::top_n_state_error)?;
-                        // We need to bring one, if any, from middle to lowest.
-                        if self.managed_middle_state.total_count() > 0 {
-                            let smallest_element_from_middle_state = self
-                                .managed_middle_state
-                                .pop_bottom_element(epoch)
-                                .await
-                                .map_err(StreamExecutorError::top_n_state_error)?
-                                .unwrap();
-                            new_ops.push(Op::Delete);
-                            new_rows.push(smallest_element_from_middle_state.1.clone());
-                            self.managed_lowest_state
-                                .insert(
-                                    smallest_element_from_middle_state.0,
-                                    smallest_element_from_middle_state.1,
-                                    epoch,
-                                )
-                                .await
-                                .map_err(StreamExecutorError::top_n_state_error)?;
-                        }
-                        // We check whether we need to/can bring one from highest to middle.
-                        // We remark that if `self.limit` is Some, it cannot be 0 as this should be
-                        // optimized away in the frontend.
-                        if self.managed_middle_state.total_count() == (num_limit - 1)
-                            && self.managed_highest_state.total_count() > 0
-                        {
-                            let smallest_element_from_highest_state = self
-                                .managed_highest_state
-                                .pop_top_element(epoch)
-                                .await
-                                .map_err(StreamExecutorError::top_n_state_error)?
-                                .unwrap();
-                            new_ops.push(Op::Insert);
-                            new_rows.push(smallest_element_from_highest_state.1.clone());
-                            self.managed_middle_state
-                                .insert(
-                                    smallest_element_from_highest_state.0,
-                                    smallest_element_from_highest_state.1,
-                                )
-                                .await;
-                        }
-                    }
-                }
-            }
+            self.managed_state
+                .apply(op, ordered_pk_row, row, epoch, &mut new_ops, &mut new_rows)
+                .await
+                .map_err(StreamExecutorError::top_n_state_error)?;
         }
         generate_output(new_rows, new_ops, &self.schema)
     }
@@ -103,6 +103,7 @@ where
                     epoch = barrier.epoch.curr;
                     yield Message::Barrier(barrier)
                 }
+                Message::Watermark(_) => {}
             };
         }
     }
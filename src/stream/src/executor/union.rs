@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use futures::StreamExt;
 use futures_async_stream::try_stream;
+use parking_lot::Mutex;
 use risingwave_common::catalog::Schema;
 use risingwave_common::util::select_all;
 
@@ -72,15 +73,25 @@ impl Executor for UnionExecutor {
 /// Merges input streams and aligns with barriers.
 pub fn merge(inputs: Vec<BoxedMessageStream>) -> BoxedMessageStream {
     let barrier = Arc::new(tokio::sync::Barrier::new(inputs.len()));
+    let buffered_watermarks = Arc::new(Mutex::new(BufferedWatermarks::new(inputs.len())));
     let mut streams = vec![];
-    for input in inputs {
+    for (input_idx, input) in inputs.into_iter().enumerate() {
         let barrier = barrier.clone();
+        let buffered_watermarks = buffered_watermarks.clone();
         let stream = #[try_stream]
         async move {
             #[for_await]
             for item in input {
                 match item? {
                     msg @ Message::Chunk(_) => yield msg,
+                    Message::Watermark(watermark) => {
+                        let watermark = buffered_watermarks
+                            .lock()
+                            .handle_watermark(input_idx, watermark);
+                        if let Some(watermark) = watermark {
+                            yield Message::Watermark(watermark);
+                        }
+                    }
                     msg @ Message::Barrier(_) => {
                         if barrier.wait().await.is_leader() {
                             // one leader is responsible for sending barrier
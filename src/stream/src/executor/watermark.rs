@@ -0,0 +1,249 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use risingwave_common::array::Row;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{
+    deserialize_datum_from, serialize_datum_into, DataType, ScalarImpl,
+};
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_pb::data::Watermark as ProstWatermark;
+use risingwave_storage::write_batch::WriteBatch;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::error::{StreamExecutorError, StreamExecutorResult};
+
+/// A watermark on the `col_idx`-th column promises that no rows with a smaller value on the column
+/// will arrive after it. Stateful executors use it to clean the state that can no longer change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watermark {
+    pub col_idx: usize,
+    pub data_type: DataType,
+    pub val: ScalarImpl,
+}
+
+impl Watermark {
+    pub fn new(col_idx: usize, data_type: DataType, val: ScalarImpl) -> Self {
+        Self {
+            col_idx,
+            data_type,
+            val,
+        }
+    }
+
+    /// Move the watermark to the `idx`-th column.
+    pub fn with_idx(self, idx: usize) -> Self {
+        Self {
+            col_idx: idx,
+            ..self
+        }
+    }
+
+    /// Derive the watermark on the `new_col_idx`-th column, which is computed by `expr`. The
+    /// `expr` must only depend on the watermark column and be monotonically non-decreasing on it.
+    ///
+    /// Returns `None` if the expression evaluates to null.
+    pub fn transform_with_expr(
+        self,
+        expr: &BoxedExpression,
+        new_col_idx: usize,
+    ) -> StreamExecutorResult<Option<Self>> {
+        let mut datums = vec![None; self.col_idx + 1];
+        datums[self.col_idx] = Some(self.val);
+        let val = expr
+            .eval_row(&Row(datums))
+            .map_err(StreamExecutorError::eval_error)?;
+        Ok(val.map(|val| Self::new(new_col_idx, expr.return_type(), val)))
+    }
+
+    pub fn to_protobuf(&self) -> Result<ProstWatermark> {
+        let mut serializer = memcomparable::Serializer::new(vec![]);
+        serialize_datum_into(&Some(self.val.clone()), &mut serializer)?;
+        Ok(ProstWatermark {
+            col_idx: self.col_idx as u32,
+            data_type: Some(self.data_type.to_protobuf()),
+            val: serializer.into_inner(),
+        })
+    }
+
+    pub fn from_protobuf(prost: &ProstWatermark) -> Result<Self> {
+        let data_type = DataType::from(prost.get_data_type()?);
+        let mut deserializer = memcomparable::Deserializer::new(prost.val.as_slice());
+        let val = deserialize_datum_from(&data_type, &mut deserializer)?.ok_or_else(|| {
+            RwError::from(ErrorCode::InternalError(
+                "watermark value must not be null".to_string(),
+            ))
+        })?;
+        Ok(Self::new(prost.col_idx as usize, data_type, val))
+    }
+}
+
+/// Aligns the watermarks from multiple upstreams. The watermark on a column only advances when the
+/// minimum of the latest watermarks from all upstreams advances.
+#[derive(Debug)]
+pub struct BufferedWatermarks {
+    upstream_cnt: usize,
+    /// The latest watermark from each upstream, grouped by the column.
+    latest: HashMap<usize, HashMap<usize, ScalarImpl>>,
+    /// The last emitted watermark on each column.
+    emitted: HashMap<usize, ScalarImpl>,
+}
+
+impl BufferedWatermarks {
+    pub fn new(upstream_cnt: usize) -> Self {
+        Self {
+            upstream_cnt,
+            latest: HashMap::new(),
+            emitted: HashMap::new(),
+        }
+    }
+
+    /// Handle the watermark from the `upstream_idx`-th upstream. Returns the watermark to emit if
+    /// the aligned watermark on the column advances.
+    pub fn handle_watermark(
+        &mut self,
+        upstream_idx: usize,
+        watermark: Watermark,
+    ) -> Option<Watermark> {
+        let latest = self.latest.entry(watermark.col_idx).or_default();
+        latest.insert(upstream_idx, watermark.val.clone());
+        if latest.len() < self.upstream_cnt {
+            return None;
+        }
+        let min = latest.values().min().unwrap().clone();
+        match self.emitted.get(&watermark.col_idx) {
+            Some(emitted) if emitted >= &min => None,
+            _ => {
+                self.emitted.insert(watermark.col_idx, min.clone());
+                Some(Watermark {
+                    val: min,
+                    ..watermark
+                })
+            }
+        }
+    }
+
+    /// Update the set of upstreams after `added_cnt` upstreams are added and the `removed` ones are
+    /// removed. The watermarks from the removed upstreams no longer hold the aligned ones back.
+    pub fn update_upstreams(&mut self, added_cnt: usize, removed: &[usize]) {
+        self.upstream_cnt = self.upstream_cnt + added_cnt - removed.len();
+        for latest in self.latest.values_mut() {
+            for upstream_idx in removed {
+                latest.remove(upstream_idx);
+            }
+        }
+    }
+}
+
+/// Delete the entries in `keyspace` whose keys start with a memcomparable-encoded datum below the
+/// `watermark`, and `descending` tells whether the datum is encoded in descending order. The whole
+/// keyspace is scanned, so this should only be called when the watermark advances.
+pub async fn delete_below_watermark<S: StateStore>(
+    keyspace: &Keyspace<S>,
+    write_batch: &mut WriteBatch<S>,
+    watermark: &Watermark,
+    descending: bool,
+    epoch: u64,
+) -> StreamExecutorResult<()> {
+    let pairs = keyspace
+        .scan(None, epoch)
+        .await
+        .map_err(StreamExecutorError::storage)?;
+    let mut local = write_batch.prefixify(keyspace);
+    for (key, _) in pairs {
+        let mut deserializer = memcomparable::Deserializer::new(key.as_ref());
+        deserializer.set_reverse(descending);
+        let datum = deserialize_datum_from(&watermark.data_type, &mut deserializer)
+            .map_err(StreamExecutorError::eval_error)?;
+        if let Some(val) = datum && val < watermark.val {
+            local.delete(key);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::{IntervalUnit, NaiveDateTimeWrapper};
+    use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
+    use risingwave_pb::expr::expr_node::Type;
+
+    use super::*;
+
+    fn int64_watermark(val: i64) -> Watermark {
+        Watermark::new(1, DataType::Int64, ScalarImpl::Int64(val))
+    }
+
+    #[test]
+    fn test_watermark_protobuf() {
+        let watermark = Watermark::new(
+            2,
+            DataType::Timestamp,
+            ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper::from_protobuf(1_000_000).unwrap()),
+        );
+        let prost = watermark.to_protobuf().unwrap();
+        assert_eq!(Watermark::from_protobuf(&prost).unwrap(), watermark);
+    }
+
+    #[test]
+    fn test_transform_with_expr() {
+        // $1 - INTERVAL '1' DAY
+        let expr = new_binary_expr(
+            Type::Subtract,
+            DataType::Timestamp,
+            Box::new(InputRefExpression::new(DataType::Timestamp, 1)),
+            Box::new(LiteralExpression::new(
+                DataType::Interval,
+                Some(ScalarImpl::Interval(IntervalUnit::from_days(1))),
+            )),
+        );
+        let ts = |secs| {
+            ScalarImpl::NaiveDateTime(
+                NaiveDateTimeWrapper::from_protobuf(secs * 1_000_000).unwrap(),
+            )
+        };
+        let watermark = Watermark::new(1, DataType::Timestamp, ts(2 * 86400));
+        let transformed = watermark.transform_with_expr(&expr, 0).unwrap().unwrap();
+        assert_eq!(
+            transformed,
+            Watermark::new(0, DataType::Timestamp, ts(86400))
+        );
+    }
+
+    #[test]
+    fn test_buffered_watermarks() {
+        let mut buffered = BufferedWatermarks::new(2);
+        // Wait for all upstreams.
+        assert_eq!(buffered.handle_watermark(0, int64_watermark(10)), None);
+        assert_eq!(
+            buffered.handle_watermark(1, int64_watermark(5)),
+            Some(int64_watermark(5))
+        );
+        // The minimum does not advance.
+        assert_eq!(buffered.handle_watermark(1, int64_watermark(5)), None);
+        assert_eq!(buffered.handle_watermark(0, int64_watermark(20)), None);
+        assert_eq!(
+            buffered.handle_watermark(1, int64_watermark(30)),
+            Some(int64_watermark(20))
+        );
+        // Watermarks on other columns are aligned separately.
+        assert_eq!(
+            buffered.handle_watermark(0, int64_watermark(1).with_idx(0)),
+            None
+        );
+    }
+}
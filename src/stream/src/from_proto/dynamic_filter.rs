@@ -0,0 +1,60 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::catalog::TableId;
+use risingwave_pb::expr::expr_node::Type as ExprType;
+
+use super::*;
+use crate::executor::{Comparator, DynamicFilterExecutor};
+
+pub struct DynamicFilterExecutorBuilder;
+
+impl ExecutorBuilder for DynamicFilterExecutorBuilder {
+    fn new_boxed_executor(
+        mut params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::DynamicFilter)?;
+        let source_r = params.input.remove(1);
+        let source_l = params.input.remove(0);
+        let comparator = match node.get_comparator()? {
+            ExprType::LessThan => Comparator::LessThan,
+            ExprType::LessThanOrEqual => Comparator::LessThanOrEqual,
+            ExprType::GreaterThan => Comparator::GreaterThan,
+            ExprType::GreaterThanOrEqual => Comparator::GreaterThanOrEqual,
+            comparator => {
+                return Err(RwError::from(ErrorCode::InternalError(format!(
+                    "invalid comparator of dynamic filter: {:?}",
+                    comparator
+                ))))
+            }
+        };
+        let keyspace_l = Keyspace::table_root(store.clone(), &TableId::new(node.left_table_id));
+        let keyspace_r = Keyspace::table_root(store, &TableId::new(node.right_table_id));
+
+        Ok(DynamicFilterExecutor::new(
+            source_l,
+            source_r,
+            node.left_key as usize,
+            comparator,
+            keyspace_l,
+            keyspace_r,
+            params.pk_indices,
+            params.executor_id,
+        )
+        .boxed())
+    }
+}
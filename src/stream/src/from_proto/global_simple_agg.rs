@@ -17,7 +17,7 @@
 use risingwave_common::catalog::TableId;
 
 use super::*;
-use crate::executor::aggregation::AggCall;
+use crate::executor::aggregation::{build_distinct_dedup_keyspaces, AggCall};
 use crate::executor::SimpleAggExecutor;
 
 pub struct SimpleAggExecutorBuilder;
@@ -42,6 +42,8 @@ impl ExecutorBuilder for SimpleAggExecutorBuilder {
             .iter()
             .map(|table_id| Keyspace::table_root(store.clone(), &TableId::new(*table_id)))
             .collect();
+        let distinct_dedup_keyspaces =
+            build_distinct_dedup_keyspaces(&store, agg_calls.len(), &node.distinct_dedup_table_ids);
         let key_indices = node
             .get_distribution_keys()
             .iter()
@@ -52,6 +54,7 @@ impl ExecutorBuilder for SimpleAggExecutorBuilder {
             params.input.remove(0),
             agg_calls,
             keyspace,
+            distinct_dedup_keyspaces,
             params.pk_indices,
             params.executor_id,
             key_indices,
@@ -20,8 +20,9 @@ use risingwave_common::catalog::TableId;
 use risingwave_common::hash::{calc_hash_key_kind, HashKey, HashKeyDispatcher};
 
 use super::*;
-use crate::executor::aggregation::AggCall;
+use crate::executor::aggregation::{build_distinct_dedup_keyspaces, AggCall};
 use crate::executor::{HashAggExecutor, PkIndices};
+use crate::task::ActorId;
 
 struct HashAggExecutorDispatcher<S: StateStore>(PhantomData<S>);
 
@@ -30,7 +31,9 @@ struct HashAggExecutorDispatcherArgs<S: StateStore> {
     agg_calls: Vec<AggCall>,
     key_indices: Vec<usize>,
     keyspace: Vec<Keyspace<S>>,
+    distinct_dedup_keyspaces: Vec<Option<Keyspace<S>>>,
     pk_indices: PkIndices,
+    actor_id: ActorId,
     executor_id: u64,
 }
 
@@ -43,7 +46,9 @@ impl<S: StateStore> HashKeyDispatcher for HashAggExecutorDispatcher<S> {
             args.input,
             args.agg_calls,
             args.keyspace,
+            args.distinct_dedup_keyspaces,
             args.pk_indices,
+            args.actor_id,
             args.executor_id,
             args.key_indices,
         )?
@@ -78,6 +83,8 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
             .iter()
             .map(|table_id| Keyspace::table_root(store.clone(), &TableId::new(*table_id)))
             .collect();
+        let distinct_dedup_keyspaces =
+            build_distinct_dedup_keyspaces(&store, agg_calls.len(), &node.distinct_dedup_table_ids);
         let input = params.input.remove(0);
         let keys = key_indices
             .iter()
@@ -89,7 +96,9 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
             agg_calls,
             key_indices,
             keyspace,
+            distinct_dedup_keyspaces,
             pk_indices: params.pk_indices,
+            actor_id: params.actor_id,
             executor_id: params.executor_id,
         };
         HashAggExecutorDispatcher::dispatch_by_kind(kind, args)
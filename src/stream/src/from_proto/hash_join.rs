@@ -22,6 +22,7 @@ use risingwave_pb::plan_common::JoinType as JoinTypeProto;
 use super::*;
 use crate::executor::hash_join::*;
 use crate::executor::PkIndices;
+use crate::task::ActorId;
 
 pub struct HashJoinExecutorBuilder;
 
@@ -108,6 +109,7 @@ impl ExecutorBuilder for HashJoinExecutorBuilder {
             params_l,
             params_r,
             pk_indices: params.pk_indices,
+            actor_id: params.actor_id,
             executor_id: params.executor_id,
             cond: condition,
             op_info: params.op_info,
@@ -131,6 +133,7 @@ struct HashJoinExecutorDispatcherArgs<S: StateStore> {
     params_l: JoinParams,
     params_r: JoinParams,
     pk_indices: PkIndices,
+    actor_id: ActorId,
     executor_id: u64,
     cond: Option<RowExpression>,
     op_info: String,
@@ -153,6 +156,7 @@ impl<S: StateStore, const T: JoinTypePrimitive> HashKeyDispatcher
             args.params_l,
             args.params_r,
             args.pk_indices,
+            args.actor_id,
             args.executor_id,
             args.cond,
             args.op_info,
@@ -64,10 +64,14 @@ pub fn build_agg_call_from_prost(
             )))
         }
     };
+    let kind = AggKind::try_from(agg_call_proto.get_type()?)?;
+    // `DISTINCT` makes no difference to `min` and `max`.
+    let distinct = agg_call_proto.distinct && !matches!(kind, AggKind::Min | AggKind::Max);
     Ok(AggCall {
-        kind: AggKind::try_from(agg_call_proto.get_type()?)?,
+        kind,
         args,
         return_type: DataType::from(agg_call_proto.get_return_type()?),
         append_only,
+        distinct,
     })
 }
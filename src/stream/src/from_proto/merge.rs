@@ -48,7 +48,8 @@ impl ExecutorBuilder for MergeExecutorBuilder {
                 schema,
                 params.pk_indices,
                 params.actor_id,
-                rxs,
+                upstreams.iter().copied().zip_eq(rxs).collect(),
+                stream.context.clone(),
                 actor_context,
                 x_node.operator_id,
             )
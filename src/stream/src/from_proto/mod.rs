@@ -16,17 +16,22 @@
 
 mod batch_query;
 mod chain;
+mod dynamic_filter;
 mod filter;
 mod global_simple_agg;
+mod group_top_n;
 mod hash_agg;
 mod hash_join;
 mod hop_window;
+mod lateral_table_function;
 mod local_simple_agg;
 mod lookup;
 mod lookup_union;
 mod merge;
 mod mview;
+mod over_agg;
 mod project;
+mod sink;
 mod source;
 mod top_n;
 mod top_n_appendonly;
@@ -42,17 +47,22 @@ use risingwave_storage::{Keyspace, StateStore};
 
 use self::batch_query::*;
 use self::chain::*;
+use self::dynamic_filter::*;
 use self::filter::*;
 use self::global_simple_agg::*;
+use self::group_top_n::*;
 use self::hash_agg::*;
 use self::hash_join::*;
 use self::hop_window::*;
+use self::lateral_table_function::*;
 use self::local_simple_agg::*;
 use self::lookup::*;
 use self::lookup_union::*;
 use self::merge::*;
 use self::mview::*;
+use self::over_agg::*;
 use self::project::*;
+use self::sink::*;
 use self::source::*;
 use self::top_n::*;
 use self::top_n_appendonly::*;
@@ -104,11 +114,15 @@ pub fn create_executor(
         NodeBody::Project => ProjectExecutorBuilder,
         NodeBody::TopN => TopNExecutorBuilder,
         NodeBody::AppendOnlyTopN => AppendOnlyTopNExecutorBuilder,
+        NodeBody::GroupTopN => GroupTopNExecutorBuilder,
         NodeBody::LocalSimpleAgg => LocalSimpleAggExecutorBuilder,
         NodeBody::GlobalSimpleAgg => SimpleAggExecutorBuilder,
         NodeBody::HashAgg => HashAggExecutorBuilder,
         NodeBody::HashJoin => HashJoinExecutorBuilder,
         NodeBody::HopWindow => HopWindowExecutorBuilder,
+        NodeBody::LateralTableFunction => LateralTableFunctionExecutorBuilder,
+        NodeBody::OverAgg => OverAggExecutorBuilder,
+        NodeBody::DynamicFilter => DynamicFilterExecutorBuilder,
         NodeBody::Chain => ChainExecutorBuilder,
         NodeBody::BatchPlan => BatchQueryExecutorBuilder,
         NodeBody::Merge => MergeExecutorBuilder,
@@ -118,5 +132,6 @@ pub fn create_executor(
         NodeBody::Lookup => LookupExecutorBuilder,
         NodeBody::Union => UnionExecutorBuilder,
         NodeBody::LookupUnion => LookupUnionExecutorBuilder,
+        NodeBody::Sink => SinkExecutorBuilder,
     }
 }
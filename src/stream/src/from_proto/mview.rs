@@ -51,6 +51,7 @@ impl ExecutorBuilder for MaterializeExecutorBuilder {
 
         let executor = MaterializeExecutor::new(
             params.input.remove(0),
+            table_id,
             keyspace,
             keys,
             column_ids,
@@ -73,7 +74,8 @@ impl ExecutorBuilder for ArrangeExecutorBuilder {
     ) -> Result<BoxedExecutor> {
         let arrange_node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::Arrange)?;
 
-        let keyspace = Keyspace::table_root(store, &TableId::from(arrange_node.table_id));
+        let table_id = TableId::from(arrange_node.table_id);
+        let keyspace = Keyspace::table_root(store, &table_id);
 
         let keys = arrange_node
             .get_table_info()?
@@ -97,6 +99,7 @@ impl ExecutorBuilder for ArrangeExecutorBuilder {
 
         let executor = MaterializeExecutor::new(
             params.input.remove(0),
+            table_id,
             keyspace,
             keys,
             column_ids,
@@ -0,0 +1,61 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::catalog::TableId;
+use risingwave_common::util::sort_util::OrderPair;
+use risingwave_expr::window_function::WindowFuncCall;
+
+use super::*;
+use crate::executor::OverAggExecutor;
+
+pub struct OverAggExecutorBuilder;
+
+impl ExecutorBuilder for OverAggExecutorBuilder {
+    fn new_boxed_executor(
+        mut params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::OverAgg)?;
+        let calls: Vec<_> = node
+            .get_window_functions()
+            .iter()
+            .map(WindowFuncCall::from_protobuf)
+            .try_collect()?;
+        let partition_by = node
+            .get_partition_by()
+            .iter()
+            .map(|idx| *idx as usize)
+            .collect_vec();
+        let order_by = node
+            .get_order_by()
+            .iter()
+            .map(OrderPair::from_prost)
+            .collect_vec();
+        let keyspace = Keyspace::table_root(store, &TableId::new(node.table_id));
+
+        Ok(OverAggExecutor::new(
+            params.input.remove(0),
+            calls,
+            partition_by,
+            order_by,
+            keyspace,
+            params.pk_indices,
+            params.actor_id,
+            params.executor_id,
+        )
+        .boxed())
+    }
+}
@@ -32,12 +32,19 @@ impl ExecutorBuilder for ProjectExecutorBuilder {
             .iter()
             .map(build_from_prost)
             .collect::<Result<Vec<_>>>()?;
+        let watermark_derivations = node
+            .get_watermark_input_key()
+            .iter()
+            .zip_eq(node.get_watermark_output_key())
+            .map(|(&input_idx, &output_idx)| (input_idx as usize, output_idx as usize))
+            .collect();
 
         Ok(ProjectExecutor::new(
             params.input.remove(0),
             params.pk_indices,
             project_exprs,
             params.executor_id,
+            watermark_derivations,
         )
         .boxed())
     }
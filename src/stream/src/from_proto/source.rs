@@ -15,6 +15,7 @@
 use risingwave_common::catalog::{ColumnId, Field, Schema, TableId};
 use risingwave_common::error::ToRwResult;
 use risingwave_connector::SplitImpl;
+use risingwave_expr::expr::build_from_prost;
 use tokio::sync::mpsc::unbounded_channel;
 
 use super::*;
@@ -65,6 +66,16 @@ impl ExecutorBuilder for SourceExecutorBuilder {
         }));
         let schema = Schema::new(fields);
         let keyspace = Keyspace::executor_root(store, params.executor_id);
+        let watermark_descs = node
+            .get_watermark_descs()
+            .iter()
+            .map(|desc| {
+                Ok((
+                    desc.watermark_idx as usize,
+                    build_from_prost(desc.get_expr()?)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Box::new(SourceExecutor::new(
             source_id,
@@ -80,6 +91,7 @@ impl ExecutorBuilder for SourceExecutorBuilder {
             params.executor_stats,
             stream_source_splits,
             stream.config.checkpoint_interval_ms as u64,
+            watermark_descs,
         )?))
     }
 }
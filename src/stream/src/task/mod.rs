@@ -19,6 +19,7 @@ use madsim::collections::HashMap;
 use parking_lot::{Mutex, MutexGuard};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::util::addr::HostAddr;
+use risingwave_rpc_client::ComputeClientPool;
 
 use crate::executor::Message;
 
@@ -68,6 +69,12 @@ pub struct SharedContext {
     pub(crate) addr: HostAddr,
 
     pub(crate) barrier_manager: Arc<Mutex<LocalBarrierManager>>,
+
+    /// The pool of compute clients, used to receive messages from remote upstream actors.
+    ///
+    /// TODO: currently the client pool won't be cleared. Should remove compute clients when
+    /// disconnected.
+    pub(crate) compute_client_pool: ComputeClientPool,
 }
 
 impl std::fmt::Debug for SharedContext {
@@ -84,6 +91,7 @@ impl SharedContext {
             channel_map: Mutex::new(HashMap::new()),
             addr,
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new())),
+            compute_client_pool: ComputeClientPool::new(u64::MAX),
         }
     }
 
@@ -93,6 +101,7 @@ impl SharedContext {
             channel_map: Mutex::new(HashMap::new()),
             addr: LOCAL_TEST_ADDR.clone(),
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::for_test())),
+            compute_client_pool: ComputeClientPool::new(u64::MAX),
         }
     }
 
@@ -22,19 +22,18 @@ use parking_lot::Mutex;
 use risingwave_common::config::StreamingConfig;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::try_match_expand;
-use risingwave_common::util::addr::{is_local_address, HostAddr};
+use risingwave_common::util::addr::HostAddr;
 use risingwave_common::util::compress::decompress_data;
 use risingwave_pb::common::ActorInfo;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
 use risingwave_pb::{stream_plan, stream_service};
-use risingwave_rpc_client::ComputeClientPool;
 use risingwave_storage::{dispatch_state_store, StateStore, StateStoreImpl};
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
 use super::{unique_executor_id, unique_operator_id, CollectResult};
 use crate::executor::dispatch::*;
-use crate::executor::merge::RemoteInput;
+use crate::executor::merge::new_input;
 use crate::executor::monitor::StreamingMetrics;
 use crate::executor::*;
 use crate::from_proto::create_executor;
@@ -74,12 +73,6 @@ pub struct LocalStreamManagerCore {
     /// Metrics of the stream manager
     streaming_metrics: Arc<StreamingMetrics>,
 
-    /// The pool of compute clients.
-    ///
-    /// TODO: currently the client pool won't be cleared. Should remove compute clients when
-    /// disconnected.
-    compute_client_pool: ComputeClientPool,
-
     /// Config of streaming engine
     pub(crate) config: StreamingConfig,
 }
@@ -354,7 +347,6 @@ impl LocalStreamManagerCore {
             mock_source: (Some(tx), Some(rx)),
             state_store,
             streaming_metrics,
-            compute_client_pool: ComputeClientPool::new(u64::MAX),
             config,
         }
     }
@@ -548,35 +540,7 @@ impl LocalStreamManagerCore {
                     Ok(self.mock_source.1.take().unwrap())
                 } else {
                     let upstream_addr = self.get_actor_info(up_id)?.get_host()?.into();
-                    if !is_local_address(&upstream_addr, &self.context.addr) {
-                        // Get the sender for `RemoteInput` to forward received messages to
-                        // receivers in `ReceiverExecutor` or
-                        // `MergerExecutor`.
-                        let sender = self.context.take_sender(&(*up_id, actor_id))?;
-                        // spawn the `RemoteInput`
-                        let up_id = *up_id;
-
-                        let pool = self.compute_client_pool.clone();
-
-                        tokio::spawn(async move {
-                            let init_client = async move {
-                                let remote_input = RemoteInput::create(
-                                    pool.get_client_for_addr(upstream_addr).await?,
-                                    (up_id, actor_id),
-                                    sender,
-                                )
-                                .await?;
-                                Ok::<_, RwError>(remote_input)
-                            };
-                            match init_client.await {
-                                Ok(remote_input) => remote_input.run().await,
-                                Err(e) => {
-                                    error!("Spawn remote input fails:{}", e);
-                                }
-                            }
-                        });
-                    }
-                    Ok::<_, RwError>(self.context.take_receiver(&(*up_id, actor_id))?)
+                    new_input(&self.context, upstream_addr, *up_id, actor_id)
                 }
             })
             .collect::<Result<Vec<_>>>()?;
@@ -0,0 +1,448 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The text and CSV formats of the data transferred by `COPY`.
+
+use std::io::{Error, ErrorKind, Result};
+
+use bytes::{BufMut, BytesMut};
+
+use crate::types::Row;
+
+/// The format of the data transferred by `COPY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    Text,
+    Csv,
+}
+
+/// The options of `COPY` deciding how rows are encoded and decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyOptions {
+    pub format: CopyFormat,
+    /// The single-byte character separating the values in a line.
+    pub delimiter: u8,
+    /// The string representing a `NULL` value.
+    pub null: String,
+    /// Whether the first line holds the column names rather than data.
+    pub header: bool,
+}
+
+impl CopyOptions {
+    /// Returns the default options of `format`, the same as PG.
+    pub fn new(format: CopyFormat) -> Self {
+        match format {
+            CopyFormat::Text => Self {
+                format,
+                delimiter: b'\t',
+                null: "\\N".to_string(),
+                header: false,
+            },
+            CopyFormat::Csv => Self {
+                format,
+                delimiter: b',',
+                null: String::new(),
+                header: false,
+            },
+        }
+    }
+
+    /// Encodes `values` as a line and appends it to `buf`.
+    pub fn encode_row(&self, values: &[Option<String>], buf: &mut BytesMut) {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                buf.put_u8(self.delimiter);
+            }
+            match value {
+                None => buf.put_slice(self.null.as_bytes()),
+                Some(value) => match self.format {
+                    CopyFormat::Text => self.encode_text_value(value, buf),
+                    CopyFormat::Csv => self.encode_csv_value(value, buf),
+                },
+            }
+        }
+        buf.put_u8(b'\n');
+    }
+
+    fn encode_text_value(&self, value: &str, buf: &mut BytesMut) {
+        for &b in value.as_bytes() {
+            match b {
+                b'\\' => buf.put_slice(b"\\\\"),
+                b'\n' => buf.put_slice(b"\\n"),
+                b'\r' => buf.put_slice(b"\\r"),
+                b'\t' => buf.put_slice(b"\\t"),
+                b if b == self.delimiter => buf.put_slice(&[b'\\', b]),
+                b => buf.put_u8(b),
+            }
+        }
+    }
+
+    fn encode_csv_value(&self, value: &str, buf: &mut BytesMut) {
+        // A value is quoted if it could otherwise be mistaken for `NULL` or break the line.
+        let need_quote = value == self.null
+            || value == "\\."
+            || value
+                .bytes()
+                .any(|b| matches!(b, b'"' | b'\n' | b'\r') || b == self.delimiter);
+        if !need_quote {
+            buf.put_slice(value.as_bytes());
+            return;
+        }
+        buf.put_u8(b'"');
+        for &b in value.as_bytes() {
+            if b == b'"' {
+                buf.put_u8(b'"');
+            }
+            buf.put_u8(b);
+        }
+        buf.put_u8(b'"');
+    }
+}
+
+/// Decodes the data sent by `COPY ... FROM STDIN` into rows. The data may be split at any byte,
+/// so the incomplete line at the end is kept until more data arrives.
+pub struct CopyDecoder {
+    options: CopyOptions,
+    /// The data not decoded yet.
+    buf: Vec<u8>,
+    /// The position in `buf` where the scan for the end of the first line resumes, so that a line
+    /// split across many chunks is scanned only once.
+    scan_pos: usize,
+    /// Whether the scan is inside a quoted CSV value at `scan_pos`.
+    in_quotes: bool,
+    /// Whether the header line is yet to be skipped.
+    skip_header: bool,
+    /// Whether the end-of-data marker `\.` has been seen, after which the data is ignored.
+    end_of_data: bool,
+}
+
+impl CopyDecoder {
+    pub fn new(options: CopyOptions) -> Self {
+        Self {
+            skip_header: options.header,
+            options,
+            buf: vec![],
+            scan_pos: 0,
+            in_quotes: false,
+            end_of_data: false,
+        }
+    }
+
+    /// Appends `data` and decodes all the complete lines.
+    pub fn decode(&mut self, data: &[u8]) -> Result<Vec<Row>> {
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.extend_from_slice(data);
+
+        let mut rows = vec![];
+        let mut start = 0;
+        while let Some(end) = self.find_line_end(&buf) {
+            self.decode_line(&buf[start..end], &mut rows)?;
+            start = end + 1;
+        }
+        buf.drain(..start);
+        self.scan_pos -= start;
+        self.buf = buf;
+        Ok(rows)
+    }
+
+    /// Decodes the last line, which may not end with a newline, after all data is received.
+    pub fn finish(mut self) -> Result<Vec<Row>> {
+        let buf = std::mem::take(&mut self.buf);
+        let mut rows = vec![];
+        if !buf.is_empty() {
+            self.decode_line(&buf, &mut rows)?;
+        }
+        Ok(rows)
+    }
+
+    /// Returns the position of the newline ending the line being scanned in `buf`, resuming from
+    /// `scan_pos`. In CSV, newlines in quoted values do not end the line.
+    fn find_line_end(&mut self, buf: &[u8]) -> Option<usize> {
+        let data = &buf[self.scan_pos..];
+        let pos = match self.options.format {
+            CopyFormat::Text => data.iter().position(|&b| b == b'\n'),
+            CopyFormat::Csv => {
+                let in_quotes = &mut self.in_quotes;
+                data.iter().position(|&b| {
+                    if b == b'"' {
+                        *in_quotes = !*in_quotes;
+                    }
+                    b == b'\n' && !*in_quotes
+                })
+            }
+        };
+        match pos {
+            Some(pos) => {
+                let end = self.scan_pos + pos;
+                self.scan_pos = end + 1;
+                Some(end)
+            }
+            None => {
+                self.scan_pos = buf.len();
+                None
+            }
+        }
+    }
+
+    fn decode_line(&mut self, line: &[u8], rows: &mut Vec<Row>) -> Result<()> {
+        if self.end_of_data {
+            return Ok(());
+        }
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line == b"\\." {
+            self.end_of_data = true;
+            return Ok(());
+        }
+        if self.skip_header {
+            self.skip_header = false;
+            return Ok(());
+        }
+        let values = match self.options.format {
+            CopyFormat::Text => self.decode_text_line(line)?,
+            CopyFormat::Csv => self.decode_csv_line(line)?,
+        };
+        rows.push(Row::new(values));
+        Ok(())
+    }
+
+    fn decode_text_line(&self, line: &[u8]) -> Result<Vec<Option<String>>> {
+        let mut values = vec![];
+        let mut value = vec![];
+        let mut value_start = 0;
+        let mut i = 0;
+        while i < line.len() {
+            match line[i] {
+                b'\\' if i + 1 < line.len() => {
+                    i += 1;
+                    match line[i] {
+                        b'b' => value.push(8),
+                        b'f' => value.push(12),
+                        b'n' => value.push(b'\n'),
+                        b'r' => value.push(b'\r'),
+                        b't' => value.push(b'\t'),
+                        b'v' => value.push(11),
+                        b'0'..=b'7' => {
+                            let digits = take_digits(&line[i..], 3, 8);
+                            value.push(parse_digits(&line[i..i + digits], 8));
+                            i += digits - 1;
+                        }
+                        b'x' if take_digits(&line[i + 1..], 2, 16) > 0 => {
+                            let digits = take_digits(&line[i + 1..], 2, 16);
+                            value.push(parse_digits(&line[i + 1..i + 1 + digits], 16));
+                            i += digits;
+                        }
+                        // Any other escaped character, including the delimiter, is taken as is.
+                        b => value.push(b),
+                    }
+                }
+                b if b == self.options.delimiter => {
+                    values.push(self.text_value(&line[value_start..i], &mut value)?);
+                    value_start = i + 1;
+                }
+                b => value.push(b),
+            }
+            i += 1;
+        }
+        values.push(self.text_value(&line[value_start..], &mut value)?);
+        Ok(values)
+    }
+
+    /// Takes the unescaped `value`, or `None` if its `raw` form is the null string.
+    fn text_value(&self, raw: &[u8], value: &mut Vec<u8>) -> Result<Option<String>> {
+        let value = std::mem::take(value);
+        if raw == self.options.null.as_bytes() {
+            return Ok(None);
+        }
+        to_string(value).map(Some)
+    }
+
+    fn decode_csv_line(&self, line: &[u8]) -> Result<Vec<Option<String>>> {
+        let mut values = vec![];
+        let mut value = vec![];
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut i = 0;
+        while i < line.len() {
+            let b = line[i];
+            if in_quotes {
+                if b != b'"' {
+                    value.push(b);
+                } else if line.get(i + 1) == Some(&b'"') {
+                    value.push(b'"');
+                    i += 1;
+                } else {
+                    in_quotes = false;
+                }
+            } else if b == b'"' {
+                in_quotes = true;
+                quoted = true;
+            } else if b == self.options.delimiter {
+                values.push(self.csv_value(&mut value, quoted)?);
+                quoted = false;
+            } else {
+                value.push(b);
+            }
+            i += 1;
+        }
+        if in_quotes {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "unterminated CSV quoted field",
+            ));
+        }
+        values.push(self.csv_value(&mut value, quoted)?);
+        Ok(values)
+    }
+
+    /// Takes `value`, or `None` if it is the null string without any quotes.
+    fn csv_value(&self, value: &mut Vec<u8>, quoted: bool) -> Result<Option<String>> {
+        let value = std::mem::take(value);
+        if !quoted && value == self.options.null.as_bytes() {
+            return Ok(None);
+        }
+        to_string(value).map(Some)
+    }
+}
+
+/// Returns the number of leading digits in `radix`, at most `max`.
+fn take_digits(data: &[u8], max: usize, radix: u32) -> usize {
+    data.iter()
+        .take(max)
+        .take_while(|&&b| (b as char).is_digit(radix))
+        .count()
+}
+
+fn parse_digits(digits: &[u8], radix: u32) -> u8 {
+    digits.iter().fold(0u8, |acc, &b| {
+        acc.wrapping_mul(radix as u8)
+            .wrapping_add((b as char).to_digit(radix).unwrap() as u8)
+    })
+}
+
+fn to_string(value: Vec<u8>) -> Result<String> {
+    String::from_utf8(value).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            "invalid byte sequence for encoding \"UTF8\"",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(options: CopyOptions, chunks: &[&[u8]]) -> Vec<Vec<Option<String>>> {
+        let mut decoder = CopyDecoder::new(options);
+        let mut rows = vec![];
+        for chunk in chunks {
+            rows.extend(decoder.decode(chunk).unwrap());
+        }
+        rows.extend(decoder.finish().unwrap());
+        rows.into_iter().map(|row| row.values().to_vec()).collect()
+    }
+
+    fn values(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|v| v.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let options = CopyOptions::new(CopyFormat::Text);
+        let rows = vec![
+            values(&[Some("1"), Some("a\tb\\c"), None]),
+            values(&[Some("2"), Some("line\nbreak"), Some("")]),
+        ];
+        let mut buf = BytesMut::new();
+        for row in &rows {
+            options.encode_row(row, &mut buf);
+        }
+        assert_eq!(
+            &buf[..],
+            b"1\ta\\tb\\\\c\t\\N\n2\tline\\nbreak\t\n".as_slice()
+        );
+
+        // The data may be split anywhere.
+        let (left, right) = buf.split_at(7);
+        assert_eq!(decode_all(options, &[left, right]), rows);
+    }
+
+    #[test]
+    fn test_text_decode() {
+        let options = CopyOptions::new(CopyFormat::Text);
+        assert_eq!(
+            decode_all(
+                options.clone(),
+                &[b"\\101\\x42\\C\t\\N\r\n3\t4\n\\.\nignored"]
+            ),
+            vec![
+                values(&[Some("ABC"), None]),
+                values(&[Some("3"), Some("4")])
+            ]
+        );
+
+        let options = CopyOptions {
+            delimiter: b'|',
+            null: "nil".to_string(),
+            ..options
+        };
+        assert_eq!(
+            decode_all(options, &[b"a\\|b|nil|"]),
+            vec![values(&[Some("a|b"), None, Some("")])]
+        );
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let options = CopyOptions::new(CopyFormat::Csv);
+        let rows = vec![
+            values(&[Some("1"), Some("a,\"b\""), None]),
+            values(&[Some("2"), Some("line\nbreak"), Some("")]),
+        ];
+        let mut buf = BytesMut::new();
+        for row in &rows {
+            options.encode_row(row, &mut buf);
+        }
+        assert_eq!(
+            &buf[..],
+            b"1,\"a,\"\"b\"\"\",\n2,\"line\nbreak\",\"\"\n".as_slice()
+        );
+
+        let (left, right) = buf.split_at(15);
+        assert_eq!(decode_all(options.clone(), &[left, right]), rows);
+
+        // The quoted newline is kept when the scan resumes at each byte.
+        let chunks = buf.chunks(1).collect::<Vec<_>>();
+        assert_eq!(decode_all(options, &chunks), rows);
+    }
+
+    #[test]
+    fn test_csv_decode() {
+        let options = CopyOptions {
+            header: true,
+            ..CopyOptions::new(CopyFormat::Csv)
+        };
+        assert_eq!(
+            decode_all(options.clone(), &[b"a,b\n1,x\n", b"2,\"y\"\"\""]),
+            vec![
+                values(&[Some("1"), Some("x")]),
+                values(&[Some("2"), Some("y\"")])
+            ]
+        );
+
+        let mut decoder = CopyDecoder::new(options);
+        assert!(decoder.decode(b"a,b\n1,\"x").unwrap().is_empty());
+        assert!(decoder.finish().is_err());
+    }
+}
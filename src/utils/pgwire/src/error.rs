@@ -19,6 +19,8 @@ use thiserror::Error;
 pub enum PsqlError {
     #[error("Encode error {0}.")]
     CancelError(String),
+    #[error("password authentication failed for user \"{0}\"")]
+    AuthenticationError(String),
 }
 
 impl PsqlError {
@@ -14,6 +14,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod copy;
 pub mod error;
 pub mod pg_field_descriptor;
 pub mod pg_message;
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::{Error, ErrorKind, Result};
+
+use crate::types::Format;
+
 /// Port from PgFieldDescriptor.java
 #[derive(Debug, Clone)]
 pub struct PgFieldDescriptor {
@@ -44,7 +48,12 @@ impl PgFieldDescriptor {
             | TypeOid::Time
             | TypeOid::Timestampz => 8,
             TypeOid::SmallInt => 2,
-            TypeOid::CharArray | TypeOid::Varchar | TypeOid::Decimal => -1,
+            TypeOid::Uuid => 16,
+            TypeOid::CharArray
+            | TypeOid::Varchar
+            | TypeOid::Decimal
+            | TypeOid::Jsonb
+            | TypeOid::Bytea => -1,
         };
 
         Self {
@@ -85,9 +94,17 @@ impl PgFieldDescriptor {
     pub fn get_format_code(&self) -> i16 {
         self.format_code
     }
+
+    /// Set the format in which values of this field are sent.
+    pub fn set_format(&mut self, format: Format) {
+        self.format_code = match format {
+            Format::Text => 0,
+            Format::Binary => 1,
+        };
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum TypeOid {
     Boolean,
     BigInt,
@@ -102,6 +119,9 @@ pub enum TypeOid {
     Timestamp,
     Timestampz,
     Decimal,
+    Jsonb,
+    Bytea,
+    Uuid,
 }
 
 impl TypeOid {
@@ -120,6 +140,35 @@ impl TypeOid {
             TypeOid::Timestamp => 1114,
             TypeOid::Timestampz => 1184,
             TypeOid::Decimal => 1231,
+            TypeOid::Jsonb => 3802,
+            TypeOid::Bytea => 17,
+            TypeOid::Uuid => 2950,
+        }
+    }
+
+    pub fn from_number(oid: i32) -> Result<Self> {
+        match oid {
+            16 => Ok(TypeOid::Boolean),
+            20 => Ok(TypeOid::BigInt),
+            21 => Ok(TypeOid::SmallInt),
+            23 => Ok(TypeOid::Int),
+            700 => Ok(TypeOid::Float4),
+            701 => Ok(TypeOid::Float8),
+            1002 => Ok(TypeOid::CharArray),
+            // Both `text` and `varchar` are represented as varchar.
+            25 | 1043 => Ok(TypeOid::Varchar),
+            1082 => Ok(TypeOid::Date),
+            1083 => Ok(TypeOid::Time),
+            1114 => Ok(TypeOid::Timestamp),
+            1184 => Ok(TypeOid::Timestampz),
+            1231 => Ok(TypeOid::Decimal),
+            3802 => Ok(TypeOid::Jsonb),
+            17 => Ok(TypeOid::Bytea),
+            2950 => Ok(TypeOid::Uuid),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unsupported type oid: {}", oid),
+            )),
         }
     }
 }
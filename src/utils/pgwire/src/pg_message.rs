@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::io::{Error, ErrorKind, IoSlice, Result, Write};
 
@@ -20,9 +21,9 @@ use byteorder::{BigEndian, ByteOrder};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use tokio::io::{AsyncRead, AsyncReadExt};
 
-use crate::pg_field_descriptor::PgFieldDescriptor;
+use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use crate::pg_response::StatementType;
-use crate::pg_server::BoxedError;
+use crate::pg_server::{BoxedError, SessionId};
 use crate::types::Row;
 
 /// Messages that can be sent from pg client to server. Implement `read`.
@@ -30,16 +31,42 @@ pub enum FeMessage {
     Ssl,
     Startup(FeStartupMessage),
     Query(FeQueryMessage),
+    Password(FePasswordMessage),
     Parse(FeParseMessage),
     Describe(FeDescribeMessage),
     Bind(FeBindMessage),
     Execute(FeExecuteMessage),
+    Close(FeCloseMessage),
+    /// A chunk of the data sent by `COPY ... FROM STDIN`.
+    CopyData(Bytes),
+    CopyDone,
+    /// The client aborts `COPY ... FROM STDIN` with an error message.
+    CopyFail(Bytes),
     Sync,
-    CancelQuery,
+    Flush,
+    CancelQuery(FeCancelMessage),
     Terminate,
 }
 
-pub struct FeStartupMessage {}
+/// Cancel request sent by a new connection, which identifies the session to cancel by the key
+/// issued in its `BackendKeyData`.
+#[derive(Debug)]
+pub struct FeCancelMessage {
+    pub target_process_id: i32,
+    pub target_secret_key: i32,
+}
+
+/// Startup message contains the connection parameters, e.g. `user` and `database`.
+#[derive(Debug, Default)]
+pub struct FeStartupMessage {
+    pub config: HashMap<String, String>,
+}
+
+/// Password message contains the password sent by the client during authentication.
+#[derive(Debug)]
+pub struct FePasswordMessage {
+    pub password: Bytes,
+}
 
 /// Query message contains the string sql.
 pub struct FeQueryMessage {
@@ -47,78 +74,140 @@ pub struct FeQueryMessage {
 }
 
 #[derive(Debug)]
-pub struct FeBindMessage {}
+pub struct FeBindMessage {
+    pub portal_name: Bytes,
+    pub statement_name: Bytes,
+    // Format codes of the parameters, 0 for text and 1 for binary.
+    pub param_format_codes: Vec<i16>,
+    // `None` for a NULL parameter.
+    pub params: Vec<Option<Bytes>>,
+    // Format codes of the result columns, 0 for text and 1 for binary.
+    pub result_format_codes: Vec<i16>,
+}
 
 #[derive(Debug)]
 pub struct FeExecuteMessage {
+    pub portal_name: Bytes,
+    // Maximum number of rows to return, 0 for no limit.
     pub max_rows: i32,
 }
 
 #[derive(Debug)]
 pub struct FeParseMessage {
+    pub statement_name: Bytes,
     pub query_string: Bytes,
+    // Oids of the parameter types, 0 for an unspecified type.
+    pub type_ids: Vec<i32>,
 }
 
 #[derive(Debug)]
 pub struct FeDescribeMessage {
     // 'S' to describe a prepared statement; or 'P' to describe a portal.
     pub kind: u8,
+    pub name: Bytes,
+}
+
+#[derive(Debug)]
+pub struct FeCloseMessage {
+    // 'S' to close a prepared statement; or 'P' to close a portal.
+    pub kind: u8,
+    pub name: Bytes,
 }
 
 impl FeDescribeMessage {
     pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
-        let kind = buf.get_u8();
-        let _pstmt_name = read_null_terminated(&mut buf)?;
+        let kind = read_u8(&mut buf)?;
+        let name = read_null_terminated(&mut buf)?;
 
-        if kind != b'S' {
-            unimplemented!("only prepared statement Describe is implemented");
-        }
+        Ok(FeMessage::Describe(FeDescribeMessage { kind, name }))
+    }
+}
+
+impl FeCloseMessage {
+    pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
+        let kind = read_u8(&mut buf)?;
+        let name = read_null_terminated(&mut buf)?;
 
-        Ok(FeMessage::Describe(FeDescribeMessage { kind }))
+        Ok(FeMessage::Close(FeCloseMessage { kind, name }))
+    }
+}
+
+impl FePasswordMessage {
+    pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
+        let password = read_null_terminated(&mut buf)?;
+
+        Ok(FeMessage::Password(FePasswordMessage { password }))
     }
 }
 
 impl FeBindMessage {
     pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
         let portal_name = read_null_terminated(&mut buf)?;
-        let _pstmt_name = read_null_terminated(&mut buf)?;
-
-        if !portal_name.is_empty() {
-            unimplemented!("named portals not implemented");
+        let statement_name = read_null_terminated(&mut buf)?;
+
+        let len = read_i16(&mut buf)?;
+        let param_format_codes = (0..len)
+            .map(|_| read_i16(&mut buf))
+            .collect::<Result<_>>()?;
+
+        let len = read_i16(&mut buf)?;
+        let mut params = Vec::with_capacity(len.max(0) as usize);
+        for _ in 0..len {
+            // A length of -1 indicates a NULL parameter value.
+            let val_len = read_i32(&mut buf)?;
+            if val_len < 0 {
+                params.push(None);
+            } else if val_len as usize > buf.remaining() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "parameter value exceeds the message length",
+                ));
+            } else {
+                params.push(Some(buf.split_to(val_len as usize)));
+            }
         }
 
-        Ok(FeMessage::Bind(FeBindMessage {}))
+        let len = read_i16(&mut buf)?;
+        let result_format_codes = (0..len)
+            .map(|_| read_i16(&mut buf))
+            .collect::<Result<_>>()?;
+
+        Ok(FeMessage::Bind(FeBindMessage {
+            portal_name,
+            statement_name,
+            param_format_codes,
+            params,
+            result_format_codes,
+        }))
     }
 }
 
 impl FeExecuteMessage {
     pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
         let portal_name = read_null_terminated(&mut buf)?;
-        let max_rows = buf.get_i32();
+        let max_rows = read_i32(&mut buf)?;
 
-        if !portal_name.is_empty() {
-            unimplemented!("named portals not implemented");
-        }
-
-        if max_rows != 0 {
-            unimplemented!("row limit in Execute message not supported");
-        }
-
-        Ok(FeMessage::Execute(FeExecuteMessage { max_rows }))
+        Ok(FeMessage::Execute(FeExecuteMessage {
+            portal_name,
+            max_rows,
+        }))
     }
 }
 
 impl FeParseMessage {
     pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
-        let _pstmt_name = read_null_terminated(&mut buf)?;
+        let statement_name = read_null_terminated(&mut buf)?;
         let query_string = read_null_terminated(&mut buf)?;
-        let nparams = buf.get_i16();
-
-        if nparams != 0 {
-            unimplemented!("query params not implemented");
-        }
-
-        Ok(FeMessage::Parse(FeParseMessage { query_string }))
+        let nparams = read_i16(&mut buf)?;
+        let type_ids = (0..nparams)
+            .map(|_| read_i32(&mut buf))
+            .collect::<Result<_>>()?;
+
+        Ok(FeMessage::Parse(FeParseMessage {
+            statement_name,
+            query_string,
+            type_ids,
+        }))
     }
 }
 
@@ -154,11 +243,20 @@ impl FeMessage {
 
         match val {
             b'Q' => Ok(FeMessage::Query(FeQueryMessage { sql_bytes })),
+            b'p' => FePasswordMessage::parse(sql_bytes),
             b'P' => FeParseMessage::parse(sql_bytes),
             b'D' => FeDescribeMessage::parse(sql_bytes),
             b'B' => FeBindMessage::parse(sql_bytes),
             b'E' => FeExecuteMessage::parse(sql_bytes),
+            b'C' => FeCloseMessage::parse(sql_bytes),
+            b'd' => Ok(FeMessage::CopyData(sql_bytes)),
+            b'c' => Ok(FeMessage::CopyDone),
+            b'f' => {
+                let mut buf = sql_bytes;
+                Ok(FeMessage::CopyFail(read_null_terminated(&mut buf)?))
+            }
             b'S' => Ok(FeMessage::Sync),
+            b'H' => Ok(FeMessage::Flush),
             b'X' => Ok(FeMessage::Terminate),
             _ => Err(std::io::Error::new(
                 ErrorKind::InvalidInput,
@@ -169,6 +267,21 @@ impl FeMessage {
 }
 
 impl FeStartupMessage {
+    /// Parse the connection parameters, which are pairs of null-terminated name and value and
+    /// end with a single null byte.
+    pub fn parse(mut buf: Bytes) -> Result<Self> {
+        let mut config = HashMap::new();
+        while buf.has_remaining() && buf[0] != 0 {
+            let name = read_null_terminated(&mut buf)?;
+            let value = read_null_terminated(&mut buf)?;
+            let to_string = |b: Bytes| {
+                String::from_utf8(b.to_vec()).map_err(|e| Error::new(ErrorKind::InvalidInput, e))
+            };
+            config.insert(to_string(name)?, to_string(value)?);
+        }
+        Ok(FeStartupMessage { config })
+    }
+
     /// Read startup message from the stream.
     pub async fn read(stream: &mut (impl AsyncRead + Unpin)) -> Result<FeMessage> {
         let len = stream.read_i32().await?;
@@ -180,10 +293,12 @@ impl FeStartupMessage {
         }
         match protocol_num {
             // code from: https://www.postgresql.org/docs/current/protocol-message-formats.html
-            196608 => Ok(FeMessage::Startup(FeStartupMessage {})),
+            196608 => Ok(FeMessage::Startup(FeStartupMessage::parse(Bytes::from(
+                payload,
+            ))?)),
             80877103 => Ok(FeMessage::Ssl),
             // Cancel request code.
-            80877102 => Ok(FeMessage::CancelQuery),
+            80877102 => FeCancelMessage::parse(Bytes::from(payload)),
             _ => Err(std::io::Error::new(
                 ErrorKind::InvalidInput,
                 format!(
@@ -195,13 +310,33 @@ impl FeStartupMessage {
     }
 }
 
+impl FeCancelMessage {
+    pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
+        if buf.remaining() < 8 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "cancel request is too short",
+            ));
+        }
+        let target_process_id = buf.get_i32();
+        let target_secret_key = buf.get_i32();
+        Ok(FeMessage::CancelQuery(FeCancelMessage {
+            target_process_id,
+            target_secret_key,
+        }))
+    }
+}
+
 /// Continue read until reached a \0. Used in reading string from Bytes.
 fn read_null_terminated(buf: &mut Bytes) -> Result<Bytes> {
     let mut result = BytesMut::new();
 
     loop {
         if !buf.has_remaining() {
-            panic!("no null-terminator in string");
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "no null-terminator in string",
+            ));
         }
 
         let byte = buf.get_u8();
@@ -214,23 +349,63 @@ fn read_null_terminated(buf: &mut Bytes) -> Result<Bytes> {
     Ok(result.freeze())
 }
 
+/// Reads a `u8`, returning an error instead of panicking on a truncated message.
+fn read_u8(buf: &mut Bytes) -> Result<u8> {
+    if !buf.has_remaining() {
+        return Err(Error::new(ErrorKind::InvalidInput, "message is truncated"));
+    }
+    Ok(buf.get_u8())
+}
+
+/// Reads an `i16`, returning an error instead of panicking on a truncated message.
+fn read_i16(buf: &mut Bytes) -> Result<i16> {
+    if buf.remaining() < 2 {
+        return Err(Error::new(ErrorKind::InvalidInput, "message is truncated"));
+    }
+    Ok(buf.get_i16())
+}
+
+/// Reads an `i32`, returning an error instead of panicking on a truncated message.
+fn read_i32(buf: &mut Bytes) -> Result<i32> {
+    if buf.remaining() < 4 {
+        return Err(Error::new(ErrorKind::InvalidInput, "message is truncated"));
+    }
+    Ok(buf.get_i32())
+}
+
 /// Message sent from server to psql client. Implement `write` (how to serialize it into psql
 /// buffer).
 #[derive(Debug)]
 pub enum BeMessage<'a> {
     AuthenticationOk,
+    AuthenticationCleartextPassword,
+    AuthenticationMd5Password(&'a [u8; 4]),
+    // The process id and the secret key that the client should use to cancel queries.
+    BackendKeyData(SessionId),
     CommandComplete(BeCommandCompleteMessage),
     // Single byte - used in response to SSLRequest/GSSENCRequest.
     EncryptionResponse,
     EmptyQueryResponse,
     ParseComplete,
     BindComplete,
-    ParameterDescription,
+    CloseComplete,
+    PortalSuspended,
+    ParameterDescription(&'a [TypeOid]),
     NoData,
     DataRow(&'a Row),
+    // A data row whose values are already encoded in the formats requested by the client.
+    EncodedDataRow(&'a [Option<Bytes>]),
     ParameterStatus(BeParameterStatusMessage<'a>),
     ReadyForQuery,
     RowDescription(&'a [PgFieldDescriptor]),
+    // The server is ready to receive the data of `COPY ... FROM STDIN` with the given number of
+    // columns in text format.
+    CopyInResponse(usize),
+    // The server starts to send the data of `COPY ... TO STDOUT` with the given number of columns
+    // in text format.
+    CopyOutResponse(usize),
+    CopyData(&'a [u8]),
+    CopyDone,
     ErrorResponse(BoxedError),
 }
 
@@ -262,6 +437,38 @@ impl<'a> BeMessage<'a> {
                 buf.put_i32(0);
             }
 
+            // AuthenticationCleartextPassword
+            // +-----+----------+-----------+
+            // | 'R' | int32(8) | int32(3)  |
+            // +-----+----------+-----------+
+            BeMessage::AuthenticationCleartextPassword => {
+                buf.put_u8(b'R');
+                buf.put_i32(8);
+                buf.put_i32(3);
+            }
+
+            // AuthenticationMD5Password
+            // +-----+-----------+-----------+--------------+
+            // | 'R' | int32(12) | int32(5)  | byte4 salt   |
+            // +-----+-----------+-----------+--------------+
+            BeMessage::AuthenticationMd5Password(salt) => {
+                buf.put_u8(b'R');
+                buf.put_i32(12);
+                buf.put_i32(5);
+                buf.put_slice(&salt[..]);
+            }
+
+            // BackendKeyData
+            // +-----+-----------+------------------+------------------+
+            // | 'K' | int32(12) | int32 process id | int32 secret key |
+            // +-----+-----------+------------------+------------------+
+            BeMessage::BackendKeyData((process_id, secret_key)) => {
+                buf.put_u8(b'K');
+                buf.put_i32(12);
+                buf.put_i32(*process_id);
+                buf.put_i32(*secret_key);
+            }
+
             // ParameterStatus
             // +-----+-----------+----------+------+-----------+------+
             // | 'S' | int32 len | str name | '\0' | str value | '\0' |
@@ -345,12 +552,18 @@ impl<'a> BeMessage<'a> {
                 write_body(buf, |buf| {
                     buf.put_u16(vals.len() as u16); // num of cols
                     for val_opt in vals.values() {
-                        if let Some(val) = val_opt {
-                            buf.put_u32(val.len() as u32);
-                            buf.put_slice(val.as_bytes());
-                        } else {
-                            buf.put_i32(-1);
-                        }
+                        write_value(buf, val_opt.as_ref().map(|val| val.as_bytes()));
+                    }
+                    Ok(())
+                })
+                .unwrap();
+            }
+            BeMessage::EncodedDataRow(vals) => {
+                buf.put_u8(b'D');
+                write_body(buf, |buf| {
+                    buf.put_u16(vals.len() as u16); // num of cols
+                    for val_opt in vals.iter() {
+                        write_value(buf, val_opt.as_deref());
                     }
                     Ok(())
                 })
@@ -406,11 +619,28 @@ impl<'a> BeMessage<'a> {
                 write_body(buf, |_| Ok(()))?;
             }
 
-            BeMessage::ParameterDescription => {
+            BeMessage::CloseComplete => {
+                buf.put_u8(b'3');
+                write_body(buf, |_| Ok(()))?;
+            }
+
+            BeMessage::PortalSuspended => {
+                buf.put_u8(b's');
+                write_body(buf, |_| Ok(()))?;
+            }
+
+            // ParameterDescription
+            // +-----+-----------+-----------------+---------+-----+---------+
+            // | 't' | int32 len | int16 paramNum  | int32   | ... | int32   |
+            // +-----+-----------+-----------------+---------+-----+---------+
+            //                                       typeOID         typeOID
+            BeMessage::ParameterDescription(param_types) => {
                 buf.put_u8(b't');
                 write_body(buf, |buf| {
-                    // we don't support params, so always 0
-                    buf.put_i16(0);
+                    buf.put_i16(param_types.len() as i16);
+                    for type_oid in param_types.iter() {
+                        buf.put_i32(type_oid.as_number());
+                    }
                     Ok(())
                 })
                 .unwrap();
@@ -425,6 +655,45 @@ impl<'a> BeMessage<'a> {
                 buf.put_u8(b'N');
             }
 
+            // CopyInResponse ('G') and CopyOutResponse ('H')
+            // +-----+-----------+--------------+--------------+--------+-----+--------+
+            // | tag | int32 len | int8 format  | int16 colNum | int16  | ... | int16  |
+            // +-----+-----------+--------------+--------------+--------+-----+--------+
+            //                                                  format         format
+            BeMessage::CopyInResponse(col_num) | BeMessage::CopyOutResponse(col_num) => {
+                let tag = match message {
+                    BeMessage::CopyInResponse(_) => b'G',
+                    _ => b'H',
+                };
+                buf.put_u8(tag);
+                write_body(buf, |buf| {
+                    // All columns are in text format.
+                    buf.put_i8(0);
+                    buf.put_i16(*col_num as i16);
+                    for _ in 0..*col_num {
+                        buf.put_i16(0);
+                    }
+                    Ok(())
+                })?;
+            }
+
+            // CopyData
+            // +-----+-----------+-------+
+            // | 'd' | int32 len | bytes |
+            // +-----+-----------+-------+
+            BeMessage::CopyData(data) => {
+                buf.put_u8(b'd');
+                write_body(buf, |buf| {
+                    buf.put_slice(data);
+                    Ok(())
+                })?;
+            }
+
+            BeMessage::CopyDone => {
+                buf.put_u8(b'c');
+                write_body(buf, |_| Ok(()))?;
+            }
+
             // EmptyQueryResponse
             // +-----+----------+
             // | 'I' | int32(4) |
@@ -500,6 +769,16 @@ where
     Ok(())
 }
 
+/// Write a column value of a data row, prepended with its length. NULL is written as length -1.
+fn write_value(buf: &mut BytesMut, val: Option<&[u8]>) {
+    if let Some(val) = val {
+        buf.put_u32(val.len() as u32);
+        buf.put_slice(val);
+    } else {
+        buf.put_i32(-1);
+    }
+}
+
 /// Safe write of s into buf as cstring (String in the protocol).
 fn write_cstr(buf: &mut BytesMut, s: &[u8]) -> Result<()> {
     if s.contains(&0) {
@@ -515,9 +794,12 @@ fn write_cstr(buf: &mut BytesMut, s: &[u8]) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use bytes::Bytes;
+    use bytes::{BufMut, Bytes, BytesMut};
 
-    use crate::pg_message::FeQueryMessage;
+    use crate::pg_message::{
+        BeMessage, FeBindMessage, FeCloseMessage, FeDescribeMessage, FeMessage, FeQueryMessage,
+        FeStartupMessage,
+    };
 
     #[tokio::test]
     async fn test_get_sql() {
@@ -530,4 +812,143 @@ mod tests {
         };
         assert!(fe.get_sql().is_err(), "{}", true);
     }
+
+    #[test]
+    fn test_parse_startup_message() {
+        let msg =
+            FeStartupMessage::parse(Bytes::from_static(b"user\0root\0database\0dev\0\0")).unwrap();
+        assert_eq!(msg.config.get("user").unwrap(), "root");
+        assert_eq!(msg.config.get("database").unwrap(), "dev");
+        assert_eq!(msg.config.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_cancel_request() {
+        let mut buf = BytesMut::new();
+        buf.put_i32(16);
+        buf.put_i32(80877102);
+        buf.put_i32(42);
+        buf.put_i32(-7);
+        let mut stream = &buf[..];
+
+        match FeStartupMessage::read(&mut stream).await.unwrap() {
+            FeMessage::CancelQuery(msg) => {
+                assert_eq!(msg.target_process_id, 42);
+                assert_eq!(msg.target_secret_key, -7);
+            }
+            _ => panic!("expect a cancel request"),
+        }
+    }
+
+    #[test]
+    fn test_parse_bind_message() {
+        let mut buf = BytesMut::new();
+        buf.put_slice(b"portal\0stmt\0");
+        // One format code applying to all parameters.
+        buf.put_i16(1);
+        buf.put_i16(1);
+        // Two parameters, the second one is NULL.
+        buf.put_i16(2);
+        buf.put_i32(4);
+        buf.put_i32(42);
+        buf.put_i32(-1);
+        // No result format codes.
+        buf.put_i16(0);
+
+        let msg = match FeBindMessage::parse(buf.freeze()).unwrap() {
+            FeMessage::Bind(msg) => msg,
+            _ => panic!("expect a bind message"),
+        };
+        assert_eq!(msg.portal_name, "portal");
+        assert_eq!(msg.statement_name, "stmt");
+        assert_eq!(msg.param_format_codes, vec![1]);
+        assert_eq!(
+            msg.params,
+            vec![Some(Bytes::from_static(&[0, 0, 0, 42])), None]
+        );
+        assert!(msg.result_format_codes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_truncated_bind_message() {
+        let mut buf = BytesMut::new();
+        buf.put_slice(b"portal\0stmt\0");
+        buf.put_i16(0);
+        // Two parameters, but only one is sent.
+        buf.put_i16(2);
+        buf.put_i32(-1);
+        let buf = buf.freeze();
+        for len in 0..buf.len() {
+            assert!(FeBindMessage::parse(buf.slice(..len)).is_err());
+        }
+        assert!(FeBindMessage::parse(buf).is_err());
+    }
+
+    #[test]
+    fn test_parse_truncated_describe_and_close_messages() {
+        let buf = Bytes::from_static(b"Sstmt\0");
+        for len in 0..buf.len() {
+            assert!(FeDescribeMessage::parse(buf.slice(..len)).is_err());
+            assert!(FeCloseMessage::parse(buf.slice(..len)).is_err());
+        }
+        match FeDescribeMessage::parse(buf.clone()).unwrap() {
+            FeMessage::Describe(msg) => {
+                assert_eq!(msg.kind, b'S');
+                assert_eq!(msg.name, "stmt");
+            }
+            _ => panic!("expect a describe message"),
+        }
+        match FeCloseMessage::parse(buf).unwrap() {
+            FeMessage::Close(msg) => {
+                assert_eq!(msg.kind, b'S');
+                assert_eq!(msg.name, "stmt");
+            }
+            _ => panic!("expect a close message"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_copy_messages() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(b'd');
+        buf.put_i32(7);
+        buf.put_slice(b"1\t2");
+        buf.put_u8(b'c');
+        buf.put_i32(4);
+        buf.put_u8(b'f');
+        buf.put_i32(10);
+        buf.put_slice(b"abort\0");
+        let mut stream = &buf[..];
+
+        match FeMessage::read(&mut stream).await.unwrap() {
+            FeMessage::CopyData(data) => assert_eq!(data, "1\t2"),
+            _ => panic!("expect a copy data message"),
+        }
+        assert!(matches!(
+            FeMessage::read(&mut stream).await.unwrap(),
+            FeMessage::CopyDone
+        ));
+        match FeMessage::read(&mut stream).await.unwrap() {
+            FeMessage::CopyFail(msg) => assert_eq!(msg, "abort"),
+            _ => panic!("expect a copy fail message"),
+        }
+    }
+
+    #[test]
+    fn test_write_copy_response() {
+        let mut buf = BytesMut::new();
+        BeMessage::write(&mut buf, &BeMessage::CopyInResponse(2)).unwrap();
+        assert_eq!(&buf[..], b"G\0\0\0\x0b\0\0\x02\0\0\0\0".as_slice());
+
+        buf.clear();
+        BeMessage::write(&mut buf, &BeMessage::CopyData(b"1,2\n")).unwrap();
+        assert_eq!(&buf[..], b"d\0\0\0\x081,2\n".as_slice());
+    }
+
+    #[test]
+    fn test_write_backend_key_data() {
+        let mut buf = BytesMut::new();
+        BeMessage::write(&mut buf, &BeMessage::BackendKeyData((1, 2))).unwrap();
+        assert_eq!(&buf[..], b"K\0\0\0\x0c\0\0\0\x01\0\0\0\x02".as_slice());
+    }
 }
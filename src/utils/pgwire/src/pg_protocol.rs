@@ -12,19 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::io::{Error as IoError, ErrorKind, Result};
 use std::sync::Arc;
 
 use bytes::{Bytes, BytesMut};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 
+use crate::copy::{CopyDecoder, CopyOptions};
 use crate::error::PsqlError;
 use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use crate::pg_message::{
-    BeCommandCompleteMessage, BeMessage, BeParameterStatusMessage, FeMessage, FeStartupMessage,
+    BeCommandCompleteMessage, BeMessage, BeParameterStatusMessage, FeBindMessage, FeCloseMessage,
+    FeDescribeMessage, FeExecuteMessage, FeMessage, FeParseMessage, FePasswordMessage,
+    FeStartupMessage,
 };
-use crate::pg_response::PgResponse;
-use crate::pg_server::{Session, SessionManager};
+use crate::pg_response::{CopyInSink, CopyTransfer, PgResponse};
+use crate::pg_server::{BoxedError, Session, SessionManager, UserAuthenticator};
+use crate::types::{binary_to_text, encode_row, Format};
+
+/// The user and database used when they are absent from the startup message.
+const DEFAULT_USER_NAME: &str = "root";
+const DEFAULT_DATABASE_NAME: &str = "dev";
+
+type PreparedStatement<SM> = <<SM as SessionManager>::Session as Session>::PreparedStatement;
+type Portal<SM> = <<SM as SessionManager>::Session as Session>::Portal;
 
 /// The state machine for each psql connection.
 /// Read pg messages from tcp stream and write results back.
@@ -43,14 +55,36 @@ where
 
     session_mgr: Arc<SM>,
     session: Option<Arc<SM::Session>>,
+
+    /// Prepared statements created by `Parse`, keyed by name. The unnamed statement is keyed by
+    /// an empty string.
+    prepared_statements: HashMap<String, PreparedStatement<SM>>,
+    /// Portals created by `Bind`, keyed by name. The unnamed portal is keyed by an empty string.
+    portals: HashMap<String, PgPortal<Portal<SM>>>,
+    /// Once an error occurs in the extended query protocol, messages are discarded until the next
+    /// `Sync`.
+    ignore_till_sync: bool,
 }
 
 /// States flow happened from top to down.
 enum PgProtocolState {
     Startup,
+    /// Waiting for the password of the user named in the startup message.
+    Authenticate(String),
     Regular,
 }
 
+/// A portal along with the states needed to send its results.
+struct PgPortal<P> {
+    portal: P,
+    /// The row description with the result formats applied.
+    row_description: Vec<PgFieldDescriptor>,
+    result_formats: Vec<Format>,
+    /// The result of a previous `Execute` that hit its row limit, along with the number of rows
+    /// sent so far.
+    suspended_result: Option<(PgResponse, usize)>,
+}
+
 // Truncate 0 from C string in Bytes and stringify it (returns slice, no allocations)
 // PG protocol strings are always C strings.
 fn cstr_to_str(b: &Bytes) -> Result<&str> {
@@ -75,18 +109,21 @@ where
             buf_out: BytesMut::with_capacity(10 * 1024),
             session_mgr,
             session: None,
+            prepared_statements: HashMap::new(),
+            portals: HashMap::new(),
+            ignore_till_sync: false,
         }
     }
 
-    pub async fn process(&mut self, unnamed_query_string: &mut Bytes) -> Result<bool> {
-        if self.do_process(unnamed_query_string).await? {
+    pub async fn process(&mut self) -> Result<bool> {
+        if self.do_process().await? {
             return Ok(true);
         }
 
         Ok(self.is_terminate())
     }
 
-    async fn do_process(&mut self, unnamed_query_string: &mut Bytes) -> Result<bool> {
+    async fn do_process(&mut self) -> Result<bool> {
         let msg = match self.read_message().await {
             Ok(msg) => msg,
             Err(e) => {
@@ -99,6 +136,24 @@ where
                 return Ok(false);
             }
         };
+
+        if self.ignore_till_sync && !matches!(msg, FeMessage::Sync | FeMessage::Terminate) {
+            return Ok(false);
+        }
+
+        // Nothing but a password is expected before the user is authenticated.
+        if matches!(self.state, PgProtocolState::Authenticate(_))
+            && !matches!(msg, FeMessage::Password(_) | FeMessage::Terminate)
+        {
+            self.write_message_no_flush(&BeMessage::ErrorResponse(Box::new(IoError::new(
+                ErrorKind::InvalidInput,
+                "expected a password message",
+            ))))?;
+            self.process_terminate();
+            self.flush().await?;
+            return Ok(true);
+        }
+
         match msg {
             FeMessage::Ssl => {
                 self.write_message_no_flush(&BeMessage::EncryptionResponse)
@@ -108,49 +163,66 @@ where
                     })?;
             }
             FeMessage::Startup(msg) => {
-                self.process_startup_msg(msg).map_err(|e| {
+                if let Err(e) = self.process_startup_msg(msg) {
                     tracing::error!("failed to set up pg session: {}", e);
-                    e
-                })?;
-                self.state = PgProtocolState::Regular;
+                    self.write_message_no_flush(&BeMessage::ErrorResponse(Box::new(e)))?;
+                    self.process_terminate();
+                }
+            }
+            FeMessage::Password(msg) => {
+                if let Err(e) = self.process_password_msg(msg) {
+                    tracing::error!("failed to authenticate: {}", e);
+                    self.write_message_no_flush(&BeMessage::ErrorResponse(e))?;
+                    self.process_terminate();
+                }
             }
             FeMessage::Query(query_msg) => {
-                self.process_query_msg(query_msg.get_sql(), false).await?;
+                // A simple query destroys the unnamed statement and portal.
+                self.prepared_statements.remove("");
+                self.portals.remove("");
+                self.process_query_msg(query_msg.get_sql()).await?;
                 self.write_message_no_flush(&BeMessage::ReadyForQuery)?;
             }
-            FeMessage::CancelQuery => {
-                self.write_message_no_flush(&BeMessage::ErrorResponse(Box::new(
-                    PsqlError::cancel(),
-                )))?;
+            FeMessage::CancelQuery(m) => {
+                // A cancel request is sent through a new connection, which is closed without any
+                // response.
+                self.session_mgr
+                    .cancel_queries_in_session((m.target_process_id, m.target_secret_key));
+                self.process_terminate();
             }
             FeMessage::Terminate => {
                 self.process_terminate();
             }
             FeMessage::Parse(m) => {
-                *unnamed_query_string = m.query_string;
-                self.write_message(&BeMessage::ParseComplete).await?;
+                let res = self.process_parse_msg(m);
+                self.handle_extended_result(res)?;
             }
-            FeMessage::Bind(_) => {
-                self.write_message(&BeMessage::BindComplete).await?;
+            FeMessage::Bind(m) => {
+                let res = self.process_bind_msg(m);
+                self.handle_extended_result(res)?;
             }
-            FeMessage::Execute(_) => {
-                self.process_query_msg(cstr_to_str(unnamed_query_string), true)
-                    .await?;
+            FeMessage::Execute(m) => {
+                let res = self.process_execute_msg(m).await;
+                self.handle_extended_result(res)?;
                 // NOTE there is no ReadyForQuery message.
             }
-            FeMessage::Describe(_) => {
-                self.write_message_no_flush(&BeMessage::ParameterDescription)?;
-                // FIXME: Introduce parser to analyze statements and bind data type. Here just
-                // hard-code a VARCHAR.
-                self.write_message(&BeMessage::RowDescription(&[PgFieldDescriptor::new(
-                    "VARCHAR".to_string(),
-                    TypeOid::Varchar,
-                )]))
-                .await?;
+            FeMessage::Describe(m) => {
+                let res = self.process_describe_msg(m);
+                self.handle_extended_result(res)?;
+            }
+            FeMessage::Close(m) => {
+                let res = self.process_close_msg(m);
+                self.handle_extended_result(res)?;
             }
             FeMessage::Sync => {
-                self.write_message(&BeMessage::ReadyForQuery).await?;
+                // Portals only live in the implicit transaction ended by `Sync`.
+                self.portals.clear();
+                self.ignore_till_sync = false;
+                self.write_message_no_flush(&BeMessage::ReadyForQuery)?;
             }
+            FeMessage::Flush => {}
+            // The client may still be sending the data of a `COPY` that has failed.
+            FeMessage::CopyData(_) | FeMessage::CopyDone | FeMessage::CopyFail(_) => {}
         }
         self.flush().await?;
         Ok(false)
@@ -159,13 +231,66 @@ where
     async fn read_message(&mut self) -> Result<FeMessage> {
         match self.state {
             PgProtocolState::Startup => FeStartupMessage::read(&mut self.stream).await,
-            PgProtocolState::Regular => FeMessage::read(&mut self.stream).await,
+            PgProtocolState::Authenticate(_) | PgProtocolState::Regular => {
+                FeMessage::read(&mut self.stream).await
+            }
+        }
+    }
+
+    fn process_startup_msg(&mut self, msg: FeStartupMessage) -> Result<()> {
+        let user_name = msg
+            .config
+            .get("user")
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_USER_NAME.to_string());
+        let database = msg
+            .config
+            .get("database")
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_DATABASE_NAME);
+        let session = self
+            .session_mgr
+            .connect(database, &user_name)
+            .map_err(IoError::other)?;
+        self.session = Some(session.clone());
+
+        match session.user_authenticator() {
+            UserAuthenticator::None => self.finish_startup()?,
+            UserAuthenticator::ClearText(_) => {
+                self.write_message_no_flush(&BeMessage::AuthenticationCleartextPassword)?;
+                self.state = PgProtocolState::Authenticate(user_name);
+            }
+            UserAuthenticator::Md5WithSalt { salt, .. } => {
+                self.write_message_no_flush(&BeMessage::AuthenticationMd5Password(salt))?;
+                self.state = PgProtocolState::Authenticate(user_name);
+            }
+        }
+        Ok(())
+    }
+
+    fn process_password_msg(
+        &mut self,
+        msg: FePasswordMessage,
+    ) -> std::result::Result<(), BoxedError> {
+        let user_name = match &self.state {
+            PgProtocolState::Authenticate(user_name) => user_name.clone(),
+            _ => {
+                return Err(
+                    IoError::new(ErrorKind::InvalidInput, "unexpected password message").into(),
+                )
+            }
+        };
+        let session = self.session.as_ref().unwrap();
+        if !session.user_authenticator().authenticate(&msg.password) {
+            return Err(PsqlError::AuthenticationError(user_name).into());
         }
+        self.finish_startup()?;
+        Ok(())
     }
 
-    fn process_startup_msg(&mut self, _msg: FeStartupMessage) -> Result<()> {
-        // TODO: Replace `DEFAULT_DATABASE_NAME` with true database name in `FeStartupMessage`.
-        self.session = Some(self.session_mgr.connect("dev").map_err(IoError::other)?);
+    /// Finishes the startup phase after the user is authenticated.
+    fn finish_startup(&mut self) -> Result<()> {
+        self.state = PgProtocolState::Regular;
         self.write_message_no_flush(&BeMessage::AuthenticationOk)?;
         self.write_message_no_flush(&BeMessage::ParameterStatus(
             BeParameterStatusMessage::ClientEncoding("utf8"),
@@ -176,6 +301,8 @@ where
         self.write_message_no_flush(&BeMessage::ParameterStatus(
             BeParameterStatusMessage::ServerVersion("9.5.0"),
         ))?;
+        let session_id = self.session.as_ref().unwrap().id();
+        self.write_message_no_flush(&BeMessage::BackendKeyData(session_id))?;
         self.write_message_no_flush(&BeMessage::ReadyForQuery)?;
         Ok(())
     }
@@ -184,11 +311,7 @@ where
         self.is_terminate = true;
     }
 
-    async fn process_query_msg(
-        &mut self,
-        query_string: Result<&str>,
-        extended: bool,
-    ) -> Result<()> {
+    async fn process_query_msg(&mut self, query_string: Result<&str>) -> Result<()> {
         match query_string {
             Ok(sql) => {
                 tracing::trace!("receive query: {}", sql);
@@ -196,11 +319,13 @@ where
                 // execute query
                 let process_res = session.run_statement(sql).await;
                 match process_res {
-                    Ok(res) => {
+                    Ok(mut res) => {
                         if res.is_empty() {
                             self.write_message_no_flush(&BeMessage::EmptyQueryResponse)?;
+                        } else if let Some(copy) = res.take_copy() {
+                            self.process_copy(res, copy).await?;
                         } else if res.is_query() {
-                            self.process_query_with_results(res, extended).await?;
+                            self.process_query_with_results(res).await?;
                         } else {
                             self.write_message_no_flush(&BeMessage::CommandComplete(
                                 BeCommandCompleteMessage {
@@ -224,15 +349,9 @@ where
         Ok(())
     }
 
-    async fn process_query_with_results(&mut self, res: PgResponse, extended: bool) -> Result<()> {
-        // The possible responses to Execute are the same as those described above for queries
-        // issued via simple query protocol, except that Execute doesn't cause ReadyForQuery or
-        // RowDescription to be issued.
-        // Quoted from: https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-EXT-QUERY
-        if !extended {
-            self.write_message(&BeMessage::RowDescription(&res.get_row_desc()))
-                .await?;
-        }
+    async fn process_query_with_results(&mut self, res: PgResponse) -> Result<()> {
+        self.write_message(&BeMessage::RowDescription(&res.get_row_desc()))
+            .await?;
 
         let mut rows_cnt = 0;
         let iter = res.iter();
@@ -248,6 +367,316 @@ where
         Ok(())
     }
 
+    async fn process_copy(&mut self, res: PgResponse, copy: CopyTransfer) -> Result<()> {
+        match copy {
+            CopyTransfer::In { options, sink } => {
+                let col_num = res.get_row_desc().len();
+                match self.process_copy_in(col_num, options, sink).await {
+                    Ok(rows_cnt) => {
+                        self.write_message_no_flush(&BeMessage::CommandComplete(
+                            BeCommandCompleteMessage {
+                                stmt_type: res.get_stmt_type(),
+                                notice: res.get_notice(),
+                                rows_cnt,
+                            },
+                        ))?;
+                    }
+                    Err(e) => {
+                        tracing::error!("failed to copy from stdin: {}", e);
+                        self.write_message_no_flush(&BeMessage::ErrorResponse(e))?;
+                    }
+                }
+            }
+            CopyTransfer::Out { options } => self.process_copy_out(res, options)?,
+        }
+        Ok(())
+    }
+
+    /// Receive the data of `COPY ... FROM STDIN` until `CopyDone`, and write the decoded rows to
+    /// `sink` as each chunk of data arrives.
+    async fn process_copy_in(
+        &mut self,
+        col_num: usize,
+        options: CopyOptions,
+        mut sink: Box<dyn CopyInSink>,
+    ) -> std::result::Result<i32, BoxedError> {
+        self.write_message(&BeMessage::CopyInResponse(col_num))
+            .await?;
+        let mut decoder = CopyDecoder::new(options);
+        loop {
+            match self.read_message().await? {
+                FeMessage::CopyData(data) => sink.write(decoder.decode(&data)?).await?,
+                FeMessage::CopyDone => {
+                    sink.write(decoder.finish()?).await?;
+                    return sink.finish().await;
+                }
+                FeMessage::CopyFail(msg) => {
+                    return Err(format!("COPY from stdin failed: {}", cstr_to_str(&msg)?).into());
+                }
+                // Both are ignored during the copy, as PG does.
+                FeMessage::Flush | FeMessage::Sync => {}
+                FeMessage::Terminate => {
+                    self.process_terminate();
+                    return Err("connection terminated during COPY from stdin".into());
+                }
+                _ => return Err("unexpected message type during COPY from stdin".into()),
+            }
+        }
+    }
+
+    /// Send the rows of `COPY ... TO STDOUT` as the copied data, each row in a `CopyData`.
+    fn process_copy_out(&mut self, res: PgResponse, options: CopyOptions) -> Result<()> {
+        let row_desc = res.get_row_desc();
+        self.write_message_no_flush(&BeMessage::CopyOutResponse(row_desc.len()))?;
+
+        let mut data = BytesMut::new();
+        if options.header {
+            let names = row_desc
+                .iter()
+                .map(|field| Some(field.get_name().to_string()))
+                .collect::<Vec<_>>();
+            options.encode_row(&names, &mut data);
+            self.write_message_no_flush(&BeMessage::CopyData(&data))?;
+        }
+        for row in res.iter() {
+            data.clear();
+            options.encode_row(row.values(), &mut data);
+            self.write_message_no_flush(&BeMessage::CopyData(&data))?;
+        }
+
+        self.write_message_no_flush(&BeMessage::CopyDone)?;
+        self.write_message_no_flush(&BeMessage::CommandComplete(BeCommandCompleteMessage {
+            stmt_type: res.get_stmt_type(),
+            notice: res.get_notice(),
+            rows_cnt: res.get_effected_rows_cnt(),
+        }))?;
+        Ok(())
+    }
+
+    /// Report the error of an extended query message to the client, and start discarding
+    /// messages until the next `Sync`.
+    fn handle_extended_result(&mut self, res: std::result::Result<(), BoxedError>) -> Result<()> {
+        if let Err(e) = res {
+            tracing::error!("failed to handle extended query message: {}", e);
+            self.ignore_till_sync = true;
+            self.write_message_no_flush(&BeMessage::ErrorResponse(e))?;
+        }
+        Ok(())
+    }
+
+    fn process_parse_msg(&mut self, msg: FeParseMessage) -> std::result::Result<(), BoxedError> {
+        let statement_name = cstr_to_str(&msg.statement_name)?.to_string();
+        let sql = cstr_to_str(&msg.query_string)?;
+        tracing::trace!("parse query: {}", sql);
+
+        // The unnamed statement is replaced by each `Parse`, while a named one must be closed
+        // explicitly before being redefined.
+        if !statement_name.is_empty() && self.prepared_statements.contains_key(&statement_name) {
+            return Err(format!("prepared statement \"{}\" already exists", statement_name).into());
+        }
+
+        let param_types = msg
+            .type_ids
+            .iter()
+            .map(|&type_id| match type_id {
+                0 => Ok(None),
+                _ => TypeOid::from_number(type_id).map(Some),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let session = self.session.clone().unwrap();
+        let prepared_statement = session.parse(sql, param_types)?;
+        self.prepared_statements
+            .insert(statement_name, prepared_statement);
+        self.write_message_no_flush(&BeMessage::ParseComplete)?;
+        Ok(())
+    }
+
+    fn process_bind_msg(&mut self, msg: FeBindMessage) -> std::result::Result<(), BoxedError> {
+        let statement_name = cstr_to_str(&msg.statement_name)?;
+        let portal_name = cstr_to_str(&msg.portal_name)?.to_string();
+
+        let prepared_statement = self
+            .prepared_statements
+            .get(statement_name)
+            .ok_or_else(|| format!("prepared statement \"{}\" does not exist", statement_name))?
+            .clone();
+
+        let session = self.session.clone().unwrap();
+        let (param_types, _) = session.describe_statement(&prepared_statement);
+        if msg.params.len() != param_types.len() {
+            return Err(format!(
+                "bind message supplies {} parameters, but prepared statement \"{}\" requires {}",
+                msg.params.len(),
+                statement_name,
+                param_types.len()
+            )
+            .into());
+        }
+
+        let param_formats = Format::expand(&msg.param_format_codes, msg.params.len())?;
+        let params = msg
+            .params
+            .iter()
+            .zip(param_formats)
+            .zip(param_types)
+            .map(|((param, format), type_oid)| {
+                param
+                    .as_ref()
+                    .map(|param| match format {
+                        Format::Text => Ok(cstr_to_str(param)?.to_string()),
+                        Format::Binary => binary_to_text(type_oid, param),
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let portal = session.clone().bind(prepared_statement, params)?;
+        let mut row_description = session.describe_portal(&portal);
+        let result_formats = Format::expand(&msg.result_format_codes, row_description.len())?;
+        for (field, format) in row_description.iter_mut().zip(&result_formats) {
+            field.set_format(*format);
+        }
+
+        self.portals.insert(
+            portal_name,
+            PgPortal {
+                portal,
+                row_description,
+                result_formats,
+                suspended_result: None,
+            },
+        );
+        self.write_message_no_flush(&BeMessage::BindComplete)?;
+        Ok(())
+    }
+
+    async fn process_execute_msg(
+        &mut self,
+        msg: FeExecuteMessage,
+    ) -> std::result::Result<(), BoxedError> {
+        let portal_name = cstr_to_str(&msg.portal_name)?.to_string();
+        let mut portal = self
+            .portals
+            .remove(&portal_name)
+            .ok_or_else(|| format!("portal \"{}\" does not exist", portal_name))?;
+        let res = self.execute_portal(&mut portal, msg.max_rows).await;
+        self.portals.insert(portal_name, portal);
+        res
+    }
+
+    /// Execute a portal and send back at most `max_rows` rows, or all rows if `max_rows` is not
+    /// positive. If rows remain, the portal is suspended and the next `Execute` on it continues
+    /// from where it stopped.
+    async fn execute_portal(
+        &mut self,
+        portal: &mut PgPortal<Portal<SM>>,
+        max_rows: i32,
+    ) -> std::result::Result<(), BoxedError> {
+        let (res, rows_sent) = match portal.suspended_result.take() {
+            Some(suspended) => suspended,
+            None => {
+                let session = self.session.clone().unwrap();
+                (session.execute(portal.portal.clone()).await?, 0)
+            }
+        };
+
+        if res.is_copy() {
+            return Err("COPY is not supported in the extended query protocol".into());
+        }
+
+        // The possible responses to Execute are the same as those described above for queries
+        // issued via simple query protocol, except that Execute doesn't cause ReadyForQuery or
+        // RowDescription to be issued.
+        // Quoted from: https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-EXT-QUERY
+        if res.is_empty() {
+            self.write_message_no_flush(&BeMessage::EmptyQueryResponse)?;
+        } else if res.is_query() {
+            let max_rows = if max_rows > 0 {
+                max_rows as usize
+            } else {
+                usize::MAX
+            };
+            let mut rows_cnt = rows_sent;
+            let mut rows = res.iter().skip(rows_sent);
+            for row in rows.by_ref().take(max_rows) {
+                let values = encode_row(row, &portal.row_description, &portal.result_formats)?;
+                self.write_message_no_flush(&BeMessage::EncodedDataRow(&values))?;
+                rows_cnt += 1;
+            }
+            let has_more = rows.next().is_some();
+
+            if has_more {
+                self.write_message_no_flush(&BeMessage::PortalSuspended)?;
+                portal.suspended_result = Some((res, rows_cnt));
+            } else {
+                self.write_message_no_flush(&BeMessage::CommandComplete(
+                    BeCommandCompleteMessage {
+                        stmt_type: res.get_stmt_type(),
+                        notice: res.get_notice(),
+                        rows_cnt: rows_cnt as i32,
+                    },
+                ))?;
+            }
+        } else {
+            self.write_message_no_flush(&BeMessage::CommandComplete(BeCommandCompleteMessage {
+                stmt_type: res.get_stmt_type(),
+                notice: res.get_notice(),
+                rows_cnt: res.get_effected_rows_cnt(),
+            }))?;
+        }
+        Ok(())
+    }
+
+    fn process_describe_msg(
+        &mut self,
+        msg: FeDescribeMessage,
+    ) -> std::result::Result<(), BoxedError> {
+        let name = cstr_to_str(&msg.name)?;
+        let row_description = match msg.kind {
+            b'S' => {
+                let prepared_statement = self
+                    .prepared_statements
+                    .get(name)
+                    .ok_or_else(|| format!("prepared statement \"{}\" does not exist", name))?;
+                let session = self.session.clone().unwrap();
+                let (param_types, row_description) = session.describe_statement(prepared_statement);
+                self.write_message_no_flush(&BeMessage::ParameterDescription(&param_types))?;
+                row_description
+            }
+            b'P' => self
+                .portals
+                .get(name)
+                .ok_or_else(|| format!("portal \"{}\" does not exist", name))?
+                .row_description
+                .clone(),
+            kind => return Err(format!("invalid DESCRIBE message subtype {}", kind as char).into()),
+        };
+
+        if row_description.is_empty() {
+            self.write_message_no_flush(&BeMessage::NoData)?;
+        } else {
+            self.write_message_no_flush(&BeMessage::RowDescription(&row_description))?;
+        }
+        Ok(())
+    }
+
+    fn process_close_msg(&mut self, msg: FeCloseMessage) -> std::result::Result<(), BoxedError> {
+        let name = cstr_to_str(&msg.name)?;
+        // It is not an error to close a nonexistent statement or portal.
+        match msg.kind {
+            b'S' => {
+                self.prepared_statements.remove(name);
+            }
+            b'P' => {
+                self.portals.remove(name);
+            }
+            kind => return Err(format!("invalid CLOSE message subtype {}", kind as char).into()),
+        }
+        self.write_message_no_flush(&BeMessage::CloseComplete)?;
+        Ok(())
+    }
+
     fn is_terminate(&self) -> bool {
         self.is_terminate
     }
@@ -269,3 +698,14 @@ where
         Ok(())
     }
 }
+
+impl<S, SM> Drop for PgProtocol<S, SM>
+where
+    SM: SessionManager,
+{
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.session_mgr.end_session(&session);
+        }
+    }
+}
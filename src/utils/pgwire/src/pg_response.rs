@@ -14,7 +14,9 @@
 
 use std::fmt::Formatter;
 
+use crate::copy::CopyOptions;
 use crate::pg_field_descriptor::PgFieldDescriptor;
+use crate::pg_server::BoxedError;
 use crate::types::Row;
 /// Port from StatementType.java.
 
@@ -32,15 +34,24 @@ pub enum StatementType {
     CREATE_TABLE,
     CREATE_MATERIALIZED_VIEW,
     CREATE_SOURCE,
+    CREATE_SINK,
     CREATE_DATABASE,
     CREATE_SCHEMA,
+    CREATE_USER,
     DESCRIBE_TABLE,
     DROP_TABLE,
     DROP_MATERIALIZED_VIEW,
     DROP_INDEX,
     DROP_SOURCE,
+    DROP_SINK,
     DROP_SCHEMA,
     DROP_DATABASE,
+    DROP_USER,
+    ALTER_USER,
+    ALTER_TABLE,
+    ALTER_SOURCE,
+    GRANT_PRIVILEGE,
+    REVOKE_PRIVILEGE,
     // Introduce ORDER_BY statement type cuz Calcite unvalidated AST has SqlKind.ORDER_BY. Note
     // that Statement Type is not designed to be one to one mapping with SqlKind.
     ORDER_BY,
@@ -68,6 +79,42 @@ pub struct PgResponse {
     notice: Option<String>,
     values: Vec<Row>,
     row_desc: Vec<PgFieldDescriptor>,
+    /// Set for `COPY`, whose data is transferred in the copy sub-protocol instead of data rows.
+    copy: Option<CopyTransfer>,
+}
+
+/// The sink of the rows copied by `COPY ... FROM STDIN`.
+#[async_trait::async_trait]
+pub trait CopyInSink: Send + Sync {
+    /// Writes the rows decoded from a chunk of the copied data.
+    async fn write(&mut self, rows: Vec<Row>) -> Result<(), BoxedError>;
+
+    /// Finishes the copy after all the data is written, and returns the number of copied rows.
+    async fn finish(self: Box<Self>) -> Result<i32, BoxedError>;
+}
+
+/// How the data of `COPY` is transferred.
+pub enum CopyTransfer {
+    /// The data sent by the client is decoded with `options` and written to `sink`.
+    In {
+        options: CopyOptions,
+        sink: Box<dyn CopyInSink>,
+    },
+    /// The rows of the response are encoded with `options` and sent to the client.
+    Out { options: CopyOptions },
+}
+
+impl std::fmt::Debug for CopyTransfer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyTransfer::In { options, .. } => {
+                f.debug_struct("In").field("options", options).finish()
+            }
+            CopyTransfer::Out { options } => {
+                f.debug_struct("Out").field("options", options).finish()
+            }
+        }
+    }
 }
 
 impl StatementType {
@@ -98,6 +145,31 @@ impl PgResponse {
             values,
             row_desc,
             notice: None,
+            copy: None,
+        }
+    }
+
+    /// The response of `COPY ... FROM STDIN` into the columns described by `row_desc`.
+    pub fn copy_in(
+        row_desc: Vec<PgFieldDescriptor>,
+        options: CopyOptions,
+        sink: Box<dyn CopyInSink>,
+    ) -> Self {
+        Self {
+            copy: Some(CopyTransfer::In { options, sink }),
+            ..Self::new(StatementType::COPY, 0, vec![], row_desc)
+        }
+    }
+
+    /// The response of `COPY ... TO STDOUT`, whose rows are sent as the copied data.
+    pub fn copy_out(
+        values: Vec<Row>,
+        row_desc: Vec<PgFieldDescriptor>,
+        options: CopyOptions,
+    ) -> Self {
+        Self {
+            copy: Some(CopyTransfer::Out { options }),
+            ..Self::new(StatementType::COPY, values.len() as i32, values, row_desc)
         }
     }
 
@@ -112,6 +184,7 @@ impl PgResponse {
             values: vec![],
             row_desc: vec![],
             notice: Some(notice),
+            copy: None,
         }
     }
 
@@ -141,6 +214,15 @@ impl PgResponse {
         self.stmt_type == StatementType::EMPTY
     }
 
+    pub fn is_copy(&self) -> bool {
+        self.copy.is_some()
+    }
+
+    /// Takes how the data of `COPY` is transferred, which is `None` for other statements.
+    pub fn take_copy(&mut self) -> Option<CopyTransfer> {
+        self.copy.take()
+    }
+
     pub fn get_row_desc(&self) -> Vec<PgFieldDescriptor> {
         self.row_desc.clone()
     }
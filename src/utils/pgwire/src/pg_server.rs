@@ -19,24 +19,100 @@ use std::sync::Arc;
 
 use tokio::net::{TcpListener, TcpStream};
 
+use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use crate::pg_protocol::PgProtocol;
 use crate::pg_response::PgResponse;
 
 pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
 
+/// The process id and the secret key of a session, which are sent to the client in
+/// `BackendKeyData` and used by cancel requests to identify the session.
+pub type SessionId = (i32, i32);
+
 /// The interface for a database system behind pgwire protocol.
 /// We can mock it for testing purpose.
 pub trait SessionManager: Send + Sync + 'static {
     type Session: Session;
 
-    fn connect(&self, database: &str) -> Result<Arc<Self::Session>, BoxedError>;
+    fn connect(&self, database: &str, user_name: &str) -> Result<Arc<Self::Session>, BoxedError>;
+
+    /// Cancels the running queries of the session with `session_id`, if there is one.
+    fn cancel_queries_in_session(&self, session_id: SessionId);
+
+    /// Called when the connection of `session` is closed.
+    fn end_session(&self, session: &Self::Session);
+}
+
+/// How a session authenticates its user during startup.
+#[derive(Debug, Clone)]
+pub enum UserAuthenticator {
+    /// The user has no password, so no authentication is needed.
+    None,
+    /// Authenticate with the password in clear text.
+    ClearText(Vec<u8>),
+    /// Authenticate with a MD5 password, where `encrypted_password` is the response expected from
+    /// the client given `salt`.
+    Md5WithSalt {
+        encrypted_password: Vec<u8>,
+        salt: [u8; 4],
+    },
+}
+
+impl UserAuthenticator {
+    /// Checks the password sent by the client.
+    pub fn authenticate(&self, password: &[u8]) -> bool {
+        match self {
+            UserAuthenticator::None => true,
+            UserAuthenticator::ClearText(text) => password == text.as_slice(),
+            UserAuthenticator::Md5WithSalt {
+                encrypted_password, ..
+            } => password == encrypted_password.as_slice(),
+        }
+    }
 }
 
 /// A psql connection. Each connection binds with a database. Switching database will need to
 /// recreate another connection.
 #[async_trait::async_trait]
 pub trait Session: Send + Sync {
+    /// A statement created by a `Parse` message in the extended query protocol.
+    type PreparedStatement: Clone + Send + Sync + 'static;
+    /// A prepared statement with parameters bound by a `Bind` message.
+    type Portal: Clone + Send + Sync + 'static;
+
     async fn run_statement(self: Arc<Self>, sql: &str) -> Result<PgResponse, BoxedError>;
+
+    /// Returns how the user of this session should be authenticated.
+    fn user_authenticator(&self) -> &UserAuthenticator;
+
+    fn id(&self) -> SessionId;
+
+    /// Parse `sql` with `$n` parameters into a prepared statement. `param_types` are the types
+    /// specified by the client, where `None` means the type should be inferred.
+    fn parse(
+        self: Arc<Self>,
+        sql: &str,
+        param_types: Vec<Option<TypeOid>>,
+    ) -> Result<Self::PreparedStatement, BoxedError>;
+
+    /// Bind parameter values in text format to a prepared statement.
+    fn bind(
+        self: Arc<Self>,
+        prepared_statement: Self::PreparedStatement,
+        params: Vec<Option<String>>,
+    ) -> Result<Self::Portal, BoxedError>;
+
+    async fn execute(self: Arc<Self>, portal: Self::Portal) -> Result<PgResponse, BoxedError>;
+
+    /// Returns the parameter types and the row description of a prepared statement. The row
+    /// description is empty if the statement returns no rows.
+    fn describe_statement(
+        &self,
+        prepared_statement: &Self::PreparedStatement,
+    ) -> (Vec<TypeOid>, Vec<PgFieldDescriptor>);
+
+    /// Returns the row description of a portal, empty if the portal returns no rows.
+    fn describe_portal(&self, portal: &Self::Portal) -> Vec<PgFieldDescriptor>;
 }
 
 /// Binds a Tcp listener at `addr`. Spawn a coroutine to serve every new connection.
@@ -66,9 +142,8 @@ pub async fn pg_serve(addr: &str, session_mgr: Arc<impl SessionManager>) -> io::
 
 async fn pg_serve_conn(socket: TcpStream, session_mgr: Arc<impl SessionManager>) {
     let mut pg_proto = PgProtocol::new(socket, session_mgr);
-    let mut unnamed_query_string = bytes::Bytes::new();
     loop {
-        let terminate = pg_proto.process(&mut unnamed_query_string).await;
+        let terminate = pg_proto.process().await;
         match terminate {
             Ok(is_ter) => {
                 if is_ter {
@@ -90,16 +165,34 @@ async fn pg_serve_conn(socket: TcpStream, session_mgr: Arc<impl SessionManager>)
 #[cfg(test)]
 mod tests {
     use std::error::Error;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
 
+    use tokio::sync::Notify;
     use tokio_postgres::NoTls;
 
     use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
     use crate::pg_response::{PgResponse, StatementType};
-    use crate::pg_server::{pg_serve, Session, SessionManager};
+    use crate::pg_server::{pg_serve, Session, SessionId, SessionManager, UserAuthenticator};
     use crate::types::Row;
 
-    struct MockSessionManager {}
+    const MOCK_SESSION_ID: SessionId = (42, 1234);
+
+    struct MockSessionManager {
+        user_authenticator: UserAuthenticator,
+        /// The sessions whose queries have been cancelled.
+        cancelled_sessions: Mutex<Vec<SessionId>>,
+        cancel_notify: Notify,
+    }
+
+    impl MockSessionManager {
+        fn new(user_authenticator: UserAuthenticator) -> Self {
+            Self {
+                user_authenticator,
+                cancelled_sessions: Mutex::new(vec![]),
+                cancel_notify: Notify::new(),
+            }
+        }
+    }
 
     impl SessionManager for MockSessionManager {
         type Session = MockSession;
@@ -107,35 +200,105 @@ mod tests {
         fn connect(
             &self,
             _database: &str,
+            _user_name: &str,
         ) -> Result<Arc<Self::Session>, Box<dyn Error + Send + Sync>> {
-            Ok(Arc::new(MockSession {}))
+            Ok(Arc::new(MockSession {
+                user_authenticator: self.user_authenticator.clone(),
+            }))
+        }
+
+        fn cancel_queries_in_session(&self, session_id: SessionId) {
+            self.cancelled_sessions.lock().unwrap().push(session_id);
+            self.cancel_notify.notify_one();
         }
+
+        fn end_session(&self, _session: &Self::Session) {}
     }
 
-    struct MockSession {}
+    struct MockSession {
+        user_authenticator: UserAuthenticator,
+    }
+
+    /// Returns a single varchar column, whose value is the first parameter if there is one.
+    fn mock_response(params: Vec<Option<String>>) -> PgResponse {
+        let value = params
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| Some("Hello, World".to_owned()));
+        PgResponse::new(
+            StatementType::SELECT,
+            1,
+            vec![Row::new(vec![value])],
+            vec![PgFieldDescriptor::new(
+                "VARCHAR".to_owned(),
+                TypeOid::Varchar,
+            )],
+        )
+    }
 
     #[async_trait::async_trait]
     impl Session for MockSession {
+        type Portal = Vec<Option<String>>;
+        // The number of `$n` parameters in the statement.
+        type PreparedStatement = usize;
+
         async fn run_statement(
             self: Arc<Self>,
             _sql: &str,
         ) -> Result<PgResponse, Box<dyn Error + Send + Sync>> {
-            Ok(PgResponse::new(
-                StatementType::SELECT,
-                1,
-                vec![Row::new(vec![Some("Hello, World".to_owned())])],
-                vec![PgFieldDescriptor::new(
-                    "VARCHAR".to_owned(),
-                    TypeOid::Varchar,
-                )],
-            ))
+            Ok(mock_response(vec![]))
+        }
+
+        fn user_authenticator(&self) -> &UserAuthenticator {
+            &self.user_authenticator
+        }
+
+        fn id(&self) -> SessionId {
+            MOCK_SESSION_ID
+        }
+
+        fn parse(
+            self: Arc<Self>,
+            sql: &str,
+            _param_types: Vec<Option<TypeOid>>,
+        ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+            Ok(sql.matches('$').count())
+        }
+
+        fn bind(
+            self: Arc<Self>,
+            _prepared_statement: usize,
+            params: Vec<Option<String>>,
+        ) -> Result<Vec<Option<String>>, Box<dyn Error + Send + Sync>> {
+            Ok(params)
+        }
+
+        async fn execute(
+            self: Arc<Self>,
+            portal: Vec<Option<String>>,
+        ) -> Result<PgResponse, Box<dyn Error + Send + Sync>> {
+            Ok(mock_response(portal))
+        }
+
+        fn describe_statement(
+            &self,
+            prepared_statement: &usize,
+        ) -> (Vec<TypeOid>, Vec<PgFieldDescriptor>) {
+            (
+                vec![TypeOid::Varchar; *prepared_statement],
+                mock_response(vec![]).get_row_desc(),
+            )
+        }
+
+        fn describe_portal(&self, _portal: &Vec<Option<String>>) -> Vec<PgFieldDescriptor> {
+            mock_response(vec![]).get_row_desc()
         }
     }
 
     #[tokio::test]
     /// The test below is copied from tokio-postgres doc.
     async fn test_psql_extended_mode_connect() {
-        let session_mgr = Arc::new(MockSessionManager {});
+        let session_mgr = Arc::new(MockSessionManager::new(UserAuthenticator::None));
         tokio::spawn(async move { pg_serve("127.0.0.1:10000", session_mgr).await });
 
         // Connect to the database.
@@ -151,15 +314,73 @@ mod tests {
             }
         });
 
-        // Now we can execute a simple statement that just returns its parameter.
         let rows = client.query("SELECT 'Hello, World'", &[]).await.unwrap();
-        // FIXME: Enable this after handle prepared statement.
-        // let rows = client
-        //     .query("SELECT $1::TEXT", &[&"hello world"])
-        //     .await
-        //     .unwrap();
+        let value: &str = rows[0].get(0);
+        assert_eq!(value, "Hello, World");
+
+        // Now we can execute a simple statement that just returns its parameter.
+        let rows = client
+            .query("SELECT $1::TEXT", &[&"hello world"])
+            .await
+            .unwrap();
+        let value: &str = rows[0].get(0);
+        assert_eq!(value, "hello world");
+
+        // A prepared statement can be executed multiple times.
+        let stmt = client.prepare("SELECT $1::TEXT").await.unwrap();
+        for param in ["foo", "bar"] {
+            let rows = client.query(&stmt, &[&param]).await.unwrap();
+            let value: &str = rows[0].get(0);
+            assert_eq!(value, param);
+        }
+    }
+    #[tokio::test]
+    async fn test_psql_password_authentication() {
+        let session_mgr = Arc::new(MockSessionManager::new(UserAuthenticator::ClearText(
+            b"abc".to_vec(),
+        )));
+        tokio::spawn(async move { pg_serve("127.0.0.1:10001", session_mgr).await });
 
+        let (client, connection) =
+            tokio_postgres::connect("host=localhost port=10001 user=root password=abc", NoTls)
+                .await
+                .unwrap();
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("connection error: {}", e);
+            }
+        });
+        let rows = client.query("SELECT 'Hello, World'", &[]).await.unwrap();
         let value: &str = rows[0].get(0);
         assert_eq!(value, "Hello, World");
+
+        let res =
+            tokio_postgres::connect("host=localhost port=10001 user=root password=xyz", NoTls)
+                .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_psql_cancel_request() {
+        let session_mgr = Arc::new(MockSessionManager::new(UserAuthenticator::None));
+        let mgr = session_mgr.clone();
+        tokio::spawn(async move { pg_serve("127.0.0.1:10002", mgr).await });
+
+        let (client, connection) = tokio_postgres::connect("host=localhost port=10002", NoTls)
+            .await
+            .unwrap();
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("connection error: {}", e);
+            }
+        });
+
+        // The cancel request carries the key sent in `BackendKeyData` during startup.
+        client.cancel_token().cancel_query(NoTls).await.unwrap();
+        session_mgr.cancel_notify.notified().await;
+        assert_eq!(
+            *session_mgr.cancelled_sessions.lock().unwrap(),
+            vec![MOCK_SESSION_ID]
+        );
     }
 }
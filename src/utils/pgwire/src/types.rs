@@ -12,11 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::{Error, ErrorKind, Result};
 use std::ops::Index;
 
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
+
 /// A row of data returned from the database by a query.
 #[derive(Debug)]
-// NOTE: Since we only support simple query protocol, the values are represented as strings.
+// NOTE: The values are represented as strings in text format. They are encoded into the format
+// requested by the client when sent in the extended query protocol, see `encode_row`.
 pub struct Row(Vec<Option<String>>);
 
 impl Row {
@@ -48,3 +54,226 @@ impl Index<usize> for Row {
         &self.0[index]
     }
 }
+
+/// Format of a value transferred in the extended query protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Binary,
+}
+
+impl Format {
+    pub fn from_i16(code: i16) -> Result<Self> {
+        match code {
+            0 => Ok(Format::Text),
+            1 => Ok(Format::Binary),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid format code: {}", code),
+            )),
+        }
+    }
+
+    /// Expand the format codes in a `Bind` message to one format for each of the `len` values.
+    /// No code means all values are in text, a single code applies to all values, otherwise there
+    /// must be exactly one code for each value.
+    pub fn expand(codes: &[i16], len: usize) -> Result<Vec<Self>> {
+        match codes.len() {
+            0 => Ok(vec![Format::Text; len]),
+            1 => Ok(vec![Format::from_i16(codes[0])?; len]),
+            n if n == len => codes.iter().map(|code| Format::from_i16(*code)).collect(),
+            n => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("got {} format codes for {} values", n, len),
+            )),
+        }
+    }
+}
+
+fn unsupported_binary(type_oid: TypeOid) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!("binary format of type {:?} is not supported", type_oid),
+    )
+}
+
+fn invalid_value(type_oid: TypeOid, value: impl std::fmt::Debug) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!("invalid value of type {:?}: {:?}", type_oid, value),
+    )
+}
+
+/// Encode a value in text format to the binary format of `type_oid`.
+pub fn text_to_binary(type_oid: TypeOid, value: &str) -> Result<Bytes> {
+    let mut buf = BytesMut::new();
+    match type_oid {
+        TypeOid::Boolean => match value {
+            "t" | "true" => buf.put_u8(1),
+            "f" | "false" => buf.put_u8(0),
+            _ => return Err(invalid_value(type_oid, value)),
+        },
+        TypeOid::SmallInt => {
+            buf.put_i16(value.parse().map_err(|_| invalid_value(type_oid, value))?)
+        }
+        TypeOid::Int => buf.put_i32(value.parse().map_err(|_| invalid_value(type_oid, value))?),
+        TypeOid::BigInt => buf.put_i64(value.parse().map_err(|_| invalid_value(type_oid, value))?),
+        TypeOid::Float4 => buf.put_f32(parse_float(type_oid, value)?),
+        TypeOid::Float8 => buf.put_f64(parse_float(type_oid, value)?),
+        TypeOid::Varchar => buf.put_slice(value.as_bytes()),
+        // The binary format of jsonb is a version number followed by the text.
+        TypeOid::Jsonb => {
+            buf.put_u8(1);
+            buf.put_slice(value.as_bytes());
+        }
+        TypeOid::Bytea => buf.put_slice(
+            &value
+                .strip_prefix("\\x")
+                .and_then(decode_hex)
+                .ok_or_else(|| invalid_value(type_oid, value))?,
+        ),
+        TypeOid::Uuid => {
+            let bytes = decode_hex(&value.replace('-', ""))
+                .filter(|bytes| bytes.len() == 16)
+                .ok_or_else(|| invalid_value(type_oid, value))?;
+            buf.put_slice(&bytes);
+        }
+        _ => return Err(unsupported_binary(type_oid)),
+    }
+    Ok(buf.freeze())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a value in the binary format of `type_oid` to text format.
+pub fn binary_to_text(type_oid: TypeOid, mut value: &[u8]) -> Result<String> {
+    let expected_len = match type_oid {
+        TypeOid::Boolean => Some(1),
+        TypeOid::SmallInt => Some(2),
+        TypeOid::Int | TypeOid::Float4 => Some(4),
+        TypeOid::BigInt | TypeOid::Float8 => Some(8),
+        TypeOid::Uuid => Some(16),
+        _ => None,
+    };
+    if matches!(expected_len, Some(len) if value.len() != len) {
+        return Err(invalid_value(type_oid, value));
+    }
+    let text = match type_oid {
+        TypeOid::Boolean => (value.get_u8() != 0).to_string(),
+        TypeOid::SmallInt => value.get_i16().to_string(),
+        TypeOid::Int => value.get_i32().to_string(),
+        TypeOid::BigInt => value.get_i64().to_string(),
+        TypeOid::Float4 => value.get_f32().to_string(),
+        TypeOid::Float8 => value.get_f64().to_string(),
+        TypeOid::Varchar => {
+            String::from_utf8(value.to_vec()).map_err(|_| invalid_value(type_oid, value))?
+        }
+        TypeOid::Jsonb => match value.split_first() {
+            Some((1, text)) => {
+                String::from_utf8(text.to_vec()).map_err(|_| invalid_value(type_oid, value))?
+            }
+            _ => return Err(invalid_value(type_oid, value)),
+        },
+        // Bytea is sent in the hex format, e.g. `\x0aff`.
+        TypeOid::Bytea => format!("\\x{}", encode_hex(value)),
+        TypeOid::Uuid => {
+            let hex = encode_hex(value);
+            format!(
+                "{}-{}-{}-{}-{}",
+                &hex[0..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..32]
+            )
+        }
+        _ => return Err(unsupported_binary(type_oid)),
+    };
+    Ok(text)
+}
+
+fn parse_float<T: std::str::FromStr>(type_oid: TypeOid, value: &str) -> Result<T> {
+    // Postgres spells the special values differently from Rust.
+    let value_rs = match value {
+        "Infinity" => "inf",
+        "-Infinity" => "-inf",
+        v => v,
+    };
+    value_rs.parse().map_err(|_| invalid_value(type_oid, value))
+}
+
+/// Encode the values of `row` in the given `formats`, using the types in `row_desc`.
+pub fn encode_row(
+    row: &Row,
+    row_desc: &[PgFieldDescriptor],
+    formats: &[Format],
+) -> Result<Vec<Option<Bytes>>> {
+    assert_eq!(row.len(), row_desc.len());
+    assert_eq!(row.len(), formats.len());
+    row.values()
+        .iter()
+        .zip(row_desc.iter().zip(formats))
+        .map(|(value, (field, format))| {
+            value
+                .as_ref()
+                .map(|value| match format {
+                    Format::Text => Ok(Bytes::copy_from_slice(value.as_bytes())),
+                    Format::Binary => text_to_binary(field.get_type_oid(), value),
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_expand() {
+        assert_eq!(Format::expand(&[], 2).unwrap(), vec![Format::Text; 2]);
+        assert_eq!(Format::expand(&[1], 2).unwrap(), vec![Format::Binary; 2]);
+        assert_eq!(
+            Format::expand(&[1, 0], 2).unwrap(),
+            vec![Format::Binary, Format::Text]
+        );
+        assert!(Format::expand(&[0, 1, 0], 2).is_err());
+        assert!(Format::expand(&[2], 1).is_err());
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        for (type_oid, text) in [
+            (TypeOid::Boolean, "true"),
+            (TypeOid::SmallInt, "-7"),
+            (TypeOid::Int, "42"),
+            (TypeOid::BigInt, "1234567890123"),
+            (TypeOid::Float8, "1.5"),
+            (TypeOid::Varchar, "hello"),
+            (TypeOid::Jsonb, r#"{"a": [1, 2]}"#),
+            (TypeOid::Bytea, "\\x00ff1a"),
+            (TypeOid::Bytea, "\\x"),
+            (TypeOid::Uuid, "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"),
+        ] {
+            let binary = text_to_binary(type_oid, text).unwrap();
+            assert_eq!(binary_to_text(type_oid, &binary).unwrap(), text);
+        }
+        assert!(binary_to_text(TypeOid::Int, &[0, 1]).is_err());
+        assert!(binary_to_text(TypeOid::Uuid, &[0; 15]).is_err());
+        assert!(text_to_binary(TypeOid::Bytea, "\\x0").is_err());
+        assert!(text_to_binary(TypeOid::Uuid, "a0eebc99").is_err());
+        assert!(text_to_binary(TypeOid::Date, "2022-01-01").is_err());
+    }
+}